@@ -25,6 +25,8 @@ THE SOFTWARE.
 use angle;
 use time;
 use coords;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 /**
 Computes solar aberration in ecliptic longitude
@@ -44,23 +46,32 @@ pub fn sol_aberr(R: f64) -> f64
     -angle::deg_frm_dms(0, 0, 20.4898).to_radians() / R
 }
 
+// Speed of light *| in AU per day*
+const LIGHT_SPEED: f64 = 173.1446335;
+
 /**
-Computes stellar aberration in equatorial coordinates
+Computes the velocity of the Earth using the complete series of
+Ron and Vondrák
+
+The series gives the velocity of the Earth with respect to the
+barycenter of the solar system, referred to the mean equator and
+equinox of J2000.0.
 
 # Returns
 
-`(abrr_in_asc, abrr_in_dec)`
+`(x, y, z)`
 
-* `abrr_in_asc`: Aberration in right ascension *| in radians*
-* `abrr_in_dec`: Aberration in declination *| in radians*
+* `x`: Velocity component towards the equinox *| in AU per day*
+* `y`: Velocity component in the equator, 90 degrees
+       east of the equinox *| in AU per day*
+* `z`: Velocity component towards the north celestial
+       pole *| in AU per day*
 
 # Arguments
 
-* `stell_eq_point`: Equatorial coordinates of the star *| in radians*
-* `JD`            : Julian (Ephemeris) day
+* `JD`: Julian (Ephemeris) day
 **/
-pub fn stell_aberr_in_eq_coords(stell_eq_point: &coords::EqPoint,
-                                JD: f64) -> (f64, f64)
+pub fn earth_vel(JD: f64) -> (f64, f64, f64)
 {
     let t = time::julian_cent(JD);
 
@@ -206,8 +217,8 @@ pub fn stell_aberr_in_eq_coords(stell_eq_point: &coords::EqPoint,
     A = l3 + l5;
     sinA = A.sin(); cosA = A.cos();
     x += 11.0*sinA - cosA;
-    y += -1.0*sinA - 10.0*cosA;
-    z += -1.0*sinA - 5.0*cosA;
+    y += -sinA - 10.0*cosA;
+    z += -sinA - 5.0*cosA;
 
     // ROW 19
     A = 2.0 * (l2 - l3);
@@ -221,7 +232,7 @@ pub fn stell_aberr_in_eq_coords(stell_eq_point: &coords::EqPoint,
     sinA = A.sin(); cosA = A.cos();
     x += -11.0*sinA - 2.0*cosA;
     y += -2.0*sinA + 9.0*cosA;
-    z += -1.0*sinA + 4.0*cosA;
+    z += -sinA + 4.0*cosA;
 
     // ROW 21
     A = 4.0*l3;
@@ -303,7 +314,7 @@ pub fn stell_aberr_in_eq_coords(stell_eq_point: &coords::EqPoint,
     // ROW 32
     A = 3.0*l2 - 4.0*l3;
     sinA = A.sin(); cosA = A.cos();
-    x += -1.0 * (sinA + cosA);
+    x += -(sinA + cosA);
     y += -2.0*sinA - 7.0*cosA;
     z += sinA - 4.0*cosA;
 
@@ -335,12 +346,193 @@ pub fn stell_aberr_in_eq_coords(stell_eq_point: &coords::EqPoint,
     y += -5.0*cosA;
     z += -2.0*cosA;
 
-    let c = 17314463350.0;
+    (x * 1e-8, y * 1e-8, z * 1e-8)
+}
+
+/**
+Computes stellar aberration in equatorial coordinates
+
+# Returns
+
+`(abrr_in_asc, abrr_in_dec)`
+
+* `abrr_in_asc`: Aberration in right ascension *| in radians*
+* `abrr_in_dec`: Aberration in declination *| in radians*
+
+# Arguments
+
+* `stell_eq_point`: Equatorial coordinates of the star *| in radians*
+* `JD`            : Julian (Ephemeris) day
+**/
+pub fn stell_aberr_in_eq_coords(stell_eq_point: &coords::EqPoint,
+                                JD: f64) -> (f64, f64)
+{
+    let vel = earth_vel(JD);
+
+    shift_in_eq_coords (
+        stell_eq_point,
+        (vel.0 / LIGHT_SPEED, vel.1 / LIGHT_SPEED, vel.2 / LIGHT_SPEED)
+    )
+}
+
+/**
+Computes planetary aberration in equatorial coordinates
+
+Planetary aberration combines the light-time correction with
+stellar aberration. Over the light-time, the motion of a body
+relative to the Earth shifts its apparent direction by the
+relative velocity of the two, divided by the speed of light. Adding
+this to the geometric position of the body at the instant of
+observation gives its apparent position.
+
+# Returns
+
+`(abrr_in_asc, abrr_in_dec)`
+
+* `abrr_in_asc`: Aberration in right ascension *| in radians*
+* `abrr_in_dec`: Aberration in declination *| in radians*
+
+# Arguments
+
+* `eq_point`: Geometric equatorial coordinates of the body,
+              referred to the mean equator and equinox of
+              J2000.0 *| in radians*
+* `body_vel`: Heliocentric velocity `(x, y, z)` of the body,
+              referred to the same frame *| in AU per day*
+* `JD`      : Julian (Ephemeris) day
+**/
+pub fn planet_aberr_in_eq_coords(eq_point: &coords::EqPoint,
+                                 body_vel: (f64, f64, f64),
+                                 JD: f64) -> (f64, f64)
+{
+    let vel = earth_vel(JD);
+
+    shift_in_eq_coords (
+        eq_point,
+        (
+            (vel.0 - body_vel.0) / LIGHT_SPEED,
+            (vel.1 - body_vel.1) / LIGHT_SPEED,
+            (vel.2 - body_vel.2) / LIGHT_SPEED
+        )
+    )
+}
+
+/**
+Computes diurnal aberration in equatorial coordinates
+
+Diurnal aberration is caused by the rotation of the Earth, which
+carries a topocentric observer eastward at up to 0.465 km/s. It is
+at most 0.32 arcseconds, reached on the equator.
+
+# Returns
+
+`(abrr_in_asc, abrr_in_dec)`
+
+* `abrr_in_asc`: Aberration in right ascension *| in radians*
+* `abrr_in_dec`: Aberration in declination *| in radians*
+
+# Arguments
+
+* `eq_point`   : Equatorial coordinates of the body *| in radians*
+* `rho_cos_phi`: The quantity `rho * cos(phi')`, where `rho` is the
+                 observer's distance from the center of the Earth
+                 *| in equatorial Earth radii* and `phi'` is the
+                 observer's geocentric latitude. See
+                 `planet::earth::rho_sin_cos_phi()`.
+* `hour_angle` : Local hour angle of the body, measured
+                 westwards *| in radians*
+**/
+pub fn diurnal_aberr_in_eq_coords(eq_point: &coords::EqPoint,
+                                  rho_cos_phi: f64,
+                                  hour_angle: f64) -> (f64, f64)
+{
+    // ratio of the Earth's equatorial rotation speed to the speed of light
+    let k = angle::deg_frm_dms(0, 0, 0.3200).to_radians();
+
+    (
+        k * rho_cos_phi * hour_angle.cos() / eq_point.dec.cos(),
+        k * rho_cos_phi * hour_angle.sin() * eq_point.dec.sin()
+    )
+}
+
+/**
+Computes the deflection of the light of a star by the gravity of the
+Sun
+
+The general-relativistic deflection is 1.75 arcseconds at the solar
+limb, falling off as the cotangent of half the elongation from the
+Sun, to about 4 milliarcseconds at 90 degrees. It's applied before
+annual aberration, to the geometric direction of the star. Within
+about a solar radius behind the Sun, where the star is hidden, the
+deflection is damped instead of growing without bound, as in SOFA.
+
+# Returns
+
+`(x, y, z)`
 
-    let (asc, dec) = (stell_eq_point.asc, stell_eq_point.dec);
+* `x`, `y`, `z`: Unit vector towards the deflected star
+
+# Arguments
+
+* `star_pos`      : Unit vector from the Earth towards the star
+* `sun_pos`       : Unit vector from the Earth towards the Sun, in
+                    the same frame
+* `earth_sun_dist`: Earth-Sun distance *| in AU*
+**/
+pub fn light_deflection(star_pos: (f64, f64, f64),
+                        sun_pos: (f64, f64, f64),
+                        earth_sun_dist: f64) -> (f64, f64, f64)
+{
+    let p = star_pos;
+    let e = (-sun_pos.0, -sun_pos.1, -sun_pos.2);
+
+    let pe = p.0*e.0 + p.1*e.1 + p.2*e.2;
+    let dlim = 1e-6 / earth_sun_dist.powi(2).max(1.0);
+    let g = SCHWARZSCHILD_RAD_SUN / earth_sun_dist / (1.0 + pe).max(dlim);
+
+    let d = (
+        p.0 + g*(e.0 - pe*p.0),
+        p.1 + g*(e.1 - pe*p.1),
+        p.2 + g*(e.2 - pe*p.2)
+    );
+    let r = (d.0*d.0 + d.1*d.1 + d.2*d.2).sqrt();
+
+    (d.0 / r, d.1 / r, d.2 / r)
+}
+
+/**
+Computes the angle by which the light of a star is deflected by the
+gravity of the Sun
+
+# Returns
+
+* `angle`: Deflection away from the Sun *| in radians*
+
+# Arguments
+
+* `elong`         : Elongation of the star from the Sun *| in radians*
+* `earth_sun_dist`: Earth-Sun distance *| in AU*
+**/
+pub fn light_deflection_angl(elong: f64, earth_sun_dist: f64) -> f64
+{
+    SCHWARZSCHILD_RAD_SUN / earth_sun_dist / (elong / 2.0).tan()
+}
+
+// Twice the gravitational parameter of the Sun over the square
+// of the speed of light *| in AU*
+const SCHWARZSCHILD_RAD_SUN: f64 = 1.97412574336e-8;
+
+// First-order shift in equatorial coordinates of a direction
+// perturbed by the given velocity, expressed as a fraction of the
+// speed of light
+fn shift_in_eq_coords(eq_point: &coords::EqPoint,
+                      beta: (f64, f64, f64)) -> (f64, f64)
+{
+    let (x, y, z) = beta;
+    let (asc, dec) = (eq_point.asc, eq_point.dec);
 
-    let delta_asc = (y*asc.cos() - x*asc.sin()) / (c*dec.cos());
-    let delta_dec = -(((x*asc.cos() + y*asc.sin())*dec.sin() - z*dec.cos())) / c;
+    let delta_asc = (y*asc.cos() - x*asc.sin()) / dec.cos();
+    let delta_dec = -((x*asc.cos() + y*asc.sin())*dec.sin() - z*dec.cos());
 
     (delta_asc, delta_dec)
 }
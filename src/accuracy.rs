@@ -0,0 +1,480 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Accuracy of the algorithms, and of the ephemerides against
+//! reference positions
+//!
+//! The accuracies built into the crate are those published with the
+//! theories, given per algorithm and range of validity by
+//! [spec()](./fn.spec.html). The crate embeds no grid of reference
+//! positions to measure them again: those of a JPL Development
+//! Ephemeris, such as DE440, aren't shipped with it. The `assess` and
+//! `report` functions measure the deviations per body and per century
+//! against reference positions supplied by the caller, reduced to the
+//! frame of the theory being assessed, or read from the vector tables
+//! of JPL Horizons by
+//! [refs_frm_horizons_vectors()](./fn.refs_frm_horizons_vectors.html).
+
+use angle;
+use error;
+use lunar;
+use planet;
+use precess;
+use sun;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Reference position of a body
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RefPos {
+    /// Julian (Ephemeris) day
+    pub JD: f64,
+    /// Ecliptic longitude *| in radians*
+    pub long: f64,
+    /// Ecliptic latitude *| in radians*
+    pub lat: f64,
+    /// Distance, in the units of the theory being assessed
+    pub dist: f64
+}
+
+/// Deviations of a theory from the reference positions in a century
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Deviation {
+    /// Julian century from J2000.0, such that the reference
+    /// positions are from `century` to `century + 1`
+    pub century: i64,
+    /// Number of reference positions in the century
+    pub n: usize,
+    /// Maximum angular deviation *| in radians*
+    pub max_angl: f64,
+    /// Root mean square angular deviation *| in radians*
+    pub rms_angl: f64,
+    /// Maximum deviation in distance
+    pub max_dist: f64,
+    /// Root mean square deviation in distance
+    pub rms_dist: f64
+}
+
+/**
+Assesses a theory of the motion of a body against reference
+positions
+
+# Returns
+
+* `deviations`: Deviations of the theory from the reference
+  positions, for each Julian century having reference positions, in
+  increasing order of the century
+
+# Arguments
+
+* `refs`  : Reference positions of the body
+* `theory`: Function computing the position of the body as
+  `(long, lat, dist)` for a Julian (Ephemeris) day
+**/
+pub fn assess<F>(refs: &[RefPos], theory: F) -> Vec<Deviation>
+    where F: Fn(f64) -> (f64, f64, f64)
+{
+    let mut deviations: Vec<Deviation> = Vec::new();
+
+    for r in refs {
+        let century = ((r.JD - 2451545.0) / 36525.0).floor() as i64;
+        let (long, lat, dist) = theory(r.JD);
+
+        let d_angl = angle::anglr_sepr(long, lat, r.long, r.lat);
+        let d_dist = (dist - r.dist).abs();
+
+        let i = match deviations.iter().position(|d| d.century == century) {
+            Some(i) => i,
+            None => {
+                deviations.push(Deviation {
+                    century,
+                    n:        0,
+                    max_angl: 0.0,
+                    rms_angl: 0.0,
+                    max_dist: 0.0,
+                    rms_dist: 0.0
+                });
+                deviations.len() - 1
+            }
+        };
+
+        let d = &mut deviations[i];
+        d.n += 1;
+        d.max_angl = d.max_angl.max(d_angl);
+        d.max_dist = d.max_dist.max(d_dist);
+        // sums of squares, until the end
+        d.rms_angl += d_angl * d_angl;
+        d.rms_dist += d_dist * d_dist;
+    }
+
+    for d in deviations.iter_mut() {
+        d.rms_angl = (d.rms_angl / (d.n as f64)).sqrt();
+        d.rms_dist = (d.rms_dist / (d.n as f64)).sqrt();
+    }
+
+    deviations.sort_by_key(|d| d.century);
+
+    deviations
+}
+
+/**
+Assesses `planet::heliocent_coords()` for a planet against reference
+positions
+
+# Returns
+
+* `deviations`: Deviations of the theory from the reference
+  positions, as in `assess()`, with the distances *| in AU*
+
+# Arguments
+
+* `planet`: Any variant of [Planet](../planet/enum.Planet.html)
+* `refs`  : Heliocentric reference positions of the planet, referred
+  to the mean ecliptic and equinox of the date, with the distances
+  *| in AU*
+**/
+pub fn assess_planet(planet: &planet::Planet, refs: &[RefPos]) -> Vec<Deviation>
+{
+    assess(refs, |JD| planet::heliocent_coords(planet, JD))
+}
+
+/**
+Assesses `lunar::geocent_ecl_pos()` against reference positions
+
+# Returns
+
+* `deviations`: Deviations of the theory from the reference
+  positions, as in `assess()`, with the distances *| in kilometers*
+
+# Arguments
+
+* `refs`: Geocentric reference positions of the Moon, referred to
+  the mean ecliptic and equinox of the date, with the distances
+  *| in kilometers*
+**/
+pub fn assess_moon(refs: &[RefPos]) -> Vec<Deviation>
+{
+    assess(refs, |JD| {
+        let (point, dist) = lunar::geocent_ecl_pos(JD);
+        (point.long, point.lat, dist)
+    })
+}
+
+/// Represents an algorithm of the crate with a published accuracy
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Algorithm {
+    /// `planet::heliocent_coords()` for Mercury, Venus, the Earth and
+    /// Mars, and `sun::geocent_ecl_pos()`, by VSOP87D
+    VSOP87Inner,
+    /// `planet::heliocent_coords()` for Jupiter and Saturn, by VSOP87D
+    VSOP87JupiterSaturn,
+    /// `planet::heliocent_coords()` for Uranus and Neptune, by VSOP87D
+    VSOP87UranusNeptune,
+    /// `lunar::geocent_ecl_pos()`, by the partial ELP-2000/82 theory
+    ELP2000,
+    /// `pluto::heliocent_pos()`
+    Pluto,
+    /// `ecliptic::mn_oblq_laskar()`
+    OblqLaskar,
+    /// `cio::cip_xy()`
+    CipXY
+}
+
+/// All the algorithms having an accuracy in `spec()`
+pub const ALGORITHMS: [Algorithm; 7] = [
+    Algorithm::VSOP87Inner,
+    Algorithm::VSOP87JupiterSaturn,
+    Algorithm::VSOP87UranusNeptune,
+    Algorithm::ELP2000,
+    Algorithm::Pluto,
+    Algorithm::OblqLaskar,
+    Algorithm::CipXY
+];
+
+/// Accuracy of an algorithm
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Spec {
+    /// Path of the function implementing the algorithm
+    pub func: &'static str,
+    /// Julian (Ephemeris) day of the start of the range of validity
+    pub JD_start: f64,
+    /// Julian (Ephemeris) day of the end of the range of validity
+    pub JD_end: f64,
+    /// Maximum error in the angular position computed, within the
+    /// range of validity *| in radians*
+    pub max_err: f64
+}
+
+impl Spec {
+    /// Checks if a Julian (Ephemeris) day is within the range of
+    /// validity of the algorithm
+    pub fn is_valid(&self, JD: f64) -> bool {
+        JD >= self.JD_start && JD <= self.JD_end
+    }
+}
+
+/**
+Returns the accuracy of an algorithm
+
+The accuracies are those published with the theories, or in the
+documentation of the functions. Where a theory has no published
+range of validity, the range is taken as a thousand years around
+J2000.0.
+
+# Arguments
+
+* `algorithm`: Any variant of [Algorithm](./enum.Algorithm.html)
+**/
+pub fn spec(algorithm: &Algorithm) -> Spec
+{
+    let J2000 = 2451545.0;
+    let years = |y: f64| y * 365.25;
+    let arcsec = |a: f64| angle::deg_frm_dms(0, 0, a).to_radians();
+
+    let (func, JD_start, JD_end, max_err) = match *algorithm {
+        Algorithm::VSOP87Inner =>
+            ("planet::heliocent_coords", J2000 - years(2000.0), J2000 + years(2000.0), arcsec(1.0)),
+        Algorithm::VSOP87JupiterSaturn =>
+            ("planet::heliocent_coords", J2000 - years(1000.0), J2000 + years(1000.0), arcsec(1.0)),
+        Algorithm::VSOP87UranusNeptune =>
+            ("planet::heliocent_coords", J2000 - years(3000.0), J2000 + years(3000.0), arcsec(1.0)),
+        Algorithm::ELP2000 =>
+            ("lunar::geocent_ecl_pos", J2000 - years(500.0), J2000 + years(500.0), arcsec(10.0)),
+        Algorithm::Pluto =>
+            ("pluto::heliocent_pos", 2409542.5, 2488069.5, arcsec(0.07)),
+        Algorithm::OblqLaskar =>
+            ("ecliptic::mn_oblq_laskar", J2000 - years(1000.0), J2000 + years(1000.0), arcsec(0.01)),
+        Algorithm::CipXY =>
+            ("cio::cip_xy", J2000 - years(100.0), J2000 + years(100.0), arcsec(0.01))
+    };
+
+    Spec {
+        func,
+        JD_start,
+        JD_end,
+        max_err
+    }
+}
+
+/**
+Returns the algorithm used by `planet::heliocent_coords()` for a planet
+
+# Arguments
+
+* `planet`: Any variant of [Planet](../planet/enum.Planet.html)
+**/
+pub fn planet_algorithm(planet: &planet::Planet) -> Algorithm
+{
+    match *planet {
+        planet::Planet::Mercury |
+        planet::Planet::Venus   |
+        planet::Planet::Earth   |
+        planet::Planet::Mars    => Algorithm::VSOP87Inner,
+        planet::Planet::Jupiter |
+        planet::Planet::Saturn  => Algorithm::VSOP87JupiterSaturn,
+        planet::Planet::Uranus  |
+        planet::Planet::Neptune => Algorithm::VSOP87UranusNeptune
+    }
+}
+
+/**
+Reads reference positions from a vector table of JPL Horizons
+
+The table must be in the CSV format of Horizons (`CSV_FORMAT=YES`),
+with the geometric states (`VEC_CORR=NONE`) referred to the ecliptic
+and equinox of J2000.0 (`REF_PLANE=ECLIPTIC`), and the Julian days in
+TDB. Each position is read from the first three numbers after the
+calendar date, so both position and state tables will do, and is
+reduced to the mean ecliptic and equinox of its date, as the theories
+of the crate are.
+
+# Returns
+
+* `Ok(refs)`  : The reference positions, with the distances in the
+  units of the table, or
+* `Err(error)`: `Error::InvalidFormat` if the table isn't between the
+  `$$SOE` and `$$EOE` markers, or a line of it can't be read
+
+# Arguments
+
+* `text`: Output of Horizons, with its header and footer
+**/
+pub fn refs_frm_horizons_vectors(text: &str) -> Result<Vec<RefPos>, error::Error>
+{
+    let start = text.find("$$SOE").ok_or(error::Error::InvalidFormat)?;
+    let end = text.find("$$EOE").ok_or(error::Error::InvalidFormat)?;
+    if end < start {
+        return Err(error::Error::InvalidFormat);
+    }
+
+    let mut refs = Vec::new();
+
+    for line in text[start + 5..end].lines().filter(|l| !l.trim().is_empty()) {
+        // the Julian day, the calendar date, and the position
+        let fields: Vec<&str> = line.split(',').map(|f| f.trim()).collect();
+        if fields.len() < 5 {
+            return Err(error::Error::InvalidFormat);
+        }
+        let number = |i: usize| -> Result<f64, error::Error> {
+            fields[i].parse().map_err(|_| error::Error::InvalidFormat)
+        };
+        let JD = number(0)?;
+        let (x, y, z) = (number(2)?, number(3)?, number(4)?);
+
+        let dist = (x*x + y*y + z*z).sqrt();
+        let (long, lat) = precess::precess_ecl_coords (
+            y.atan2(x), (z / dist).asin(), 2451545.0, JD
+        );
+
+        refs.push(RefPos {
+            JD,
+            long: angle::limit_to_two_PI(long),
+            lat,
+            dist
+        });
+    }
+
+    Ok(refs)
+}
+
+/// Holds the accuracy of an algorithm, assessed against reference
+/// positions
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Report {
+    /// The algorithm assessed
+    pub algorithm: Algorithm,
+    /// Its deviations from the reference positions in each Julian
+    /// century, as from `assess()`
+    pub deviations: Vec<Deviation>
+}
+
+impl Report {
+    /// The maximum angular deviation over the Julian days from
+    /// `JD_start` to `JD_end`, of the centuries overlapping them
+    /// *| in radians*
+    pub fn max_angl_in(&self, JD_start: f64, JD_end: f64) -> f64 {
+        self.deviations.iter()
+            .filter(|d| {
+                let (start, end) = century_range(d.century);
+                start <= JD_end && end >= JD_start
+            })
+            .fold(0.0, |max, d| max.max(d.max_angl))
+    }
+
+    /// Checks if the deviations in the centuries wholly within the
+    /// range of validity are within the published accuracy, from
+    /// `spec()`
+    pub fn meets_spec(&self) -> bool {
+        let spec = spec(&self.algorithm);
+
+        self.deviations.iter()
+            .filter(|d| {
+                let (start, end) = century_range(d.century);
+                spec.is_valid(start) && spec.is_valid(end)
+            })
+            .all(|d| d.max_angl <= spec.max_err)
+    }
+}
+
+/**
+Reports the accuracy of `planet::heliocent_coords()` for a planet
+
+# Returns
+
+* `report`: The [Report](./struct.Report.html), with the distances
+  *| in AU*
+
+# Arguments
+
+* `planet`: Any variant of [Planet](../planet/enum.Planet.html)
+* `refs`  : Heliocentric reference positions of the planet, as in
+  `assess_planet()`
+**/
+pub fn report_planet(planet: &planet::Planet, refs: &[RefPos]) -> Report
+{
+    let algorithm = planet_algorithm(planet);
+
+    Report {
+        algorithm,
+        deviations: assess_planet(planet, refs)
+    }
+}
+
+/**
+Reports the accuracy of `sun::geocent_ecl_pos()`
+
+# Returns
+
+* `report`: The [Report](./struct.Report.html), with the distances
+  *| in AU*
+
+# Arguments
+
+* `refs`: Geocentric geometric reference positions of the Sun,
+  referred to the mean ecliptic and equinox of the date, with the
+  distances *| in AU*
+**/
+pub fn report_sun(refs: &[RefPos]) -> Report
+{
+    Report {
+        algorithm: Algorithm::VSOP87Inner,
+        deviations: assess(refs, |JD| {
+            let (point, dist) = sun::geocent_ecl_pos(JD);
+            (point.long, point.lat, dist)
+        })
+    }
+}
+
+/**
+Reports the accuracy of `lunar::geocent_ecl_pos()`
+
+# Returns
+
+* `report`: The [Report](./struct.Report.html), with the distances
+  *| in kilometers*
+
+# Arguments
+
+* `refs`: Geocentric reference positions of the Moon, as in
+  `assess_moon()`
+**/
+pub fn report_moon(refs: &[RefPos]) -> Report
+{
+    Report {
+        algorithm: Algorithm::ELP2000,
+        deviations: assess_moon(refs)
+    }
+}
+
+// Julian days of the start and end of a Julian century from J2000.0
+#[inline]
+fn century_range(century: i64) -> (f64, f64)
+{
+    let start = 2451545.0 + 36525.0*(century as f64);
+
+    (start, start + 36525.0)
+}
@@ -21,14 +21,348 @@ THE SOFTWARE.
 */
 
 //! Angles for astronomy
-
+//!
+//! The functions on points take typed angles, `Rad`, `Deg`, `Dms`
+//! and `Hms`, so that an angle in the wrong unit doesn't compile.
+//! Their older forms, on `f64`s in radians, are deprecated. The
+//! reductions such as `limit_to_360()`, and `anglr_sepr()`, stay
+//! generic over the float type.
+
+use error::Error;
+use float::Float;
 use std;
+use std::fmt;
+use std::str::FromStr;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+#[cfg(not(feature = "std"))]
+use math::*;
 
 pub const TWO_PI: f64 = 2.0 * std::f64::consts::PI;
 
+/// An angle *| in radians*
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rad(pub f64);
+
+/// An angle *| in degrees*
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Deg(pub f64);
+
+/// An angle expressed in degrees, arcminutes and arcseconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Dms {
+    /// Degrees
+    pub deg: i64,
+    /// Arcminutes
+    pub min: i64,
+    /// Arcseconds
+    pub sec: f64
+}
+
+/// An angle expressed in hours, minutes and seconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Hms {
+    /// Hours
+    pub hour: i64,
+    /// Minutes
+    pub min: i64,
+    /// Seconds
+    pub sec: f64
+}
+
+// Arithmetic of angles in the same unit, and their scaling
+macro_rules! impl_angle_ops {
+    ($unit: ident) => {
+        impl Add for $unit {
+            type Output = $unit;
+            fn add(self, other: $unit) -> $unit { $unit(self.0 + other.0) }
+        }
+        impl Sub for $unit {
+            type Output = $unit;
+            fn sub(self, other: $unit) -> $unit { $unit(self.0 - other.0) }
+        }
+        impl Neg for $unit {
+            type Output = $unit;
+            fn neg(self) -> $unit { $unit(-self.0) }
+        }
+        impl Mul<f64> for $unit {
+            type Output = $unit;
+            fn mul(self, k: f64) -> $unit { $unit(self.0 * k) }
+        }
+        impl Div<f64> for $unit {
+            type Output = $unit;
+            fn div(self, k: f64) -> $unit { $unit(self.0 / k) }
+        }
+    };
+}
+
+impl_angle_ops!(Rad);
+impl_angle_ops!(Deg);
+
+impl Rad {
+    /// Sine of the angle
+    #[inline]
+    pub fn sin(self) -> f64 { self.0.sin() }
+
+    /// Cosine of the angle
+    #[inline]
+    pub fn cos(self) -> f64 { self.0.cos() }
+
+    /// Tangent of the angle
+    #[inline]
+    pub fn tan(self) -> f64 { self.0.tan() }
+
+    /// The equivalent angle in [0, 2π] radian range
+    #[inline]
+    pub fn limited(self) -> Rad { Rad(limit_to_two_PI(self.0)) }
+
+    /// The equivalent angle in [-π, π) radian range
+    #[inline]
+    pub fn limited_pm(self) -> Rad { Rad(limit_to_pm_PI(self.0)) }
+}
+
+impl Deg {
+    /// The equivalent angle in [0, 360] degree range
+    #[inline]
+    pub fn limited(self) -> Deg { Deg(limit_to_360(self.0)) }
+
+    /// The equivalent angle in [-180, 180) degree range
+    #[inline]
+    pub fn limited_pm(self) -> Deg { Deg(limit_to_pm_180(self.0)) }
+}
+
+impl From<Deg> for Rad {
+    fn from(angl: Deg) -> Rad { Rad(angl.0.to_radians()) }
+}
+
+impl From<Rad> for Deg {
+    fn from(angl: Rad) -> Deg { Deg(angl.0.to_degrees()) }
+}
+
+impl From<Dms> for Deg {
+    fn from(angl: Dms) -> Deg { Deg(deg_frm_dms(angl.deg, angl.min, angl.sec)) }
+}
+
+impl From<Deg> for Dms {
+    fn from(angl: Deg) -> Dms {
+        let (deg, min, sec) = dms(angl.0);
+        Dms { deg, min, sec }
+    }
+}
+
+impl From<Hms> for Deg {
+    fn from(angl: Hms) -> Deg { Deg(deg_frm_hms(angl.hour, angl.min, angl.sec)) }
+}
+
+impl From<Deg> for Hms {
+    fn from(angl: Deg) -> Hms {
+        let (hour, min, sec) = hms(angl.0);
+        Hms { hour, min, sec }
+    }
+}
+
+impl From<Dms> for Rad {
+    fn from(angl: Dms) -> Rad { Rad::from(Deg::from(angl)) }
+}
+
+impl From<Hms> for Rad {
+    fn from(angl: Hms) -> Rad { Rad::from(Deg::from(angl)) }
+}
+
+impl From<Rad> for Dms {
+    fn from(angl: Rad) -> Dms { Dms::from(Deg::from(angl)) }
+}
+
+impl From<Rad> for Hms {
+    fn from(angl: Rad) -> Hms { Hms::from(Deg::from(angl)) }
+}
+
+/// Formats the angle as `+23°26'21.4"`, with one decimal of the
+/// arcseconds unless the precision asks for another number, as in
+/// `{:.3}`
+impl fmt::Display for Dms {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let deg = deg_frm_dms(self.deg, self.min, self.sec);
+        let (sign, (d, m, s)) = split_sexagesimal(deg * 3600.0, f.precision().unwrap_or(1));
+
+        write!(f, "{}{}°{:02}'{}\"", if sign { '-' } else { '+' }, d, m, s)
+    }
+}
+
+/// Formats the angle as `12h 34m 56.7s`, with one decimal of the
+/// seconds unless the precision asks for another number, as in
+/// `{:.3}`
+impl fmt::Display for Hms {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let hours = deg_frm_hms(self.hour, self.min, self.sec) / 15.0;
+        let (sign, (h, m, s)) = split_sexagesimal(hours * 3600.0, f.precision().unwrap_or(1));
+
+        write!(f, "{}{}h {:02}m {}s", if sign { "-" } else { "" }, h, m, s)
+    }
+}
+
+/// Parses an angle in degrees, arcminutes and arcseconds
+///
+/// The parts may be separated by spaces, colons, or the marks
+/// `°'"`, `′″` or `dms`, as in `+23°26'21.4"`, `23 26 21.4`,
+/// `-23:26:21.4` or `23d26m21.4s`. Trailing parts may be left out,
+/// and the last part given may have decimals, as in `23°26.36'` or
+/// `23.4393`.
+impl FromStr for Dms {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Dms, Error> {
+        let (negative, parts) = parse_sexagesimal(text, &['°', 'º', 'd', '\'', '′', 'm', '"', '″', 's'])?;
+        let (deg, min, sec) = join_sexagesimal(negative, parts);
+
+        Ok(Dms { deg, min, sec })
+    }
+}
+
+/// Parses an angle in hours, minutes and seconds
+///
+/// The parts may be separated by spaces, colons, or the marks
+/// `hms`, as in `12h 34m 56.7s`, `12 34 56.7` or `12:34:56.7`.
+/// Trailing parts may be left out, and the last part given may have
+/// decimals, as in `12h 34.945m`.
+impl FromStr for Hms {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Hms, Error> {
+        let (negative, parts) = parse_sexagesimal(text, &['h', 'm', 's'])?;
+        let (hour, min, sec) = join_sexagesimal(negative, parts);
+
+        Ok(Hms { hour, min, sec })
+    }
+}
+
+// Splits a number of seconds, rounded to some decimals, into its sign,
+// and its whole units, whole minutes and seconds as text
+fn split_sexagesimal(secs: f64, decimals: usize) -> (bool, (i64, i64, String)) {
+
+    let decimals = decimals.min(9);
+    let scale = 10_i64.pow(decimals as u32);
+    let ticks = (secs.abs() * (scale as f64)).round() as i64;
+
+    let s = ticks % (60*scale);
+    let sec = if decimals == 0 {
+        format!("{:02}", s)
+    } else {
+        format!("{:02}.{:0width$}", s / scale, s % scale, width = decimals)
+    };
+
+    (secs < 0.0 && ticks > 0, (ticks / (3600*scale), ticks / (60*scale) % 60, sec))
+
+}
+
+// Splits a sexagesimal text into its sign and up to three numbers,
+// checking that only the last one has decimals and that minutes and
+// seconds are below 60
+fn parse_sexagesimal(text: &str, marks: &[char]) -> Result<(bool, Vec<f64>), Error> {
+
+    let text = text.trim();
+    let (negative, body) = match text.chars().next() {
+        Some('-') | Some('−') => (true, &text[text.chars().next().unwrap().len_utf8()..]),
+        Some('+') => (false, &text[1..]),
+        _ => (false, text)
+    };
+
+    let mut parts: Vec<&str> = Vec::new();
+    let mut start = None;
+    for (i, c) in body.char_indices() {
+        if c.is_ascii_digit() || c == '.' {
+            if start.is_none() {
+                start = Some(i);
+            }
+        } else {
+            if let Some(j) = start.take() {
+                parts.push(&body[j..i]);
+            }
+            if !(c.is_whitespace() || c == ':' || marks.contains(&c)) {
+                return Err(Error::InvalidFormat);
+            }
+        }
+    }
+    if let Some(j) = start {
+        parts.push(&body[j..]);
+    }
+
+    if parts.is_empty() || parts.len() > 3 {
+        return Err(Error::InvalidFormat);
+    }
+    let mut numbers = Vec::new();
+    for (i, part) in parts.iter().enumerate() {
+        if part.contains('.') && i + 1 < parts.len() {
+            return Err(Error::InvalidFormat);
+        }
+        let x: f64 = part.parse().map_err(|_| Error::InvalidFormat)?;
+        if i > 0 && x >= 60.0 {
+            return Err(Error::InvalidFormat);
+        }
+        numbers.push(x);
+    }
+
+    Ok((negative, numbers))
+
+}
+
+// Joins parsed numbers into whole units, whole minutes and seconds,
+// with the sign on the whole units, or on the minutes and seconds if
+// the whole units are zero, as deg_frm_dms() expects
+fn join_sexagesimal(negative: bool, parts: Vec<f64>) -> (i64, i64, f64) {
+
+    let (mut a, mut b, mut c) = match parts.len() {
+        1 => {
+            let mins = parts[0].fract() * 60.0;
+            (parts[0].trunc() as i64, mins.trunc() as i64, mins.fract() * 60.0)
+        },
+        2 => (parts[0] as i64, parts[1].trunc() as i64, parts[1].fract() * 60.0),
+        _ => (parts[0] as i64, parts[1] as i64, parts[2])
+    };
+
+    if negative {
+        if a != 0 {
+            a = -a;
+        } else {
+            b = -b;
+            c = -c;
+        }
+    }
+
+    (a, b, c)
+
+}
+
+/**
+Computes the angular separation between two angular points, with
+typed angles
+
+# Arguments
+
+* `p1a1`: Angle 1 of point 1
+* `p1a2`: Angle 2 of point 1
+* `p2a1`: Angle 1 of point 2
+* `p2a2`: Angle 2 of point 2
+
+Angle 1 may be right ascension or longitude.
+Angle 2 may be declination or latitude.
+**/
+#[inline]
+pub fn anglr_sepr_rad(p1a1: Rad, p1a2: Rad, p2a1: Rad, p2a2: Rad) -> Rad
+{
+    Rad(anglr_sepr(p1a1.0, p1a2.0, p2a1.0, p2a2.0))
+}
+
 /**
 Computes the angular separation between two angular points
 
+The separation is found from Vincenty's formula, which unlike the
+cosine formula stays accurate for separations near 0 and near 180
+degrees.
+
 # Arguments
 
 * `p1a1`: Angle 1 of point 1 *| in radians*
@@ -40,12 +374,375 @@ Angle 1 may be right ascension or longitude.
 Angle 2 may be declination or latitude.
 **/
 #[inline]
-pub fn anglr_sepr(p1a1: f64, p1a2: f64, p2a1: f64, p2a2: f64) -> f64
+pub fn anglr_sepr<T: Float>(p1a1: T, p1a2: T, p2a1: T, p2a2: T) -> T
 {
-    (
-          p1a2.sin() * p2a2.sin()
-        + p1a2.cos() * p2a2.cos() * (p1a1 - p2a1).cos()
-    ).acos()
+    let (s1, c1) = p1a2.sin_cos();
+    let (s2, c2) = p2a2.sin_cos();
+    let (sd, cd) = (p2a1 - p1a1).sin_cos();
+
+    let x = c1*s2 - s1*c2*cd;
+    let y = c2 * sd;
+
+    x.hypot(y).atan2(s1*s2 + c1*c2*cd)
+}
+
+/**
+Computes the position angle of one angular point with respect to
+another
+
+# Returns
+
+* `pos_angl`: Position angle of point 2 with respect to point 1,
+              measured from the direction of increasing angle 2
+              towards increasing angle 1, such as from the north
+              towards the east. *range: 0 to 2pi*
+
+# Arguments
+
+* `p1a1`: Angle 1 of point 1
+* `p1a2`: Angle 2 of point 1
+* `p2a1`: Angle 1 of point 2
+* `p2a2`: Angle 2 of point 2
+
+Angle 1 may be right ascension or longitude.
+Angle 2 may be declination or latitude.
+**/
+pub fn pos_angl_rad(p1a1: Rad, p1a2: Rad, p2a1: Rad, p2a2: Rad) -> Rad
+{
+    let (s1, c1) = p1a2.0.sin_cos();
+    let (s2, c2) = p2a2.0.sin_cos();
+    let (sd, cd) = (p2a1 - p1a1).0.sin_cos();
+
+    Rad((c2 * sd).atan2(c1*s2 - s1*c2*cd)).limited()
+}
+
+/// Computes the position angle of one angular point with respect to
+/// another, from and to angles *| in radians*
+#[deprecated(note = "use `pos_angl_rad()`, which takes typed angles")]
+#[inline]
+pub fn pos_angl(p1a1: f64, p1a2: f64, p2a1: f64, p2a2: f64) -> f64
+{
+    pos_angl_rad(Rad(p1a1), Rad(p1a2), Rad(p2a1), Rad(p2a2)).0
+}
+
+/**
+Computes the diameter of the smallest circle containing three angular
+points
+
+The diameter is computed as on a plane, which is accurate for points
+within a few degrees of each other.
+
+# Returns
+
+`(diameter, is_longest_sepr)`
+
+* `diameter`       : Diameter of the smallest circle
+* `is_longest_sepr`: `true` if the diameter is the longest separation
+                     between two of the points, with the third point
+                     inside the circle, and `false` if the circle
+                     passes through all three points
+
+# Arguments
+
+* `p1a1`: Angle 1 of point 1
+* `p1a2`: Angle 2 of point 1
+* `p2a1`: Angle 1 of point 2
+* `p2a2`: Angle 2 of point 2
+* `p3a1`: Angle 1 of point 3
+* `p3a2`: Angle 2 of point 3
+
+Angle 1 may be right ascension or longitude.
+Angle 2 may be declination or latitude.
+**/
+pub fn smallest_circle_rad (
+
+    p1a1 : Rad,
+    p1a2 : Rad,
+    p2a1 : Rad,
+    p2a2 : Rad,
+    p3a1 : Rad,
+    p3a2 : Rad
+
+) -> (Rad, bool) {
+
+    let mut seprs = [
+        anglr_sepr(p1a1.0, p1a2.0, p2a1.0, p2a2.0),
+        anglr_sepr(p2a1.0, p2a2.0, p3a1.0, p3a2.0),
+        anglr_sepr(p3a1.0, p3a2.0, p1a1.0, p1a2.0)
+    ];
+    seprs.sort_by(|x, y| y.partial_cmp(x).unwrap());
+    let (a, b, c) = (seprs[0], seprs[1], seprs[2]);
+
+    if a >= (b*b + c*c).sqrt() {
+        (Rad(a), true)
+    } else {
+        (
+            Rad(2.0*a*b*c / ((a + b + c)*(a + b - c)*(b + c - a)*(a - b + c)).sqrt()),
+            false
+        )
+    }
+
+}
+
+/// Computes the diameter of the smallest circle containing three
+/// angular points, from and to angles *| in radians*
+#[deprecated(note = "use `smallest_circle_rad()`, which takes typed angles")]
+#[inline]
+pub fn smallest_circle(p1a1: f64, p1a2: f64, p2a1: f64, p2a2: f64, p3a1: f64, p3a2: f64) -> (f64, bool)
+{
+    let (diameter, is_longest_sepr) = smallest_circle_rad (
+        Rad(p1a1), Rad(p1a2), Rad(p2a1), Rad(p2a2), Rad(p3a1), Rad(p3a2)
+    );
+
+    (diameter.0, is_longest_sepr)
+}
+
+/**
+Computes the smallest circle containing three angular points, on
+the sphere
+
+Unlike `smallest_circle_rad()`, which works on a plane, the circle is
+found rigorously on the sphere, so that the points may be any
+distance apart, as long as they lie in one hemisphere. The center
+is the point on which to center a field of view showing all three
+points, such as bodies in conjunction.
+
+# Returns
+
+`((a1, a2), diameter, is_longest_sepr)`
+
+* `a1`             : Angle 1 of the center of the circle
+* `a2`             : Angle 2 of the center of the circle
+* `diameter`       : Diameter of the circle
+* `is_longest_sepr`: `true` if the diameter is the longest separation
+                     between two of the points, with the third point
+                     inside the circle, and `false` if the circle
+                     passes through all three points
+
+# Arguments
+
+* `points`: The three points `(angle 1, angle 2)`
+
+Angle 1 may be right ascension or longitude.
+Angle 2 may be declination or latitude.
+**/
+pub fn smallest_sphr_circle_rad(points: &[(Rad, Rad); 3]) -> ((Rad, Rad), Rad, bool)
+{
+    let points = [points[0].angles(), points[1].angles(), points[2].angles()];
+    let u = [unit_vec(points[0]), unit_vec(points[1]), unit_vec(points[2])];
+    let sepr = |i: usize, j: usize| {
+        anglr_sepr(points[i].0, points[i].1, points[j].0, points[j].1)
+    };
+    let angles = |c: (f64, f64, f64)| {
+        (Rad(c.1.atan2(c.0)).limited(), Rad(c.2.atan2(c.0.hypot(c.1))))
+    };
+
+    // the pair with the longest separation, and the third point
+    let mut pairs = [(0, 1, 2), (1, 2, 0), (2, 0, 1)];
+    pairs.sort_by(|x, y| sepr(y.0, y.1).partial_cmp(&sepr(x.0, x.1)).unwrap());
+    let (i, j, k) = pairs[0];
+
+    let mid = normalized((u[i].0 + u[j].0, u[i].1 + u[j].1, u[i].2 + u[j].2));
+    let diameter = sepr(i, j);
+    if arc(mid, u[k]) <= 0.5*diameter*(1.0 + 1e-12) {
+        return (angles(mid), Rad(diameter), true);
+    }
+
+    // the pole of the circle through all three points
+    let d1 = (u[1].0 - u[0].0, u[1].1 - u[0].1, u[1].2 - u[0].2);
+    let d2 = (u[2].0 - u[0].0, u[2].1 - u[0].1, u[2].2 - u[0].2);
+    let mut pole = normalized(cross(d1, d2));
+    if dot(pole, u[0]) < 0.0 {
+        pole = (-pole.0, -pole.1, -pole.2);
+    }
+
+    (angles(pole), Rad(2.0*arc(pole, u[0])), false)
+}
+
+/// Computes the smallest circle containing three angular points, on
+/// the sphere, from and to angles *| in radians*
+#[deprecated(note = "use `smallest_sphr_circle_rad()`, which takes typed angles")]
+#[inline]
+pub fn smallest_sphr_circle(points: &[(f64, f64); 3]) -> ((f64, f64), f64, bool)
+{
+    let typed = |p: (f64, f64)| (Rad(p.0), Rad(p.1));
+    let ((a1, a2), diameter, is_longest_sepr) = smallest_sphr_circle_rad (
+        &[typed(points[0]), typed(points[1]), typed(points[2])]
+    );
+
+    ((a1.0, a2.0), diameter.0, is_longest_sepr)
+}
+
+// angle between two unit vectors
+#[inline]
+fn arc(a: (f64, f64, f64), b: (f64, f64, f64)) -> f64
+{
+    let c = cross(a, b);
+
+    dot(c, c).sqrt().atan2(dot(a, b))
+}
+
+#[inline]
+fn cross(a: (f64, f64, f64), b: (f64, f64, f64)) -> (f64, f64, f64)
+{
+    (a.1*b.2 - a.2*b.1, a.2*b.0 - a.0*b.2, a.0*b.1 - a.1*b.0)
+}
+
+#[inline]
+fn normalized(a: (f64, f64, f64)) -> (f64, f64, f64)
+{
+    let r = dot(a, a).sqrt();
+
+    (a.0 / r, a.1 / r, a.2 / r)
+}
+
+/**
+Computes the area of a polygon on a sphere
+
+The sides of the polygon are arcs of great circles. Sides along
+small circles, such as the parallels of declination bounding the
+constellations, must be divided into shorter sides first.
+
+# Returns
+
+* `area`: Area of the smaller of the two regions bounded by the
+          polygon *| in steradians*. *range: 0 to 2pi*
+
+# Arguments
+
+* `vertices`: Vertices `(angle 1, angle 2)` of the polygon, in order
+              along its boundary and in either direction
+
+Angle 1 may be right ascension or longitude.
+Angle 2 may be declination or latitude.
+**/
+pub fn sphr_polygon_area_rad(vertices: &[(Rad, Rad)]) -> f64
+{
+    polygon_area(vertices)
+}
+
+/// Computes the area of a polygon on a sphere, from vertices
+/// *| in radians*
+#[deprecated(note = "use `sphr_polygon_area_rad()`, which takes typed angles")]
+#[inline]
+pub fn sphr_polygon_area(vertices: &[(f64, f64)]) -> f64
+{
+    polygon_area(vertices)
+}
+
+fn polygon_area<V: Vertex>(vertices: &[V]) -> f64
+{
+    if vertices.len() < 3 {
+        return 0.0;
+    }
+
+    let area = left_area(vertices);
+
+    area.min(4.0*std::f64::consts::PI - area)
+}
+
+/**
+Checks if a point lies inside a polygon on a sphere
+
+The sides of the polygon are arcs of great circles, and the inside
+of the polygon is the smaller of the two regions it bounds.
+
+# Returns
+
+* `is_inside`: `true` if the point is inside the polygon
+
+# Arguments
+
+* `a1`      : Angle 1 of the point
+* `a2`      : Angle 2 of the point
+* `vertices`: Vertices `(angle 1, angle 2)` of the polygon, in order
+              along its boundary and in either direction
+
+Angle 1 may be right ascension or longitude.
+Angle 2 may be declination or latitude.
+**/
+pub fn is_in_sphr_polygon_rad(a1: Rad, a2: Rad, vertices: &[(Rad, Rad)]) -> bool
+{
+    is_in_polygon(a1.0, a2.0, vertices)
+}
+
+/// Checks if a point lies inside a polygon on a sphere, from angles
+/// *| in radians*
+#[deprecated(note = "use `is_in_sphr_polygon_rad()`, which takes typed angles")]
+#[inline]
+pub fn is_in_sphr_polygon(a1: f64, a2: f64, vertices: &[(f64, f64)]) -> bool
+{
+    is_in_polygon(a1, a2, vertices)
+}
+
+fn is_in_polygon<V: Vertex>(a1: f64, a2: f64, vertices: &[V]) -> bool
+{
+    if vertices.len() < 3 {
+        return false;
+    }
+
+    // seen from the antipode of the point, the fan of triangles covers
+    // the region to the left of the boundary once, less the whole sphere
+    // if the point lies in that region
+    let antipode = (-a2.cos()*a1.cos(), -a2.cos()*a1.sin(), -a2.sin());
+    let is_in_left = fan_excess(antipode, vertices) < 0.0;
+
+    is_in_left == (left_area(vertices) < std::f64::consts::PI * 2.0)
+}
+
+// area of the region to the left of the boundary, seen from outside
+fn left_area<V: Vertex>(vertices: &[V]) -> f64
+{
+    let four_PI = 4.0 * std::f64::consts::PI;
+    let area = fan_excess(unit_vec(vertices[0].angles()), vertices) % four_PI;
+
+    if area < 0.0 { area + four_PI } else { area }
+}
+
+// sum of the signed spherical excesses of the triangles from the apex
+// to each side, given by Van Oosterom and Strackee (1983)
+fn fan_excess<V: Vertex>(apex: (f64, f64, f64), vertices: &[V]) -> f64
+{
+    let a = apex;
+    let mut excess = 0.0;
+
+    for i in 0..vertices.len() {
+        let b = unit_vec(vertices[i].angles());
+        let c = unit_vec(vertices[(i + 1) % vertices.len()].angles());
+        let triple =
+            a.0 * (b.1*c.2 - b.2*c.1)
+          + a.1 * (b.2*c.0 - b.0*c.2)
+          + a.2 * (b.0*c.1 - b.1*c.0);
+        let denom = 1.0 + dot(a, b) + dot(b, c) + dot(c, a);
+        excess += 2.0 * triple.atan2(denom);
+    }
+
+    excess
+}
+
+// a point (angle 1, angle 2) on the sphere, typed or in radians
+trait Vertex: Copy {
+    fn angles(self) -> (f64, f64);
+}
+
+impl Vertex for (f64, f64) {
+    fn angles(self) -> (f64, f64) { self }
+}
+
+impl Vertex for (Rad, Rad) {
+    fn angles(self) -> (f64, f64) { ((self.0).0, (self.1).0) }
+}
+
+#[inline]
+fn unit_vec(p: (f64, f64)) -> (f64, f64, f64)
+{
+    (p.1.cos() * p.0.cos(), p.1.cos() * p.0.sin(), p.1.sin())
+}
+
+#[inline]
+fn dot(a: (f64, f64, f64), b: (f64, f64, f64)) -> f64
+{
+    a.0*b.0 + a.1*b.1 + a.2*b.2
 }
 
 /**
@@ -88,8 +785,16 @@ arcseconds, from an angle in degrees with decimals
 
 * `deg`: Angle in degrees with decimals
 **/
+#[deprecated(note = "use `Dms::from(Deg(deg))`, which takes a typed angle")]
 #[inline]
 pub fn dms_frm_deg(deg: f64) -> (i64, i64, f64)
+{
+    let Dms { deg, min, sec } = Dms::from(Deg(deg));
+
+    (deg, min, sec)
+}
+
+fn dms(deg: f64) -> (i64, i64, f64)
 {
     let degree = deg as i64;
 
@@ -110,11 +815,18 @@ expressed in hours, minutes and seconds
 * `hours`: Hours
 * `min`: Minutes
 * `sec`: Seconds
+
+As in `deg_frm_dms()`, the minutes and seconds take the sign of
+negative hours.
 **/
 #[inline]
 pub fn deg_frm_hms(hour: i64, min: i64, sec: f64) -> f64
 {
-    15.0 * ((hour as f64) + (min as f64)/60.0 + sec/3600.0)
+    let (M, S) =
+        if hour < 0 { (-min.abs(), -sec.abs()) }
+        else        { (min, sec) };
+
+    15.0 * ((hour as f64) + (M as f64)/60.0 + S/3600.0)
 }
 
 /**
@@ -133,8 +845,16 @@ seconds, from an angle in degrees with decimals
 
 * `deg`: Angle in degrees with decimals
 **/
+#[deprecated(note = "use `Hms::from(Deg(deg))`, which takes a typed angle")]
 #[inline]
 pub fn hms_frm_deg(deg: f64) -> (i64, i64, f64)
+{
+    let Hms { hour, min, sec } = Hms::from(Deg(deg));
+
+    (hour, min, sec)
+}
+
+fn hms(deg: f64) -> (i64, i64, f64)
 {
     let hours = deg / 15.0;
     let hour = hours as i64;
@@ -155,13 +875,13 @@ Computes the equivalent angle in [0, 360] degree range
 * `angl`: Angle *| in degrees*
 **/
 #[inline]
-pub fn limit_to_360(angl: f64) -> f64
+pub fn limit_to_360<T: Float>(angl: T) -> T
 {
-    let n = (angl / 360.0) as i64;
-    let limited_angl = angl - (360.0 * (n as f64));
+    let full = T::from_f64(360.0);
+    let limited_angl = angl - full*(angl / full).trunc();
 
-    if limited_angl < 0.0 { limited_angl + 360.0 }
-    else                  { limited_angl }
+    if limited_angl < T::from_f64(0.0) { limited_angl + full }
+    else                                { limited_angl }
 }
 
 /**
@@ -172,11 +892,60 @@ Computes the equivalent angle in [0, 2π] radian range
 * `angl`: Angle *| in radians*
 **/
 #[inline]
-pub fn limit_to_two_PI(angl: f64) -> f64
+pub fn limit_to_two_PI<T: Float>(angl: T) -> T
+{
+    let full = T::PI + T::PI;
+    let limited_angl = angl - full*(angl / full).trunc();
+
+    if limited_angl < T::from_f64(0.0) { limited_angl + full }
+    else                                { limited_angl }
+}
+
+/**
+Computes the equivalent angle in [-π, π) radian range
+
+This suits differences of angles, hour angles and longitudes
+measured either way from a meridian.
+
+# Arguments
+
+* `angl`: Angle *| in radians*
+**/
+#[inline]
+pub fn limit_to_pm_PI<T: Float>(angl: T) -> T
+{
+    limit_to_two_PI(angl + T::PI) - T::PI
+}
+
+/**
+Computes the equivalent angle in [-180, 180) degree range
+
+# Arguments
+
+* `angl`: Angle *| in degrees*
+**/
+#[inline]
+pub fn limit_to_pm_180<T: Float>(angl: T) -> T
+{
+    let half = T::from_f64(180.0);
+
+    limit_to_360(angl + half) - half
+}
+
+/**
+Computes the equivalent time in [0, 24) hour range, for sidereal and
+solar times and hour angles in hours
+
+# Arguments
+
+* `hours`: Time *| in hours*
+**/
+#[inline]
+pub fn limit_to_24<T: Float>(hours: T) -> T
 {
-    let n = (angl / TWO_PI) as i64;
-    let limited_angl = angl - (TWO_PI * (n as f64));
+    let full = T::from_f64(24.0);
+    let limited_hours = hours - full*(hours / full).trunc();
 
-    if limited_angl < 0.0 { limited_angl + TWO_PI }
-    else                  { limited_angl }
+    if limited_hours < T::from_f64(0.0) { limited_hours + full }
+    else                                 { limited_hours }
 }
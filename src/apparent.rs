@@ -0,0 +1,288 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Apparent positions of the Sun, the Moon, the planets and the stars
+//! in one call
+//!
+//! [apparent_position()](./fn.apparent_position.html) composes the
+//! routines of [ephemeris](../ephemeris/index.html),
+//! [pluto](../pluto/index.html) and [observer](../observer/index.html)
+//! in the right order, for those who want the apparent right ascension
+//! and declination of a body without assembling them by hand.
+//!
+//! The instants at which a body reaches an apparent ecliptic longitude,
+//! such as the ingresses into the signs of the zodiac, are found by
+//! [ecl_long_crossings()](./fn.ecl_long_crossings.html) and
+//! [sign_ingresses()](./fn.sign_ingresses.html).
+
+use angle;
+use body::Body;
+use coords;
+use ecliptic;
+use error;
+use ephemeris;
+use nutation;
+use numerics;
+use observer;
+use planet;
+use pluto;
+use star;
+use std::cell::Cell;
+use std::f64::consts::PI;
+use time;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Holds the apparent position of a body
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ApparentPos {
+    /// Apparent equatorial point, referred to the true equator and
+    /// equinox of the date, geocentric or, for an observer,
+    /// topocentric *| in radians*
+    pub eq_point: coords::EqPoint,
+    /// Geometric distance from the center of the Earth, or infinity
+    /// for a star of unknown parallax *| in AU*
+    pub dist: f64,
+    /// Horizontal point seen through the observer's atmosphere, for
+    /// an observer *| in radians*
+    pub hz_point: Option<coords::HzPoint>,
+}
+
+/**
+Computes the apparent position of a body
+
+The position is corrected for light-time, annual aberration,
+precession and nutation, by the analytic theories of the crate: VSOP87
+for the Sun and the planets, ELP-2000/82 for the Moon, and the theory
+of `pluto::heliocent_pos()` for Pluto. As by convention, the Moon's
+position isn't corrected for aberration. A star's position is its
+apparent place from `star::apprnt_eq_point()`, corrected for annual
+parallax when its parallax is known. For an observer, it's then
+corrected for parallax, and the horizontal point for refraction by
+the observer's weather.
+
+# Returns
+
+* `Ok(apparent_pos)`: The [ApparentPos](./struct.ApparentPos.html)
+                      of the body, or
+* `Err(error)`      : `Error::InvalidBody` for the Earth, or
+                      `Error::DateOutOfRange` for Pluto outside the
+                      years 1885 to 2099 of its theory
+
+# Arguments
+
+* `body`    : The [Body](../body/enum.Body.html)
+* `JD`      : Julian (Ephemeris) day
+* `observer`: The [Observer](../observer/struct.Observer.html) on the
+              Earth, or `None` for the geocentric position
+**/
+pub fn apparent_position(body: &Body, JD: f64, observer: Option<&observer::Observer>) -> Result<ApparentPos, error::Error> {
+
+    let (eq_point, dist) = match *body {
+        Body::Planet(planet::Planet::Earth) => return Err(error::Error::InvalidBody),
+        Body::Pluto => pluto::geocent_apprnt_eq_coords(JD, false)?,
+        Body::Star(ref place) => {
+            let dist = if place.parallax != 0.0 { 1.0 / place.parallax.abs() } else { f64::INFINITY };
+            (star::apprnt_eq_point(place, JD, place.parallax != 0.0, true), dist)
+        },
+        // the analytic theories cover every Julian day
+        _ => {
+            let (row, dist) = ephemeris::row_and_geom_dist(&ephemeris::Analytic, body, JD).unwrap();
+            (row.eq_point, dist)
+        }
+    };
+
+    let observer = match observer {
+        Some(observer) => observer,
+        None => return Ok(ApparentPos { eq_point, dist, hz_point: None })
+    };
+
+    let (nut_in_long, nut_in_oblq) = nutation::nutation(JD);
+    let true_oblq = ecliptic::mn_oblq_IAU(JD) + nut_in_oblq;
+    let JD_UT = JD - time::delta_t_frm_julian_day(JD)/86400.0;
+    let green_sidr = time::apprnt_sidr(time::mn_sidr(JD_UT), nut_in_long, true_oblq);
+
+    // a star of unknown parallax is too far for any diurnal parallax
+    let eq_point = if dist.is_finite() { observer.topocentric(&eq_point, dist, green_sidr) } else { eq_point };
+
+    Ok(ApparentPos {
+        eq_point,
+        dist,
+        hz_point: Some(observer.apprnt_hz(&eq_point, green_sidr))
+    })
+
+}
+
+/**
+Computes the apparent geocentric ecliptic longitude of a body
+
+# Returns
+
+* `Ok(ecl_long)`: Apparent ecliptic longitude, referred to the true
+                  equinox of the date *| in radians*, or
+* `Err(error)`  : As from `apparent_position()`
+
+# Arguments
+
+* `body`: The [Body](../body/enum.Body.html)
+* `JD`  : Julian (Ephemeris) day
+**/
+pub fn apprnt_ecl_long(body: &Body, JD: f64) -> Result<f64, error::Error> {
+
+    let eq_point = apparent_position(body, JD, None)?.eq_point;
+    let (_, nut_in_oblq) = nutation::nutation(JD);
+    let true_oblq = ecliptic::mn_oblq_IAU(JD) + nut_in_oblq;
+
+    Ok(angle::limit_to_two_PI(coords::ecl_long_frm_eq(eq_point.asc, eq_point.dec, true_oblq)))
+
+}
+
+/**
+Finds the instants at which a body reaches an apparent ecliptic
+longitude
+
+The longitude is sampled at intervals short enough for the motion of
+the body, and each crossing found is refined by Brent's method. A
+planet near a stationary point may cross a longitude twice within an
+interval, and those crossings are missed.
+
+# Returns
+
+* `Ok(JDs)`   : Julian (Ephemeris) days of the crossings, in
+                chronological order, several for a planet retrograding
+                across the longitude, or
+* `Err(error)`: As from `apparent_position()`, or `Error::NoSolution`
+                if a crossing can't be refined
+
+# Arguments
+
+* `body`    : The [Body](../body/enum.Body.html)
+* `ecl_long`: Apparent ecliptic longitude, referred to the true
+              equinox of the date *| in radians*
+* `JD_start`: Julian (Ephemeris) day at the start of the search
+* `JD_end`  : Julian (Ephemeris) day at the end of the search
+**/
+pub fn ecl_long_crossings(body: &Body, ecl_long: f64, JD_start: f64, JD_end: f64) -> Result<Vec<f64>, error::Error> {
+
+    let diff = |JD: f64| apprnt_ecl_long(body, JD).map(|long| angle::limit_to_pm_PI(long - ecl_long));
+
+    let mut crossings = Vec::new();
+    scan(body, JD_start, JD_end, |JD_prev, JD_next| {
+        let (prev, next) = (diff(JD_prev)?, diff(JD_next)?);
+        // a sign change across the opposite longitude isn't a crossing
+        if prev.signum() != next.signum() && (next - prev).abs() < PI {
+            crossings.push(refine(&diff, JD_prev, JD_next)?);
+        }
+        Ok(())
+    })?;
+
+    Ok(crossings)
+
+}
+
+/**
+Finds the ingresses of a body into the signs of the zodiac
+
+# Returns
+
+* `Ok(ingresses)`: Julian (Ephemeris) days of the ingresses, each
+                   with the [ZodiacSign](../ecliptic/enum.ZodiacSign.html)
+                   entered, in chronological order. A planet moving
+                   retrograde enters a sign from its end.
+* `Err(error)`   : As from `apparent_position()`, or
+                   `Error::NoSolution` if an ingress can't be refined
+
+# Arguments
+
+* `body`    : The [Body](../body/enum.Body.html)
+* `JD_start`: Julian (Ephemeris) day at the start of the search
+* `JD_end`  : Julian (Ephemeris) day at the end of the search
+**/
+pub fn sign_ingresses(body: &Body, JD_start: f64, JD_end: f64) -> Result<Vec<(f64, ecliptic::ZodiacSign)>, error::Error> {
+
+    let mut ingresses = Vec::new();
+    scan(body, JD_start, JD_end, |JD_prev, JD_next| {
+        let (prev, _) = ecliptic::zodiac_sign(apprnt_ecl_long(body, JD_prev)?);
+        let (next, _) = ecliptic::zodiac_sign(apprnt_ecl_long(body, JD_next)?);
+        if prev == next {
+            return Ok(());
+        }
+
+        // the boundary between the signs, from the later one when
+        // moving direct, and from the earlier one when retrograde
+        let (prev, next) = (prev as usize, next as usize);
+        let boundary = if (next + 12 - prev) % 12 == 1 { next } else { prev };
+        let boundary = (boundary as f64) * PI / 6.0;
+
+        let diff = |JD: f64| apprnt_ecl_long(body, JD).map(|long| angle::limit_to_pm_PI(long - boundary));
+        ingresses.push((refine(&diff, JD_prev, JD_next)?, ecliptic::ZODIAC_SIGNS[next]));
+        Ok(())
+    })?;
+
+    Ok(ingresses)
+
+}
+
+// Calls a function for each interval of the sampling of a body's
+// longitude from JD_start to JD_end
+fn scan<F>(body: &Body, JD_start: f64, JD_end: f64, mut f: F) -> Result<(), error::Error>
+    where F: FnMut(f64, f64) -> Result<(), error::Error>
+{
+    // intervals over which the longitude changes by a few degrees, and
+    // within which planets seldom turn back
+    let step = match *body {
+        Body::Moon                            => 0.25,
+        Body::Planet(planet::Planet::Mercury) => 1.0,
+        Body::Sun |
+        Body::Planet(planet::Planet::Venus)   => 2.0,
+        Body::Planet(planet::Planet::Mars)    => 4.0,
+        _                                     => 8.0
+    };
+
+    let mut JD_prev = JD_start;
+    while JD_prev < JD_end {
+        let JD_next = (JD_prev + step).min(JD_end);
+        f(JD_prev, JD_next)?;
+        JD_prev = JD_next;
+    }
+
+    Ok(())
+}
+
+// Refines the zero of a difference of longitudes between two Julian
+// days by Brent's method
+fn refine<F>(diff: &F, JD_prev: f64, JD_next: f64) -> Result<f64, error::Error>
+    where F: Fn(f64) -> Result<f64, error::Error>
+{
+    // the first error from the difference, which stops the search
+    let error = Cell::new(None);
+    let JD = numerics::brent(|JD| diff(JD).unwrap_or_else(|e| {
+        error.set(error.get().or(Some(e)));
+        0.0
+    }), JD_prev, JD_next, 1e-6);
+
+    match error.get() {
+        Some(e) => Err(e),
+        None    => JD.ok_or(error::Error::NoSolution)
+    }
+}
@@ -20,7 +20,14 @@ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
 THE SOFTWARE.
 */
 
-//! Diameters of asteroids
+//! Diameters, magnitudes and ephemerides of asteroids
+
+use interpol;
+use orbit;
+use planet;
+use std;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 /**
 Computes the diameter of an asteroid
@@ -59,3 +66,231 @@ pub fn apparent_diameter(true_diameter: f64, asteroid_earth_dist: f64) -> f64
 {
     1.3788 * true_diameter/asteroid_earth_dist
 }
+
+/**
+Computes the apparent magnitude of an asteroid in the H, G system
+
+The phase function is that of Bowell et al. (1989), adopted by the
+IAU in 1985, and is valid for phase angles up to 120 degrees.
+
+# Returns
+
+* `app_mag`: Apparent magnitude of the asteroid
+
+# Arguments
+
+* `H`         : Absolute magnitude of the asteroid
+* `G`         : Slope parameter of the asteroid, 0.15 if unknown
+* `r`         : Asteroid-Sun distance *| in AU*
+* `delta`     : Asteroid-Earth distance *| in AU*
+* `phase_angl`: Phase angle of the asteroid *| in radians*
+**/
+pub fn apprnt_mag_HG(H: f64, G: f64, r: f64, delta: f64, phase_angl: f64) -> f64
+{
+    let tan = (phase_angl / 2.0).tan();
+    let phi1 = (-3.33 * tan.powf(0.63)).exp();
+    let phi2 = (-1.87 * tan.powf(1.22)).exp();
+
+    H + 5.0*(r * delta).log10() - 2.5*((1.0 - G)*phi1 + G*phi2).log10()
+}
+
+/**
+Computes the apparent magnitude of an asteroid in the H, G1, G2
+system
+
+The phase function is that of Muinonen et al. (2010), adopted by the
+IAU in 2012, which fits the opposition effect and the phase curves
+of dark and bright asteroids better than the H, G system. It is valid
+for phase angles up to 150 degrees.
+
+# Returns
+
+* `app_mag`: Apparent magnitude of the asteroid
+
+# Arguments
+
+* `H`         : Absolute magnitude of the asteroid
+* `G1`        : First slope parameter of the asteroid
+* `G2`        : Second slope parameter of the asteroid
+* `r`         : Asteroid-Sun distance *| in AU*
+* `delta`     : Asteroid-Earth distance *| in AU*
+* `phase_angl`: Phase angle of the asteroid *| in radians*
+**/
+pub fn apprnt_mag_HG1G2(H: f64, G1: f64, G2: f64, r: f64, delta: f64, phase_angl: f64) -> f64
+{
+    let a = phase_angl;
+    let deg = |x: f64| x.to_radians();
+    let PI = std::f64::consts::PI;
+
+    let (phi1, phi2) = if a < deg(7.5) {
+        (1.0 - 6.0*a/PI, 1.0 - 9.0*a/(5.0*PI))
+    } else {
+        (
+            clamped_spline(&PHI1_NODES, &PHI1_VALS, PHI1_ENDS, a),
+            clamped_spline(&PHI2_NODES, &PHI2_VALS, PHI2_ENDS, a)
+        )
+    };
+    let phi3 = if a < deg(30.0) {
+        clamped_spline(&PHI3_NODES, &PHI3_VALS, PHI3_ENDS, a)
+    } else {
+        0.0
+    };
+
+    H + 5.0*(r * delta).log10() - 2.5*(G1*phi1 + G2*phi2 + (1.0 - G1 - G2)*phi3).log10()
+}
+
+// Nodes *| in degrees*, values and end derivatives *| per radian* of
+// the cubic splines of the basis functions of the H, G1, G2 system
+const PHI1_NODES: [f64; 6] = [7.5, 30.0, 60.0, 90.0, 120.0, 150.0];
+const PHI1_VALS: [f64; 6] = [7.5e-1, 3.3486016e-1, 1.3410560e-1, 5.1104756e-2, 2.1465687e-2, 3.6396989e-3];
+const PHI1_ENDS: (f64, f64) = (-1.9098593, -9.1328612e-2);
+const PHI2_NODES: [f64; 6] = [7.5, 30.0, 60.0, 90.0, 120.0, 150.0];
+const PHI2_VALS: [f64; 6] = [9.25e-1, 6.2884169e-1, 3.1755495e-1, 1.2716367e-1, 2.2373903e-2, 1.6505689e-4];
+const PHI2_ENDS: (f64, f64) = (-5.7295780e-1, -8.6573138e-8);
+const PHI3_NODES: [f64; 9] = [0.0, 0.3, 1.0, 2.0, 4.0, 8.0, 12.0, 20.0, 30.0];
+const PHI3_VALS: [f64; 9] = [
+    1.0, 8.3381185e-1, 5.7735424e-1, 4.2144772e-1, 2.3174230e-1,
+    1.0348178e-1, 6.1733473e-2, 1.6107006e-2, 0.0
+];
+const PHI3_ENDS: (f64, f64) = (-1.0630097, 0.0);
+
+// Value at x *| in radians* of the cubic spline through the given
+// nodes *| in degrees*, with the given derivatives at the ends
+fn clamped_spline(nodes: &[f64], vals: &[f64], ends: (f64, f64), x: f64) -> f64
+{
+    let t: Vec<f64> = nodes.iter().map(|d| d.to_radians()).collect();
+
+    interpol::CubicSpline::clamped(&t, vals, ends.0, ends.1).value(x)
+}
+
+/// Holds a row of the observing ephemeris of an asteroid
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Row {
+    /// Julian (Ephemeris) day
+    pub JD: f64,
+    /// Astrometric right ascension, referred to the mean equator and
+    /// equinox of J2000.0 *| in radians*
+    pub asc: f64,
+    /// Astrometric declination, referred to the mean equator and
+    /// equinox of J2000.0 *| in radians*
+    pub dec: f64,
+    /// Asteroid-Earth distance *| in AU*
+    pub delta: f64,
+    /// Asteroid-Sun distance *| in AU*
+    pub r: f64,
+    /// Elongation of the asteroid from the Sun *| in radians*
+    pub elong: f64,
+    /// Phase angle of the asteroid *| in radians*
+    pub phase_angl: f64,
+    /// Apparent magnitude of the asteroid, in the H, G system
+    pub mag: f64
+}
+
+/**
+Iterator over the rows of the observing ephemeris of an asteroid at
+equal intervals of time
+**/
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ephemeris {
+    elements: orbit::Elements,
+    H: f64,
+    G: f64,
+    start: f64,
+    end: f64,
+    step: f64,
+    n: u32
+}
+
+impl Iterator for Ephemeris {
+    type Item = Row;
+
+    fn next(&mut self) -> Option<Row>
+    {
+        let JD = self.start + self.step * (self.n as f64);
+        if JD > self.end {
+            return None;
+        }
+        self.n += 1;
+
+        Some(ephemeris_row(&self.elements, self.H, self.G, JD))
+    }
+}
+
+/**
+Computes an observing ephemeris of an asteroid
+
+The positions are propagated from the osculating elements with
+`orbit::position()`, and the magnitudes computed with
+`apprnt_mag_HG()`.
+
+# Returns
+
+* `ephemeris`: An [Ephemeris](./struct.Ephemeris.html) iterator over
+               the [Row](./struct.Row.html)s of the ephemeris, from
+               `JD_start` to `JD_end`
+
+# Arguments
+
+* `elements`: Osculating [Elements](../orbit/struct.Elements.html)
+              of the asteroid
+* `H`       : Absolute magnitude of the asteroid
+* `G`       : Slope parameter of the asteroid, 0.15 if unknown
+* `JD_start`: Julian (Ephemeris) day of the first row
+* `JD_end`  : Julian (Ephemeris) day after which there are no rows
+* `step`    : Interval between the rows *| in days*
+**/
+pub fn ephemeris(elements: &orbit::Elements, H: f64, G: f64,
+                 JD_start: f64, JD_end: f64, step: f64) -> Ephemeris
+{
+    Ephemeris {
+        elements: *elements,
+        H,
+        G,
+        start: JD_start,
+        end: JD_end,
+        step,
+        n: 0
+    }
+}
+
+/**
+Computes a row of the observing ephemeris of an asteroid
+
+# Returns
+
+* `row`: The [Row](./struct.Row.html) of the ephemeris
+
+# Arguments
+
+* `elements`: Osculating [Elements](../orbit/struct.Elements.html)
+              of the asteroid
+* `H`       : Absolute magnitude of the asteroid
+* `G`       : Slope parameter of the asteroid, 0.15 if unknown
+* `JD`      : Julian (Ephemeris) day
+**/
+pub fn ephemeris_row(elements: &orbit::Elements, H: f64, G: f64, JD: f64) -> Row
+{
+    let pos = orbit::position(elements, JD);
+    let earth = planet::heliocent_ecl_rect_coords_J2000(&planet::Planet::Earth, JD);
+
+    let (x, y, z) = pos.heliocent;
+    let r = (x*x + y*y + z*z).sqrt();
+    let R = (earth.0*earth.0 + earth.1*earth.1 + earth.2*earth.2).sqrt();
+    let delta = pos.dist;
+
+    let phase_angl = planet::phase_angl(r, delta, R);
+    let cos_elong = (R*R + delta*delta - r*r) / (2.0 * R * delta);
+
+    Row {
+        JD,
+        asc: pos.asc,
+        dec: pos.dec,
+        delta,
+        r,
+        elong: cos_elong.clamp(-1.0, 1.0).acos(),
+        phase_angl,
+        mag: apprnt_mag_HG(H, G, r, delta, phase_angl)
+    }
+}
@@ -20,10 +20,13 @@ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
 THE SOFTWARE.
 */
 
-//! Corrections for atmospheric refraction
+//! Corrections for atmospheric refraction and extinction, and the
+//! brightness of the sky
 
 use angle;
 use std::f64::consts::PI;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 /**
 Computes the refraction term for true altitudes greater than 15
@@ -41,12 +44,10 @@ degrees
 **/
 pub fn refrac_frm_apprnt_alt_15(apprnt_alt: f64) -> f64
 {
-    let x =
-        angle::deg_frm_dms(0, 0, 0.0668).to_radians()
-      * (PI - apprnt_alt).tan();
+    let x = (PI/2.0 - apprnt_alt).tan();
 
-      angle::deg_frm_dms(0, 0, 58.294).to_radians() * (PI - apprnt_alt).tan()
-    - x * x * x
+      angle::deg_frm_dms(0, 0, 58.294).to_radians() * x
+    - angle::deg_frm_dms(0, 0, 0.0668).to_radians() * x * x * x
 }
 
 /**
@@ -65,12 +66,10 @@ degrees
 **/
 pub fn refrac_frm_true_alt_15(true_alt: f64) -> f64
 {
-    let x =
-        angle::deg_frm_dms(0, 0, 0.0824).to_radians()
-      * (PI - true_alt).tan();
+    let x = (PI/2.0 - true_alt).tan();
 
-      angle::deg_frm_dms(0, 0, 58.276).to_radians() * (PI - true_alt).tan()
-    - x * x * x
+      angle::deg_frm_dms(0, 0, 58.276).to_radians() * x
+    - angle::deg_frm_dms(0, 0, 0.0824).to_radians() * x * x * x
 }
 
 /**
@@ -80,7 +79,7 @@ Computes the refraction term for true altitude
 
 * `refrac_term`: The refraction term *| in radians*, that needs to be
                  subtracted from the apparent altitude to get the
-                 rue altitude
+                 true altitude
 
 The accuracy of `refrac_term` is upto 0.07 arcminutes.
 
@@ -90,7 +89,7 @@ The accuracy of `refrac_term` is upto 0.07 arcminutes.
 **/
 pub fn refrac_frm_apprnt_alt(apprnt_alt: f64) -> f64
 {
-    if apprnt_alt == PI { 0.0 }
+    if apprnt_alt == PI/2.0 { 0.0 }
     else {
         let apprnt_alt_deg = apprnt_alt.to_degrees();
         let a = apprnt_alt_deg + 7.31/(apprnt_alt_deg + 4.4);
@@ -118,7 +117,7 @@ within 4 arcseconds.
 **/
 pub fn refrac_frm_true_alt(true_alt: f64) -> f64
 {
-    if true_alt == PI { 0.0 }
+    if true_alt == PI/2.0 { 0.0 }
     else {
         let true_alt_deg = true_alt.to_degrees();
         let a = true_alt_deg + 10.3/(true_alt_deg + 5.11);
@@ -165,3 +164,352 @@ pub fn refrac_by_temp(temp: f64) -> f64
 {
     283.0 / temp
 }
+
+/// Represents a model of atmospheric refraction
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RefracModel {
+    /// Bennett's formula, from the apparent altitude, good to
+    /// 0.07 arcminutes
+    Bennett,
+    /// Sæmundsson's formula, from the true altitude, consistent
+    /// with Bennett's to 4 arcseconds
+    Saemundsson,
+    /// Numerical integration through a model atmosphere, with a
+    /// troposphere of constant lapse rate up to 11 kilometers and
+    /// an isothermal stratosphere above
+    Integrated
+}
+
+/**
+Computes the true altitude from the apparent altitude
+
+# Returns
+
+* `true_alt`: True altitude *| in radians*
+
+# Arguments
+
+* `apprnt_alt`: Apparent altitude *| in radians*
+* `pressure`  : Local pressure *| in millibars*
+* `temp`      : Local temperature *| in kelvins*
+* `model`     : The [RefracModel](./enum.RefracModel.html) to use
+**/
+pub fn true_alt_frm_apprnt(apprnt_alt: f64, pressure: f64, temp: f64, model: &RefracModel) -> f64
+{
+    match *model {
+        RefracModel::Bennett =>
+            apprnt_alt - refrac_frm_apprnt_alt(apprnt_alt) * weather_fac(pressure, temp),
+        RefracModel::Saemundsson => {
+            // invert the formula by fixed-point iteration, which
+            // converges since the refraction changes slowly
+            let mut true_alt = apprnt_alt;
+            for _ in 0..20 {
+                true_alt = apprnt_alt - refrac_frm_true_alt(true_alt) * weather_fac(pressure, temp);
+            }
+            true_alt
+        },
+        RefracModel::Integrated =>
+            apprnt_alt - integrated_refrac(apprnt_alt, pressure, temp)
+    }
+}
+
+/**
+Computes the apparent altitude from the true altitude
+
+# Returns
+
+* `apprnt_alt`: Apparent altitude *| in radians*
+
+# Arguments
+
+* `true_alt`: True altitude *| in radians*
+* `pressure`: Local pressure *| in millibars*
+* `temp`    : Local temperature *| in kelvins*
+* `model`   : The [RefracModel](./enum.RefracModel.html) to use
+**/
+pub fn apprnt_alt_frm_true(true_alt: f64, pressure: f64, temp: f64, model: &RefracModel) -> f64
+{
+    match *model {
+        RefracModel::Saemundsson =>
+            true_alt + refrac_frm_true_alt(true_alt) * weather_fac(pressure, temp),
+        _ => {
+            let mut apprnt_alt = true_alt;
+            for _ in 0..20 {
+                apprnt_alt += true_alt - true_alt_frm_apprnt(apprnt_alt, pressure, temp, model);
+            }
+            apprnt_alt
+        }
+    }
+}
+
+/**
+Computes the airmass by the formula of Kasten and Young
+
+The formula is good to well under a percent down to the horizon.
+
+# Returns
+
+* `airmass`: Relative optical airmass, which is 1 at the zenith
+
+# Arguments
+
+* `true_alt`: True altitude *| in radians*
+**/
+pub fn airmass_kasten_young(true_alt: f64) -> f64
+{
+    let h = true_alt.to_degrees();
+
+    1.0 / (true_alt.sin() + 0.50572 * (h + 6.07995).powf(-1.6364))
+}
+
+/**
+Computes the airmass by the formula of Pickering
+
+The formula is fitted to the airmass of a model atmosphere, and is
+accurate down to the horizon.
+
+# Returns
+
+* `airmass`: Relative optical airmass, which is 1 at the zenith
+
+# Arguments
+
+* `apprnt_alt`: Apparent altitude *| in radians*
+**/
+pub fn airmass_pickering(apprnt_alt: f64) -> f64
+{
+    let h = apprnt_alt.to_degrees();
+
+    1.0 / (h + 244.0/(165.0 + 47.0*h.powf(1.1))).to_radians().sin()
+}
+
+#[inline]
+fn weather_fac(pressure: f64, temp: f64) -> f64
+{
+    refrac_by_pressr(pressure) * refrac_by_temp(temp)
+}
+
+// Radius of the Earth, and the height of the top of the model
+// atmosphere *| in meters*
+const EARTH_RAD: f64 = 6378137.0;
+const ATMOS_TOP: f64 = 80000.0;
+
+// Refractive index of the model atmosphere at a height *| in meters*,
+// for a pressure and temperature at the ground
+fn refrac_index(h: f64, pressure: f64, temp: f64) -> f64
+{
+    let lapse = 0.0065;
+    let tropopause = 11000.0;
+    // g / (R * lapse), for dry air
+    let expo = 9.80665 / (287.053 * lapse);
+
+    let (p, t) = if h < tropopause {
+        let t = temp - lapse*h;
+        (pressure * (t/temp).powf(expo), t)
+    } else {
+        let t = temp - lapse*tropopause;
+        let p = pressure * (t/temp).powf(expo);
+        (p * (-9.80665*(h - tropopause)/(287.053*t)).exp(), t)
+    };
+
+    1.0 + 77.6e-6 * p/t
+}
+
+// Refraction for an apparent altitude, by integrating over the zenith
+// distance of the ray through the model atmosphere, after Auer and
+// Standish
+fn integrated_refrac(apprnt_alt: f64, pressure: f64, temp: f64) -> f64
+{
+    let n0 = refrac_index(0.0, pressure, temp);
+    let z0 = PI/2.0 - apprnt_alt;
+    // n r sin(z) is constant along the ray
+    let c = n0 * EARTH_RAD * z0.sin();
+
+    let height_at = |z: f64| {
+        let target = c / z.sin();
+        let (mut lo, mut hi) = (0.0, ATMOS_TOP);
+        for _ in 0..60 {
+            let mid = 0.5 * (lo + hi);
+            if refrac_index(mid, pressure, temp) * (EARTH_RAD + mid) < target {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        0.5 * (lo + hi)
+    };
+
+    let integrand = |z: f64| {
+        let h = if z == z0 { 0.0 } else { height_at(z) };
+        let dh = 1.0;
+        let n = refrac_index(h, pressure, temp);
+        let r_dn_dr = (EARTH_RAD + h)
+                    * (refrac_index(h + dh, pressure, temp) - refrac_index((h - dh).max(0.0), pressure, temp))
+                    / (dh + dh.min(h));
+        -r_dn_dr / (n + r_dn_dr)
+    };
+
+    let z_top = (c / (refrac_index(ATMOS_TOP, pressure, temp) * (EARTH_RAD + ATMOS_TOP))).asin();
+
+    // Simpson's rule
+    let steps = 400;
+    let step = (z_top - z0) / (steps as f64);
+    let mut sum = integrand(z0) + integrand(z_top);
+    for i in 1..steps {
+        let w = if i % 2 == 1 { 4.0 } else { 2.0 };
+        sum += w * integrand(z0 + (i as f64)*step);
+    }
+
+    -sum * step / 3.0
+}
+
+/// Represents a photometric band of the Johnson-Cousins system
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Band {
+    U,
+    B,
+    V,
+    R,
+    I
+}
+
+/**
+Computes the extinction coefficient in a photometric band
+
+The coefficient is the sum of those of Rayleigh scattering by the
+air, scattering by aerosols, and absorption by ozone, for a clear
+and dry night, after Schaefer (1993).
+
+# Returns
+
+* `k`: Extinction coefficient *| in magnitudes per airmass*
+
+# Arguments
+
+* `band`           : The [Band](./enum.Band.html)
+* `observer_height`: Observer's height above sea level *| in meters*
+**/
+pub fn extinction_coeff(band: &Band, observer_height: f64) -> f64
+{
+    // effective wavelength *| in micrometers*, and the ozone
+    // absorption
+    let (lambda, k_ozone) = match *band {
+        Band::U => (0.365_f64, 0.000),
+        Band::B => (0.440, 0.016),
+        Band::V => (0.550, 0.031),
+        Band::R => (0.700, 0.008),
+        Band::I => (0.900, 0.000)
+    };
+    let w = lambda / 0.55;
+
+    let k_rayleigh = 0.1066 * (-observer_height/8200.0).exp() * w.powi(-4);
+    let k_aerosol = 0.120 * w.powf(-1.3) * (-observer_height/1500.0).exp();
+
+    k_rayleigh + k_aerosol + k_ozone
+}
+
+/**
+Computes the atmospheric extinction of the light of a body
+
+# Returns
+
+* `extinction`: Extinction *| in magnitudes*
+
+# Arguments
+
+* `true_alt`       : True altitude of the body *| in radians*
+* `band`           : The [Band](./enum.Band.html)
+* `observer_height`: Observer's height above sea level *| in meters*
+**/
+pub fn extinction(true_alt: f64, band: &Band, observer_height: f64) -> f64
+{
+    extinction_coeff(band, observer_height) * airmass_kasten_young(true_alt)
+}
+
+/**
+Computes the brightness of the night sky in the V band
+
+The moonlit sky follows the model of Krisciunas and Schaefer (1991),
+which is good to about 20 percent for a moderately dusty sky. The
+light of the dark sky grows towards the horizon as the airmass,
+less its extinction.
+
+# Returns
+
+* `sky_brightness`: Brightness of the sky *| in magnitudes per
+                    square arcsecond*
+
+# Arguments
+
+* `true_alt`: True altitude of the point of the sky *| in radians*
+* `dark_sky`: Brightness of the moonless sky at the zenith *| in
+              magnitudes per square arcsecond*, about 21.7 at a dark
+              site
+* `k`       : Extinction coefficient in the V band *| in magnitudes
+              per airmass*, such as from `extinction_coeff()`
+* `moon`    : `(moon_alt, sepr, phase_angl)` of the Moon *| in radians*,
+              which are the altitude of the Moon, its angular
+              separation from the point of the sky, and its phase
+              angle. `None` for a moonless sky.
+**/
+pub fn sky_brightness(true_alt: f64, dark_sky: f64, k: f64, moon: Option<(f64, f64, f64)>) -> f64
+{
+    let X = |alt: f64| 1.0 / (1.0 - 0.96 * (PI/2.0 - alt).sin().powi(2)).sqrt();
+
+    let X_sky = X(true_alt);
+    let mut B = nano_lamberts_frm_mag(dark_sky) * X_sky * 10_f64.powf(-0.4 * k * (X_sky - 1.0));
+
+    if let Some((moon_alt, sepr, phase_angl)) = moon {
+        if moon_alt > 0.0 {
+            let alpha = phase_angl.to_degrees().abs();
+            let rho = sepr.to_degrees();
+
+            let I = 10_f64.powf(-0.4 * (3.84 + 0.026*alpha + 4e-9*alpha.powi(4)));
+            let f = 10_f64.powf(5.36) * (1.06 + sepr.cos().powi(2))
+                  + 10_f64.powf(6.15 - rho/40.0);
+
+            B += f * I
+               * 10_f64.powf(-0.4 * k * X(moon_alt))
+               * (1.0 - 10_f64.powf(-0.4 * k * X_sky));
+        }
+    }
+
+    (20.7233 - (B / 34.08).ln()) / 0.92104
+}
+
+/**
+Computes the limiting magnitude of the naked eye
+
+The limiting magnitude at the zenith follows from the brightness of
+the sky, after Schaefer (1990), and is then lessened by the
+extinction at the altitude of interest.
+
+# Returns
+
+* `limiting_mag`: Faintest magnitude visible to the naked eye
+
+# Arguments
+
+* `sky_brightness`: Brightness of the sky, such as from
+                    `sky_brightness()` *| in magnitudes per square
+                    arcsecond*
+* `true_alt`      : True altitude of interest *| in radians*
+* `k`             : Extinction coefficient in the V band *| in
+                    magnitudes per airmass*
+**/
+pub fn limiting_mag(sky_brightness: f64, true_alt: f64, k: f64) -> f64
+{
+    let zenith = 7.93 - 5.0 * (10_f64.powf(4.316 - sky_brightness/5.0) + 1.0).log10();
+
+    zenith - k * (airmass_kasten_young(true_alt) - 1.0)
+}
+
+// Surface brightness *| in magnitudes per square arcsecond* to
+// nanolamberts
+#[inline]
+fn nano_lamberts_frm_mag(mag: f64) -> f64
+{
+    34.08 * (20.7233 - 0.92104*mag).exp()
+}
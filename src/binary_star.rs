@@ -20,9 +20,14 @@ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
 THE SOFTWARE.
 */
 
-//! Binary stars
+//! Binary stars, and eclipsing binaries
 
 use angle;
+use error;
+use orbit::elliptic;
+use std;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 /**
 Computes mean annual motion of companion star
@@ -30,7 +35,6 @@ Computes mean annual motion of companion star
 * `P`: Period of revolution of binary star
        (*mean solar year*)
 **/
-
 #[inline]
 pub fn mn_ann_motion_of_compan(P: f64) -> f64
 {
@@ -48,7 +52,6 @@ Computes mean anomaly of companion star
 * `T`: Time of periastron passage, given as
        a year with decimals (eg: 1945.62)
 **/
-
 #[inline]
 pub fn mn_anom_of_compan(n: f64, t: f64, T: f64) -> f64
 {
@@ -64,7 +67,6 @@ Computes radius vector of a binary star
 * `e`       : Eccentricity of true orbit
 * `ecc_anom`: Eccentric anomaly of binary star
 **/
-
 #[inline]
 pub fn rad_vec(a: f64, e: f64, ecc_anom: f64) -> f64
 {
@@ -79,7 +81,6 @@ Computes true anomaly of a binary star
 * `e`       : Eccentricity of true orbit
 * `ecc_anom`: Eccentric anomaly of binary star
 **/
-
 #[inline]
 pub fn true_anom(e: f64, ecc_anom: f64) -> f64
 {
@@ -145,12 +146,499 @@ pub fn ecc_of_apprnt_orb(e: f64, w: f64, i: f64) -> f64
 {
     let i_cos = i.cos();
     let e_w_cos = e * w.cos();
-    let e_w_cos_sqr = e_w_cos * e_w_cos;
+    let e_w_sin = e * w.sin();
 
-    let a = (1.0 - e_w_cos_sqr) * i_cos * i_cos;
-    let b = e * w.sin() * e_w_cos * i_cos;
-    let c = 1.0 - e_w_cos_sqr;
+    let a = (1.0 - e_w_cos*e_w_cos) * i_cos * i_cos;
+    let b = e_w_sin * e_w_cos * i_cos;
+    let c = 1.0 - e_w_sin*e_w_sin;
     let d = ((a - c)*(a - c) + 4.0*b*b).sqrt();
 
     ((2.0 * d) / (a + c + d)).sqrt()
 }
+
+/**
+Orbit of a visual binary star, given by its seven elements
+
+The orbit is that of the companion relative to the primary star.
+The ascending node is the node at which the companion recedes
+from the observer.
+**/
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VisualBinary {
+    /// Period of revolution *| in mean solar years*
+    pub P: f64,
+    /// Time of periastron passage, as a year with decimals
+    pub T: f64,
+    /// Eccentricity of the true orbit
+    pub e: f64,
+    /// Semimajor axis of the true orbit *| in arcseconds*
+    pub a: f64,
+    /// Inclination of the true orbit to a plane at right
+    /// angles to the line of sight *| in radians*
+    pub i: f64,
+    /// Position angle of the ascending node *| in radians*
+    pub asc_node: f64,
+    /// Longitude of periastron, measured in the plane of the
+    /// true orbit from the ascending node *| in radians*
+    pub w: f64
+}
+
+/// Apparent position of the companion of a visual binary star
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ApparentPosition {
+    /// Time, as a year with decimals
+    pub year: f64,
+    /// Position angle of the companion, measured from the north
+    /// towards the east *| in radians*
+    pub pos_angl: f64,
+    /// Angular separation of the companion *| in arcseconds*
+    pub sepr: f64
+}
+
+/**
+Computes the apparent position of the companion of a visual binary
+star
+
+# Returns
+
+* `pos`: The [ApparentPosition](./struct.ApparentPosition.html)
+         of the companion
+
+# Arguments
+
+* `binary`: Visual binary star
+* `t`     : Time, as a year with decimals (eg: 1980.0)
+**/
+pub fn apprnt_position(binary: &VisualBinary, t: f64) -> ApparentPosition
+{
+    let n = mn_ann_motion_of_compan(binary.P);
+    let mn_anom = angle::limit_to_two_PI(mn_anom_of_compan(n, t, binary.T));
+    let v = elliptic::true_anom_frm_mn_anom(mn_anom, binary.e, 1e-12);
+    let r = binary.a * (1.0 - binary.e*binary.e) / (1.0 + binary.e*v.cos());
+
+    ApparentPosition {
+        year:     t,
+        pos_angl: apprnt_coords_angl(binary.asc_node, v, binary.w, binary.i),
+        sepr:     anglr_sepr(r, v, binary.w, binary.i)
+    }
+}
+
+/// Ellipse described by the companion of a visual binary star on
+/// the sky
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ApparentEllipse {
+    /// Semimajor axis *| in arcseconds*
+    pub a: f64,
+    /// Semiminor axis *| in arcseconds*
+    pub b: f64,
+    /// Eccentricity
+    pub e: f64,
+    /// Position angle of the major axis, between 0 and
+    /// 180 degrees *| in radians*
+    pub pos_angl: f64,
+    /// Offset of the center of the ellipse from the primary
+    /// star, towards the north *| in arcseconds*
+    pub center_north: f64,
+    /// Offset of the center of the ellipse from the primary
+    /// star, towards the east *| in arcseconds*
+    pub center_east: f64
+}
+
+/**
+Computes the apparent orbit of a visual binary star, which is the
+projection of the true orbit on the sky
+
+The primary star is at a focus of the true orbit, but in general
+not at a focus of the apparent orbit.
+
+# Returns
+
+* `ellipse`: The [ApparentEllipse](./struct.ApparentEllipse.html)
+
+# Arguments
+
+* `binary`: Visual binary star
+**/
+pub fn apprnt_ellipse(binary: &VisualBinary) -> ApparentEllipse
+{
+    let (A, B, F, G) = thiele_innes(binary);
+
+    // conjugate semidiameters of the apparent ellipse
+    let b_fac = (1.0 - binary.e*binary.e).sqrt();
+    let (F, G) = (F * b_fac, G * b_fac);
+
+    let p = A*A + F*F;
+    let q = B*B + G*G;
+    let r = A*B + F*G;
+    let h = (0.25*(p - q)*(p - q) + r*r).sqrt();
+    let semimaj = (0.5*(p + q) + h).sqrt();
+    let semimin = (0.5*(p + q) - h).max(0.0).sqrt();
+
+    ApparentEllipse {
+        a:            semimaj,
+        b:            semimin,
+        e:            (1.0 - (semimin*semimin)/(semimaj*semimaj)).sqrt(),
+        pos_angl:     angle::limit_to_two_PI((2.0 * r).atan2(p - q)) / 2.0,
+        center_north: -binary.e * A,
+        center_east:  -binary.e * B
+    }
+}
+
+/**
+Computes the radial velocities of both components of a visual
+binary star
+
+# Returns
+
+`(rad_vel1, rad_vel2)`
+
+* `rad_vel1`: Radial velocity of the primary star
+              *| in kilometers per second*
+* `rad_vel2`: Radial velocity of the companion
+              *| in kilometers per second*
+
+# Arguments
+
+* `binary`    : Visual binary star
+* `t`         : Time, as a year with decimals (eg: 1980.0)
+* `parallax`  : Parallax of the binary star *| in arcseconds*
+* `mass_ratio`: Mass of the companion divided by the mass of the
+                primary star
+* `sys_vel`   : Radial velocity of the barycenter of the system
+                *| in kilometers per second*
+**/
+pub fn rad_vels(binary: &VisualBinary,
+                t: f64,
+                parallax: f64,
+                mass_ratio: f64,
+                sys_vel: f64) -> (f64, f64)
+{
+    let n = mn_ann_motion_of_compan(binary.P);
+    let mn_anom = angle::limit_to_two_PI(mn_anom_of_compan(n, t, binary.T));
+    let v = elliptic::true_anom_frm_mn_anom(mn_anom, binary.e, 1e-12);
+
+    // semi-amplitude of the relative orbit, from AU per year
+    let K = AU_PER_YEAR * n * (binary.a / parallax) * binary.i.sin()
+          / (1.0 - binary.e*binary.e).sqrt();
+    let rel_vel = K * ((v + binary.w).cos() + binary.e*binary.w.cos());
+
+    (
+        sys_vel - rel_vel * mass_ratio / (1.0 + mass_ratio),
+        sys_vel + rel_vel / (1.0 + mass_ratio)
+    )
+}
+
+/**
+Iterator over the apparent positions of the companion of a visual
+binary star at equal intervals of time
+**/
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ephemeris {
+    binary: VisualBinary,
+    start: f64,
+    end: f64,
+    step: f64,
+    n: u32
+}
+
+impl Iterator for Ephemeris {
+    type Item = ApparentPosition;
+
+    fn next(&mut self) -> Option<ApparentPosition>
+    {
+        let t = self.start + self.step * (self.n as f64);
+        if t > self.end {
+            return None;
+        }
+        self.n += 1;
+
+        Some(apprnt_position(&self.binary, t))
+    }
+}
+
+/**
+Computes an ephemeris of the companion of a visual binary star
+
+# Returns
+
+* `ephemeris`: An [Ephemeris](./struct.Ephemeris.html) iterator over
+               the apparent positions of the companion, from `t_start`
+               to `t_end`
+
+# Arguments
+
+* `binary` : Visual binary star
+* `t_start`: First time of the ephemeris, as a year with decimals
+* `t_end`  : Last time of the ephemeris, as a year with decimals
+* `dt`     : Interval between the entries *| in years*
+**/
+pub fn ephemeris(binary: &VisualBinary, t_start: f64, t_end: f64, dt: f64) -> Ephemeris
+{
+    Ephemeris {
+        binary: *binary,
+        start:  t_start,
+        end:    t_end,
+        step:   dt,
+        n:      0
+    }
+}
+
+// Velocity of 1 AU per year, in km/s
+const AU_PER_YEAR: f64 = 4.740470446;
+
+// Thiele-Innes constants of a visual binary star, with the x-axis
+// towards the north and the y-axis towards the east
+fn thiele_innes(binary: &VisualBinary) -> (f64, f64, f64, f64)
+{
+    let (sw, cw) = binary.w.sin_cos();
+    let (sn, cn) = binary.asc_node.sin_cos();
+    let ci = binary.i.cos();
+
+    (
+        binary.a * ( cw*cn - sw*sn*ci),
+        binary.a * ( cw*sn + sw*cn*ci),
+        binary.a * (-sw*cn - cw*sn*ci),
+        binary.a * (-sw*sn + cw*cn*ci)
+    )
+}
+
+/**
+Orbit of an eclipsing binary around a third body, which delays the
+light from the binary
+**/
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LightTimeOrbit {
+    /// Period of the orbit *| in days*
+    pub P: f64,
+    /// Julian (Ephemeris) day of periastron passage
+    pub T: f64,
+    /// Eccentricity of the orbit
+    pub e: f64,
+    /// Argument of periastron of the orbit of the
+    /// binary around the barycenter *| in radians*
+    pub w: f64,
+    /// Projected semimajor axis of the orbit of
+    /// the binary around the barycenter *| in AU*
+    pub a_sin_i: f64
+}
+
+/**
+Eclipsing binary star
+
+The stars are taken as uniformly bright spheres, with the
+primary star eclipsed at the primary minimum.
+**/
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EclipsingBinary {
+    /// Julian (Ephemeris) day of an observed primary minimum
+    pub epoch: f64,
+    /// Period of revolution *| in days*
+    pub P: f64,
+    /// Eccentricity of the true orbit
+    pub e: f64,
+    /// Longitude of periastron *| in radians*
+    pub w: f64,
+    /// Inclination of the true orbit to a plane at right
+    /// angles to the line of sight *| in radians*
+    pub i: f64,
+    /// Radius of the primary star, in units of the
+    /// semimajor axis of the true orbit
+    pub r1: f64,
+    /// Radius of the secondary star, in units of the
+    /// semimajor axis of the true orbit
+    pub r2: f64,
+    /// Fraction of the total light given by the primary star
+    pub L1: f64,
+    /// Orbit around a third body, if any
+    pub light_time: Option<LightTimeOrbit>
+}
+
+/// Represents a minimum in the light of an eclipsing binary
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Minimum {
+    /// Eclipse of the primary star
+    Primary,
+    /// Eclipse of the secondary star
+    Secondary
+}
+
+/**
+Computes the light-time effect on the times of an eclipsing binary
+due to it's orbit around a third body
+
+# Returns
+
+* `light_time`: Delay in the light from the binary, relative to the
+                barycenter of the system *| in days*
+
+# Arguments
+
+* `orbit`: Orbit of the binary around the third body
+* `JD`   : Julian (Ephemeris) day
+**/
+pub fn light_time(orbit: &LightTimeOrbit, JD: f64) -> f64
+{
+    let mn_anom = angle::TWO_PI * (JD - orbit.T) / orbit.P;
+    let v = elliptic::true_anom_frm_mn_anom(mn_anom, orbit.e, 1e-10);
+
+    0.0057755183 * orbit.a_sin_i * (
+        (1.0 - orbit.e*orbit.e) / (1.0 + orbit.e*v.cos()) * (v + orbit.w).sin()
+      + orbit.e * orbit.w.sin()
+    )
+}
+
+/**
+Computes the time of a minimum of an eclipsing binary
+
+The displacement of the secondary minimum due to the eccentricity
+of the orbit is taken from the conjunctions of the stars, which is
+exact for an inclination of 90°.
+
+# Returns
+
+* `JD_min`: Julian (Ephemeris) day of the minimum
+
+# Arguments
+
+* `binary` : Eclipsing binary star
+* `cycle`  : Number of periods after the epoch of the binary
+* `minimum`: Primary or secondary minimum
+**/
+pub fn minimum(binary: &EclipsingBinary, cycle: i64, minimum: &Minimum) -> f64
+{
+    let mut JD = geometric_epoch(binary) + binary.P * (cycle as f64);
+    if *minimum == Minimum::Secondary {
+        let phase = angle::limit_to_two_PI (
+            conjunction_mn_anom(binary, 1.5 * std::f64::consts::PI)
+          - conjunction_mn_anom(binary, 0.5 * std::f64::consts::PI)
+        ) / angle::TWO_PI;
+        JD += binary.P * phase;
+    }
+
+    JD + binary.light_time.map_or(0.0, |orbit| light_time(&orbit, JD))
+}
+
+/**
+Computes the time of the next minimum of an eclipsing binary
+
+# Returns
+
+`Ok((cycle, JD_min))`, or `Err(error)` with `Error::NotPositive` if
+the period of the binary isn't positive, or `Error::NotFinite`
+
+* `cycle` : Number of periods after the epoch of the binary
+* `JD_min`: Julian (Ephemeris) day of the minimum
+
+# Arguments
+
+* `binary` : Eclipsing binary star
+* `JD`     : Julian (Ephemeris) day after which to look for
+             the minimum
+* `minimum`: Primary or secondary minimum
+**/
+pub fn next_minimum(binary: &EclipsingBinary, JD: f64, minimum: &Minimum) -> Result<(i64, f64), error::Error>
+{
+    error::check_positive(binary.P)?;
+    if !JD.is_finite() || !binary.epoch.is_finite() {
+        return Err(error::Error::NotFinite);
+    }
+
+    let mut cycle = ((JD - binary.epoch) / binary.P).floor() as i64;
+    while self::minimum(binary, cycle - 1, minimum) > JD {
+        cycle -= 1;
+    }
+    while self::minimum(binary, cycle, minimum) <= JD {
+        cycle += 1;
+    }
+
+    Ok((cycle, self::minimum(binary, cycle, minimum)))
+}
+
+/**
+Computes the brightness of an eclipsing binary, relative to it's
+brightness outside the eclipses
+
+# Returns
+
+* `rel_flux`: Brightness of the binary, as a fraction of it's
+              brightness outside the eclipses
+
+# Arguments
+
+* `binary`: Eclipsing binary star
+* `JD`    : Julian (Ephemeris) day
+**/
+pub fn rel_flux(binary: &EclipsingBinary, JD: f64) -> f64
+{
+    let t = JD - binary.light_time.map_or(0.0, |orbit| light_time(&orbit, JD));
+    let mn_anom = conjunction_mn_anom(binary, 0.5 * std::f64::consts::PI)
+                + angle::TWO_PI * (t - geometric_epoch(binary)) / binary.P;
+    let v = elliptic::true_anom_frm_mn_anom (
+        angle::limit_to_two_PI(mn_anom), binary.e, 1e-10
+    );
+
+    let r = (1.0 - binary.e*binary.e) / (1.0 + binary.e*v.cos());
+    let u = v + binary.w;
+    let d = r * (1.0 - (binary.i.sin() * u.sin()).powi(2)).sqrt();
+    let overlap = overlap_area(binary.r1, binary.r2, d);
+
+    // the secondary star is in front of the primary star
+    // at the primary minimum, where u = 90°
+    if u.sin() > 0.0 {
+        1.0 - binary.L1 * overlap / (std::f64::consts::PI * binary.r1 * binary.r1)
+    } else {
+        1.0 - (1.0 - binary.L1) * overlap / (std::f64::consts::PI * binary.r2 * binary.r2)
+    }
+}
+
+/**
+Computes the drop in magnitude of an eclipsing binary, relative
+to it's magnitude outside the eclipses
+
+# Arguments
+
+* `binary`: Eclipsing binary star
+* `JD`    : Julian (Ephemeris) day
+**/
+#[inline]
+pub fn mag_drop(binary: &EclipsingBinary, JD: f64) -> f64
+{
+    -2.5 * rel_flux(binary, JD).log10()
+}
+
+// Mean anomaly of the binary at the conjunction where v + w = u
+fn conjunction_mn_anom(binary: &EclipsingBinary, u: f64) -> f64
+{
+    elliptic::mn_anom_frm_true_anom(u - binary.w, binary.e)
+}
+
+// Epoch of the binary, corrected for the light-time effect
+fn geometric_epoch(binary: &EclipsingBinary) -> f64
+{
+    binary.epoch - binary.light_time.map_or(0.0, |orbit| light_time(&orbit, binary.epoch))
+}
+
+// Area of overlap of two disks of radii r1 and r2 with
+// centers separated by d
+fn overlap_area(r1: f64, r2: f64, d: f64) -> f64
+{
+    if d >= r1 + r2 {
+        0.0
+    } else if d <= (r1 - r2).abs() {
+        std::f64::consts::PI * r1.min(r2).powi(2)
+    } else {
+        let a1 = ((d*d + r1*r1 - r2*r2) / (2.0 * d * r1)).acos();
+        let a2 = ((d*d + r2*r2 - r1*r1) / (2.0 * d * r2)).acos();
+
+        r1*r1*a1 + r2*r2*a2 - 0.5*(
+            (-d + r1 + r2) * (d + r1 - r2) * (d - r1 + r2) * (d + r1 + r2)
+        ).sqrt()
+    }
+}
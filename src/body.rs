@@ -0,0 +1,53 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+
+//! Bodies of the sky
+//!
+//! The routines of [apparent](../apparent/index.html),
+//! [ephemeris](../ephemeris/index.html), [transit](../transit/index.html),
+//! [tide](../tide/index.html), and of the
+//! [groupings](../planet/grouping/index.html) and
+//! [phenomena](../planet/phenomena/index.html) of the planets, all take
+//! a [Body](./enum.Body.html). Each documents the bodies it computes,
+//! and returns an `Error::InvalidBody` for the others, such as the
+//! Earth for a position seen from the Earth's center.
+
+use planet;
+use star;
+
+/// Represents a body of the sky
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Body {
+    /// The Sun
+    Sun,
+    /// The Moon
+    Moon,
+    /// A planet
+    Planet(planet::Planet),
+    /// Pluto
+    Pluto,
+    /// A star, with its mean place at J2000.0 referred to the ICRS or
+    /// FK5 system
+    Star(star::MeanPlace)
+}
@@ -0,0 +1,255 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Chebyshev approximations of ephemerides
+//!
+//! A position computed by any function of the crate can be fitted
+//! once over an interval of time, and then evaluated many times at a
+//! small fraction of the cost of the theory, as with the Chebyshev
+//! polynomials of the JPL Development Ephemerides.
+
+use error;
+use planet;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Chebyshev approximation of a function over an interval
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Series {
+    /// Start of the interval
+    pub start: f64,
+    /// End of the interval
+    pub end: f64,
+    /// Coefficients of the Chebyshev polynomials, from the zeroth
+    /// degree upwards
+    pub coeffs: Vec<f64>
+}
+
+impl Series {
+    /**
+    Fits a Chebyshev approximation to a function over an interval
+
+    The function is interpolated at the Chebyshev nodes of the
+    interval, which keeps the error of the approximation close to
+    the least possible for the degree.
+
+    # Arguments
+
+    * `f`    : Function to approximate
+    * `start`: Start of the interval
+    * `end`  : End of the interval
+    * `n`    : Number of coefficients, one more than the degree
+    **/
+    pub fn fit<F>(f: F, start: f64, end: f64, n: usize) -> Series where F: Fn(f64) -> f64 {
+        let values: Vec<f64> = (0..n)
+            .map(|k| f(from_unit(node(k, n), start, end)))
+            .collect();
+
+        Series {
+            start,
+            end,
+            coeffs : coeffs_frm_values(&values)
+        }
+    }
+
+    /**
+    Evaluates the approximation
+
+    # Arguments
+
+    * `x`: Value within the interval of the approximation
+    **/
+    pub fn eval(&self, x: f64) -> f64 {
+        clenshaw(&self.coeffs, to_unit(x, self.start, self.end))
+    }
+}
+
+/// Chebyshev approximation of the rectangular coordinates of a body,
+/// over consecutive intervals of time
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ephemeris {
+    /// Julian (Ephemeris) day of the start of the first interval
+    pub start: f64,
+    /// Length of each interval *| in days*
+    pub interval: f64,
+    /// Approximations of the `(x, y, z)` coordinates, for each
+    /// interval
+    pub segments: Vec<[Series; 3]>
+}
+
+impl Ephemeris {
+    /**
+    Fits Chebyshev approximations to the rectangular coordinates of
+    a body
+
+    # Returns
+
+    * `Ok(ephemeris)`: The fitted [Ephemeris](./struct.Ephemeris.html), or
+    * `Err(error)`   : `Error::NotPositive` if `interval` isn't
+      positive, or `Error::NotFinite`
+
+    # Arguments
+
+    * `pos`     : Function giving the rectangular coordinates `(x, y, z)`
+      of the body for a Julian (Ephemeris) day
+    * `start`   : Julian (Ephemeris) day of the start of the ephemeris
+    * `end`     : Julian (Ephemeris) day of the end of the ephemeris
+    * `interval`: Length of each interval, positive *| in days*
+    * `n`       : Number of coefficients in each interval
+    **/
+    pub fn fit<F>(pos: F, start: f64, end: f64, interval: f64, n: usize) -> Result<Ephemeris, error::Error>
+        where F: Fn(f64) -> (f64, f64, f64)
+    {
+        error::check_positive(interval)?;
+        if !start.is_finite() || !end.is_finite() {
+            return Err(error::Error::NotFinite);
+        }
+
+        let num_segments = ((end - start) / interval).ceil().max(1.0) as usize;
+
+        let segments = (0..num_segments).map(|i| {
+            let a = start + (i as f64)*interval;
+            let b = a + interval;
+            let values: Vec<(f64, f64, f64)> = (0..n)
+                .map(|k| pos(from_unit(node(k, n), a, b)))
+                .collect();
+
+            let coord = |c: fn(&(f64, f64, f64)) -> f64| Series {
+                start  : a,
+                end    : b,
+                coeffs : coeffs_frm_values(&values.iter().map(c).collect::<Vec<f64>>())
+            };
+
+            [coord(|p| p.0), coord(|p| p.1), coord(|p| p.2)]
+        }).collect();
+
+        Ok(Ephemeris {
+            start,
+            interval,
+            segments
+        })
+    }
+
+    /**
+    Evaluates the rectangular coordinates of the body
+
+    # Returns
+
+    * `Some((x, y, z))`: Rectangular coordinates of the body, or
+    * `None`           : if `JD` is outside the ephemeris
+
+    # Arguments
+
+    * `JD`: Julian (Ephemeris) day
+    **/
+    pub fn pos(&self, JD: f64) -> Option<(f64, f64, f64)> {
+        let t = (JD - self.start) / self.interval;
+        if t < 0.0 || t > self.segments.len() as f64 {
+            return None;
+        }
+
+        let i = (t.floor() as usize).min(self.segments.len() - 1);
+        let s = &self.segments[i];
+
+        Some((s[0].eval(JD), s[1].eval(JD), s[2].eval(JD)))
+    }
+}
+
+/**
+Fits Chebyshev approximations to a planet's heliocentric rectangular
+coordinates, referred to the ecliptic and mean equinox of J2000.0
+
+An interval of 8 days with 12 coefficients reproduces
+`planet::heliocent_ecl_rect_coords_J2000()` for the inner planets
+to better than 10<sup>-9</sup> AU.
+
+# Returns
+
+* `Ok(ephemeris)`: The fitted [Ephemeris](./struct.Ephemeris.html), or
+* `Err(error)`   : `Error::NotPositive` if `interval` isn't positive,
+                   or `Error::NotFinite`
+
+# Arguments
+
+* `planet`  : Any variant of [Planet](../planet/enum.Planet.html)
+* `start`   : Julian (Ephemeris) day of the start of the ephemeris
+* `end`     : Julian (Ephemeris) day of the end of the ephemeris
+* `interval`: Length of each interval, positive *| in days*
+* `n`       : Number of coefficients in each interval
+**/
+pub fn planet_ephemeris(planet: &planet::Planet, start: f64, end: f64, interval: f64, n: usize) -> Result<Ephemeris, error::Error>
+{
+    Ephemeris::fit(
+        |JD| planet::heliocent_ecl_rect_coords_J2000(planet, JD), start, end, interval, n
+    )
+}
+
+// k-th of n Chebyshev nodes in [-1, 1]
+#[inline]
+fn node(k: usize, n: usize) -> f64
+{
+    (std::f64::consts::PI * ((k as f64) + 0.5) / (n as f64)).cos()
+}
+
+#[inline]
+fn from_unit(u: f64, a: f64, b: f64) -> f64
+{
+    (a + b)/2.0 + u*(b - a)/2.0
+}
+
+#[inline]
+fn to_unit(x: f64, a: f64, b: f64) -> f64
+{
+    (2.0*x - a - b) / (b - a)
+}
+
+// Coefficients of the Chebyshev polynomials interpolating values at
+// the Chebyshev nodes
+fn coeffs_frm_values(values: &[f64]) -> Vec<f64>
+{
+    let n = values.len();
+
+    (0..n).map(|j| {
+        let sum = values.iter().enumerate().fold(0.0, |s, (k, v)| {
+            s + v * (std::f64::consts::PI * (j as f64) * ((k as f64) + 0.5) / (n as f64)).cos()
+        });
+        if j == 0 { sum / (n as f64) } else { 2.0 * sum / (n as f64) }
+    }).collect()
+}
+
+// Sum of a Chebyshev series at u in [-1, 1], by Clenshaw's recurrence
+pub(crate) fn clenshaw(coeffs: &[f64], u: f64) -> f64
+{
+    let (mut b1, mut b2) = (0.0, 0.0);
+    for c in coeffs.iter().skip(1).rev() {
+        let b = 2.0*u*b1 - b2 + c;
+        b2 = b1;
+        b1 = b;
+    }
+
+    match coeffs.first() {
+        Some(c0) => u*b1 - b2 + c0,
+        None     => 0.0
+    }
+}
@@ -0,0 +1,302 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! The celestial intermediate origin, and the CIO based transformation
+//! between celestial and terrestrial coordinates
+
+use angle;
+use precess::{Mat, bias_precess_nut_mat, rot_x, rot_y, rot_z, mat_mul, transpose, mat_vec_mul, vec_frm_sph, sph_frm_vec};
+use time;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/**
+Computes the coordinates of the celestial intermediate pole
+
+The bias and precession are those of the IAU 2006 model, through
+the Fukushima-Williams angles, and the nutation is that of
+`nutation::nutation()`. So `X` and `Y` are good to a few
+milliarcseconds, rather than to the microarcseconds of the full
+IAU 2006/2000A series.
+
+# Returns
+
+`(X, Y)`
+
+* `X`: X coordinate of the celestial intermediate pole in
+       the GCRS *| in radians*
+* `Y`: Y coordinate of the celestial intermediate pole in
+       the GCRS *| in radians*
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn cip_xy(JD: f64) -> (f64, f64)
+{
+    let npb = bias_precess_nut_mat(JD);
+
+    (npb[2][0], npb[2][1])
+}
+
+/**
+Computes the CIO locator, which positions the celestial
+intermediate origin on the equator of the celestial
+intermediate pole
+
+The series is that of the IAU 2006 model, with the terms smaller
+than about a microarcsecond omitted.
+
+# Returns
+
+* `s`: The CIO locator *| in radians*
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+* `X` : X coordinate of the celestial intermediate pole *| in radians*
+* `Y` : Y coordinate of the celestial intermediate pole *| in radians*
+**/
+pub fn cio_locator(JD: f64, X: f64, Y: f64) -> f64
+{
+    struct terms(i8, i8, i8, i8, i8, f64, f64);
+
+    let terms_t0 = [
+        terms(0, 0, 0, 0, 1, -2640.73,  0.39),
+        terms(0, 0, 0, 0, 2,   -63.53,  0.02),
+        terms(0, 0, 2,-2, 3,   -11.75, -0.01),
+        terms(0, 0, 2,-2, 1,   -11.21, -0.01),
+        terms(0, 0, 2,-2, 2,     4.57,  0.00),
+        terms(0, 0, 2, 0, 3,    -2.02,  0.00),
+        terms(0, 0, 2, 0, 1,    -1.98,  0.00),
+        terms(0, 0, 0, 0, 3,     1.72,  0.00),
+        terms(0, 1, 0, 0, 1,     1.41,  0.01),
+        terms(0, 1, 0, 0,-1,     1.26,  0.01)
+    ];
+    let terms_t1 = [
+        terms(0, 0, 0, 0, 2, -0.07,  3.57),
+        terms(0, 0, 0, 0, 1,  1.73, -0.03)
+    ];
+    let terms_t2 = [
+        terms(0, 0, 0, 0, 1,  743.52, -0.17),
+        terms(0, 0, 2,-2, 2,   56.91,  0.06),
+        terms(0, 0, 2, 0, 2,    9.84, -0.01),
+        terms(0, 0, 0, 0, 2,   -8.85,  0.01),
+        terms(0, 1, 0, 0, 0,   -6.38, -0.05),
+        terms(1, 0, 0, 0, 0,   -3.07,  0.00),
+        terms(0, 1, 2,-2, 2,    2.23,  0.00),
+        terms(0, 0, 2, 0, 1,    1.67,  0.00),
+        terms(1, 0, 2, 0, 2,    1.30,  0.00)
+    ];
+    let terms_t3 = [
+        terms(0, 0, 0, 0, 1,  0.30, -23.42),
+        terms(0, 0, 2,-2, 2, -0.03,  -1.46)
+    ];
+
+    let t = time::julian_cent(JD);
+    let (l, l1, F, D, om) = fund_args(t);
+
+    let sum = |series: &[terms]| -> f64 {
+        series.iter().fold(0.0, |s, x| {
+            let arg =
+                (x.0 as f64)*l + (x.1 as f64)*l1 + (x.2 as f64)*F
+              + (x.3 as f64)*D + (x.4 as f64)*om;
+
+            s + x.5*arg.sin() + x.6*arg.cos()
+        })
+    };
+
+    // s + XY/2 *| in microarcseconds*
+    let s_plus_XY_2 =
+        Horner_eval!(t, 94.0, 3808.65, -122.68, -72574.11, 27.98, 15.62)
+      + sum(&terms_t0)
+      + sum(&terms_t1) * t
+      + sum(&terms_t2) * t * t
+      + sum(&terms_t3) * t * t * t
+      - 0.26 * om.sin() * t * t * t * t;
+
+    angle::deg_frm_dms(0, 0, s_plus_XY_2 * 1e-6).to_radians() - X*Y/2.0
+}
+
+/**
+Computes the Earth rotation angle
+
+The Earth rotation angle is the angle between the celestial
+intermediate origin and the terrestrial intermediate origin,
+measured along the equator of the celestial intermediate pole.
+
+# Returns
+
+* `era`: Earth rotation angle *| in radians*
+
+# Arguments
+
+* `JD`: Julian day (UT1)
+**/
+pub fn earth_rot_angl(JD: f64) -> f64
+{
+    let Tu = JD - 2451545.0;
+
+    angle::limit_to_two_PI (
+        angle::TWO_PI * (Tu.fract() + 0.7790572732640 + 0.00273781191135448*Tu)
+    )
+}
+
+/**
+Computes the equation of the origins
+
+The equation of the origins is the distance between the celestial
+intermediate origin and the true equinox, along the equator of the
+celestial intermediate pole. Apparent sidereal time is the Earth
+rotation angle minus the equation of the origins.
+
+# Returns
+
+* `eq_of_origins`: Equation of the origins *| in radians*
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn eq_of_origins(JD: f64) -> f64
+{
+    let npb = bias_precess_nut_mat(JD);
+    let s = cio_locator(JD, npb[2][0], npb[2][1]);
+
+    // the celestial intermediate origin, in the true equator and
+    // equinox of date
+    let x = npb[2][0];
+    let ax = x / (1.0 + npb[2][2]);
+    let cio = [1.0 - ax*x, -ax*npb[2][1], -x];
+
+    let p = npb[0][0]*cio[0] + npb[0][1]*cio[1] + npb[0][2]*cio[2];
+    let q = npb[1][0]*cio[0] + npb[1][1]*cio[1] + npb[1][2]*cio[2];
+
+    if p == 0.0 && q == 0.0 {
+        s
+    } else {
+        s - q.atan2(p)
+    }
+}
+
+/**
+Computes terrestrial coordinates from celestial coordinates, by
+the CIO based transformation
+
+# Returns
+
+`(long, lat)`
+
+* `long`: Longitude in the ITRS, positive east *| in radians*
+* `lat` : Latitude in the ITRS *| in radians*
+
+# Arguments
+
+* `asc`   : Right ascension in the GCRS *| in radians*
+* `dec`   : Declination in the GCRS *| in radians*
+* `JD`    : Julian (Ephemeris) day
+* `JD_UT1`: Julian day (UT1) corresponding to `JD`
+* `xp`    : X coordinate of the pole *| in radians*, or zero to
+            ignore polar motion
+* `yp`    : Y coordinate of the pole *| in radians*, or zero to
+            ignore polar motion
+**/
+pub fn terr_frm_celes(asc: f64, dec: f64, JD: f64, JD_UT1: f64, xp: f64, yp: f64) -> (f64, f64)
+{
+    let m = celes_to_terr_mat(JD, JD_UT1, xp, yp);
+
+    sph_frm_vec(&mat_vec_mul(&m, &vec_frm_sph(asc, dec)))
+}
+
+/**
+Computes celestial coordinates from terrestrial coordinates, by
+the CIO based transformation
+
+This is the inverse of `terr_frm_celes()`.
+
+# Returns
+
+`(asc, dec)`
+
+* `asc`: Right ascension in the GCRS *| in radians*
+* `dec`: Declination in the GCRS *| in radians*
+
+# Arguments
+
+* `long`  : Longitude in the ITRS, positive east *| in radians*
+* `lat`   : Latitude in the ITRS *| in radians*
+* `JD`    : Julian (Ephemeris) day
+* `JD_UT1`: Julian day (UT1) corresponding to `JD`
+* `xp`    : X coordinate of the pole *| in radians*, or zero to
+            ignore polar motion
+* `yp`    : Y coordinate of the pole *| in radians*, or zero to
+            ignore polar motion
+**/
+pub fn celes_frm_terr(long: f64, lat: f64, JD: f64, JD_UT1: f64, xp: f64, yp: f64) -> (f64, f64)
+{
+    let m = transpose(&celes_to_terr_mat(JD, JD_UT1, xp, yp));
+
+    sph_frm_vec(&mat_vec_mul(&m, &vec_frm_sph(long, lat)))
+}
+
+// Fundamental arguments of the IERS Conventions (2003): the mean
+// anomalies of the Moon and the Sun, the mean argument of
+// latitude of the Moon, the mean elongation of the Moon from the
+// Sun and the longitude of the mean ascending node of the Moon
+fn fund_args(t: f64) -> (f64, f64, f64, f64, f64)
+{
+    let arcsec = |a: f64| angle::deg_frm_dms(0, 0, a % 1296000.0).to_radians();
+
+    (
+        arcsec(Horner_eval!(t, 485868.249036, 1717915923.2178, 31.8792, 0.051635, -0.00024470)),
+        arcsec(Horner_eval!(t, 1287104.793048, 129596581.0481, -0.5532, 0.000136, -0.00001149)),
+        arcsec(Horner_eval!(t, 335779.526232, 1739527262.8478, -12.7512, -0.001037, 0.00000417)),
+        arcsec(Horner_eval!(t, 1072260.703692, 1602961601.2090, -6.3706, 0.006593, -0.00003169)),
+        arcsec(Horner_eval!(t, 450160.398036, -6962890.5431, 7.4722, 0.007702, -0.00005939))
+    )
+}
+
+// Matrix from the GCRS to the ITRS
+fn celes_to_terr_mat(JD: f64, JD_UT1: f64, xp: f64, yp: f64) -> Mat
+{
+    let (X, Y) = cip_xy(JD);
+    let s = cio_locator(JD, X, Y);
+
+    // from the GCRS to the celestial intermediate reference system
+    let r2 = X*X + Y*Y;
+    let e = if r2 > 0.0 { Y.atan2(X) } else { 0.0 };
+    let d = (r2 / (1.0 - r2)).sqrt().atan();
+
+    let mut m = rot_z(e);
+    m = mat_mul(&rot_y(d), &m);
+    m = mat_mul(&rot_z(-(e + s)), &m);
+
+    // Earth rotation, and polar motion with the TIO locator
+    m = mat_mul(&rot_z(earth_rot_angl(JD_UT1)), &m);
+
+    let s1 = angle::deg_frm_dms(0, 0, -47e-6 * time::julian_cent(JD)).to_radians();
+    let mut w = rot_z(s1);
+    w = mat_mul(&rot_y(-xp), &w);
+    w = mat_mul(&rot_x(-yp), &w);
+
+    mat_mul(&w, &m)
+}
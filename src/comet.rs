@@ -0,0 +1,574 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Comets
+
+use angle;
+use consts;
+use ecliptic;
+use planet;
+use precess;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Holds the orbital elements of a comet, referred to the ecliptic
+/// and mean equinox of J2000.0
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Elements {
+    /// Perihelion distance *| in AU*
+    pub q: f64,
+    /// Eccentricity of the orbit
+    pub e: f64,
+    /// Inclination of the orbit *| in radians*
+    pub i: f64,
+    /// Longitude of the ascending node *| in radians*
+    pub sigma: f64,
+    /// Argument of the perihelion *| in radians*
+    pub w: f64,
+    /// Time of passage through the perihelion, in Julian (Ephemeris) day
+    pub T: f64,
+}
+
+/// Holds the orbital elements of a comet or asteroid together with
+/// their covariance
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UncertainElements {
+    /// The orbital elements
+    pub elements: Elements,
+    /// Covariance matrix of the elements, in the order `q`, `e`, `i`,
+    /// `sigma`, `w`, `T` *| in AU, radians and days*
+    pub covar: [[f64; 6]; 6],
+}
+
+/// Holds the uncertainty ellipse of a position on the sky
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SkyEllipse {
+    /// Nominal astrometric right ascension, referred to the mean
+    /// equator and equinox of J2000.0 *| in radians*
+    pub asc: f64,
+    /// Nominal astrometric declination, referred to the mean equator
+    /// and equinox of J2000.0 *| in radians*
+    pub dec: f64,
+    /// Semimajor axis of the one-sigma ellipse *| in radians*
+    pub semimaj_axis: f64,
+    /// Semiminor axis of the one-sigma ellipse *| in radians*
+    pub semimin_axis: f64,
+    /// Position angle of the major axis, measured eastwards from the
+    /// north *| in radians*. *range: 0 to pi*
+    pub pos_angl: f64,
+}
+
+/// Holds the position of a dust particle in the tail of a comet, as
+/// seen from the Earth
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TailPoint {
+    /// Ratio of the radiation pressure to the solar gravity acting
+    /// on the particle
+    pub beta: f64,
+    /// Time elapsed since the particle was released from the nucleus
+    /// *| in days*
+    pub age: f64,
+    /// Offset of the particle from the nucleus towards the east,
+    /// on the tangent plane of the sky *| in radians*
+    pub xi: f64,
+    /// Offset of the particle from the nucleus towards the north,
+    /// on the tangent plane of the sky *| in radians*
+    pub eta: f64,
+    /// Position angle of the particle, measured eastwards from the
+    /// north around the nucleus *| in radians*
+    pub pos_angl: f64,
+    /// Angular distance of the particle from the nucleus *| in radians*
+    pub dist: f64,
+}
+
+/**
+Computes a syndyne of the dust tail of a comet
+
+A syndyne is the locus of dust particles having the same ratio of
+radiation pressure to solar gravity (`beta`), that were released from
+the nucleus with zero relative velocity at different times in the
+past.
+
+# Returns
+
+* `syndyne`: The points of the syndyne, one for each value in `ages`
+
+# Arguments
+
+* `elements`: The comet's [Elements](./struct.Elements.html)
+* `JD`      : Julian (Ephemeris) day of observation
+* `beta`    : Ratio of the radiation pressure to the solar gravity
+              acting on the particles. *range: 0.0 to 1.0*
+* `ages`    : Times elapsed since the release of the particles
+              *| in days*
+**/
+pub fn syndyne(elements: &Elements, JD: f64, beta: f64, ages: &[f64]) -> Vec<TailPoint> {
+
+    let obs = Observation::new(elements, JD);
+
+    ages.iter()
+        .map(|&age| obs.tail_point(elements, beta, age))
+        .collect()
+
+}
+
+/**
+Computes a synchrone of the dust tail of a comet
+
+A synchrone is the locus of dust particles released from the nucleus
+with zero relative velocity at the same time in the past, having
+different ratios of radiation pressure to solar gravity.
+
+# Returns
+
+* `synchrone`: The points of the synchrone, one for each value in
+               `betas`
+
+# Arguments
+
+* `elements`: The comet's [Elements](./struct.Elements.html)
+* `JD`      : Julian (Ephemeris) day of observation
+* `age`     : Time elapsed since the release of the particles
+              *| in days*
+* `betas`   : Ratios of the radiation pressure to the solar gravity
+              acting on the particles. *range: 0.0 to 1.0*
+**/
+pub fn synchrone(elements: &Elements, JD: f64, age: f64, betas: &[f64]) -> Vec<TailPoint> {
+
+    let obs = Observation::new(elements, JD);
+
+    betas.iter()
+        .map(|&beta| obs.tail_point(elements, beta, age))
+        .collect()
+
+}
+
+/**
+Computes the uncertainty ellipse of the geocentric position of a
+comet or asteroid on the sky
+
+The covariance of the elements is propagated linearly, with the
+partial derivatives of the position on the tangent plane of the sky
+taken by central differences. The ellipse is valid as long as the
+uncertainty of the position is small compared to the curvature of
+the orbit, which is the case for orbits from a few nights of
+observations propagated over weeks, but not for much longer arcs of
+extrapolation.
+
+# Returns
+
+* `ellipse`: The [SkyEllipse](./struct.SkyEllipse.html), at one sigma
+
+# Arguments
+
+* `elements`: The body's [UncertainElements](./struct.UncertainElements.html)
+* `JD`      : Julian (Ephemeris) day of observation
+**/
+pub fn sky_uncertainty(elements: &UncertainElements, JD: f64) -> SkyEllipse {
+
+    let nominal = Observation::new(&elements.elements, JD);
+
+    let steps = [1e-7 * elements.elements.q, 1e-7, 1e-7, 1e-7, 1e-7, 1e-5];
+    let mut jacobian = [[0.0; 6]; 2];
+
+    for (k, &h) in steps.iter().enumerate() {
+        let offset = |sign: f64| {
+            let mut el = elements.elements;
+            match k {
+                0 => el.q += sign*h,
+                1 => el.e += sign*h,
+                2 => el.i += sign*h,
+                3 => el.sigma += sign*h,
+                4 => el.w += sign*h,
+                _ => el.T += sign*h,
+            }
+            let obs = Observation::new(&el, JD);
+            tangent_plane(obs.asc0, obs.dec0, nominal.asc0, nominal.dec0)
+        };
+        let (xi_plus, eta_plus) = offset(1.0);
+        let (xi_minus, eta_minus) = offset(-1.0);
+        jacobian[0][k] = (xi_plus - xi_minus) / (2.0*h);
+        jacobian[1][k] = (eta_plus - eta_minus) / (2.0*h);
+    }
+
+    // covariance of the offsets towards the east and the north
+    let mut sky = [[0.0; 2]; 2];
+    for m in 0..2 {
+        for n in 0..2 {
+            for j in 0..6 {
+                for k in 0..6 {
+                    sky[m][n] += jacobian[m][j] * elements.covar[j][k] * jacobian[n][k];
+                }
+            }
+        }
+    }
+
+    let mn = (sky[0][0] + sky[1][1]) / 2.0;
+    let diff = (sky[0][0] - sky[1][1]) / 2.0;
+    let rad = (diff*diff + sky[0][1]*sky[0][1]).sqrt();
+    let phi = (2.0 * sky[0][1]).atan2(sky[0][0] - sky[1][1]) / 2.0;
+
+    SkyEllipse {
+        asc          : nominal.asc0,
+        dec          : nominal.dec0,
+        semimaj_axis : (mn + rad).sqrt(),
+        semimin_axis : (mn - rad).max(0.0).sqrt(),
+        pos_angl     : angle::limit_to_two_PI(std::f64::consts::FRAC_PI_2 - phi) % std::f64::consts::PI
+    }
+
+}
+
+/**
+Computes the heliocentric rectangular position and velocity of a
+comet, referred to the ecliptic and mean equinox of J2000.0
+
+# Returns
+
+`((x, y, z), (vx, vy, vz))`
+
+* `x`, `y`, `z`   : Heliocentric position of the comet *| in AU*
+* `vx`, `vy`, `vz`: Heliocentric velocity of the comet *| in AU per day*
+
+# Arguments
+
+* `elements`: The comet's [Elements](./struct.Elements.html)
+* `JD`      : Julian (Ephemeris) day
+**/
+pub fn heliocent_rect_state(elements: &Elements, JD: f64) -> ((f64, f64, f64), (f64, f64, f64)) {
+
+    let mu = consts::GAUSS_GRAV * consts::GAUSS_GRAV;
+
+    let (sin_w, cos_w) = elements.w.sin_cos();
+    let (sin_s, cos_s) = elements.sigma.sin_cos();
+    let (sin_i, cos_i) = elements.i.sin_cos();
+
+    let P = (
+        cos_w*cos_s - sin_w*sin_s*cos_i,
+        cos_w*sin_s + sin_w*cos_s*cos_i,
+        sin_w*sin_i
+    );
+    let Q = (
+       -sin_w*cos_s - cos_w*sin_s*cos_i,
+       -sin_w*sin_s + cos_w*cos_s*cos_i,
+        cos_w*sin_i
+    );
+
+    let q = elements.q;
+    let v = (mu * (1.0 + elements.e) / q).sqrt();
+
+    universal_propagate (
+        (q*P.0, q*P.1, q*P.2),
+        (v*Q.0, v*Q.1, v*Q.2),
+        JD - elements.T,
+        mu
+    )
+
+}
+
+/**
+Computes the orbital elements of a comet from it's heliocentric
+rectangular position and velocity
+
+This is the inverse of
+[heliocent_rect_state()](./fn.heliocent_rect_state.html), and works
+for elliptic, parabolic and hyperbolic orbits alike. For an orbit in
+the plane of the ecliptic, whose ascending node is undefined, `sigma`
+is zero and `w` is the longitude of the perihelion.
+
+# Returns
+
+* `elements`: The comet's [Elements](./struct.Elements.html), referred
+              to the same ecliptic and equinox as the position and
+              velocity
+
+# Arguments
+
+* `pos`: Heliocentric rectangular position `(x, y, z)` *| in AU*
+* `vel`: Heliocentric rectangular velocity `(vx, vy, vz)`
+         *| in AU per day*
+* `JD` : Julian (Ephemeris) day of the position and velocity
+**/
+pub fn elements_frm_heliocent_rect_state (
+
+    pos : (f64, f64, f64),
+    vel : (f64, f64, f64),
+    JD  : f64
+
+) -> Elements {
+
+    let mu = consts::GAUSS_GRAV * consts::GAUSS_GRAV;
+    let k = consts::GAUSS_GRAV;
+
+    let r = dist(pos);
+    let h = cross(pos, vel);
+    let h_n = dist(h);
+    let rv = pos.0*vel.0 + pos.1*vel.1 + pos.2*vel.2;
+
+    let (vh_x, vh_y, vh_z) = cross(vel, h);
+    let ecc_vec = (
+        vh_x/mu - pos.0/r,
+        vh_y/mu - pos.1/r,
+        vh_z/mu - pos.2/r
+    );
+    let e = dist(ecc_vec);
+
+    let h_xy = (h.0*h.0 + h.1*h.1).sqrt();
+    let i = h_xy.atan2(h.2);
+    let sigma = if h_xy > 1e-12 * h_n { angle::limit_to_two_PI(h.0.atan2(-h.1)) } else { 0.0 };
+
+    // the argument of the perihelion from the directions of the node
+    // and of the point of the orbit 90 degrees past it, which stay
+    // defined as the inclination goes to zero
+    let node = (sigma.cos(), sigma.sin(), 0.0);
+    let past_node = cross((h.0/h_n, h.1/h_n, h.2/h_n), node);
+    let w = angle::limit_to_two_PI(
+        (ecc_vec.0*past_node.0 + ecc_vec.1*past_node.1 + ecc_vec.2*past_node.2)
+            .atan2(ecc_vec.0*node.0 + ecc_vec.1*node.1)
+    );
+
+    let p = h_n*h_n / mu;
+    let v = (rv * h_n / (mu * r)).atan2(p/r - 1.0);
+    let q = p / (1.0 + e);
+
+    let dt = if e < 1.0 {
+        let a = q / (1.0 - e);
+        let E = 2.0 * ((1.0 - e).sqrt() * (v/2.0).tan()).atan2((1.0 + e).sqrt());
+        (E - e*E.sin()) * a.powf(1.5) / k
+    } else if e > 1.0 {
+        let a = q / (e - 1.0);
+        let H = 2.0 * (((e - 1.0)/(e + 1.0)).sqrt() * (v/2.0).tan()).atanh();
+        (e*H.sinh() - H) * a.powf(1.5) / k
+    } else {
+        let D = (v/2.0).tan();
+        (2.0*q*q*q).sqrt() * (D + D*D*D/3.0) / k
+    };
+
+    Elements {
+        q,
+        e,
+        i,
+        sigma,
+        w,
+        T: JD - dt
+    }
+
+}
+
+struct Observation {
+    t: f64,
+    earth: (f64, f64, f64),
+    asc0: f64,
+    dec0: f64,
+    oblq: f64,
+}
+
+impl Observation {
+
+    fn new(elements: &Elements, JD: f64) -> Observation {
+
+        let (L, B, R) = planet::heliocent_coords(&planet::Planet::Earth, JD);
+        let (L, B) = precess::precess_ecl_coords(L, B, JD, J2000);
+        let earth = (R*B.cos()*L.cos(), R*B.cos()*L.sin(), R*B.sin());
+
+        let oblq = ecliptic::mn_oblq_IAU(J2000);
+
+        let (pos, _) = heliocent_rect_state(elements, JD);
+        let light_time = 0.0057755183 * dist(sub(pos, earth));
+        let t = JD - light_time;
+
+        let (pos, _) = heliocent_rect_state(elements, t);
+        let (asc0, dec0) = eq_coords(sub(pos, earth), oblq);
+
+        Observation {
+            t,
+            earth,
+            asc0,
+            dec0,
+            oblq
+        }
+
+    }
+
+    fn tail_point(&self, elements: &Elements, beta: f64, age: f64) -> TailPoint {
+
+        let mu = consts::GAUSS_GRAV * consts::GAUSS_GRAV * (1.0 - beta);
+
+        let (pos, vel) = heliocent_rect_state(elements, self.t - age);
+        let (pos, _) = universal_propagate(pos, vel, age, mu);
+
+        let (asc, dec) = eq_coords(sub(pos, self.earth), self.oblq);
+
+        let D =
+            dec.sin() * self.dec0.sin()
+          + dec.cos() * self.dec0.cos() * (asc - self.asc0).cos();
+        let (xi, eta) = tangent_plane(asc, dec, self.asc0, self.dec0);
+
+        TailPoint {
+            beta,
+            age,
+            xi,
+            eta,
+            pos_angl : xi.atan2(eta),
+            dist     : D.min(1.0).acos()
+        }
+
+    }
+
+}
+
+const J2000: f64 = 2451545.0;
+
+// Gnomonic projection of a point onto the tangent plane of the sky
+// at (asc0, dec0), towards the east and the north
+fn tangent_plane(asc: f64, dec: f64, asc0: f64, dec0: f64) -> (f64, f64) {
+
+    let d_asc = asc - asc0;
+    let D =
+        dec.sin() * dec0.sin()
+      + dec.cos() * dec0.cos() * d_asc.cos();
+
+    (
+        dec.cos() * d_asc.sin() / D,
+        (dec.sin()*dec0.cos() - dec.cos()*dec0.sin()*d_asc.cos()) / D
+    )
+
+}
+
+#[inline]
+fn sub(a: (f64, f64, f64), b: (f64, f64, f64)) -> (f64, f64, f64) {
+
+    (a.0 - b.0, a.1 - b.1, a.2 - b.2)
+
+}
+
+#[inline]
+fn cross(a: (f64, f64, f64), b: (f64, f64, f64)) -> (f64, f64, f64) {
+
+    (a.1*b.2 - a.2*b.1, a.2*b.0 - a.0*b.2, a.0*b.1 - a.1*b.0)
+
+}
+
+#[inline]
+fn dist(a: (f64, f64, f64)) -> f64 {
+
+    (a.0*a.0 + a.1*a.1 + a.2*a.2).sqrt()
+
+}
+
+fn eq_coords(ecl: (f64, f64, f64), oblq: f64) -> (f64, f64) {
+
+    let (x, y, z) = ecl;
+    let u = y*oblq.cos() - z*oblq.sin();
+    let v = y*oblq.sin() + z*oblq.cos();
+
+    (u.atan2(x), v.atan2((x*x + u*u).sqrt()))
+
+}
+
+// Stumpff functions c2(z) and c3(z)
+fn stumpff(z: f64) -> (f64, f64) {
+
+    if z > 1e-8 {
+        let s = z.sqrt();
+        ((1.0 - s.cos())/z, (s - s.sin())/(s*z))
+    } else if z < -1e-8 {
+        let s = (-z).sqrt();
+        ((s.cosh() - 1.0)/(-z), (s.sinh() - s)/(s*(-z)))
+    } else {
+        (0.5 - z/24.0, 1.0/6.0 - z/120.0)
+    }
+
+}
+
+// Propagates a two-body state by dt days using universal variables,
+// for any kind of conic section
+fn universal_propagate (
+
+    r0 : (f64, f64, f64),
+    v0 : (f64, f64, f64),
+    dt : f64,
+    mu : f64
+
+) -> ((f64, f64, f64), (f64, f64, f64)) {
+
+    if mu <= 0.0 || dt == 0.0 {
+        // no attraction at all; uniform rectilinear motion
+        let dt = if mu <= 0.0 { dt } else { 0.0 };
+        return ((r0.0 + v0.0*dt, r0.1 + v0.1*dt, r0.2 + v0.2*dt), v0);
+    }
+
+    let sqrt_mu = mu.sqrt();
+    let r0_n = dist(r0);
+    let v0_sq = v0.0*v0.0 + v0.1*v0.1 + v0.2*v0.2;
+    let rv = (r0.0*v0.0 + r0.1*v0.1 + r0.2*v0.2) / sqrt_mu;
+    let alpha = 2.0/r0_n - v0_sq/mu;
+
+    let mut chi = sqrt_mu * dt * if alpha.abs() > 1e-8 { alpha.abs() } else { 1.0/r0_n };
+
+    for _ in 0..100 {
+        let z = alpha * chi * chi;
+        let (c2, c3) = stumpff(z);
+        let chi2 = chi * chi;
+
+        let f =
+            rv * chi2 * c2
+          + (1.0 - alpha*r0_n) * chi2 * chi * c3
+          + r0_n * chi
+          - sqrt_mu * dt;
+        let df =
+            rv * chi * (1.0 - z*c3)
+          + (1.0 - alpha*r0_n) * chi2 * c2
+          + r0_n;
+
+        let delta = f / df;
+        chi -= delta;
+        if delta.abs() < 1e-12 { break; }
+    }
+
+    let z = alpha * chi * chi;
+    let (c2, c3) = stumpff(z);
+    let chi2 = chi * chi;
+
+    let f = 1.0 - chi2*c2/r0_n;
+    let g = dt - chi2*chi*c3/sqrt_mu;
+    let r = (
+        f*r0.0 + g*v0.0,
+        f*r0.1 + g*v0.1,
+        f*r0.2 + g*v0.2
+    );
+    let r_n = dist(r);
+
+    let f_dot = sqrt_mu * chi * (z*c3 - 1.0) / (r_n * r0_n);
+    let g_dot = 1.0 - chi2*c2/r_n;
+    let v = (
+        f_dot*r0.0 + g_dot*v0.0,
+        f_dot*r0.1 + g_dot*v0.1,
+        f_dot*r0.2 + g_dot*v0.2
+    );
+
+    (r, v)
+
+}
@@ -0,0 +1,285 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Compact low-precision theory of the planets and the Moon
+//!
+//! The positions are computed from mean orbital elements varying
+//! linearly with time, with the largest perturbations of Jupiter,
+//! Saturn, Uranus and the Moon added, after Paul Schlyter's *How to
+//! compute planetary positions*. The whole theory has about a
+//! hundred coefficients, against the tens of thousands of the
+//! VSOP87 and ELP-2000/82 series, and its functions take the same
+//! arguments as `planet::heliocent_coords()` and
+//! `lunar::geocent_ecl_pos()`, so that either can be used.
+//!
+//! The theory is intended for targets where the size of the program
+//! matters more than precision; a program that calls only these
+//! functions links none of the series. Within a few centuries of
+//! J2000.0, the accuracy is:
+//!
+//! * about `1` arcminute for the Sun and the inner planets
+//! * `1` to `2` arcminutes for the outer planets
+//! * `2` arcminutes, and `500` kilometers in distance, for the Moon
+
+use angle;
+use coords;
+use planet;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+// Mean orbital elements (N, i, w, a, e, M) at 2000 January 0.0 TT,
+// each as (value, rate per day), with angles in degrees. The
+// elements are referred to the mean equinox of the date.
+const ELEMENTS: [[(f64, f64); 6]; 8] = [
+    // Mercury
+    [(48.3313, 3.24587e-5), (7.0047, 5.0e-8), (29.1241, 1.01444e-5),
+     (0.387098, 0.0), (0.205635, 5.59e-10), (168.6562, 4.0923344368)],
+    // Venus
+    [(76.6799, 2.46590e-5), (3.3946, 2.75e-8), (54.8910, 1.38374e-5),
+     (0.723330, 0.0), (0.006773, -1.302e-9), (48.0052, 1.6021302244)],
+    // Earth, from the elements of the Sun's geocentric orbit
+    [(0.0, 0.0), (0.0, 0.0), (282.9404, 4.70935e-5),
+     (1.000000, 0.0), (0.016709, -1.151e-9), (356.0470, 0.9856002585)],
+    // Mars
+    [(49.5574, 2.11081e-5), (1.8497, -1.78e-8), (286.5016, 2.92961e-5),
+     (1.523688, 0.0), (0.093405, 2.516e-9), (18.6021, 0.5240207766)],
+    // Jupiter
+    [(100.4542, 2.76854e-5), (1.3030, -1.557e-7), (273.8777, 1.64505e-5),
+     (5.20256, 0.0), (0.048498, 4.469e-9), (19.8950, 0.0830853001)],
+    // Saturn
+    [(113.6634, 2.38980e-5), (2.4886, -1.081e-7), (339.3939, 2.97661e-5),
+     (9.55475, 0.0), (0.055546, -9.499e-9), (316.9670, 0.0334442282)],
+    // Uranus
+    [(74.0005, 1.3978e-5), (0.7733, 1.9e-8), (96.6612, 3.0565e-5),
+     (19.18171, -1.55e-8), (0.047318, 7.45e-9), (142.5905, 0.011725806)],
+    // Neptune
+    [(131.7806, 3.0173e-5), (1.7700, -2.55e-7), (272.8461, -6.027e-6),
+     (30.05826, 3.313e-8), (0.008606, 2.15e-9), (260.2471, 0.005995147)],
+];
+
+// Mean orbital elements of the Moon, with a in Earth radii
+const MOON_ELEMENTS: [(f64, f64); 6] = [
+    (125.1228, -0.0529538083), (5.1454, 0.0), (318.0634, 0.1643573223),
+    (60.2666, 0.0), (0.054900, 0.0), (115.3654, 13.0649929509)
+];
+
+// Terms (coefficient, arguments) of the perturbations, where the
+// argument of a term is the sum of the multipliers times the mean
+// anomalies of Jupiter, Saturn and Uranus, and a phase, in degrees
+const JUPITER_LONG: [(f64, [f64; 4]); 7] = [
+    (-0.332, [2.0, -5.0, 0.0, -67.6]),
+    (-0.056, [2.0, -2.0, 0.0, 21.0]),
+    ( 0.042, [3.0, -5.0, 0.0, 21.0]),
+    (-0.036, [1.0, -2.0, 0.0, 0.0]),
+    ( 0.022, [1.0, -1.0, 0.0, 90.0]),
+    ( 0.023, [2.0, -3.0, 0.0, 52.0]),
+    (-0.016, [1.0, -5.0, 0.0, -69.0]),
+];
+
+const SATURN_LONG: [(f64, [f64; 4]); 5] = [
+    ( 0.812, [2.0, -5.0, 0.0, -67.6]),
+    (-0.229, [2.0, -4.0, 0.0, 88.0]),
+    ( 0.119, [1.0, -2.0, 0.0, -3.0]),
+    ( 0.046, [2.0, -6.0, 0.0, -69.0]),
+    ( 0.014, [1.0, -3.0, 0.0, 32.0]),
+];
+
+const SATURN_LAT: [(f64, [f64; 4]); 2] = [
+    (-0.020, [2.0, -4.0, 0.0, 88.0]),
+    ( 0.018, [2.0, -6.0, 0.0, -49.0]),
+];
+
+const URANUS_LONG: [(f64, [f64; 4]); 3] = [
+    ( 0.040, [0.0, 1.0, -2.0, 6.0]),
+    ( 0.035, [0.0, 1.0, -3.0, 33.0]),
+    (-0.015, [1.0, 0.0, -1.0, 20.0]),
+];
+
+// Terms (coefficient, arguments) of the perturbations of the Moon,
+// where the argument of a term is the sum of the multipliers times
+// the mean anomalies of the Sun and the Moon, the mean elongation D
+// and the argument of latitude F, and a phase, in degrees
+const MOON_LONG: [(f64, [f64; 5]); 12] = [
+    (-1.274, [0.0, 1.0, -2.0, 0.0, 0.0]),
+    ( 0.658, [0.0, 0.0, 2.0, 0.0, 0.0]),
+    (-0.186, [1.0, 0.0, 0.0, 0.0, 0.0]),
+    (-0.059, [0.0, 2.0, -2.0, 0.0, 0.0]),
+    (-0.057, [1.0, 1.0, -2.0, 0.0, 0.0]),
+    ( 0.053, [0.0, 1.0, 2.0, 0.0, 0.0]),
+    ( 0.046, [-1.0, 0.0, 2.0, 0.0, 0.0]),
+    ( 0.041, [-1.0, 1.0, 0.0, 0.0, 0.0]),
+    (-0.035, [0.0, 0.0, 1.0, 0.0, 0.0]),
+    (-0.031, [1.0, 1.0, 0.0, 0.0, 0.0]),
+    (-0.015, [0.0, 0.0, -2.0, 2.0, 0.0]),
+    ( 0.011, [0.0, 1.0, -4.0, 0.0, 0.0]),
+];
+
+const MOON_LAT: [(f64, [f64; 5]); 5] = [
+    (-0.173, [0.0, 0.0, -2.0, 1.0, 0.0]),
+    (-0.055, [0.0, 1.0, -2.0, -1.0, 0.0]),
+    (-0.046, [0.0, 1.0, -2.0, 1.0, 0.0]),
+    ( 0.033, [0.0, 0.0, 2.0, 1.0, 0.0]),
+    ( 0.017, [0.0, 2.0, 0.0, 1.0, 0.0]),
+];
+
+// In Earth radii, with the phases turning sines into cosines
+const MOON_DIST: [(f64, [f64; 5]); 2] = [
+    (-0.58, [0.0, 1.0, -2.0, 0.0, 90.0]),
+    (-0.46, [0.0, 0.0, 2.0, 0.0, 90.0]),
+];
+
+// Julian day of 2000 January 0.0, the epoch of the elements
+const EPOCH: f64 = 2451543.5;
+
+/**
+Computes a planet's heliocentric coordinates with the compact theory
+
+# Returns
+
+`(heliocent_long, heliocent_lat, rad_vec)`
+
+* `heliocent_long`: Heliocentric longitude *| in radians*
+* `heliocent_lat` : Heliocentric latitude *| in radians*
+* `rad_vec`       : Radius vector *| in AU*
+
+The coordinates are referred to the ecliptic and mean equinox of
+the date, as those of `planet::heliocent_coords()`.
+
+# Arguments
+
+* `planet`: Any variant of [Planet](../planet/enum.Planet.html)
+* `JD`    : Julian (Ephemeris) day
+**/
+pub fn heliocent_coords(planet: &planet::Planet, JD: f64) -> (f64, f64, f64) {
+
+    let d = JD - EPOCH;
+    let index = match *planet {
+        planet::Planet::Mercury => 0,
+        planet::Planet::Venus   => 1,
+        planet::Planet::Earth   => 2,
+        planet::Planet::Mars    => 3,
+        planet::Planet::Jupiter => 4,
+        planet::Planet::Saturn  => 5,
+        planet::Planet::Uranus  => 6,
+        planet::Planet::Neptune => 7,
+    };
+
+    let (mut long, mut lat, r) = ecl_coords(&ELEMENTS[index], d);
+
+    let M = |i: usize| (ELEMENTS[i][5].0 + ELEMENTS[i][5].1*d).to_radians();
+    let anoms = [M(4), M(5), M(6), 0.0];
+    match *planet {
+        planet::Planet::Earth => {
+            // the elements are of the Sun's geocentric orbit
+            long += std::f64::consts::PI;
+            lat = -lat;
+        },
+        planet::Planet::Jupiter => long += sum_terms(&JUPITER_LONG, &anoms),
+        planet::Planet::Saturn  => {
+            long += sum_terms(&SATURN_LONG, &anoms);
+            lat += sum_terms(&SATURN_LAT, &anoms);
+        },
+        planet::Planet::Uranus  => long += sum_terms(&URANUS_LONG, &anoms),
+        _ => {}
+    }
+
+    (angle::limit_to_two_PI(long), lat, r)
+}
+
+/**
+Computes the geocentric ecliptic position of the Moon with the
+compact theory
+
+# Returns
+
+`(moon_ecl_point, rad_vec)`
+
+* `moon_ecl_point`: Ecliptic point of the Moon *| in radians*
+* `rad_vec`       : Moon-Earth distance *| in kilometers*
+
+The coordinates are referred to the mean equinox of the date.
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn moon_geocent_ecl_pos(JD: f64) -> (coords::EclPoint, f64) {
+
+    let d = JD - EPOCH;
+    let (long, lat, r) = ecl_coords(&MOON_ELEMENTS, d);
+
+    let mn = |e: &[(f64, f64); 6], i: usize| (e[i].0 + e[i].1*d).to_radians();
+    let sun_M = mn(&ELEMENTS[2], 5);
+    let sun_L = sun_M + mn(&ELEMENTS[2], 2);
+    let moon_M = mn(&MOON_ELEMENTS, 5);
+    let moon_L = moon_M + mn(&MOON_ELEMENTS, 2) + mn(&MOON_ELEMENTS, 0);
+    let D = moon_L - sun_L;
+    let F = moon_L - mn(&MOON_ELEMENTS, 0);
+    let args = [sun_M, moon_M, D, F, 0.0];
+
+    let long = long + sum_terms(&MOON_LONG, &args);
+    let lat = lat + sum_terms(&MOON_LAT, &args);
+    let r = r + sum_terms(&MOON_DIST, &args).to_degrees();
+
+    (
+        coords::EclPoint {
+            long: angle::limit_to_two_PI(long),
+            lat
+        },
+        r * planet::earth::eq_rad()
+    )
+}
+
+// Ecliptic coordinates from the mean elements at d days from the
+// epoch, in the unit of the semimajor axis
+fn ecl_coords(elements: &[(f64, f64); 6], d: f64) -> (f64, f64, f64) {
+
+    let el = |i: usize| elements[i].0 + elements[i].1*d;
+    let (N, i, w) = (el(0).to_radians(), el(1).to_radians(), el(2).to_radians());
+    let (a, e, M) = (el(3), el(4), el(5).to_radians());
+
+    let mut E = M + e*M.sin()*(1.0 + e*M.cos());
+    for _ in 0..3 {
+        E -= (E - e*E.sin() - M) / (1.0 - e*E.cos());
+    }
+
+    let x = a * (E.cos() - e);
+    let y = a * (1.0 - e*e).sqrt() * E.sin();
+    let r = x.hypot(y);
+    let u = y.atan2(x) + w;
+
+    let (sin_u, cos_u) = u.sin_cos();
+    let long = N + (sin_u * i.cos()).atan2(cos_u);
+    let lat = (sin_u * i.sin()).asin();
+
+    (long, lat, r)
+}
+
+// Sum of periodic terms, in radians from coefficients in degrees
+fn sum_terms<A>(terms: &[(f64, A)], args: &[f64]) -> f64 where A: AsRef<[f64]> {
+    terms.iter().fold(0.0, |s, &(c, ref m)| {
+        let m = m.as_ref();
+        let n = m.len() - 1;
+        let arg = (0..n).fold(m[n].to_radians(), |a, k| a + m[k]*args[k]);
+        s + c.to_radians() * arg.sin()
+    })
+}
@@ -36,3 +36,12 @@ pub const EARTH_MOON_MASS_RATIO: f64 = 81.3007;
 
 /// Sun-Earth mass ratio
 pub const SUN_EARTH_MASS_RATIO: f64 = 332946.0;
+
+/// Astronomical unit *| in meters*
+pub const ASTRONOMICAL_UNIT: f64 = 149597870700.0;
+
+/// Heliocentric gravitational constant *| in meters^3 per second^2*
+pub const SUN_GRAV_PARAM: f64 = 1.32712440018e20;
+
+/// Nominal radius of the Sun *| in meters*
+pub const SUN_RADIUS: f64 = 6.957e8;
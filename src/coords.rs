@@ -21,11 +21,29 @@ THE SOFTWARE.
 */
 
 //! Transform between coordinate systems
+//!
+//! The points `EqPoint`, `EclPoint`, `HzPoint` and `GalPoint` name
+//! the two angles of a position, and the functions of `coords`,
+//! `planet`, `sun` and `star` giving a position on the sky return
+//! them. Their older forms returning tuples are deprecated. A point
+//! doesn't carry its equinox or frame, which the function returning
+//! it documents.
 
 use angle;
+use atmos;
+use error;
+use float::Float;
+use misc;
+use nutation;
+use planet;
+use precess;
+use time;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 /// Represents a point on the geographical surface of the Earth
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GeographPoint {
     /// Geographical longitude
     pub long: f64,
@@ -34,6 +52,17 @@ pub struct GeographPoint {
 }
 
 impl GeographPoint {
+    /// A geographic point, checking that the latitude is within ±90
+    /// degrees
+    pub fn try_new(long: f64, lat: f64) -> Result<GeographPoint, error::Error> {
+        if !long.is_finite() {
+            return Err(error::Error::NotFinite);
+        }
+        error::check_lat(lat)?;
+
+        Ok(GeographPoint { long, lat })
+    }
+
     pub fn anglr_sepr(&self, other_point: &GeographPoint) -> f64 {
         angle::anglr_sepr (
             self.long, self.lat,
@@ -43,7 +72,8 @@ impl GeographPoint {
 }
 
 /// Represents a point in the equatorial coordinate system
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EqPoint {
     /// Right ascension
     pub asc: f64,
@@ -61,7 +91,8 @@ impl EqPoint {
 }
 
 /// Represents a point in the ecliptic coordinate system
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EclPoint {
     /// Ecliptic longitude
     pub long: f64,
@@ -78,6 +109,181 @@ impl EclPoint {
     }
 }
 
+/// Represents a point in the local horizontal coordinate system
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HzPoint {
+    /// Azimuth, measured westwards from the South
+    pub az: f64,
+    /// Altitude
+    pub alt: f64,
+}
+
+impl HzPoint {
+    pub fn anglr_sepr(&self, other_point: &HzPoint) -> f64 {
+        angle::anglr_sepr (
+            self.az, self.alt,
+            other_point.az, other_point.alt
+        )
+    }
+}
+
+/// Represents a point in the galactic coordinate system
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GalPoint {
+    /// Galactic longitude
+    pub long: f64,
+    /// Galactic latitude
+    pub lat: f64,
+}
+
+impl GalPoint {
+    pub fn anglr_sepr(&self, other_point: &GalPoint) -> f64 {
+        angle::anglr_sepr (
+            self.long, self.lat,
+            other_point.long, other_point.lat
+        )
+    }
+
+    /// The equatorial point, referred to the equinox of B1950.0
+    pub fn to_eq(&self) -> EqPoint {
+        EqPoint {
+            asc: asc_frm_gal(self.long, self.lat),
+            dec: dec_frm_gal(self.long, self.lat)
+        }
+    }
+
+    /// The equatorial point, referred to the ICRS or the FK5 system at
+    /// J2000.0, by the rotation of the Hipparcos catalogue
+    pub fn to_eq_J2000(&self) -> EqPoint {
+        let (asc, dec) = rotate(&precess::transpose(&EQ_J2000_TO_GAL), self.long, self.lat);
+        EqPoint { asc, dec }
+    }
+
+    /// The equatorial point, referred to the FK4 system at B1950.0, by
+    /// the rotation exact for the defining pole
+    pub fn to_eq_B1950(&self) -> EqPoint {
+        let (asc, dec) = rotate(&precess::transpose(&EQ_B1950_TO_GAL), self.long, self.lat);
+        EqPoint { asc, dec }
+    }
+}
+
+impl EqPoint {
+    /// The ecliptic point, for the obliquity of the ecliptic
+    /// `oblq_eclip` *| in radians*
+    pub fn to_ecl(&self, oblq_eclip: f64) -> EclPoint {
+        EclPoint {
+            long: ecl_long_frm_eq(self.asc, self.dec, oblq_eclip),
+            lat: ecl_lat_frm_eq(self.asc, self.dec, oblq_eclip)
+        }
+    }
+
+    /// The local horizontal point, for the sidereal time at Greenwich
+    /// `green_sidreal` *| in radians* and the observer's geographic
+    /// point
+    pub fn to_hz(&self, green_sidreal: f64, observer: &GeographPoint) -> HzPoint {
+        let H = hr_angl_frm_observer_long(green_sidreal, observer.long, self.asc);
+        HzPoint {
+            az: az_frm_eq(H, self.dec, observer.lat),
+            alt: alt_frm_eq(H, self.dec, observer.lat)
+        }
+    }
+
+    /// The local horizontal point as seen through the atmosphere, for
+    /// the sidereal time at Greenwich `green_sidreal` *| in radians*,
+    /// the observer's geographic point, and the local `(pressure, temp)`
+    /// *| in millibars and kelvins*, or `None` to neglect refraction
+    pub fn to_apprnt_hz(&self, green_sidreal: f64, observer: &GeographPoint, weather: Option<(f64, f64)>) -> HzPoint {
+        let hz_point = self.to_hz(green_sidreal, observer);
+        HzPoint {
+            az: hz_point.az,
+            alt: hz_point.alt + refrac_term(hz_point.alt, weather, atmos::refrac_frm_true_alt)
+        }
+    }
+
+    /// The parallactic angle *| in radians*, for the sidereal time at
+    /// Greenwich `green_sidreal` *| in radians* and the observer's
+    /// geographic point
+    pub fn parallactic_angl(&self, green_sidreal: f64, observer: &GeographPoint) -> f64 {
+        let H = hr_angl_frm_observer_long(green_sidreal, observer.long, self.asc);
+        misc::parllc_angl(observer.lat, H, self.dec)
+    }
+
+    /// The galactic point, for an equatorial point referred to the
+    /// equinox of B1950.0
+    pub fn to_gal(&self) -> GalPoint {
+        GalPoint {
+            long: gal_long_frm_eq(self.asc, self.dec),
+            lat: gal_lat_frm_eq(self.asc, self.dec)
+        }
+    }
+
+    /// The galactic point, for an equatorial point referred to the
+    /// ICRS or the FK5 system at J2000.0, by the rotation of the
+    /// Hipparcos catalogue
+    pub fn to_gal_J2000(&self) -> GalPoint {
+        let (long, lat) = rotate(&EQ_J2000_TO_GAL, self.asc, self.dec);
+        GalPoint { long, lat }
+    }
+
+    /// The galactic point, for an equatorial point referred to the FK4
+    /// system at B1950.0, by the rotation exact for the defining pole
+    pub fn to_gal_B1950(&self) -> GalPoint {
+        let (long, lat) = rotate(&EQ_B1950_TO_GAL, self.asc, self.dec);
+        GalPoint { long, lat }
+    }
+}
+
+impl EclPoint {
+    /// The equatorial point, for the obliquity of the ecliptic
+    /// `oblq_eclip` *| in radians*
+    pub fn to_eq(&self, oblq_eclip: f64) -> EqPoint {
+        EqPoint {
+            asc: asc_frm_ecl(self.long, self.lat, oblq_eclip),
+            dec: dec_frm_ecl(self.long, self.lat, oblq_eclip)
+        }
+    }
+}
+
+impl HzPoint {
+    /// The equatorial point, for the sidereal time at Greenwich
+    /// `green_sidreal` *| in radians* and the observer's geographic
+    /// point
+    pub fn to_eq(&self, green_sidreal: f64, observer: &GeographPoint) -> EqPoint {
+        let H = hr_angl_frm_hz(self.az, self.alt, observer.lat);
+        EqPoint {
+            asc: angle::limit_to_two_PI(green_sidreal - observer.long - H),
+            dec: dec_frm_hz(self.az, self.alt, observer.lat)
+        }
+    }
+
+    /// The equatorial point, for a horizontal point seen through the
+    /// atmosphere, the sidereal time at Greenwich `green_sidreal`
+    /// *| in radians*, the observer's geographic point, and the local
+    /// `(pressure, temp)` *| in millibars and kelvins*, or `None` to
+    /// neglect refraction
+    pub fn apprnt_to_eq(&self, green_sidreal: f64, observer: &GeographPoint, weather: Option<(f64, f64)>) -> EqPoint {
+        HzPoint {
+            az: self.az,
+            alt: self.alt - refrac_term(self.alt, weather, atmos::refrac_frm_apprnt_alt)
+        }.to_eq(green_sidreal, observer)
+    }
+}
+
+// The refraction term for an altitude, scaled for the local pressure
+// and temperature. Below a degree under the horizon, where the
+// formulae break down, no refraction is applied.
+fn refrac_term(alt: f64, weather: Option<(f64, f64)>, refrac: fn(f64) -> f64) -> f64 {
+
+    match weather {
+        Some((pressure, temp)) if alt > -1_f64.to_radians() =>
+            refrac(alt) * atmos::refrac_by_pressr(pressure) * atmos::refrac_by_temp(temp),
+        _ => 0.0
+    }
+
+}
+
 /**
 Computes the hour angle from geographical longitude and Greenwhich
 sidereal time
@@ -93,7 +299,7 @@ sidereal time
 * `asc`: Right ascension *| in radians*
 **/
 #[inline]
-pub fn hr_angl_frm_observer_long(green_sidreal: f64, observer_long: f64, asc: f64) -> f64 {
+pub fn hr_angl_frm_observer_long<T: Float>(green_sidreal: T, observer_long: T, asc: T) -> T {
 
     green_sidreal - observer_long - asc
 
@@ -113,7 +319,7 @@ ascension
 * `asc`: Right ascension *| in radians*
 **/
 #[inline]
-pub fn hr_angl_frm_loc_sidr(local_sidreal: f64, asc: f64) -> f64 {
+pub fn hr_angl_frm_loc_sidr<T: Float>(local_sidreal: T, asc: T) -> T {
 
     local_sidreal - asc
 
@@ -134,7 +340,7 @@ Computes the ecliptic longitude from equatorial coordinates
                     nutation, then *true* obliquity. If not, then
                     *mean* obliquity. *| in radians*
 **/
-pub fn ecl_long_frm_eq(asc: f64, dec: f64, oblq_eclip: f64,) -> f64 {
+pub fn ecl_long_frm_eq<T: Float>(asc: T, dec: T, oblq_eclip: T) -> T {
 
     (
         asc.sin() * oblq_eclip.cos()
@@ -158,7 +364,7 @@ Computes the ecliptic latitude from equatorial coordinates
                     nutation, then *true* obliquity. If not, then
                     *mean* obliquity. *| in radians*
 **/
-pub fn ecl_lat_frm_eq(asc: f64, dec: f64, oblq_eclip: f64) -> f64 {
+pub fn ecl_lat_frm_eq<T: Float>(asc: T, dec: T, oblq_eclip: T) -> T {
 
     (
         dec.sin() * oblq_eclip.cos()
@@ -208,7 +414,7 @@ Computes the right ascension from ecliptic coordinates
                     for nutation, then *true* obliquity. If not, then
                     *mean* obliquity. *| in radians*
 **/
-pub fn asc_frm_ecl(ecl_long: f64, ecl_lat: f64, oblq_eclip: f64) -> f64 {
+pub fn asc_frm_ecl<T: Float>(ecl_long: T, ecl_lat: T, oblq_eclip: T) -> T {
 
     (
         ecl_long.sin() * oblq_eclip.cos()
@@ -232,7 +438,7 @@ Computes the declination from ecliptic coordinates
                     for nutation, then *true* obliquity. If not, then
                     *mean* obliquity. *| in radians*
 **/
-pub fn dec_frm_ecl(ecl_long: f64, ecl_lat: f64, oblq_eclip: f64) -> f64 {
+pub fn dec_frm_ecl<T: Float>(ecl_long: T, ecl_lat: T, oblq_eclip: T) -> T {
 
     (
         ecl_lat.sin() * oblq_eclip.cos()
@@ -280,7 +486,7 @@ Computes the azimuth from equatorial coordinates
 * `dec`: Declination *| in radians*
 * `observer_lat`: Observer's geographical latitude *| in radians*
 **/
-pub fn az_frm_eq(hour_angle: f64, dec: f64, observer_lat: f64) -> f64 {
+pub fn az_frm_eq<T: Float>(hour_angle: T, dec: T, observer_lat: T) -> T {
 
     hour_angle.sin().atan2 (
         hour_angle.cos()  * observer_lat.sin()
@@ -302,7 +508,7 @@ Computes the altitude from equatorial coordinates
 * `dec`: Declination *| in radians*
 * `observer_lat`: Observer's geographical latitude *| in radians*
 **/
-pub fn alt_frm_eq(hour_angle: f64, dec: f64, observer_lat: f64) -> f64 {
+pub fn alt_frm_eq<T: Float>(hour_angle: T, dec: T, observer_lat: T) -> T {
 
     (
         observer_lat.sin() * dec.sin()
@@ -348,7 +554,7 @@ Computes the hour angle from local horizontal coordinates
 * `alt`: Altitude *| in radians*
 * `observer_lat`: Observer's geographical latitude *| in radians*
 **/
-pub fn hr_angl_frm_hz(az: f64, alt: f64, observer_lat: f64) -> f64 {
+pub fn hr_angl_frm_hz<T: Float>(az: T, alt: T, observer_lat: T) -> T {
 
     az.sin().atan2 (
         az.cos() * observer_lat.sin()
@@ -370,15 +576,62 @@ Computes the declination from local horizontal coordinates
 * `alt`: Altitude *| in radians*
 * `observer_lat`: Observer's geographical latitude *| in radians*
 **/
-pub fn dec_frm_hz(az: f64, alt: f64, observer_lat: f64) -> f64 {
+pub fn dec_frm_hz<T: Float>(az: T, alt: T, observer_lat: T) -> T {
 
     (
         observer_lat.sin() * alt.sin()
-      - observer_lat.cos() * az.cos() * az.cos()
+      - observer_lat.cos() * alt.cos() * az.cos()
     ).asin()
 
 }
 
+/// Computes the parallactic angle of a celestial body, as
+/// `misc::parllc_angl()` does with its arguments in another order
+#[deprecated(note = "use `misc::parllc_angl()`")]
+pub fn parallactic_angl<T: Float>(hour_angle: T, dec: T, observer_lat: T) -> T {
+
+    T::from_f64(misc::parllc_angl(observer_lat.to_f64(), hour_angle.to_f64(), dec.to_f64()))
+
+}
+
+/// Computes the parallactic angle of a celestial body on the horizon,
+/// as `misc::parllc_angl_on_hz()` does with its arguments in another
+/// order, and `NaN` for a body that never reaches the horizon
+#[deprecated(note = "use `misc::parllc_angl_on_hz()`")]
+pub fn parallactic_angl_on_hz<T: Float>(dec: T, observer_lat: T) -> T {
+
+    T::from_f64(misc::parllc_angl_on_hz(observer_lat.to_f64(), dec.to_f64()))
+
+}
+
+/**
+Computes the rate of change of the parallactic angle of a celestial
+body
+
+This is the rate at which the field of view of an alt-azimuth mount
+rotates, which grows without bound towards the zenith.
+
+# Returns
+
+* `rate`: Rate of change of the parallactic angle *| in radians per
+          second*
+
+# Arguments
+
+* `hour_angle`  : Hour angle *| in radians*
+* `dec`         : Declination *| in radians*
+* `observer_lat`: Observer's geographical latitude *| in radians*
+**/
+pub fn parallactic_angl_rate(hour_angle: f64, dec: f64, observer_lat: f64) -> f64 {
+
+    let n = hour_angle.sin();
+    let d = observer_lat.tan()*dec.cos() - dec.sin()*hour_angle.cos();
+
+    planet::earth::rot_angular_velocity()
+  * (observer_lat.tan()*dec.cos()*hour_angle.cos() - dec.sin()) / (n*n + d*d)
+
+}
+
 /**
 Computes the galactic longitude from equatorial coordinates
 
@@ -530,3 +783,769 @@ macro_rules! eq_frm_gal {
          astro::coords::dec_frm_gal($gal_long, $gal_lat))
     }};
 }
+
+/**
+Computes galactic coordinates from equatorial coordinates referred
+to the J2000.0 system
+
+The rotation is the one defined for the Hipparcos catalogue, with
+the north galactic pole at right ascension 192.85948° and
+declination 27.12825° in the ICRS.
+
+# Returns
+
+`(gal_long, gal_lat)`
+
+* `gal_long`: Galactic longitude *| in radians*
+* `gal_lat` : Galactic latitude *| in radians*
+
+# Arguments
+
+* `asc`: Right ascension, referred to the ICRS or the FK5 system
+         at J2000.0 *| in radians*
+* `dec`: Declination, referred to the ICRS or the FK5 system
+         at J2000.0 *| in radians*
+**/
+#[deprecated(note = "use `EqPoint::to_gal_J2000()`, which returns a point")]
+pub fn gal_frm_eq_J2000(asc: f64, dec: f64) -> (f64, f64) {
+
+    let point = EqPoint { asc, dec }.to_gal_J2000();
+
+    (point.long, point.lat)
+
+}
+
+/**
+Computes equatorial coordinates referred to the J2000.0 system from
+galactic coordinates
+
+This is the inverse of `gal_frm_eq_J2000()`.
+
+# Returns
+
+`(asc, dec)`
+
+* `asc`: Right ascension, referred to the ICRS or the FK5 system
+         at J2000.0 *| in radians*
+* `dec`: Declination, referred to the ICRS or the FK5 system
+         at J2000.0 *| in radians*
+
+# Arguments
+
+* `gal_long`: Galactic longitude *| in radians*
+* `gal_lat` : Galactic latitude *| in radians*
+**/
+#[deprecated(note = "use `GalPoint::to_eq_J2000()`, which returns a point")]
+pub fn eq_J2000_frm_gal(gal_long: f64, gal_lat: f64) -> (f64, f64) {
+
+    let point = GalPoint { long: gal_long, lat: gal_lat }.to_eq_J2000();
+
+    (point.asc, point.dec)
+
+}
+
+/**
+Computes galactic coordinates from equatorial coordinates referred
+to the B1950.0 system
+
+Unlike `gal_long_frm_eq()` and `gal_lat_frm_eq()`, the rotation
+is exact for the defining pole at right ascension 192.25°,
+declination 27.4° and galactic longitude 123° of the north
+celestial pole.
+
+# Returns
+
+`(gal_long, gal_lat)`
+
+* `gal_long`: Galactic longitude *| in radians*
+* `gal_lat` : Galactic latitude *| in radians*
+
+# Arguments
+
+* `asc`: Right ascension, referred to the FK4 system at
+         B1950.0 *| in radians*
+* `dec`: Declination, referred to the FK4 system at
+         B1950.0 *| in radians*
+**/
+#[deprecated(note = "use `EqPoint::to_gal_B1950()`, which returns a point")]
+pub fn gal_frm_eq_B1950(asc: f64, dec: f64) -> (f64, f64) {
+
+    let point = EqPoint { asc, dec }.to_gal_B1950();
+
+    (point.long, point.lat)
+
+}
+
+/**
+Computes equatorial coordinates referred to the B1950.0 system from
+galactic coordinates
+
+This is the inverse of `gal_frm_eq_B1950()`.
+
+# Returns
+
+`(asc, dec)`
+
+* `asc`: Right ascension, referred to the FK4 system at
+         B1950.0 *| in radians*
+* `dec`: Declination, referred to the FK4 system at
+         B1950.0 *| in radians*
+
+# Arguments
+
+* `gal_long`: Galactic longitude *| in radians*
+* `gal_lat` : Galactic latitude *| in radians*
+**/
+#[deprecated(note = "use `GalPoint::to_eq_B1950()`, which returns a point")]
+pub fn eq_B1950_frm_gal(gal_long: f64, gal_lat: f64) -> (f64, f64) {
+
+    let point = GalPoint { long: gal_long, lat: gal_lat }.to_eq_B1950();
+
+    (point.asc, point.dec)
+
+}
+
+/**
+Computes supergalactic coordinates from galactic coordinates
+
+The north supergalactic pole is at galactic longitude 47.37° and
+latitude 6.32°, and the origin of supergalactic longitude at
+galactic longitude 137.37° on the galactic equator.
+
+# Returns
+
+`(sgal_long, sgal_lat)`
+
+* `sgal_long`: Supergalactic longitude *| in radians*
+* `sgal_lat` : Supergalactic latitude *| in radians*
+
+# Arguments
+
+* `gal_long`: Galactic longitude *| in radians*
+* `gal_lat` : Galactic latitude *| in radians*
+**/
+pub fn sgal_frm_gal(gal_long: f64, gal_lat: f64) -> (f64, f64) {
+
+    rotate(&GAL_TO_SGAL, gal_long, gal_lat)
+
+}
+
+/**
+Computes galactic coordinates from supergalactic coordinates
+
+This is the inverse of `sgal_frm_gal()`.
+
+# Returns
+
+`(gal_long, gal_lat)`
+
+* `gal_long`: Galactic longitude *| in radians*
+* `gal_lat` : Galactic latitude *| in radians*
+
+# Arguments
+
+* `sgal_long`: Supergalactic longitude *| in radians*
+* `sgal_lat` : Supergalactic latitude *| in radians*
+**/
+pub fn gal_frm_sgal(sgal_long: f64, sgal_lat: f64) -> (f64, f64) {
+
+    rotate(&precess::transpose(&GAL_TO_SGAL), sgal_long, sgal_lat)
+
+}
+
+// Matrix taking a vector from the ICRS, or the FK5 system at J2000,
+// to galactic coordinates
+pub(crate) const EQ_J2000_TO_GAL: [[f64; 3]; 3] = [
+    [-0.0548755604162154, -0.873437090234885,  -0.4838350155487132],
+    [ 0.4941094278755837, -0.4448296299600112,  0.746982244497219 ],
+    [-0.8676661490190047, -0.1980763734312015,  0.4559837761750669]
+];
+
+// Matrix taking a vector from the FK4 system at B1950 to galactic
+// coordinates
+const EQ_B1950_TO_GAL: [[f64; 3]; 3] = [
+    [-0.0669887394151508, -0.8727557658519927, -0.4835389146321842],
+    [ 0.4927284660753235, -0.4503469580199614,  0.7445846332830311],
+    [-0.8676008111514348, -0.1883746017229203,  0.4601997847838517]
+];
+
+// Matrix taking a vector from galactic to supergalactic coordinates
+const GAL_TO_SGAL: [[f64; 3]; 3] = [
+    [-0.7357425748043749,  0.6772612964138943,  0.0               ],
+    [-0.0745537783652337, -0.0809914713069767,  0.9939225903997749],
+    [ 0.6731453021092076,  0.7312711658169645,  0.1100812622247821]
+];
+
+fn rotate(m: &[[f64; 3]; 3], a1: f64, a2: f64) -> (f64, f64) {
+
+    precess::sph_frm_vec(&precess::mat_vec_mul(m, &precess::vec_frm_sph(a1, a2)))
+
+}
+
+/**
+Represents a rotation between two coordinate frames, as a 3×3
+matrix
+
+The matrix takes the rectangular components of a vector in the
+old frame to its components in the new frame. Rotations compose
+with `then()`, so a chain of frame changes can be folded into a
+single matrix and applied to many points at the cost of one
+matrix product each.
+**/
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Matrix3 {
+    /// Rows of the matrix
+    pub rows: [[f64; 3]; 3],
+}
+
+impl Matrix3 {
+    /// The identity rotation
+    pub fn identity() -> Matrix3 {
+        Matrix3 { rows: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]] }
+    }
+
+    /// Rotation of the frame through an angle `a` *| in radians*
+    /// about its x axis
+    pub fn rot_x(a: f64) -> Matrix3 {
+        Matrix3 { rows: precess::rot_x(a) }
+    }
+
+    /// Rotation of the frame through an angle `a` *| in radians*
+    /// about its y axis
+    pub fn rot_y(a: f64) -> Matrix3 {
+        Matrix3 { rows: precess::rot_y(a) }
+    }
+
+    /// Rotation of the frame through an angle `a` *| in radians*
+    /// about its z axis
+    pub fn rot_z(a: f64) -> Matrix3 {
+        Matrix3 { rows: precess::rot_z(a) }
+    }
+
+    /// Frame bias, from the ICRS to the mean equator and equinox of
+    /// J2000.0
+    pub fn bias() -> Matrix3 {
+        Matrix3 { rows: precess::bias_mat() }
+    }
+
+    /// Frame bias and precession, from the ICRS to the mean equator
+    /// and equinox of the Julian (Ephemeris) day `JD`
+    pub fn bias_precess(JD: f64) -> Matrix3 {
+        Matrix3 { rows: precess::bias_precess_mat(JD) }
+    }
+
+    /// Precession, from the mean equator and equinox of `JD1` to
+    /// that of `JD2` (Julian Ephemeris days)
+    pub fn precession(JD1: f64, JD2: f64) -> Matrix3 {
+        Matrix3 { rows: precess::matrix(JD1, JD2) }
+    }
+
+    /// Nutation, from the mean equator and equinox of the Julian
+    /// (Ephemeris) day `JD` to the true equator and equinox of
+    /// that day
+    pub fn nutation(JD: f64) -> Matrix3 {
+        let (_, _, _, eps) = precess::fw_angles(JD);
+        let (nut_in_long, nut_in_oblq) = nutation::nutation(JD);
+
+        Matrix3::rot_x(eps)
+            .then(&Matrix3::rot_z(-nut_in_long))
+            .then(&Matrix3::rot_x(-(eps + nut_in_oblq)))
+    }
+
+    /// Equatorial to ecliptic coordinates, for an obliquity of the
+    /// ecliptic `oblq_eclip` *| in radians*
+    pub fn ecl_frm_eq(oblq_eclip: f64) -> Matrix3 {
+        Matrix3::rot_x(oblq_eclip)
+    }
+
+    /// Ecliptic to equatorial coordinates, for an obliquity of the
+    /// ecliptic `oblq_eclip` *| in radians*
+    pub fn eq_frm_ecl(oblq_eclip: f64) -> Matrix3 {
+        Matrix3::rot_x(-oblq_eclip)
+    }
+
+    /// Equatorial coordinates in the ICRS, or the FK5 system at
+    /// J2000, to galactic coordinates
+    pub fn gal_frm_eq() -> Matrix3 {
+        Matrix3 { rows: EQ_J2000_TO_GAL }
+    }
+
+    /// Galactic coordinates to equatorial coordinates in the ICRS,
+    /// or the FK5 system at J2000
+    pub fn eq_frm_gal() -> Matrix3 {
+        Matrix3::gal_frm_eq().transpose()
+    }
+
+    /// Galactic to supergalactic coordinates
+    pub fn sgal_frm_gal() -> Matrix3 {
+        Matrix3 { rows: GAL_TO_SGAL }
+    }
+
+    /// The rotation that applies `self` first and then `next`
+    pub fn then(&self, next: &Matrix3) -> Matrix3 {
+        Matrix3 { rows: precess::mat_mul(&next.rows, &self.rows) }
+    }
+
+    /// The inverse rotation
+    pub fn transpose(&self) -> Matrix3 {
+        Matrix3 { rows: precess::transpose(&self.rows) }
+    }
+
+    /// Rotates a vector of rectangular components
+    pub fn apply(&self, v: &[f64; 3]) -> [f64; 3] {
+        precess::mat_vec_mul(&self.rows, v)
+    }
+
+    /// Rotates a point given by two spherical angles *| in radians*,
+    /// such as `(asc, dec)` or `(long, lat)`
+    pub fn apply_sph(&self, a1: f64, a2: f64) -> (f64, f64) {
+        rotate(&self.rows, a1, a2)
+    }
+
+    /// Rotates many points given by two spherical angles *| in radians*
+    pub fn apply_sph_many(&self, points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+        points.iter().map(|&(a1, a2)| self.apply_sph(a1, a2)).collect()
+    }
+}
+
+/**
+Represents a rotation as a unit quaternion
+
+A quaternion holds the same rotation as a `Matrix3` in four
+numbers, stays orthonormal under repeated composition after
+`normalize()`, and can be interpolated smoothly with `slerp()`.
+The quaternion from `frm_matrix()` rotates vectors exactly as the
+matrix does.
+**/
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Quaternion {
+    /// Scalar part
+    pub w: f64,
+    /// Vector part, along the x axis
+    pub x: f64,
+    /// Vector part, along the y axis
+    pub y: f64,
+    /// Vector part, along the z axis
+    pub z: f64,
+}
+
+impl Quaternion {
+    /// The identity rotation
+    pub fn identity() -> Quaternion {
+        Quaternion { w: 1.0, x: 0.0, y: 0.0, z: 0.0 }
+    }
+
+    /// Rotation of a vector through an angle `angl` *| in radians*
+    /// about `axis`, counter-clockwise when looking down the axis
+    ///
+    /// A rotation of the frame through `angl` is the rotation of
+    /// vectors through `-angl`.
+    pub fn frm_axis_angl(axis: &[f64; 3], angl: f64) -> Quaternion {
+        let n = (axis[0]*axis[0] + axis[1]*axis[1] + axis[2]*axis[2]).sqrt();
+        let (s, c) = (angl / 2.0).sin_cos();
+
+        Quaternion { w: c, x: s * axis[0]/n, y: s * axis[1]/n, z: s * axis[2]/n }
+    }
+
+    /// The quaternion of a rotation matrix
+    pub fn frm_matrix(m: &Matrix3) -> Quaternion {
+        let r = &m.rows;
+        let tr = r[0][0] + r[1][1] + r[2][2];
+
+        let q = if tr > 0.0 {
+            let s = 2.0 * (1.0 + tr).sqrt();
+            Quaternion { w: s/4.0, x: (r[2][1] - r[1][2])/s, y: (r[0][2] - r[2][0])/s, z: (r[1][0] - r[0][1])/s }
+        } else if r[0][0] > r[1][1] && r[0][0] > r[2][2] {
+            let s = 2.0 * (1.0 + r[0][0] - r[1][1] - r[2][2]).sqrt();
+            Quaternion { w: (r[2][1] - r[1][2])/s, x: s/4.0, y: (r[0][1] + r[1][0])/s, z: (r[0][2] + r[2][0])/s }
+        } else if r[1][1] > r[2][2] {
+            let s = 2.0 * (1.0 + r[1][1] - r[0][0] - r[2][2]).sqrt();
+            Quaternion { w: (r[0][2] - r[2][0])/s, x: (r[0][1] + r[1][0])/s, y: s/4.0, z: (r[1][2] + r[2][1])/s }
+        } else {
+            let s = 2.0 * (1.0 + r[2][2] - r[0][0] - r[1][1]).sqrt();
+            Quaternion { w: (r[1][0] - r[0][1])/s, x: (r[0][2] + r[2][0])/s, y: (r[1][2] + r[2][1])/s, z: s/4.0 }
+        };
+
+        q.normalize()
+    }
+
+    /// The rotation matrix of the quaternion
+    pub fn to_matrix(&self) -> Matrix3 {
+        let (w, x, y, z) = (self.w, self.x, self.y, self.z);
+
+        Matrix3 { rows: [
+            [1.0 - 2.0*(y*y + z*z), 2.0*(x*y - z*w),       2.0*(x*z + y*w)      ],
+            [2.0*(x*y + z*w),       1.0 - 2.0*(x*x + z*z), 2.0*(y*z - x*w)      ],
+            [2.0*(x*z - y*w),       2.0*(y*z + x*w),       1.0 - 2.0*(x*x + y*y)]
+        ]}
+    }
+
+    /// The quaternion scaled to unit length
+    pub fn normalize(&self) -> Quaternion {
+        let n = self.dot(self).sqrt();
+
+        Quaternion { w: self.w/n, x: self.x/n, y: self.y/n, z: self.z/n }
+    }
+
+    /// The inverse rotation
+    pub fn conjugate(&self) -> Quaternion {
+        Quaternion { w: self.w, x: -self.x, y: -self.y, z: -self.z }
+    }
+
+    /// The rotation that applies `self` first and then `next`
+    pub fn then(&self, next: &Quaternion) -> Quaternion {
+        let (a, b) = (next, self);
+
+        Quaternion {
+            w: a.w*b.w - a.x*b.x - a.y*b.y - a.z*b.z,
+            x: a.w*b.x + a.x*b.w + a.y*b.z - a.z*b.y,
+            y: a.w*b.y - a.x*b.z + a.y*b.w + a.z*b.x,
+            z: a.w*b.z + a.x*b.y - a.y*b.x + a.z*b.w,
+        }
+    }
+
+    /// Rotates a vector of rectangular components
+    pub fn apply(&self, v: &[f64; 3]) -> [f64; 3] {
+        let p = Quaternion { w: 0.0, x: v[0], y: v[1], z: v[2] };
+        let r = self.conjugate().then(&p).then(self);
+
+        [r.x, r.y, r.z]
+    }
+
+    /// Rotates a point given by two spherical angles *| in radians*
+    pub fn apply_sph(&self, a1: f64, a2: f64) -> (f64, f64) {
+        precess::sph_frm_vec(&self.apply(&precess::vec_frm_sph(a1, a2)))
+    }
+
+    /**
+    Interpolates between two rotations along the shortest arc
+
+    # Arguments
+
+    * `other`: Rotation at `f = 1`
+    * `f`    : Interpolation factor, `0` giving `self` and `1`
+               giving `other`
+    **/
+    pub fn slerp(&self, other: &Quaternion, f: f64) -> Quaternion {
+        let mut d = self.dot(other);
+        let mut o = *other;
+        if d < 0.0 {
+            d = -d;
+            o = Quaternion { w: -o.w, x: -o.x, y: -o.y, z: -o.z };
+        }
+
+        let (s, t) = if d > 1.0 - 1e-12 {
+            (1.0 - f, f)
+        } else {
+            let theta = d.acos();
+            (((1.0 - f) * theta).sin() / theta.sin(), (f * theta).sin() / theta.sin())
+        };
+
+        Quaternion {
+            w: s*self.w + t*o.w,
+            x: s*self.x + t*o.x,
+            y: s*self.y + t*o.y,
+            z: s*self.z + t*o.z,
+        }.normalize()
+    }
+
+    fn dot(&self, other: &Quaternion) -> f64 {
+        self.w*other.w + self.x*other.x + self.y*other.y + self.z*other.z
+    }
+}
+
+/**
+Computes Heliocentric Earth Ecliptic (HEE) coordinates from
+heliocentric ecliptic coordinates
+
+The HEE frame has it's origin at the center of the Sun, it's x-axis
+directed towards the Earth and it's z-axis towards the north pole of
+the ecliptic of the date.
+
+# Returns
+
+`(x, y, z)`
+
+* `x`: The X coordinate *| in AU*
+* `y`: The Y coordinate *| in AU*
+* `z`: The Z coordinate *| in AU*
+
+# Arguments
+
+* `helio_long`: Heliocentric ecliptic longitude, referred to the mean
+                equinox of the date *| in radians*
+* `helio_lat` : Heliocentric ecliptic latitude *| in radians*
+* `rad_vec`   : Heliocentric radius vector *| in AU*
+* `JD`        : Julian (Ephemeris) day
+**/
+pub fn hee_frm_heliocent_ecl(helio_long: f64, helio_lat: f64, rad_vec: f64, JD: f64) -> (f64, f64, f64) {
+
+    let (earth_long, _, _) = planet::heliocent_coords(&planet::Planet::Earth, JD);
+    let long = helio_long - earth_long;
+
+    (
+        rad_vec * helio_lat.cos() * long.cos(),
+        rad_vec * helio_lat.cos() * long.sin(),
+        rad_vec * helio_lat.sin()
+    )
+
+}
+
+/**
+Computes Heliocentric Earth Equatorial (HEEQ) coordinates from
+heliocentric ecliptic coordinates
+
+The HEEQ frame has it's origin at the center of the Sun, it's z-axis
+directed along the Sun's axis of rotation, and it's x-axis towards the
+intersection of the solar equator with the central meridian as seen
+from the Earth. The corresponding spherical coordinates are the
+Stonyhurst heliographic coordinates.
+
+# Returns
+
+`(x, y, z)`
+
+* `x`: The X coordinate *| in AU*
+* `y`: The Y coordinate *| in AU*
+* `z`: The Z coordinate *| in AU*
+
+# Arguments
+
+* `helio_long`: Heliocentric ecliptic longitude, referred to the mean
+                equinox of the date *| in radians*
+* `helio_lat` : Heliocentric ecliptic latitude *| in radians*
+* `rad_vec`   : Heliocentric radius vector *| in AU*
+* `JD`        : Julian (Ephemeris) day
+**/
+pub fn heeq_frm_heliocent_ecl(helio_long: f64, helio_lat: f64, rad_vec: f64, JD: f64) -> (f64, f64, f64) {
+
+    let (earth_long, earth_lat, _) = planet::heliocent_coords(&planet::Planet::Earth, JD);
+    let (earth_node_long, _) = heliograph_frm_heliocent_ecl(earth_long, earth_lat, JD);
+    let (node_long, lat) = heliograph_frm_heliocent_ecl(helio_long, helio_lat, JD);
+    let long = node_long - earth_node_long;
+
+    (
+        rad_vec * lat.cos() * long.cos(),
+        rad_vec * lat.cos() * long.sin(),
+        rad_vec * lat.sin()
+    )
+
+}
+
+/**
+Computes Carrington heliographic coordinates from heliocentric
+ecliptic coordinates
+
+The Carrington frame rotates with the Sun, with a sidereal period of
+25.38 days. It's prime meridian is the one that passed through the
+ascending node of the solar equator on the ecliptic on 1853 Nov 9.
+
+# Returns
+
+`(carr_long, carr_lat)`
+
+* `carr_long`: Carrington longitude *| in radians*
+* `carr_lat` : Heliographic latitude *| in radians*
+
+# Arguments
+
+* `helio_long`: Heliocentric ecliptic longitude, referred to the mean
+                equinox of the date *| in radians*
+* `helio_lat` : Heliocentric ecliptic latitude *| in radians*
+* `JD`        : Julian (Ephemeris) day
+**/
+pub fn carrington_frm_heliocent_ecl(helio_long: f64, helio_lat: f64, JD: f64) -> (f64, f64) {
+
+    let (node_long, lat) = heliograph_frm_heliocent_ecl(helio_long, helio_lat, JD);
+    let theta = angle::limit_to_360((JD - 2398220.0) * 360.0/25.38).to_radians();
+
+    (angle::limit_to_two_PI(node_long - theta), lat)
+
+}
+
+// Computes heliographic coordinates with the longitude measured from
+// the ascending node of the solar equator on the ecliptic
+fn heliograph_frm_heliocent_ecl(helio_long: f64, helio_lat: f64, JD: f64) -> (f64, f64) {
+
+    let I = 7.25_f64.to_radians();
+    let K = (73.6667 + 1.3958333*(JD - 2396758.0)/36525.0).to_radians();
+
+    let x = helio_lat.cos() * (helio_long - K).cos();
+    let y = helio_lat.cos() * (helio_long - K).sin() * I.cos() + helio_lat.sin() * I.sin();
+    let z = helio_lat.sin() * I.cos() - helio_lat.cos() * (helio_long - K).sin() * I.sin();
+
+    (y.atan2(x), z.asin())
+
+}
+
+/// Represents a projection of the sphere onto a plane
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Projection {
+    /// Conformal, maps circles on the sphere to circles. Maps the whole
+    /// sphere except the point opposite the center of projection
+    Stereographic,
+    /// The sphere as seen from infinitely far away. Maps the hemisphere
+    /// around the center of projection
+    Orthographic,
+    /// Maps great circles to straight lines. Maps the open hemisphere
+    /// around the center of projection
+    Gnomonic,
+}
+
+/**
+Computes plane coordinates from a projection of the sphere
+
+The plane touches the sphere at the center of projection, and the
+plane coordinates are in units of the sphere's radius, so that the
+scale at the center of projection is 1 for all projections. The
+x-axis points towards increasing angle 1, which on the sky is to the
+east, and appears to the left on a chart seen from inside the sphere.
+The y-axis points towards increasing angle 2.
+
+# Returns
+
+* `Some((x, y))`: Plane coordinates of the point
+* `None`        : If the point can't be mapped by the projection
+
+# Arguments
+
+* `proj`: Projection to use
+* `a1`  : Angle 1 of the point *| in radians*
+* `a2`  : Angle 2 of the point *| in radians*
+* `c1`  : Angle 1 of the center of projection *| in radians*
+* `c2`  : Angle 2 of the center of projection *| in radians*
+
+Angle 1 may be right ascension, longitude or azimuth.
+Angle 2 may be declination, latitude or altitude.
+**/
+pub fn plane_frm_sky(proj: Projection, a1: f64, a2: f64, c1: f64, c2: f64) -> Option<(f64, f64)> {
+
+    let cos_c = c2.sin()*a2.sin() + c2.cos()*a2.cos()*(a1 - c1).cos();
+    let x = a2.cos() * (a1 - c1).sin();
+    let y = c2.cos()*a2.sin() - c2.sin()*a2.cos()*(a1 - c1).cos();
+
+    let k = match proj {
+        Projection::Stereographic => if cos_c > -1.0 + 1e-12 { 2.0 / (1.0 + cos_c) } else { return None },
+        Projection::Orthographic  => if cos_c >= 0.0 { 1.0 } else { return None },
+        Projection::Gnomonic      => if cos_c > 1e-12 { 1.0 / cos_c } else { return None },
+    };
+
+    Some((k * x, k * y))
+
+}
+
+/**
+Computes a point on the sphere from plane coordinates of a projection
+
+This function is the inverse of `plane_frm_sky()`.
+
+# Returns
+
+* `Some((a1, a2))`: Angles 1 and 2 of the point *| in radians*
+* `None`          : If the plane point lies outside the orthographic
+                    projection of the sphere
+
+# Arguments
+
+* `proj`: Projection to use
+* `x`   : X coordinate of the point on the plane
+* `y`   : Y coordinate of the point on the plane
+* `c1`  : Angle 1 of the center of projection *| in radians*
+* `c2`  : Angle 2 of the center of projection *| in radians*
+**/
+pub fn sky_frm_plane(proj: Projection, x: f64, y: f64, c1: f64, c2: f64) -> Option<(f64, f64)> {
+
+    let rho = x.hypot(y);
+    if rho == 0.0 {
+        return Some((c1, c2));
+    }
+
+    let c = match proj {
+        Projection::Stereographic => 2.0 * (rho / 2.0).atan(),
+        Projection::Orthographic  => if rho <= 1.0 { rho.asin() } else { return None },
+        Projection::Gnomonic      => rho.atan(),
+    };
+
+    let a2 = (c.cos()*c2.sin() + y*c.sin()*c2.cos()/rho).asin();
+    let a1 = c1 + (x * c.sin()).atan2(rho*c2.cos()*c.cos() - y*c2.sin()*c.sin());
+
+    Some((angle::limit_to_two_PI(a1), a2))
+
+}
+
+/**
+Samples points along the great circle arc between two points
+
+# Returns
+
+* `points`: `n` points `(angle 1, angle 2)` evenly spaced along the
+            shorter arc, including both end points *| in radians*
+
+# Arguments
+
+* `a1`: Angle 1 of the first point *| in radians*
+* `a2`: Angle 2 of the first point *| in radians*
+* `b1`: Angle 1 of the second point *| in radians*
+* `b2`: Angle 2 of the second point *| in radians*
+* `n` : Number of points to sample
+**/
+pub fn great_circle_path(a1: f64, a2: f64, b1: f64, b2: f64, n: usize) -> Vec<(f64, f64)> {
+
+    let p = (a2.cos()*a1.cos(), a2.cos()*a1.sin(), a2.sin());
+    let q = (b2.cos()*b1.cos(), b2.cos()*b1.sin(), b2.sin());
+    let d = angle::anglr_sepr(a1, a2, b1, b2);
+
+    (0..n).map(|i| {
+        let f = if n > 1 { (i as f64) / ((n - 1) as f64) } else { 0.0 };
+        let (s, t) = if d.sin() > 1e-12 {
+            (((1.0 - f) * d).sin() / d.sin(), (f * d).sin() / d.sin())
+        } else {
+            (1.0 - f, f)
+        };
+
+        let x = s*p.0 + t*q.0;
+        let y = s*p.1 + t*q.1;
+        let z = s*p.2 + t*q.2;
+
+        (angle::limit_to_two_PI(y.atan2(x)), z.atan2(x.hypot(y)))
+    }).collect()
+
+}
+
+/**
+Samples the diurnal path of a point on the sky in local horizontal
+coordinates
+
+# Returns
+
+* `path`: `n` points `(JD, az, alt)` evenly spaced in time, including
+          both end points
+
+  * `JD` : Julian (Universal) day of the point
+  * `az` : Azimuth, measured westwards from the south *| in radians*
+  * `alt`: Altitude *| in radians*
+
+# Arguments
+
+* `asc`     : Right ascension *| in radians*
+* `dec`     : Declination *| in radians*
+* `observer`: Observer's geographical position (longitude measured
+              positively westwards)
+* `JD_start`: Julian (Universal) day of the first point
+* `JD_end`  : Julian (Universal) day of the last point
+* `n`       : Number of points to sample
+**/
+pub fn hz_path(asc: f64, dec: f64, observer: &GeographPoint, JD_start: f64, JD_end: f64, n: usize) -> Vec<(f64, f64, f64)> {
+
+    (0..n).map(|i| {
+        let f = if n > 1 { (i as f64) / ((n - 1) as f64) } else { 0.0 };
+        let JD = JD_start + f*(JD_end - JD_start);
+        let hour_angle = hr_angl_frm_observer_long(time::mn_sidr(JD), observer.long, asc);
+
+        (JD, az_frm_eq(hour_angle, dec, observer.lat), alt_frm_eq(hour_angle, dec, observer.lat))
+    }).collect()
+
+}
@@ -0,0 +1,770 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Besselian elements of solar eclipses
+
+/*
+
+The Besselian elements describe the shadow of the Moon on the
+fundamental plane, which passes through the Earth's center
+perpendicular to the axis of the shadow. Lengths are in units of the
+Earth's equatorial radius.
+
+The elements are published as cubic polynomials in the time t, in
+hours from a reference instant t0 in Terrestrial Dynamical Time, eg:
+in the NASA eclipse bulletins and the eclipse pages of F. Espenak.
+
+As there, the hour angle μ is referred to the ephemeris meridian,
+which lies 1.002738 ΔT east of the Greenwich meridian. ΔT is
+therefore needed only to turn geographic longitudes into ephemeris
+longitudes when computing local circumstances.
+
+*/
+
+use aberr;
+use angle;
+use ecliptic;
+use error;
+use lunar;
+use coords;
+use nutation;
+use planet;
+use sun;
+use time;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Holds the polynomial Besselian elements of a solar eclipse
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Elements {
+    /// Julian (Ephemeris) day of the reference instant t0
+    pub JD0: f64,
+    /// ΔT = TT - UT at the eclipse *| in seconds*
+    pub delta_t: f64,
+    /// Coefficients of x *| in Earth radii per hour^n*
+    pub x: [f64; 4],
+    /// Coefficients of y *| in Earth radii per hour^n*
+    pub y: [f64; 4],
+    /// Coefficients of the declination d of the shadow axis
+    /// *| in radians per hour^n*
+    pub d: [f64; 4],
+    /// Coefficients of the radius l1 of the penumbra on the
+    /// fundamental plane *| in Earth radii per hour^n*
+    pub l1: [f64; 4],
+    /// Coefficients of the radius l2 of the umbra on the fundamental
+    /// plane, negative for a total eclipse *| in Earth radii per hour^n*
+    pub l2: [f64; 4],
+    /// Coefficients of the hour angle μ of the shadow axis on the
+    /// ephemeris meridian *| in radians per hour^n*
+    pub mu: [f64; 4],
+    /// Tangent of the half-angle f1 of the penumbral cone
+    pub tan_f1: f64,
+    /// Tangent of the half-angle f2 of the umbral cone
+    pub tan_f2: f64
+}
+
+/// Holds the values of the Besselian elements at an instant
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Values {
+    /// x coordinate of the shadow axis *| in Earth radii*
+    pub x: f64,
+    /// y coordinate of the shadow axis *| in Earth radii*
+    pub y: f64,
+    /// Declination of the shadow axis *| in radians*
+    pub d: f64,
+    /// Radius of the penumbra on the fundamental plane *| in Earth radii*
+    pub l1: f64,
+    /// Radius of the umbra on the fundamental plane *| in Earth radii*
+    pub l2: f64,
+    /// Hour angle of the shadow axis on the ephemeris meridian
+    /// *| in radians*
+    pub mu: f64
+}
+
+/**
+Evaluates polynomial Besselian elements at an instant
+
+# Returns
+
+* `values`: [Values](./struct.Values.html) of the elements
+
+# Arguments
+
+* `elements`: The [Elements](./struct.Elements.html)
+* `JD`      : Julian (Ephemeris) day
+**/
+pub fn values(elements: &Elements, JD: f64) -> Values {
+
+    let t = (JD - elements.JD0) * 24.0;
+    let eval = |c: &[f64; 4]| c[0] + t*(c[1] + t*(c[2] + t*c[3]));
+
+    Values {
+        x  : eval(&elements.x),
+        y  : eval(&elements.y),
+        d  : eval(&elements.d),
+        l1 : eval(&elements.l1),
+        l2 : eval(&elements.l2),
+        mu : angle::limit_to_two_PI(eval(&elements.mu))
+    }
+
+}
+
+/**
+Evaluates the hourly rates of change of polynomial Besselian elements
+at an instant
+
+# Returns
+
+* `rates`: [Values](./struct.Values.html) holding the rate of each
+           element *| per hour*
+
+# Arguments
+
+* `elements`: The [Elements](./struct.Elements.html)
+* `JD`      : Julian (Ephemeris) day
+**/
+pub fn rates(elements: &Elements, JD: f64) -> Values {
+
+    let t = (JD - elements.JD0) * 24.0;
+    let eval = |c: &[f64; 4]| c[1] + t*(2.0*c[2] + t*3.0*c[3]);
+
+    Values {
+        x  : eval(&elements.x),
+        y  : eval(&elements.y),
+        d  : eval(&elements.d),
+        l1 : eval(&elements.l1),
+        l2 : eval(&elements.l2),
+        mu : eval(&elements.mu)
+    }
+
+}
+
+/**
+Computes the longitude of the ephemeris meridian, on which the hour
+angle μ of Besselian elements is referred
+
+# Returns
+
+* `long`: Longitude of the ephemeris meridian, measured positively
+          westwards from the Greenwich meridian *| in radians*
+
+# Arguments
+
+* `delta_t`: ΔT = TT - UT *| in seconds*
+**/
+#[inline]
+pub fn ephem_meridian(delta_t: f64) -> f64 {
+
+    -1.002738 * delta_t * angle::TWO_PI/86400.0
+
+}
+
+/**
+Computes the coordinates of an observer on the fundamental plane
+
+# Returns
+
+`(xi, eta, zeta)`
+
+* `xi`  : Coordinate of the observer along the x-axis
+          *| in Earth radii*
+* `eta` : Coordinate of the observer along the y-axis
+          *| in Earth radii*
+* `zeta`: Coordinate of the observer along the shadow axis,
+          positive towards the Sun *| in Earth radii*
+
+# Arguments
+
+* `elements`      : The [Elements](./struct.Elements.html)
+* `JD`            : Julian (Ephemeris) day
+* `geograph_point`: Geographic point of the observer *| in radians*
+* `height`        : Observer's height above sea level *| in meters*
+**/
+pub fn observer_coords (
+
+    elements       : &Elements,
+    JD             : f64,
+    geograph_point : &coords::GeographPoint,
+    height         : f64
+
+) -> (f64, f64, f64) {
+
+    let v = values(elements, JD);
+    let (rho_sin_phi, rho_cos_phi) = planet::earth::rho_sin_cos_phi(geograph_point.lat, height);
+
+    let theta = v.mu - (geograph_point.long - ephem_meridian(elements.delta_t));
+    let (sin_d, cos_d) = v.d.sin_cos();
+    let (sin_t, cos_t) = theta.sin_cos();
+
+    (
+        rho_cos_phi * sin_t,
+        rho_sin_phi*cos_d - rho_cos_phi*cos_t*sin_d,
+        rho_sin_phi*sin_d + rho_cos_phi*cos_t*cos_d
+    )
+
+}
+
+/**
+Computes the Besselian elements of a solar eclipse at an instant,
+from the Sun and Moon of this library
+
+# Returns
+
+* `values`: [Values](./struct.Values.html) of the elements
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn instantaneous_values(JD: f64) -> Values {
+
+    let (sun, moon, sidr) = apprnt_rect_coords(JD);
+
+    let g = (sun.0 - moon.0, sun.1 - moon.1, sun.2 - moon.2);
+    let g_n = (g.0*g.0 + g.1*g.1 + g.2*g.2).sqrt();
+    let (tan_f1, tan_f2) = tan_f(g_n);
+
+    values_on_axis (
+        moon, g.1.atan2(g.0), (g.2 / g_n).asin(), sidr,
+        (tan_f1, K1*(1.0 + tan_f1*tan_f1).sqrt()),
+        (tan_f2, -K2*(1.0 + tan_f2*tan_f2).sqrt())
+    )
+
+}
+
+/**
+Computes the Besselian elements of a lunar occultation of a star at
+an instant, from the Moon of this library
+
+As the star is infinitely far, the shadow of the Moon is a cylinder,
+and both `l1` and `-l2` are the radius of the Moon.
+
+# Returns
+
+* `values`: [Values](./struct.Values.html) of the elements
+
+# Arguments
+
+* `JD`      : Julian (Ephemeris) day
+* `star_asc`: Apparent right ascension of the star *| in radians*
+* `star_dec`: Apparent declination of the star *| in radians*
+**/
+pub fn instantaneous_occult_values(JD: f64, star_asc: f64, star_dec: f64) -> Values {
+
+    let (_, moon, sidr) = apprnt_rect_coords(JD);
+
+    values_on_axis(moon, star_asc, star_dec, sidr, (0.0, K1), (0.0, -K1))
+
+}
+
+/**
+Computes the Besselian elements of a lunar occultation of a planet at
+an instant, from the Moon and the planets of this library
+
+The shadow of the Moon is the cone from the center of the planet
+through the Moon's limb, so the elements give the occultation of the
+center of the planet, and `l2` is `-l1`.
+
+# Returns
+
+* `values`: [Values](./struct.Values.html) of the elements
+
+# Arguments
+
+* `JD`    : Julian (Ephemeris) day
+* `planet`: Any variant of [Planet](../../planet/enum.Planet.html)
+            other than the Earth
+**/
+pub fn instantaneous_planet_occult_values(JD: f64, planet: &planet::Planet) -> Values {
+
+    let (_, moon, sidr) = apprnt_rect_coords(JD);
+    let body = planet_apprnt_rect_coords(JD, planet);
+
+    let g = (body.0 - moon.0, body.1 - moon.1, body.2 - moon.2);
+    let g_n = (g.0*g.0 + g.1*g.1 + g.2*g.2).sqrt();
+    let tan_f = K1 / g_n;
+    let l = K1 * (1.0 + tan_f*tan_f).sqrt();
+
+    values_on_axis(moon, g.1.atan2(g.0), (g.2 / g_n).asin(), sidr, (tan_f, l), (-tan_f, -l))
+
+}
+
+/**
+Computes the polynomial Besselian elements of a solar eclipse, from
+the Sun and Moon of this library
+
+The polynomials are least-squares cubics fitted to the instantaneous
+elements over three hours either side of `JD0`.
+
+# Returns
+
+* `elements`: The [Elements](./struct.Elements.html)
+
+# Arguments
+
+* `JD0`    : Julian (Ephemeris) day of the reference instant t0,
+             preferably the whole hour nearest the greatest eclipse
+* `delta_t`: ΔT = TT - UT *| in seconds*, recorded in the
+             elements
+**/
+pub fn elements(JD0: f64, delta_t: f64) -> Elements {
+
+    let (_, sun_dist) = sun::geocent_ecl_pos(JD0);
+    let (_, moon_dist) = lunar::geocent_ecl_pos(JD0);
+    let (tan_f1, tan_f2) = tan_f((sun_dist*149597870.7 - moon_dist) / EARTH_RADIUS);
+
+    fit(JD0, delta_t, tan_f1, tan_f2, instantaneous_values)
+
+}
+
+/**
+Computes the polynomial Besselian elements of a lunar occultation of
+a star, from the Moon of this library
+
+The polynomials are least-squares cubics fitted to the instantaneous
+elements over three hours either side of `JD0`. The half-angles of
+the shadow cones are zero.
+
+# Returns
+
+* `elements`: The [Elements](./struct.Elements.html)
+
+# Arguments
+
+* `JD0`     : Julian (Ephemeris) day of the reference instant t0,
+              preferably the whole hour nearest the conjunction
+              of the Moon and the star
+* `delta_t` : ΔT = TT - UT *| in seconds*, recorded in the
+              elements
+* `star_asc`: Apparent right ascension of the star *| in radians*
+* `star_dec`: Apparent declination of the star *| in radians*
+**/
+pub fn occult_elements(JD0: f64, delta_t: f64, star_asc: f64, star_dec: f64) -> Elements {
+
+    fit(JD0, delta_t, 0.0, 0.0, |JD| instantaneous_occult_values(JD, star_asc, star_dec))
+
+}
+
+/**
+Computes the polynomial Besselian elements of a lunar occultation of
+a planet, from the Moon and the planets of this library
+
+The polynomials are least-squares cubics fitted to the instantaneous
+elements over three hours either side of `JD0`.
+
+# Returns
+
+* `elements`: The [Elements](./struct.Elements.html)
+
+# Arguments
+
+* `JD0`    : Julian (Ephemeris) day of the reference instant t0,
+             preferably the whole hour nearest the conjunction
+             of the Moon and the planet
+* `delta_t`: ΔT = TT - UT *| in seconds*, recorded in the
+             elements
+* `planet` : Any variant of [Planet](../../planet/enum.Planet.html)
+             other than the Earth
+**/
+pub fn planet_occult_elements(JD0: f64, delta_t: f64, planet: &planet::Planet) -> Elements {
+
+    let (_, moon, _) = apprnt_rect_coords(JD0);
+    let body = planet_apprnt_rect_coords(JD0, planet);
+    let g = (body.0 - moon.0, body.1 - moon.1, body.2 - moon.2);
+    let tan_f = K1 / (g.0*g.0 + g.1*g.1 + g.2*g.2).sqrt();
+
+    fit(JD0, delta_t, tan_f, -tan_f, |JD| instantaneous_planet_occult_values(JD, planet))
+
+}
+
+/**
+Parses polynomial Besselian elements from text
+
+The text is expected in the layout of the NASA eclipse bulletins and
+the eclipse pages of F. Espenak, eg:
+
+```text
+Polynomial Besselian Elements for the Total Solar Eclipse of 2017 August 21
+
+                     t0 = 18.00 TDT
+                     ΔT = 70.3 s
+
+   n      x           y           d          l1          l2          μ
+   0  -0.1295710   0.4854160  11.8669600   0.5420930  -0.0040250  89.2454300
+   1   0.5406426  -0.1416400  -0.0136220   0.0001241   0.0001234  15.0039400
+   2  -0.0000294  -0.0000905  -0.0000020  -0.0000118  -0.0000117
+   3  -0.0000081   0.0000021
+
+Tan f1 = 0.0046222    Tan f2 = 0.0045992
+```
+
+The date of the eclipse is read from the first line mentioning a year,
+a month's name and a day, and `t0` and `ΔT` (or `Delta T`) from lines
+of the form `name = value`. The rows of coefficients start with the
+power `n` of t, and list the coefficients of x, y, d, l1, l2 and μ
+in that order, missing trailing coefficients being taken as zero.
+The angles d and μ are in degrees.
+
+# Returns
+
+* `Ok(elements)`: The [Elements](./struct.Elements.html), or
+* `Err(error)`  : `Error::InvalidFormat`, if the date, `t0`, `Tan f1`
+                  or `Tan f2`, or the coefficients for n = 0 and 1,
+                  can't be found
+
+# Arguments
+
+* `text`: The text to parse
+**/
+pub fn parse(text: &str) -> Result<Elements, error::Error> {
+
+    let mut date = None;
+    let mut t0 = None;
+    let mut delta_t = None;
+    let mut tan_f1 = None;
+    let mut tan_f2 = None;
+    let mut rows: [Option<Vec<f64>>; 4] = [None, None, None, None];
+
+    for line in text.lines() {
+        let lower = line.to_lowercase();
+
+        if date.is_none() {
+            date = date_in(line);
+        }
+        if t0.is_none() {
+            t0 = value_after(&lower, "t0");
+        }
+        if delta_t.is_none() {
+            delta_t = value_after(&lower, "δt").or_else(|| value_after(&lower, "delta t"));
+        }
+        if tan_f1.is_none() {
+            tan_f1 = value_after(&lower, "tan f1");
+        }
+        if tan_f2.is_none() {
+            tan_f2 = value_after(&lower, "tan f2");
+        }
+
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if tokens.len() < 3 || tokens.len() > 7 {
+            continue;
+        }
+        let n = match tokens[0].parse::<usize>() {
+            Ok(n) if n < 4 => n,
+            _ => continue
+        };
+        let coeffs: Result<Vec<f64>, _> = tokens[1..].iter().map(|s| s.parse::<f64>()).collect();
+        if let Ok(coeffs) = coeffs {
+            if rows[n].is_none() {
+                rows[n] = Some(coeffs);
+            }
+        }
+    }
+
+    let (year, month, day) = match date {
+        Some(date) => date,
+        None => return Err(error::Error::InvalidFormat)
+    };
+    let t0 = match t0 {
+        Some(t0) => t0,
+        None => return Err(error::Error::InvalidFormat)
+    };
+    let (tan_f1, tan_f2) = match (tan_f1, tan_f2) {
+        (Some(f1), Some(f2)) => (f1, f2),
+        _ => return Err(error::Error::InvalidFormat)
+    };
+    if rows[0].is_none() || rows[1].is_none() {
+        return Err(error::Error::InvalidFormat);
+    }
+
+    let mut columns = [[0.0; 4]; 6];
+    for (n, row) in rows.iter().enumerate() {
+        if let Some(ref row) = *row {
+            for (k, &c) in row.iter().enumerate() {
+                columns[k][n] = c;
+            }
+        }
+    }
+
+    let cal_type = if (year, month as u8, day) < (1582, 10, 15) {
+        time::CalType::Julian
+    } else {
+        time::CalType::Gregorian
+    };
+    let JD0 = time::julian_day(&time::Date {
+        year,
+        month,
+        decimal_day : (day as f64) + t0/24.0,
+        cal_type
+    });
+
+    let to_radians = |c: [f64; 4]| [
+        c[0].to_radians(), c[1].to_radians(), c[2].to_radians(), c[3].to_radians()
+    ];
+
+    Ok(Elements {
+        JD0,
+        delta_t : delta_t.unwrap_or(0.0),
+        x       : columns[0],
+        y       : columns[1],
+        d       : to_radians(columns[2]),
+        l1      : columns[3],
+        l2      : columns[4],
+        mu      : to_radians(columns[5]),
+        tan_f1,
+        tan_f2
+    })
+
+}
+
+// Equatorial radius of the Earth, in kilometers
+const EARTH_RADIUS: f64 = 6378.137;
+
+// Apparent geocentric equatorial rectangular coordinates of the Sun
+// and the Moon, in Earth radii, and the apparent sidereal time at
+// Greenwich for the Julian (Ephemeris) day, as if it was UT
+fn apprnt_rect_coords(JD: f64) -> ((f64, f64, f64), (f64, f64, f64), f64) {
+
+    let (nut_long, nut_oblq) = nutation::nutation(JD);
+    let oblq = ecliptic::mn_oblq_IAU(JD) + nut_oblq;
+    let (sin_e, cos_e) = oblq.sin_cos();
+
+    let rect = |long: f64, lat: f64, r: f64| {
+        let (x, y, z) = (lat.cos()*long.cos(), lat.cos()*long.sin(), lat.sin());
+        (r*x, r*(y*cos_e - z*sin_e), r*(y*sin_e + z*cos_e))
+    };
+
+    let (sun_point, sun_dist) = sun::geocent_ecl_pos(JD);
+    let sun_long = sun_point.long + nut_long - angle::deg_frm_dms(0, 0, 20.4898).to_radians()/sun_dist;
+    let sun = rect(sun_long, sun_point.lat, sun_dist * 149597870.7 / EARTH_RADIUS);
+
+    let (moon_point, moon_dist) = lunar::geocent_ecl_pos(JD);
+    let moon = rect(moon_point.long + nut_long, moon_point.lat, moon_dist / EARTH_RADIUS);
+
+    let sidr = time::apprnt_sidr(time::mn_sidr(JD), nut_long, oblq);
+
+    (sun, moon, sidr)
+
+}
+
+// Apparent geocentric equatorial rectangular coordinates of a planet,
+// in Earth radii
+fn planet_apprnt_rect_coords(JD: f64, planet: &planet::Planet) -> (f64, f64, f64) {
+
+    let (nut_long, nut_oblq) = nutation::nutation(JD);
+    let oblq = ecliptic::mn_oblq_IAU(JD) + nut_oblq;
+
+    let (point, dist) = planet::geocent_apprnt_ecl_coords(planet, JD);
+    let long = point.long + nut_long;
+    let eq_point = coords::EqPoint {
+        asc: coords::asc_frm_ecl(long, point.lat, oblq),
+        dec: coords::dec_frm_ecl(long, point.lat, oblq)
+    };
+    let (d_asc, d_dec) = aberr::stell_aberr_in_eq_coords(&eq_point, JD);
+    let (asc, dec) = (eq_point.asc + d_asc, eq_point.dec + d_dec);
+
+    let r = dist * 149597870.7 / EARTH_RADIUS;
+
+    (r*dec.cos()*asc.cos(), r*dec.cos()*asc.sin(), r*dec.sin())
+
+}
+
+// Besselian elements for a shadow axis of right ascension a and
+// declination d, with the (tan f, l on the Moon) of each cone
+fn values_on_axis (
+
+    moon : (f64, f64, f64),
+    a    : f64,
+    d    : f64,
+    sidr : f64,
+    pen  : (f64, f64),
+    umb  : (f64, f64)
+
+) -> Values {
+
+    let (sin_d, cos_d) = d.sin_cos();
+    let (sin_a, cos_a) = a.sin_cos();
+    let x = -moon.0*sin_a + moon.1*cos_a;
+    let y = -moon.0*cos_a*sin_d - moon.1*sin_a*sin_d + moon.2*cos_d;
+    let z = moon.0*cos_a*cos_d + moon.1*sin_a*cos_d + moon.2*sin_d;
+
+    Values {
+        x,
+        y,
+        d,
+        l1 : z*pen.0 + pen.1,
+        l2 : z*umb.0 + umb.1,
+        mu : angle::limit_to_two_PI(sidr - a)
+    }
+
+}
+
+// Fits cubic polynomials to instantaneous elements over three hours
+// either side of JD0
+fn fit<F>(JD0: f64, delta_t: f64, tan_f1: f64, tan_f2: f64, instantaneous: F) -> Elements
+    where F: Fn(f64) -> Values {
+
+    let n = 25;
+    let mut t = Vec::with_capacity(n);
+    let mut vals = Vec::with_capacity(n);
+
+    for i in 0..n {
+        let hours = -3.0 + 6.0*(i as f64)/((n - 1) as f64);
+        t.push(hours);
+        vals.push(instantaneous(JD0 + hours/24.0));
+    }
+
+    // unwrap μ, which passes through 2π once a day
+    let mut mu: Vec<f64> = vals.iter().map(|v| v.mu).collect();
+    for i in 1..n {
+        while mu[i] < mu[i - 1] { mu[i] += angle::TWO_PI; }
+    }
+
+    let fit_of = |f: &dyn Fn(&Values) -> f64| {
+        let y: Vec<f64> = vals.iter().map(f).collect();
+        cubic_fit(&t, &y)
+    };
+
+    let mut mu_fit = cubic_fit(&t, &mu);
+    mu_fit[0] = angle::limit_to_two_PI(mu_fit[0]);
+
+    Elements {
+        JD0,
+        delta_t,
+        x  : fit_of(&|v| v.x),
+        y  : fit_of(&|v| v.y),
+        d  : fit_of(&|v| v.d),
+        l1 : fit_of(&|v| v.l1),
+        l2 : fit_of(&|v| v.l2),
+        mu : mu_fit,
+        tan_f1,
+        tan_f2
+    }
+
+}
+
+// Radius of the Moon for the penumbral and the umbral cones, in Earth
+// radii
+const K1: f64 = 0.2725076;
+const K2: f64 = 0.272281;
+
+// Tangents of the half-angles of the penumbral and umbral cones, for a
+// Sun-Moon distance in Earth radii
+fn tan_f(sun_moon_dist: f64) -> (f64, f64) {
+
+    let sun_radius = 696000.0 / EARTH_RADIUS;
+    let f1 = ((sun_radius + K1) / sun_moon_dist).asin();
+    let f2 = ((sun_radius - K2) / sun_moon_dist).asin();
+
+    (f1.tan(), f2.tan())
+
+}
+
+// Least-squares fit of a cubic polynomial, returning it's coefficients
+// in increasing powers
+fn cubic_fit(t: &[f64], y: &[f64]) -> [f64; 4] {
+
+    let mut A = [[0.0; 5]; 4];
+    for (&ti, &yi) in t.iter().zip(y.iter()) {
+        let p = [1.0, ti, ti*ti, ti*ti*ti];
+        for r in 0..4 {
+            for c in 0..4 {
+                A[r][c] += p[r] * p[c];
+            }
+            A[r][4] += p[r] * yi;
+        }
+    }
+
+    // Gauss-Jordan elimination with partial pivoting
+    for c in 0..4 {
+        let pivot = (c..4).max_by(|&i, &j|
+            A[i][c].abs().partial_cmp(&A[j][c].abs()).unwrap()
+        ).unwrap();
+        A.swap(c, pivot);
+
+        let pivot_row = A[c];
+        for (r, row) in A.iter_mut().enumerate() {
+            if r != c {
+                let f = row[c] / pivot_row[c];
+                for (a, b) in row[c..].iter_mut().zip(pivot_row[c..].iter()) {
+                    *a -= f * b;
+                }
+            }
+        }
+    }
+
+    [A[0][4]/A[0][0], A[1][4]/A[1][1], A[2][4]/A[2][2], A[3][4]/A[3][3]]
+
+}
+
+// Finds the value of a "name = value" pair in a line
+fn value_after(line: &str, name: &str) -> Option<f64> {
+
+    let start = line.find(name)? + name.len();
+    let rest = line[start..].trim_start();
+    if !rest.starts_with('=') {
+        return None;
+    }
+
+    rest[1..].split_whitespace().next()?.parse::<f64>().ok()
+
+}
+
+// Finds a date of the form "2017 August 21" in a line
+fn date_in(line: &str) -> Option<(i16, time::Month, u8)> {
+
+    let tokens: Vec<&str> = line.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    for w in tokens.windows(3) {
+        let year = match w[0].parse::<i16>() { Ok(y) => y, Err(_) => continue };
+        let day = match w[2].parse::<u8>() { Ok(d) if (1..=31).contains(&d) => d, _ => continue };
+        let month = match month_frm_name(w[1]) { Some(m) => m, None => continue };
+
+        return Some((year, month, day));
+    }
+
+    None
+
+}
+
+fn month_frm_name(name: &str) -> Option<time::Month> {
+
+    let name = name.to_lowercase();
+    if name.len() < 3 {
+        return None;
+    }
+
+    let months = [
+        ("jan", time::Month::Jan), ("feb", time::Month::Feb), ("mar", time::Month::Mar),
+        ("apr", time::Month::Apr), ("may", time::Month::May), ("jun", time::Month::June),
+        ("jul", time::Month::July), ("aug", time::Month::Aug), ("sep", time::Month::Sept),
+        ("oct", time::Month::Oct), ("nov", time::Month::Nov), ("dec", time::Month::Dec)
+    ];
+
+    months.iter()
+        .find(|&&(prefix, _)| name.starts_with(prefix))
+        .map(|&(_, month)| month)
+
+}
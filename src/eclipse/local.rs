@@ -0,0 +1,243 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+//! Local circumstances of solar eclipses
+
+/*
+
+The circumstances for an observer follow from the Besselian elements
+as in the Explanatory Supplement to the Astronomical Almanac: on the
+fundamental plane, the observer sees a contact when their distance
+from the shadow axis equals the radius of the penumbra (first and
+fourth contacts) or of the umbra (second and third contacts) on the
+plane through the observer parallel to the fundamental plane.
+
+*/
+
+use angle;
+use coords;
+use std;
+use super::besselian;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Represents the kind of a solar eclipse seen by an observer
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LocalEclipseType {
+    /// The Moon covers only part of the Sun
+    Partial,
+    /// The Moon covers all of the Sun
+    Total,
+    /// The Moon lies within the Sun's disk, leaving a ring
+    Annular
+}
+
+/// Holds a contact of a solar eclipse for an observer
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Contact {
+    /// Julian (Ephemeris) day of the contact
+    pub JD: f64,
+    /// Position angle of the point of contact on the Sun's limb,
+    /// measured eastwards from the north *| in radians*
+    pub pos_angl: f64
+}
+
+/// Holds the circumstances of a solar eclipse for an observer
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LocalCircumstances {
+    /// Kind of eclipse seen by the observer
+    pub eclipse_type: LocalEclipseType,
+    /// First contact, the beginning of the partial phase
+    pub first: Contact,
+    /// Second contact, the beginning of totality or annularity, if
+    /// the observer is inside the path
+    pub second: Option<Contact>,
+    /// Julian (Ephemeris) day of the maximum of the eclipse
+    pub maximum: f64,
+    /// Third contact, the end of totality or annularity, if the
+    /// observer is inside the path
+    pub third: Option<Contact>,
+    /// Fourth contact, the end of the partial phase
+    pub fourth: Contact,
+    /// Fraction of the Sun's diameter covered by the Moon at the
+    /// maximum
+    pub magnitude: f64,
+    /// Fraction of the Sun's disk covered by the Moon at the maximum
+    pub obscuration: f64,
+    /// Duration of totality or annularity *| in seconds*, or zero
+    /// for a partial eclipse
+    pub duration: f64,
+    /// Altitude of the Sun at the maximum, ignoring refraction
+    /// *| in radians*
+    pub sun_alt: f64
+}
+
+/**
+Computes the circumstances of a solar eclipse for an observer
+
+# Returns
+
+* `circumstances`: The [LocalCircumstances](./struct.LocalCircumstances.html),
+                   or `None` if the observer is outside the penumbra,
+                   or if the Sun is below the observer's horizon at the
+                   maximum
+
+# Arguments
+
+* `elements`      : The Besselian [Elements](../besselian/struct.Elements.html)
+* `geograph_point`: Geographic point of the observer *| in radians*
+* `height`        : Observer's height above sea level *| in meters*
+**/
+pub fn local_circumstances (
+
+    elements       : &besselian::Elements,
+    geograph_point : &coords::GeographPoint,
+    height         : f64
+
+) -> Option<LocalCircumstances> {
+
+    let h = 1.0 / 1440.0;
+    let state = |JD: f64| {
+        let v = besselian::values(elements, JD);
+        let (xi, eta, zeta) = besselian::observer_coords(elements, JD, geograph_point, height);
+        (
+            v.x - xi, v.y - eta,
+            v.l1 - zeta*elements.tan_f1,
+            v.l2 - zeta*elements.tan_f2,
+            zeta
+        )
+    };
+    let motion = |JD: f64| {
+        let (u, v, _, _, _) = state(JD);
+        let (u1, v1, _, _, _) = state(JD + h);
+        (u, v, (u1 - u)/h, (v1 - v)/h)
+    };
+
+    // the maximum, at the closest approach to the shadow axis
+    let mut JD = elements.JD0;
+    for _ in 0..5 {
+        let (u, v, a, b) = motion(JD);
+        JD -= (u*a + v*b) / (a*a + b*b);
+    }
+    let maximum = JD;
+    let (u, v, L1, L2, zeta) = state(maximum);
+    let m = (u*u + v*v).sqrt();
+    if m >= L1 || zeta <= 0.0 {
+        return None;
+    }
+
+    // the contacts with the shadow of radius L(JD), from the linear
+    // motion relative to the observer
+    let contact = |sign: f64, radius: &dyn Fn(f64) -> f64| {
+        let mut JD = maximum;
+        let mut iters = 0;
+        let mut residual = f64::INFINITY;
+        while iters < 10 && residual.abs() > 1e-8 {
+            let (u, v, a, b) = motion(JD);
+            let L = radius(JD);
+            let n_sqr = a*a + b*b;
+            let n = n_sqr.sqrt();
+            let delta = (u*b - v*a) / n;
+            let tau = -(u*a + v*b) / n_sqr;
+            residual = tau + sign * (L*L - delta*delta).max(0.0).sqrt() / n;
+            JD += residual;
+            iters += 1;
+        }
+        trace_event!("eclipse::local::local_circumstances", Converged, iters, residual);
+        let (u, v, _, _, _) = state(JD);
+        Contact { JD, pos_angl: angle::limit_to_two_PI(u.atan2(v)) }
+    };
+
+    let penumbra = |JD: f64| state(JD).2;
+    let umbra = |JD: f64| state(JD).3.abs();
+
+    let first = contact(-1.0, &penumbra);
+    let fourth = contact(1.0, &penumbra);
+    let (second, third) = if m < L2.abs() {
+        (Some(contact(-1.0, &umbra)), Some(contact(1.0, &umbra)))
+    } else {
+        (None, None)
+    };
+
+    let eclipse_type = match second {
+        None                => LocalEclipseType::Partial,
+        Some(_) if L2 < 0.0 => LocalEclipseType::Total,
+        Some(_)             => LocalEclipseType::Annular
+    };
+    let duration = match (second, third) {
+        (Some(c2), Some(c3)) => (c3.JD - c2.JD) * 86400.0,
+        _                    => 0.0
+    };
+
+    let magnitude = (L1 - m) / (L1 + L2);
+    let ratio = (L1 - L2) / (L1 + L2);
+
+    Some(LocalCircumstances {
+        eclipse_type,
+        first,
+        second,
+        maximum,
+        third,
+        fourth,
+        magnitude,
+        obscuration: obscuration(magnitude, ratio),
+        duration,
+        sun_alt: zeta.clamp(-1.0, 1.0).asin()
+    })
+
+}
+
+/**
+Computes the fraction of the Sun's disk covered by the Moon
+
+# Returns
+
+* `obscuration`: Fraction of the area of the Sun's disk covered
+                 by the Moon
+
+# Arguments
+
+* `magnitude`: Fraction of the Sun's diameter covered by the Moon
+* `ratio`    : Ratio of the apparent diameters of the Moon and
+               the Sun
+**/
+pub fn obscuration(magnitude: f64, ratio: f64) -> f64 {
+
+    // the separation of the centers, in radii of the Sun
+    let c = 1.0 + ratio - 2.0*magnitude;
+    let k = ratio;
+
+    if c >= 1.0 + k {
+        0.0
+    } else if c <= (1.0 - k).abs() {
+        (k*k).min(1.0)
+    } else {
+        let a = ((c*c + 1.0 - k*k) / (2.0*c)).acos();
+        let b = ((c*c + k*k - 1.0) / (2.0*c*k)).acos();
+        let s = ((-c + 1.0 + k) * (c + 1.0 - k) * (c - 1.0 + k) * (c + 1.0 + k)).sqrt();
+
+        (a + k*k*b - s/2.0) / std::f64::consts::PI
+    }
+
+}
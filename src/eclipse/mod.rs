@@ -0,0 +1,560 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Solar and lunar eclipses
+
+pub mod besselian;
+pub mod local;
+pub mod path;
+
+use angle;
+use coords;
+use ecliptic;
+use lunar;
+use nutation;
+use observer;
+use sun;
+use time;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Represents a rule for enlarging the Earth's shadow, to account
+/// for the Earth's atmosphere
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ShadowEnlargement {
+    /// No enlargement; the geometric shadow of the solid Earth
+    Geometric,
+    /// The traditional rule of Chauvenet, enlarging the radii of the
+    /// geometric shadow by 1/50
+    Traditional,
+    /// The rule of Danjon, enlarging the Earth's radius by 1/85
+    Danjon
+}
+
+/**
+Computes the angular radii of the Earth's umbra and penumbra at the
+distance of the Moon
+
+The Earth's oblateness is accounted for by taking the radius of the
+Earth at latitude 45 degrees, ie: 0.998340 times the equatorial radius.
+
+# Returns
+
+`(umbra, penumbra)`
+
+* `umbra`   : Radius of the umbra, as seen from the Earth's
+              center *| in radians*
+* `penumbra`: Radius of the penumbra, as seen from the Earth's
+              center *| in radians*
+
+# Arguments
+
+* `moon_eq_hz_parllx`: Equatorial horizontal parallax of the Moon
+                       *| in radians*
+* `sun_eq_hz_parllx` : Equatorial horizontal parallax of the Sun
+                       *| in radians*
+* `sun_semidiameter` : Semidiameter of the Sun *| in radians*
+* `enlargement`      : The [ShadowEnlargement](./enum.ShadowEnlargement.html)
+                       rule
+**/
+pub fn earth_shadow_radii (
+
+    moon_eq_hz_parllx : f64,
+    sun_eq_hz_parllx  : f64,
+    sun_semidiameter  : f64,
+    enlargement       : &ShadowEnlargement
+
+) -> (f64, f64) {
+
+    let earth_parllx = 0.998340 * moon_eq_hz_parllx;
+
+    match *enlargement {
+        ShadowEnlargement::Geometric => (
+            earth_parllx + sun_eq_hz_parllx - sun_semidiameter,
+            earth_parllx + sun_eq_hz_parllx + sun_semidiameter
+        ),
+        ShadowEnlargement::Traditional => (
+            1.02 * (earth_parllx + sun_eq_hz_parllx - sun_semidiameter),
+            1.02 * (earth_parllx + sun_eq_hz_parllx + sun_semidiameter)
+        ),
+        ShadowEnlargement::Danjon => {
+            let earth_parllx = earth_parllx * (1.0 + 1.0/85.0);
+            (
+                earth_parllx + sun_eq_hz_parllx - sun_semidiameter,
+                earth_parllx + sun_eq_hz_parllx + sun_semidiameter
+            )
+        }
+    }
+
+}
+
+/**
+Computes the angular radii of the Earth's umbra and penumbra at the
+distance of the Moon, for a given time
+
+# Returns
+
+`(umbra, penumbra)`
+
+* `umbra`   : Radius of the umbra, as seen from the Earth's
+              center *| in radians*
+* `penumbra`: Radius of the penumbra, as seen from the Earth's
+              center *| in radians*
+
+# Arguments
+
+* `JD`         : Julian (Ephemeris) day
+* `enlargement`: The [ShadowEnlargement](./enum.ShadowEnlargement.html)
+                 rule
+**/
+pub fn earth_shadow_radii_at(JD: f64, enlargement: &ShadowEnlargement) -> (f64, f64) {
+
+    let (_, earth_moon_dist) = lunar::geocent_ecl_pos(JD);
+    let (_, sun_earth_dist) = sun::geocent_ecl_pos(JD);
+
+    earth_shadow_radii (
+        lunar::eq_hz_parllx(earth_moon_dist),
+        angle::deg_frm_dms(0, 0, 8.794).to_radians() / sun_earth_dist,
+        sun::semidiameter(sun_earth_dist).to_radians(),
+        enlargement
+    )
+
+}
+
+/// Represents a type of solar eclipse
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SolarEclipseType {
+    /// The Moon covers the Sun only partly, everywhere
+    Partial,
+    /// The Moon is seen within the Sun, leaving a ring
+    Annular,
+    /// The Moon covers the Sun completely
+    Total,
+    /// The eclipse is annular along parts of its path, and total
+    /// along others
+    Hybrid
+}
+
+/// Represents a type of lunar eclipse
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LunarEclipseType {
+    /// The Moon enters only the Earth's penumbra
+    Penumbral,
+    /// The Moon enters the Earth's umbra partly
+    Partial,
+    /// The Moon enters the Earth's umbra completely
+    Total
+}
+
+/// Holds the circumstances of a solar eclipse
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SolarEclipse {
+    /// Type of the eclipse
+    pub kind: SolarEclipseType,
+    /// Julian (Ephemeris) day of the greatest eclipse
+    pub JD: f64,
+    /// Least distance of the shadow axis from the Earth's center, in
+    /// Earth radii, positive if the axis passes north of it
+    pub gamma: f64,
+    /// Radius of the umbral cone on the fundamental plane, in Earth
+    /// radii, negative for a total eclipse
+    pub u: f64,
+    /// Magnitude of the eclipse at the greatest eclipse
+    pub magnitude: f64,
+    /// Whether the shadow axis meets the Earth
+    pub central: bool
+}
+
+/// Holds the circumstances of a lunar eclipse
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LunarEclipse {
+    /// Type of the eclipse
+    pub kind: LunarEclipseType,
+    /// Julian (Ephemeris) day of the greatest eclipse
+    pub JD: f64,
+    /// Least distance of the Moon's center from the shadow axis, in
+    /// Earth radii, positive if the Moon passes north of it
+    pub gamma: f64,
+    /// Radius of the Earth's umbra on the fundamental plane, in Earth
+    /// radii, less the radius of the Earth
+    pub u: f64,
+    /// Penumbral magnitude of the eclipse
+    pub penumbral_mag: f64,
+    /// Umbral magnitude of the eclipse, negative for a penumbral
+    /// eclipse
+    pub umbral_mag: f64,
+    /// Semiduration of the penumbral phase *| in days*
+    pub penumbral_semidur: f64,
+    /// Semiduration of the partial phase *| in days*, zero for a
+    /// penumbral eclipse
+    pub partial_semidur: f64,
+    /// Semiduration of totality *| in days*, zero for a penumbral or
+    /// partial eclipse
+    pub total_semidur: f64
+}
+
+/**
+Finds the next solar eclipse after a given time
+
+The eclipses are found from the mean new Moons, with the method of
+*Meeus* (Astronomical Algorithms, chapter 54), which gives the time of
+the greatest eclipse to within a few minutes.
+
+# Returns
+
+* `eclipse`: The next [SolarEclipse](./struct.SolarEclipse.html)
+             whose type is in `filter`
+
+# Arguments
+
+* `after_JD`: Julian (Ephemeris) day after which to search
+* `filter`  : The [SolarEclipseType](./enum.SolarEclipseType.html)s
+              of interest, or an empty slice for every type
+* `observer`: The [Observer](../observer/struct.Observer.html) from
+              whom the eclipse should be visible, if any
+**/
+pub fn next_solar (
+
+    after_JD : f64,
+    filter   : &[SolarEclipseType],
+    observer : Option<&observer::Observer>
+
+) -> SolarEclipse {
+
+    let mut k = ((after_JD - 2451550.09766)/29.530588861).floor() - 1.0;
+
+    loop {
+        if let Some(eclipse) = solar_eclipse(k) {
+            if eclipse.JD > after_JD &&
+               (filter.is_empty() || filter.contains(&eclipse.kind)) &&
+               observer.is_none_or(|observer| solar_visible(&eclipse, observer)) {
+                return eclipse;
+            }
+        }
+        k += 1.0;
+    }
+
+}
+
+/**
+Finds the next lunar eclipse after a given time
+
+The eclipses are found from the mean full Moons, with the method of
+*Meeus* (Astronomical Algorithms, chapter 54), which gives the time of
+the greatest eclipse to within a few minutes.
+
+# Returns
+
+* `eclipse`: The next [LunarEclipse](./struct.LunarEclipse.html)
+             whose type is in `filter`
+
+# Arguments
+
+* `after_JD`: Julian (Ephemeris) day after which to search
+* `filter`  : The [LunarEclipseType](./enum.LunarEclipseType.html)s
+              of interest, or an empty slice for every type
+* `observer`: The [Observer](../observer/struct.Observer.html) from
+              whom the eclipse should be visible, if any
+**/
+pub fn next_lunar (
+
+    after_JD : f64,
+    filter   : &[LunarEclipseType],
+    observer : Option<&observer::Observer>
+
+) -> LunarEclipse {
+
+    let mut k = ((after_JD - 2451550.09766)/29.530588861).floor() - 0.5;
+
+    loop {
+        if let Some(eclipse) = lunar_eclipse(k) {
+            if eclipse.JD > after_JD &&
+               (filter.is_empty() || filter.contains(&eclipse.kind)) &&
+               observer.is_none_or(|observer| lunar_visible(&eclipse, observer)) {
+                return eclipse;
+            }
+        }
+        k += 1.0;
+    }
+
+}
+
+/**
+Finds all the solar eclipses within a time range
+
+The eclipses are found with the same method as
+[next_solar()](./fn.next_solar.html).
+
+# Returns
+
+* `eclipses`: The [SolarEclipse](./struct.SolarEclipse.html)s whose
+              greatest eclipse lies within the range, in order
+
+# Arguments
+
+* `JD_start`: Julian (Ephemeris) day of the start of the range
+* `JD_end`  : Julian (Ephemeris) day of the end of the range
+**/
+pub fn solar_in_range(JD_start: f64, JD_end: f64) -> Vec<SolarEclipse> {
+
+    let mut k = ((JD_start - 2451550.09766)/29.530588861).floor() - 1.0;
+    let mut eclipses = Vec::new();
+
+    while 2451550.09766 + 29.530588861*(k - 1.0) <= JD_end {
+        if let Some(eclipse) = solar_eclipse(k) {
+            if eclipse.JD >= JD_start && eclipse.JD <= JD_end {
+                eclipses.push(eclipse);
+            }
+        }
+        k += 1.0;
+    }
+
+    eclipses
+
+}
+
+/**
+Finds all the lunar eclipses within a time range
+
+The eclipses are found with the same method as
+[next_lunar()](./fn.next_lunar.html).
+
+# Returns
+
+* `eclipses`: The [LunarEclipse](./struct.LunarEclipse.html)s whose
+              greatest eclipse lies within the range, in order
+
+# Arguments
+
+* `JD_start`: Julian (Ephemeris) day of the start of the range
+* `JD_end`  : Julian (Ephemeris) day of the end of the range
+**/
+pub fn lunar_in_range(JD_start: f64, JD_end: f64) -> Vec<LunarEclipse> {
+
+    let mut k = ((JD_start - 2451550.09766)/29.530588861).floor() - 0.5;
+    let mut eclipses = Vec::new();
+
+    while 2451550.09766 + 29.530588861*(k - 1.0) <= JD_end {
+        if let Some(eclipse) = lunar_eclipse(k) {
+            if eclipse.JD >= JD_start && eclipse.JD <= JD_end {
+                eclipses.push(eclipse);
+            }
+        }
+        k += 1.0;
+    }
+
+    eclipses
+
+}
+
+// The time of greatest eclipse, γ, u and the Moon's mean anomaly at
+// the new (integral k) or full (half-integral k) Moon, if there is
+// an eclipse, by the method of Meeus
+fn meeus_eclipse(k: f64) -> Option<(f64, f64, f64, f64)> {
+
+    let T = k / 1236.85;
+
+    let F = (
+        160.7108 + 390.67050284*k
+      - T*T*(0.0016118 + T*(0.00000227 - T*0.000000011))
+    ).to_radians();
+    if F.sin().abs() > 0.36 {
+        return None;
+    }
+
+    let M = (2.5534 + 29.1053567*k - T*T*(0.0000014 + T*0.00000011)).to_radians();
+    let M1 = (
+        201.5643 + 385.81693528*k
+      + T*T*(0.0107582 + T*(0.00001238 - T*0.000000058))
+    ).to_radians();
+    let omega = (124.7746 - 1.56375588*k + T*T*(0.0020672 + T*0.00000215)).to_radians();
+    let E = 1.0 - T*(0.002516 + T*0.0000074);
+    let F1 = F - 0.02665_f64.to_radians()*omega.sin();
+    let A1 = (299.77 + 0.107408*k - 0.009173*T*T).to_radians();
+
+    let is_new = k.fract() == 0.0;
+
+    let JD =
+        2451550.09766 + 29.530588861*k
+      + T*T*(0.00015437 - T*(0.00000015 - T*0.00000000073))
+      + if is_new { -0.4075*M1.sin() + 0.1721*E*M.sin() }
+        else      { -0.4065*M1.sin() + 0.1727*E*M.sin() }
+      + 0.0161 * (2.0*M1).sin()
+      - 0.0097 * (2.0*F1).sin()
+      + 0.0073 * E * (M1 - M).sin()
+      - 0.005 * E * (M1 + M).sin()
+      - 0.0023 * (M1 - 2.0*F1).sin()
+      + 0.0021 * E * (2.0*M).sin()
+      + 0.0012 * (M1 + 2.0*F1).sin()
+      + 0.0006 * E * (2.0*M1 + M).sin()
+      - 0.0004 * (3.0*M1).sin()
+      - 0.0003 * E * (M + 2.0*F1).sin()
+      + 0.0003 * A1.sin()
+      - 0.0002 * E * (M - 2.0*F1).sin()
+      - 0.0002 * E * (2.0*M1 - M).sin()
+      - 0.0002 * omega.sin();
+
+    let P =
+        0.207 * E * M.sin()
+      + 0.0024 * E * (2.0*M).sin()
+      - 0.0392 * M1.sin()
+      + 0.0116 * (2.0*M1).sin()
+      - 0.0073 * E * (M1 + M).sin()
+      + 0.0067 * E * (M1 - M).sin()
+      + 0.0118 * (2.0*F1).sin();
+
+    let Q =
+        5.2207
+      - 0.0048 * E * M.cos()
+      + 0.002 * E * (2.0*M).cos()
+      - 0.3299 * M1.cos()
+      - 0.006 * E * (M1 + M).cos()
+      + 0.0041 * E * (M1 - M).cos();
+
+    let W = F1.cos().abs();
+    let gamma = (P*F1.cos() + Q*F1.sin()) * (1.0 - 0.0048*W);
+
+    let u =
+        0.0059
+      + 0.0046 * E * M.cos()
+      - 0.0182 * M1.cos()
+      + 0.0004 * (2.0*M1).cos()
+      - 0.0005 * (M + M1).cos();
+
+    Some((JD, gamma, u, M1))
+
+}
+
+fn solar_eclipse(k: f64) -> Option<SolarEclipse> {
+
+    let (JD, gamma, u, _) = meeus_eclipse(k)?;
+    let g = gamma.abs();
+
+    if g > 1.5433 + u {
+        return None;
+    }
+
+    let central = g < 0.9972;
+    let kind =
+        if !central && g > 0.9972 + u.abs() { SolarEclipseType::Partial }
+        else if u < 0.0                     { SolarEclipseType::Total }
+        else if u > 0.0047                  { SolarEclipseType::Annular }
+        else if u < 0.00464 * (1.0 - gamma*gamma).sqrt() { SolarEclipseType::Hybrid }
+        else                                { SolarEclipseType::Annular };
+
+    let magnitude = if kind == SolarEclipseType::Partial {
+        (1.5433 + u - g) / (0.5461 + 2.0*u)
+    } else {
+        // the ratio of the apparent diameters of the Moon and the Sun,
+        // seen from where the shadow axis is nearest the Earth's center
+        let v = besselian::instantaneous_values(JD);
+        let zeta = (1.0 - (g*g).min(1.0)).sqrt();
+        let L1 = v.l1 - zeta*0.0046;
+        let L2 = v.l2 - zeta*0.0046;
+        (L1 - L2) / (L1 + L2)
+    };
+
+    Some(SolarEclipse { kind, JD, gamma, u, magnitude, central })
+
+}
+
+fn lunar_eclipse(k: f64) -> Option<LunarEclipse> {
+
+    let (JD, gamma, u, M1) = meeus_eclipse(k)?;
+    let g = gamma.abs();
+
+    let penumbral_mag = (1.5573 + u - g) / 0.545;
+    if penumbral_mag <= 0.0 {
+        return None;
+    }
+    let umbral_mag = (1.0128 - u - g) / 0.545;
+
+    let kind =
+        if umbral_mag <= 0.0     { LunarEclipseType::Penumbral }
+        else if umbral_mag < 1.0 { LunarEclipseType::Partial }
+        else                     { LunarEclipseType::Total };
+
+    let n = 0.5458 + 0.04*M1.cos();
+    let semidur = |r: f64| if r > g { (r*r - g*g).sqrt() / (n * 24.0) } else { 0.0 };
+
+    Some(LunarEclipse {
+        kind,
+        JD,
+        gamma,
+        u,
+        penumbral_mag,
+        umbral_mag,
+        penumbral_semidur : semidur(1.5573 + u),
+        partial_semidur   : semidur(1.0128 - u),
+        total_semidur     : semidur(0.4678 - u)
+    })
+
+}
+
+// Whether an observer sees any part of a solar eclipse, with the Sun
+// above the horizon
+fn solar_visible(eclipse: &SolarEclipse, observer: &observer::Observer) -> bool {
+
+    let elements = besselian::elements(eclipse.JD, time::delta_t_frm_julian_day(eclipse.JD));
+
+    (-90..91).any(|i| {
+        let JD = eclipse.JD + (i as f64)*2.0/1440.0;
+        let v = besselian::values(&elements, JD);
+        let (xi, eta, zeta) = besselian::observer_coords(&elements, JD, &observer.geograph_point(), observer.height);
+        let L1 = v.l1 - zeta*elements.tan_f1;
+
+        zeta > 0.0 && (v.x - xi).hypot(v.y - eta) < L1
+    })
+
+}
+
+// Whether an observer sees any part of a lunar eclipse, with the Moon
+// above the horizon
+fn lunar_visible(eclipse: &LunarEclipse, observer: &observer::Observer) -> bool {
+
+    let dt = time::delta_t_frm_julian_day(eclipse.JD);
+    let n = (eclipse.penumbral_semidur * 1440.0 / 5.0).ceil() as i32;
+
+    (-n..(n + 1)).any(|i| {
+        let JD = eclipse.JD + (i as f64)*5.0/1440.0;
+
+        let (nut_long, nut_oblq) = nutation::nutation(JD);
+        let oblq = ecliptic::mn_oblq_IAU(JD) + nut_oblq;
+        let (moon, moon_dist) = lunar::geocent_ecl_pos(JD);
+        let asc = coords::asc_frm_ecl(moon.long + nut_long, moon.lat, oblq);
+        let dec = coords::dec_frm_ecl(moon.long + nut_long, moon.lat, oblq);
+
+        let sidr = time::apprnt_sidr(time::mn_sidr(JD - dt/86400.0), nut_long, oblq);
+        let hour_angle = coords::hr_angl_frm_observer_long(sidr, observer.long, asc);
+        let alt = coords::alt_frm_eq(hour_angle, dec, observer.lat);
+
+        // the topocentric altitude, lowered by the Moon's parallax
+        alt - lunar::eq_hz_parllx(moon_dist)*alt.cos() > 0.0
+    })
+
+}
+
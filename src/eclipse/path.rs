@@ -0,0 +1,405 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Paths of solar eclipses and lunar occultations on the Earth
+
+/*
+
+The points on the Earth's surface are found from the Besselian
+elements of [besselian](../besselian/index.html), so that the paths
+can be computed for the published elements as well as for those of
+this library.
+
+A limit of a shadow is the envelope of the shadow's edge as it sweeps
+over the Earth, ie: at each instant, the point on the edge where the
+shadow moves parallel to the edge relative to the observer. The
+northern and southern limits of the lunar shadow of an occultation
+are the paths of grazing occultations.
+
+*/
+
+use angle;
+use coords;
+use error;
+use planet;
+use super::besselian;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Represents a cone of the Moon's shadow
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Cone {
+    /// The penumbra, for a partial eclipse
+    Penumbra,
+    /// The umbra (or antumbra), for a total or annular eclipse, or
+    /// the cylindrical shadow of an occultation
+    Umbra
+}
+
+/// Represents a limit of a shadow on the Earth
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Limit {
+    /// The northern limit
+    Northern,
+    /// The southern limit
+    Southern
+}
+
+/// Holds a point of a path on the Earth's surface
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PathPoint {
+    /// Julian (Ephemeris) day at which the point is on the path
+    pub JD: f64,
+    /// Julian day in Universal Time at which the point is on the path
+    pub JD_UT: f64,
+    /// Geographic point on the path
+    pub point: coords::GeographPoint
+}
+
+/// Holds a point of the central line of a solar eclipse
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CentralPoint {
+    /// Julian (Ephemeris) day at which the point is on the line
+    pub JD: f64,
+    /// Julian day in Universal Time at which the point is on the line
+    pub JD_UT: f64,
+    /// Geographic point on the central line
+    pub point: coords::GeographPoint,
+    /// Width of the path of totality or annularity, across the
+    /// central line *| in kilometers*
+    pub width: f64
+}
+
+/// Holds the path of a solar eclipse on the Earth, as lines of
+/// points suitable for plotting on a map
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Path {
+    /// Central line, which is empty for a partial eclipse
+    pub central_line: Vec<CentralPoint>,
+    /// Northern limit of the umbra or the antumbra
+    pub northern_limit: Vec<PathPoint>,
+    /// Southern limit of the umbra or the antumbra
+    pub southern_limit: Vec<PathPoint>,
+    /// Northern limit of the penumbra
+    pub northern_penumbral_limit: Vec<PathPoint>,
+    /// Southern limit of the penumbra
+    pub southern_penumbral_limit: Vec<PathPoint>
+}
+
+/**
+Computes the point of a limit of a shadow on the Earth at an instant
+
+# Returns
+
+* `point`: Geographic point of the limit, or `None` if the limit
+           doesn't lie on the Earth at the instant
+
+# Arguments
+
+* `elements`: The Besselian [Elements](../besselian/struct.Elements.html)
+* `JD`      : Julian (Ephemeris) day
+* `cone`    : The [Cone](./enum.Cone.html) of the shadow
+* `limit`   : The [Limit](./enum.Limit.html) of the cone
+**/
+pub fn limit_point (
+
+    elements : &besselian::Elements,
+    JD       : f64,
+    cone     : &Cone,
+    limit    : &Limit
+
+) -> Option<coords::GeographPoint> {
+
+    let v = besselian::values(elements, JD);
+    let r = besselian::rates(elements, JD);
+    let (sin_d, cos_d) = v.d.sin_cos();
+
+    let (l, tan_f) = match *cone {
+        Cone::Penumbra => (v.l1, elements.tan_f1),
+        Cone::Umbra    => (v.l2, elements.tan_f2)
+    };
+
+    let (mut xi_rate, mut eta_rate, mut zeta) = (0.0, 0.0, 0.0);
+    let mut solution = None;
+
+    for _ in 0..6 {
+        let L = (l - zeta*tan_f).abs();
+
+        // the edge of the shadow lies across the relative velocity
+        let (a, b) = (r.x - xi_rate, r.y - eta_rate);
+        let n = (a*a + b*b).sqrt();
+        let (mut p, mut q) = (-b/n, a/n);
+        let north = match *limit { Limit::Northern => true, Limit::Southern => false };
+        if (q > 0.0) != north {
+            p = -p;
+            q = -q;
+        }
+
+        let (xi, eta) = (v.x + L*p, v.y + L*q);
+        let (point, z) = geograph_point(elements, &v, xi, eta)?;
+
+        zeta = z;
+        xi_rate = r.mu * (-eta*sin_d + zeta*cos_d);
+        eta_rate = r.mu * xi * sin_d - zeta * r.d;
+        solution = Some(point);
+    }
+
+    solution
+
+}
+
+/**
+Computes a limit of a shadow on the Earth as a sequence of points
+
+# Returns
+
+* `Ok(path)`  : [PathPoint](./struct.PathPoint.html)s of the limit, at
+                the instants it lies on the Earth, or
+* `Err(error)`: `Error::NotPositive` if `step` isn't positive, or
+                `Error::NotFinite`
+
+# Arguments
+
+* `elements`: The Besselian [Elements](../besselian/struct.Elements.html)
+* `cone`    : The [Cone](./enum.Cone.html) of the shadow
+* `limit`   : The [Limit](./enum.Limit.html) of the cone
+* `JD_start`: Julian (Ephemeris) day to start the path from
+* `JD_end`  : Julian (Ephemeris) day to end the path at
+* `step`    : Interval between the points, positive *| in days*
+**/
+pub fn limit_line (
+
+    elements : &besselian::Elements,
+    cone     : &Cone,
+    limit    : &Limit,
+    JD_start : f64,
+    JD_end   : f64,
+    step     : f64
+
+) -> Result<Vec<PathPoint>, error::Error> {
+
+    let mut path = Vec::new();
+
+    for JD in instants(JD_start, JD_end, step)? {
+        if let Some(point) = limit_point(elements, JD, cone, limit) {
+            path.push(PathPoint { JD, JD_UT: JD_UT(elements, JD), point });
+        }
+    }
+
+    Ok(path)
+
+}
+
+/**
+Computes the point of the central line of a solar eclipse on the Earth
+at an instant
+
+# Returns
+
+`(point, width)`
+
+* `point`: Geographic point on the central line
+* `width`: Width of the path of totality or annularity, across the
+           central line *| in kilometers*
+
+`None` is returned if the shadow axis doesn't meet the Earth at the
+instant.
+
+# Arguments
+
+* `elements`: The Besselian [Elements](../besselian/struct.Elements.html)
+* `JD`      : Julian (Ephemeris) day
+**/
+pub fn central_point (
+
+    elements : &besselian::Elements,
+    JD       : f64
+
+) -> Option<(coords::GeographPoint, f64)> {
+
+    let v = besselian::values(elements, JD);
+    let r = besselian::rates(elements, JD);
+    let (sin_d, cos_d) = v.d.sin_cos();
+
+    let (point, zeta) = geograph_point(elements, &v, v.x, v.y)?;
+
+    // the relative velocity of the shadow and the observer, and the
+    // direction across it on the fundamental plane
+    let a = r.x - r.mu * (-v.y*sin_d + zeta*cos_d);
+    let b = r.y - (r.mu * v.x * sin_d - zeta * r.d);
+    let n = (a*a + b*b).sqrt();
+    let (p, q) = (-b/n, a/n);
+
+    // the diameter of the shadow across the relative velocity, seen
+    // on the inclined surface of the Earth
+    let L = (v.l2 - zeta*elements.tan_f2).abs();
+    let slope = (v.x*p + v.y*q) / zeta;
+    let width = 2.0 * L * (1.0 + slope*slope).sqrt() * planet::earth::eq_rad();
+
+    Some((point, width))
+
+}
+
+/**
+Computes the central line of a solar eclipse as a sequence of points
+
+# Returns
+
+* `Ok(central_line)`: [CentralPoint](./struct.CentralPoint.html)s of
+                      the central line, at the instants the shadow
+                      axis meets the Earth, or
+* `Err(error)`      : `Error::NotPositive` if `step` isn't positive,
+                      or `Error::NotFinite`
+
+# Arguments
+
+* `elements`: The Besselian [Elements](../besselian/struct.Elements.html)
+* `JD_start`: Julian (Ephemeris) day to start the line from
+* `JD_end`  : Julian (Ephemeris) day to end the line at
+* `step`    : Interval between the points, positive *| in days*
+**/
+pub fn central_line (
+
+    elements : &besselian::Elements,
+    JD_start : f64,
+    JD_end   : f64,
+    step     : f64
+
+) -> Result<Vec<CentralPoint>, error::Error> {
+
+    let mut line = Vec::new();
+
+    for JD in instants(JD_start, JD_end, step)? {
+        if let Some((point, width)) = central_point(elements, JD) {
+            line.push(CentralPoint { JD, JD_UT: JD_UT(elements, JD), point, width });
+        }
+    }
+
+    Ok(line)
+
+}
+
+/**
+Computes the path of a solar eclipse on the Earth
+
+# Returns
+
+* `Ok(path)`  : The [Path](./struct.Path.html) of the eclipse, with
+                the central line, the limits of the umbra or antumbra,
+                and the northern and southern limits of the penumbra,
+                or
+* `Err(error)`: `Error::NotPositive` if `step` isn't positive, or
+                `Error::NotFinite`
+
+# Arguments
+
+* `elements`: The Besselian [Elements](../besselian/struct.Elements.html)
+* `JD_start`: Julian (Ephemeris) day to start the path from
+* `JD_end`  : Julian (Ephemeris) day to end the path at
+* `step`    : Interval between the points, positive *| in days*
+**/
+pub fn path (
+
+    elements : &besselian::Elements,
+    JD_start : f64,
+    JD_end   : f64,
+    step     : f64
+
+) -> Result<Path, error::Error> {
+
+    let limit = |cone, limit| limit_line(elements, &cone, &limit, JD_start, JD_end, step);
+
+    Ok(Path {
+        central_line             : central_line(elements, JD_start, JD_end, step)?,
+        northern_limit           : limit(Cone::Umbra, Limit::Northern)?,
+        southern_limit           : limit(Cone::Umbra, Limit::Southern)?,
+        northern_penumbral_limit : limit(Cone::Penumbra, Limit::Northern)?,
+        southern_penumbral_limit : limit(Cone::Penumbra, Limit::Southern)?
+    })
+
+}
+
+#[inline]
+fn JD_UT(elements: &besselian::Elements, JD: f64) -> f64 {
+
+    JD - elements.delta_t/86400.0
+
+}
+
+fn instants(JD_start: f64, JD_end: f64, step: f64) -> Result<Vec<f64>, error::Error> {
+
+    error::check_positive(step)?;
+    if !JD_start.is_finite() || !JD_end.is_finite() {
+        return Err(error::Error::NotFinite);
+    }
+
+    let n = ((JD_end - JD_start)/step + 1e-6).floor().max(0.0) as usize;
+
+    Ok((0..(n + 1)).map(|i| JD_start + (i as f64)*step).collect())
+
+}
+
+// Finds the point on the Earth's ellipsoid, on the side facing along
+// the shadow axis, whose coordinates on the fundamental plane are
+// (xi, eta). Returns the point and its zeta coordinate.
+fn geograph_point (
+
+    elements : &besselian::Elements,
+    v        : &besselian::Values,
+    xi       : f64,
+    eta      : f64
+
+) -> Option<(coords::GeographPoint, f64)> {
+
+    let (sin_d, cos_d) = v.d.sin_cos();
+    let b2 = (1.0 - planet::earth::flat_fac()).powi(2);
+
+    // the point is (-eta sin d + zeta cos d, xi, eta cos d + zeta sin d)
+    // with respect to the equator and the meridian of the shadow axis
+    let A = cos_d*cos_d + sin_d*sin_d/b2;
+    let B = eta * sin_d * cos_d * (1.0/b2 - 1.0);
+    let C = xi*xi + eta*eta*(sin_d*sin_d + cos_d*cos_d/b2) - 1.0;
+
+    let disc = B*B - A*C;
+    if disc < 0.0 {
+        return None;
+    }
+    let zeta = (-B + disc.sqrt()) / A;
+
+    let X = -eta*sin_d + zeta*cos_d;
+    let Z = eta*cos_d + zeta*sin_d;
+
+    let theta = xi.atan2(X);
+    let lat = (Z / (b2 * (X*X + xi*xi).sqrt())).atan();
+
+    // the hour angle μ is on the ephemeris meridian
+    let ephem_long = v.mu - theta;
+    let long = angle::limit_to_pm_PI(ephem_long + besselian::ephem_meridian(elements.delta_t));
+
+    Some((coords::GeographPoint { long, lat }, zeta))
+
+}
@@ -23,8 +23,12 @@ THE SOFTWARE.
 //! The Earth's ecliptic
 
 use angle;
+use coords;
+use nutation;
 use std::f64::consts::PI;
 use time;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 /**
 Computes the mean obliquity of the ecliptic using
@@ -99,8 +103,10 @@ a horizon on Earth
 
 `(long_point_1, long_point_2)`
 
-* `long_point_1`: Longitude of ecliptic point 1 *| in radians*
-* `long_point_2`: Longitude of ecliptic point 2 *| in radians*
+* `long_point_1`: Longitude of ecliptic point 1 *| in radians*,
+                  the point setting on the western horizon
+* `long_point_2`: Longitude of ecliptic point 2 *| in radians*,
+                  the point rising on the eastern horizon
 
 # Arguments
 
@@ -141,3 +147,138 @@ pub fn angl_betwn_eclip_and_hz(oblq_eclip: f64, observer_lat: f64,
       - oblq_eclip.sin() * observer_lat.cos() * loc_sidreal.sin()
     ).acos()
 }
+
+/**
+Computes the ecliptic points on the horizon, and the angle between the
+ecliptic and the horizon, for a time and an observer on Earth
+
+The true obliquity of the ecliptic and the apparent sidereal time are
+used, so the longitudes are referred to the true equinox of the date.
+
+# Returns
+
+`(rising_long, setting_long, angle)`
+
+* `rising_long` : Longitude of the ecliptic point rising on the
+                  eastern horizon *| in radians*
+* `setting_long`: Longitude of the ecliptic point setting on the
+                  western horizon *| in radians*
+* `angle`       : Angle between the ecliptic and the horizon
+                  *| in radians*
+
+# Arguments
+
+* `JD`            : Julian (Ephemeris) day
+* `delta_t`       : ΔT = TT - UT *| in seconds*
+* `geograph_point`: Geographic point of the observer *| in radians*
+**/
+pub fn eclip_and_hz_at (
+
+    JD             : f64,
+    delta_t        : f64,
+    geograph_point : &coords::GeographPoint
+
+) -> (f64, f64, f64) {
+
+    let (nut_long, nut_oblq) = nutation::nutation(JD);
+    let oblq = mn_oblq_IAU(JD) + nut_oblq;
+
+    let sidr = time::apprnt_sidr(time::mn_sidr(JD - delta_t/86400.0), nut_long, oblq);
+    let loc_sidr = sidr - geograph_point.long;
+
+    let (setting_long, rising_long) = eclip_points_on_hz(oblq, geograph_point.lat, loc_sidr);
+
+    (
+        angle::limit_to_two_PI(rising_long),
+        angle::limit_to_two_PI(setting_long),
+        angl_betwn_eclip_and_hz(oblq, geograph_point.lat, loc_sidr)
+    )
+
+}
+
+/// Represents a sign of the zodiac, a 30 degree span of ecliptic
+/// longitude from the equinox
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ZodiacSign {
+    /// Aries, from 0 degrees
+    Aries = 0,
+    /// Taurus, from 30 degrees
+    Taurus = 1,
+    /// Gemini, from 60 degrees
+    Gemini = 2,
+    /// Cancer, from 90 degrees
+    Cancer = 3,
+    /// Leo, from 120 degrees
+    Leo = 4,
+    /// Virgo, from 150 degrees
+    Virgo = 5,
+    /// Libra, from 180 degrees
+    Libra = 6,
+    /// Scorpio, from 210 degrees
+    Scorpio = 7,
+    /// Sagittarius, from 240 degrees
+    Sagittarius = 8,
+    /// Capricorn, from 270 degrees
+    Capricorn = 9,
+    /// Aquarius, from 300 degrees
+    Aquarius = 10,
+    /// Pisces, from 330 degrees
+    Pisces = 11,
+}
+
+/// The signs of the zodiac, in order of ecliptic longitude
+pub const ZODIAC_SIGNS: [ZodiacSign; 12] = [
+    ZodiacSign::Aries,     ZodiacSign::Taurus,   ZodiacSign::Gemini,
+    ZodiacSign::Cancer,    ZodiacSign::Leo,      ZodiacSign::Virgo,
+    ZodiacSign::Libra,     ZodiacSign::Scorpio,  ZodiacSign::Sagittarius,
+    ZodiacSign::Capricorn, ZodiacSign::Aquarius, ZodiacSign::Pisces,
+];
+
+/// Half the width of the zodiacal band, the belt of the sky about the
+/// ecliptic traditionally taken to hold the paths of the Sun, the
+/// Moon and the planets *| in radians*
+pub const ZODIAC_HALF_WIDTH: f64 = 8.0 * PI / 180.0;
+
+/**
+Computes the sign of the zodiac at an ecliptic longitude
+
+# Returns
+
+`(sign, long_in_sign)`
+
+* `sign`        : The [ZodiacSign](./enum.ZodiacSign.html)
+* `long_in_sign`: Longitude from the start of the sign *| in radians*,
+                  between `0` and `PI/6`
+
+# Arguments
+
+* `ecl_long`: Ecliptic longitude *| in radians*
+**/
+pub fn zodiac_sign(ecl_long: f64) -> (ZodiacSign, f64) {
+
+    let span = PI / 6.0;
+    let long = angle::limit_to_two_PI(ecl_long);
+    let i = ((long / span).floor() as usize).min(11);
+
+    (ZODIAC_SIGNS[i], long - (i as f64)*span)
+
+}
+
+/**
+Checks whether an ecliptic latitude is within the zodiacal band
+
+# Returns
+
+* `true` if the latitude is within [ZODIAC_HALF_WIDTH](./constant.ZODIAC_HALF_WIDTH.html)
+  of the ecliptic
+
+# Arguments
+
+* `ecl_lat`: Ecliptic latitude *| in radians*
+**/
+pub fn in_zodiacal_band(ecl_lat: f64) -> bool {
+
+    ecl_lat.abs() <= ZODIAC_HALF_WIDTH
+
+}
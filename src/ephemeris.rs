@@ -0,0 +1,487 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Pluggable ephemerides of the Moon and the planets
+//!
+//! A [Provider](./trait.Provider.html) gives the rectangular
+//! coordinates of the bodies. The analytic theories of the crate are
+//! the default provider, and the JPL Development Ephemerides can be
+//! read from their ASCII files by those who have them.
+//!
+//! A [Generator](./struct.Generator.html) turns the coordinates of a
+//! provider into rows of an apparent ephemeris of a body.
+
+use aberr;
+use angle;
+use body::Body;
+use chebyshev;
+use consts;
+use coords;
+use ecliptic;
+use error;
+use lunar;
+use nutation;
+use planet;
+use precess;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// A source of positions of the Moon and the planets
+pub trait Provider {
+    /**
+    Computes a planet's heliocentric rectangular coordinates, referred
+    to the ecliptic and mean equinox of J2000.0
+
+    # Returns
+
+    * `Some((x, y, z))`: Heliocentric rectangular coordinates
+                         *| in AU*, or
+    * `None`           : if `JD` is outside the ephemeris
+
+    # Arguments
+
+    * `planet`: Any variant of [Planet](../planet/enum.Planet.html)
+    * `JD`    : Julian (Ephemeris) day
+    **/
+    fn heliocent_ecl_rect_coords_J2000(&self, planet: &planet::Planet, JD: f64) -> Option<(f64, f64, f64)>;
+
+    /**
+    Computes the Moon's geocentric rectangular coordinates, referred
+    to the ecliptic and mean equinox of J2000.0
+
+    # Returns
+
+    * `Some((x, y, z))`: Geocentric rectangular coordinates
+                         *| in kilometers*, or
+    * `None`           : if `JD` is outside the ephemeris
+
+    # Arguments
+
+    * `JD`: Julian (Ephemeris) day
+    **/
+    fn moon_geocent_ecl_rect_coords_J2000(&self, JD: f64) -> Option<(f64, f64, f64)>;
+
+    /**
+    Computes a planet's geometric geocentric rectangular coordinates,
+    referred to the ecliptic and mean equinox of J2000.0
+
+    # Returns
+
+    * `Some((x, y, z))`: Geocentric rectangular coordinates
+                         *| in AU*, or
+    * `None`           : if `JD` is outside the ephemeris
+
+    # Arguments
+
+    * `planet`: Any variant of [Planet](../planet/enum.Planet.html)
+    * `JD`    : Julian (Ephemeris) day
+    **/
+    fn geocent_ecl_rect_coords_J2000(&self, planet: &planet::Planet, JD: f64) -> Option<(f64, f64, f64)> {
+        let p = self.heliocent_ecl_rect_coords_J2000(planet, JD)?;
+        let e = self.heliocent_ecl_rect_coords_J2000(&planet::Planet::Earth, JD)?;
+
+        Some((p.0 - e.0, p.1 - e.1, p.2 - e.2))
+    }
+}
+
+/// The analytic theories of the crate, VSOP87 for the planets and
+/// ELP-2000/82 for the Moon
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Analytic;
+
+impl Provider for Analytic {
+    fn heliocent_ecl_rect_coords_J2000(&self, planet: &planet::Planet, JD: f64) -> Option<(f64, f64, f64)> {
+        Some(planet::heliocent_ecl_rect_coords_J2000(planet, JD))
+    }
+
+    fn moon_geocent_ecl_rect_coords_J2000(&self, JD: f64) -> Option<(f64, f64, f64)> {
+        let (point, dist) = lunar::geocent_ecl_pos(JD);
+        let (long, lat) = precess::precess_ecl_coords(point.long, point.lat, JD, 2451545.0);
+
+        Some((dist*lat.cos()*long.cos(), dist*lat.cos()*long.sin(), dist*lat.sin()))
+    }
+}
+
+/**
+A JPL Development Ephemeris, such as DE430 or DE440
+
+The ephemeris holds the Chebyshev coefficients of the barycentric
+positions of the bodies, referred to the ICRF. They are rotated to the
+ecliptic of J2000.0 with the IAU 1976 obliquity, neglecting the frame
+bias between the ICRF and the mean equator of J2000.0, of a few
+hundredths of an arcsecond.
+**/
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct JplDe {
+    /// Julian (Ephemeris) day of the start of the ephemeris
+    pub start: f64,
+    /// Julian (Ephemeris) day of the end of the ephemeris
+    pub end: f64,
+    /// Length of each record of coefficients *| in days*
+    pub interval: f64,
+    /// Astronomical unit *| in kilometers*
+    pub AU: f64,
+    /// Earth-Moon mass ratio
+    pub EMRAT: f64,
+    // (offset, coefficients per component, subintervals) of each item
+    layout: Vec<(usize, usize, usize)>,
+    records: Vec<Vec<f64>>
+}
+
+// items of the ephemeris, in the order of the files
+const MERCURY: usize = 0;
+const EARTH_MOON_BARY: usize = 2;
+const MOON: usize = 9;
+const SUN: usize = 10;
+
+impl JplDe {
+    /**
+    Reads a JPL Development Ephemeris from its ASCII files
+
+    # Returns
+
+    * `Ok(ephemeris)`: The [JplDe](./struct.JplDe.html) read, or
+    * `Err(error)`   : `Error::InvalidFormat`, if a group of the header,
+                       or a record of the data, is missing or can't be
+                       read
+
+    # Arguments
+
+    * `header`: Contents of the header file, such as `header.440`
+    * `data`  : Contents of the data files, such as `ascp01950.440`,
+                in order of time
+    **/
+    pub fn parse_ascii(header: &str, data: &[&str]) -> Result<JplDe, error::Error> {
+        let tokens: Vec<&str> = header.split_whitespace().collect();
+        let group = |num: &str| -> Result<Vec<&str>, error::Error> {
+            let i = tokens.windows(2).position(|w| w[0] == "GROUP" && w[1] == num)
+                .ok_or(error::Error::InvalidFormat)?;
+            Ok(tokens[i + 2..].iter().cloned().take_while(|&t| t != "GROUP").collect())
+        };
+
+        let span: Vec<f64> = group("1030")?.into_iter().map(parse_num).collect::<Result<_, _>>()?;
+        if span.len() < 3 {
+            return Err(error::Error::InvalidFormat);
+        }
+
+        let names = group("1040")?;
+        let values = group("1041")?;
+        let constant = |name: &str| -> Result<f64, error::Error> {
+            let i = names.iter().skip(1).position(|&n| n == name)
+                .ok_or(error::Error::InvalidFormat)?;
+            values.get(i + 1)
+                .ok_or(error::Error::InvalidFormat)
+                .and_then(|&t| parse_num(t))
+        };
+        let AU = constant("AU")?;
+        let EMRAT = constant("EMRAT")?;
+
+        let layout: Vec<usize> = group("1050")?.iter()
+            .map(|t| t.parse::<usize>().map_err(|_| error::Error::InvalidFormat))
+            .collect::<Result<_, _>>()?;
+        let num_items = layout.len() / 3;
+        if num_items <= SUN {
+            return Err(error::Error::InvalidFormat);
+        }
+        let layout: Vec<(usize, usize, usize)> = (0..num_items)
+            .map(|i| (layout[i], layout[num_items + i], layout[2*num_items + i]))
+            .collect();
+
+        let mut records: Vec<Vec<f64>> = Vec::new();
+        for text in data {
+            let mut tokens = text.split_whitespace();
+            while let (Some(_), Some(n)) = (tokens.next(), tokens.next()) {
+                let n = n.parse::<usize>().map_err(|_| error::Error::InvalidFormat)?;
+                let coeffs: Vec<f64> = tokens.by_ref().take(n).map(parse_num).collect::<Result<_, _>>()?;
+                if coeffs.len() < n {
+                    return Err(error::Error::InvalidFormat);
+                }
+                // the last line of a record is padded to three numbers
+                for _ in 0..((3 - n % 3) % 3) {
+                    tokens.next();
+                }
+
+                // the data files overlap by one record
+                if records.last().is_none_or(|last| coeffs[0] >= last[1]) {
+                    records.push(coeffs);
+                }
+            }
+        }
+        if records.is_empty() {
+            return Err(error::Error::InvalidFormat);
+        }
+
+        Ok(JplDe {
+            start    : records[0][0],
+            end      : records[records.len() - 1][1],
+            interval : span[2],
+            AU,
+            EMRAT,
+            layout,
+            records
+        })
+    }
+
+    // Barycentric equatorial coordinates of an item, in kilometers
+    fn item_pos(&self, item: usize, JD: f64) -> Option<(f64, f64, f64)> {
+        if JD < self.start || JD > self.end {
+            return None;
+        }
+
+        let i = (((JD - self.start) / self.interval).floor() as usize).min(self.records.len() - 1);
+        let record = &self.records[i];
+        let (offset, n, num_sub) = self.layout[item];
+
+        let sub_len = (record[1] - record[0]) / (num_sub as f64);
+        let sub = (((JD - record[0]) / sub_len).floor() as usize).min(num_sub - 1);
+        let sub_start = record[0] + (sub as f64)*sub_len;
+        let u = 2.0*(JD - sub_start)/sub_len - 1.0;
+
+        let base = offset - 1 + sub*n*3;
+        let coord = |c: usize| record.get(base + c*n..base + (c + 1)*n)
+            .map(|coeffs| chebyshev::clenshaw(coeffs, u));
+
+        Some((coord(0)?, coord(1)?, coord(2)?))
+    }
+}
+
+impl Provider for JplDe {
+    fn heliocent_ecl_rect_coords_J2000(&self, planet: &planet::Planet, JD: f64) -> Option<(f64, f64, f64)> {
+        let pos = match *planet {
+            planet::Planet::Earth => {
+                let emb = self.item_pos(EARTH_MOON_BARY, JD)?;
+                let moon = self.item_pos(MOON, JD)?;
+                let f = 1.0 / (1.0 + self.EMRAT);
+                (emb.0 - f*moon.0, emb.1 - f*moon.1, emb.2 - f*moon.2)
+            },
+            _ => self.item_pos(MERCURY + planet_index(planet), JD)?
+        };
+        let sun = self.item_pos(SUN, JD)?;
+
+        let (x, y, z) = ecl_frm_eq((pos.0 - sun.0, pos.1 - sun.1, pos.2 - sun.2));
+
+        Some((x / self.AU, y / self.AU, z / self.AU))
+    }
+
+    fn moon_geocent_ecl_rect_coords_J2000(&self, JD: f64) -> Option<(f64, f64, f64)> {
+        self.item_pos(MOON, JD).map(ecl_frm_eq)
+    }
+}
+
+// Index of a planet's item, with the Earth-Moon barycenter in place of
+// the Earth
+fn planet_index(planet: &planet::Planet) -> usize {
+    match *planet {
+        planet::Planet::Mercury => 0,
+        planet::Planet::Venus   => 1,
+        planet::Planet::Earth   => 2,
+        planet::Planet::Mars    => 3,
+        planet::Planet::Jupiter => 4,
+        planet::Planet::Saturn  => 5,
+        planet::Planet::Uranus  => 6,
+        planet::Planet::Neptune => 7,
+    }
+}
+
+// Rotates equatorial coordinates of J2000.0 to the ecliptic
+fn ecl_frm_eq(p: (f64, f64, f64)) -> (f64, f64, f64) {
+    let e = angle::deg_frm_dms(23, 26, 21.448).to_radians();
+
+    (p.0, p.1*e.cos() + p.2*e.sin(), -p.1*e.sin() + p.2*e.cos())
+}
+
+// Parses a number written in the D notation of Fortran
+fn parse_num(text: &str) -> Result<f64, error::Error> {
+    text.replace('D', "E").parse::<f64>().map_err(|_| error::Error::InvalidFormat)
+}
+
+/// Holds a row of an ephemeris
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Row {
+    /// Julian (Ephemeris) day
+    pub JD: f64,
+    /// Geocentric apparent equatorial point, referred to the true
+    /// equator and equinox of the date *| in radians*
+    pub eq_point: coords::EqPoint,
+    /// Geocentric distance of the body when the light left it
+    /// *| in AU*
+    pub dist: f64,
+    /// Apparent magnitude, for the Sun and the planets
+    pub mag: Option<f64>,
+    /// Elongation from the Sun *| in radians*
+    pub elong: f64
+}
+
+/**
+Generator of the rows of an ephemeris of a body, at equal intervals
+of time
+
+The generator is an iterator, so that long ephemerides can be
+streamed. The positions of the Sun and the planets are corrected for
+light-time, annual aberration, precession and nutation, and that of
+the Moon for precession and nutation. The magnitudes of the planets
+are computed with the formulae of the Astronomical Almanac of 1984.
+
+The iteration ends at the end of the range, or when the provider has
+no coordinates for a time. The bodies are the Sun, the Moon and the
+planets other than the Earth, and there are no rows for the others.
+**/
+#[derive(Debug, Clone)]
+pub struct Generator<'a, P: Provider + 'a> {
+    provider: &'a P,
+    body: Body,
+    start: f64,
+    end: f64,
+    step: f64,
+    n: u32
+}
+
+impl<'a, P: Provider> Generator<'a, P> {
+    /**
+    Creates a generator of the rows of an ephemeris
+
+    # Arguments
+
+    * `provider`: The [Provider](./trait.Provider.html) of coordinates,
+                  such as `Analytic`
+    * `body`    : The [Body](../body/enum.Body.html)
+    * `JD_start`: Julian (Ephemeris) day of the first row
+    * `JD_end`  : Julian (Ephemeris) day after which there are no rows
+    * `step`    : Interval between the rows *| in days*
+    **/
+    pub fn new(provider: &'a P, body: Body, JD_start: f64, JD_end: f64, step: f64) -> Generator<'a, P> {
+        Generator {
+            provider,
+            body,
+            start : JD_start,
+            end   : JD_end,
+            step,
+            n     : 0
+        }
+    }
+
+    // The row of the ephemeris for a Julian day
+    fn row(&self, JD: f64) -> Option<Row> {
+        row(self.provider, &self.body, JD)
+    }
+}
+
+impl<'a, P: Provider> Iterator for Generator<'a, P> {
+    type Item = Row;
+
+    fn next(&mut self) -> Option<Row> {
+        let JD = self.start + self.step * (self.n as f64);
+        if JD > self.end {
+            return None;
+        }
+        self.n += 1;
+
+        let row = self.row(JD);
+        if row.is_none() {
+            self.end = f64::MIN;
+        }
+
+        row
+    }
+}
+
+// The row of an apparent ephemeris of a body for a Julian day
+pub(crate) fn row<P: Provider>(provider: &P, body: &Body, JD: f64) -> Option<Row> {
+    row_and_geom_dist(provider, body, JD).map(|(row, _)| row)
+}
+
+// The row of an apparent ephemeris of a body for a Julian day, and the
+// geometric geocentric distance of the body at that day
+pub(crate) fn row_and_geom_dist<P: Provider>(provider: &P, body: &Body, JD: f64) -> Option<(Row, f64)> {
+    let earth = provider.heliocent_ecl_rect_coords_J2000(&planet::Planet::Earth, JD)?;
+    let sun = (-earth.0, -earth.1, -earth.2);
+
+    // geometric geocentric position, corrected for light-time
+    let geocent = |tau: f64| -> Option<(f64, f64, f64)> {
+        match *body {
+            Body::Sun => Some(sun),
+            Body::Moon => provider.moon_geocent_ecl_rect_coords_J2000(JD - tau).map(|p| {
+                let km_per_AU = consts::ASTRONOMICAL_UNIT / 1000.0;
+                (p.0 / km_per_AU, p.1 / km_per_AU, p.2 / km_per_AU)
+            }),
+            Body::Planet(planet::Planet::Earth) => None,
+            Body::Planet(ref planet) => provider.heliocent_ecl_rect_coords_J2000(planet, JD - tau)
+                .map(|p| (p.0 + sun.0, p.1 + sun.1, p.2 + sun.2)),
+            Body::Pluto | Body::Star(_) => None
+        }
+    };
+    let mut p = geocent(0.0)?;
+    let geom_dist = norm(p);
+    for _ in 0..3 {
+        p = geocent(LIGHT_TIME_PER_AU * norm(p))?;
+    }
+    let dist = norm(p);
+
+    let (long, lat) = precess::precess_ecl_coords (
+        p.1.atan2(p.0), (p.2 / dist).asin(), 2451545.0, JD
+    );
+    let (nut_in_long, nut_in_oblq) = nutation::nutation(JD);
+    let true_oblq = ecliptic::mn_oblq_IAU(JD) + nut_in_oblq;
+    let long = long + nut_in_long;
+
+    let mut eq_point = coords::EqPoint {
+        asc: coords::asc_frm_ecl(long, lat, true_oblq),
+        dec: coords::dec_frm_ecl(long, lat, true_oblq)
+    };
+    if *body != Body::Moon {
+        let (d_asc, d_dec) = aberr::stell_aberr_in_eq_coords(&eq_point, JD);
+        eq_point.asc += d_asc;
+        eq_point.dec += d_dec;
+    }
+    eq_point.asc = angle::limit_to_two_PI(eq_point.asc);
+
+    let mag = match *body {
+        Body::Sun                => Some(SUN_MAG_AT_1_AU + 5.0*dist.log10()),
+        Body::Moon               => None,
+        Body::Planet(ref planet) => planet::apprnt_mag(planet, JD, &planet::MagFormula::Almanac84).ok(),
+        Body::Pluto | Body::Star(_) => None
+    };
+
+    let cos_elong = (p.0*sun.0 + p.1*sun.1 + p.2*sun.2) / (dist * norm(sun));
+
+    Some((Row {
+        JD,
+        eq_point,
+        dist,
+        mag,
+        elong: cos_elong.clamp(-1.0, 1.0).acos()
+    }, geom_dist))
+}
+
+// Light-time for a distance of 1 AU, in days
+const LIGHT_TIME_PER_AU: f64 = 0.0057755183;
+
+// Apparent magnitude of the Sun at a distance of 1 AU
+const SUN_MAG_AT_1_AU: f64 = -26.74;
+
+#[inline]
+fn norm(p: (f64, f64, f64)) -> f64 {
+    (p.0*p.0 + p.1*p.1 + p.2*p.2).sqrt()
+}
@@ -0,0 +1,98 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Errors of invalid inputs
+
+use std;
+use std::fmt;
+
+/// Represents an invalid input to a function
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Error {
+    /// An eccentricity outside the range of the orbit
+    InvalidEccentricity(f64),
+    /// A latitude beyond ±90 degrees *| in radians*
+    InvalidLatitude(f64),
+    /// A date that doesn't exist in its calendar, such as a day
+    /// beyond the end of its month, or a Gregorian date before the
+    /// reform of 1582 October 15
+    InvalidDate,
+    /// A value that isn't finite
+    NotFinite,
+    /// A Julian day outside the range of validity of a theory
+    DateOutOfRange(f64),
+    /// A text that doesn't follow the format it is parsed as
+    InvalidFormat,
+    /// A step, interval or period that isn't positive
+    NotPositive(f64),
+    /// A body the routine doesn't compute, such as the Earth for a
+    /// position seen from the Earth's center
+    InvalidBody,
+    /// Inputs for which a method finds no solution, such as
+    /// observations that no orbit fits
+    NoSolution,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::InvalidEccentricity(e) => write!(f, "invalid eccentricity {} for the orbit", e),
+            Error::InvalidLatitude(lat) => write!(f, "invalid latitude {} radians", lat),
+            Error::InvalidDate => write!(f, "invalid date for the calendar"),
+            Error::NotFinite => write!(f, "value isn't finite"),
+            Error::DateOutOfRange(JD) => write!(f, "Julian day {} is outside the range of the theory", JD),
+            Error::InvalidFormat => write!(f, "invalid format of the text"),
+            Error::NotPositive(x) => write!(f, "{} isn't positive", x),
+            Error::InvalidBody => write!(f, "invalid body for the routine"),
+            Error::NoSolution => write!(f, "no solution for the inputs"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// Checks that a latitude is within ±90 degrees
+pub(crate) fn check_lat(lat: f64) -> Result<(), Error> {
+
+    if !lat.is_finite() {
+        Err(Error::NotFinite)
+    } else if lat.abs() > std::f64::consts::FRAC_PI_2 {
+        Err(Error::InvalidLatitude(lat))
+    } else {
+        Ok(())
+    }
+
+}
+
+// Checks that a step, interval or period is positive and finite
+pub(crate) fn check_positive(x: f64) -> Result<(), Error> {
+
+    if !x.is_finite() {
+        Err(Error::NotFinite)
+    } else if x <= 0.0 {
+        Err(Error::NotPositive(x))
+    } else {
+        Ok(())
+    }
+
+}
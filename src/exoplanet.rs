@@ -0,0 +1,386 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Transits of exoplanets
+
+use coords;
+use ecliptic;
+use lunar;
+use observer;
+use planet;
+use precess;
+use sun;
+use time;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+// Light-time for unit distance *| in days*
+const AU_LIGHT_TIME: f64 = 0.0057755183;
+
+const J2000: f64 = 2451545.0;
+
+/// Holds the ephemeris of the transits of an exoplanet
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TransitEphemeris {
+    /// Time of a mid-transit, in Barycentric Julian Day (TDB)
+    pub T0: f64,
+    /// Orbital period *| in days*
+    pub P: f64,
+    /// Duration of the transit, from the first to the fourth contact
+    /// *| in days*
+    pub duration: f64,
+    /// Right ascension of the host star, referred to the mean
+    /// equator and equinox of J2000.0 *| in radians*
+    pub asc: f64,
+    /// Declination of the host star, referred to the mean equator
+    /// and equinox of J2000.0 *| in radians*
+    pub dec: f64
+}
+
+/// Holds the conditions for observing a transit
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Constraints {
+    /// Least altitude of the host star *| in radians*
+    pub min_alt: f64,
+    /// Greatest altitude of the Sun, such as `-18°` for
+    /// astronomical darkness *| in radians*
+    pub max_sun_alt: f64,
+    /// Least angular distance of the host star from the Moon
+    /// *| in radians*
+    pub min_moon_sepr: f64
+}
+
+/// Holds a transit of an exoplanet, and it's observability
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TransitWindow {
+    /// Number of the transit, counted from the one at `T0`
+    pub epoch: i64,
+    /// Time of mid-transit, in Barycentric Julian Day (TDB)
+    pub mid_BJD: f64,
+    /// Julian (Ephemeris) day of the first contact, at the Earth
+    pub ingress: f64,
+    /// Julian (Ephemeris) day of mid-transit, at the Earth
+    pub mid: f64,
+    /// Julian (Ephemeris) day of the fourth contact, at the Earth
+    pub egress: f64,
+    /// Whether the first contact is observable
+    pub ingress_visible: bool,
+    /// Whether mid-transit is observable
+    pub mid_visible: bool,
+    /// Whether the fourth contact is observable
+    pub egress_visible: bool
+}
+
+/**
+Computes the Barycentric Julian Day of an event on a star, as
+observed at the Earth
+
+The time is corrected for the light-time across the Earth's orbit
+(the Rømer delay) towards the star, and for the periodic difference
+between TDB and TT. The Earth's barycentric position is found from
+VSOP87 and [sun::barycent_rect_coords()](../sun/fn.barycent_rect_coords.html),
+so that the result is good to a few tenths of a second.
+
+# Returns
+
+* `BJD`: Barycentric Julian Day (TDB)
+
+# Arguments
+
+* `JD` : Julian (Ephemeris) day of observation (TT)
+* `asc`: Right ascension of the star, referred to the mean
+  equator and equinox of J2000.0 *| in radians*
+* `dec`: Declination of the star, referred to the mean equator and
+  equinox of J2000.0 *| in radians*
+**/
+pub fn bjd_frm_jd(JD: f64, asc: f64, dec: f64) -> f64 {
+
+    JD + bary_corr(JD, asc, dec, (0.0, 0.0, 0.0))
+
+}
+
+/**
+Computes the Julian (Ephemeris) day at which an event on a star,
+given in Barycentric Julian Day, is observed at the Earth
+
+This is the inverse of [bjd_frm_jd()](./fn.bjd_frm_jd.html).
+
+# Returns
+
+* `JD`: Julian (Ephemeris) day of observation (TT)
+
+# Arguments
+
+* `BJD`: Barycentric Julian Day (TDB)
+* `asc`: Right ascension of the star, referred to the mean
+  equator and equinox of J2000.0 *| in radians*
+* `dec`: Declination of the star, referred to the mean equator and
+  equinox of J2000.0 *| in radians*
+**/
+pub fn jd_frm_bjd(BJD: f64, asc: f64, dec: f64) -> f64 {
+
+    // the correction changes by less than a millisecond in 8 minutes
+    let JD = BJD - bary_corr(BJD, asc, dec, (0.0, 0.0, 0.0));
+
+    BJD - bary_corr(JD, asc, dec, (0.0, 0.0, 0.0))
+
+}
+
+/**
+Computes the Barycentric Julian Day of an event on a star, as
+observed from a place on the Earth
+
+This is [bjd_frm_jd()](./fn.bjd_frm_jd.html), with the light-time
+across the Earth to the observer added. That is upto `21`
+milliseconds, which matters for the timing of pulsars and of short
+transits.
+
+# Returns
+
+* `BJD`: Barycentric Julian Day (TDB)
+
+# Arguments
+
+* `JD`      : Julian (Ephemeris) day of observation (TT)
+* `asc`     : Right ascension of the star, referred to the mean
+  equator and equinox of J2000.0 *| in radians*
+* `dec`     : Declination of the star, referred to the mean equator
+  and equinox of J2000.0 *| in radians*
+* `observer`: The [Observer](../observer/struct.Observer.html)
+**/
+pub fn topocent_bjd_frm_jd(JD: f64, asc: f64, dec: f64, observer: &observer::Observer) -> f64 {
+
+    let (obs_pos, _) = observer.geocent_rect_state(JD);
+
+    JD + bary_corr(JD, asc, dec, obs_pos)
+
+}
+
+/**
+Computes the Heliocentric Julian Day of an event on a star, as
+observed at the Earth
+
+The time is corrected for the light-time across the Earth's orbit
+towards the star, as seen from the center of the Sun rather than from
+the barycenter of the solar system. This is the older convention of
+variable star and eclipsing binary ephemerides, and differs from the
+Barycentric Julian Day by upto about `8` seconds. The result is in the
+same time scale as `JD`, which is traditionally UTC.
+
+# Returns
+
+* `HJD`: Heliocentric Julian Day
+
+# Arguments
+
+* `JD` : Julian day of observation
+* `asc`: Right ascension of the star, referred to the mean
+  equator and equinox of J2000.0 *| in radians*
+* `dec`: Declination of the star, referred to the mean equator and
+  equinox of J2000.0 *| in radians*
+**/
+pub fn hjd_frm_jd(JD: f64, asc: f64, dec: f64) -> f64 {
+
+    JD + helio_corr(JD, asc, dec, (0.0, 0.0, 0.0))
+
+}
+
+/**
+Computes the Julian day at which an event on a star, given in
+Heliocentric Julian Day, is observed at the Earth
+
+This is the inverse of [hjd_frm_jd()](./fn.hjd_frm_jd.html).
+
+# Returns
+
+* `JD`: Julian day of observation
+
+# Arguments
+
+* `HJD`: Heliocentric Julian Day
+* `asc`: Right ascension of the star, referred to the mean
+  equator and equinox of J2000.0 *| in radians*
+* `dec`: Declination of the star, referred to the mean equator and
+  equinox of J2000.0 *| in radians*
+**/
+pub fn jd_frm_hjd(HJD: f64, asc: f64, dec: f64) -> f64 {
+
+    let JD = HJD - helio_corr(HJD, asc, dec, (0.0, 0.0, 0.0));
+
+    HJD - helio_corr(JD, asc, dec, (0.0, 0.0, 0.0))
+
+}
+
+/**
+Computes the transits of an exoplanet in an interval of time, and
+their observability by an observer on the Earth
+
+# Returns
+
+* `windows`: The [TransitWindow](./struct.TransitWindow.html)s of the
+  transits whose mid-transit falls in the interval, whether observable
+  or not
+
+# Arguments
+
+* `ephemeris`     : The exoplanet's [TransitEphemeris](./struct.TransitEphemeris.html)
+* `observer`      : The [Observer](../observer/struct.Observer.html)
+* `constraints`   : The [Constraints](./struct.Constraints.html) for
+  observing
+* `JD_start`      : Julian (Ephemeris) day of the start of the interval
+* `JD_end`        : Julian (Ephemeris) day of the end of the interval
+**/
+pub fn transit_windows (
+
+    ephemeris      : &TransitEphemeris,
+    observer       : &observer::Observer,
+    constraints    : &Constraints,
+    JD_start       : f64,
+    JD_end         : f64
+
+) -> Vec<TransitWindow> {
+
+    let (asc, dec) = (ephemeris.asc, ephemeris.dec);
+    let first = ((bjd_frm_jd(JD_start, asc, dec) - ephemeris.T0) / ephemeris.P).ceil() as i64;
+    let last = ((bjd_frm_jd(JD_end, asc, dec) - ephemeris.T0) / ephemeris.P).floor() as i64;
+
+    let visible = |JD: f64| is_observable(ephemeris, observer, constraints, JD);
+
+    (first..(last + 1)).map(|epoch| {
+        let mid_BJD = ephemeris.T0 + (epoch as f64)*ephemeris.P;
+        let mid = jd_frm_bjd(mid_BJD, asc, dec);
+        let (ingress, egress) = (mid - ephemeris.duration/2.0, mid + ephemeris.duration/2.0);
+
+        TransitWindow {
+            epoch,
+            mid_BJD,
+            ingress,
+            mid,
+            egress,
+            ingress_visible : visible(ingress),
+            mid_visible     : visible(mid),
+            egress_visible  : visible(egress)
+        }
+    }).collect()
+
+}
+
+/**
+Checks if the host star of an exoplanet is observable at a time
+
+# Returns
+
+* `observable`: Whether the star's altitude, the Sun's altitude and
+  the star's distance from the Moon all meet the constraints.
+  Refraction and parallax are neglected.
+
+# Arguments
+
+* `ephemeris`     : The exoplanet's [TransitEphemeris](./struct.TransitEphemeris.html)
+* `observer`      : The [Observer](../observer/struct.Observer.html)
+* `constraints`   : The [Constraints](./struct.Constraints.html) for
+  observing
+* `JD`            : Julian (Ephemeris) day
+**/
+pub fn is_observable (
+
+    ephemeris      : &TransitEphemeris,
+    observer       : &observer::Observer,
+    constraints    : &Constraints,
+    JD             : f64
+
+) -> bool {
+
+    let JD_UT = time::ut_frm_tt(JD);
+    let sidr = time::mn_sidr(JD_UT);
+    let oblq = ecliptic::mn_oblq_laskar(JD);
+
+    let alt = |asc: f64, dec: f64| {
+        let H = coords::hr_angl_frm_observer_long(sidr, observer.long, asc);
+        coords::alt_frm_eq(H, dec, observer.lat)
+    };
+
+    let (asc, dec) = precess::precess_eq_coords(ephemeris.asc, ephemeris.dec, J2000, JD);
+    if alt(asc, dec) < constraints.min_alt {
+        return false;
+    }
+
+    let (sun_point, _) = sun::geocent_ecl_pos(JD);
+    let sun_asc = coords::asc_frm_ecl(sun_point.long, sun_point.lat, oblq);
+    let sun_dec = coords::dec_frm_ecl(sun_point.long, sun_point.lat, oblq);
+    if alt(sun_asc, sun_dec) > constraints.max_sun_alt {
+        return false;
+    }
+
+    let (moon_point, _) = lunar::geocent_ecl_pos(JD);
+    let moon_asc = coords::asc_frm_ecl(moon_point.long, moon_point.lat, oblq);
+    let moon_dec = coords::dec_frm_ecl(moon_point.long, moon_point.lat, oblq);
+
+    coords::EqPoint { asc, dec }.anglr_sepr(&coords::EqPoint { asc: moon_asc, dec: moon_dec })
+        >= constraints.min_moon_sepr
+
+}
+
+// BJD - JD, in days, for an observer at a geocentric position referred
+// to the mean equator of J2000.0, in AU
+fn bary_corr(JD: f64, asc: f64, dec: f64, obs_pos: (f64, f64, f64)) -> f64 {
+
+    let (sun_x, sun_y, sun_z) = sun::barycent_rect_coords(JD);
+
+    helio_corr(JD, asc, dec, obs_pos)
+  + AU_LIGHT_TIME * dot(eq_frm_ecl((sun_x, sun_y, sun_z)), unit_vec(asc, dec))
+  + time::tdb_minus_tt(JD)/86400.0
+
+}
+
+// HJD - JD, in days, for an observer at a geocentric position referred
+// to the mean equator of J2000.0, in AU
+fn helio_corr(JD: f64, asc: f64, dec: f64, obs_pos: (f64, f64, f64)) -> f64 {
+
+    let (x, y, z) = eq_frm_ecl(planet::heliocent_ecl_rect_coords_J2000(&planet::Planet::Earth, JD));
+
+    AU_LIGHT_TIME * dot((x + obs_pos.0, y + obs_pos.1, z + obs_pos.2), unit_vec(asc, dec))
+
+}
+
+fn eq_frm_ecl((x, y, z): (f64, f64, f64)) -> (f64, f64, f64) {
+
+    let oblq = ecliptic::mn_oblq_laskar(J2000);
+
+    (x, y*oblq.cos() - z*oblq.sin(), y*oblq.sin() + z*oblq.cos())
+
+}
+
+fn unit_vec(asc: f64, dec: f64) -> (f64, f64, f64) {
+
+    (dec.cos()*asc.cos(), dec.cos()*asc.sin(), dec.sin())
+
+}
+
+fn dot(a: (f64, f64, f64), b: (f64, f64, f64)) -> f64 {
+
+    a.0*b.0 + a.1*b.1 + a.2*b.2
+
+}
@@ -0,0 +1,189 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+
+//! Floating point types the algorithms can be computed in
+//!
+//! These functions of the crate are generic over the
+//! [Float](./trait.Float.html) trait:
+//!
+//! * the reduction of angles and the angular separation, in
+//!   [angle](../angle/index.html)
+//! * the transformations between equatorial, ecliptic and horizontal
+//!   coordinates, and the parallactic angle, in
+//!   [coords](../coords/index.html)
+//! * the conversions between the anomalies of an elliptic orbit, in
+//!   [orbit::elliptic](../orbit/elliptic/index.html)
+//! * the interpolation of tabulated values, in
+//!   [interpol](../interpol/index.html)
+//!
+//! The trait is implemented here for `f32`, for GPUs and embedded
+//! processors without double precision, and for `f64`. Extended
+//! precision types, such as double-double arithmetic for long-term
+//! integrations, can be used by implementing the trait for them.
+//!
+//! The theories of the crate, such as VSOP87, ELP-2000/82 and the
+//! nutation series, aren't generic, and are computed in `f64` only.
+
+use std;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+/// A floating point type the generic algorithms can be computed in
+pub trait Float:
+    Copy + PartialOrd + std::fmt::Debug
+  + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+  + Div<Output = Self> + Rem<Output = Self> + Neg<Output = Self>
+  + AddAssign + SubAssign + MulAssign + DivAssign
+{
+    /// Archimedes' constant π
+    const PI: Self;
+
+    /// The value nearest to an `f64`
+    fn from_f64(x: f64) -> Self;
+    /// The nearest `f64`
+    fn to_f64(self) -> f64;
+
+    // The methods of the same names of f64
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+    fn copysign(self, sign: Self) -> Self;
+    fn min(self, other: Self) -> Self;
+    fn max(self, other: Self) -> Self;
+    fn is_finite(self) -> bool;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+    fn trunc(self) -> Self;
+    fn fract(self) -> Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
+    fn sqrt(self) -> Self;
+    fn cbrt(self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn log10(self) -> Self;
+    fn to_radians(self) -> Self;
+    fn to_degrees(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn sin_cos(self) -> (Self, Self);
+    fn asin(self) -> Self;
+    fn acos(self) -> Self;
+    fn atan(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn sinh(self) -> Self;
+    fn cosh(self) -> Self;
+    fn tanh(self) -> Self;
+    fn atanh(self) -> Self;
+}
+
+// Implements the trait with the methods of the type, which core has
+// for some of them, and std, or else libm, for the others
+macro_rules! impl_float {
+    (
+        $t: ident,
+        [$(($un: ident, $un_libm: ident)),*],
+        [$(($bin: ident, $bin_libm: ident)),*]
+    ) => {
+        impl Float for $t {
+            const PI: $t = std::$t::consts::PI;
+
+            #[inline] fn from_f64(x: f64) -> $t { x as $t }
+            #[inline] fn to_f64(self) -> f64 { self as f64 }
+
+            #[inline] fn abs(self) -> $t { $t::abs(self) }
+            #[inline] fn signum(self) -> $t { $t::signum(self) }
+            #[inline] fn copysign(self, sign: $t) -> $t { $t::copysign(self, sign) }
+            #[inline] fn min(self, other: $t) -> $t { $t::min(self, other) }
+            #[inline] fn max(self, other: $t) -> $t { $t::max(self, other) }
+            #[inline] fn is_finite(self) -> bool { $t::is_finite(self) }
+            #[inline] fn to_radians(self) -> $t { $t::to_radians(self) }
+            #[inline] fn to_degrees(self) -> $t { $t::to_degrees(self) }
+
+            $(
+                #[cfg(feature = "std")]
+                #[inline] fn $un(self) -> $t { $t::$un(self) }
+                #[cfg(not(feature = "std"))]
+                #[inline] fn $un(self) -> $t { libm::$un_libm(self) }
+            )*
+
+            $(
+                #[cfg(feature = "std")]
+                #[inline] fn $bin(self, other: $t) -> $t { $t::$bin(self, other) }
+                #[cfg(not(feature = "std"))]
+                #[inline] fn $bin(self, other: $t) -> $t { libm::$bin_libm(self, other) }
+            )*
+
+            #[cfg(feature = "std")]
+            #[inline] fn fract(self) -> $t { $t::fract(self) }
+            #[cfg(not(feature = "std"))]
+            #[inline] fn fract(self) -> $t { self - Float::trunc(self) }
+
+            #[cfg(feature = "std")]
+            #[inline] fn rem_euclid(self, rhs: $t) -> $t { $t::rem_euclid(self, rhs) }
+            #[cfg(not(feature = "std"))]
+            #[inline] fn rem_euclid(self, rhs: $t) -> $t {
+                let r = self % rhs;
+                if r < 0.0 { r + rhs.abs() } else { r }
+            }
+
+            #[cfg(feature = "std")]
+            #[inline] fn powi(self, n: i32) -> $t { $t::powi(self, n) }
+            #[cfg(not(feature = "std"))]
+            #[inline] fn powi(self, n: i32) -> $t { Float::powf(self, n as $t) }
+
+            #[cfg(feature = "std")]
+            #[inline] fn sin_cos(self) -> ($t, $t) { $t::sin_cos(self) }
+            #[cfg(not(feature = "std"))]
+            #[inline] fn sin_cos(self) -> ($t, $t) { (Float::sin(self), Float::cos(self)) }
+        }
+    };
+}
+
+impl_float!(
+    f64,
+    [
+        (floor, floor), (ceil, ceil), (round, round), (trunc, trunc),
+        (sqrt, sqrt),
+        (cbrt, cbrt), (exp, exp), (ln, log), (log10, log10),
+        (sin, sin), (cos, cos), (tan, tan), (asin, asin), (acos, acos),
+        (atan, atan), (sinh, sinh), (cosh, cosh), (tanh, tanh),
+        (atanh, atanh)
+    ],
+    [(hypot, hypot), (powf, pow), (atan2, atan2)]
+);
+
+impl_float!(
+    f32,
+    [
+        (floor, floorf), (ceil, ceilf), (round, roundf), (trunc, truncf),
+        (sqrt, sqrtf),
+        (cbrt, cbrtf), (exp, expf), (ln, logf), (log10, log10f),
+        (sin, sinf), (cos, cosf), (tan, tanf), (asin, asinf),
+        (acos, acosf), (atan, atanf), (sinh, sinhf), (cosh, coshf),
+        (tanh, tanhf), (atanh, atanhf)
+    ],
+    [(hypot, hypotf), (powf, powf), (atan2, atan2f)]
+);
@@ -0,0 +1,92 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+
+//! Fundamental arguments of the theories of the Moon and of nutation
+//!
+//! The theories of the Moon's position, of nutation and of the Sun's
+//! apparent position all depend on the same few angles: the mean
+//! elongation of the Moon from the Sun, the mean anomalies of the Sun
+//! and the Moon, the Moon's argument of latitude, and the longitude of
+//! the Moon's mean ascending node. Computing them once with
+//! [FundamentalArgs::new()](./struct.FundamentalArgs.html#method.new)
+//! and passing them to the `_frm_args` variants of those routines
+//! avoids computing them again for each routine called at the same
+//! instant.
+
+use lunar;
+use time;
+
+/// Holds the fundamental arguments at an instant
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FundamentalArgs {
+    /// Julian (Ephemeris) day
+    pub JD: f64,
+    /// Julian century, from J2000.0
+    pub JC: f64,
+    /// Mean elongation of the Moon *| in radians*
+    pub D: f64,
+    /// Mean anomaly of the Sun *| in radians*
+    pub M: f64,
+    /// Mean anomaly of the Moon *| in radians*
+    pub M1: f64,
+    /// Argument of latitude of the Moon *| in radians*
+    pub F: f64,
+    /// Longitude of the mean ascending node of the Moon *| in radians*
+    pub om: f64,
+    /// Mean longitude of the Moon *| in radians*
+    pub L1: f64,
+    /// Factor for the decreasing eccentricity of the Earth's orbit, in
+    /// the terms of the Moon's position that depend on `M`
+    pub E: f64
+}
+
+impl FundamentalArgs {
+    /**
+    Computes the fundamental arguments at an instant
+
+    The expressions are those of the Moon's position in the *Meeus*
+    book, from the ELP-2000/82 theory.
+
+    # Arguments
+
+    * `JD`: Julian (Ephemeris) day
+    **/
+    pub fn new(JD: f64) -> FundamentalArgs
+    {
+        let JC = time::julian_cent(JD);
+        let (D, M, M1) = lunar::DMM1(JC);
+
+        FundamentalArgs {
+            JD,
+            JC,
+            D,
+            M,
+            M1,
+            F: lunar::F(JC),
+            om: lunar::mn_ascend_node(JC),
+            L1: lunar::mn_long(JC),
+            E: lunar::E(JC)
+        }
+    }
+}
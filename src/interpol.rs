@@ -22,8 +22,12 @@ THE SOFTWARE.
 
 //! Interpolation of intermediate values of functions
 
+use float::Float;
+#[cfg(not(feature = "std"))]
+use math::*;
+
 /**
-Interpolates an intermediate value of a function from three of it's
+Interpolates an intermediate value of a function from three of its
 given values
 
 # Returns
@@ -38,19 +42,18 @@ given values
 * `n` : Interpolating factor, measured from the central value
         `y2`, positively towards `y3`
 **/
-
 #[inline]
-pub fn three_values(y1: f64, y2: f64, y3: f64, n: f64) -> f64
+pub fn three_values<T: Float>(y1: T, y2: T, y3: T, n: T) -> T
 {
     let a = y2 - y1;
     let b = y3 - y2;
     let c = b - a;
 
-    y2 + n*(a + b + n*c)/2.0
+    y2 + n*(a + b + n*c)/T::from_f64(2.0)
 }
 
 /**
-Interpolates an intermediate value of a function from five of it's
+Interpolates an intermediate value of a function from five of its
 given values
 
 # Returns
@@ -94,3 +97,427 @@ pub fn five_values(y1: f64, y2: f64, y3: f64, y4: f64, y5: f64, n: f64) -> f64
         k
     ) / 2.0
 }
+
+/**
+Interpolates the extremum of a function from three of its given
+values
+
+# Returns
+
+`(extremum, n)`
+
+* `extremum`: Extreme value of the function
+* `n`       : Interpolating factor of the extremum, measured from
+              the central value `y2`, positively towards `y3`
+
+# Arguments
+
+* `y1`: Value 1 of the function
+* `y2`: Value 2 of the function
+* `y3`: Value 3 of the function
+**/
+pub fn extremum_three_values(y1: f64, y2: f64, y3: f64) -> (f64, f64)
+{
+    let a = y2 - y1;
+    let b = y3 - y2;
+    let c = b - a;
+
+    (
+        y2 - (a + b)*(a + b)/(8.0 * c),
+        -(a + b) / (2.0 * c)
+    )
+}
+
+/**
+Interpolates the zero of a function from three of its given values
+
+# Returns
+
+* `Some(n)`: Interpolating factor of the zero, measured from the
+             central value `y2`, positively towards `y3`, or
+* `None`   : if the interpolating parabola has no zero that can be
+             reached from `y2`, ie: if the iterations don't converge
+
+# Arguments
+
+* `y1`: Value 1 of the function
+* `y2`: Value 2 of the function
+* `y3`: Value 3 of the function
+**/
+pub fn zero_three_values(y1: f64, y2: f64, y3: f64) -> Option<f64>
+{
+    let a = y2 - y1;
+    let b = y3 - y2;
+    let c = b - a;
+
+    newton(|n| (2.0*y2 + n*(a + b + c*n), a + b + 2.0*c*n))
+}
+
+/**
+Interpolates the zero of a function from five of its given values
+
+# Returns
+
+* `Some(n)`: Interpolating factor of the zero, measured from the
+             central value `y3`, positively towards `y4`, or
+* `None`   : if the interpolating polynomial has no zero that can be
+             reached from `y3`, ie: if the iterations don't converge
+
+# Arguments
+
+* `y1`: Value 1 of the function
+* `y2`: Value 2 of the function
+* `y3`: Value 3 of the function
+* `y4`: Value 4 of the function
+* `y5`: Value 5 of the function
+**/
+pub fn zero_five_values(y1: f64, y2: f64, y3: f64, y4: f64, y5: f64) -> Option<f64>
+{
+    let a = y2 - y1;
+    let b = y3 - y2;
+    let c = y4 - y3;
+    let d = y5 - y4;
+
+    let e = b - a;
+    let f = c - b;
+    let g = d - c;
+
+    let h = f - e;
+    let j = g - f;
+
+    let k = (j - h) / 12.0;
+    let h_j_12 = (h + j) / 6.0;
+
+    newton(|n| (
+        y3 + Horner_eval!(n, 0.0, b + c - h_j_12, f - k, h_j_12, k)/2.0,
+        Horner_eval!(n, b + c - h_j_12, 2.0*(f - k), 3.0*h_j_12, 4.0*k)/2.0
+    ))
+}
+
+// Finds a zero of a function from n = 0 by Newton's method, given the
+// function's value and derivative at n
+fn newton<F>(val_and_deriv: F) -> Option<f64> where F: Fn(f64) -> (f64, f64)
+{
+    let mut n = 0.0;
+
+    for _ in 0..100 {
+        let (val, deriv) = val_and_deriv(n);
+        let delta_n = -val / deriv;
+        n += delta_n;
+        if !n.is_finite() {
+            return None;
+        }
+        if delta_n.abs() < 1e-12 {
+            return Some(n);
+        }
+    }
+
+    None
+}
+
+/**
+Interpolates an intermediate value of a function from any number of
+its given values, with Lagrange's formula
+
+The arguments needn't be equally spaced.
+
+# Returns
+
+* `interpol_val`: Intermediate value of the function
+
+# Arguments
+
+* `x`  : Arguments of the given values of the function
+* `y`  : Given values of the function, one for each argument
+* `arg`: Argument of the intermediate value
+**/
+pub fn lagrange<T: Float>(x: &[T], y: &[T], arg: T) -> T
+{
+    let mut val = T::from_f64(0.0);
+
+    for i in 0..x.len() {
+        let mut c = T::from_f64(1.0);
+        for j in 0..x.len() {
+            if j != i {
+                c *= (arg - x[j]) / (x[i] - x[j]);
+            }
+        }
+        val += c * y[i];
+    }
+
+    val
+}
+
+/**
+Interpolates an intermediate value of a function from any number of
+its given values and derivatives, with Hermite's formula
+
+# Returns
+
+* `interpol_val`: Intermediate value of the function
+
+# Arguments
+
+* `x`    : Arguments of the given values of the function
+* `y`    : Given values of the function, one for each argument
+* `deriv`: Given derivatives of the function, one for each argument
+* `arg`  : Argument of the intermediate value
+**/
+pub fn hermite<T: Float>(x: &[T], y: &[T], deriv: &[T], arg: T) -> T
+{
+    // Newton's divided differences over the doubled arguments, where
+    // the derivatives stand in for the differences of equal ones
+    let n = 2 * x.len();
+    let z: Vec<T> = (0..n).map(|i| x[i / 2]).collect();
+    let mut q: Vec<T> = (0..n).map(|i| y[i / 2]).collect();
+
+    for order in 1..n {
+        for i in (order..n).rev() {
+            q[i] = if order == 1 && i % 2 == 1 {
+                deriv[i / 2]
+            } else {
+                (q[i] - q[i - 1]) / (z[i] - z[i - order])
+            };
+        }
+    }
+
+    let mut val = q[n - 1];
+    for i in (0..(n - 1)).rev() {
+        val = val*(arg - z[i]) + q[i];
+    }
+
+    val
+}
+
+/// Represents a cubic spline through the given values of a function
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CubicSpline {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    second_deriv: Vec<f64>
+}
+
+impl CubicSpline {
+    /**
+    Returns the natural cubic spline through the given values of a
+    function, which has no curvature at the first and last arguments
+
+    # Arguments
+
+    * `x`: Arguments of the given values of the function, in
+           increasing order and at least two
+    * `y`: Given values of the function, one for each argument
+    **/
+    pub fn natural(x: &[f64], y: &[f64]) -> CubicSpline
+    {
+        CubicSpline::new(x, y, None)
+    }
+
+    /**
+    Returns the clamped cubic spline through the given values of a
+    function, which has the given derivatives at the first and last
+    arguments
+
+    # Arguments
+
+    * `x`     : Arguments of the given values of the function, in
+                increasing order and at least two
+    * `y`     : Given values of the function, one for each argument
+    * `first` : Derivative of the function at the first argument
+    * `last`  : Derivative of the function at the last argument
+    **/
+    pub fn clamped(x: &[f64], y: &[f64], first: f64, last: f64) -> CubicSpline
+    {
+        CubicSpline::new(x, y, Some((first, last)))
+    }
+
+    fn new(x: &[f64], y: &[f64], ends: Option<(f64, f64)>) -> CubicSpline
+    {
+        let n = x.len();
+        let h: Vec<f64> = (0..(n - 1)).map(|i| x[i + 1] - x[i]).collect();
+
+        // the second derivatives at the arguments, from the
+        // tridiagonal system of the spline, solved by Thomas' algorithm
+        let mut diag = vec![1.0; n];
+        let mut upper = vec![0.0; n];
+        let mut lower = vec![0.0; n];
+        let mut rhs = vec![0.0; n];
+        if let Some((first, last)) = ends {
+            diag[0] = 2.0 * h[0];
+            upper[0] = h[0];
+            rhs[0] = 6.0 * ((y[1] - y[0])/h[0] - first);
+            diag[n - 1] = 2.0 * h[n - 2];
+            lower[n - 1] = h[n - 2];
+            rhs[n - 1] = 6.0 * (last - (y[n - 1] - y[n - 2])/h[n - 2]);
+        }
+        for i in 1..(n - 1) {
+            lower[i] = h[i - 1];
+            diag[i] = 2.0 * (h[i - 1] + h[i]);
+            upper[i] = h[i];
+            rhs[i] = 6.0 * ((y[i + 1] - y[i])/h[i] - (y[i] - y[i - 1])/h[i - 1]);
+        }
+
+        for i in 1..n {
+            let m = lower[i] / diag[i - 1];
+            diag[i] -= m * upper[i - 1];
+            rhs[i] -= m * rhs[i - 1];
+        }
+        let mut second_deriv = vec![0.0; n];
+        second_deriv[n - 1] = rhs[n - 1] / diag[n - 1];
+        for i in (0..(n - 1)).rev() {
+            second_deriv[i] = (rhs[i] - upper[i]*second_deriv[i + 1]) / diag[i];
+        }
+
+        CubicSpline {
+            x: x.to_vec(),
+            y: y.to_vec(),
+            second_deriv
+        }
+    }
+
+    /**
+    Interpolates an intermediate value of the function
+
+    Arguments outside the given ones are extrapolated from the
+    nearest piece of the spline.
+
+    # Arguments
+
+    * `arg`: Argument of the intermediate value
+    **/
+    pub fn value(&self, arg: f64) -> f64
+    {
+        let i = self.piece(arg);
+        let h = self.x[i + 1] - self.x[i];
+        let a = (self.x[i + 1] - arg) / h;
+        let b = (arg - self.x[i]) / h;
+
+        a*self.y[i] + b*self.y[i + 1]
+      + ((a*a*a - a)*self.second_deriv[i] + (b*b*b - b)*self.second_deriv[i + 1]) * h*h / 6.0
+    }
+
+    /**
+    Interpolates the derivative of the function
+
+    # Arguments
+
+    * `arg`: Argument of the derivative
+    **/
+    pub fn deriv(&self, arg: f64) -> f64
+    {
+        let i = self.piece(arg);
+        let h = self.x[i + 1] - self.x[i];
+        let a = (self.x[i + 1] - arg) / h;
+        let b = (arg - self.x[i]) / h;
+
+        (self.y[i + 1] - self.y[i]) / h
+      - ((3.0*a*a - 1.0)*self.second_deriv[i] - (3.0*b*b - 1.0)*self.second_deriv[i + 1]) * h / 6.0
+    }
+
+    /**
+    Finds the extrema of the function within the given arguments
+
+    # Returns
+
+    * `extrema`: `(arg, value)` of each extremum, in increasing order
+                 of the argument
+    **/
+    pub fn extrema(&self) -> Vec<(f64, f64)>
+    {
+        let mut extrema = Vec::new();
+
+        for i in 0..(self.x.len() - 1) {
+            for arg in self.turning_points(i) {
+                extrema.push((arg, self.value(arg)));
+            }
+        }
+
+        extrema
+    }
+
+    /**
+    Finds the zeros of the function within the given arguments
+
+    # Returns
+
+    * `zeros`: Argument of each zero, in increasing order
+    **/
+    pub fn zeros(&self) -> Vec<f64>
+    {
+        let mut zeros = Vec::new();
+
+        for i in 0..(self.x.len() - 1) {
+            // each piece is monotonic between its turning points,
+            // and so has at most one zero between two of them
+            let mut bounds = vec![self.x[i]];
+            bounds.extend(self.turning_points(i));
+            bounds.push(self.x[i + 1]);
+
+            for w in bounds.windows(2) {
+                let (mut lo, mut hi) = (w[0], w[1]);
+                let (y_lo, y_hi) = (self.value(lo), self.value(hi));
+                if y_lo == 0.0 && zeros.last() != Some(&lo) {
+                    zeros.push(lo);
+                }
+                if y_lo * y_hi >= 0.0 {
+                    continue;
+                }
+                while hi - lo > 1e-12 * (1.0 + lo.abs()) {
+                    let mid = (lo + hi) / 2.0;
+                    if self.value(mid) * y_lo > 0.0 { lo = mid; } else { hi = mid; }
+                }
+                zeros.push((lo + hi) / 2.0);
+            }
+        }
+        let last = self.x[self.x.len() - 1];
+        if self.value(last) == 0.0 && zeros.last() != Some(&last) {
+            zeros.push(last);
+        }
+
+        zeros
+    }
+
+    fn piece(&self, arg: f64) -> usize
+    {
+        (0..(self.x.len() - 1)).rev()
+                               .find(|&i| arg >= self.x[i])
+                               .unwrap_or(0)
+    }
+
+    // Arguments within the ith piece where its derivative, a
+    // quadratic, vanishes and changes sign
+    fn turning_points(&self, i: usize) -> Vec<f64>
+    {
+        let (x0, x1) = (self.x[i], self.x[i + 1]);
+        let h = x1 - x0;
+        let d0 = self.deriv(x0);
+        let dm = self.deriv(x0 + h/2.0);
+        let d1 = self.deriv(x1);
+
+        // the derivative as A*t^2 + B*t + C, with t from 0 to 1
+        let A = 2.0 * (d0 + d1 - 2.0*dm);
+        let B = d1 - d0 - A;
+        let C = d0;
+
+        let mut roots = Vec::new();
+        if A.abs() < 1e-15 * (B.abs() + C.abs()) {
+            if B != 0.0 {
+                roots.push(-C / B);
+            }
+        } else {
+            let disc = B*B - 4.0*A*C;
+            if disc > 0.0 {
+                let q = -(B + B.signum()*disc.sqrt()) / 2.0;
+                roots.push(q / A);
+                roots.push(C / q);
+            }
+        }
+        roots.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        roots.into_iter()
+             .filter(|&t| t > 0.0 && t < 1.0)
+             .map(|t| x0 + t*h)
+             .collect()
+    }
+}
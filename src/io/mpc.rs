@@ -0,0 +1,410 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+
+//! Orbits and observations of minor planets, in the formats of the
+//! Minor Planet Center
+//!
+//! Element lines of `MPCORB.DAT` are read into
+//! [orbit::Elements](../../orbit/struct.Elements.html), and optical
+//! observations in the 80-column format into
+//! [Observation](./struct.Observation.html)s.
+
+use angle;
+use error;
+use orbit;
+use time;
+#[cfg(not(feature = "std"))]
+use math::*;
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, string::ToString};
+
+/// Holds a minor planet and its osculating orbit, as listed in `MPCORB.DAT`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MinorPlanet {
+    /// Designation of the minor planet, unpacked, such as `"1"` or
+    /// `"2007 TA418"`
+    pub designation: String,
+    /// Absolute magnitude *H*, if given
+    pub H: Option<f64>,
+    /// Slope parameter *G*, if given
+    pub G: Option<f64>,
+    /// Osculating orbital elements, referred to the ecliptic and mean
+    /// equinox of J2000.0
+    pub elements: orbit::Elements,
+}
+
+/// Holds an optical observation of a minor planet or comet
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Observation {
+    /// Designation of the observed body, unpacked
+    pub designation: String,
+    /// Whether the observation is the discovery observation
+    pub discovery: bool,
+    /// Note on the observation, or `' '`
+    pub note: char,
+    /// Type of the observation, such as `'C'` for CCD, or `' '`
+    /// for a photographic one
+    pub kind: char,
+    /// Julian day of the observation, in UTC
+    pub JD: f64,
+    /// Observed right ascension, referred to the mean equator and
+    /// equinox of J2000.0 *| in radians*
+    pub asc: f64,
+    /// Observed declination, referred to the mean equator and equinox
+    /// of J2000.0 *| in radians*
+    pub dec: f64,
+    /// Observed magnitude, if given
+    pub mag: Option<f64>,
+    /// Band of the magnitude, or `' '`
+    pub band: char,
+    /// Code of the observatory, such as `"568"`
+    pub observatory: String,
+}
+
+/**
+Reads a minor planet from its line in `MPCORB.DAT`
+
+# Returns
+
+* `Ok(minor_planet)`: The [MinorPlanet](./struct.MinorPlanet.html) read, or
+* `Err(error)`      : `Error::InvalidFormat`, if the line isn't a
+                      valid element line
+
+# Arguments
+
+* `line`: Line of `MPCORB.DAT`, or of any file in the same format
+**/
+pub fn minor_planet_frm_line(line: &str) -> Result<MinorPlanet, error::Error> {
+
+    let elements = orbit::Elements {
+        a: parse_num(column(line, 93, 103))?,
+        e: parse_num(column(line, 71, 79))?,
+        i: parse_num(column(line, 60, 68))?.to_radians(),
+        sigma: parse_num(column(line, 49, 57))?.to_radians(),
+        w: parse_num(column(line, 38, 46))?.to_radians(),
+        M: parse_num(column(line, 27, 35))?.to_radians(),
+        epoch: unpack_epoch(column(line, 21, 25)).ok_or(error::Error::InvalidFormat)?
+    };
+
+    let designation = unpack_designation(column(line, 1, 7))
+        .ok_or(error::Error::InvalidFormat)?;
+
+    Ok(MinorPlanet {
+        designation,
+        H: parse_num(column(line, 9, 13)).ok(),
+        G: parse_num(column(line, 15, 19)).ok(),
+        elements
+    })
+
+}
+
+/**
+Reads the minor planets of `MPCORB.DAT`
+
+The header of the file, up to the line of dashes, and blank lines
+are skipped.
+
+# Returns
+
+* `Ok(minor_planets)`: The [MinorPlanet](./struct.MinorPlanet.html)s
+                       read, in order, or
+* `Err(error)`       : `Error::InvalidFormat`, if a line isn't a
+                       valid element line
+
+# Arguments
+
+* `contents`: Contents of `MPCORB.DAT`, or of any file in the same format
+**/
+pub fn minor_planets(contents: &str) -> Result<Vec<MinorPlanet>, error::Error> {
+
+    let body = match contents.find("\n-----") {
+        Some(start) => contents[start + 1..].split_once('\n').map_or("", |(_, body)| body),
+        None        => contents
+    };
+
+    body.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(minor_planet_frm_line)
+        .collect()
+
+}
+
+/**
+Reads an optical observation in the 80-column format of the MPC
+
+The second lines of observations made from satellites and roving
+observers, and radar observations, aren't optical positions and
+give an error.
+
+# Returns
+
+* `Ok(observation)`: The [Observation](./struct.Observation.html) read, or
+* `Err(error)`     : `Error::InvalidFormat`, if the line isn't a
+                     valid optical observation
+
+# Arguments
+
+* `line`: Line of 80 columns
+**/
+pub fn observation_frm_line(line: &str) -> Result<Observation, error::Error> {
+
+    let kind = char_at(line, 15);
+    if "svrRx".contains(kind) {
+        return Err(error::Error::InvalidFormat);
+    }
+
+    let designation = match column(line, 1, 5) {
+        "" => column(line, 6, 12),
+        number => number
+    };
+    let designation = unpack_designation(designation)
+        .ok_or(error::Error::InvalidFormat)?;
+
+    let date: Vec<&str> = column(line, 16, 32).split_whitespace().collect();
+    if date.len() != 3 {
+        return Err(error::Error::InvalidFormat);
+    }
+    let year = date[0].parse::<i16>().map_err(|_| error::Error::InvalidFormat)?;
+    let month = date[1].parse::<usize>().ok()
+        .and_then(month_frm_num)
+        .ok_or(error::Error::InvalidFormat)?;
+    let day = parse_num(date[2])?;
+    let JD = time::julian_day(
+        &time::Date {
+            year,
+            month,
+            decimal_day: day,
+            cal_type: time::CalType::Gregorian
+        }
+    );
+
+    let (_, hour, min, sec) = parse_sexagesimal(column(line, 33, 44))
+        .ok_or(error::Error::InvalidFormat)?;
+    let asc = angle::deg_frm_hms(hour, min, sec).to_radians();
+
+    let (negative, deg, min, sec) = parse_sexagesimal(column(line, 45, 56))
+        .ok_or(error::Error::InvalidFormat)?;
+    let dec = angle::deg_frm_dms(deg, min, sec).to_radians();
+    let dec = if negative { -dec } else { dec };
+
+    Ok(Observation {
+        designation,
+        discovery: char_at(line, 13) == '*',
+        note: char_at(line, 14),
+        kind,
+        JD,
+        asc,
+        dec,
+        mag: parse_num(column(line, 66, 70)).ok(),
+        band: char_at(line, 71),
+        observatory: column(line, 78, 80).to_string()
+    })
+
+}
+
+/**
+Reads the optical observations of a file in the 80-column format of
+the MPC
+
+Blank lines, and lines which aren't optical positions, such as the
+second lines of satellite observations, are skipped.
+
+# Returns
+
+* `Ok(observations)`: The [Observation](./struct.Observation.html)s
+                      read, in order, or
+* `Err(error)`      : `Error::InvalidFormat`, if a line is an
+                      invalid optical observation
+
+# Arguments
+
+* `contents`: Contents of the file
+**/
+pub fn observations(contents: &str) -> Result<Vec<Observation>, error::Error> {
+
+    contents.lines()
+        .filter(|line| !line.trim().is_empty() && !"svrRx".contains(char_at(line, 15)))
+        .map(observation_frm_line)
+        .collect()
+
+}
+
+/**
+Unpacks a designation of a minor planet in the packed form of the MPC
+
+# Returns
+
+* `designation`: The number of a numbered minor planet, such as
+                 `"100001"` for `"A0001"`, the provisional designation,
+                 such as `"2007 TA418"` for `"K07Tf8A"`, or `None`
+                 if the packed designation is invalid
+
+# Arguments
+
+* `packed`: Packed designation
+**/
+pub fn unpack_designation(packed: &str) -> Option<String> {
+
+    let packed = packed.trim();
+    let chars: Vec<char> = packed.chars().collect();
+
+    match chars.len() {
+        5 if chars[0] == '~' => {
+            let mut num = 0;
+            for &c in &chars[1..] {
+                num = num * 62 + base62_digit(c)?;
+            }
+            Some((num + 620000).to_string())
+        },
+        5 => {
+            let high = base62_digit(chars[0])?;
+            let low = packed[1..].parse::<u32>().ok()?;
+            Some((high * 10000 + low).to_string())
+        },
+        7 => {
+            let year = century(chars[0])? * 100 + packed[1..3].parse::<u32>().ok()?;
+            let cycle = base62_digit(chars[4])? * 10 + chars[5].to_digit(10)?;
+            if !chars[3].is_ascii_uppercase() || !chars[6].is_ascii_alphabetic() {
+                return None;
+            }
+            let mut designation = format!("{} {}{}", year, chars[3], chars[6]);
+            if cycle > 0 {
+                designation.push_str(&cycle.to_string());
+            }
+            Some(designation)
+        },
+        _ => None
+    }
+
+}
+
+/**
+Unpacks a date in the packed form of the MPC
+
+# Returns
+
+* `JD`: Julian (Ephemeris) day of 0h TT of the date, such as
+        2460400.5 for `"K243V"` (2024 March 31), or `None` if the
+        packed date is invalid
+
+# Arguments
+
+* `packed`: Packed date
+**/
+pub fn unpack_epoch(packed: &str) -> Option<f64> {
+
+    let chars: Vec<char> = packed.trim().chars().collect();
+    if chars.len() != 5 {
+        return None;
+    }
+
+    let year = century(chars[0])? * 100 + chars[1].to_digit(10)? * 10 + chars[2].to_digit(10)?;
+    let month = month_frm_num(base62_digit(chars[3])? as usize)?;
+    let day = base62_digit(chars[4])?;
+    if !(1..=31).contains(&day) {
+        return None;
+    }
+
+    Some(time::julian_day(
+        &time::Date {
+            year: year as i16,
+            month,
+            decimal_day: day as f64,
+            cal_type: time::CalType::Gregorian
+        }
+    ))
+
+}
+
+// Text of the columns `first` to `last` of a line, counted from 1,
+// trimmed
+fn column(line: &str, first: usize, last: usize) -> &str {
+    line.get(first - 1..last.min(line.len()))
+        .unwrap_or("")
+        .trim()
+}
+
+fn char_at(line: &str, col: usize) -> char {
+    line.chars().nth(col - 1).unwrap_or(' ')
+}
+
+fn parse_num(text: &str) -> Result<f64, error::Error> {
+    text.trim().parse::<f64>().map_err(|_| error::Error::InvalidFormat)
+}
+
+// (negative, hours or degrees, minutes, seconds) of a sexagesimal
+// angle, such as "-05 41 23.5"
+fn parse_sexagesimal(text: &str) -> Option<(bool, i64, i64, f64)> {
+
+    let negative = text.starts_with('-');
+    let text = text.trim_start_matches(['-', '+']);
+    let fields: Vec<&str> = text.split_whitespace().collect();
+
+    match fields.len() {
+        3 => Some((
+            negative,
+            fields[0].parse().ok()?,
+            fields[1].parse().ok()?,
+            fields[2].parse().ok()?
+        )),
+        2 => {
+            let min = fields[1].parse::<f64>().ok()?;
+            Some((negative, fields[0].parse().ok()?, min as i64, min.fract() * 60.0))
+        },
+        _ => None
+    }
+
+}
+
+fn century(c: char) -> Option<u32> {
+    match c {
+        'I' => Some(18),
+        'J' => Some(19),
+        'K' => Some(20),
+        _   => None
+    }
+}
+
+// Digits 0-9, then A-Z for 10-35, then a-z for 36-61
+fn base62_digit(c: char) -> Option<u32> {
+    match c {
+        '0'..='9' => Some(c as u32 - '0' as u32),
+        'A'..='Z' => Some(c as u32 - 'A' as u32 + 10),
+        'a'..='z' => Some(c as u32 - 'a' as u32 + 36),
+        _         => None
+    }
+}
+
+fn month_frm_num(num: usize) -> Option<time::Month> {
+
+    let months = [
+        time::Month::Jan, time::Month::Feb, time::Month::Mar, time::Month::Apr,
+        time::Month::May, time::Month::June, time::Month::July, time::Month::Aug,
+        time::Month::Sept, time::Month::Oct, time::Month::Nov, time::Month::Dec
+    ];
+
+    if num >= 1 { months.get(num - 1).cloned() } else { None }
+
+}
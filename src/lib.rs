@@ -20,31 +20,103 @@ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
 THE SOFTWARE.
 */
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![allow(non_snake_case)]
 #![allow(non_camel_case_types)]
+// The docs align the continuation lines of their lists, and the
+// formulae take as many arguments as they have quantities
+#![allow(clippy::doc_overindented_list_items)]
+#![allow(clippy::too_many_arguments)]
+
+// Without the standard library, core stands in for it, alloc gives
+// the collections, and libm the mathematical functions
+#[cfg(not(feature = "std"))]
+extern crate core as std;
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+extern crate libm;
+
+#[cfg(all(not(feature = "std"), not(feature = "libm")))]
+compile_error!("the libm feature is needed without the std feature");
+
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+
+#[cfg(feature = "chrono")]
+extern crate chrono;
+
+#[cfg(feature = "time")]
+extern crate time as time_crate;
 
 #[macro_use]
 pub mod util;
 
+mod math;
+
+#[cfg(feature = "trace")]
+#[macro_use]
+pub mod trace;
+
+// Reporting of iterative routines compiles to nothing without
+// the trace feature
+#[cfg(not(feature = "trace"))]
+macro_rules! trace_event {
+    ($routine: expr, $outcome: ident, $iters: expr, $residual: expr) => {
+        if false { let _ = ($routine, $iters, $residual); }
+    };
+}
+
 #[macro_use]
 pub mod coords;
 pub mod aberr;
+pub mod accuracy;
 pub mod angle;
+pub mod apparent;
 pub mod asteroid;
 pub mod atmos;
 pub mod binary_star;
+pub mod body;
+pub mod chebyshev;
+pub mod cio;
+pub mod comet;
+pub mod compact;
 pub mod consts;
+pub mod eclipse;
 pub mod ecliptic;
+pub mod ephemeris;
+pub mod error;
+pub mod exoplanet;
+pub mod float;
+pub mod fund_args;
 pub mod interpol;
+pub mod io;
 pub mod lunar;
+pub mod meteor;
 pub mod misc;
+pub mod numerics;
 pub mod nutation;
+pub mod observer;
+pub mod occult;
 pub mod orbit;
 pub mod parallax;
 pub mod planet;
 pub mod pluto;
 pub mod precess;
+pub mod redshift;
+pub mod satellite;
+pub mod selenography;
 pub mod star;
+pub mod strict;
 pub mod sun;
+pub mod sundial;
+pub mod tide;
 pub mod time;
 pub mod transit;
+
+pub use apparent::apparent_position;
+pub use body::Body;
+pub use error::Error;
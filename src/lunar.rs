@@ -22,9 +22,17 @@ THE SOFTWARE.
 
 //! The Earth's moon
 
+use aberr;
 use angle;
+use consts;
 use coords;
+use ecliptic;
+use fund_args;
+use nutation;
+use sun;
 use time;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 /**
 Computes the equatorial horizontal parallax of the Moon
@@ -100,7 +108,7 @@ fn A (
 }
 
 #[inline]
-fn F(JC: f64) -> f64 {
+pub(crate) fn F(JC: f64) -> f64 {
 
     angle::limit_to_360(
         Horner_eval!(
@@ -116,14 +124,14 @@ fn F(JC: f64) -> f64 {
 }
 
 #[inline]
-fn E(JC: f64) -> f64 {
+pub(crate) fn E(JC: f64) -> f64 {
 
-    1.0 - JC*(0.002_516 + JC*0.000_0074)
+    1.0 - JC*(0.002_516 + JC*0.000_007_4)
 
 }
 
 #[inline]
-fn DMM1(JC: f64) -> (f64, f64, f64) {
+pub(crate) fn DMM1(JC: f64) -> (f64, f64, f64) {
 
     let D = angle::limit_to_360(
         Horner_eval!(
@@ -367,10 +375,10 @@ pub fn total_libr (
 ) -> (f64, f64) {
 
     let (opt_long, opt_lat) = optical_libr (
-        mn_ecl_long_moon, apprnt_ecl_lat_moon, JD
+        JD, mn_ecl_long_moon, apprnt_ecl_lat_moon
     );
     let (phys_long, phys_lat) = physical_libr (
-        mn_ecl_long_moon, apprnt_ecl_lat_moon, JD, opt_lat
+        JD, mn_ecl_long_moon, apprnt_ecl_lat_moon, opt_lat
     );
 
     (opt_long + phys_long, opt_lat + phys_lat)
@@ -506,20 +514,33 @@ lunar theory, so the accruacy of:
 **/
 pub fn geocent_ecl_pos(JD: f64) -> (coords::EclPoint, f64) {
 
-    let JC = time::julian_cent(JD);
-    let (D, M, M1) = DMM1(JC);
-    let F = F(JC);
-    let E = E(JC);
-    let L1 = angle::limit_to_360(
-        Horner_eval!(
-            JC,
-            218.3164477,
-            481267.88123421,
-           -0.0015786,
-            1.0 / 538841.0,
-           -1.0 / 65194000.0
-        )
-    ).to_radians();
+    geocent_ecl_pos_frm_args(&fund_args::FundamentalArgs::new(JD))
+
+}
+
+/**
+Computes the geocentric ecliptic position of the Moon, referred to
+the mean equinox of the date, from the fundamental arguments
+
+This is `geocent_ecl_pos()`, for arguments already computed for
+another routine at the same instant.
+
+# Returns
+
+`(moon_ecl_point, rad_vec)`
+
+* `moon_ecl_point`: Ecliptic point of the Moon *| in radians*
+* `rad_vec`: Moon-Earth distance *| in kilometers*
+
+# Arguments
+
+* `args`: [FundamentalArgs](../fund_args/struct.FundamentalArgs.html)
+          of the instant
+**/
+pub fn geocent_ecl_pos_frm_args(args: &fund_args::FundamentalArgs) -> (coords::EclPoint, f64) {
+
+    let JC = args.JC;
+    let (D, M, M1, F, E, L1) = (args.D, args.M, args.M1, args.F, args.E, args.L1);
 
     let A1 = angle::limit_to_360(119.75 + 131.849*JC).to_radians();
     let A2 = angle::limit_to_360(53.090 + 479264.29*JC).to_radians();
@@ -715,6 +736,23 @@ pub fn geocent_ecl_pos(JD: f64) -> (coords::EclPoint, f64) {
 
 }
 
+// The mean longitude of the Moon
+#[inline]
+pub(crate) fn mn_long(JC: f64) -> f64 {
+
+    angle::limit_to_360(
+        Horner_eval!(
+            JC,
+            218.3164477,
+            481267.88123421,
+           -0.0015786,
+            1.0 / 538841.0,
+           -1.0 / 65194000.0
+        )
+    ).to_radians()
+
+}
+
 /**
 Computes the longitude of the mean ascending node of the Moon
 
@@ -852,7 +890,7 @@ pub fn illum_frac_frm_eq_coords (
 ) -> f64 {
 
     illuminated_frac (
-        sun_eq_point.anglr_sepr(&moon_eq_point).acos(),
+        sun_eq_point.anglr_sepr(moon_eq_point),
         earth_moon_dist,
         earth_sun_dist
     )
@@ -900,11 +938,124 @@ fn illuminated_frac (
 
 ) -> f64 {
 
-    let i = (earth_sun_dist * moon_geocent_elong.sin()).atan2 (
+    let i = phase_angl(moon_geocent_elong, earth_moon_dist, earth_sun_dist);
+
+    (1.0 + i.cos()) / 2.0
+
+}
+
+#[inline]
+fn phase_angl (
+
+    moon_geocent_elong : f64,
+    earth_moon_dist    : f64,
+    earth_sun_dist     : f64
+
+) -> f64 {
+
+    (earth_sun_dist * moon_geocent_elong.sin()).atan2 (
         earth_moon_dist - earth_sun_dist*moon_geocent_elong.cos()
+    )
+
+}
+
+/// Holds the state of the lunar disk, as seen from the center of the
+/// Earth
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LunarDiskState {
+    /// Illuminated fraction of the disk
+    pub illum_frac: f64,
+    /// Phase angle, the selenocentric elongation of the Earth from
+    /// the Sun *| in radians*
+    pub phase_angl: f64,
+    /// Position angle of the midpoint of the bright limb, measured
+    /// eastwards from the north point of the disk *| in radians*
+    pub bright_limb: f64,
+    /// Optical libration in longitude *| in radians*
+    pub optical_libr_long: f64,
+    /// Optical libration in latitude *| in radians*
+    pub optical_libr_lat: f64,
+    /// Physical libration in longitude *| in radians*
+    pub physical_libr_long: f64,
+    /// Physical libration in latitude *| in radians*
+    pub physical_libr_lat: f64,
+    /// Total libration in longitude, or the selenographic longitude
+    /// of the center of the disk *| in radians*
+    pub total_libr_long: f64,
+    /// Total libration in latitude, or the selenographic latitude of
+    /// the center of the disk *| in radians*
+    pub total_libr_lat: f64,
+    /// Position angle of the Moon's axis of rotation *| in radians*
+    pub pos_angl_of_axis: f64,
+}
+
+/**
+Computes the state of the lunar disk, as seen from the center of the
+Earth
+
+The illuminated fraction, phase angle, position angle of the bright
+limb, librations and position angle of the axis are found together,
+from the apparent positions of the Moon and the Sun, by the methods
+of *Meeus* (Astronomical Algorithms, chapters 48 and 53). The
+librations in longitude are between `-PI` and `PI`.
+
+# Returns
+
+* `state`: The [LunarDiskState](./struct.LunarDiskState.html)
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn disk_state(JD: f64) -> LunarDiskState {
+
+    let (moon_point, moon_dist) = geocent_ecl_pos(JD);
+    let (sun_point, sun_dist) = sun::geocent_ecl_pos(JD);
+
+    let (nut_in_long, nut_in_oblq) = nutation::nutation(JD);
+    let true_oblq = ecliptic::mn_oblq_IAU(JD) + nut_in_oblq;
+
+    let eq_point = |long: f64, lat: f64| coords::EqPoint {
+        asc: coords::asc_frm_ecl(long, lat, true_oblq),
+        dec: coords::dec_frm_ecl(long, lat, true_oblq)
+    };
+    let moon_eq_point = eq_point(moon_point.long + nut_in_long, moon_point.lat);
+    let sun_eq_point = eq_point (
+        sun_point.long + nut_in_long + aberr::sol_aberr(sun_dist),
+        sun_point.lat
     );
 
-    (1.0 + i.cos()) / 2.0
+    let elong = sun_eq_point.anglr_sepr(&moon_eq_point);
+    let i = phase_angl(elong, moon_dist, sun_dist * consts::ASTRONOMICAL_UNIT / 1000.0);
+
+    let (optical_libr_long, optical_libr_lat) = optical_libr(JD, moon_point.long, moon_point.lat);
+    let (physical_libr_long, physical_libr_lat) = physical_libr (
+        JD, moon_point.long, moon_point.lat, optical_libr_lat
+    );
+    let total_libr_lat = optical_libr_lat + physical_libr_lat;
+
+    let pos_angl_of_axis = pos_angl_of_axis_of_rot (
+        JD,
+        mn_ascend_node(time::julian_cent(JD)),
+        total_libr_lat,
+        nut_in_long,
+        true_oblq,
+        moon_eq_point.asc
+    );
+
+    LunarDiskState {
+        illum_frac: (1.0 + i.cos()) / 2.0,
+        phase_angl: i,
+        bright_limb: angle::limit_to_two_PI(bright_limb(sun_eq_point, moon_eq_point)),
+        optical_libr_long: angle::limit_to_pm_PI(optical_libr_long),
+        optical_libr_lat,
+        physical_libr_long,
+        physical_libr_lat,
+        total_libr_long: angle::limit_to_pm_PI(optical_libr_long + physical_libr_long),
+        total_libr_lat,
+        pos_angl_of_axis
+    }
 
 }
 
@@ -926,10 +1077,10 @@ descending nodes, close to a given date
 #[inline]
 pub fn time_of_passage_through_nodes(date: &time::Date) -> (f64, f64) {
 
-    let k = 13.4223 * (time::decimal_year(&date) - 2000.05);
+    let k = 13.4223 * (time::decimal_year(date) - 2000.05);
     let T = k / 1342.23;
     let k1 = (k as i32) as f64;
-    let k2 = (k1 as f64) + 0.5;
+    let k2 = k1 + 0.5;
 
     (time_of_passage_through_node(k1, T), time_of_passage_through_node(k2, T))
 
@@ -952,8 +1103,7 @@ fn time_of_passage_through_node(k: f64, T: f64) -> f64 {
     let P = sigma + (272.75 - T*2.3).to_radians();
     let V = (299.75 + T*(132.85 - T*0.009173)).to_radians();
 
-    (
-        2451565.1619
+    2451565.1619
       + 27.212220817 * k
       + T * T * (
             0.0002762
@@ -983,11 +1133,282 @@ fn time_of_passage_through_node(k: f64, T: f64) -> f64 {
           + V.sin()
           + P.sin()
         )
-    )
+
+}
+
+/// Represents a node of the Moon's orbit
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Node {
+    /// Ascending node
+    Ascend,
+    /// Descending node
+    Descend
+}
+
+/**
+Computes the time of passage of the Moon through a node of its orbit
+
+# Returns
+
+* `JD`: Julian (Ephemeris) day of the passage through `node` that is
+        closest to `date`
+
+# Arguments
+
+* `date`: Date of interest, close to the passage
+* `node`: The [Node](./enum.Node.html)
+**/
+pub fn node_passage(date: &time::Date, node: &Node) -> f64 {
+
+    let k = 13.4223 * (time::decimal_year(date) - 2000.05);
+    let k = match *node {
+        Node::Ascend  => k.round(),
+        Node::Descend => (k - 0.5).round() + 0.5
+    };
+
+    time_of_passage_through_node(k, k / 1342.23)
+
+}
+
+/// Represents an apsis of the Moon's orbit
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Apsis {
+    /// Perigee, the point of the orbit closest to the Earth
+    Perigee,
+    /// Apogee, the point of the orbit farthest from the Earth
+    Apogee
+}
+
+/**
+Computes the time of passage of the Moon through an apsis of its
+orbit, and the Earth-Moon distance then
+
+The periodic terms of *Meeus* (Astronomical Algorithms, chapter 50)
+are used, which give the time with an error of upto about 30 minutes
+for the perigee and 3 minutes for the apogee, and the distance to
+within a few kilometers.
+
+# Returns
+
+`(JD, earth_moon_dist)`
+
+* `JD`             : Julian (Ephemeris) day of the passage through
+                     `apsis` that is closest to `date`
+* `earth_moon_dist`: Earth-Moon distance at the apsis *| in
+                     kilometers*
+
+# Arguments
+
+* `date` : Date of interest, close to the apsis
+* `apsis`: The [Apsis](./enum.Apsis.html)
+**/
+pub fn apsis(date: &time::Date, apsis: &Apsis) -> (f64, f64) {
+
+    let k = 13.2555 * (time::decimal_year(date) - 1999.97);
+    let k = match *apsis {
+        Apsis::Perigee => k.round(),
+        Apsis::Apogee  => (k - 0.5).round() + 0.5
+    };
+    let T = k / 1325.55;
+
+    let mean_JD =
+        2451534.6698
+      + 27.55454989 * k
+      + T * T * (-0.0006691 + T * (-0.000001098 + T * 0.0000000052));
+
+    let D = (
+        171.9179
+      + 335.9106046 * k
+      + T * T * (-0.0100383 + T * (-0.00001156 + T * 0.000000055))
+    ).to_radians();
+    let M = (347.3477 + 27.1577721 * k + T * T * (-0.000813 - T * 0.000001)).to_radians();
+    let F = (316.6109 + 364.5287911 * k + T * T * (-0.0125053 - T * 0.0000148)).to_radians();
+
+    // multiples of D, M and F, and the coefficient and its rate of
+    // change with T
+    struct terms(i8, i8, i8, f64, f64);
+    let arg = |t: &terms| (t.0 as f64)*D + (t.1 as f64)*M + (t.2 as f64)*F;
+
+    let (time_terms, parllx_terms, mean_parllx) = match *apsis {
+        Apsis::Perigee => (vec![
+            terms(2, 0, 0, -1.6769, 0.0),
+            terms(4, 0, 0, 0.4589, 0.0),
+            terms(6, 0, 0, -0.1856, 0.0),
+            terms(8, 0, 0, 0.0883, 0.0),
+            terms(2, -1, 0, -0.0773, 0.00019),
+            terms(0, 1, 0, 0.0502, -0.00013),
+            terms(10, 0, 0, -0.046, 0.0),
+            terms(4, -1, 0, 0.0422, -0.00011),
+            terms(6, -1, 0, -0.0256, 0.0),
+            terms(12, 0, 0, 0.0253, 0.0),
+            terms(1, 0, 0, 0.0237, 0.0),
+            terms(8, -1, 0, 0.0162, 0.0),
+            terms(14, 0, 0, -0.0145, 0.0),
+            terms(0, 0, 2, 0.0129, 0.0),
+            terms(3, 0, 0, -0.0112, 0.0),
+            terms(10, -1, 0, -0.0104, 0.0),
+            terms(16, 0, 0, 0.0086, 0.0),
+            terms(12, -1, 0, 0.0069, 0.0),
+            terms(5, 0, 0, 0.0066, 0.0),
+            terms(2, 0, 2, -0.0053, 0.0),
+            terms(18, 0, 0, -0.0052, 0.0),
+            terms(14, -1, 0, -0.0046, 0.0),
+            terms(7, 0, 0, -0.0041, 0.0),
+            terms(2, 1, 0, 0.004, 0.0),
+            terms(20, 0, 0, 0.0032, 0.0),
+            terms(1, 1, 0, -0.0032, 0.0),
+            terms(16, -1, 0, 0.0031, 0.0),
+            terms(4, 1, 0, -0.0029, 0.0),
+            terms(9, 0, 0, 0.0027, 0.0),
+            terms(4, 0, 2, 0.0027, 0.0),
+            terms(2, -2, 0, -0.0027, 0.0),
+            terms(4, -2, 0, 0.0024, 0.0),
+            terms(6, -2, 0, -0.0021, 0.0),
+            terms(22, 0, 0, -0.0021, 0.0),
+            terms(18, -1, 0, -0.0021, 0.0),
+            terms(6, 1, 0, 0.0019, 0.0),
+            terms(11, 0, 0, -0.0018, 0.0),
+            terms(8, 1, 0, -0.0014, 0.0),
+            terms(4, 0, -2, -0.0014, 0.0),
+            terms(6, 0, 2, -0.0014, 0.0),
+            terms(3, 1, 0, 0.0014, 0.0),
+            terms(5, 1, 0, -0.0014, 0.0),
+            terms(13, 0, 0, 0.0013, 0.0),
+            terms(20, -1, 0, 0.0013, 0.0),
+            terms(3, 2, 0, 0.0011, 0.0),
+            terms(4, -2, 2, -0.0011, 0.0),
+            terms(1, 2, 0, -0.001, 0.0),
+            terms(22, -1, 0, -0.0009, 0.0),
+            terms(0, 0, 4, -0.0008, 0.0),
+            terms(6, 0, -2, 0.0008, 0.0),
+            terms(2, 1, -2, 0.0008, 0.0),
+            terms(0, 2, 0, 0.0007, 0.0),
+            terms(0, -1, 2, 0.0007, 0.0),
+            terms(2, 0, 4, 0.0007, 0.0),
+            terms(0, -2, 2, -0.0006, 0.0),
+            terms(2, 2, -2, -0.0006, 0.0),
+            terms(24, 0, 0, 0.0006, 0.0),
+            terms(4, 0, -4, 0.0005, 0.0),
+            terms(2, 2, 0, 0.0005, 0.0),
+            terms(1, -1, 0, -0.0004, 0.0),
+        ], vec![
+            terms(2, 0, 0, 63.224, 0.0),
+            terms(4, 0, 0, -6.99, 0.0),
+            terms(2, -1, 0, 2.834, -0.0071),
+            terms(6, 0, 0, 1.927, 0.0),
+            terms(1, 0, 0, -1.263, 0.0),
+            terms(8, 0, 0, -0.702, 0.0),
+            terms(0, 1, 0, 0.696, -0.0017),
+            terms(0, 0, 2, -0.69, 0.0),
+            terms(4, -1, 0, -0.629, 0.0016),
+            terms(2, 0, -2, -0.392, 0.0),
+            terms(10, 0, 0, 0.297, 0.0),
+            terms(6, -1, 0, 0.26, 0.0),
+            terms(3, 0, 0, 0.201, 0.0),
+            terms(2, 1, 0, -0.161, 0.0),
+            terms(1, 1, 0, 0.157, 0.0),
+            terms(12, 0, 0, -0.138, 0.0),
+            terms(8, -1, 0, -0.127, 0.0),
+            terms(2, 0, 2, 0.104, 0.0),
+            terms(2, -2, 0, 0.104, 0.0),
+            terms(5, 0, 0, -0.079, 0.0),
+            terms(14, 0, 0, 0.068, 0.0),
+            terms(10, -1, 0, 0.067, 0.0),
+            terms(4, 1, 0, 0.054, 0.0),
+            terms(12, -1, 0, -0.038, 0.0),
+            terms(4, -2, 0, -0.038, 0.0),
+            terms(7, 0, 0, 0.037, 0.0),
+            terms(4, 0, 2, -0.037, 0.0),
+            terms(16, 0, 0, -0.035, 0.0),
+            terms(3, 1, 0, -0.03, 0.0),
+            terms(1, -1, 0, 0.029, 0.0),
+            terms(6, 1, 0, -0.025, 0.0),
+            terms(0, 2, 0, 0.023, 0.0),
+            terms(14, -1, 0, 0.023, 0.0),
+            terms(2, 2, 0, -0.023, 0.0),
+            terms(6, -2, 0, 0.022, 0.0),
+            terms(2, -1, -2, -0.021, 0.0),
+            terms(9, 0, 0, -0.02, 0.0),
+            terms(18, 0, 0, 0.019, 0.0),
+            terms(6, 0, 2, 0.017, 0.0),
+            terms(0, -1, 2, 0.014, 0.0),
+            terms(16, -1, 0, -0.014, 0.0),
+            terms(4, 0, -2, 0.013, 0.0),
+            terms(8, 1, 0, 0.012, 0.0),
+            terms(11, 0, 0, 0.011, 0.0),
+            terms(5, 1, 0, 0.01, 0.0),
+            terms(20, 0, 0, -0.01, 0.0),
+        ], 3629.215),
+        Apsis::Apogee => (vec![
+            terms(2, 0, 0, 0.4392, 0.0),
+            terms(4, 0, 0, 0.0684, 0.0),
+            terms(0, 1, 0, 0.0456, -0.00011),
+            terms(2, -1, 0, 0.0426, -0.00011),
+            terms(0, 0, 2, 0.0212, 0.0),
+            terms(1, 0, 0, -0.0189, 0.0),
+            terms(6, 0, 0, 0.0144, 0.0),
+            terms(4, -1, 0, 0.0113, 0.0),
+            terms(2, 0, 2, 0.0047, 0.0),
+            terms(1, 1, 0, 0.0036, 0.0),
+            terms(8, 0, 0, 0.0035, 0.0),
+            terms(6, -1, 0, 0.0034, 0.0),
+            terms(2, 0, -2, -0.0034, 0.0),
+            terms(2, -2, 0, 0.0022, 0.0),
+            terms(3, 0, 0, -0.0017, 0.0),
+            terms(4, 0, 2, 0.0013, 0.0),
+            terms(8, -1, 0, 0.0011, 0.0),
+            terms(4, -2, 0, 0.001, 0.0),
+            terms(10, 0, 0, 0.0009, 0.0),
+            terms(3, 1, 0, 0.0007, 0.0),
+            terms(0, 2, 0, 0.0006, 0.0),
+            terms(2, 1, 0, 0.0005, 0.0),
+            terms(2, 2, 0, 0.0005, 0.0),
+            terms(6, 0, 2, 0.0004, 0.0),
+            terms(6, -2, 0, 0.0004, 0.0),
+            terms(10, -1, 0, 0.0004, 0.0),
+            terms(5, 0, 0, -0.0004, 0.0),
+            terms(4, 0, -2, -0.0004, 0.0),
+            terms(0, 1, 2, 0.0003, 0.0),
+            terms(12, 0, 0, 0.0003, 0.0),
+            terms(2, -1, 2, 0.0003, 0.0),
+            terms(1, -1, 0, -0.0003, 0.0),
+        ], vec![
+            terms(2, 0, 0, -9.147, 0.0),
+            terms(1, 0, 0, -0.841, 0.0),
+            terms(0, 0, 2, 0.697, 0.0),
+            terms(0, 1, 0, -0.656, 0.0016),
+            terms(4, 0, 0, 0.355, 0.0),
+            terms(2, -1, 0, 0.159, 0.0),
+            terms(1, 1, 0, 0.127, 0.0),
+            terms(4, -1, 0, 0.065, 0.0),
+            terms(6, 0, 0, 0.052, 0.0),
+            terms(2, 1, 0, 0.043, 0.0),
+            terms(2, 0, 2, 0.031, 0.0),
+            terms(2, 0, -2, -0.023, 0.0),
+            terms(2, -2, 0, 0.022, 0.0),
+            terms(2, 2, 0, 0.019, 0.0),
+            terms(0, 2, 0, -0.016, 0.0),
+            terms(6, -1, 0, 0.014, 0.0),
+            terms(8, 0, 0, 0.01, 0.0),
+        ], 3245.251)
+    };
+
+    let JD = mean_JD + time_terms.iter()
+        .map(|t| (t.3 + t.4*T) * arg(t).sin())
+        .sum::<f64>();
+    let parllx = mean_parllx + parllx_terms.iter()
+        .map(|t| (t.3 + t.4*T) * arg(t).cos())
+        .sum::<f64>();
+
+    (JD, 6378.14 / (parllx / 3600.0).to_radians().sin())
 
 }
 
 /// Represents a phase of the Moon
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Phase {
     /// New Moon
     New,
@@ -1018,14 +1439,59 @@ and mid-2020 AD is 3.8 seconds.
 **/
 pub fn time_of_phase(date: &time::Date, phase: &Phase) -> f64 {
 
-    let mut K = 12.3685 * (time::decimal_year(&date) - 2000.0);
+    let mut K = 12.3685 * (time::decimal_year(date) - 2000.0);
     K = (K as i64) as f64;
 
-    let k = match phase {
-        &Phase::New   => K,
-        &Phase::First => K + 0.25,
-        &Phase::Full  => K + 0.5,
-        &Phase::Last  => K + 0.75,
+    time_of_phase_frm_lunation(K, phase)
+
+}
+
+/**
+Computes the Julian days of all the phases of the Moon in a time range
+
+# Returns
+
+* `phases`: Julian (Ephemeris) days of the phases that occur between
+            `JD_start` and `JD_end`, each with its
+            [Phase](./enum.Phase.html), in chronological order
+
+# Arguments
+
+* `JD_start`: Julian (Ephemeris) day of the start of the range
+* `JD_end`  : Julian (Ephemeris) day of the end of the range
+**/
+pub fn phases_in_range(JD_start: f64, JD_end: f64) -> Vec<(f64, Phase)> {
+
+    let mut phases = Vec::new();
+
+    // start a lunation early, as the true phases can precede the mean
+    // ones by upto about 14 hours
+    let mut K = ((JD_start - 2451550.09766) / 29.530588861).floor() - 1.0;
+
+    loop {
+        for phase in [Phase::New, Phase::First, Phase::Full, Phase::Last].iter() {
+            let JD = time_of_phase_frm_lunation(K, phase);
+            if JD > JD_end {
+                return phases;
+            }
+            if JD >= JD_start {
+                phases.push((JD, *phase));
+            }
+        }
+        K += 1.0;
+    }
+
+}
+
+// Computes the Julian day of a phase of the Moon in the lunation `K`,
+// counted from the new moon of 2000 January 6
+fn time_of_phase_frm_lunation(K: f64, phase: &Phase) -> f64 {
+
+    let k = match *phase {
+        Phase::New   => K,
+        Phase::First => K + 0.25,
+        Phase::Full  => K + 0.5,
+        Phase::Last  => K + 0.75,
     };
 
     let T = k / 1236.85;
@@ -1113,13 +1579,13 @@ pub fn time_of_phase(date: &time::Date, phase: &Phase) -> f64 {
         let W =
             0.00306
           - 0.00038 * E * M.cos()
-          + 0.00026 * M1.cos();
+          + 0.00026 * M1.cos()
           - 0.00002 * ((M1 - M).cos() - (M1 + M).cos() - (2.0 * F).cos());
 
-        JD += match phase {
-            &Phase::Last  => -W,
-            &Phase::First =>  W,
-            &_  => 0.0,
+        JD += match *phase {
+            Phase::Last  => -W,
+            Phase::First =>  W,
+            _  => 0.0,
         };
 
         let corrections = [
@@ -1155,10 +1621,7 @@ pub fn time_of_phase(date: &time::Date, phase: &Phase) -> f64 {
         }
     }
     else {
-        let is_new = match phase {
-            &Phase::New => true,
-            &_ => false,
-        };
+        let is_new = matches!(*phase, Phase::New);
 
         let sine_arguments = [
             M1,
@@ -1274,3 +1737,175 @@ pub fn time_of_phase(date: &time::Date, phase: &Phase) -> f64 {
     JD
 
 }
+
+/**
+Computes the phase angle of the Earth as seen from the Moon
+
+# Returns
+
+* `phase_angl`: Phase angle of the Earth seen from the
+                Moon *| in radians*, equal to the geocentric
+                elongation of the Moon from the Sun
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn earth_phase_angl_frm_moon(JD: f64) -> f64 {
+
+    let (moon_point, _) = geocent_ecl_pos(JD);
+    let (sun_point, _) = sun::geocent_ecl_pos(JD);
+
+    moon_point.anglr_sepr(&sun_point)
+
+}
+
+/**
+Computes the illuminated fraction of the Earth's disk as seen from
+the Moon
+
+# Returns
+
+* `illum_frac`: Illuminated fraction of the Earth's disk
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn earth_illum_frac_frm_moon(JD: f64) -> f64 {
+
+    (1.0 + earth_phase_angl_frm_moon(JD).cos()) / 2.0
+
+}
+
+/**
+Computes the apparent magnitude of the Earth as seen from the Moon
+
+# Returns
+
+* `apprnt_mag`: Apparent magnitude of the Earth
+
+The phase function of the Earth is that of Mallama and Hilton
+(2018), from observations of the earthshine.
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn earth_apprnt_mag_frm_moon(JD: f64) -> f64 {
+
+    let (moon_point, earth_moon_dist) = geocent_ecl_pos(JD);
+    let (sun_point, sun_earth_dist) = sun::geocent_ecl_pos(JD);
+
+    let i = moon_point.anglr_sepr(&sun_point).to_degrees();
+    let delta = earth_moon_dist * 1000.0 / consts::ASTRONOMICAL_UNIT;
+
+    -3.99 + 5.0*(sun_earth_dist * delta).log10() + i*(-0.001060 + i*0.0002054)
+
+}
+
+/**
+Computes the illuminance of the Moon by the Earth, which determines
+the brightness of the earthshine
+
+# Returns
+
+* `illuminance`: Illuminance of the Moon's surface normal to the
+                 Earth's direction *| in lux*
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn earthshine_illuminance(JD: f64) -> f64 {
+
+    // illuminance of a star of visual magnitude zero
+    2.54e-6 * 10_f64.powf(-0.4 * earth_apprnt_mag_frm_moon(JD))
+
+}
+
+/**
+Computes the times of moonrise and moonset on a day
+
+The Moon is taken to rise or set when the topocentric altitude of its
+upper limb is -34', which is a geocentric altitude of its center of
+0.7275π - 34', where π is its equatorial horizontal parallax. As the
+Moon moves by about 13 degrees a day, its apparent position and
+parallax are computed anew at each iteration, instead of being
+interpolated.
+
+# Returns
+
+`(rise, set)`
+
+* `rise`: Julian (Universal) day of the moonrise, or `None` if the
+          Moon doesn't rise on the day
+* `set` : Julian (Universal) day of the moonset, or `None` if the
+          Moon doesn't set on the day
+
+As the Moon rises about 50 minutes later each day, there's a day
+each month without a moonrise, and another without a moonset.
+
+# Arguments
+
+* `geograph_point`: Geographic point of the observer *| in radians*
+* `date`          : The date of interest, of which only the day is
+                    considered
+**/
+pub fn rise_set(geograph_point: &coords::GeographPoint, date: &time::Date) -> (Option<f64>, Option<f64>) {
+
+    let JD0 = (time::julian_day(date) - 0.5).floor() + 0.5;
+    let delta_t = time::delta_t_frm_julian_day(JD0) / 86400.0;
+    let L = geograph_point.long;
+    let lat = geograph_point.lat;
+
+    // the local hour angle, declination and altitude of the definition
+    // of the Moon at a fraction m of the day
+    let hr_angl_dec_h0 = |m: f64| {
+        let JD = JD0 + m;
+        let JDE = JD + delta_t;
+
+        let (nut_in_long, nut_in_oblq) = nutation::nutation(JDE);
+        let true_oblq = ecliptic::mn_oblq_IAU(JDE) + nut_in_oblq;
+        let (point, dist) = geocent_ecl_pos(JDE);
+        let long = point.long + nut_in_long;
+        let asc = coords::asc_frm_ecl(long, point.lat, true_oblq);
+        let dec = coords::dec_frm_ecl(long, point.lat, true_oblq);
+
+        let theta = time::apprnt_sidr(time::mn_sidr(JD), nut_in_long, true_oblq);
+        let H = coords::hr_angl_frm_observer_long(theta, L, asc);
+        let h0 = 0.7275*eq_hz_parllx(dist) - angle::deg_frm_dms(0, 34, 0.0).to_radians();
+
+        (H - angle::TWO_PI*(H / angle::TWO_PI).round(), dec, h0)
+    };
+
+    // the Julian day at which the Moon crosses the altitude of the
+    // definition, rising (sign = -1) or setting (sign = 1), starting
+    // from the crossing nearest to the middle of the day. As the
+    // crossings are a lunar day of about 1.035 days apart, there's no
+    // other crossing within the day if that one isn't.
+    let in_day = |sign: f64| {
+        let mut m = 0.5;
+        for _ in 0..20 {
+            let (H, dec, h0) = hr_angl_dec_h0(m);
+            let cos_H0 = (h0.sin() - lat.sin()*dec.sin()) / (lat.cos()*dec.cos());
+            if cos_H0.abs() > 1.0 {
+                return None;
+            }
+
+            // the hour angle of the Moon increases by about 0.966
+            // revolutions a day
+            let dH = sign*cos_H0.acos() - H;
+            let dm = (dH - angle::TWO_PI*(dH / angle::TWO_PI).round()) / (0.966 * angle::TWO_PI);
+            m += dm;
+
+            if dm.abs() < 1e-6 {
+                return if (0.0..1.0).contains(&m) { Some(JD0 + m) } else { None };
+            }
+        }
+        None
+    };
+
+    (in_day(-1.0), in_day(1.0))
+
+}
@@ -0,0 +1,191 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Meteors
+
+use comet;
+use consts;
+use coords;
+use ecliptic;
+use planet;
+use precess;
+use time;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/**
+Computes the geocentric radiant and velocity of a meteoroid,
+corrected for the zenith attraction of the Earth
+
+The Earth's gravity accelerates a meteoroid and bends its path
+towards the ground, so that the observed radiant lies closer to the
+zenith, and the observed velocity is higher, than they would be had
+the Earth no mass.
+
+# Returns
+
+`(geocent_radiant, geocent_vel)`
+
+* `geocent_radiant`: Geocentric radiant of the meteoroid, referred to
+                     the same equator and equinox as the observed
+                     radiant
+* `geocent_vel`    : Geocentric velocity of the meteoroid
+                     *| in kilometers per second*
+
+# Arguments
+
+* `radiant`       : Observed radiant of the meteor
+* `obs_vel`       : Observed (pre-atmospheric) velocity of the meteor
+                    *| in kilometers per second*
+* `JD`            : Julian day of the observation
+* `geograph_point`: Geographical position of the observer (longitude
+                    measured positively westwards)
+**/
+pub fn zenith_attraction (
+
+    radiant        : &coords::EqPoint,
+    obs_vel        : f64,
+    JD             : f64,
+    geograph_point : &coords::GeographPoint
+
+) -> (coords::EqPoint, f64) {
+
+    let geocent_vel = (obs_vel*obs_vel - ESCAPE_VEL_SQR).max(0.0).sqrt();
+
+    let loc_sidr = time::mn_sidr(JD) - geograph_point.long;
+    let zenith = unit_vec(loc_sidr, geograph_point.lat);
+    let rad = unit_vec(radiant.asc, radiant.dec);
+
+    let cos_z = (rad.0*zenith.0 + rad.1*zenith.1 + rad.2*zenith.2).min(1.0);
+    let z = cos_z.acos();
+
+    if z < 1e-9 {
+        return (coords::EqPoint { asc: radiant.asc, dec: radiant.dec }, geocent_vel);
+    }
+
+    let dz = 2.0 * (
+        (obs_vel - geocent_vel) / (obs_vel + geocent_vel) * (z/2.0).tan()
+    ).atan();
+    let z_c = z + dz;
+
+    let k = z_c.sin() / z.sin();
+    let corrected = (
+        k*(rad.0 - cos_z*zenith.0) + z_c.cos()*zenith.0,
+        k*(rad.1 - cos_z*zenith.1) + z_c.cos()*zenith.1,
+        k*(rad.2 - cos_z*zenith.2) + z_c.cos()*zenith.2
+    );
+
+    (
+        coords::EqPoint {
+            asc : corrected.1.atan2(corrected.0),
+            dec : corrected.2.atan2((corrected.0*corrected.0 + corrected.1*corrected.1).sqrt())
+        },
+        geocent_vel
+    )
+
+}
+
+/**
+Computes the heliocentric orbit of a meteoroid from it's observed
+radiant and velocity
+
+The observed radiant and velocity are first corrected for the zenith
+attraction of the Earth. The Earth's heliocentric velocity is then
+added to the geocentric velocity of the meteoroid, which is placed at
+the Earth's center.
+
+# Returns
+
+* `elements`: Heliocentric orbital [Elements](../comet/struct.Elements.html)
+              of the meteoroid, referred to the ecliptic and mean
+              equinox of J2000.0
+
+# Arguments
+
+* `radiant`       : Observed radiant of the meteor, referred to the
+                    mean equator and equinox of J2000.0
+* `obs_vel`       : Observed (pre-atmospheric) velocity of the meteor
+                    *| in kilometers per second*
+* `JD`            : Julian day of the observation
+* `geograph_point`: Geographical position of the observer (longitude
+                    measured positively westwards)
+**/
+pub fn heliocent_orbit (
+
+    radiant        : &coords::EqPoint,
+    obs_vel        : f64,
+    JD             : f64,
+    geograph_point : &coords::GeographPoint
+
+) -> comet::Elements {
+
+    let (geocent_radiant, geocent_vel) = zenith_attraction(
+        radiant, obs_vel, JD, geograph_point
+    );
+
+    let oblq = ecliptic::mn_oblq_IAU(J2000);
+    let (x, y, z) = unit_vec(geocent_radiant.asc, geocent_radiant.dec);
+    let rad = (
+        x,
+        y*oblq.cos() + z*oblq.sin(),
+       -y*oblq.sin() + z*oblq.cos()
+    );
+
+    // the meteoroid moves away from the radiant
+    let v = geocent_vel * 86400.0 * 1000.0 / consts::ASTRONOMICAL_UNIT;
+
+    let earth = earth_rect_coords(JD);
+    let h = 0.05;
+    let earth_prev = earth_rect_coords(JD - h);
+    let earth_next = earth_rect_coords(JD + h);
+
+    let vel = (
+        (earth_next.0 - earth_prev.0)/(2.0*h) - v*rad.0,
+        (earth_next.1 - earth_prev.1)/(2.0*h) - v*rad.1,
+        (earth_next.2 - earth_prev.2)/(2.0*h) - v*rad.2
+    );
+
+    comet::elements_frm_heliocent_rect_state(earth, vel, JD)
+
+}
+
+const J2000: f64 = 2451545.0;
+
+// Square of the escape velocity from the Earth at the height of
+// meteor ablation (about 100 km), in square kilometers per square
+// second
+const ESCAPE_VEL_SQR: f64 = 2.0 * consts::wgs84::GRAV_CONST / 6.4781e+6 / 1.0e+6;
+
+fn unit_vec(asc: f64, dec: f64) -> (f64, f64, f64) {
+
+    (dec.cos()*asc.cos(), dec.cos()*asc.sin(), dec.sin())
+
+}
+
+fn earth_rect_coords(JD: f64) -> (f64, f64, f64) {
+
+    let (L, B, R) = planet::heliocent_coords(&planet::Planet::Earth, JD);
+    let (L, B) = precess::precess_ecl_coords(L, B, JD, J2000);
+
+    (R*B.cos()*L.cos(), R*B.cos()*L.sin(), R*B.sin())
+
+}
@@ -22,13 +22,17 @@ THE SOFTWARE.
 
 //! Miscellaneous routines
 
+#[cfg(not(feature = "std"))]
+use math::*;
+
 /**
 Computes the parallactic angle of a celestial body
 
 # Returns
 
 * `parallac_angle`: Parallactic angle of the celestial
-                    body *| in radians*
+                    body, negative before and positive after the
+                    meridian transit *| in radians*
 
 # Arguments
 
@@ -52,7 +56,10 @@ horizon
 # Returns
 
 * `parallac_angle`: Parallactic angle of the celestial body
-                    on the horizon *| in radians*
+                    on the horizon when setting *| in radians*, which
+                    is its negative when rising. It's `NaN` for a
+                    body that never reaches the horizon, being
+                    circumpolar or never rising.
 
 # Arguments
 
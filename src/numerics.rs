@@ -0,0 +1,219 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Refinement of the instants of events
+
+
+/**
+Finds a zero of a function by bisection
+
+The function must change sign between the given arguments, and
+the zero found is the one that bisection converges to, if there are
+several of them.
+
+# Returns
+
+* `zero`: Argument of the zero, within `tol` of the true zero, or
+          `None` if the function doesn't change sign between `a` and
+          `b`
+
+# Arguments
+
+* `f`  : The function, such as of a Julian (Ephemeris) day
+* `a`  : An argument at one end of the interval
+* `b`  : An argument at the other end of the interval
+* `tol`: Tolerance of the argument of the zero
+**/
+pub fn bisection<F>(f: F, a: f64, b: f64, tol: f64) -> Option<f64> where F: Fn(f64) -> f64 {
+
+    let (mut a, mut b) = (a, b);
+    let a_negative = f(a) <= 0.0;
+    if a_negative == (f(b) <= 0.0) {
+        return None;
+    }
+
+    let mut iters = 0;
+    while (b - a).abs() > tol {
+        let m = (a + b) / 2.0;
+        if (f(m) <= 0.0) == a_negative { a = m; } else { b = m; }
+        iters += 1;
+    }
+    trace_event!("numerics::bisection", Converged, iters, (b - a).abs());
+
+    Some((a + b) / 2.0)
+
+}
+
+/**
+Finds a zero of a function by Brent's method
+
+Brent's method combines bisection with the secant method and inverse
+quadratic interpolation, so that it is as robust as bisection, but
+converges much faster for smooth functions, such as the differences
+of longitudes that define the instants of equinoxes, phases and
+conjunctions.
+
+# Returns
+
+* `zero`: Argument of the zero, within `tol` of the true zero, or
+          `None` if the function doesn't change sign between `a` and
+          `b`
+
+# Arguments
+
+* `f`  : The function, such as of a Julian (Ephemeris) day
+* `a`  : An argument at one end of the interval
+* `b`  : An argument at the other end of the interval
+* `tol`: Tolerance of the argument of the zero
+**/
+pub fn brent<F>(f: F, a: f64, b: f64, tol: f64) -> Option<f64> where F: Fn(f64) -> f64 {
+
+    let (mut a, mut b) = (a, b);
+    let (mut fa, mut fb) = (f(a), f(b));
+    if fa == 0.0 {
+        return Some(a);
+    }
+    if fb == 0.0 {
+        return Some(b);
+    }
+    if fa.signum() == fb.signum() {
+        return None;
+    }
+
+    let (mut c, mut fc) = (a, fa);
+    let mut d = b - a;
+    let mut e = d;
+
+    for iters in 1..200 {
+        if fb.signum() == fc.signum() {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+
+        let tol1 = 2.0*f64::EPSILON*b.abs() + tol/2.0;
+        let m = (c - b) / 2.0;
+        if m.abs() <= tol1 || fb == 0.0 {
+            trace_event!("numerics::brent", Converged, iters, m.abs());
+            return Some(b);
+        }
+
+        if e.abs() >= tol1 && fa.abs() > fb.abs() {
+            // the secant method, or inverse quadratic interpolation
+            // when there are three distinct values
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0*m*s, 1.0 - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s*(2.0*m*q*(q - r) - (b - a)*(r - 1.0)),
+                    (q - 1.0)*(r - 1.0)*(s - 1.0)
+                )
+            };
+            if p > 0.0 { q = -q; } else { p = -p; }
+
+            if 2.0*p < (3.0*m*q - (tol1*q).abs()).min((e*q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = m;
+                e = m;
+            }
+        } else {
+            d = m;
+            e = m;
+        }
+
+        a = b;
+        fa = fb;
+        b += if d.abs() > tol1 { d } else { tol1.copysign(m) };
+        fb = f(b);
+    }
+    trace_event!("numerics::brent", Truncated, 200, (c - b).abs());
+
+    Some(b)
+
+}
+
+/**
+Finds a minimum of a function by golden section search
+
+The function must have a single minimum between the given arguments,
+or the minimum found is a local one. As a function is flat near its
+minimum, its values can't tell apart arguments much nearer than the
+square root of the machine epsilon, relative to the function's scale,
+so a smaller `tol` doesn't make the minimum more accurate.
+
+# Returns
+
+* `minimum`: Argument of the minimum, within `tol` of the true
+             minimum
+
+# Arguments
+
+* `f`  : The function, such as of a Julian (Ephemeris) day
+* `a`  : An argument at one end of the interval
+* `b`  : An argument at the other end of the interval
+* `tol`: Tolerance of the argument of the minimum
+**/
+pub fn golden_section<F>(f: F, a: f64, b: f64, tol: f64) -> f64 where F: Fn(f64) -> f64 {
+
+    // the inverse of the golden ratio
+    let ratio = 0.618_033_988_749_894_9;
+
+    let (mut a, mut b) = (a, b);
+    let (mut c, mut d) = (b - ratio*(b - a), a + ratio*(b - a));
+    let (mut fc, mut fd) = (f(c), f(d));
+
+    let mut iters = 0;
+    while (b - a).abs() > tol {
+        if fc < fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = b - ratio*(b - a);
+            fc = f(c);
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = a + ratio*(b - a);
+            fd = f(d);
+        }
+        iters += 1;
+    }
+    trace_event!("numerics::golden_section", Converged, iters, (b - a).abs());
+
+    (a + b) / 2.0
+
+}
@@ -22,9 +22,12 @@ THE SOFTWARE.
 
 //! Corrections for nutation
 
-use angle;
-use time;
 use coords;
+use fund_args;
+use std;
+use util;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 /**
 Computes nutation in ecliptic longitude and obliquity
@@ -42,109 +45,154 @@ Computes nutation in ecliptic longitude and obliquity
 **/
 pub fn nutation(JD: f64) -> (f64, f64)
 {
-    struct terms(i8, i8, i8, i8, i8, i32, i32, i32, i16);
-    let terms_for_nutation = [
-        terms( 0,  0,  0,  0,  1, -171996, -1742, 92025,  89),
-        terms(-2,  0,  0,  2,  2,  -13187,   -16,  5736, -31),
-        terms( 0,  0,  0,  2,  2,   -2274,    -2,   977,  -5),
-        terms( 0,  0,  0,  0,  2,    2062,     2,  -895,   5),
-        terms( 0,  1,  0,  0,  0,    1426,   -34,    54,  -1),
-        terms( 0,  0,  1,  0,  0,     712,     1,    -7,   0),
-        terms(-2,  1,  0,  2,  2,    -517,    12,   224,  -6),
-        terms( 0,  0,  0,  2,  1,    -386,    -4,   200,   0),
-        terms( 0,  0,  1,  2,  2,    -301,     0,   129,  -1),
-        terms(-2, -1,  0,  2,  2,     217,    -5,   -95,   3),
-        terms(-2,  0,  1,  0,  0,    -158,     0,     0,   0),
-        terms(-2,  0,  0,  2,  1,     129,     1,   -70,   0),
-        terms( 0,  0, -1,  2,  2,     123,     0,   -53,   0),
-        terms( 2,  0,  0,  0,  0,      63,     0,     0,   0),
-        terms( 0,  0,  1,  0,  1,      63,     1,   -33,   0),
-        terms( 2,  0, -1,  2,  2,     -59,     0,    26,   0),
-        terms( 0,  0, -1,  0,  1,     -58,    -1,    32,   0),
-        terms( 0,  0,  1,  2,  1,     -51,     0,    27,   0),
-        terms(-2,  0,  2,  0,  0,      48,     0,     0,   0),
-        terms( 0,  0, -2,  2,  1,      46,     0,   -24,   0),
-        terms( 2,  0,  0,  2,  2,     -38,     0,    16,   0),
-        terms( 0,  0,  2,  2,  2,     -31,     0,    13,   0),
-        terms( 0,  0,  2,  0,  0,      29,     0,     0,   0),
-        terms(-2,  0,  1,  2,  2,      29,     0,   -12,   0),
-        terms( 0,  0,  0,  2,  0,      26,     0,     0,   0),
-        terms(-2,  0,  0,  2,  0,     -22,     0,     0,   0),
-        terms( 0,  0, -1,  2,  1,      21,     0,   -10,   0),
-        terms( 0,  2,  0,  0,  0,      17,    -1,     0,   0),
-        terms( 2,  0, -1,  0,  1,      16,     0,    -8,   0),
-        terms(-2,  2,  0,  2,  2,     -16,     1,     7,   0),
-        terms( 0,  1,  0,  0,  1,     -15,     0,     9,   0),
-        terms(-2,  0,  1,  0,  1,     -13,     0,     7,   0),
-        terms( 0, -1,  0,  0,  1,     -12,     0,     6,   0),
-        terms( 0,  0,  2, -2,  0,      11,     0,     0,   0),
-        terms( 2,  0, -1,  2,  1,     -10,     0,     5,   0),
-        terms( 2,  0,  1,  2,  2,      -8,     0,     3,   0),
-        terms( 0,  1,  0,  2,  2,       7,     0,    -3,   0),
-        terms(-2,  1,  1,  0,  0,      -7,     0,     0,   0),
-        terms( 0, -1,  0,  2,  2,      -7,     0,     3,   0),
-        terms( 2,  0,  0,  2,  1,      -7,     0,     3,   0),
-        terms( 2,  0,  1,  0,  0,       6,     0,     0,   0),
-        terms(-2,  0,  2,  2,  2,       6,     0,    -3,   0),
-        terms(-2,  0,  1,  2,  1,       6,     0,    -3,   0),
-        terms( 2,  0, -2,  0,  1,      -6,     0,     3,   0),
-        terms( 2,  0,  0,  0,  1,      -6,     0,     3,   0),
-        terms( 0, -1,  1,  0,  0,       5,     0,     0,   0),
-        terms(-2, -1,  0,  2,  1,      -5,     0,     3,   0),
-        terms(-2,  0,  0,  0,  1,      -5,     0,     3,   0),
-        terms( 0,  0,  2,  2,  1,      -5,     0,     3,   0),
-        terms(-2,  0,  2,  0,  1,       4,     0,     0,   0),
-        terms(-2,  1,  0,  2,  1,       4,     0,     0,   0),
-        terms( 0,  0,  1, -2,  0,       4,     0,     0,   0),
-        terms(-1,  0,  1,  0,  0,      -4,     0,     0,   0),
-        terms(-2,  1,  0,  0,  0,      -4,     0,     0,   0),
-        terms( 1,  0,  0,  0,  0,      -4,     0,     0,   0),
-        terms( 0,  0,  1,  2,  0,       3,     0,     0,   0),
-        terms( 0,  0, -2,  2,  2,      -3,     0,     0,   0),
-        terms(-1, -1,  1,  0,  0,      -3,     0,     0,   0),
-        terms( 0,  1,  1,  0,  0,      -3,     0,     0,   0),
-        terms( 0, -1,  1,  2,  2,      -3,     0,     0,   0),
-        terms( 2, -1, -1,  2,  2,      -3,     0,     0,   0),
-        terms( 0,  0,  3,  2,  2,      -3,     0,     0,   0),
-        terms( 2, -1,  0,  2,  2,      -3,     0,     0,   0),
-    ];
-
-    let t = time::julian_cent(JD);
-
-    let M1 = angle::limit_to_360(
-        134.96298 + t*(477198.867398 + t*(0.0086972 + t/56250.0))
-    ).to_radians();
-    let M  = angle::limit_to_360(
-        357.52772 + t*(35999.05034   - t*(0.0001603 + t/300000.0))
-    ).to_radians();
-    let D  = angle::limit_to_360(
-        297.85036 + t*(445267.11148  - t*(0.0019142 - t/189474.0))
-    ).to_radians();
-    let F  = angle::limit_to_360(
-        93.27191  + t*(483202.017538 - t*(0.0036825 - t/327270.0))
-    ).to_radians();
-    let om = angle::limit_to_360(
-        125.04452 - t*(1934.136261   - t*(0.0020708 + t/450000.0))
-    ).to_radians();
-
-    let mut nut_in_long = 0.0;
-    let mut nut_in_oblq = 0.0;
+    nutation_frm_args(&fund_args::FundamentalArgs::new(JD))
+}
 
-    let div = 0.0001/3600.0;
+/**
+Computes nutation in ecliptic longitude and obliquity from the
+fundamental arguments
+
+This is `nutation()`, for arguments already computed for another
+routine at the same instant.
+
+# Returns
+
+`(nut_in_long, nut_in_oblq)`
+
+* `nut_in_long`: Nutation in ecliptic longitude *| in radians*
+* `nut_in_oblq`: Nutation in obliquity of the ecliptic *| in radians*
 
-    for x in terms_for_nutation.iter() {
-        let arg =
-            (x.0 as f64) * D   +
-            (x.1 as f64) * M   +
-            (x.2 as f64) * M1  +
-            (x.3 as f64) * F   +
-            (x.4 as f64) * om;
+# Arguments
 
-        nut_in_long += ((x.5 as f64) + t*(x.6 as f64)/10.0) * arg.sin() * div;
-        nut_in_oblq += ((x.7 as f64) + t*(x.8 as f64)/10.0) * arg.cos() * div;
+`args`: [FundamentalArgs](../fund_args/struct.FundamentalArgs.html)
+        of the instant
+**/
+pub fn nutation_frm_args(args: &fund_args::FundamentalArgs) -> (f64, f64)
+{
+    let mut nut = [(0.0, 0.0)];
+    sum_nutation_series(std::slice::from_ref(args), &mut nut);
+
+    nut[0]
+}
+
+/**
+Computes nutation in ecliptic longitude and obliquity for many
+instants
+
+This gives the same results as `nutation()`, evaluating each term of
+the series for a chunk of the instants in a tight loop the compiler
+can vectorize. With the `rayon` feature, the chunks are evaluated in
+parallel.
+
+# Returns
+
+* `nut`: `(nut_in_long, nut_in_oblq)` for each instant, as returned
+         by `nutation()`
+
+# Arguments
+
+`JD`: Julian (Ephemeris) days
+**/
+pub fn nutation_many(JD: &[f64]) -> Vec<(f64, f64)>
+{
+    let args: Vec<fund_args::FundamentalArgs> = JD.iter().map(|&JD| fund_args::FundamentalArgs::new(JD)).collect();
+    let mut nut = vec![(0.0, 0.0); JD.len()];
+
+    util::for_each_chunk(&args, &mut nut, sum_nutation_series);
+
+    nut
+}
+
+struct terms(i8, i8, i8, i8, i8, i32, i32, i32, i16);
+
+const TERMS_FOR_NUTATION: [terms; 63] = [
+    terms( 0,  0,  0,  0,  1, -171996, -1742, 92025,  89),
+    terms(-2,  0,  0,  2,  2,  -13187,   -16,  5736, -31),
+    terms( 0,  0,  0,  2,  2,   -2274,    -2,   977,  -5),
+    terms( 0,  0,  0,  0,  2,    2062,     2,  -895,   5),
+    terms( 0,  1,  0,  0,  0,    1426,   -34,    54,  -1),
+    terms( 0,  0,  1,  0,  0,     712,     1,    -7,   0),
+    terms(-2,  1,  0,  2,  2,    -517,    12,   224,  -6),
+    terms( 0,  0,  0,  2,  1,    -386,    -4,   200,   0),
+    terms( 0,  0,  1,  2,  2,    -301,     0,   129,  -1),
+    terms(-2, -1,  0,  2,  2,     217,    -5,   -95,   3),
+    terms(-2,  0,  1,  0,  0,    -158,     0,     0,   0),
+    terms(-2,  0,  0,  2,  1,     129,     1,   -70,   0),
+    terms( 0,  0, -1,  2,  2,     123,     0,   -53,   0),
+    terms( 2,  0,  0,  0,  0,      63,     0,     0,   0),
+    terms( 0,  0,  1,  0,  1,      63,     1,   -33,   0),
+    terms( 2,  0, -1,  2,  2,     -59,     0,    26,   0),
+    terms( 0,  0, -1,  0,  1,     -58,    -1,    32,   0),
+    terms( 0,  0,  1,  2,  1,     -51,     0,    27,   0),
+    terms(-2,  0,  2,  0,  0,      48,     0,     0,   0),
+    terms( 0,  0, -2,  2,  1,      46,     0,   -24,   0),
+    terms( 2,  0,  0,  2,  2,     -38,     0,    16,   0),
+    terms( 0,  0,  2,  2,  2,     -31,     0,    13,   0),
+    terms( 0,  0,  2,  0,  0,      29,     0,     0,   0),
+    terms(-2,  0,  1,  2,  2,      29,     0,   -12,   0),
+    terms( 0,  0,  0,  2,  0,      26,     0,     0,   0),
+    terms(-2,  0,  0,  2,  0,     -22,     0,     0,   0),
+    terms( 0,  0, -1,  2,  1,      21,     0,   -10,   0),
+    terms( 0,  2,  0,  0,  0,      17,    -1,     0,   0),
+    terms( 2,  0, -1,  0,  1,      16,     0,    -8,   0),
+    terms(-2,  2,  0,  2,  2,     -16,     1,     7,   0),
+    terms( 0,  1,  0,  0,  1,     -15,     0,     9,   0),
+    terms(-2,  0,  1,  0,  1,     -13,     0,     7,   0),
+    terms( 0, -1,  0,  0,  1,     -12,     0,     6,   0),
+    terms( 0,  0,  2, -2,  0,      11,     0,     0,   0),
+    terms( 2,  0, -1,  2,  1,     -10,     0,     5,   0),
+    terms( 2,  0,  1,  2,  2,      -8,     0,     3,   0),
+    terms( 0,  1,  0,  2,  2,       7,     0,    -3,   0),
+    terms(-2,  1,  1,  0,  0,      -7,     0,     0,   0),
+    terms( 0, -1,  0,  2,  2,      -7,     0,     3,   0),
+    terms( 2,  0,  0,  2,  1,      -7,     0,     3,   0),
+    terms( 2,  0,  1,  0,  0,       6,     0,     0,   0),
+    terms(-2,  0,  2,  2,  2,       6,     0,    -3,   0),
+    terms(-2,  0,  1,  2,  1,       6,     0,    -3,   0),
+    terms( 2,  0, -2,  0,  1,      -6,     0,     3,   0),
+    terms( 2,  0,  0,  0,  1,      -6,     0,     3,   0),
+    terms( 0, -1,  1,  0,  0,       5,     0,     0,   0),
+    terms(-2, -1,  0,  2,  1,      -5,     0,     3,   0),
+    terms(-2,  0,  0,  0,  1,      -5,     0,     3,   0),
+    terms( 0,  0,  2,  2,  1,      -5,     0,     3,   0),
+    terms(-2,  0,  2,  0,  1,       4,     0,     0,   0),
+    terms(-2,  1,  0,  2,  1,       4,     0,     0,   0),
+    terms( 0,  0,  1, -2,  0,       4,     0,     0,   0),
+    terms(-1,  0,  1,  0,  0,      -4,     0,     0,   0),
+    terms(-2,  1,  0,  0,  0,      -4,     0,     0,   0),
+    terms( 1,  0,  0,  0,  0,      -4,     0,     0,   0),
+    terms( 0,  0,  1,  2,  0,       3,     0,     0,   0),
+    terms( 0,  0, -2,  2,  2,      -3,     0,     0,   0),
+    terms(-1, -1,  1,  0,  0,      -3,     0,     0,   0),
+    terms( 0,  1,  1,  0,  0,      -3,     0,     0,   0),
+    terms( 0, -1,  1,  2,  2,      -3,     0,     0,   0),
+    terms( 2, -1, -1,  2,  2,      -3,     0,     0,   0),
+    terms( 0,  0,  3,  2,  2,      -3,     0,     0,   0),
+    terms( 2, -1,  0,  2,  2,      -3,     0,     0,   0),
+];
+
+// Sums the series of nutation for each of the instants
+fn sum_nutation_series(args: &[fund_args::FundamentalArgs], nut: &mut [(f64, f64)])
+{
+    let div = 0.0001/3600.0;
+
+    for x in TERMS_FOR_NUTATION.iter() {
+        for (nut, args) in nut.iter_mut().zip(args.iter()) {
+            let arg =
+                (x.0 as f64) * args.D  +
+                (x.1 as f64) * args.M  +
+                (x.2 as f64) * args.M1 +
+                (x.3 as f64) * args.F  +
+                (x.4 as f64) * args.om;
+
+            nut.0 += ((x.5 as f64) + args.JC*(x.6 as f64)/10.0) * arg.sin() * div;
+            nut.1 += ((x.7 as f64) + args.JC*(x.8 as f64)/10.0) * arg.cos() * div;
+        }
     }
 
-    (nut_in_long.to_radians(), nut_in_oblq.to_radians())
+    for nut in nut.iter_mut() {
+        *nut = (nut.0.to_radians(), nut.1.to_radians());
+    }
 }
 
 /**
@@ -0,0 +1,228 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+
+//! Observers on the Earth
+//!
+//! An [Observer](./struct.Observer.html) bundles the location, the
+//! weather and the time zone of an observer, and hands them to the
+//! routines of [coords](../coords/index.html),
+//! [parallax](../parallax/index.html), [atmos](../atmos/index.html) and
+//! [transit](../transit/index.html) in their own conventions.
+
+use angle;
+use atmos;
+use body::Body;
+use consts;
+use coords;
+use error;
+use parallax;
+use planet::earth;
+use time;
+use transit;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Holds the location, weather and time zone of an observer
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Observer {
+    /// Geographic longitude, positive westwards from the Greenwich
+    /// meridian, as everywhere in this library *| in radians*
+    pub long: f64,
+    /// Geodetic latitude, positive northwards *| in radians*
+    pub lat: f64,
+    /// Height above sea level *| in meters*
+    pub height: f64,
+    /// Local atmospheric pressure *| in millibars*
+    pub pressure: f64,
+    /// Local temperature *| in kelvins*
+    pub temp: f64,
+    /// Offset of the local time from UTC, positive eastwards from the
+    /// Greenwich meridian, such as +1 for Central European Time
+    /// *| in hours*
+    pub utc_offset: f64,
+}
+
+impl Observer {
+    /**
+    Makes an observer, checking the location
+
+    The weather is the standard one of 1010 millibars and 283 kelvins,
+    for which the refraction formulae are given, and the local time is
+    UTC.
+
+    # Returns
+
+    * `Ok(observer)`: The [Observer](./struct.Observer.html), or
+    * `Err(error)`  : `Error::InvalidLatitude` if the latitude is
+                      beyond ±90 degrees, or `Error::NotFinite`
+
+    # Arguments
+
+    * `long`  : Geographic longitude, positive westwards *| in radians*
+    * `lat`   : Geodetic latitude *| in radians*
+    * `height`: Height above sea level *| in meters*
+    **/
+    pub fn try_new(long: f64, lat: f64, height: f64) -> Result<Observer, error::Error> {
+        if !long.is_finite() || !height.is_finite() {
+            return Err(error::Error::NotFinite);
+        }
+        error::check_lat(lat)?;
+
+        Ok(Observer {
+            long,
+            lat,
+            height,
+            pressure: 1010.0,
+            temp: 283.0,
+            utc_offset: 0.0
+        })
+    }
+
+    /// Makes an observer as [try_new()](#method.try_new), from a
+    /// longitude positive eastwards, as given by maps and receivers
+    /// of satellite navigation *| in radians*
+    pub fn try_frm_east_long(east_long: f64, lat: f64, height: f64) -> Result<Observer, error::Error> {
+        Observer::try_new(-east_long, lat, height)
+    }
+
+    /// The observer's geographic point
+    pub fn geograph_point(&self) -> coords::GeographPoint {
+        coords::GeographPoint { long: self.long, lat: self.lat }
+    }
+
+    /// The observer's geodetic point, on a reference `ellipsoid`
+    pub fn geodetic_point(&self, ellipsoid: parallax::Ellipsoid) -> parallax::GeodeticPoint {
+        parallax::GeodeticPoint {
+            long: self.long,
+            lat: self.lat,
+            height: self.height,
+            ellipsoid
+        }
+    }
+
+    /// The local time, for a Julian day in UTC
+    pub fn local_frm_utc(&self, JD: f64) -> f64 {
+        JD + self.utc_offset/24.0
+    }
+
+    /// The Julian day in UTC, for a local time
+    pub fn utc_frm_local(&self, JD: f64) -> f64 {
+        JD - self.utc_offset/24.0
+    }
+
+    /// The topocentric equatorial point of a body, from its
+    /// geocentric equatorial point, its distance `dist` from the
+    /// center of the Earth *| in AU* and the sidereal time at
+    /// Greenwich `green_sidr` *| in radians*, on the WGS84 ellipsoid
+    pub fn topocentric(&self, eq_point: &coords::EqPoint, dist: f64, green_sidr: f64) -> coords::EqPoint {
+        parallax::topocentric(eq_point, dist, &self.geodetic_point(parallax::Ellipsoid::WGS84), green_sidr)
+    }
+
+    /// The horizontal point of a body seen through the local
+    /// atmosphere, from its topocentric equatorial point and the
+    /// sidereal time at Greenwich `green_sidr` *| in radians*
+    pub fn apprnt_hz(&self, eq_point: &coords::EqPoint, green_sidr: f64) -> coords::HzPoint {
+        eq_point.to_apprnt_hz(green_sidr, &self.geograph_point(), Some((self.pressure, self.temp)))
+    }
+
+    /// The topocentric equatorial point of a body, from its horizontal
+    /// point seen through the local atmosphere and the sidereal time
+    /// at Greenwich `green_sidr` *| in radians*
+    pub fn eq_frm_apprnt_hz(&self, hz_point: &coords::HzPoint, green_sidr: f64) -> coords::EqPoint {
+        hz_point.apprnt_to_eq(green_sidr, &self.geograph_point(), Some((self.pressure, self.temp)))
+    }
+
+    /// The true altitude, from the apparent altitude through the local
+    /// atmosphere *| in radians*, by a refraction `model`
+    pub fn true_alt_frm_apprnt(&self, apprnt_alt: f64, model: &atmos::RefracModel) -> f64 {
+        atmos::true_alt_frm_apprnt(apprnt_alt, self.pressure, self.temp, model)
+    }
+
+    /// The apparent altitude through the local atmosphere, from the
+    /// true altitude *| in radians*, by a refraction `model`
+    pub fn apprnt_alt_frm_true(&self, true_alt: f64, model: &atmos::RefracModel) -> f64 {
+        atmos::apprnt_alt_frm_true(true_alt, self.pressure, self.temp, model)
+    }
+
+    /// The extinction of the light of a body at the true altitude
+    /// `true_alt` *| in radians*, in a photometric `band`, for the
+    /// observer's height *| in magnitudes*
+    pub fn extinction(&self, true_alt: f64, band: &atmos::Band) -> f64 {
+        atmos::extinction(true_alt, band, self.height)
+    }
+
+    /// The observer's position and velocity with respect to the
+    /// center of the Earth, `((x, y, z), (vx, vy, vz))` *| in AU and
+    /// AU per day*, referred to the mean equator and equinox of
+    /// J2000.0, for a Julian (Ephemeris) day `JD`. Polar motion and
+    /// nutation, which move the observer by some tens of meters, are
+    /// neglected.
+    pub fn geocent_rect_state(&self, JD: f64) -> ((f64, f64, f64), (f64, f64, f64)) {
+        let JD_UT = JD - time::delta_t_frm_julian_day(JD)/86400.0;
+        let loc_sidr = time::mn_sidr(JD_UT) - self.long;
+
+        let (rho_sin_phi, rho_cos_phi) = earth::rho_sin_cos_phi(self.lat, self.height);
+        let r = earth::eq_rad() * 1000.0 / consts::ASTRONOMICAL_UNIT;
+        let (x, y, z) = (r*rho_cos_phi*loc_sidr.cos(), r*rho_cos_phi*loc_sidr.sin(), r*rho_sin_phi);
+
+        // rate of rotation of the Earth *| in radians per day*
+        let w = angle::TWO_PI * 1.002737811911354;
+
+        let to_J2000 = coords::Matrix3::precession(JD, 2451545.0);
+        let pos = to_J2000.apply(&[x, y, z]);
+        let vel = to_J2000.apply(&[-w*y, w*x, 0.0]);
+
+        ((pos[0], pos[1], pos[2]), (vel[0], vel[1], vel[2]))
+    }
+
+    /**
+    Computes the times of rise, transit and set of a body on a local
+    day
+
+    # Returns
+
+    * `Ok(rise_set_transit)`: The [RiseSetTransit](../transit/enum.RiseSetTransit.html)
+                              times from the local midnight, as Julian
+                              days in UTC, or
+    * `Err(error)`          : As from `transit::rise_set_transit()`
+
+    # Arguments
+
+    * `body`    : The [Body](../body/enum.Body.html)
+    * `date`    : The local date, whose time of day is ignored
+    * `altitude`: The [Altitude](../transit/enum.Altitude.html)
+                  definition of rising and setting
+    **/
+    pub fn rise_set_transit(&self, body: &Body, date: &time::Date,
+                            altitude: &transit::Altitude) -> Result<transit::RiseSetTransit, error::Error> {
+        let JD = time::julian_day(&time::Date {
+            year: date.year,
+            month: date.month,
+            decimal_day: date.decimal_day.floor(),
+            cal_type: date.cal_type
+        });
+
+        transit::rise_set_transit(body, &self.geograph_point(), self.utc_frm_local(JD), altitude)
+    }
+}
@@ -0,0 +1,222 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+
+//! Lunar occultations of stars and planets
+
+/*
+
+The circumstances of an occultation for an observer follow from the
+Besselian elements of the occultation, as for a solar eclipse: the
+body is hidden while the observer is inside the shadow of the Moon
+cast by the body. A star casts a cylinder, and a planet a cone from
+its center, so the times are those of the disappearance and the
+reappearance of the center of the planet.
+
+*/
+
+use angle;
+use coords;
+use eclipse::besselian;
+use planet;
+use time;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Holds the circumstances of a lunar occultation for an observer
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Occultation {
+    /// Julian (Ephemeris) day of the disappearance
+    pub disappearance: f64,
+    /// Julian (Ephemeris) day of the reappearance
+    pub reappearance: f64,
+    /// Position angle of the point of disappearance on the Moon's
+    /// limb, measured eastwards from the north *| in radians*
+    pub disappearance_pa: f64,
+    /// Position angle of the point of reappearance on the Moon's
+    /// limb, measured eastwards from the north *| in radians*
+    pub reappearance_pa: f64
+}
+
+/**
+Predicts the occultation of a star by the Moon for an observer
+
+# Returns
+
+* `occultation`: The [Occultation](./struct.Occultation.html), or
+                 `None` if the Moon misses the star as seen by the
+                 observer, or if the star is below the observer's
+                 horizon at the middle of the occultation
+
+# Arguments
+
+* `star_asc`      : Apparent right ascension of the star *| in radians*
+* `star_dec`      : Apparent declination of the star *| in radians*
+* `JD`            : Julian (Ephemeris) day within a few hours of the
+                    conjunction of the Moon and the star in right
+                    ascension
+* `geograph_point`: Geographic point of the observer *| in radians*
+* `height`        : Observer's height above sea level *| in meters*
+**/
+pub fn star (
+
+    star_asc       : f64,
+    star_dec       : f64,
+    JD             : f64,
+    geograph_point : &coords::GeographPoint,
+    height         : f64
+
+) -> Option<Occultation> {
+
+    let JD0 = conjunction(JD, |JD| besselian::instantaneous_occult_values(JD, star_asc, star_dec).x);
+    let elements = besselian::occult_elements(JD0, time::delta_t_frm_julian_day(JD0), star_asc, star_dec);
+
+    local_circumstances(&elements, JD0, geograph_point, height)
+
+}
+
+/**
+Predicts the occultation of a planet by the Moon for an observer
+
+The times are those of the disappearance and the reappearance of the
+center of the planet.
+
+# Returns
+
+* `occultation`: The [Occultation](./struct.Occultation.html), or
+                 `None` if the Moon misses the planet as seen by the
+                 observer, or if the planet is below the observer's
+                 horizon at the middle of the occultation
+
+# Arguments
+
+* `planet`        : Any variant of [Planet](../planet/enum.Planet.html)
+                    other than the Earth
+* `JD`            : Julian (Ephemeris) day within a few hours of the
+                    conjunction of the Moon and the planet in right
+                    ascension
+* `geograph_point`: Geographic point of the observer *| in radians*
+* `height`        : Observer's height above sea level *| in meters*
+**/
+pub fn planet (
+
+    planet         : &planet::Planet,
+    JD             : f64,
+    geograph_point : &coords::GeographPoint,
+    height         : f64
+
+) -> Option<Occultation> {
+
+    let JD0 = conjunction(JD, |JD| besselian::instantaneous_planet_occult_values(JD, planet).x);
+    let elements = besselian::planet_occult_elements(JD0, time::delta_t_frm_julian_day(JD0), planet);
+
+    local_circumstances(&elements, JD0, geograph_point, height)
+
+}
+
+// Finds the instant near JD at which the x coordinate of the axis of
+// the shadow is zero
+fn conjunction<F>(mut JD: f64, x: F) -> f64 where F: Fn(f64) -> f64 {
+
+    let h = 1.0 / 24.0;
+    let mut iters = 0;
+    let mut residual = f64::INFINITY;
+
+    while iters < 10 && residual.abs() > 1e-7 {
+        let x0 = x(JD);
+        let rate = (x(JD + h) - x0) / h;
+        residual = x0 / rate;
+        JD -= residual;
+        iters += 1;
+    }
+    trace_event!("occult::conjunction", Converged, iters, residual);
+
+    JD
+
+}
+
+// Computes the contacts of the observer with the shadow, by iterating
+// the linear motion of the shadow relative to the observer
+fn local_circumstances (
+
+    elements       : &besselian::Elements,
+    JD0            : f64,
+    geograph_point : &coords::GeographPoint,
+    height         : f64
+
+) -> Option<Occultation> {
+
+    let h = 1.0 / 1440.0;
+    let state = |JD: f64| {
+        let v = besselian::values(elements, JD);
+        let (xi, eta, zeta) = besselian::observer_coords(elements, JD, geograph_point, height);
+        (v.x - xi, v.y - eta, v.l1 - zeta*elements.tan_f1, zeta)
+    };
+    let motion = |JD: f64| {
+        let (u, v, L, zeta) = state(JD);
+        let (u1, v1, _, _) = state(JD + h);
+        (u, v, L, zeta, (u1 - u)/h, (v1 - v)/h)
+    };
+
+    // the closest approach
+    let mut JD = JD0;
+    for _ in 0..3 {
+        let (u, v, _, _, a, b) = motion(JD);
+        JD -= (u*a + v*b) / (a*a + b*b);
+    }
+    let (u, v, L, zeta, _, _) = motion(JD);
+    if u*u + v*v >= L*L || zeta <= 0.0 {
+        return None;
+    }
+    let JD_mid = JD;
+
+    let contact = |sign: f64| {
+        let mut JD = JD_mid;
+        let mut iters = 0;
+        let mut residual = f64::INFINITY;
+        while iters < 10 && residual.abs() > 1e-8 {
+            let (u, v, L, _, a, b) = motion(JD);
+            let n_sqr = a*a + b*b;
+            let n = n_sqr.sqrt();
+            let delta = (u*b - v*a) / n;
+            let tau = -(u*a + v*b) / n_sqr;
+            residual = tau + sign * (L*L - delta*delta).max(0.0).sqrt() / n;
+            JD += residual;
+            iters += 1;
+        }
+        trace_event!("occult::local_circumstances", Converged, iters, residual);
+        let (u, v, _, _) = state(JD);
+        (JD, angle::limit_to_two_PI((-u).atan2(-v)))
+    };
+
+    let (disappearance, disappearance_pa) = contact(-1.0);
+    let (reappearance, reappearance_pa) = contact(1.0);
+
+    Some(Occultation {
+        disappearance,
+        reappearance,
+        disappearance_pa,
+        reappearance_pa
+    })
+
+}
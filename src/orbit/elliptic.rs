@@ -22,7 +22,12 @@ THE SOFTWARE.
 
 //! Elliptic orbits
 
+use angle;
+use error;
+use error::Error;
+use float::Float;
 use orbit;
+use strict;
 use std::f64::consts::PI;
 
 /**
@@ -38,10 +43,12 @@ Computes the true anomaly of a body in an elliptic orbit
 * `ecc`     : Eccentricity of the orbit
 **/
 #[inline]
-pub fn true_anom(ecc_anom: f64, ecc: f64) -> f64 {
+pub fn true_anom<T: Float>(ecc_anom: T, ecc: T) -> T {
 
-    2.0 * ((1.0 + ecc).sqrt() * (ecc_anom/2.0).tan()).atan2 (
-        (1.0 - ecc).sqrt()
+    let (one, two) = (T::from_f64(1.0), T::from_f64(2.0));
+
+    two * ((one + ecc).sqrt() * (ecc_anom/two).tan()).atan2 (
+        (one - ecc).sqrt()
     )
 
 }
@@ -61,9 +68,9 @@ eccentric anomaly
 * `ecc`     : Eccentricity of the orbit
 **/
 #[inline]
-pub fn rad_vec_frm_ecc_anom(ecc_anom: f64, a: f64, ecc: f64) -> f64 {
+pub fn rad_vec_frm_ecc_anom<T: Float>(ecc_anom: T, a: T, ecc: T) -> T {
 
-    a * (1.0 - ecc*ecc_anom.cos())
+    a * (T::from_f64(1.0) - ecc*ecc_anom.cos())
     
 }
 
@@ -82,8 +89,9 @@ true anomaly
 * `ecc`      : Eccentricity of the orbit
 **/
 #[inline]
-pub fn rad_vec_frm_true_anom(true_anom: f64, a: f64, ecc: f64) -> f64 {
-    a * (1.0 - ecc*ecc) / (1.0 + ecc*true_anom.cos())
+pub fn rad_vec_frm_true_anom<T: Float>(true_anom: T, a: T, ecc: T) -> T {
+    let one = T::from_f64(1.0);
+    a * (one - ecc*ecc) / (one + ecc*true_anom.cos())
 }
 
 /**
@@ -103,15 +111,352 @@ Computes the eccentric anomaly of a body in an elliptic orbit
 pub fn ecc_anom(mean_anom: f64, ecc: f64, accuracy: f64) -> f64 {
     let mut prev_E = 0.0;
     let mut E = mean_anom;
+    let mut iters = 0;
 
     while (E - prev_E).abs() > accuracy {
         prev_E = E;
-        E = mean_anom + ecc * E.sin();
+        E = mean_anom + ecc * strict::kepler_sin(E);
+        iters += 1;
     }
 
+    trace_event!("orbit::elliptic::ecc_anom", Converged, iters, (E - prev_E).abs());
+
     E
 }
 
+/**
+Computes the eccentric anomaly of a body in an elliptic orbit,
+checking the eccentricity and the accuracy
+
+# Returns
+
+* `ecc_anom`: Eccentric anomaly of the body *| in radians*, or an
+              `Error::InvalidEccentricity` if `ecc` isn't in the range
+              [0, 1) of an elliptic orbit
+
+# Arguments
+
+* `mean_anom`: Mean anomaly of the body *| in radians*
+* `ecc`      : Eccentricity of the orbit
+* `accuracy` : Desired accuracy for the eccentric anomaly, which must
+               be positive
+**/
+pub fn try_ecc_anom(mean_anom: f64, ecc: f64, accuracy: f64) -> Result<f64, Error> {
+
+    if !mean_anom.is_finite() || !accuracy.is_finite() || accuracy <= 0.0 {
+        return Err(Error::NotFinite);
+    }
+    check_ecc(ecc)?;
+
+    Ok(ecc_anom(mean_anom, ecc, accuracy))
+
+}
+
+/**
+Computes the eccentric anomaly of a body in an elliptic orbit, by
+Newton's method
+
+Unlike `ecc_anom()`, whose fixed-point iteration slows down greatly
+as `ecc` approaches 1, this function converges in a handful of
+iterations for any eccentricity. Starting from π for high
+eccentricities, the iterates approach the root monotonically after
+the first step, so the method can't diverge.
+
+# Returns
+
+* `ecc_anom`: Eccentric anomaly of the body *| in radians*
+
+# Arguments
+
+* `mean_anom`: Mean anomaly of the body *| in radians*
+* `ecc`      : Eccentricity of the orbit, in the range [0, 1)
+* `accuracy` : Desired accuracy for the eccentric anomaly
+               *| in radians*
+**/
+pub fn ecc_anom_newton(mean_anom: f64, ecc: f64, accuracy: f64) -> f64 {
+
+    let (M, revs) = reduce_mn_anom(mean_anom);
+
+    let mut E = if ecc < 0.8 { M + ecc*strict::kepler_sin(M) } else { PI.copysign(M) };
+    let mut iters = 0;
+
+    loop {
+        let dE = (E - ecc*strict::kepler_sin(E) - M) / (1.0 - ecc*strict::kepler_cos(E));
+        E -= dE;
+        iters += 1;
+
+        if dE.abs() <= accuracy {
+            trace_event!("orbit::elliptic::ecc_anom_newton", Converged, iters, dE.abs());
+            break;
+        }
+        if iters == 100 {
+            trace_event!("orbit::elliptic::ecc_anom_newton", Truncated, iters, dE.abs());
+            break;
+        }
+    }
+
+    E + revs
+
+}
+
+/**
+Computes the eccentric anomaly of a body in an elliptic orbit, by
+the non-iterative method of Markley
+
+A cubic approximation of Kepler's equation gives a starting value,
+which a single fifth-order correction refines to near the precision
+of `f64`, for any eccentricity below 1.
+
+# Returns
+
+* `ecc_anom`: Eccentric anomaly of the body *| in radians*
+
+# Arguments
+
+* `mean_anom`: Mean anomaly of the body *| in radians*
+* `ecc`      : Eccentricity of the orbit, in the range [0, 1)
+**/
+pub fn ecc_anom_markley(mean_anom: f64, ecc: f64) -> f64 {
+
+    let (M, revs) = reduce_mn_anom(mean_anom);
+    if M == 0.0 {
+        return revs;
+    }
+
+    let pi2 = PI * PI;
+    let alpha = (3.0*pi2 + 1.6*PI*(PI - M.abs())/(1.0 + ecc)) / (pi2 - 6.0);
+    let d = 3.0*(1.0 - ecc) + alpha*ecc;
+    let q = 2.0*alpha*d*(1.0 - ecc) - M*M;
+    let r = 3.0*alpha*d*(d - 1.0 + ecc)*M + M*M*M;
+    let w = (r.abs() + (q*q*q + r*r).sqrt()).powf(2.0/3.0);
+    let E1 = (2.0*r*w/(w*w + w*q + q*q) + M) / d;
+
+    // the fifth-order correction
+    let f2 = ecc * strict::kepler_sin(E1);
+    let f3 = ecc * E1.cos();
+    let f0 = E1 - f2 - M;
+    let f1 = 1.0 - f3;
+    let f4 = -f2;
+
+    let d3 = -f0 / (f1 - 0.5*f0*f2/f1);
+    let d4 = -f0 / (f1 + 0.5*d3*f2 + d3*d3*f3/6.0);
+    let d5 = -f0 / (f1 + 0.5*d4*f2 + d4*d4*f3/6.0 + d4*d4*d4*f4/24.0);
+
+    E1 + d5 + revs
+
+}
+
+// Splits a mean anomaly into one in the range [-π, π], and the
+// whole revolutions removed
+fn reduce_mn_anom(mean_anom: f64) -> (f64, f64) {
+
+    let revs = (mean_anom / angle::TWO_PI).round() * angle::TWO_PI;
+
+    (mean_anom - revs, revs)
+
+}
+
+/**
+Computes the eccentric anomaly of a body in an elliptic orbit from
+it's true anomaly
+
+# Returns
+
+* `ecc_anom`: Eccentric anomaly of the body *| in radians*
+
+# Arguments
+
+* `true_anom`: True anomaly of the body *| in radians*
+* `ecc`      : Eccentricity of the orbit
+**/
+#[inline]
+pub fn ecc_anom_frm_true_anom<T: Float>(true_anom: T, ecc: T) -> T {
+
+    let (one, two) = (T::from_f64(1.0), T::from_f64(2.0));
+
+    two * ((one - ecc).sqrt() * (true_anom/two).tan()).atan2 (
+        (one + ecc).sqrt()
+    )
+
+}
+
+/**
+Computes the mean anomaly of a body in an elliptic orbit from it's
+eccentric anomaly, using Kepler's equation
+
+# Returns
+
+* `mean_anom`: Mean anomaly of the body *| in radians*
+
+# Arguments
+
+* `ecc_anom`: Eccentric anomaly of the body *| in radians*
+* `ecc`     : Eccentricity of the orbit
+**/
+#[inline]
+pub fn mn_anom_frm_ecc_anom<T: Float>(ecc_anom: T, ecc: T) -> T {
+
+    ecc_anom - ecc*ecc_anom.sin()
+
+}
+
+/**
+Computes the mean anomaly of a body in an elliptic orbit from it's
+true anomaly
+
+# Returns
+
+* `mean_anom`: Mean anomaly of the body *| in radians*
+
+# Arguments
+
+* `true_anom`: True anomaly of the body *| in radians*
+* `ecc`      : Eccentricity of the orbit
+**/
+#[inline]
+pub fn mn_anom_frm_true_anom<T: Float>(true_anom: T, ecc: T) -> T {
+
+    mn_anom_frm_ecc_anom(ecc_anom_frm_true_anom(true_anom, ecc), ecc)
+
+}
+
+/**
+Computes the true anomaly of a body in an elliptic orbit from it's
+mean anomaly
+
+# Returns
+
+* `true_anom`: True anomaly of the body *| in radians*
+
+# Arguments
+
+* `mean_anom`: Mean anomaly of the body *| in radians*
+* `ecc`      : Eccentricity of the orbit
+* `accuracy` : Desired accuracy for the eccentric anomaly
+               used in the computation
+**/
+#[inline]
+pub fn true_anom_frm_mn_anom(mean_anom: f64, ecc: f64, accuracy: f64) -> f64 {
+
+    true_anom(ecc_anom(mean_anom, ecc, accuracy), ecc)
+
+}
+
+/**
+Computes the true anomaly of a body in an elliptic orbit from its
+mean anomaly, checking the eccentricity and the accuracy
+
+# Returns
+
+* `true_anom`: True anomaly of the body *| in radians*, or an
+               `Error::InvalidEccentricity` if `ecc` isn't in the
+               range [0, 1) of an elliptic orbit
+
+# Arguments
+
+* `mean_anom`: Mean anomaly of the body *| in radians*
+* `ecc`      : Eccentricity of the orbit
+* `accuracy` : Desired accuracy for the eccentric anomaly
+               used in the computation, which must be positive
+**/
+pub fn try_true_anom_frm_mn_anom(mean_anom: f64, ecc: f64, accuracy: f64) -> Result<f64, Error> {
+
+    Ok(true_anom(try_ecc_anom(mean_anom, ecc, accuracy)?, ecc))
+
+}
+
+// Checks that an eccentricity is that of an elliptic orbit
+fn check_ecc(ecc: f64) -> Result<(), Error> {
+
+    if !ecc.is_finite() {
+        Err(Error::NotFinite)
+    } else if !(0.0..1.0).contains(&ecc) {
+        Err(Error::InvalidEccentricity(ecc))
+    } else {
+        Ok(())
+    }
+
+}
+
+/**
+Computes the time of passage in perihelion of a body in an elliptic
+orbit, from its mean anomaly at an epoch
+
+The perihelion passage nearest to the epoch is returned, ie, the
+mean anomaly is taken in the range -π to π.
+
+# Returns
+
+* `Ok(T)`     : Time of passage in perihelion, in Julian
+                (Ephemeris) day, or
+* `Err(error)`: `Error::NotPositive` for a semimajor axis that isn't
+                positive, `Error::InvalidEccentricity` for an
+                eccentricity outside the range of the orbit, or
+                `Error::NotFinite`
+
+# Arguments
+
+* `mean_anom`: Mean anomaly of the body at the epoch *| in radians*
+* `epoch`    : Epoch of the mean anomaly, in Julian (Ephemeris) day
+* `a`        : Semimajor axis of the orbit *| in AU*
+* `ecc`      : Eccentricity of the orbit. *range: 0.0 to 1.0,
+               excluding 1.0*
+**/
+pub fn perih_time_frm_mn_anom (
+
+    mean_anom : f64,
+    epoch     : f64,
+    a         : f64,
+    ecc       : f64
+
+) -> Result<f64, Error> {
+
+    error::check_positive(a)?;
+    check_ecc(ecc)?;
+
+    let M = angle::limit_to_pm_PI(mean_anom);
+
+    Ok(epoch - M/mn_motion(a))
+
+}
+
+/**
+Computes the mean anomaly at an epoch of a body in an elliptic orbit,
+from its time of passage in perihelion
+
+# Returns
+
+* `Ok(mean_anom)`: Mean anomaly of the body at the epoch
+                   *| in radians*, or
+* `Err(error)`   : `Error::NotPositive` for a semimajor axis that
+                   isn't positive, `Error::InvalidEccentricity` for
+                   an eccentricity outside the range of the orbit, or
+                   `Error::NotFinite`
+
+# Arguments
+
+* `T`    : Time of passage in perihelion, in Julian (Ephemeris) day
+* `epoch`: Epoch of the mean anomaly, in Julian (Ephemeris) day
+* `a`    : Semimajor axis of the orbit *| in AU*
+* `ecc`  : Eccentricity of the orbit. *range: 0.0 to 1.0,
+           excluding 1.0*
+**/
+pub fn mn_anom_frm_perih_time (
+
+    T     : f64,
+    epoch : f64,
+    a     : f64,
+    ecc   : f64
+
+) -> Result<f64, Error> {
+
+    error::check_positive(a)?;
+    check_ecc(ecc)?;
+
+    Ok(angle::limit_to_two_PI(mn_motion(a) * (epoch - T)))
+
+}
+
 /**
 Computes the velocity of a body in an elliptic orbit
 
@@ -0,0 +1,138 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Hyperbolic orbits
+
+use consts;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/**
+Computes the hyperbolic anomaly of a body in a hyperbolic orbit
+
+Kepler's equation for the hyperbola, `ecc sinh(H) - H = M`, is solved
+by Newton's method. The function is convex on either side of zero,
+so starting from `ln(2|M|/ecc + 1.8)` the iteration converges for
+every eccentricity greater than 1, including those just above it.
+
+# Returns
+
+* `hyp_anom`: Hyperbolic anomaly of the body
+
+# Arguments
+
+* `mean_anom`: Mean anomaly of the body
+* `ecc`      : Eccentricity of the orbit, greater than 1
+* `accuracy` : Desired accuracy for the hyperbolic anomaly
+**/
+pub fn hyp_anom(mean_anom: f64, ecc: f64, accuracy: f64) -> f64 {
+
+    let M = mean_anom.abs();
+    let mut H = (2.0*M/ecc + 1.8).ln();
+    let mut iters = 0;
+
+    loop {
+        let dH = (ecc*H.sinh() - H - M) / (ecc*H.cosh() - 1.0);
+        H -= dH;
+        iters += 1;
+
+        if dH.abs() <= accuracy * H.abs().max(1.0) {
+            trace_event!("orbit::hyperbolic::hyp_anom", Converged, iters, dH.abs());
+            break;
+        }
+        if iters == 100 {
+            trace_event!("orbit::hyperbolic::hyp_anom", Truncated, iters, dH.abs());
+            break;
+        }
+    }
+
+    H.copysign(mean_anom)
+
+}
+
+/**
+Computes the true anomaly of a body in a hyperbolic orbit
+
+# Returns
+
+* `true_anom`: True anomaly of the body *| in radians*
+
+# Arguments
+
+* `hyp_anom`: Hyperbolic anomaly of the body
+* `ecc`     : Eccentricity of the orbit
+**/
+pub fn true_anom(hyp_anom: f64, ecc: f64) -> f64 {
+
+    2.0 * (((ecc + 1.0)/(ecc - 1.0)).sqrt() * (hyp_anom/2.0).tanh()).atan()
+
+}
+
+/**
+Computes the radius vector of a body in a hyperbolic orbit from
+its hyperbolic anomaly
+
+# Returns
+
+* `rad_vec`: Radius vector of the body *| in AU*
+
+# Arguments
+
+* `hyp_anom`: Hyperbolic anomaly of the body
+* `q`       : Perihelion distance *| in AU*
+* `ecc`     : Eccentricity of the orbit
+**/
+pub fn rad_vec_frm_hyp_anom(hyp_anom: f64, q: f64, ecc: f64) -> f64 {
+
+    q * (ecc*hyp_anom.cosh() - 1.0) / (ecc - 1.0)
+
+}
+
+/**
+Computes the true anomaly and radius vector of a body in a
+hyperbolic orbit at a given time
+
+# Returns
+
+`(true_anom, rad_vec)`
+
+* `true_anom`: True anomaly of the body at time `t` *| in radians*
+* `rad_vec`  : Radius vector of the body at time `t` *| in AU*
+
+# Arguments
+
+* `t`       : Time of interest, in Julian (Ephemeris) day
+* `T`       : Time of passage through the perihelion, in Julian
+              (Ephemeris) day
+* `q`       : Perihelion distance *| in AU*
+* `ecc`     : Eccentricity of the orbit, greater than 1
+* `accuracy`: Desired accuracy for the hyperbolic anomaly
+**/
+pub fn true_anom_and_rad_vec(t: f64, T: f64, q: f64, ecc: f64, accuracy: f64) -> (f64, f64) {
+
+    let a = q / (ecc - 1.0);
+    let M = consts::GAUSS_GRAV * (t - T) / a.powf(1.5);
+    let H = hyp_anom(M, ecc, accuracy);
+
+    (true_anom(H, ecc), rad_vec_frm_hyp_anom(H, q, ecc))
+
+}
@@ -20,16 +20,402 @@ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
 THE SOFTWARE.
 */
 
-//! Elliptic, parabolic and near-parabolic orbits
+//! Elliptic, parabolic, near-parabolic and hyperbolic orbits
+
+use angle;
+use comet;
+use consts;
+use coords;
+use ecliptic;
+use error;
+use planet;
+use time;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 pub mod elliptic;
 pub mod parabolic;
 pub mod near_parabolic;
+pub mod hyperbolic;
+#[cfg(feature = "nbody")]
+pub mod nbody;
 
 /// Represents an orbital node
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Node {
     /// Ascending node
     Ascend,
     /// Descending node
     Descend
 }
+
+/// Holds the osculating orbital elements of a minor planet or comet,
+/// referred to the ecliptic and mean equinox of J2000.0, in the form
+/// published by the Minor Planet Center
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Elements {
+    /// Semimajor axis *| in AU*, negative for a hyperbolic orbit
+    pub a: f64,
+    /// Eccentricity of the orbit, which isn't 1
+    pub e: f64,
+    /// Inclination of the orbit *| in radians*
+    pub i: f64,
+    /// Longitude of the ascending node *| in radians*
+    pub sigma: f64,
+    /// Argument of the perihelion *| in radians*
+    pub w: f64,
+    /// Mean anomaly at the epoch *| in radians*
+    pub M: f64,
+    /// Epoch of the elements, in Julian (Ephemeris) day
+    pub epoch: f64,
+}
+
+impl Elements {
+    /// The elements of an orbit with perihelion distance `q` *| in AU*
+    /// and time of passage through the perihelion `T`, in Julian
+    /// (Ephemeris) day, which becomes the epoch. `e` mustn't be 1.
+    pub fn frm_perih_time(q: f64, e: f64, i: f64, sigma: f64, w: f64, T: f64) -> Elements {
+        Elements {
+            a: q / (1.0 - e),
+            e,
+            i,
+            sigma,
+            w,
+            M: 0.0,
+            epoch: T
+        }
+    }
+
+    /// Mean motion *| in radians per day*
+    pub fn mn_motion(&self) -> f64 {
+        consts::GAUSS_GRAV / self.a.abs().powf(1.5)
+    }
+
+    /// Time of passage through the perihelion, in Julian (Ephemeris) day
+    pub fn perih_time(&self) -> f64 {
+        self.epoch - self.M / self.mn_motion()
+    }
+
+    /// The same orbit, as [comet::Elements](../comet/struct.Elements.html)
+    pub fn to_comet_elements(&self) -> comet::Elements {
+        comet::Elements {
+            q: self.a * (1.0 - self.e),
+            e: self.e,
+            i: self.i,
+            sigma: self.sigma,
+            w: self.w,
+            T: self.perih_time()
+        }
+    }
+}
+
+/// Holds the position of a minor planet or comet at a given time
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Position {
+    /// Heliocentric rectangular coordinates `(x, y, z)` of the body,
+    /// referred to the mean equator and equinox of J2000.0,
+    /// at the time of observation *| in AU*
+    pub heliocent: (f64, f64, f64),
+    /// Astrometric right ascension, referred to the mean equator and
+    /// equinox of J2000.0 *| in radians*
+    pub asc: f64,
+    /// Astrometric declination, referred to the mean equator and
+    /// equinox of J2000.0 *| in radians*
+    pub dec: f64,
+    /// Distance of the body from the Earth *| in AU*
+    pub dist: f64,
+    /// Time taken by the light from the body to reach the Earth
+    /// *| in days*
+    pub light_time: f64,
+}
+
+/**
+Computes the position of a minor planet or comet from its osculating
+orbital elements
+
+The body is placed where it was when the light seen at the Earth
+left it, by iterating the light-time. The result is an astrometric
+position, which is free of aberration and nutation, and comparable
+with star catalogues referred to J2000.0.
+
+# Returns
+
+* `position`: The [Position](./struct.Position.html) of the body
+
+# Arguments
+
+* `elements`: The body's [Elements](./struct.Elements.html)
+* `JD`      : Julian (Ephemeris) day
+**/
+pub fn position(elements: &Elements, JD: f64) -> Position {
+
+    let elements = elements.to_comet_elements();
+    let oblq = ecliptic::mn_oblq_IAU(2451545.0);
+    let to_eq = |(x, y, z): (f64, f64, f64)| (
+        x,
+        y*oblq.cos() - z*oblq.sin(),
+        y*oblq.sin() + z*oblq.cos()
+    );
+
+    let earth = planet::heliocent_ecl_rect_coords_J2000(&planet::Planet::Earth, JD);
+
+    let mut light_time = 0.0;
+    let mut geocent = (0.0, 0.0, 0.0);
+    for _ in 0..10 {
+        let (pos, _) = comet::heliocent_rect_state(&elements, JD - light_time);
+        geocent = (pos.0 - earth.0, pos.1 - earth.1, pos.2 - earth.2);
+
+        let prev = light_time;
+        light_time = LIGHT_TIME_PER_AU * (geocent.0*geocent.0 + geocent.1*geocent.1 + geocent.2*geocent.2).sqrt();
+        if (light_time - prev).abs() < 1e-9 {
+            break;
+        }
+    }
+
+    let (x, y, z) = to_eq(geocent);
+    let dist = (x*x + y*y + z*z).sqrt();
+    let (pos, _) = comet::heliocent_rect_state(&elements, JD);
+
+    Position {
+        heliocent: to_eq(pos),
+        asc: angle::limit_to_two_PI(y.atan2(x)),
+        dec: (z / dist).asin(),
+        dist,
+        light_time
+    }
+
+}
+
+/// Holds an astrometric observation of a minor planet or comet
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Observation {
+    /// Julian (Ephemeris) day of the observation
+    pub JD: f64,
+    /// Astrometric right ascension, referred to the mean equator and
+    /// equinox of J2000.0 *| in radians*
+    pub asc: f64,
+    /// Astrometric declination, referred to the mean equator and
+    /// equinox of J2000.0 *| in radians*
+    pub dec: f64,
+}
+
+/**
+Determines a preliminary orbit of a minor planet or comet from three
+observations, by Gauss's method
+
+The distance of the body at the second observation is found from the
+roots of Gauss's polynomial of the eighth degree, and is then refined
+with the exact f and g functions of the orbit, correcting the times
+of the observations for the light-time. Where the polynomial gives
+more than one distance, the smallest one is taken.
+
+The observations should be a few days to a few weeks apart, and the
+body shouldn't move near a great circle through the Sun.
+
+# Returns
+
+* `Ok(elements)`: Osculating [Elements](./struct.Elements.html) at the
+                  epoch of the second observation, or
+* `Err(error)`  : `Error::NotPositive`, if the observations aren't in
+                  order of time, or `Error::NoSolution`, if no orbit
+                  fits them
+
+# Arguments
+
+* `obs1`    : First [Observation](./struct.Observation.html)
+* `obs2`    : Second [Observation](./struct.Observation.html)
+* `obs3`    : Third [Observation](./struct.Observation.html)
+* `observer`: Geographic point of the observer *| in radians*, and
+              height of the observer above sea level *| in meters*,
+              or `None` for observations reduced to the center of
+              the Earth
+**/
+pub fn determine_gauss(obs1: &Observation, obs2: &Observation, obs3: &Observation,
+                       observer: Option<(&coords::GeographPoint, f64)>) -> Result<Elements, error::Error> {
+
+    let mu = consts::GAUSS_GRAV * consts::GAUSS_GRAV;
+    let oblq = ecliptic::mn_oblq_IAU(2451545.0);
+    let to_ecl = |(x, y, z): (f64, f64, f64)| (
+        x,
+        y*oblq.cos() + z*oblq.sin(),
+        -y*oblq.sin() + z*oblq.cos()
+    );
+
+    let obs = [obs1, obs2, obs3];
+    let mut dirs = [(0.0, 0.0, 0.0); 3];
+    let mut sites = [(0.0, 0.0, 0.0); 3];
+    for k in 0..3 {
+        let (asc, dec) = (obs[k].asc, obs[k].dec);
+        dirs[k] = to_ecl((dec.cos()*asc.cos(), dec.cos()*asc.sin(), dec.sin()));
+        sites[k] = observer_heliocent(obs[k].JD, observer, &to_ecl);
+    }
+
+    let p1 = cross(dirs[1], dirs[2]);
+    let p2 = cross(dirs[0], dirs[2]);
+    let p3 = cross(dirs[0], dirs[1]);
+    let D0 = dot(dirs[0], p1);
+    if D0.abs() < 1e-12 {
+        return Err(error::Error::NoSolution);
+    }
+    let D = |i: usize| (dot(sites[i], p1), dot(sites[i], p2), dot(sites[i], p3));
+    let (D11, D12, D13) = D(0);
+    let (D21, D22, D23) = D(1);
+    let (D31, D32, D33) = D(2);
+
+    let tau1 = obs1.JD - obs2.JD;
+    let tau3 = obs3.JD - obs2.JD;
+    let tau = tau3 - tau1;
+    error::check_positive(-tau1)?;
+    error::check_positive(tau3)?;
+
+    let A = (-D12*tau3/tau + D22 + D32*tau1/tau) / D0;
+    let B = (D12*(tau3*tau3 - tau*tau)*tau3/tau + D32*(tau*tau - tau1*tau1)*tau1/tau) / (6.0*D0);
+    let E = dot(sites[1], dirs[1]);
+    let R2 = dot(sites[1], sites[1]);
+
+    let a = -(A*A + 2.0*A*E + R2);
+    let b = -2.0*mu*B*(A + E);
+    let c = -mu*mu*B*B;
+    let poly = |x: f64| x.powi(8) + a*x.powi(6) + b*x.powi(3) + c;
+
+    // the root at the distance of the observer is spurious
+    let rho_min = 1e-4;
+    let mut r2 = None;
+    let mut x0 = 0.01;
+    while x0 < 1000.0 && r2.is_none() {
+        let x1 = x0 * 1.01;
+        if poly(x0) * poly(x1) <= 0.0 {
+            let (mut lo, mut hi) = (x0, x1);
+            for _ in 0..100 {
+                let mid = 0.5 * (lo + hi);
+                if poly(lo) * poly(mid) <= 0.0 { hi = mid; } else { lo = mid; }
+            }
+            let r = 0.5 * (lo + hi);
+            if A + mu*B/(r*r*r) > rho_min {
+                r2 = Some(r);
+            }
+        }
+        x0 = x1;
+    }
+    let r2 = r2.ok_or(error::Error::NoSolution)?;
+
+    let u = mu / (r2*r2*r2);
+    let mut f1 = 1.0 - 0.5*u*tau1*tau1;
+    let mut f3 = 1.0 - 0.5*u*tau3*tau3;
+    let mut g1 = tau1 - u*tau1*tau1*tau1/6.0;
+    let mut g3 = tau3 - u*tau3*tau3*tau3/6.0;
+
+    let mut elements = None;
+    let mut prev_rho2 = 0.0;
+    let mut iters = 0;
+    while iters < 50 {
+        iters += 1;
+
+        let det = f1*g3 - f3*g1;
+        let c1 = g3 / det;
+        let c3 = -g1 / det;
+        let rho = [
+            (-D11 + D21/c1 - c3/c1*D31) / D0,
+            (-c1*D12 + D22 - c3*D32) / D0,
+            (-c1/c3*D13 + D23/c3 - D33) / D0
+        ];
+        if rho[1] <= 0.0 || !rho[1].is_finite() {
+            trace_event!("orbit::determine_gauss", Failed, iters, rho[1]);
+            return Err(error::Error::NoSolution);
+        }
+
+        let pos = |k: usize| {
+            let (s, d) = (sites[k], dirs[k]);
+            (s.0 + rho[k]*d.0, s.1 + rho[k]*d.1, s.2 + rho[k]*d.2)
+        };
+        let (r1, r2, r3) = (pos(0), pos(1), pos(2));
+        let v2 = (
+            (-f3*r1.0 + f1*r3.0) / det,
+            (-f3*r1.1 + f1*r3.1) / det,
+            (-f3*r1.2 + f1*r3.2) / det
+        );
+
+        let t = [
+            obs1.JD - rho[0]*LIGHT_TIME_PER_AU,
+            obs2.JD - rho[1]*LIGHT_TIME_PER_AU,
+            obs3.JD - rho[2]*LIGHT_TIME_PER_AU
+        ];
+        let orbit = comet::elements_frm_heliocent_rect_state(r2, v2, t[1]);
+        elements = Some(orbit);
+
+        if (rho[1] - prev_rho2).abs() < 1e-12 {
+            trace_event!("orbit::determine_gauss", Converged, iters, rho[1] - prev_rho2);
+            break;
+        }
+        prev_rho2 = rho[1];
+
+        // the exact f and g functions, from the positions on the orbit
+        let fg = |JD: f64| {
+            let (r, _) = comet::heliocent_rect_state(&orbit, JD);
+            let (rr, rv, vv) = (dot(r2, r2), dot(r2, v2), dot(v2, v2));
+            let (pr, pv) = (dot(r, r2), dot(r, v2));
+            let det = rr*vv - rv*rv;
+            ((pr*vv - pv*rv) / det, (pv*rr - pr*rv) / det)
+        };
+        let (f, g) = fg(t[0]);
+        f1 = f; g1 = g;
+        let (f, g) = fg(t[2]);
+        f3 = f; g3 = g;
+    }
+    if iters == 50 {
+        trace_event!("orbit::determine_gauss", Truncated, iters, 0.0);
+    }
+
+    let orbit = elements.ok_or(error::Error::NoSolution)?;
+    if !orbit.q.is_finite() || !orbit.e.is_finite() || (orbit.e - 1.0).abs() < 1e-12 {
+        return Err(error::Error::NoSolution);
+    }
+
+    let mut elements = Elements::frm_perih_time(orbit.q, orbit.e, orbit.i, orbit.sigma, orbit.w, orbit.T);
+    elements.M = elements.mn_motion() * (obs2.JD - orbit.T);
+    elements.epoch = obs2.JD;
+    if elements.e < 1.0 {
+        elements.M = angle::limit_to_two_PI(elements.M);
+    }
+
+    Ok(elements)
+
+}
+
+// Heliocentric position of an observer, referred to the ecliptic and
+// mean equinox of J2000.0 *| in AU*
+fn observer_heliocent<F>(JD: f64, observer: Option<(&coords::GeographPoint, f64)>, to_ecl: &F) -> (f64, f64, f64)
+    where F: Fn((f64, f64, f64)) -> (f64, f64, f64) {
+
+    let earth = planet::heliocent_ecl_rect_coords_J2000(&planet::Planet::Earth, JD);
+
+    match observer {
+        Some((point, height)) => {
+            let (rho_sin, rho_cos) = planet::earth::rho_sin_cos_phi(point.lat, height);
+            let loc_sidr = time::local_sidr(time::mn_sidr(time::ut_frm_tt(JD)), point.long);
+            let scale = planet::earth::eq_rad() * 1000.0 / consts::ASTRONOMICAL_UNIT;
+            let site = to_ecl((
+                scale * rho_cos * loc_sidr.cos(),
+                scale * rho_cos * loc_sidr.sin(),
+                scale * rho_sin
+            ));
+            (earth.0 + site.0, earth.1 + site.1, earth.2 + site.2)
+        },
+        None => earth
+    }
+
+}
+
+fn dot(a: (f64, f64, f64), b: (f64, f64, f64)) -> f64 {
+    a.0*b.0 + a.1*b.1 + a.2*b.2
+}
+
+fn cross(a: (f64, f64, f64), b: (f64, f64, f64)) -> (f64, f64, f64) {
+    (a.1*b.2 - a.2*b.1, a.2*b.0 - a.0*b.2, a.0*b.1 - a.1*b.0)
+}
+
+// Time taken by light to travel 1 AU *| in days*
+const LIGHT_TIME_PER_AU: f64 = 0.0057755183;
@@ -0,0 +1,267 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Numerical integration of the motion of minor bodies
+
+use consts;
+use error;
+use orbit;
+use planet;
+use precess;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/**
+Represents the state of a minor body in heliocentric ecliptic
+rectangular coordinates, referred to the standard equinox of J2000.0
+**/
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct State {
+    /// The X coordinate *| in AU*
+    pub x: f64,
+    /// The Y coordinate *| in AU*
+    pub y: f64,
+    /// The Z coordinate *| in AU*
+    pub z: f64,
+    /// Velocity along the x-axis *| in AU per day*
+    pub vx: f64,
+    /// Velocity along the y-axis *| in AU per day*
+    pub vy: f64,
+    /// Velocity along the z-axis *| in AU per day*
+    pub vz: f64,
+}
+
+/**
+Computes the state of a body in an elliptic orbit from it's orbital
+elements, considering only the attraction of the Sun
+
+# Returns
+
+* `state`: Heliocentric state of the body, referred to the same
+           ecliptic and equinox as the elements passed
+
+# Arguments
+
+* `a`    : Semimajor axis of the orbit *| in AU*
+* `e`    : Eccentricity of the orbit
+* `i`    : Inclination of the orbit *| in radians*
+* `sigma`: Longitude of the ascending node *| in radians*
+* `w`    : Argument of the perihelion *| in radians*
+* `M`    : Mean anomaly of the body *| in radians*
+**/
+pub fn state_frm_orb_elements (
+
+    a     : f64,
+    e     : f64,
+    i     : f64,
+    sigma : f64,
+    w     : f64,
+    M     : f64
+
+) -> State {
+
+    let E = orbit::elliptic::ecc_anom(M, e, 1e-14);
+    let (sin_E, cos_E) = E.sin_cos();
+    let b = (1.0 - e*e).sqrt();
+
+    let (sin_w, cos_w) = w.sin_cos();
+    let (sin_s, cos_s) = sigma.sin_cos();
+    let (sin_i, cos_i) = i.sin_cos();
+
+    let P = (
+        cos_w*cos_s - sin_w*sin_s*cos_i,
+        cos_w*sin_s + sin_w*cos_s*cos_i,
+        sin_w*sin_i
+    );
+    let Q = (
+       -sin_w*cos_s - cos_w*sin_s*cos_i,
+       -sin_w*sin_s + cos_w*cos_s*cos_i,
+        cos_w*sin_i
+    );
+
+    let p = a * (cos_E - e);
+    let q = a * b * sin_E;
+
+    let r = a * (1.0 - e*cos_E);
+    let k = consts::GAUSS_GRAV * a.sqrt() / r;
+    let vp = -k * sin_E;
+    let vq =  k * b * cos_E;
+
+    State {
+        x : p*P.0 + q*Q.0,
+        y : p*P.1 + q*Q.1,
+        z : p*P.2 + q*Q.2,
+        vx: vp*P.0 + vq*Q.0,
+        vy: vp*P.1 + vq*Q.1,
+        vz: vp*P.2 + vq*Q.2
+    }
+
+}
+
+/**
+Propagates the state of a minor body to a different time by numerical
+integration of it's equations of motion
+
+The body is attracted by the Sun and perturbed by the planets in
+`perturbers`, whose positions are taken from
+[`planet::heliocent_coords()`](../../planet/fn.heliocent_coords.html).
+The mass of the minor body itself is neglected. The equations of
+motion are integrated with the classical fourth-order Runge-Kutta
+method, using a fixed step size.
+
+# Returns
+
+* `Ok(state)` : State of the body on `JD_end`, or
+* `Err(error)`: `Error::NotPositive` if `step` isn't positive, or
+                `Error::NotFinite`
+
+# Arguments
+
+* `state`     : State of the body on `JD_start`
+* `JD_start`  : Julian (Ephemeris) day of the initial state
+* `JD_end`    : Julian (Ephemeris) day to propagate to. May be
+                earlier than `JD_start`.
+* `step`      : Step size of the integrator, positive *| in days*. A
+                step of a day or two is adequate for most main-belt
+                asteroids; use smaller steps for close approaches to
+                planets.
+* `perturbers`: The [Planet](../../planet/enum.Planet.html)s whose
+                attraction should be taken into account
+**/
+pub fn propagate (
+
+    state      : &State,
+    JD_start   : f64,
+    JD_end     : f64,
+    step       : f64,
+    perturbers : &[planet::Planet]
+
+) -> Result<State, error::Error> {
+
+    error::check_positive(step)?;
+    if !JD_start.is_finite() || !JD_end.is_finite() {
+        return Err(error::Error::NotFinite);
+    }
+
+    let mut s = *state;
+    let mut JD = JD_start;
+
+    let h_abs = step;
+    let dir = if JD_end < JD_start { -1.0 } else { 1.0 };
+
+    while (JD_end - JD) * dir > 0.0 {
+        let h = dir * h_abs.min((JD_end - JD).abs());
+        s = runge_kutta_step(&s, JD, h, perturbers);
+        JD += h;
+    }
+
+    Ok(s)
+
+}
+
+fn runge_kutta_step(s: &State, JD: f64, h: f64, perturbers: &[planet::Planet]) -> State {
+
+    let k1 = derivative(s, JD, perturbers);
+    let k2 = derivative(&add_scaled(s, &k1, h/2.0), JD + h/2.0, perturbers);
+    let k3 = derivative(&add_scaled(s, &k2, h/2.0), JD + h/2.0, perturbers);
+    let k4 = derivative(&add_scaled(s, &k3, h), JD + h, perturbers);
+
+    State {
+        x : s.x  + h * (k1.x  + 2.0*(k2.x  + k3.x)  + k4.x)  / 6.0,
+        y : s.y  + h * (k1.y  + 2.0*(k2.y  + k3.y)  + k4.y)  / 6.0,
+        z : s.z  + h * (k1.z  + 2.0*(k2.z  + k3.z)  + k4.z)  / 6.0,
+        vx: s.vx + h * (k1.vx + 2.0*(k2.vx + k3.vx) + k4.vx) / 6.0,
+        vy: s.vy + h * (k1.vy + 2.0*(k2.vy + k3.vy) + k4.vy) / 6.0,
+        vz: s.vz + h * (k1.vz + 2.0*(k2.vz + k3.vz) + k4.vz) / 6.0
+    }
+
+}
+
+#[inline]
+fn add_scaled(s: &State, d: &State, h: f64) -> State {
+
+    State {
+        x : s.x  + h*d.x,
+        y : s.y  + h*d.y,
+        z : s.z  + h*d.z,
+        vx: s.vx + h*d.vx,
+        vy: s.vy + h*d.vy,
+        vz: s.vz + h*d.vz
+    }
+
+}
+
+// Returns the time derivative of a state, with the velocity in the
+// position fields and the acceleration in the velocity fields
+fn derivative(s: &State, JD: f64, perturbers: &[planet::Planet]) -> State {
+
+    let k2 = consts::GAUSS_GRAV * consts::GAUSS_GRAV;
+
+    let r = (s.x*s.x + s.y*s.y + s.z*s.z).sqrt();
+    let r3 = r * r * r;
+
+    let mut ax = -k2 * s.x / r3;
+    let mut ay = -k2 * s.y / r3;
+    let mut az = -k2 * s.z / r3;
+
+    for p in perturbers.iter() {
+        let mu = k2 / planet::sun_planet_mass_ratio(p);
+        let (px, py, pz) = planet_rect_coords_J2000(p, JD);
+
+        let (dx, dy, dz) = (px - s.x, py - s.y, pz - s.z);
+        let d = (dx*dx + dy*dy + dz*dz).sqrt();
+        let d3 = d * d * d;
+
+        let rp = (px*px + py*py + pz*pz).sqrt();
+        let rp3 = rp * rp * rp;
+
+        // direct attraction of the planet, minus it's attraction on
+        // the Sun since the origin is heliocentric
+        ax += mu * (dx/d3 - px/rp3);
+        ay += mu * (dy/d3 - py/rp3);
+        az += mu * (dz/d3 - pz/rp3);
+    }
+
+    State {
+        x : s.vx,
+        y : s.vy,
+        z : s.vz,
+        vx: ax,
+        vy: ay,
+        vz: az
+    }
+
+}
+
+fn planet_rect_coords_J2000(p: &planet::Planet, JD: f64) -> (f64, f64, f64) {
+
+    let (L, B, R) = planet::heliocent_coords(p, JD);
+    let (L, B) = precess::precess_ecl_coords(L, B, JD, 2451545.0);
+
+    (
+        R * B.cos() * L.cos(),
+        R * B.cos() * L.sin(),
+        R * B.sin()
+    )
+
+}
@@ -24,6 +24,8 @@ THE SOFTWARE.
 
 use angle;
 use consts;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 /**
 Computes the true anomaly and radius vector of a body in a near-parabolic
@@ -36,6 +38,10 @@ orbit at a given time
 * `true_anom`: True anomaly of the body at time `t` *| in radians*
 * `rad_vec`  : Radius vector of the body at time `t` *| in AU*
 
+An `Err` is returned when the iteration fails to converge, which
+happens when the orbit is too far from parabolic (say `ecc` below
+about `0.95` at large distances from the perihelion).
+
 # Arguments
 
 * `t`       : Time of interest, in Julian (Ephemeris) day
@@ -88,6 +94,7 @@ pub fn true_anom_and_rad_vec<'a> (
                 let f = z1 * g1;
                 q3 += f;
                 if z > 50.0 || f.abs() > d1 {
+                    trace_event!("orbit::near_parabolic::true_anom_and_rad_vec", Failed, l, f.abs());
                     return Err("No convergence at orbit::near_parabolic::true_anom_and_rad_vec()");
                 }
                 if f.abs() <= accuracy { break; }
@@ -95,6 +102,7 @@ pub fn true_anom_and_rad_vec<'a> (
 
             l += 1.0;
             if l > 50.0 {
+                trace_event!("orbit::near_parabolic::true_anom_and_rad_vec", Failed, l, (s - s0).abs());
                 return Err("No convergence at orbit::near_parabolic::true_anom_and_rad_vec()");
             }
 
@@ -104,7 +112,10 @@ pub fn true_anom_and_rad_vec<'a> (
                 if (s - s1).abs() <= accuracy { break; }
             }
 
-            if (s - s0).abs() <= accuracy { break; }
+            if (s - s0).abs() <= accuracy {
+                trace_event!("orbit::near_parabolic::true_anom_and_rad_vec", Converged, l, (s - s0).abs());
+                break;
+            }
         }
     }
 
@@ -24,11 +24,17 @@ THE SOFTWARE.
 
 use std;
 use orbit;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 /**
 Computes the true anomaly and radius vector of a body in a parabolic
 orbit at a given time
 
+Barker's equation, the cubic in `tan(v/2)` that replaces Kepler's
+equation for the parabola, is solved in closed form, without
+iteration.
+
 # Returns
 
 `(tru_anom, rad_vec)`
@@ -75,7 +81,6 @@ orbit and it's radius vector at that time
 * `T`   : Time of passage in perihelion, in Julian (Ephemeris) day
 * `node`: `Ascend` or `Descend` node
 **/
-
 #[inline]
 pub fn passage_through_node (
 
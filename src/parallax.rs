@@ -23,8 +23,11 @@ THE SOFTWARE.
 //! Find topocentric coordinates
 
 use angle;
+use consts;
 use coords;
 use planet;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 /**
 Computes the equatorial horizontal parallax of a celestial body
@@ -174,3 +177,205 @@ pub fn topopcent_ecl_coords(ecl_point: &coords::EclPoint,
         geocent_semdia_1
     )
 }
+
+/// The reference ellipsoids of the Earth, such as `Ellipsoid::WGS84`
+/// and `Ellipsoid::IAU1976`, used by Meeus
+pub use planet::earth::Ellipsoid;
+
+/// Holds the geodetic position of an observer
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GeodeticPoint {
+    /// Geographic longitude, positive westwards *| in radians*
+    pub long: f64,
+    /// Geodetic latitude *| in radians*
+    pub lat: f64,
+    /// Height above the ellipsoid *| in meters*
+    pub height: f64,
+    /// The reference [Ellipsoid](../planet/earth/struct.Ellipsoid.html)
+    pub ellipsoid: Ellipsoid
+}
+
+/**
+Computes the quantities ρ sin φ' and ρ cos φ' of an observer
+
+ρ is the observer's distance from the center of the Earth, and φ' is
+the geocentric latitude.
+
+# Returns
+
+`(rho_sin_phi, rho_cos_phi)`
+
+* `rho_sin_phi`: ρ sin φ' *| in equatorial radii of the ellipsoid*
+* `rho_cos_phi`: ρ cos φ' *| in equatorial radii of the ellipsoid*
+
+# Arguments
+
+* `observer`: The observer's [GeodeticPoint](./struct.GeodeticPoint.html)
+**/
+pub fn rho_sin_cos_phi(observer: &GeodeticPoint) -> (f64, f64) {
+
+    let b_a = 1.0 - observer.ellipsoid.flat_fac;
+    let u = (b_a * observer.lat.tan()).atan();
+    let x = observer.height / (observer.ellipsoid.eq_rad * 1000.0);
+
+    (
+        b_a*u.sin() + x*observer.lat.sin(),
+        u.cos() + x*observer.lat.cos()
+    )
+
+}
+
+/**
+Computes the geocentric latitude of an observer
+
+# Returns
+
+* `geocent_lat`: Geocentric latitude φ' *| in radians*
+
+# Arguments
+
+* `observer`: The observer's [GeodeticPoint](./struct.GeodeticPoint.html)
+**/
+pub fn geocent_lat(observer: &GeodeticPoint) -> f64 {
+
+    let (rho_sin, rho_cos) = rho_sin_cos_phi(observer);
+
+    rho_sin.atan2(rho_cos)
+
+}
+
+/**
+Computes the geodetic position of an observer from ρ sin φ' and
+ρ cos φ'
+
+This is the inverse of [rho_sin_cos_phi()](./fn.rho_sin_cos_phi.html),
+by Bowring's iteration.
+
+# Returns
+
+* `observer`: The observer's [GeodeticPoint](./struct.GeodeticPoint.html)
+
+# Arguments
+
+* `rho_sin_phi`: ρ sin φ' *| in equatorial radii of the ellipsoid*
+* `rho_cos_phi`: ρ cos φ' *| in equatorial radii of the ellipsoid*
+* `long`       : Geographic longitude, positive westwards *| in radians*
+* `ellipsoid`  : The reference [Ellipsoid](../planet/earth/struct.Ellipsoid.html)
+**/
+pub fn geodetic_frm_rho_sin_cos_phi(rho_sin_phi: f64, rho_cos_phi: f64,
+                                    long: f64, ellipsoid: Ellipsoid) -> GeodeticPoint {
+
+    let f = ellipsoid.flat_fac;
+    let b_a = 1.0 - f;
+    let e_sqr = f * (2.0 - f);
+
+    let mut lat = rho_sin_phi.atan2(b_a * b_a * rho_cos_phi);
+    for _ in 0..5 {
+        let u = (b_a * lat.tan()).atan();
+        lat = (rho_sin_phi + e_sqr/b_a * u.sin().powi(3))
+            .atan2(rho_cos_phi - e_sqr * u.cos().powi(3));
+    }
+
+    let N = 1.0 / (1.0 - e_sqr*lat.sin()*lat.sin()).sqrt();
+    let height = if lat.cos().abs() > 1e-6 {
+        rho_cos_phi/lat.cos() - N
+    } else {
+        rho_sin_phi/lat.sin() - N*(1.0 - e_sqr)
+    };
+
+    GeodeticPoint {
+        long,
+        lat,
+        height: height * ellipsoid.eq_rad * 1000.0,
+        ellipsoid
+    }
+
+}
+
+/**
+Computes the topocentric equatorial coordinates of a celestial body,
+rigorously from its distance
+
+# Returns
+
+* `topocent_eq_point`: Topocentric equatorial point of the
+                       celestial body *| in radians*
+
+# Arguments
+
+* `eq_point`   : Geocentric equatorial point of the celestial body
+                 *| in radians*
+* `dist`       : Distance of the celestial body from the center of
+                 the Earth *| in AU*
+* `observer`   : The observer's [GeodeticPoint](./struct.GeodeticPoint.html)
+* `green_sidr` : Sidereal time at Greenwich *| in radians*
+**/
+pub fn topocentric(eq_point: &coords::EqPoint, dist: f64,
+                   observer: &GeodeticPoint, green_sidr: f64) -> coords::EqPoint {
+
+    let (x, y, z) = topocent_rect(eq_point.asc, eq_point.dec, dist, observer, green_sidr, 0.0);
+
+    coords::EqPoint {
+        asc: angle::limit_to_two_PI(y.atan2(x)),
+        dec: z.atan2(x.hypot(y))
+    }
+
+}
+
+/**
+Computes the topocentric ecliptic coordinates of a celestial body,
+rigorously from its distance
+
+# Returns
+
+* `topocent_ecl_point`: Topocentric ecliptic point of the celestial
+                        body *| in radians*
+
+# Arguments
+
+* `ecl_point` : Geocentric ecliptic point of the celestial body
+                *| in radians*
+* `dist`      : Distance of the celestial body from the center of
+                the Earth *| in AU*
+* `observer`  : The observer's [GeodeticPoint](./struct.GeodeticPoint.html)
+* `green_sidr`: Sidereal time at Greenwich *| in radians*
+* `oblq_eclip`: Obliquity of the ecliptic *| in radians*
+**/
+pub fn topocentric_ecl(ecl_point: &coords::EclPoint, dist: f64, observer: &GeodeticPoint,
+                       green_sidr: f64, oblq_eclip: f64) -> coords::EclPoint {
+
+    let (x, y, z) = topocent_rect(ecl_point.long, ecl_point.lat, dist, observer, green_sidr, oblq_eclip);
+
+    coords::EclPoint {
+        long: angle::limit_to_two_PI(y.atan2(x)),
+        lat: z.atan2(x.hypot(y))
+    }
+
+}
+
+// Topocentric rectangular coordinates of a body at the spherical
+// coordinates (a, b) and distance dist, in a frame inclined by oblq
+// to the equator
+fn topocent_rect(a: f64, b: f64, dist: f64, observer: &GeodeticPoint,
+                 green_sidr: f64, oblq: f64) -> (f64, f64, f64) {
+
+    let (rho_sin, rho_cos) = rho_sin_cos_phi(observer);
+    let scale = observer.ellipsoid.eq_rad * 1000.0 / consts::ASTRONOMICAL_UNIT;
+    let loc_sidr = green_sidr - observer.long;
+
+    let (ox, oy, oz) = (
+        scale * rho_cos * loc_sidr.cos(),
+        scale * rho_cos * loc_sidr.sin(),
+        scale * rho_sin
+    );
+    let (sin_e, cos_e) = oblq.sin_cos();
+    let (ox, oy, oz) = (ox, oy*cos_e + oz*sin_e, -oy*sin_e + oz*cos_e);
+
+    (
+        dist*b.cos()*a.cos() - ox,
+        dist*b.cos()*a.sin() - oy,
+        dist*b.sin() - oz
+    )
+
+}
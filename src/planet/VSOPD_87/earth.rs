@@ -1,3 +1,6 @@
+#[cfg(not(feature = "std"))]
+use math::*;
+
 #[inline(always)]
 pub fn terms() -> Vec<Vec<Vec<[f64; 3]>>> {
     vec![
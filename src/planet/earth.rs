@@ -25,6 +25,8 @@ THE SOFTWARE.
 use coords;
 use angle;
 use time;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 /**
 Returns the flattening factor of the Earth
@@ -65,6 +67,81 @@ pub fn ecc_of_meridian() -> f64 {
     (flat_fac() * (2.0 - flat_fac())).sqrt()
 }
 
+/// Represents a reference ellipsoid of the Earth
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ellipsoid {
+    /// Equatorial radius *| in kilometers*
+    pub eq_rad: f64,
+    /// Flattening factor
+    pub flat_fac: f64,
+}
+
+impl Ellipsoid {
+    /// World Geodetic System 1984, used by the rest of this module
+    pub const WGS84: Ellipsoid = Ellipsoid { eq_rad: 6378.137, flat_fac: 1.0 / 298.257223563 };
+    /// Geodetic Reference System 1980
+    pub const GRS80: Ellipsoid = Ellipsoid { eq_rad: 6378.137, flat_fac: 1.0 / 298.257222101 };
+    /// IERS Conventions 2003
+    pub const IERS2003: Ellipsoid = Ellipsoid { eq_rad: 6378.1366, flat_fac: 1.0 / 298.25642 };
+    /// IAU 1976, used by Meeus
+    pub const IAU1976: Ellipsoid = Ellipsoid { eq_rad: 6378.140, flat_fac: 1.0 / 298.257 };
+    /// International (Hayford) ellipsoid of 1924
+    pub const INTERNATIONAL1924: Ellipsoid = Ellipsoid { eq_rad: 6378.388, flat_fac: 1.0 / 297.0 };
+    /// Clarke 1866, of the North American Datum of 1927
+    pub const CLARKE1866: Ellipsoid = Ellipsoid { eq_rad: 6378.2064, flat_fac: 1.0 / 294.978698214 };
+
+    /// Polar radius *| in kilometers*
+    #[inline]
+    pub fn pol_rad(&self) -> f64 {
+        self.eq_rad * (1.0 - self.flat_fac)
+    }
+
+    /// Eccentricity of the meridian
+    #[inline]
+    pub fn ecc(&self) -> f64 {
+        (self.flat_fac * (2.0 - self.flat_fac)).sqrt()
+    }
+
+    /// Radius of curvature of the meridian at a geographic latitude
+    /// *| in kilometers*
+    pub fn rad_curv_of_meridian(&self, geograph_lat: f64) -> f64 {
+        let e = self.ecc();
+
+        self.eq_rad * (1.0 - e*e) / (1.0 - (e * geograph_lat.sin()).powi(2)).powf(1.5)
+    }
+
+    /// Radius of curvature of the prime vertical at a geographic
+    /// latitude *| in kilometers*
+    pub fn rad_curv_of_prime_vertical(&self, geograph_lat: f64) -> f64 {
+        let e = self.ecc();
+
+        self.eq_rad / (1.0 - (e * geograph_lat.sin()).powi(2)).sqrt()
+    }
+
+    /// Radius of curvature of the normal section at a geographic
+    /// latitude along an azimuth measured from the north *| in
+    /// kilometers*
+    pub fn rad_curv_in_azi(&self, geograph_lat: f64, azi: f64) -> f64 {
+        let M = self.rad_curv_of_meridian(geograph_lat);
+        let N = self.rad_curv_of_prime_vertical(geograph_lat);
+
+        M * N / (N * azi.cos().powi(2) + M * azi.sin().powi(2))
+    }
+
+    /// Radius of the parallel of a geographic latitude *| in
+    /// kilometers*
+    pub fn rad_of_parll_lat(&self, geograph_lat: f64) -> f64 {
+        self.rad_curv_of_prime_vertical(geograph_lat) * geograph_lat.cos()
+    }
+}
+
+impl Default for Ellipsoid {
+    fn default() -> Ellipsoid {
+        Ellipsoid::WGS84
+    }
+}
+
 /**
 Computes a low accuracy geodesic distance between two points
 on the Earth's surface *| in kilometers*
@@ -79,7 +156,7 @@ Assumes that the Earth is a sphere.
 #[inline]
 pub fn approx_geodesic_dist(p1: &coords::GeographPoint, p2: &coords::GeographPoint) -> f64 {
 
-    6371.0 * p1.anglr_sepr(&p2)
+    6371.0 * p1.anglr_sepr(p2)
 
 }
 
@@ -121,6 +198,172 @@ pub fn geodesic_dist (
 
 }
 
+/**
+Computes the geodesic distance and azimuths between two points on an
+ellipsoid, by Vincenty's inverse method
+
+The distance is accurate to a fraction of a millimeter.
+
+# Returns
+
+`Some((dist, azi1, azi2))`, or `None` if the iteration fails to
+converge, which can happen for nearly antipodal points
+
+* `dist`: Geodesic distance *| in kilometers*
+* `azi1`: Azimuth of the geodesic at `p1`, measured eastwards from
+          the north *| in radians*
+* `azi2`: Azimuth of the geodesic at `p2`, in the direction of
+          travel *| in radians*
+
+# Arguments
+
+* `p1`       : `GeographPoint` 1 (longitude measured positively
+               westwards)
+* `p2`       : `GeographPoint` 2
+* `ellipsoid`: Reference ellipsoid
+**/
+pub fn vincenty_dist(p1: &coords::GeographPoint, p2: &coords::GeographPoint, ellipsoid: &Ellipsoid) -> Option<(f64, f64, f64)> {
+
+    let f = ellipsoid.flat_fac;
+    let b = ellipsoid.pol_rad();
+
+    let L = p1.long - p2.long;
+    let U1 = ((1.0 - f) * p1.lat.tan()).atan();
+    let U2 = ((1.0 - f) * p2.lat.tan()).atan();
+    let (sin_U1, cos_U1) = U1.sin_cos();
+    let (sin_U2, cos_U2) = U2.sin_cos();
+
+    let mut lambda = L;
+    for _ in 0..200 {
+        let (sin_lambda, cos_lambda) = lambda.sin_cos();
+        let sin_sigma = (
+            (cos_U2 * sin_lambda).powi(2)
+          + (cos_U1*sin_U2 - sin_U1*cos_U2*cos_lambda).powi(2)
+        ).sqrt();
+        if sin_sigma == 0.0 {
+            return Some((0.0, 0.0, 0.0));
+        }
+
+        let cos_sigma = sin_U1*sin_U2 + cos_U1*cos_U2*cos_lambda;
+        let sigma = sin_sigma.atan2(cos_sigma);
+        let sin_alpha = cos_U1 * cos_U2 * sin_lambda / sin_sigma;
+        let cos2_alpha = 1.0 - sin_alpha*sin_alpha;
+        let cos_2sigma_m = if cos2_alpha != 0.0 { cos_sigma - 2.0*sin_U1*sin_U2/cos2_alpha } else { 0.0 };
+
+        let C = f / 16.0 * cos2_alpha * (4.0 + f*(4.0 - 3.0*cos2_alpha));
+        let lambda_prev = lambda;
+        lambda = L + (1.0 - C) * f * sin_alpha * (
+            sigma + C * sin_sigma * (cos_2sigma_m + C * cos_sigma * (-1.0 + 2.0*cos_2sigma_m*cos_2sigma_m))
+        );
+
+        if (lambda - lambda_prev).abs() < 1e-12 {
+            let u2 = cos2_alpha * (ellipsoid.eq_rad.powi(2) - b*b) / (b*b);
+            let (A, B) = vincenty_A_B(u2);
+            let delta_sigma = vincenty_delta_sigma(B, sin_sigma, cos_sigma, cos_2sigma_m);
+
+            let (sin_lambda, cos_lambda) = lambda.sin_cos();
+            let azi1 = (cos_U2 * sin_lambda).atan2(cos_U1*sin_U2 - sin_U1*cos_U2*cos_lambda);
+            let azi2 = (cos_U1 * sin_lambda).atan2(-sin_U1*cos_U2 + cos_U1*sin_U2*cos_lambda);
+
+            return Some((
+                b * A * (sigma - delta_sigma),
+                angle::limit_to_two_PI(azi1),
+                angle::limit_to_two_PI(azi2)
+            ));
+        }
+    }
+
+    None
+
+}
+
+/**
+Computes the point reached by travelling a geodesic distance from a
+point along an azimuth, by Vincenty's direct method
+
+# Returns
+
+`(p2, azi2)`
+
+* `p2`  : `GeographPoint` reached (longitude measured positively
+          westwards)
+* `azi2`: Azimuth of the geodesic at `p2`, in the direction of
+          travel *| in radians*
+
+# Arguments
+
+* `p1`       : Starting `GeographPoint`
+* `azi1`     : Azimuth at `p1`, measured eastwards from the north
+               *| in radians*
+* `dist`     : Geodesic distance *| in kilometers*
+* `ellipsoid`: Reference ellipsoid
+**/
+pub fn vincenty_dest(p1: &coords::GeographPoint, azi1: f64, dist: f64, ellipsoid: &Ellipsoid) -> (coords::GeographPoint, f64) {
+
+    let f = ellipsoid.flat_fac;
+    let b = ellipsoid.pol_rad();
+
+    let tan_U1 = (1.0 - f) * p1.lat.tan();
+    let cos_U1 = 1.0 / (1.0 + tan_U1*tan_U1).sqrt();
+    let sin_U1 = tan_U1 * cos_U1;
+    let (sin_azi1, cos_azi1) = azi1.sin_cos();
+
+    let sigma1 = tan_U1.atan2(cos_azi1);
+    let sin_alpha = cos_U1 * sin_azi1;
+    let cos2_alpha = 1.0 - sin_alpha*sin_alpha;
+    let u2 = cos2_alpha * (ellipsoid.eq_rad.powi(2) - b*b) / (b*b);
+    let (A, B) = vincenty_A_B(u2);
+
+    let mut sigma = dist / (b * A);
+    let mut cos_2sigma_m = (2.0*sigma1 + sigma).cos();
+    for _ in 0..200 {
+        cos_2sigma_m = (2.0*sigma1 + sigma).cos();
+        let (sin_sigma, cos_sigma) = sigma.sin_cos();
+        let sigma_prev = sigma;
+        sigma = dist / (b * A) + vincenty_delta_sigma(B, sin_sigma, cos_sigma, cos_2sigma_m);
+        if (sigma - sigma_prev).abs() < 1e-12 {
+            break;
+        }
+    }
+
+    let (sin_sigma, cos_sigma) = sigma.sin_cos();
+    let tmp = sin_U1*sin_sigma - cos_U1*cos_sigma*cos_azi1;
+    let lat = (sin_U1*cos_sigma + cos_U1*sin_sigma*cos_azi1).atan2(
+        (1.0 - f) * (sin_alpha*sin_alpha + tmp*tmp).sqrt()
+    );
+    let lambda = (sin_sigma * sin_azi1).atan2(cos_U1*cos_sigma - sin_U1*sin_sigma*cos_azi1);
+    let C = f / 16.0 * cos2_alpha * (4.0 + f*(4.0 - 3.0*cos2_alpha));
+    let L = lambda - (1.0 - C) * f * sin_alpha * (
+        sigma + C * sin_sigma * (cos_2sigma_m + C * cos_sigma * (-1.0 + 2.0*cos_2sigma_m*cos_2sigma_m))
+    );
+
+    let long = angle::limit_to_pm_PI(p1.long - L);
+    let azi2 = angle::limit_to_two_PI(sin_alpha.atan2(-tmp));
+
+    (coords::GeographPoint { long, lat }, azi2)
+
+}
+
+// Vincenty's series coefficients A and B, for the second
+// eccentricity term u²
+fn vincenty_A_B(u2: f64) -> (f64, f64) {
+    (
+        1.0 + u2 / 16384.0 * (4096.0 + u2*(-768.0 + u2*(320.0 - 175.0*u2))),
+        u2 / 1024.0 * (256.0 + u2*(-128.0 + u2*(74.0 - 47.0*u2)))
+    )
+}
+
+fn vincenty_delta_sigma(B: f64, sin_sigma: f64, cos_sigma: f64, cos_2sigma_m: f64) -> f64 {
+    let c2 = cos_2sigma_m * cos_2sigma_m;
+
+    B * sin_sigma * (
+        cos_2sigma_m + B / 4.0 * (
+            cos_sigma * (-1.0 + 2.0*c2)
+          - B / 6.0 * cos_2sigma_m * (-3.0 + 4.0*sin_sigma*sin_sigma) * (-3.0 + 4.0*c2)
+        )
+    )
+}
+
 /**
 Computes two quantities that are used elsewhere in the library
 
@@ -324,3 +567,26 @@ pub fn angl_betwn_diurnal_path_and_hz(dec: f64, observer_lat: f64) -> f64 {
     (C * dec.cos()).atan2(observer_lat.tan())
 
 }
+
+/**
+Computes the rate at which the altitude of a celestial body changes
+on the horizon
+
+# Returns
+
+* `rate`: Rate of change of the altitude of a celestial body on the
+          horizon, the same for rising and setting *| in radians
+          per second*
+
+# Arguments
+
+* `dec`         : Declination of the celestial body
+                  *| in radians*
+* `observer_lat`: Observer's geographic latitude
+                  *| in radians*
+**/
+pub fn alt_rate_on_hz(dec: f64, observer_lat: f64) -> f64 {
+
+    rot_angular_velocity() * dec.cos() * angl_betwn_diurnal_path_and_hz(dec, observer_lat).sin()
+
+}
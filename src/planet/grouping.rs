@@ -0,0 +1,366 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Groupings of planets and the Moon on the sky
+
+/*
+
+A grouping (or massing) is an episode during which some of a set of
+bodies fit within a circle of a given diameter on the sky, as seen
+from the Earth's center. The circle considered at each instant is the
+smallest one containing any subset of the required number of bodies.
+Each episode is reported with that circle at the instants examined
+within it, and at the tightest instant, found by searching between
+them.
+
+The circle containing three bodies is found as by *Meeus*, on a plane,
+and that containing more bodies as the smallest cap on the sphere.
+
+The Moon's position is geocentric, and may be off by up to a degree
+for an observer on the Earth's surface.
+
+*/
+
+use angle;
+use body::Body;
+use coords;
+use error;
+use lunar;
+use numerics;
+use planet;
+use std;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Holds the smallest circle containing some of a set of bodies at an
+/// instant
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Circle {
+    /// Julian (Ephemeris) day
+    pub JD: f64,
+    /// Diameter of the circle *| in radians*
+    pub diameter: f64,
+    /// Indices, in the slice of bodies searched, of the bodies in
+    /// the circle
+    pub members: Vec<usize>
+}
+
+/// Holds an episode of a grouping
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Grouping {
+    /// Julian (Ephemeris) day at which the grouping starts
+    pub start: f64,
+    /// Julian (Ephemeris) day at which the grouping ends
+    pub end: f64,
+    /// Julian (Ephemeris) day at which the grouping is tightest
+    pub JD_tightest: f64,
+    /// Diameter of the smallest circle containing the bodies when
+    /// the grouping is tightest *| in radians*
+    pub diameter: f64,
+    /// Indices, in the slice of bodies searched, of the bodies in
+    /// the smallest circle when the grouping is tightest
+    pub members: Vec<usize>,
+    /// The smallest [Circle](./struct.Circle.html)s over the
+    /// episode, at its start, at each instant examined within it, at
+    /// the tightest instant and at its end, in the order of time
+    pub circles: Vec<Circle>
+}
+
+/**
+Computes the smallest circle on the sky containing some of a set of
+bodies
+
+# Returns
+
+* `Ok((diameter, members))`: `diameter` is the diameter of the smallest
+                             circle containing `min_bodies` of the
+                             bodies *| in radians*, and `members` the
+                             indices in `bodies` of the bodies in the
+                             circle, or
+* `Err(error)`             : `Error::InvalidBody` for a body other
+                             than the Moon and the planets other than
+                             the Earth
+
+# Arguments
+
+* `bodies`    : The [Body](../../body/enum.Body.html)s to consider
+* `min_bodies`: Number of bodies the circle should contain,
+                at least 2
+* `JD`        : Julian (Ephemeris) day
+**/
+pub fn smallest_circle(bodies: &[Body], min_bodies: usize, JD: f64) -> Result<(f64, Vec<usize>), error::Error> {
+
+    check_bodies(bodies)?;
+
+    Ok(circle(bodies, min_bodies, JD))
+
+}
+
+// The smallest circle containing some of a set of checked bodies
+fn circle(bodies: &[Body], min_bodies: usize, JD: f64) -> (f64, Vec<usize>) {
+
+    let points: Vec<coords::EclPoint> = bodies.iter().map(|body| position(body, JD)).collect();
+
+    let mut best = (f64::MAX, Vec::new());
+    for subset in combinations(points.len(), min_bodies) {
+        let diameter = if let [i, j, k] = subset[..] {
+            let (p1, p2, p3) = (&points[i], &points[j], &points[k]);
+            let rad = angle::Rad;
+            (angle::smallest_circle_rad(
+                rad(p1.long), rad(p1.lat), rad(p2.long), rad(p2.lat), rad(p3.long), rad(p3.lat)
+            ).0).0
+        } else {
+            let directions: Vec<(f64, f64, f64)> = subset.iter().map(|&i| direction(&points[i])).collect();
+            enclosing_diameter(&directions)
+        };
+        if diameter < best.0 {
+            best = (diameter, subset);
+        }
+    }
+
+    best
+
+}
+
+/**
+Finds the groupings of some of a set of bodies within a circle on the
+sky
+
+# Returns
+
+* `Ok(groupings)`: The [Grouping](./struct.Grouping.html)s found, in
+                   the order of time, or
+* `Err(error)`   : As from `smallest_circle()`
+
+# Arguments
+
+* `bodies`    : The [Body](../../body/enum.Body.html)s to consider
+* `min_bodies`: Number of bodies that should fit within the circle,
+                at least 2
+* `diameter`  : Diameter of the circle *| in radians*
+* `JD_start`  : Julian (Ephemeris) day to start the search from
+* `JD_end`    : Julian (Ephemeris) day to end the search at
+* `step`      : Interval between the instants examined *| in days*,
+                which should be less than the shortest grouping
+                of interest
+**/
+pub fn groupings (
+
+    bodies     : &[Body],
+    min_bodies : usize,
+    diameter   : f64,
+    JD_start   : f64,
+    JD_end     : f64,
+    step       : f64
+
+) -> Result<Vec<Grouping>, error::Error> {
+
+    check_bodies(bodies)?;
+    let excess = |JD: f64| circle(bodies, min_bodies, JD).0 - diameter;
+
+    let at = |JD: f64| {
+        let (diameter, members) = circle(bodies, min_bodies, JD);
+        Circle { JD, diameter, members }
+    };
+
+    let mut groupings = Vec::new();
+    let mut start = None;
+    let mut tightest = (0.0, f64::MAX);
+    let mut circles = Vec::new();
+
+    let n = ((JD_end - JD_start)/step).ceil() as usize;
+    let mut previous = JD_start;
+
+    for i in 0..(n + 1) {
+        let JD = (JD_start + (i as f64)*step).min(JD_end);
+        let e = excess(JD);
+
+        if e <= 0.0 {
+            if start.is_none() {
+                let s = if i == 0 { JD } else { numerics::bisection(excess, previous, JD, 1e-5).unwrap_or(JD) };
+                start = Some(s);
+                tightest = (JD, f64::MAX);
+                circles = if s < JD { vec![at(s)] } else { Vec::new() };
+            }
+            if e < tightest.1 {
+                tightest = (JD, e);
+            }
+            circles.push(at(JD));
+        }
+
+        let ended = e > 0.0 || i == n;
+        if let (true, Some(s)) = (ended, start) {
+            let end = if e > 0.0 { numerics::bisection(excess, previous, JD, 1e-5).unwrap_or(JD) } else { JD };
+            let JD_tightest = tightest_time(&excess, tightest.0, step, s, end);
+            let tightest = at(JD_tightest);
+
+            if e > 0.0 {
+                circles.push(at(end));
+            }
+            let k = circles.iter().position(|c| c.JD > JD_tightest).unwrap_or(circles.len());
+            circles.insert(k, tightest.clone());
+
+            groupings.push(Grouping {
+                start: s,
+                end,
+                JD_tightest,
+                diameter: tightest.diameter,
+                members: tightest.members,
+                circles: std::mem::take(&mut circles)
+            });
+            start = None;
+        }
+
+        previous = JD;
+    }
+
+    Ok(groupings)
+
+}
+
+// Checks that the bodies are the Moon and planets other than the Earth
+fn check_bodies(bodies: &[Body]) -> Result<(), error::Error> {
+
+    for body in bodies {
+        match *body {
+            Body::Planet(planet::Planet::Earth) => return Err(error::Error::InvalidBody),
+            Body::Planet(_) | Body::Moon        => (),
+            _                                   => return Err(error::Error::InvalidBody)
+        }
+    }
+
+    Ok(())
+
+}
+
+// The geocentric ecliptic position of a checked body, referred to the
+// mean equinox of the date
+fn position(body: &Body, JD: f64) -> coords::EclPoint {
+
+    match *body {
+        Body::Planet(ref planet) => planet::geocent_apprnt_ecl_coords(planet, JD).0,
+        Body::Moon               => lunar::geocent_ecl_pos(JD).0,
+        _                        => unreachable!("the bodies are checked")
+    }
+
+}
+
+// An ecliptic position as a unit vector
+fn direction(point: &coords::EclPoint) -> (f64, f64, f64) {
+
+    (
+        point.lat.cos() * point.long.cos(),
+        point.lat.cos() * point.long.sin(),
+        point.lat.sin()
+    )
+
+}
+
+// The angular diameter of the smallest cap on the unit sphere that
+// contains some points lying within a hemisphere. The boundary of
+// such a cap passes through two or three of the points.
+fn enclosing_diameter(points: &[(f64, f64, f64)]) -> f64 {
+
+    let dot = |a: (f64, f64, f64), b: (f64, f64, f64)| a.0*b.0 + a.1*b.1 + a.2*b.2;
+    let normalized = |a: (f64, f64, f64)| {
+        let n = dot(a, a).sqrt();
+        (a.0/n, a.1/n, a.2/n)
+    };
+    let contains_all = |center: (f64, f64, f64), radius: f64| {
+        points.iter().all(|&p| dot(center, p).clamp(-1.0, 1.0).acos() <= radius + 1e-12)
+    };
+
+    let mut best = f64::MAX;
+    let n = points.len();
+
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let (a, b) = (points[i], points[j]);
+            let center = normalized((a.0 + b.0, a.1 + b.1, a.2 + b.2));
+            let radius = dot(center, a).clamp(-1.0, 1.0).acos();
+            if 2.0*radius < best && contains_all(center, radius) {
+                best = 2.0*radius;
+            }
+
+            for &c in &points[(j + 1)..] {
+                let (u, v) = ((b.0 - a.0, b.1 - a.1, b.2 - a.2), (c.0 - a.0, c.1 - a.1, c.2 - a.2));
+                let normal = (u.1*v.2 - u.2*v.1, u.2*v.0 - u.0*v.2, u.0*v.1 - u.1*v.0);
+                if dot(normal, normal) == 0.0 {
+                    continue;
+                }
+                let mut center = normalized(normal);
+                if dot(center, a) < 0.0 {
+                    center = (-center.0, -center.1, -center.2);
+                }
+                let radius = dot(center, a).clamp(-1.0, 1.0).acos();
+                if 2.0*radius < best && contains_all(center, radius) {
+                    best = 2.0*radius;
+                }
+            }
+        }
+    }
+
+    best
+
+}
+
+// The subsets of k of the indices 0..n
+fn combinations(n: usize, k: usize) -> Vec<Vec<usize>> {
+
+    if k == 0 {
+        return vec![Vec::new()];
+    }
+    if k > n {
+        return Vec::new();
+    }
+
+    let mut subsets = combinations(n - 1, k);
+    for mut subset in combinations(n - 1, k - 1) {
+        subset.push(n - 1);
+        subsets.push(subset);
+    }
+
+    subsets
+
+}
+
+// The instant of the least value of a function near a sampled
+// minimum, kept within the episode
+fn tightest_time<F>(f: &F, JD: f64, step: f64, start: f64, end: f64) -> f64 where F: Fn(f64) -> f64 {
+
+    let ratio = (5.0_f64.sqrt() - 1.0)/2.0;
+    let (mut a, mut b) = ((JD - step).max(start), (JD + step).min(end));
+
+    let mut iters = 0;
+    while b - a > 1e-5 {
+        let c = b - ratio*(b - a);
+        let d = a + ratio*(b - a);
+        if f(c) < f(d) { b = d; } else { a = c; }
+        iters += 1;
+    }
+    trace_event!("planet::grouping::tightest_time", Converged, iters, b - a);
+
+    (a + b)/2.0
+
+}
@@ -28,6 +28,8 @@ use angle;
 use nutation;
 use planet;
 use coords;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 /**
 Computes Jupiter's equatorial semidiameter
@@ -65,8 +67,30 @@ pub fn pol_semidiameter(jup_earth_dist: f64) -> f64 {
 
 }
 
+/**
+Computes the apparent rectangular coordinates of the four Galilean
+moons, with high accuracy
+
+# Returns
+
+`[io, europa, ganymede, callisto]`
+
+Each element is the `(X, Y, Z)` of a moon, as returned by
+[`moon::accurate_apprnt_rect_coords()`](./moon/fn.accurate_apprnt_rect_coords.html)
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn moons(JD: f64) -> [(f64, f64, f64); 4] {
+
+    moon::accurate_apprnt_rect_coords_all(JD)
+
+}
+
 /// Holds Jupiter's ephemeris values for physical observations
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Ephemeris {
     /// Jupiter-centric declination of the Earth
     pub De : f64,
@@ -117,28 +141,17 @@ pub fn ephemeris (
 
     let (l0, b0, R) = planet::heliocent_coords(&planet::Planet::Earth, JD);
 
-    let (mut l, mut b, mut r) = (0.0, 0.0, 0.0);
-    let mut x;
-    let mut y;
-    let mut z;
-    let mut jup_earth_dist = 0.0;
-    let mut light_time = 0.0;
-
-    let mut i: u8 = 1;
-    while i <= 2 {
-
-        let (new_l, new_b, new_r) = planet::heliocent_coords(&planet::Planet::Jupiter, JD - light_time);
-        l = new_l; b = new_b; r = new_r;
-
-        let (new_x, new_y, new_z) = planet::geocent_ecl_rect_coords(l0, b0, R, l, b, r);
-        x = new_x; y = new_y; z = new_z;
-
-        jup_earth_dist = planet::dist_frm_ecl_rect_coords(x, y, z);
-        light_time = planet::light_time(jup_earth_dist);
-
-        i += 1;
+    let dist = |tau: f64| {
+        let (l, b, r) = planet::heliocent_coords(&planet::Planet::Jupiter, JD - tau);
+        let (x, y, z) = planet::geocent_ecl_rect_coords(l0, b0, R, l, b, r);
+        planet::dist_frm_ecl_rect_coords(x, y, z)
+    };
+    let light_time = planet::light_time_iter(
+        dist, planet::LIGHT_TIME_TOL, planet::LIGHT_TIME_MAX_ITERS
+    );
 
-    }
+    let (mut l, b, r) = planet::heliocent_coords(&planet::Planet::Jupiter, JD - light_time);
+    let mut jup_earth_dist = dist(light_time);
 
     l -= 0.01299_f64.to_radians()*jup_earth_dist / (r*r);
     let (x, y, z) = planet::geocent_ecl_rect_coords(l0, b0, R, l, b, r);
@@ -180,7 +193,7 @@ pub fn ephemeris (
                 + asc.cos()*dec.sin()*l0.sin());
 
     let (asc_nut, dec_nut) = nutation::nutation_in_eq_coords(
-        &coords::EqPoint{asc: asc, dec: dec},
+        &coords::EqPoint{asc, dec},
         nut_in_long,
         nut_in_oblq,
         tru_oblq
@@ -203,9 +216,9 @@ pub fn ephemeris (
     Ephemeris {
         De: D_e,
         Ds: D_s,
-        P : P,
-        w1: w1,
-        w2: w2
+        P,
+        w1,
+        w2
     }
 
 }
@@ -22,6 +22,14 @@ THE SOFTWARE.
 
 //! The four Galilean moons
 
+use consts;
+use error;
+use planet;
+use planet::moon_phenomena;
+use precess;
+#[cfg(not(feature = "std"))]
+use math::*;
+
 /*
 
 Meeus referrs to the moons as Satellites I, II, III and IV.
@@ -31,6 +39,8 @@ and IV is Callisto. That mapping is used.
 */
 
 /// Represents a Galilean moon
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Moon {
     /// Io
     Io,
@@ -93,28 +103,28 @@ pub fn apprnt_rect_coords(JD: f64, moon: &Moon) -> (f64, f64) {
     let u2 = (358.414  + 101.2916335*d_minus_delta_by_173).to_radians() + phi_minus_B;
     let u3 = (5.7176   + 50.234518*d_minus_delta_by_173).to_radians()   + phi_minus_B;
 
-    let mut u = match moon {
-        &Moon::Io       => u1,
-        &Moon::Europa   => u2,
-        &Moon::Ganymede => u3,
-        &Moon::Callisto => (224.8092 + 21.48798*d_minus_delta_by_173).to_radians() + phi_minus_B,
+    let mut u = match *moon {
+        Moon::Io       => u1,
+        Moon::Europa   => u2,
+        Moon::Ganymede => u3,
+        Moon::Callisto => (224.8092 + 21.48798*d_minus_delta_by_173).to_radians() + phi_minus_B,
     };
 
     let G = (331.18 + 50.310482*d_minus_delta_by_173).to_radians();
     let H = (87.45  + 21.569231*d_minus_delta_by_173).to_radians();
 
-    u += (match moon {
-        &Moon::Io       => 0.473 * (2.0*(u1 - u2)).sin(),
-        &Moon::Europa   => 1.065 * (2.0*(u2 - u3)).sin(),
-        &Moon::Ganymede => 0.165 * G.sin(),
-        &Moon::Callisto => 0.843 * H.sin(),
+    u += (match *moon {
+        Moon::Io       => 0.473 * (2.0*(u1 - u2)).sin(),
+        Moon::Europa   => 1.065 * (2.0*(u2 - u3)).sin(),
+        Moon::Ganymede => 0.165 * G.sin(),
+        Moon::Callisto => 0.843 * H.sin(),
     }).to_radians();
 
-    let r_moon = match moon {
-        &Moon::Io       => (5.9057  - 0.0244*(2.0*(u1 - u2)).cos()),
-        &Moon::Europa   => (9.3966  - 0.0882*(2.0*(u2 - u3)).cos()),
-        &Moon::Ganymede => (14.9883 - 0.0216*G.cos()),
-        &Moon::Callisto => (26.3627 - 0.1939*H.cos()),
+    let r_moon = match *moon {
+        Moon::Io       => 5.9057  - 0.0244*(2.0*(u1 - u2)).cos(),
+        Moon::Europa   => 9.3966  - 0.0882*(2.0*(u2 - u3)).cos(),
+        Moon::Ganymede => 14.9883 - 0.0216*G.cos(),
+        Moon::Callisto => 26.3627 - 0.1939*H.cos(),
     };
 
     let lambda = (34.35 + 0.083091*d + 0.329*V.sin()).to_radians() + B;
@@ -130,3 +140,754 @@ pub fn apprnt_rect_coords(JD: f64, moon: &Moon) -> (f64, f64) {
     (X, Y)
 
 }
+
+/**
+Computes the apparent magnitude of a Galilean moon
+
+The magnitudes at unit distances from the Sun and the Earth, and
+the phase coefficients, are those of the *Explanatory Supplement to
+the Astronomical Almanac* (1992).
+
+# Returns
+
+* `apprnt_mag`: Apparent magnitude of the moon
+
+# Arguments
+
+* `JD`  : Julian (Ephemeris) day
+* `moon`: The [Moon](./enum.Moon.html)
+**/
+pub fn apprnt_mag(JD: f64, moon: &Moon) -> f64 {
+
+    let (r, delta, R) = planet::dists(&planet::Planet::Jupiter, JD);
+    let i = planet::phase_angl(r, delta, R).to_degrees();
+
+    let (V0, c1, c2) = match *moon {
+        Moon::Io       => (-1.68, 0.046,  -0.0010),
+        Moon::Europa   => (-1.41, 0.0312, -0.00125),
+        Moon::Ganymede => (-2.09, 0.0323, -0.00066),
+        Moon::Callisto => (-1.05, 0.078,  -0.00274),
+    };
+
+    V0 + 5.0*(r*delta).log10() + i*(c1 + i*c2)
+
+}
+
+/**
+Computes the apparent rectangular coordinates for a Galilean moon,
+with high accuracy
+
+This function implements the high accuracy method described in Meeus's
+book, which is based on the theory E5 of J. H. Lieske. It takes into
+account the light-time from Jupiter to the Earth, and the differences
+in light-time from the moons to the Earth.
+
+# Returns
+
+`(X, Y, Z)`
+
+`X` and `Y` are as returned by
+[`apprnt_rect_coords()`](./fn.apprnt_rect_coords.html), in units of
+Jupiter's equatorial radius.
+
+`Z` is measured negatively when the moon is closer to the Earth than
+Jupiter, and positively when it is farther. It is needed to tell
+whether a moon in front of or behind Jupiter's disk is in transit or
+in occultation.
+
+# Arguments
+
+* `JD`  : Julian (Ephemeris) day
+* `moon`: The [Moon](./enum.Moon.html)
+**/
+pub fn accurate_apprnt_rect_coords(JD: f64, moon: &Moon) -> (f64, f64, f64) {
+
+    accurate_apprnt_rect_coords_all(JD)[index(moon)]
+
+}
+
+/**
+Computes the position of a Galilean moon with respect to Jupiter
+
+The position follows from the same theory as
+[`accurate_apprnt_rect_coords()`](./fn.accurate_apprnt_rect_coords.html),
+and can be given to the routines of
+[moon_phenomena](../../moon_phenomena/index.html).
+
+# Returns
+
+`(x, y, z)`
+
+* `x`, `y`, `z`: Rectangular coordinates of the moon with respect to
+                 the center of Jupiter, referred to the ecliptic and
+                 mean equinox of J2000.0 *| in AU*
+
+# Arguments
+
+* `JD`  : Julian (Ephemeris) day
+* `moon`: The [Moon](./enum.Moon.html)
+**/
+pub fn jovicent_rect_coords(JD: f64, moon: &Moon) -> (f64, f64, f64) {
+
+    let (orbits, psi) = jovicent_orbits(JD - 2443000.5);
+    let (P, psi) = precession(JD, psi);
+
+    let (L, B, R) = orbits[index(moon)];
+    let R = R * THEORY_RADIUS / (consts::ASTRONOMICAL_UNIT / 1000.0);
+    let u = (L + P - psi).to_radians();
+    let (x, y, z) = ecl_frm_jovi_eq(JD, psi, (R*u.cos()*B.cos(), R*u.sin()*B.cos(), R*B.sin()));
+
+    let (long, lat) = precess::precess_ecl_coords(y.atan2(x), z.atan2(x.hypot(y)), JD, 2451545.0);
+
+    (R*lat.cos()*long.cos(), R*lat.cos()*long.sin(), R*lat.sin())
+
+}
+
+/**
+Computes the eclipses, occultations, transits and shadow transits of
+a Galilean moon in an interval of time
+
+# Returns
+
+* `Ok(phenomena)`: [Phenomena](../../moon_phenomena/struct.Phenomenon.html)
+                   of the moon, ordered by their start, or
+* `Err(error)`   : `Error::NotPositive` if `step` isn't positive, or
+                   `Error::NotFinite`
+
+# Arguments
+
+* `moon`    : The [Moon](./enum.Moon.html)
+* `JD_start`: Julian (Ephemeris) day at the start of the search
+* `JD_end`  : Julian (Ephemeris) day at the end of the search
+* `step`    : Sampling interval, positive *| in days*, such as 0.01
+**/
+pub fn phenomena(moon: &Moon, JD_start: f64, JD_end: f64, step: f64) -> Result<Vec<moon_phenomena::Phenomenon>, error::Error> {
+
+    moon_phenomena::phenomena (
+        &moon_phenomena::primary(planet::Planet::Jupiter),
+        |JD| jovicent_rect_coords(JD, moon),
+        JD_start,
+        JD_end,
+        step
+    )
+
+}
+
+/// Represents a mutual event of the Galilean moons
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MutualEventType {
+    /// A moon hides part or all of another, as seen from the Earth
+    Occultation,
+    /// A moon casts its penumbra on another
+    Eclipse
+}
+
+/// Holds a mutual event of the Galilean moons
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MutualEvent {
+    /// The [MutualEventType](./enum.MutualEventType.html)
+    pub kind: MutualEventType,
+    /// The occulting or eclipsing [Moon](./enum.Moon.html)
+    pub active: Moon,
+    /// The occulted or eclipsed [Moon](./enum.Moon.html)
+    pub passive: Moon,
+    /// Julian (Ephemeris) day of the first contact
+    pub start: f64,
+    /// Julian (Ephemeris) day of the last contact
+    pub end: f64
+}
+
+/**
+Computes the mutual occultations and eclipses of the Galilean moons
+in an interval of time
+
+The moons are taken as spheres, and an eclipse lasts while the
+eclipsed moon is touched by the penumbra of the eclipsing one. Mutual
+events occur only in the months around the equinoxes of Jupiter,
+when the Earth and the Sun pass through the plane of the orbits of
+the moons.
+
+# Returns
+
+* `Ok(events)`: [MutualEvents](./struct.MutualEvent.html) ordered by
+                their start. Events in progress at `JD_start` or
+                `JD_end` are clipped to the interval.
+* `Err(error)`: `Error::NotPositive` if `step` isn't positive, or
+                `Error::NotFinite`
+
+# Arguments
+
+* `JD_start`: Julian (Ephemeris) day at the start of the search
+* `JD_end`  : Julian (Ephemeris) day at the end of the search
+* `step`    : Sampling interval, positive *| in days*. Should be well
+              below the duration of the shortest event, such as 0.005.
+**/
+pub fn mutual_events(JD_start: f64, JD_end: f64, step: f64) -> Result<Vec<MutualEvent>, error::Error> {
+
+    error::check_positive(step)?;
+    if !JD_start.is_finite() || !JD_end.is_finite() {
+        return Err(error::Error::NotFinite);
+    }
+
+    let mut events = Vec::new();
+
+    let mut JD_prev = JD_start;
+    let mut prev = mutual_state(JD_prev);
+    let mut starts = [None; 32];
+    for (k, start) in starts.iter_mut().enumerate() {
+        if prev[k] { *start = Some(JD_start); }
+    }
+
+    while JD_prev < JD_end {
+        let JD_next = (JD_prev + step).min(JD_end);
+        let next = mutual_state(JD_next);
+
+        for k in 0..32 {
+            if next[k] == prev[k] {
+                continue;
+            }
+
+            let (mut lo, mut hi) = (JD_prev, JD_next);
+            let mut iters = 0;
+            while hi - lo > 1e-6 {
+                let mid = (lo + hi) / 2.0;
+                if mutual_state(mid)[k] == prev[k] {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+                iters += 1;
+            }
+            trace_event!("planet::jupiter::moon::mutual_events", Converged, iters, hi - lo);
+            let JD = (lo + hi) / 2.0;
+
+            if next[k] {
+                starts[k] = Some(JD);
+            } else if let Some(start) = starts[k].take() {
+                events.push(mutual_event(k, start, JD));
+            }
+        }
+
+        JD_prev = JD_next;
+        prev = next;
+    }
+
+    for (k, start) in starts.iter().enumerate() {
+        if let Some(start) = *start {
+            events.push(mutual_event(k, start, JD_end));
+        }
+    }
+
+    events.sort_by(|a, b| a.start.partial_cmp(&b.start).unwrap());
+
+    Ok(events)
+
+}
+
+// Computes the high accuracy apparent rectangular coordinates of the
+// four moons, in the order Io, Europa, Ganymede and Callisto
+pub(crate) fn accurate_apprnt_rect_coords_all(JD: f64) -> [(f64, f64, f64); 4] {
+
+    let (l0, b0, R) = planet::heliocent_coords(&planet::Planet::Earth, JD);
+
+    let geocent = |tau: f64| {
+        let (l, b, r) = planet::heliocent_coords(&planet::Planet::Jupiter, JD - tau);
+        planet::geocent_ecl_rect_coords(l0, b0, R, l, b, r)
+    };
+    let tau = planet::light_time_iter(
+        |tau| {
+            let (x, y, z) = geocent(tau);
+            planet::dist_frm_ecl_rect_coords(x, y, z)
+        },
+        planet::LIGHT_TIME_TOL, planet::LIGHT_TIME_MAX_ITERS
+    );
+    let (x, y, z) = geocent(tau);
+    let jup_earth_dist = planet::dist_frm_ecl_rect_coords(x, y, z);
+    let lambda0 = y.atan2(x);
+    let beta0 = z.atan2(x.hypot(y));
+
+    let t = JD - 2443000.5 - tau;
+    let (orbits, psi) = jovicent_orbits(t);
+    let (P, psi) = precession(JD, psi);
+
+    // rotates from Jupiter's equator to the sky plane, with the x-axis
+    // along the ecliptic
+    let rotate = |X: f64, Y: f64, Z: f64| {
+        let (A4, B4, C4) = ecl_frm_jovi_eq(JD, psi, (X, Y, Z));
+        let (A5, B5, C5) = (
+            A4*lambda0.sin() - B4*lambda0.cos(),
+            A4*lambda0.cos() + B4*lambda0.sin(),
+            C4
+        );
+
+        (A5, C5*beta0.sin() + B5*beta0.cos(), C5*beta0.cos() - B5*beta0.sin())
+    };
+
+    // a fictitious moon above Jupiter's north pole gives the direction
+    // of its axis on the sky
+    let (A6, _, C6) = rotate(0.0, 0.0, 1.0);
+    let D = A6.atan2(C6);
+
+    let moon_coords = |L: f64, B: f64, R: f64, K: f64| {
+        let u = (L + P - psi).to_radians();
+        let (A6, B6, C6) = rotate(R * u.cos() * B.cos(), R * u.sin() * B.cos(), R * B.sin());
+
+        let mut X = A6*D.cos() - C6*D.sin();
+        let mut Y = A6*D.sin() + C6*D.cos();
+        let Z = B6;
+
+        // the difference in light-time from the moon and from Jupiter
+        X += Z.abs()/K * (1.0 - (X/R).powi(2)).sqrt();
+
+        // perspective of the moon, closer to or farther from the Earth
+        let W = jup_earth_dist / (jup_earth_dist + Z/2095.0);
+        X *= W;
+        Y *= W;
+
+        (X, Y, Z)
+    };
+
+    let K = [17295.0, 21819.0, 27558.0, 36548.0];
+    let moon = |k: usize| {
+        let (L, B, R) = orbits[k];
+        moon_coords(L, B, R, K[k])
+    };
+
+    [moon(0), moon(1), moon(2), moon(3)]
+
+}
+
+// Computes the precession from B1950.0 to the equinox of the date, and
+// the longitude of the node of Jupiter's equator on the ecliptic
+// referred to it, from that referred to B1950.0 *| in degrees*
+fn precession(JD: f64, psi: f64) -> (f64, f64) {
+
+    let T = (JD - 2433282.423) / 36525.0;
+    let P = 1.3966626*T + 0.0003088*T*T;
+
+    (P, psi + P)
+
+}
+
+// Rotates coordinates referred to Jupiter's equator, with the x-axis
+// toward its node `psi` *| in degrees*, to the ecliptic and mean
+// equinox of the date
+fn ecl_frm_jovi_eq(JD: f64, psi: f64, (X, Y, Z): (f64, f64, f64)) -> (f64, f64, f64) {
+
+    // Julian centuries from J2000.0, in which the node and inclination
+    // of Jupiter's orbit are given, as in the chapter 31 of *Meeus*
+    let T = (JD - 2451545.0) / 36525.0;
+
+    let I = (3.120262 + 0.0006*T).to_radians();
+    let node = 100.464407 + T*(1.0209774 + T*(0.00040315 + T*0.000000404));
+    let incl = (1.303267 - T*(0.0054965 - T*(0.00000466 - T*0.000000002))).to_radians();
+    let phi = (psi - node).to_radians();
+    let node = node.to_radians();
+
+    let (A1, B1, C1) = (X, Y*I.cos() - Z*I.sin(), Y*I.sin() + Z*I.cos());
+    let (A2, B2, C2) = (A1*phi.cos() - B1*phi.sin(), A1*phi.sin() + B1*phi.cos(), C1);
+    let (A3, B3, C3) = (A2, B2*incl.cos() - C2*incl.sin(), B2*incl.sin() + C2*incl.cos());
+
+    (A3*node.cos() - B3*node.sin(), A3*node.sin() + B3*node.cos(), C3)
+
+}
+
+// Computes the true longitudes *| in degrees*, latitudes *| in radians*
+// and radius vectors *| in Jupiter's equatorial radii* of the four
+// moons, referred to Jupiter's equator, with the longitude of the node
+// of the equator on the ecliptic *| in degrees*, for `t` days after
+// JD 2443000.5
+fn jovicent_orbits(t: f64) -> ([(f64, f64, f64); 4], f64) {
+
+    // all the angles below are in degrees
+    let sin = |x: f64| x.to_radians().sin();
+    let cos = |x: f64| x.to_radians().cos();
+
+    let l1 = 106.07719 + 203.48895579 * t;
+    let l2 = 175.73161 + 101.374724735 * t;
+    let l3 = 120.55883 + 50.317609207 * t;
+    let l4 = 84.44459  + 21.571071177 * t;
+
+    let pi1 = 97.0881  + 0.16138586 * t;
+    let pi2 = 154.8663 + 0.04726307 * t;
+    let pi3 = 188.184  + 0.00712734 * t;
+    let pi4 = 335.2868 + 0.00184 * t;
+
+    let w1 = 312.3346 - 0.13279386 * t;
+    let w2 = 100.4411 - 0.03263064 * t;
+    let w3 = 119.1942 - 0.00717703 * t;
+    let w4 = 322.6186 - 0.00175934 * t;
+
+    let Gamma =
+        0.33033 * sin(163.679 + 0.0010512*t)
+      + 0.03439 * sin(34.486 - 0.0161731*t);
+    let phi_lambda = 199.6766 + 0.1737919 * t;
+    let psi = 316.5182 - 0.00000208 * t;
+    let G = 30.23756 + 0.0830925701*t + Gamma;
+    let G1 = 31.97853 + 0.0334597339 * t;
+    let PI = 13.469942;
+
+    let sigma1 =
+        0.47259 * sin(2.0*(l1 - l2))
+      - 0.03478 * sin(pi3 - pi4)
+      + 0.01081 * sin(l2 - 2.0*l3 + pi3)
+      + 0.00738 * sin(phi_lambda)
+      + 0.00713 * sin(l2 - 2.0*l3 + pi2)
+      - 0.00674 * sin(pi1 + pi3 - 2.0*PI - 2.0*G)
+      + 0.00666 * sin(l2 - 2.0*l3 + pi4)
+      + 0.00445 * sin(l1 - pi3)
+      - 0.00354 * sin(l1 - l2)
+      - 0.00317 * sin(2.0*psi - 2.0*PI)
+      + 0.00265 * sin(l1 - pi4)
+      - 0.00186 * sin(G)
+      + 0.00162 * sin(pi2 - pi3)
+      + 0.00158 * sin(4.0*(l1 - l2))
+      - 0.00155 * sin(l1 - l3)
+      - 0.00138 * sin(psi + w3 - 2.0*PI - 2.0*G)
+      - 0.00115 * sin(2.0*(l1 - 2.0*l2 + w2))
+      + 0.00089 * sin(pi2 - pi4)
+      + 0.00085 * sin(l1 + pi3 - 2.0*PI - 2.0*G)
+      + 0.00083 * sin(w2 - w3)
+      + 0.00053 * sin(psi - w2);
+
+    let sigma2 =
+        1.06476 * sin(2.0*(l2 - l3))
+      + 0.04256 * sin(l1 - 2.0*l2 + pi3)
+      + 0.03581 * sin(l2 - pi3)
+      + 0.02395 * sin(l1 - 2.0*l2 + pi4)
+      + 0.01984 * sin(l2 - pi4)
+      - 0.01778 * sin(phi_lambda)
+      + 0.01654 * sin(l2 - pi2)
+      + 0.01334 * sin(l2 - 2.0*l3 + pi2)
+      + 0.01294 * sin(pi3 - pi4)
+      - 0.01142 * sin(l2 - l3)
+      - 0.01057 * sin(G)
+      - 0.00775 * sin(2.0*(psi - PI))
+      + 0.00524 * sin(2.0*(l1 - l2))
+      - 0.0046  * sin(l1 - l3)
+      + 0.00316 * sin(psi - 2.0*G + w3 - 2.0*PI)
+      - 0.00203 * sin(pi1 + pi3 - 2.0*PI - 2.0*G)
+      + 0.00146 * sin(psi - w3)
+      - 0.00145 * sin(2.0*G)
+      + 0.00125 * sin(psi - w4)
+      - 0.00115 * sin(l1 - 2.0*l3 + pi3)
+      - 0.00094 * sin(2.0*(l2 - w2))
+      + 0.00086 * sin(2.0*(l1 - 2.0*l2 + w2))
+      - 0.00086 * sin(5.0*G1 - 2.0*G + 52.225)
+      - 0.00078 * sin(l2 - l4)
+      - 0.00064 * sin(3.0*l3 - 7.0*l4 + 4.0*pi4)
+      + 0.00064 * sin(pi1 - pi4)
+      - 0.00063 * sin(l1 - 2.0*l3 + pi4)
+      + 0.00058 * sin(w3 - w4)
+      + 0.00056 * sin(2.0*(psi - PI - G))
+      + 0.00056 * sin(2.0*(l2 - l4))
+      + 0.00055 * sin(2.0*(l1 - l3))
+      + 0.00052 * sin(3.0*l3 - 7.0*l4 + pi3 + 3.0*pi4)
+      - 0.00043 * sin(l1 - pi3)
+      + 0.00041 * sin(5.0*(l2 - l3))
+      + 0.00041 * sin(pi4 - PI)
+      + 0.00032 * sin(w2 - w3)
+      + 0.00032 * sin(2.0*(l3 - G - PI));
+
+    let sigma3 =
+        0.1649  * sin(l3 - pi3)
+      + 0.09081 * sin(l3 - pi4)
+      - 0.06907 * sin(l2 - l3)
+      + 0.03784 * sin(pi3 - pi4)
+      + 0.01846 * sin(2.0*(l3 - l4))
+      - 0.0134  * sin(G)
+      - 0.01014 * sin(2.0*(psi - PI))
+      + 0.00704 * sin(l2 - 2.0*l3 + pi3)
+      - 0.0062  * sin(l2 - 2.0*l3 + pi2)
+      - 0.00541 * sin(l3 - l4)
+      + 0.00381 * sin(l2 - 2.0*l3 + pi4)
+      + 0.00235 * sin(psi - w3)
+      + 0.00198 * sin(psi - w4)
+      + 0.00176 * sin(phi_lambda)
+      + 0.0013  * sin(3.0*(l3 - l4))
+      + 0.00125 * sin(l1 - l3)
+      - 0.00119 * sin(5.0*G1 - 2.0*G + 52.225)
+      + 0.00109 * sin(l1 - l2)
+      - 0.001   * sin(3.0*l3 - 7.0*l4 + 4.0*pi4)
+      + 0.00091 * sin(w3 - w4)
+      + 0.0008  * sin(3.0*l3 - 7.0*l4 + pi3 + 3.0*pi4)
+      - 0.00075 * sin(2.0*l2 - 3.0*l3 + pi3)
+      + 0.00072 * sin(pi1 + pi3 - 2.0*PI - 2.0*G)
+      + 0.00069 * sin(pi4 - PI)
+      - 0.00058 * sin(2.0*l3 - 3.0*l4 + pi4)
+      - 0.00057 * sin(l3 - 2.0*l4 + pi4)
+      + 0.00056 * sin(l3 + pi3 - 2.0*PI - 2.0*G)
+      - 0.00052 * sin(l2 - 2.0*l3 + pi1)
+      - 0.0005  * sin(pi2 - pi3)
+      + 0.00048 * sin(l3 - 2.0*l4 + pi3)
+      - 0.00045 * sin(2.0*l2 - 3.0*l3 + pi4)
+      - 0.00041 * sin(pi2 - pi4)
+      - 0.00038 * sin(2.0*G)
+      - 0.00037 * sin(pi3 - pi4 + w3 - w4)
+      - 0.00032 * sin(3.0*l3 - 7.0*l4 + 2.0*pi3 + 2.0*pi4)
+      + 0.0003  * sin(4.0*(l3 - l4))
+      + 0.00029 * sin(l3 + pi4 - 2.0*PI - 2.0*G)
+      - 0.00028 * sin(w3 + psi - 2.0*PI - 2.0*G)
+      + 0.00026 * sin(l3 - PI - G)
+      + 0.00024 * sin(l2 - 3.0*l3 + 2.0*l4)
+      + 0.00021 * sin(2.0*(l3 - PI - G))
+      - 0.00021 * sin(l3 - pi2)
+      + 0.00017 * sin(2.0*(l3 - pi3));
+
+    let sigma4 =
+        0.84287 * sin(l4 - pi4)
+      + 0.03431 * sin(pi4 - pi3)
+      - 0.03305 * sin(psi - w4)
+      - 0.03211 * sin(G)
+      - 0.01862 * sin(l4 - pi3)
+      + 0.01186 * sin(psi - w3)
+      + 0.00623 * sin(l4 + pi4 - 2.0*G - 2.0*PI)
+      + 0.00387 * sin(2.0*(l4 - pi4))
+      - 0.00284 * sin(5.0*G1 - 2.0*G + 52.225)
+      - 0.00234 * sin(2.0*(psi - PI))
+      - 0.00223 * sin(l3 - l4)
+      - 0.00208 * sin(l4 - PI)
+      + 0.00178 * sin(psi + w4 - 2.0*pi4)
+      + 0.00134 * sin(pi4 - PI)
+      + 0.00125 * sin(2.0*(l4 - G - PI))
+      - 0.00117 * sin(2.0*G)
+      - 0.00112 * sin(2.0*(l3 - l4))
+      + 0.00107 * sin(3.0*l3 - 7.0*l4 + 4.0*pi4)
+      + 0.00102 * sin(l4 - G - PI)
+      + 0.00096 * sin(2.0*l4 - psi - w4)
+      + 0.00087 * sin(2.0*(psi - w4))
+      - 0.00085 * sin(3.0*l3 - 7.0*l4 + pi3 + 3.0*pi4)
+      + 0.00085 * sin(l3 - 2.0*l4 + pi4)
+      - 0.00081 * sin(2.0*(l4 - psi))
+      + 0.00071 * sin(l4 + pi4 - 2.0*PI - 3.0*G)
+      + 0.00061 * sin(l1 - l4)
+      - 0.00056 * sin(psi - w3)
+      - 0.00054 * sin(l3 - 2.0*l4 + pi3)
+      + 0.00051 * sin(l2 - l4)
+      + 0.00042 * sin(2.0*(psi - G - PI))
+      + 0.00039 * sin(2.0*(pi4 - w4))
+      + 0.00036 * sin(psi + PI - pi4 - w4)
+      + 0.00035 * sin(2.0*G1 - G + 188.37)
+      - 0.00035 * sin(l4 - pi4 + 2.0*PI - 2.0*psi)
+      - 0.00032 * sin(l4 + pi4 - 2.0*PI - G)
+      + 0.0003  * sin(2.0*G1 - 2.0*G + 149.15)
+      + 0.00029 * sin(3.0*l3 - 7.0*l4 + 2.0*pi3 + 2.0*pi4)
+      + 0.00028 * sin(l4 - pi4 + 2.0*psi - 2.0*PI)
+      - 0.00028 * sin(2.0*(l4 - w4))
+      - 0.00027 * sin(pi3 - pi4 + w3 - w4)
+      - 0.00026 * sin(5.0*G1 - 3.0*G + 188.37)
+      + 0.00025 * sin(w4 - w3)
+      - 0.00025 * sin(l2 - 3.0*l3 + 2.0*l4)
+      - 0.00023 * sin(3.0*(l3 - l4))
+      + 0.00021 * sin(2.0*l4 - 2.0*PI - 3.0*G)
+      - 0.00021 * sin(2.0*l3 - 3.0*l4 + pi4)
+      + 0.00019 * sin(l4 - pi4 - G)
+      - 0.00019 * sin(2.0*l4 - pi3 - pi4)
+      - 0.00018 * sin(l4 - pi4 + G)
+      - 0.00016 * sin(l4 + pi3 - 2.0*PI - 2.0*G);
+
+    let L1 = l1 + sigma1;
+    let L2 = l2 + sigma2;
+    let L3 = l3 + sigma3;
+    let L4 = l4 + sigma4;
+
+    let B1 = (
+        0.0006393 * sin(L1 - w1)
+      + 0.0001825 * sin(L1 - w2)
+      + 0.0000329 * sin(L1 - w3)
+      - 0.0000311 * sin(L1 - psi)
+      + 0.0000093 * sin(L1 - w4)
+      + 0.0000075 * sin(3.0*L1 - 4.0*l2 - 1.9927*sigma1 + w2)
+      + 0.0000046 * sin(L1 + psi - 2.0*PI - 2.0*G)
+    ).atan();
+
+    let B2 = (
+        0.0081004 * sin(L2 - w2)
+      + 0.0004512 * sin(L2 - w3)
+      - 0.0003284 * sin(L2 - psi)
+      + 0.000116  * sin(L2 - w4)
+      + 0.0000272 * sin(l1 - 2.0*l3 + 1.0146*sigma2 + w2)
+      - 0.0000144 * sin(L2 - w1)
+      + 0.0000143 * sin(L2 + psi - 2.0*PI - 2.0*G)
+      + 0.0000035 * sin(L2 - psi + G)
+      - 0.0000028 * sin(l1 - 2.0*l3 + 1.0146*sigma2 + w3)
+    ).atan();
+
+    let B3 = (
+        0.0032402 * sin(L3 - w3)
+      - 0.0016911 * sin(L3 - psi)
+      + 0.0006847 * sin(L3 - w4)
+      - 0.0002797 * sin(L3 - w2)
+      + 0.0000321 * sin(L3 + psi - 2.0*PI - 2.0*G)
+      + 0.0000051 * sin(L3 - psi + G)
+      - 0.0000045 * sin(L3 - psi - G)
+      - 0.0000045 * sin(L3 + psi - 2.0*PI)
+      + 0.0000037 * sin(L3 + psi - 2.0*PI - 3.0*G)
+      + 0.000003  * sin(2.0*l2 - 3.0*L3 + 4.03*sigma3 + w2)
+      - 0.0000021 * sin(2.0*l2 - 3.0*L3 + 4.03*sigma3 + w3)
+    ).atan();
+
+    let B4 = (
+      - 0.0076579 * sin(L4 - psi)
+      + 0.0044134 * sin(L4 - w4)
+      - 0.0005112 * sin(L4 - w3)
+      + 0.0000773 * sin(L4 + psi - 2.0*PI - 2.0*G)
+      + 0.0000104 * sin(L4 - psi + G)
+      - 0.0000102 * sin(L4 - psi - G)
+      + 0.0000088 * sin(L4 + psi - 2.0*PI - 3.0*G)
+      - 0.0000038 * sin(L4 + psi - 2.0*PI - G)
+    ).atan();
+
+    let R1 = 5.90569 * (1.0
+      - 0.0041339 * cos(2.0*(l1 - l2))
+      - 0.0000387 * cos(l1 - pi3)
+      - 0.0000214 * cos(l1 - pi4)
+      + 0.000017  * cos(l1 - l2)
+      - 0.0000131 * cos(4.0*(l1 - l2))
+      + 0.0000106 * cos(l1 - l3)
+      - 0.0000066 * cos(l1 + pi3 - 2.0*PI - 2.0*G)
+    );
+
+    let R2 = 9.39657 * (1.0
+      + 0.0093848 * cos(l1 - l2)
+      - 0.0003116 * cos(l2 - pi3)
+      - 0.0001744 * cos(l2 - pi4)
+      - 0.0001442 * cos(l2 - pi2)
+      + 0.0000553 * cos(l2 - l3)
+      + 0.0000523 * cos(l1 - l3)
+      - 0.000029  * cos(2.0*(l1 - l2))
+      + 0.0000164 * cos(2.0*(l2 - w2))
+      + 0.0000107 * cos(l1 - 2.0*l3 + pi3)
+      - 0.0000102 * cos(l2 - pi1)
+      - 0.0000091 * cos(2.0*(l1 - l3))
+    );
+
+    let R3 = 14.98832 * (1.0
+      - 0.0014388 * cos(l3 - pi3)
+      - 0.0007919 * cos(l3 - pi4)
+      + 0.0006342 * cos(l2 - l3)
+      - 0.0001761 * cos(2.0*(l3 - l4))
+      + 0.0000294 * cos(l3 - l4)
+      - 0.0000156 * cos(3.0*(l3 - l4))
+      + 0.0000156 * cos(l1 - l3)
+      - 0.0000153 * cos(l1 - l2)
+      + 0.000007  * cos(2.0*l2 - 3.0*l3 + pi3)
+      - 0.0000051 * cos(l3 + pi3 - 2.0*PI - 2.0*G)
+    );
+
+    let R4 = 26.36273 * (1.0
+      - 0.0073546 * cos(l4 - pi4)
+      + 0.0001621 * cos(l4 - pi3)
+      + 0.0000974 * cos(l3 - l4)
+      - 0.0000543 * cos(l4 + pi4 - 2.0*PI - 2.0*G)
+      - 0.0000271 * cos(2.0*(l4 - pi4))
+      + 0.0000182 * cos(l4 - PI)
+      + 0.0000177 * cos(2.0*(l3 - l4))
+      - 0.0000167 * cos(2.0*l4 - psi - w4)
+      + 0.0000167 * cos(psi - w4)
+      - 0.0000155 * cos(2.0*(l4 - PI - G))
+      + 0.0000142 * cos(2.0*(l4 - psi))
+      + 0.0000105 * cos(l1 - l4)
+      + 0.0000092 * cos(l2 - l4)
+      - 0.0000089 * cos(l4 - PI - G)
+      - 0.0000062 * cos(l4 + pi4 - 2.0*PI - 3.0*G)
+      + 0.0000048 * cos(2.0*(l4 - w4))
+    );
+
+    (
+        [(L1, B1, R1), (L2, B2, R2), (L3, B3, R3), (L4, B4, R4)],
+        psi
+    )
+
+}
+
+// Unit of the radius vectors of the theory *| in kilometers*
+const THEORY_RADIUS: f64 = 71398.0;
+
+// Radii of the moons *| in kilometers*, in the order Io, Europa,
+// Ganymede and Callisto
+const RADII: [f64; 4] = [1821.6, 1560.8, 2631.2, 2410.3];
+
+// Radius of the Sun *| in kilometers*
+const SUN_RADIUS: f64 = 696000.0;
+
+const MOONS: [Moon; 4] = [Moon::Io, Moon::Europa, Moon::Ganymede, Moon::Callisto];
+
+fn index(moon: &Moon) -> usize {
+
+    match *moon {
+        Moon::Io       => 0,
+        Moon::Europa   => 1,
+        Moon::Ganymede => 2,
+        Moon::Callisto => 3,
+    }
+
+}
+
+// Makes the mutual event of index k of mutual_state()
+fn mutual_event(k: usize, start: f64, end: f64) -> MutualEvent {
+
+    let (active, passive) = (k / 2 % 4, k / 2 / 4);
+
+    MutualEvent {
+        kind: match k % 2 {
+            0 => MutualEventType::Occultation,
+            _ => MutualEventType::Eclipse
+        },
+        active: MOONS[active],
+        passive: MOONS[passive],
+        start,
+        end
+    }
+
+}
+
+// Computes whether each moon occults and eclipses each other moon. The
+// index of an event is 2*(4*passive + active), plus 1 for an eclipse.
+fn mutual_state(JD: f64) -> [bool; 32] {
+
+    let (t, jup, earth) = planet::light_time_corrected_J2000(&planet::Planet::Jupiter, JD);
+    let km_per_AU = consts::ASTRONOMICAL_UNIT / 1000.0;
+
+    let mut helio = [(0.0, 0.0, 0.0); 4];
+    for (k, moon) in MOONS.iter().enumerate() {
+        let (x, y, z) = jovicent_rect_coords(t, moon);
+        helio[k] = (jup.0 + x, jup.1 + y, jup.2 + z);
+    }
+
+    let sub = |a: (f64, f64, f64), b: (f64, f64, f64)| (a.0 - b.0, a.1 - b.1, a.2 - b.2);
+    let norm = |a: (f64, f64, f64)| (a.0*a.0 + a.1*a.1 + a.2*a.2).sqrt();
+    let angle = |a: (f64, f64, f64), b: (f64, f64, f64)| {
+        let c = (a.1*b.2 - a.2*b.1, a.2*b.0 - a.0*b.2, a.0*b.1 - a.1*b.0);
+        norm(c).atan2(a.0*b.0 + a.1*b.1 + a.2*b.2)
+    };
+
+    let mut state = [false; 32];
+    for active in 0..4 {
+        for passive in 0..4 {
+            if active == passive {
+                continue;
+            }
+            let k = 2 * (4*passive + active);
+            let (r_act, r_pas) = (RADII[active] / km_per_AU, RADII[passive] / km_per_AU);
+
+            // seen from the Earth, the nearer moon overlaps the farther
+            let (act, pas) = (sub(helio[active], earth), sub(helio[passive], earth));
+            let (d_act, d_pas) = (norm(act), norm(pas));
+            state[k] = d_act < d_pas && angle(act, pas) < r_act/d_act + r_pas/d_pas;
+
+            // seen from the eclipsed moon, the eclipsing moon overlaps
+            // the Sun
+            let to_act = sub(helio[active], helio[passive]);
+            let to_sun = sub((0.0, 0.0, 0.0), helio[passive]);
+            let d = norm(to_act);
+            state[k + 1] = norm(helio[active]) < norm(helio[passive])
+                && angle(to_act, to_sun) < SUN_RADIUS/km_per_AU/norm(to_sun) + (r_act + r_pas)/d;
+        }
+    }
+
+    state
+
+}
@@ -22,10 +22,13 @@ THE SOFTWARE.
 
 //! Mars
 
+use std;
 use angle;
 use planet;
 use time;
 use coords;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 /**
 Returns the equatorial coordinates of Mars's north pole for the epoch
@@ -90,6 +93,7 @@ pub fn north_pol_ecl_coords(JC: f64) -> coords::EclPoint {
 
 /// Holds Mar's ephemeris values for physical observations
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Ephemeris {
     /// Mars-centric declination of the Earth
     pub De: f64,
@@ -100,6 +104,9 @@ pub struct Ephemeris {
     pub P : f64,
     /// Angular amount of the greatest defect of illumination
     pub q : f64,
+    /// Geocentric position angle of the greatest defect of
+    /// illumination
+    pub Q : f64,
     /// Longitude of the central meridian, as seen from the Earth
     pub w : f64,
     /// Apparent diameter of Mars
@@ -140,26 +147,18 @@ pub fn ephemeris (
 
     let (l0, b0, R) = planet::heliocent_coords(&planet::Planet::Earth, JD);
 
-    let (mut l, mut b, mut r) = (0.0, 0.0, 0.0);
-    let (mut x, mut y, mut z) = (0.0, 0.0, 0.0);
-    let mut mars_earth_dist = 0.0;
-    let mut light_time = 0.0;
-
-    let mut i: u8 = 1;
-    while i <= 2 {
-
-        let (new_l, new_b, new_r) = planet::heliocent_coords(&planet::Planet::Mars, JD - light_time);
-        l = new_l; b = new_b; r = new_r;
-
-        let (new_x, new_y, new_z) = planet::geocent_ecl_rect_coords(l0, b0, R, l, b, r);
-        x = new_x; y = new_y; z = new_z;
-
-        mars_earth_dist = planet::dist_frm_ecl_rect_coords(x, y, z);
-        light_time = planet::light_time(mars_earth_dist);
-
-        i += 1;
+    let dist = |tau: f64| {
+        let (l, b, r) = planet::heliocent_coords(&planet::Planet::Mars, JD - tau);
+        let (x, y, z) = planet::geocent_ecl_rect_coords(l0, b0, R, l, b, r);
+        planet::dist_frm_ecl_rect_coords(x, y, z)
+    };
+    let light_time = planet::light_time_iter(
+        dist, planet::LIGHT_TIME_TOL, planet::LIGHT_TIME_MAX_ITERS
+    );
 
-    }
+    let (l, b, r) = planet::heliocent_coords(&planet::Planet::Mars, JD - light_time);
+    let (x, y, z) = planet::geocent_ecl_rect_coords(l0, b0, R, l, b, r);
+    let mars_earth_dist = planet::dist_frm_ecl_rect_coords(x, y, z);
 
     let (mut lambda, mut beta) = planet::ecl_coords_frm_ecl_rect_coords(x, y, z);
 
@@ -219,13 +218,512 @@ pub fn ephemeris (
     let k = planet::illum_frac_frm_dist(r, mars_earth_dist, R);
     let q = (1.0 - k) * d;
 
+    // the greatest defect lies opposite the midpoint of the bright limb
+    let sun_long = l0 + std::f64::consts::PI + nut_in_long - (20.4898 / (3600.0 * R)).to_radians();
+    let sun_eq_point = coords::EqPoint {
+        asc: coords::asc_frm_ecl(sun_long, -b0, true_oblq),
+        dec: coords::dec_frm_ecl(sun_long, -b0, true_oblq)
+    };
+    let Q = angle::limit_to_two_PI(
+        planet::pos_angle_of_bright_limb(sun_eq_point, coords::EqPoint {asc: asc1, dec: dec1})
+      + std::f64::consts::PI
+    );
+
     Ephemeris {
         De: D_e,
         Ds: D_s,
-        P: P,
-        q: q,
-        w: w,
-        d: d
+        P,
+        q,
+        Q,
+        w,
+        d
+    }
+
+}
+
+/// Represents a moon of Mars
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Moon {
+    /// Phobos
+    Phobos,
+    /// Deimos
+    Deimos
+}
+
+/**
+Computes the position of a moon of Mars with respect to Mars
+
+Each moon moves in an ellipse whose apsides and node precess uniformly
+on the moon's Laplace plane, following the elements of A. T. Sinclair
+(1989), as given in the *Explanatory Supplement to the Astronomical
+Almanac* (1992). The libration in longitude of Deimos, due to the
+Sun, is included.
+
+# Returns
+
+`(x, y, z)`
+
+* `x`, `y`, `z`: Rectangular coordinates of the moon with respect to
+                 the center of Mars, referred to the ecliptic and
+                 mean equinox of J2000.0 *| in AU*
+
+# Arguments
+
+* `JD`  : Julian (Ephemeris) day
+* `moon`: The [Moon](./enum.Moon.html)
+**/
+pub fn moon_areocent_rect_coords(JD: f64, moon: &Moon) -> (f64, f64, f64) {
+
+    let d = JD - 2441266.5;
+    let T = d / 365.25;
+
+    // semimajor axis, eccentricity, inclination to the Laplace plane,
+    // mean longitude, longitude of the periapsis and of the node, and
+    // the node and inclination of the Laplace plane on the mean
+    // equator of B1950.0
+    let (a, e, i, L, w, sigma, N, J): (f64, f64, f64, f64, f64, f64, f64, f64) = match *moon {
+        Moon::Phobos => (
+            9379.4, 0.0150, 1.1029,
+            232.412 + 1128.8445566*d + 0.001237*T*T,
+            278.96 + 0.43526*d,
+            327.90 - 0.43533*d,
+            47.386 - 0.00140*T,
+            37.271 + 0.00080*T
+        ),
+        Moon::Deimos => (
+            23459.0, 0.0004, 1.7891,
+            28.963 + 285.1618875*d - 0.274*(196.55 - 0.01801*d).to_radians().sin(),
+            111.7 + 0.01798*d,
+            240.38 - 0.01801*d,
+            46.367 - 0.00138*T,
+            36.623 + 0.00079*T
+        ),
+    };
+    let a = a / 149597870.7;
+    let (i, w, sigma) = (i.to_radians(), w.to_radians(), sigma.to_radians());
+
+    let M = angle::limit_to_two_PI(L.to_radians() - w);
+    let mut E = M;
+    for _ in 0..10 {
+        let delta = (E - e*E.sin() - M) / (1.0 - e*E.cos());
+        E -= delta;
+        if delta.abs() < 1e-12 { break; }
+    }
+    let v = 2.0 * (((1.0 + e)/(1.0 - e)).sqrt() * (E/2.0).tan()).atan();
+    let r = a * (1.0 - e*E.cos());
+
+    // in the Laplace plane, from its ascending node on the mean
+    // equator of B1950.0
+    let u = v + w - sigma;
+    let x = r * (u.cos()*sigma.cos() - u.sin()*sigma.sin()*i.cos());
+    let y = r * (u.cos()*sigma.sin() + u.sin()*sigma.cos()*i.cos());
+    let z = r * u.sin() * i.sin();
+
+    // to the mean equator of B1950.0
+    let (N, J) = (N.to_radians(), J.to_radians());
+    let y1 = y*J.cos() - z*J.sin();
+    let z1 = y*J.sin() + z*J.cos();
+    let x2 = x*N.cos() - y1*N.sin();
+    let y2 = x*N.sin() + y1*N.cos();
+
+    // to the mean equator of J2000.0
+    let x3 = 0.9999256782*x2 - 0.0111820611*y2 - 0.0048579477*z1;
+    let y3 = 0.0111820610*x2 + 0.9999374784*y2 - 0.0000271765*z1;
+    let z3 = 0.0048579479*x2 - 0.0000271474*y2 + 0.9999881997*z1;
+
+    // to the ecliptic of J2000.0
+    let oblq = 23.4392911_f64.to_radians();
+
+    (
+        x3,
+        y3*oblq.cos() + z3*oblq.sin(),
+       -y3*oblq.sin() + z3*oblq.cos()
+    )
+
+}
+
+/**
+Computes the apparent position of a moon of Mars with respect to
+Mars, as seen from the Earth
+
+# Returns
+
+`(X, Y)`
+
+The offsets of the moon from the center of Mars, projected on the
+plane of the sky and referred to the mean equator of J2000.0.
+
+* `X`: Offset towards the east *| in radians*
+* `Y`: Offset towards the north *| in radians*
+
+# Arguments
+
+* `JD`  : Julian (Ephemeris) day
+* `moon`: The [Moon](./enum.Moon.html)
+**/
+pub fn moon_apprnt_offsets(JD: f64, moon: &Moon) -> (f64, f64) {
+
+    planet::satellite_apprnt_offsets (
+        &planet::Planet::Mars, JD, |t| moon_areocent_rect_coords(t, moon)
+    )
+
+}
+
+/**
+Computes the apparent positions of Phobos and Deimos with respect to
+Mars, as seen from the Earth
+
+# Returns
+
+`[phobos, deimos]`
+
+Each element is the `(X, Y, pos_angl, sep)` of a moon, where `X` and
+`Y` are as returned by
+[`moon_apprnt_offsets()`](./fn.moon_apprnt_offsets.html), and
+
+* `pos_angl`: Position angle of the moon from the center of Mars,
+              measured from the north towards the east *| in radians*
+* `sep`     : Angular separation of the moon from the center of Mars
+              *| in radians*
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn moons(JD: f64) -> [(f64, f64, f64, f64); 2] {
+
+    let position = |moon| {
+        let (X, Y) = moon_apprnt_offsets(JD, &moon);
+        (X, Y, angle::limit_to_two_PI(X.atan2(Y)), (X*X + Y*Y).sqrt().atan())
+    };
+
+    [position(Moon::Phobos), position(Moon::Deimos)]
+
+}
+
+// Perturbations of the equation of center of Mars by the other
+// planets (amplitude in degrees, period in Julian years, phase in
+// degrees), from Allison and McEwen (2000)
+const PBS_TERMS: [(f64, f64, f64); 7] = [
+    (0.0071,  2.2353,  49.409),
+    (0.0057,  2.7543, 168.173),
+    (0.0039,  1.1177, 191.837),
+    (0.0037, 15.7866,  21.736),
+    (0.0021,  2.1354,  15.704),
+    (0.0020,  2.4694,  95.528),
+    (0.0018, 32.8493,  49.095),
+];
+
+/// Length of a mean solar day on Mars, the sol *| in days*
+pub const SOL: f64 = 1.0274912517;
+
+// Equation of center, and right ascension of the fictitious mean Sun,
+// in degrees
+fn eq_of_center_and_fms(JD: f64) -> (f64, f64) {
+
+    let d = JD - 2451545.0;
+
+    let M = (19.3871 + 0.52402073*d).to_radians();
+    let alpha_FMS = 270.3871 + 0.524038496*d;
+
+    let PBS = PBS_TERMS.iter().fold(0.0, |s, &(A, tau, phi)| {
+        s + A * (0.985626*d/tau + phi).to_radians().cos()
+    });
+
+    let v_M =
+        (10.691 + 3.0e-7*d) * M.sin()
+      + 0.623 * (2.0*M).sin()
+      + 0.050 * (3.0*M).sin()
+      + 0.005 * (4.0*M).sin()
+      + 0.0005 * (5.0*M).sin()
+      + PBS;
+
+    (v_M, alpha_FMS)
+
+}
+
+/**
+Computes the areocentric longitude of the Sun
+
+The Mars clock of Allison and McEwen (2000), as used by NASA's
+Mars24 Sunclock, is used for this and the other functions of Martian
+timekeeping.
+
+# Returns
+
+* `Ls`: Areocentric longitude of the Sun, measured from the
+        vernal equinox of Mars's northern hemisphere *| in radians*
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn areocent_solar_long(JD: f64) -> f64 {
+
+    let (v_M, alpha_FMS) = eq_of_center_and_fms(JD);
+
+    angle::limit_to_360(alpha_FMS + v_M).to_radians()
+
+}
+
+/**
+Computes the equation of time on Mars
+
+# Returns
+
+* `eq_of_time`: Equation of time, true minus mean solar time
+                *| in radians*
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn eq_of_time(JD: f64) -> f64 {
+
+    let (v_M, alpha_FMS) = eq_of_center_and_fms(JD);
+    let Ls = (alpha_FMS + v_M).to_radians();
+
+    (
+        2.861 * (2.0*Ls).sin()
+      - 0.071 * (4.0*Ls).sin()
+      + 0.002 * (6.0*Ls).sin()
+      - v_M
+    ).to_radians()
+
+}
+
+/**
+Computes the Mars Sol Date
+
+The Mars Sol Date counts sols from midnight at the prime meridian
+(the crater Airy-0) on 1873 December 29, like the Julian day counts
+days on Earth.
+
+# Returns
+
+* `MSD`: Mars Sol Date
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn sol_date(JD: f64) -> f64 {
+
+    (JD - 2451549.5) / SOL + 44796.0 - 0.00096
+
+}
+
+/**
+Computes the Mars Coordinated Time
+
+The Mars Coordinated Time is the mean solar time at the prime
+meridian of Mars.
+
+# Returns
+
+* `MTC`: Mars Coordinated Time *| in hours*. *range: 0.0 to 24.0*
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn coord_time(JD: f64) -> f64 {
+
+    24.0 * (sol_date(JD) - sol_date(JD).floor())
+
+}
+
+/**
+Computes the local mean solar time on Mars
+
+# Returns
+
+* `LMST`: Local mean solar time *| in hours*. *range: 0.0 to 24.0*
+
+# Arguments
+
+* `JD`  : Julian (Ephemeris) day
+* `long`: Areographic longitude, positive eastwards from the prime
+          meridian *| in radians*
+**/
+pub fn loc_mn_solar_time(JD: f64, long: f64) -> f64 {
+
+    angle::limit_to_24(coord_time(JD) + long.to_degrees()/15.0)
+
+}
+
+/**
+Computes the local true solar time on Mars
+
+# Returns
+
+* `LTST`: Local true solar time *| in hours*. *range: 0.0 to 24.0*
+
+# Arguments
+
+* `JD`  : Julian (Ephemeris) day
+* `long`: Areographic longitude, positive eastwards from the prime
+          meridian *| in radians*
+**/
+pub fn loc_true_solar_time(JD: f64, long: f64) -> f64 {
+
+    angle::limit_to_24(loc_mn_solar_time(JD, long) + eq_of_time(JD).to_degrees()/15.0)
+
+}
+
+/**
+Computes the number of a mission's sol
+
+A mission counts sols from local midnight at it's site. Sol counts
+start from sol 0 for Phoenix, Curiosity and Perseverance, and from
+sol 1 for the Mars Exploration Rovers.
+
+# Returns
+
+* `sol`: Number of the sol containing `JD`
+
+# Arguments
+
+* `JD`        : Julian (Ephemeris) day
+* `landing_JD`: Julian (Ephemeris) day of the landing
+* `long`      : Areographic longitude of the site, positive eastwards
+                *| in radians*
+* `first_sol` : Number of the sol of the landing
+**/
+pub fn mission_sol(JD: f64, landing_JD: f64, long: f64, first_sol: i64) -> i64 {
+
+    let loc_sol_date = |JD: f64| (sol_date(JD) + long / (2.0*std::f64::consts::PI)).floor() as i64;
+
+    loc_sol_date(JD) - loc_sol_date(landing_JD) + first_sol
+
+}
+
+/// Names of the months of the Darian calendar
+pub const DARIAN_MONTHS: [&str; 24] = [
+    "Sagittarius", "Dhanus", "Capricornus", "Makara",
+    "Aquarius", "Kumbha", "Pisces", "Mina",
+    "Aries", "Mesha", "Taurus", "Rishabha",
+    "Gemini", "Mithuna", "Cancer", "Karka",
+    "Leo", "Simha", "Virgo", "Kanya",
+    "Libra", "Tula", "Scorpius", "Vrishika"
+];
+
+// Mars Sol Date of the start of the Darian calendar, close to the
+// northern vernal equinox of 1609
+const DARIAN_EPOCH: f64 = -94129.0;
+
+// Number of sols in 500 Darian years
+const DARIAN_CYCLE: i64 = 500*668 + 296;
+
+/// Represents a date of the Darian calendar for Mars
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DarianDate {
+    /// Year
+    pub year: i64,
+    /// Month
+    ///
+    /// range: *1 - 24*
+    pub month: u8,
+    /// Decimal sol of the month
+    ///
+    /// range: *1.0 - 29.0*
+    pub decimal_sol: f64
+}
+
+/**
+Checks if a year of the Darian calendar is a leap year
+
+Leap years, of `669` sols, are the odd years, and the years
+divisible by `10` but not by `100`, or divisible by `500`. Other
+years have `668` sols.
+
+# Arguments
+
+* `year`: Year of the Darian calendar
+**/
+pub fn is_darian_leap_year(year: i64) -> bool {
+
+    (year % 2 != 0) || (year % 10 == 0 && year % 100 != 0) || year % 500 == 0
+
+}
+
+// Number of sols in a month of the Darian calendar
+fn darian_month_len(year: i64, month: u8) -> i64 {
+
+    match month {
+        6 | 12 | 18                      => 27,
+        24 if !is_darian_leap_year(year) => 27,
+        _                                => 28
+    }
+
+}
+
+/**
+Computes the date of the Darian calendar from a Mars Sol Date
+
+# Returns
+
+* `date`: The [DarianDate](./struct.DarianDate.html)
+
+# Arguments
+
+* `MSD`: Mars Sol Date
+**/
+pub fn darian_frm_sol_date(MSD: f64) -> DarianDate {
+
+    let sols = MSD - DARIAN_EPOCH;
+    let whole = sols.floor() as i64;
+
+    let cycles = whole.div_euclid(DARIAN_CYCLE);
+    let mut sol = whole.rem_euclid(DARIAN_CYCLE);
+    let mut year = 500 * cycles;
+
+    loop {
+        let len = if is_darian_leap_year(year) { 669 } else { 668 };
+        if sol < len { break; }
+        sol -= len;
+        year += 1;
+    }
+
+    let mut month = 1;
+    while sol >= darian_month_len(year, month) {
+        sol -= darian_month_len(year, month);
+        month += 1;
     }
 
+    DarianDate {
+        year,
+        month,
+        decimal_sol: (sol + 1) as f64 + (sols - sols.floor())
+    }
+
+}
+
+/**
+Computes the Mars Sol Date from a date of the Darian calendar
+
+# Returns
+
+* `MSD`: Mars Sol Date
+
+# Arguments
+
+* `date`: The [DarianDate](./struct.DarianDate.html)
+**/
+pub fn sol_date_frm_darian(date: &DarianDate) -> f64 {
+
+    let cycles = date.year.div_euclid(500);
+    let mut sols = cycles * DARIAN_CYCLE;
+
+    for year in (500 * cycles)..date.year {
+        sols += if is_darian_leap_year(year) { 669 } else { 668 };
+    }
+    for month in 1..date.month {
+        sols += darian_month_len(date.year, month);
+    }
+
+    DARIAN_EPOCH + (sols as f64) + date.decimal_sol - 1.0
+
 }
@@ -22,18 +22,39 @@ THE SOFTWARE.
 
 //! 8 Planets of the Solar System
 
+// the coefficients of the series are as published, some of them
+// near PI
+#[allow(clippy::approx_constant)]
 mod VSOPD_87;
 
 pub mod earth;
 pub mod mars;
 pub mod jupiter;
 pub mod saturn;
+pub mod uranus;
+pub mod neptune;
+pub mod grouping;
+pub mod phenomena;
+pub mod moon_phenomena;
+pub mod solar_transit;
+pub mod viewpoint;
 
 use angle;
 use coords;
+use ecliptic;
+use error;
+use interpol;
+use nutation;
+use precess;
+use strict;
 use time;
+use util;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 /// Represents a planet
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Planet {
     /// Mercury *Helped with testing General Relativity*
     Mercury,
@@ -111,7 +132,7 @@ Computes a planet's phase angle
 #[inline]
 pub fn phase_angl(r: f64, delta: f64, R: f64) -> f64 {
 
-    (r*r + delta*delta - R*R) / (2.0 * r * delta)
+    ((r*r + delta*delta - R*R) / (2.0 * r * delta)).acos()
 
 }
 
@@ -184,6 +205,37 @@ pub fn semidiameter<'a> (
 
 }
 
+/**
+Returns the ratio of the mass of the Sun to the mass of a planet
+
+# Returns
+
+* `mass_ratio`: Sun-planet mass ratio
+
+The value returned for `Planet::Earth` is the ratio of the mass of the
+Sun to the combined mass of the Earth and the Moon.
+
+Reference: [IAU 2009 System of Astronomical Constants](http://maia.usno.navy.mil/NSFA/IAU2009_consts.html)
+
+# Arguments
+
+* `planet`: Any variant of [Planet](./enum.Planet.html)
+**/
+pub fn sun_planet_mass_ratio(planet: &Planet) -> f64 {
+
+    match *planet {
+        Planet::Mercury => 6023597.400,
+        Planet::Venus   => 408523.719,
+        Planet::Earth   => 328900.560,
+        Planet::Mars    => 3098703.590,
+        Planet::Jupiter => 1047.348644,
+        Planet::Saturn  => 3497.901768,
+        Planet::Uranus  => 22902.981613,
+        Planet::Neptune => 19412.237346,
+    }
+
+}
+
 /**
 Computes a planet's orbital elements, referred to the mean equinox of
 the date
@@ -222,9 +274,9 @@ pub fn orb_elements(planet: &Planet, JD: f64) -> (f64, f64, f64, f64, f64, f64,
     let omega;
     let pi;
 
-    match planet {
+    match *planet {
 
-        &Planet::Mercury => {
+        Planet::Mercury => {
             L = 252.250906 + 149474.0722491*T + 0.0003035*TT + 0.000000018*TTT;
             a = 0.038709831;
             e = 0.20563175 + 0.000020407*T - 0.0000000283*TT + 0.00000000018*TTT;
@@ -233,7 +285,7 @@ pub fn orb_elements(planet: &Planet, JD: f64) -> (f64, f64, f64, f64, f64, f64,
             pi = 77.456119 + 1.5564776*T + 0.00029544*TT + 0.000000009*TTT;
         },
 
-        &Planet::Venus => {
+        Planet::Venus => {
             L = 181.979801 + 58519.2130302*T + 0.00031014*TT + 0.000000015*TTT;
             a = 0.72332982;
             e = 0.00677192 - 0.000047765*T + 0.0000000981*TTT + 0.00000000046*TTT;
@@ -242,7 +294,7 @@ pub fn orb_elements(planet: &Planet, JD: f64) -> (f64, f64, f64, f64, f64, f64,
             pi = 131.563703 + 1.4022288*T - 0.00107618*TT - 0.000005678*TTT;
         },
 
-        &Planet::Earth => {
+        Planet::Earth => {
             L = 100.466457 + 36000.7698278*T + 0.00030322*TT + 0.00000002*TTT;
             a = 1.000001018;
             e = 0.01670863 - 0.000042037*T - 0.0000001267*TTT + 0.00000000014*TTT;
@@ -251,7 +303,7 @@ pub fn orb_elements(planet: &Planet, JD: f64) -> (f64, f64, f64, f64, f64, f64,
             omega = 0.0
         },
 
-        &Planet::Mars => {
+        Planet::Mars => {
             L = 355.433 + 19141.6964471*T + 0.00031052*TT + 0.000000016*TTT;
             a = 1.523679342;
             e = 0.09340065 + 0.000090484*T - 0.0000000806*TTT - 0.00000000025*TTT;
@@ -260,7 +312,7 @@ pub fn orb_elements(planet: &Planet, JD: f64) -> (f64, f64, f64, f64, f64, f64,
             pi = 336.060234 + 1.8410449*T + 0.00013477*TT + 0.000000536*TTT;
         },
 
-        &Planet::Jupiter => {
+        Planet::Jupiter => {
             L = 34.351519 + 3036.3027748*T + 0.0002233*TT + 0.000000037*TTT;
             a = 5.202603209 + 0.0000001913*T;
             e = 0.04849793 + 0.000163225*T - 0.0000004714*TTT - 0.00000000201*TTT;
@@ -269,7 +321,7 @@ pub fn orb_elements(planet: &Planet, JD: f64) -> (f64, f64, f64, f64, f64, f64,
             pi = 14.331207 + 1.6126352*T + 0.00103042*TT - 0.000004464*TTT;
         },
 
-        &Planet::Saturn => {
+        Planet::Saturn => {
             L = 50.077444 + 1223.5110686*T + 0.00051908*TT - 0.00000003*TTT;
             a = 9.554909192 - 0.0000021390*T + 0.000000004*TT;
             e = 0.05554814 - 0.000346641*T - 0.0000006436*TTT + 0.0000000034*TTT;
@@ -278,7 +330,7 @@ pub fn orb_elements(planet: &Planet, JD: f64) -> (f64, f64, f64, f64, f64, f64,
             pi = 93.057237 + 1.9637613*T + 0.00083753*TT + 0.000004928*TTT;
         },
 
-        &Planet::Uranus => {
+        Planet::Uranus => {
             L = 314.055005 + 429.8640561*T + 0.0003039*TT - 0.000000026*TTT;
             a = 19.218446062 - 0.0000000372*T + 0.00000000098*TT;
             e = 0.04638122 - 0.000027293*T + 0.0000000789*TTT + 0.00000000024*TTT;
@@ -287,7 +339,7 @@ pub fn orb_elements(planet: &Planet, JD: f64) -> (f64, f64, f64, f64, f64, f64,
             pi = 173.005291 + 1.486379*T + 0.00021406*TT + 0.000000434*TTT;
         },
 
-        &Planet::Neptune => {
+        Planet::Neptune => {
             L = 304.348665 + 219.8833092*T + 0.00030882*TT + 0.000000018*TTT;
             a = 30.110386869 - 0.0000001663*T + 0.00000000069*TT;
             e = 0.00945575 + 0.000006033*T - 0.00000000005*TTT;
@@ -330,56 +382,257 @@ equinox of the date
 **/
 pub fn heliocent_coords(planet: &Planet, JD: f64) -> (f64, f64, f64) {
 
-    let VSOPD87_Terms = match planet {
-        &Planet::Mercury  => VSOPD_87::mercury::terms(),
-        &Planet::Venus    => VSOPD_87::venus::terms(),
-        &Planet::Earth    => VSOPD_87::earth::terms(),
-        &Planet::Mars     => VSOPD_87::mars::terms(),
-        &Planet::Jupiter  => VSOPD_87::jupiter::terms(),
-        &Planet::Saturn   => VSOPD_87::saturn::terms(),
-        &Planet::Uranus   => VSOPD_87::uranus::terms(),
-        &Planet::Neptune  => VSOPD_87::neptune::terms(),
-    };
+    let mut coords = [(0.0, 0.0, 0.0)];
+    sum_VSOPD87_series(&VSOPD87_terms(planet), &[JD], &mut coords);
+
+    coords[0]
+
+}
+
+/**
+Computes a planet's heliocentric coordinates, referred to the mean
+equinox of the date, for many instants
+
+This gives the same results as `heliocent_coords()`, but loads the
+terms of the series once, and evaluates each term for a chunk of the
+instants in a tight loop the compiler can vectorize. With the `rayon`
+feature, the chunks are evaluated in parallel.
+
+# Returns
+
+* `coords`: `(long, lat, rad_vec)` for each instant, as returned by
+            `heliocent_coords()`
+
+# Arguments
+
+* `planet`: Any variant of [Planet](./enum.Planet.html)
+* `JD`    : Julian (Ephemeris) days
+**/
+pub fn heliocent_coords_many(planet: &Planet, JD: &[f64]) -> Vec<(f64, f64, f64)> {
+
+    let terms = VSOPD87_terms(planet);
+    let mut coords = vec![(0.0, 0.0, 0.0); JD.len()];
+
+    util::for_each_chunk(JD, &mut coords, |JD, coords| sum_VSOPD87_series(&terms, JD, coords));
+
+    coords
+
+}
+
+fn VSOPD87_terms(planet: &Planet) -> Vec<Vec<Vec<[f64; 3]>>> {
+
+    match *planet {
+        Planet::Mercury => VSOPD_87::mercury::terms(),
+        Planet::Venus   => VSOPD_87::venus::terms(),
+        Planet::Earth   => VSOPD_87::earth::terms(),
+        Planet::Mars    => VSOPD_87::mars::terms(),
+        Planet::Jupiter => VSOPD_87::jupiter::terms(),
+        Planet::Saturn  => VSOPD_87::saturn::terms(),
+        Planet::Uranus  => VSOPD_87::uranus::terms(),
+        Planet::Neptune => VSOPD_87::neptune::terms(),
+    }
+
+}
 
-    let mut L = 0.0;
-    let mut B = 0.0;
-    let mut R = 0.0;
+// Sums the series of L, B and R for each of the instants, which are
+// at most a chunk of util::BATCH_CHUNK
+fn sum_VSOPD87_series(terms: &[Vec<Vec<[f64; 3]>>], JD: &[f64], coords: &mut [(f64, f64, f64)]) {
 
-    let JM = time::julian_mill(JD);
+    let n_JD = JD.len();
+    let mut JM = [0.0; util::BATCH_CHUNK];
+    let mut T = [0.0; util::BATCH_CHUNK];
+    let mut y = [0.0; util::BATCH_CHUNK];
+    let (JM, T, y) = (&mut JM[..n_JD], &mut T[..n_JD], &mut y[..n_JD]);
 
-    let mut n: u8 = 1; // L, then B, then R
-    for i in VSOPD87_Terms.iter() { // L or B or R
+    for (JM, &JD) in JM.iter_mut().zip(JD.iter()) {
+        *JM = time::julian_mill(JD);
+    }
+
+    for (n, i) in terms.iter().enumerate() { // L or B or R
 
-        let mut T = 1.0;
-        let mut y = 0.0;
+        for T in T.iter_mut() {
+            *T = 1.0;
+        }
 
         for j in i.iter() { // T or T**2 or T**3 or ...
 
-            for k in j.iter() { // add [A * cos(B + C*T)]
-                y += k[0] * (k[1] + k[2]*JM).cos();
+            for y in y.iter_mut() {
+                *y = 0.0;
             }
 
-            if n == 1 {
-                L += y * T;
-            } else if n == 2 {
-                B += y * T;
-            } else if n == 3 {
-                R += y * T;
+            for k in j.iter() { // add [A * cos(B + C*T)]
+                for (y, &JM) in y.iter_mut().zip(JM.iter()) {
+                    *y += k[0] * strict::series_cos(k[1] + k[2]*JM);
+                }
             }
 
-            y = 0.0;
-            T *= JM;
+            for (t, coords) in coords.iter_mut().enumerate() {
+                let sum = match n {
+                    0 => &mut coords.0,
+                    1 => &mut coords.1,
+                    _ => &mut coords.2
+                };
+                *sum += y[t] * T[t];
+                T[t] *= JM[t];
+            }
 
         }
 
-        n += 1;
+    }
 
+    for coords in coords.iter_mut() {
+        coords.0 = angle::limit_to_two_PI(coords.0);
+        coords.1 = angle::limit_to_two_PI(coords.1);
     }
 
-    L = angle::limit_to_two_PI(L);
-    B = angle::limit_to_two_PI(B);
+}
 
-    (L, B, R)
+/**
+Computes a planet's heliocentric rectangular coordinates, referred to
+the ecliptic and mean equinox of J2000.0
+
+# Returns
+
+`(x, y, z)`
+
+* `x`, `y`, `z`: Heliocentric rectangular coordinates *| in AU*
+
+# Arguments
+
+* `planet`: Any variant of [Planet](./enum.Planet.html)
+* `JD`    : Julian (Ephemeris) day
+**/
+pub fn heliocent_ecl_rect_coords_J2000(planet: &Planet, JD: f64) -> (f64, f64, f64) {
+
+    let (L, B, R) = heliocent_coords(planet, JD);
+    let (L, B) = precess::precess_ecl_coords(L, B, JD, 2451545.0);
+
+    (R*B.cos()*L.cos(), R*B.cos()*L.sin(), R*B.sin())
+
+}
+
+// Computes the planet-Sun, planet-Earth and Sun-Earth distances of a
+// planet, in AU, neglecting the light-time
+pub(crate) fn dists(planet: &Planet, JD: f64) -> (f64, f64, f64) {
+
+    let pos = heliocent_ecl_rect_coords_J2000(planet, JD);
+    let earth = heliocent_ecl_rect_coords_J2000(&Planet::Earth, JD);
+    let (x, y, z) = (pos.0 - earth.0, pos.1 - earth.1, pos.2 - earth.2);
+
+    (
+        (pos.0*pos.0 + pos.1*pos.1 + pos.2*pos.2).sqrt(),
+        (x*x + y*y + z*z).sqrt(),
+        (earth.0*earth.0 + earth.1*earth.1 + earth.2*earth.2).sqrt()
+    )
+
+}
+
+// Computes the time at which the light seen from a planet at JD left it,
+// and the heliocentric rectangular coordinates (ecliptic of J2000.0)
+// of the planet at that time and of the Earth at JD
+fn light_time_corrected_J2000(planet: &Planet, JD: f64) -> (f64, (f64, f64, f64), (f64, f64, f64)) {
+
+    let earth = heliocent_ecl_rect_coords_J2000(&Planet::Earth, JD);
+
+    let tau = light_time_iter(|tau| {
+        let pos = heliocent_ecl_rect_coords_J2000(planet, JD - tau);
+        let (x, y, z) = (pos.0 - earth.0, pos.1 - earth.1, pos.2 - earth.2);
+        (x*x + y*y + z*z).sqrt()
+    }, LIGHT_TIME_TOL, LIGHT_TIME_MAX_ITERS);
+
+    let t = JD - tau;
+    let pos = heliocent_ecl_rect_coords_J2000(planet, t);
+
+    (t, pos, earth)
+
+}
+
+// Computes the apparent offsets (towards the east and the north, in
+// radians) of a satellite from the center of it's planet, given a
+// function for it's planetocentric position in the ecliptic of J2000.0
+fn satellite_apprnt_offsets<F> (
+
+    planet        : &Planet,
+    JD            : f64,
+    planetocent   : F
+
+) -> (f64, f64) where F: Fn(f64) -> (f64, f64, f64) {
+
+    let (t, pos, earth) = light_time_corrected_J2000(planet, JD);
+    let (x, y, z) = planetocent(t);
+
+    let oblq = ecliptic::mn_oblq_IAU(2451545.0);
+    let eq_coords = |(x, y, z): (f64, f64, f64)| {
+        let u = y*oblq.cos() - z*oblq.sin();
+        let v = y*oblq.sin() + z*oblq.cos();
+        (u.atan2(x), v.atan2((x*x + u*u).sqrt()))
+    };
+
+    let (asc0, dec0) = eq_coords((pos.0 - earth.0, pos.1 - earth.1, pos.2 - earth.2));
+    let (asc, dec) = eq_coords((pos.0 + x - earth.0, pos.1 + y - earth.1, pos.2 + z - earth.2));
+
+    let d_asc = asc - asc0;
+    let D = dec.sin()*dec0.sin() + dec.cos()*dec0.cos()*d_asc.cos();
+
+    (
+        dec.cos() * d_asc.sin() / D,
+        (dec.sin()*dec0.cos() - dec.cos()*dec0.sin()*d_asc.cos()) / D
+    )
+
+}
+
+// Computes the physical ephemeris of a planet, referred to the mean
+// equator of J2000.0, given the right ascension and declination of its
+// north pole and a function for the angle of its prime meridian from
+// the ascending node of its equator on the Earth's equator, at a
+// light-time corrected Julian day. Returns the planetocentric
+// declinations of the Earth and the Sun, the position angle of the
+// north pole, the west longitude of the central meridian (all in
+// radians) and the planet-Earth distance (in AU).
+fn physical_ephemeris<F> (
+
+    planet         : &Planet,
+    JD             : f64,
+    pole_asc       : f64,
+    pole_dec       : f64,
+    prime_meridian : F
+
+) -> (f64, f64, f64, f64, f64) where F: Fn(f64) -> f64 {
+
+    let (t, pos, earth) = light_time_corrected_J2000(planet, JD);
+
+    let oblq = ecliptic::mn_oblq_IAU(2451545.0);
+    let to_eq = |(x, y, z): (f64, f64, f64)| (
+        x,
+        y*oblq.cos() - z*oblq.sin(),
+        y*oblq.sin() + z*oblq.cos()
+    );
+    let dot = |a: (f64, f64, f64), b: (f64, f64, f64)| a.0*b.0 + a.1*b.1 + a.2*b.2;
+
+    let geocent = to_eq((pos.0 - earth.0, pos.1 - earth.1, pos.2 - earth.2));
+    let heliocent = to_eq(pos);
+    let dist = dot(geocent, geocent).sqrt();
+    let r = dot(heliocent, heliocent).sqrt();
+
+    let (sin_a0, cos_a0) = pole_asc.sin_cos();
+    let (sin_d0, cos_d0) = pole_dec.sin_cos();
+    let pole = (cos_d0*cos_a0, cos_d0*sin_a0, sin_d0);
+    let node = (-sin_a0, cos_a0, 0.0);
+    let third = (-sin_d0*cos_a0, -sin_d0*sin_a0, cos_d0);
+
+    let D_e = (-dot(geocent, pole) / dist).asin();
+    let D_s = (-dot(heliocent, pole) / r).asin();
+
+    let asc = geocent.1.atan2(geocent.0);
+    let dec = (geocent.2 / dist).asin();
+    let P = (cos_d0 * (pole_asc - asc).sin()).atan2 (
+        sin_d0*dec.cos() - cos_d0*dec.sin()*(pole_asc - asc).cos()
+    );
+
+    let earth_long = (-dot(geocent, third)).atan2(-dot(geocent, node));
+    let w = angle::limit_to_two_PI(prime_meridian(t) - earth_long);
+
+    (D_e, D_s, P, w, dist)
 
 }
 
@@ -390,20 +643,69 @@ fn light_time(dist: f64) -> f64 {
 
 }
 
+/// Tolerance of the light-time iterations in this module *| in days*
+pub const LIGHT_TIME_TOL: f64 = 1e-9;
+
+/// Maximum number of light-time iterations in this module
+pub const LIGHT_TIME_MAX_ITERS: u32 = 10;
+
+/**
+Computes the light-time from a body to an observer, by iteration
+
+Starting from no light-time, the distance of the body is found at
+the time the light left it, until the light-time changes by no more
+than the tolerance, or the maximum number of iterations is reached.
+The change in the last iteration is reported, with the `trace`
+feature, as the residual of it's [Event](../trace/struct.Event.html).
+
+# Returns
+
+* `light_time`: Light-time from the body to the observer *| in days*
+
+# Arguments
+
+* `dist`     : Function giving the distance of the body from the
+               observer *| in AU*, for a light-time *| in days*
+* `tol`      : Tolerance of the light-time *| in days*
+* `max_iters`: Maximum number of iterations
+**/
+pub fn light_time_iter<F>(dist: F, tol: f64, max_iters: u32) -> f64 where F: Fn(f64) -> f64 {
+
+    let mut tau = 0.0;
+    let mut residual = f64::INFINITY;
+    let mut iters = 0;
+
+    while iters < max_iters {
+        let next = light_time(dist(tau));
+        residual = (next - tau).abs();
+        tau = next;
+        iters += 1;
+
+        if residual <= tol {
+            trace_event!("planet::light_time_iter", Converged, iters, residual);
+            return tau;
+        }
+    }
+
+    trace_event!("planet::light_time_iter", Truncated, iters, residual);
+
+    tau
+
+}
+
 /**
 Computes a planet's geocentric, geometric ecliptic position,
 uncorrected for light-time
 
 # Returns
 
-`(ecl_long, ecl_lat, rad_vec, light_time)`
+`(ecl_point, rad_vec, light_time)`
 
-* `ecl_long`: Geometric longitude of the planet *| in radians*
-* `ecl_lat`: Geometric latitude of the planet *| in radians*
-* `rad_vec`: Geometric radius vector of the planet *| in AU*
+* `ecl_point` : Geometric ecliptic point of the planet *| in radians*
+* `rad_vec`   : Geometric radius vector of the planet *| in AU*
 * `light_time`: Time taken by light to travel to the Earth
-                    from the planet's current position, in days of
-                    dynamical time
+                from the planet's current position, in days of
+                dynamical time
 
 The coordinates returned here refer to the true position
 of the planet at the time of interest, and therefore
@@ -413,25 +715,42 @@ are not corrected for the effect of light-time.
 
 * `L0`: Heliocentric longitude of the Earth *| in radians*
 * `B0`: Heliocentric latitude of the Earth *| in radians*
-* `R0`: Heliocentric radius vector of the Earth *| in radians*
+* `R0`: Heliocentric radius vector of the Earth *| in AU*
 * `L` : Heliocentric longitude of the planet *| in radians*
 * `B` : Heliocentric latitude of the planet *| in radians*
-* `R` : Heliocentric radius vector of the planet *| in radians*
+* `R` : Heliocentric radius vector of the planet *| in AU*
 **/
-pub fn geocent_geomet_ecl_coords (
+pub fn geocent_geomet_ecl_point (
 
     L0 : f64, B0 : f64, R0 : f64,
     L  : f64, B  : f64, R  : f64
 
-) -> (f64, f64, f64, f64) {
+) -> (coords::EclPoint, f64, f64) {
 
     let (x, y, z) = geocent_ecl_rect_coords(L0, B0, R0, L, B, R);
 
-    let (lambda, beta) = ecl_coords_frm_ecl_rect_coords(x, y, z);
+    let (long, lat) = ecl_coords_frm_ecl_rect_coords(x, y, z);
     let planet_earth_dist = dist_frm_ecl_rect_coords(x, y, z);
     let light_time = light_time(planet_earth_dist);
 
-    (lambda, beta, planet_earth_dist, light_time)
+    (coords::EclPoint { long, lat }, planet_earth_dist, light_time)
+
+}
+
+/// Computes a planet's geocentric, geometric ecliptic position,
+/// uncorrected for light-time, as
+/// `(ecl_long, ecl_lat, rad_vec, light_time)`
+#[deprecated(note = "use `geocent_geomet_ecl_point()`, which returns a point")]
+pub fn geocent_geomet_ecl_coords (
+
+    L0 : f64, B0 : f64, R0 : f64,
+    L  : f64, B  : f64, R  : f64
+
+) -> (f64, f64, f64, f64) {
+
+    let (point, rad_vec, light_time) = geocent_geomet_ecl_point(L0, B0, R0, L, B, R);
+
+    (point.long, point.lat, rad_vec, light_time)
 
 }
 
@@ -533,48 +852,40 @@ coordinates for the effect of light-time.
 * `planet`: Any variant of [Planet](./enum.Planet.html)
 * `JD`    : Julian (Ephemeris) day
 **/
-#[allow(unused_variables)]
 pub fn geocent_apprnt_ecl_coords(planet: &Planet, JD: f64) -> (coords::EclPoint, f64) {
 
     let (L0, B0, R0) = heliocent_coords(&Planet::Earth, JD);
 
-    let (L1, B1, R1) = heliocent_coords(&planet, JD);
-    let (l1, b1, r1, t) = geocent_geomet_ecl_coords(L0, B0, R0, L1, B1, R1);
-
-    let (L2, B2, R2) = heliocent_coords(&planet, JD - t);
-    let (l2, b2, r2, t2) = geocent_geomet_ecl_coords(L0, B0, R0, L2, B2, R2);
-
-    let ecl_point = coords::EclPoint {
-        long:l2,
-        lat: b2
+    let geomet = |tau: f64| {
+        let (L, B, R) = heliocent_coords(planet, JD - tau);
+        geocent_geomet_ecl_point(L0, B0, R0, L, B, R)
     };
+    let tau = light_time_iter(|tau| geomet(tau).1, LIGHT_TIME_TOL, LIGHT_TIME_MAX_ITERS);
+
+    let (ecl_point, r2, _) = geomet(tau);
 
     (ecl_point, r2)
 
 }
 
 /**
-Computes a planet's geocentric ecliptic coordinates converted to the
-FK5 system
+Computes a planet's geocentric ecliptic point converted to the FK5
+system
 
 # Returns
 
-`(ecl_long_FK5, ecl_lat_FK5)`
-
-* `ecl_long_FK5`: Ecliptic longitude of the planet, converted to the
-                  FK5 system *| in radians*
-* `ecl_lat_FK5` : Ecliptic latitude of the planet, converted to the
-                  FK5 system *| in radians*
+* `ecl_point_FK5`: Ecliptic point of the planet, converted to the
+                   FK5 system *| in radians*
 
 # Arguments
 
-* `JD`      : Julian (Ephemeris) day
-* `ecl_long`: Ecliptic longitude of the planet on `JD` referred to
-              the mean equinox of the date *| in radians*
-* `ecl_lat` : Ecliptic latitude of the planet on `JD`, referred to
-              the mean equinox of the date *| in radians*
+* `JD`       : Julian (Ephemeris) day
+* `ecl_point`: Ecliptic point of the planet on `JD`, referred to the
+               mean equinox of the date *| in radians*
 **/
-pub fn ecl_coords_to_FK5(JD: f64, ecl_long: f64, ecl_lat: f64) -> (f64, f64) {
+pub fn ecl_point_to_FK5(JD: f64, ecl_point: &coords::EclPoint) -> coords::EclPoint {
+
+    let (ecl_long, ecl_lat) = (ecl_point.long, ecl_point.lat);
 
     let JC = time::julian_cent(JD);
     let lambda1 = ecl_long - JC*(1.397 + JC*0.00031).to_radians();
@@ -583,10 +894,21 @@ pub fn ecl_coords_to_FK5(JD: f64, ecl_long: f64, ecl_lat: f64) -> (f64, f64) {
     let ecl_long_correction = - angle::deg_frm_dms(0, 0, 0.09033).to_radians()
                               + x*(lambda1.cos() + lambda1.sin())*ecl_lat.tan();
 
-    (
-        ecl_long + ecl_long_correction,
-        ecl_lat  + x*(lambda1.cos() - lambda1.sin())
-    )
+    coords::EclPoint {
+        long: ecl_long + ecl_long_correction,
+        lat:  ecl_lat  + x*(lambda1.cos() - lambda1.sin())
+    }
+
+}
+
+/// Computes a planet's geocentric ecliptic coordinates converted to
+/// the FK5 system, as `(ecl_long_FK5, ecl_lat_FK5)`
+#[deprecated(note = "use `ecl_point_to_FK5()`, which takes and returns a point")]
+pub fn ecl_coords_to_FK5(JD: f64, ecl_long: f64, ecl_lat: f64) -> (f64, f64) {
+
+    let point = ecl_point_to_FK5(JD, &coords::EclPoint { long: ecl_long, lat: ecl_lat });
+
+    (point.long, point.lat)
 
 }
 
@@ -672,17 +994,18 @@ pub fn apprnt_mag_muller<'a> (
 ) -> Result<f64, &'a str> {
 
     let x = 5.0 * (r*delta).log10();
+    let i = i.to_degrees();
 
     match *planet {
         Planet::Mercury => Ok( x + 1.16 + (i - 50.0)*(0.02838 + (i - 50.0)*0.000102) ),
         Planet::Venus   => Ok( x - 4.0 + i*(0.01322 + i*i*0.0000004247) ),
         Planet::Earth   => {
-            return Err("Planet::Earth was passed to the function planet::apprnt_mag_muller()");
+            Err("Planet::Earth was passed to the function planet::apprnt_mag_muller()")
         },
         Planet::Mars    => Ok(x - 1.3 + i*0.01486),
         Planet::Jupiter => Ok(x - 8.93),
         Planet::Saturn  => {
-            return Err("Planet::Saturn was passed to the function planet::apprnt_mag_muller(). Use the function planet::saturn::apprnt_mag_muller() instead.");
+            Err("Planet::Saturn was passed to the function planet::apprnt_mag_muller(). Use the function planet::saturn::apprnt_mag_muller() instead.")
         },
         Planet::Uranus  => Ok(x - 6.85),
         Planet::Neptune => Ok(x - 7.05),
@@ -715,20 +1038,247 @@ pub fn apprnt_mag_84<'a> (
 ) -> Result<f64, &'a str> {
 
     let x = 5.0 * (r*delta).log10();
+    let i = i.to_degrees();
 
     match *planet {
         Planet::Mercury => Ok( x - 0.42 + i*(0.0380 - i*(0.000273 - i*0.00000200)) ),
         Planet::Venus   => Ok( x - 4.40 + i*(0.0009 + i*(0.000239 - i*0.00000065)) ),
         Planet::Earth   => {
-            return Err("Planet::Earth was passed to the function planet::apprnt_mag_84()");
+            Err("Planet::Earth was passed to the function planet::apprnt_mag_84()")
         },
         Planet::Mars    => Ok( x - 1.52 + i*0.016 ),
         Planet::Jupiter => Ok( x - 9.4 + i*0.005 ),
         Planet::Saturn  => {
-            return Err("Planet::Saturn was passed to the function planet::apprnt_mag_84(). Use the function planet::saturn::apprnt_mag_84() instead.");
+            Err("Planet::Saturn was passed to the function planet::apprnt_mag_84(). Use the function planet::saturn::apprnt_mag_84() instead.")
         },
         Planet::Uranus  => Ok( x - 7.19 ),
         Planet::Neptune => Ok( x - 6.87 ),
     }
 
 }
+
+/// Represents a formula for the apparent magnitude of a planet
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MagFormula {
+    /// G. Muller's formulae
+    Muller,
+    /// The Astronomical Almanac's formulae adopted in 1984
+    Almanac84
+}
+
+/**
+Computes a planet's phase angle on a Julian day
+
+# Returns
+
+* `phase_angl`: Phase angle of the planet *| in radians*
+
+# Arguments
+
+* `planet`: Any variant of [Planet](./enum.Planet.html) other than
+            `Planet::Earth`
+* `JD`    : Julian (Ephemeris) day
+**/
+pub fn geocent_phase_angl(planet: &Planet, JD: f64) -> f64 {
+
+    let (r, delta, R) = dists(planet, JD);
+
+    phase_angl(r, delta, R)
+
+}
+
+/**
+Computes the illuminated fraction of a planet's disk on a Julian day
+
+# Returns
+
+* `illum_frac`: Illuminated fraction of the planet's disk
+
+# Arguments
+
+* `planet`: Any variant of [Planet](./enum.Planet.html) other than
+            `Planet::Earth`
+* `JD`    : Julian (Ephemeris) day
+**/
+pub fn geocent_illum_frac(planet: &Planet, JD: f64) -> f64 {
+
+    illum_frac_frm_phase_angl(geocent_phase_angl(planet, JD))
+
+}
+
+/**
+Computes a planet's apparent magnitude on a Julian day
+
+The magnitude of Saturn includes the contribution of its ring.
+
+# Returns
+
+* `Ok(app_mag)`: Apparent magnitude of the planet, or
+* `Err(error)` : `Error::InvalidBody` for the Earth
+
+# Arguments
+
+* `planet` : Any variant of [Planet](./enum.Planet.html) other than
+             `Planet::Earth`
+* `JD`     : Julian (Ephemeris) day
+* `formula`: The [MagFormula](./enum.MagFormula.html)
+**/
+pub fn apprnt_mag (
+
+    planet  : &Planet,
+    JD      : f64,
+    formula : &MagFormula
+
+) -> Result<f64, error::Error> {
+
+    let (r, delta, R) = match *planet {
+        Planet::Earth => return Err(error::Error::InvalidBody),
+        _ => dists(planet, JD)
+    };
+
+    match (planet, formula) {
+        (&Planet::Saturn, _) => {
+            let (nut_in_long, nut_in_oblq) = nutation::nutation(JD);
+            let true_oblq = ecliptic::mn_oblq_IAU(JD) + nut_in_oblq;
+            let ring = saturn::ring::elements(JD, nut_in_long, true_oblq);
+
+            Ok(match *formula {
+                MagFormula::Muller    => saturn::apprnt_mag_muller(delta, r, ring.deltaU, ring.B),
+                MagFormula::Almanac84 => saturn::apprnt_mag_84(delta, r, ring.deltaU, ring.B)
+            })
+        },
+        (_, &MagFormula::Muller)    => apprnt_mag_muller(planet, phase_angl(r, delta, R), delta, r),
+        (_, &MagFormula::Almanac84) => apprnt_mag_84(planet, phase_angl(r, delta, R), delta, r)
+    }.map_err(|_| error::Error::InvalidBody)
+
+}
+
+/// Represents an apsis of a planet's orbit
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Apsis {
+    /// Perihelion, the point of the orbit closest to the Sun
+    Perihelion,
+    /// Aphelion, the point of the orbit farthest from the Sun
+    Aphelion
+}
+
+/**
+Computes the time of passage of a planet through an apsis of its
+orbit, and the planet's heliocentric distance then
+
+The mean orbits of *Meeus* (Astronomical Algorithms, chapter 38) are
+used for Mercury, Venus, Earth and Mars, and for the Earth the time is
+corrected for the motion of the Earth about the barycenter of the
+Earth-Moon system. The perturbations of the outer planets by one
+another shift their apsides by up to months for Jupiter and Saturn,
+and by years for Uranus and Neptune, so for these the time from the
+mean orbit is corrected by searching for the extremum of the radius
+vector from VSOP87 about it.
+
+# Returns
+
+`(JD, rad_vec)`
+
+* `JD`     : Julian (Ephemeris) day of the passage through `apsis`
+             that is closest to `year`
+* `rad_vec`: Heliocentric radius vector of the planet at the
+             apsis *| in AU*
+
+# Arguments
+
+* `planet`: Any variant of [Planet](./enum.Planet.html)
+* `year`  : Decimal year of interest, close to the apsis
+* `apsis` : The [Apsis](./enum.Apsis.html)
+**/
+pub fn apsis(planet: &Planet, year: f64, apsis: &Apsis) -> (f64, f64) {
+
+    // JD of the perihelion for k = 0, rate of k per year, year for
+    // k = 0, period, and quadratic term
+    let (JD0, k_rate, year0, period, quad) = match *planet {
+        Planet::Mercury => (2451590.257, 4.15201, 2000.12, 87.96934963,  0.0),
+        Planet::Venus   => (2451738.233, 1.62549, 2000.53, 224.7008188, -0.0000000327),
+        Planet::Earth   => (2451547.507, 0.99997, 2000.01, 365.2596358,  0.0000000156),
+        Planet::Mars    => (2452195.026, 0.53166, 2001.78, 686.9957857, -0.0000001187),
+        Planet::Jupiter => (2455636.936, 0.08430, 2011.20, 4332.897065,  0.0001367),
+        Planet::Saturn  => (2452830.12,  0.03393, 2003.52, 10764.21676,  0.000827),
+        Planet::Uranus  => (2470213.5,   0.01190, 2051.1,  30694.8767,  -0.00541),
+        Planet::Neptune => (2468895.1,   0.00607, 2047.5,  60190.33,     0.03429),
+    };
+
+    let k = k_rate * (year - year0);
+    let k = match *apsis {
+        Apsis::Perihelion => k.round(),
+        Apsis::Aphelion   => (k - 0.5).round() + 0.5
+    };
+
+    let mut JD = JD0 + period*k + quad*k*k;
+
+    match *planet {
+        Planet::Earth => {
+            let A1 = (328.41 + 132.788585*k).to_radians();
+            let A2 = (316.13 + 584.903153*k).to_radians();
+            let A3 = (346.20 + 450.380738*k).to_radians();
+            let A4 = (136.95 + 659.306737*k).to_radians();
+            let A5 = (249.52 + 329.653368*k).to_radians();
+
+            JD += match *apsis {
+                Apsis::Perihelion =>
+                    1.278*A1.sin() - 0.055*A2.sin() - 0.091*A3.sin()
+                  - 0.056*A4.sin() - 0.045*A5.sin(),
+                Apsis::Aphelion   =>
+                  - 1.352*A1.sin() + 0.061*A2.sin() + 0.062*A3.sin()
+                  + 0.029*A4.sin() + 0.031*A5.sin()
+            };
+        },
+        Planet::Jupiter | Planet::Saturn | Planet::Uranus | Planet::Neptune => {
+            JD = apsis_frm_VSOP87(planet, JD, 0.15 * period, apsis);
+        },
+        _ => {}
+    }
+
+    (JD, heliocent_coords(planet, JD).2)
+
+}
+
+/*
+
+Finds the extremum of the radius vector of a planet that lies within
+half_window days of JD, sampling the radius vector and refining the
+most extreme sample by repeated three-point interpolation
+
+*/
+fn apsis_frm_VSOP87(planet: &Planet, JD: f64, half_window: f64, apsis: &Apsis) -> f64 {
+
+    let sign = match *apsis {
+        Apsis::Perihelion => -1.0,
+        Apsis::Aphelion   =>  1.0
+    };
+
+    let n = 200;
+    let mut step = 2.0 * half_window / (n as f64);
+    let JDs: Vec<f64> = (0..(n + 1)).map(|i| JD - half_window + (i as f64)*step).collect();
+    let rad_vecs = heliocent_coords_many(planet, &JDs);
+
+    let mut best = 0;
+    for (i, coords) in rad_vecs.iter().enumerate() {
+        if sign*coords.2 > sign*rad_vecs[best].2 {
+            best = i;
+        }
+    }
+
+    let mut JD = JDs[best];
+    while step > 0.0001 {
+        let (_, n) = interpol::extremum_three_values (
+            heliocent_coords(planet, JD - step).2,
+            heliocent_coords(planet, JD).2,
+            heliocent_coords(planet, JD + step).2
+        );
+        JD += n.clamp(-1.0, 1.0) * step;
+        step /= 4.0;
+    }
+
+    JD
+
+}
@@ -0,0 +1,336 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Phenomena of the natural satellites of the planets
+
+/*
+
+The phenomena are computed from the planetocentric positions of a
+satellite, given by any theory of it's motion, and from the figure of
+the planet. The planet's shadow is taken as a cylinder, which is good
+enough for the outer planets, whose umbras are much longer than the
+orbits of their satellites. The satellite is taken as a point, so the
+times are those of the passage of it's center across the limb of the
+planet or of the shadow.
+
+*/
+
+use consts;
+use error;
+use numerics;
+use planet;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Holds the figure and orientation of a planet, for computing the
+/// phenomena of its satellites
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Primary {
+    /// The planet
+    pub planet: planet::Planet,
+    /// Equatorial radius of the planet *| in kilometers*
+    pub eq_radius: f64,
+    /// Polar radius of the planet *| in kilometers*
+    pub polar_radius: f64,
+    /// Right ascension of the planet's north pole, referred to the
+    /// mean equator and equinox of J2000.0 *| in radians*
+    pub pole_asc: f64,
+    /// Declination of the planet's north pole, referred to the mean
+    /// equator and equinox of J2000.0 *| in radians*
+    pub pole_dec: f64
+}
+
+/// Represents a phenomenon of a satellite
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PhenomenonType {
+    /// The satellite passes in front of the planet's disk
+    Transit,
+    /// The satellite is hidden behind the planet's disk
+    Occultation,
+    /// The satellite is in the planet's shadow
+    Eclipse,
+    /// The satellite's shadow falls on the planet's disk
+    ShadowTransit
+}
+
+/// Holds a phenomenon of a satellite
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Phenomenon {
+    /// The [PhenomenonType](./enum.PhenomenonType.html)
+    pub kind: PhenomenonType,
+    /// Julian (Ephemeris) day of the start of the phenomenon
+    pub start: f64,
+    /// Julian (Ephemeris) day of the end of the phenomenon
+    pub end: f64
+}
+
+/**
+Returns the figure and orientation of a planet
+
+The radii and the north pole are those given by the IAU Working Group
+on Cartographic Coordinates and Rotational Elements (2009), the slow
+motion of the poles being neglected.
+
+# Returns
+
+* `primary`: [Primary](./struct.Primary.html) for the planet
+
+# Arguments
+
+* `planet`: Any variant of [Planet](../enum.Planet.html)
+**/
+pub fn primary(planet: planet::Planet) -> Primary {
+
+    let (eq_radius, polar_radius, pole_asc, pole_dec): (f64, f64, f64, f64) = match planet {
+        planet::Planet::Mercury => (2440.53, 2438.26, 281.0097, 61.4143),
+        planet::Planet::Venus   => (6051.8,  6051.8,  272.76,   67.16),
+        planet::Planet::Earth   => (6378.14, 6356.75,   0.0,    90.0),
+        planet::Planet::Mars    => (3396.19, 3376.20, 317.68143, 52.88650),
+        planet::Planet::Jupiter => (71492.0, 66854.0, 268.056595, 64.495303),
+        planet::Planet::Saturn  => (60268.0, 54364.0,  40.589,   83.537),
+        planet::Planet::Uranus  => (25559.0, 24973.0, 257.311,  -15.175),
+        planet::Planet::Neptune => (24764.0, 24341.0, 299.36,    43.46),
+    };
+
+    Primary {
+        planet,
+        eq_radius,
+        polar_radius,
+        pole_asc : pole_asc.to_radians(),
+        pole_dec : pole_dec.to_radians()
+    }
+
+}
+
+/**
+Checks whether a satellite is in a phenomenon at a given time
+
+# Arguments
+
+* `primary`    : The satellite's [Primary](./struct.Primary.html)
+* `kind`       : The [PhenomenonType](./enum.PhenomenonType.html)
+* `JD`         : Julian (Ephemeris) day
+* `planetocent`: Function giving the satellite's position with respect
+                 to the center of the planet, referred to the ecliptic
+                 and mean equinox of J2000.0 *| in AU*, for a Julian
+                 (Ephemeris) day, eg: [uranicent_rect_coords()](../uranus/moons/fn.uranicent_rect_coords.html)
+**/
+pub fn is_in_phenomenon<F> (
+
+    primary     : &Primary,
+    kind        : &PhenomenonType,
+    JD          : f64,
+    planetocent : &F
+
+) -> bool where F: Fn(f64) -> (f64, f64, f64) {
+
+    let state = state(primary, JD, planetocent);
+
+    state[index(kind)]
+
+}
+
+/**
+Computes the phenomena of a satellite in an interval of time
+
+The phenomena are sampled at every `step`, and each start and end
+found is refined by bisection. Phenomena in progress at `JD_start` or
+`JD_end` are clipped to the interval.
+
+# Returns
+
+* `Ok(phenomena)`: [Phenomena](./struct.Phenomenon.html) ordered by
+                   their start, or
+* `Err(error)`   : `Error::NotPositive` if `step` isn't positive, or
+                   `Error::NotFinite`
+
+# Arguments
+
+* `primary`    : The satellite's [Primary](./struct.Primary.html)
+* `planetocent`: Function giving the satellite's position with respect
+                 to the center of the planet, referred to the ecliptic
+                 and mean equinox of J2000.0 *| in AU*, for a Julian
+                 (Ephemeris) day
+* `JD_start`   : Julian (Ephemeris) day at the start of the search
+* `JD_end`     : Julian (Ephemeris) day at the end of the search
+* `step`       : Sampling interval, positive *| in days*. Should be
+                 well below the duration of the shortest phenomenon.
+**/
+pub fn phenomena<F> (
+
+    primary     : &Primary,
+    planetocent : F,
+    JD_start    : f64,
+    JD_end      : f64,
+    step        : f64
+
+) -> Result<Vec<Phenomenon>, error::Error> where F: Fn(f64) -> (f64, f64, f64) {
+
+    error::check_positive(step)?;
+    if !JD_start.is_finite() || !JD_end.is_finite() {
+        return Err(error::Error::NotFinite);
+    }
+
+    let mut phenomena = Vec::new();
+
+    let mut JD_prev = JD_start;
+    let mut prev = state(primary, JD_prev, &planetocent);
+    let mut starts = [None; 4];
+    for (k, start) in starts.iter_mut().enumerate() {
+        if prev[k] { *start = Some(JD_start); }
+    }
+
+    while JD_prev < JD_end {
+        let JD_next = (JD_prev + step).min(JD_end);
+        let next = state(primary, JD_next, &planetocent);
+
+        for k in 0..4 {
+            if next[k] == prev[k] {
+                continue;
+            }
+
+            // -1 before the change of state, and 1 after it
+            let changed = |JD: f64| if state(primary, JD, &planetocent)[k] == prev[k] { -1.0 } else { 1.0 };
+            let JD = numerics::bisection(changed, JD_prev, JD_next, 1e-6).unwrap_or(JD_next);
+
+            if next[k] {
+                starts[k] = Some(JD);
+            } else if let Some(start) = starts[k].take() {
+                phenomena.push(Phenomenon { kind: KINDS[k], start, end: JD });
+            }
+        }
+
+        JD_prev = JD_next;
+        prev = next;
+    }
+
+    for (k, start) in starts.iter().enumerate() {
+        if let Some(start) = *start {
+            phenomena.push(Phenomenon { kind: KINDS[k], start, end: JD_end });
+        }
+    }
+
+    phenomena.sort_by(|a, b| a.start.partial_cmp(&b.start).unwrap());
+
+    Ok(phenomena)
+
+}
+
+const KINDS: [PhenomenonType; 4] = [
+    PhenomenonType::Transit,
+    PhenomenonType::Occultation,
+    PhenomenonType::Eclipse,
+    PhenomenonType::ShadowTransit
+];
+
+fn index(kind: &PhenomenonType) -> usize {
+
+    match *kind {
+        PhenomenonType::Transit       => 0,
+        PhenomenonType::Occultation   => 1,
+        PhenomenonType::Eclipse       => 2,
+        PhenomenonType::ShadowTransit => 3,
+    }
+
+}
+
+// Computes whether the satellite is in transit, occultation, eclipse
+// and shadow transit, in that order
+fn state<F>(primary: &Primary, JD: f64, planetocent: &F) -> [bool; 4]
+    where F: Fn(f64) -> (f64, f64, f64) {
+
+    let (t, pos, earth) = planet::light_time_corrected_J2000(&primary.planet, JD);
+    let sat = planetocent(t);
+
+    let km_per_AU = consts::ASTRONOMICAL_UNIT / 1000.0;
+    let a = primary.eq_radius / km_per_AU;
+    let b = primary.polar_radius / km_per_AU;
+
+    let oblq = 23.4392911_f64.to_radians();
+    let (x, y, z) = (
+        primary.pole_dec.cos() * primary.pole_asc.cos(),
+        primary.pole_dec.cos() * primary.pole_asc.sin(),
+        primary.pole_dec.sin()
+    );
+    let pole = (x, y*oblq.cos() + z*oblq.sin(), -y*oblq.sin() + z*oblq.cos());
+
+    let (earth_on_disk, earth_behind) = on_disk (
+        sat, (pos.0 - earth.0, pos.1 - earth.1, pos.2 - earth.2), pole, a, b
+    );
+    let (sun_on_disk, sun_behind) = on_disk(sat, pos, pole, a, b);
+
+    [
+        earth_on_disk && !earth_behind,
+        earth_on_disk && earth_behind,
+        sun_on_disk && sun_behind,
+        sun_on_disk && !sun_behind
+    ]
+
+}
+
+// Checks whether a satellite projects onto the disk of the planet, as
+// seen along the direction u, and whether it lies behind the planet
+fn on_disk (
+
+    sat  : (f64, f64, f64),
+    u    : (f64, f64, f64),
+    pole : (f64, f64, f64),
+    a    : f64,
+    b    : f64
+
+) -> (bool, bool) {
+
+    let u_n = dot(u, u).sqrt();
+    let u = (u.0/u_n, u.1/u_n, u.2/u_n);
+
+    let d = dot(sat, u);
+    let p = (sat.0 - d*u.0, sat.1 - d*u.1, sat.2 - d*u.2);
+
+    // the apparent disk is an ellipse whose minor axis lies along the
+    // projection of the pole
+    let sin_D = dot(pole, u);
+    let k = (pole.0 - sin_D*u.0, pole.1 - sin_D*u.1, pole.2 - sin_D*u.2);
+    let k_n = dot(k, k).sqrt();
+
+    let (x_sqr, y_sqr) = if k_n > 1e-12 {
+        let y = dot(p, k) / k_n;
+        (dot(p, p) - y*y, y*y)
+    } else {
+        (dot(p, p), 0.0)
+    };
+
+    let cos_sqr_D = 1.0 - sin_D*sin_D;
+    let b_apprnt_sqr = b*b*cos_sqr_D + a*a*(1.0 - cos_sqr_D);
+
+    (x_sqr/(a*a) + y_sqr/b_apprnt_sqr < 1.0, d > 0.0)
+
+}
+
+#[inline]
+fn dot(a: (f64, f64, f64), b: (f64, f64, f64)) -> f64 {
+
+    a.0*b.0 + a.1*b.1 + a.2*b.2
+
+}
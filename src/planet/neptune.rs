@@ -0,0 +1,203 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Neptune
+
+use consts;
+use planet;
+use planet::moon_phenomena;
+use time;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Holds Neptune's ephemeris values for physical observations
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ephemeris {
+    /// Neptune-centric declination of the Earth, or the latitude of
+    /// the sub-Earth point
+    pub De: f64,
+    /// Neptune-centric declination of the Sun, or the latitude of the
+    /// sub-solar point
+    pub Ds: f64,
+    /// Geocentric position angle of Neptune's northern rotation pole,
+    /// or also called the position angle of the axis
+    pub P : f64,
+    /// Longitude of the central meridian, as seen from the Earth
+    pub w : f64,
+    /// Apparent equatorial diameter of Neptune
+    pub d : f64,
+}
+
+/**
+Computes quantites used in the ephemeris for physical observations of
+Neptune
+
+The north pole and the rotation of Neptune, including the slow
+precession of the pole, are those adopted by the IAU Working Group on
+Cartographic Coordinates and Rotational Elements (2009). The longitude
+of the central meridian is measured towards the west, and the position
+angle of the axis is referred to the mean equator of J2000.0, like the
+position of Triton.
+
+# Returns
+
+* `ephemeris`: Neptune's ephemeris. *All angles are in radians*
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn ephemeris(JD: f64) -> Ephemeris {
+
+    let N = (357.85 + 52.316*time::julian_cent(JD)).to_radians();
+    let pole_asc = (299.36 + 0.70*N.sin()).to_radians();
+    let pole_dec = (43.46 - 0.51*N.cos()).to_radians();
+
+    let (D_e, D_s, P, w, dist) = planet::physical_ephemeris (
+        &planet::Planet::Neptune, JD, pole_asc, pole_dec,
+        |t| (249.978 + 541.1397757*(t - 2451545.0) - 0.48*N.sin()).to_radians()
+    );
+
+    let eq_radius = moon_phenomena::primary(planet::Planet::Neptune).eq_radius;
+
+    Ephemeris {
+        De: D_e,
+        Ds: D_s,
+        P,
+        w,
+        d : 2.0 * (eq_radius * 1000.0 / (dist * consts::ASTRONOMICAL_UNIT)).asin()
+    }
+
+}
+
+/**
+Computes the position of Triton with respect to Neptune
+
+Triton's orbit is modelled as a circle inclined to Neptune's invariable
+plane, whose node on that plane regresses uniformly, following A. W.
+Harris (1984), as given in the *Explanatory Supplement to the
+Astronomical Almanac* (1992). The error of this model is a few
+hundredths of an arcsecond, as seen from the Earth.
+
+# Returns
+
+`(x, y, z)`
+
+* `x`, `y`, `z`: Rectangular coordinates of Triton with respect to
+                 the center of Neptune, referred to the ecliptic and
+                 mean equinox of J2000.0 *| in AU*
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn triton_neptunicent_rect_coords(JD: f64) -> (f64, f64, f64) {
+
+    let t = JD - 2433282.5;
+
+    let a = 0.0023683;
+    let L = (200.913 + 61.2572637*t).to_radians();
+    let gamma = 158.996_f64.to_radians();
+    let sigma = (151.401 + 0.57806*t/365.25).to_radians();
+
+    // in the invariable plane, from it's ascending node on the mean
+    // equator of B1950.0
+    let u = L - sigma;
+    let x = a * (u.cos()*sigma.cos() - u.sin()*sigma.sin()*gamma.cos());
+    let y = a * (u.cos()*sigma.sin() + u.sin()*sigma.cos()*gamma.cos());
+    let z = a * u.sin() * gamma.sin();
+
+    // to the mean equator of B1950.0, the pole of the invariable plane
+    // being at right ascension 298.72 and declination 42.63 degrees
+    let N = (298.72_f64 + 90.0).to_radians();
+    let J = (90.0_f64 - 42.63).to_radians();
+    let y1 = y*J.cos() - z*J.sin();
+    let z1 = y*J.sin() + z*J.cos();
+    let x2 = x*N.cos() - y1*N.sin();
+    let y2 = x*N.sin() + y1*N.cos();
+
+    // to the mean equator of J2000.0
+    let x3 = 0.9999256782*x2 - 0.0111820611*y2 - 0.0048579477*z1;
+    let y3 = 0.0111820610*x2 + 0.9999374784*y2 - 0.0000271765*z1;
+    let z3 = 0.0048579479*x2 - 0.0000271474*y2 + 0.9999881997*z1;
+
+    // to the ecliptic of J2000.0
+    let oblq = 23.4392911_f64.to_radians();
+
+    (
+        x3,
+        y3*oblq.cos() + z3*oblq.sin(),
+       -y3*oblq.sin() + z3*oblq.cos()
+    )
+
+}
+
+/**
+Computes the apparent position of Triton with respect to Neptune, as
+seen from the Earth
+
+# Returns
+
+`(X, Y)`
+
+The offsets of Triton from the center of Neptune, projected on the
+plane of the sky and referred to the mean equator of J2000.0.
+
+* `X`: Offset towards the east *| in radians*
+* `Y`: Offset towards the north *| in radians*
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn triton_apprnt_offsets(JD: f64) -> (f64, f64) {
+
+    planet::satellite_apprnt_offsets (
+        &planet::Planet::Neptune, JD, triton_neptunicent_rect_coords
+    )
+
+}
+
+/**
+Computes the apparent magnitude of Triton
+
+The magnitude at unit distances from the Sun and the Earth is that
+of the *Explanatory Supplement to the Astronomical Almanac* (1992).
+The variation with phase angle, which never exceeds `2` degrees for
+Neptune, is neglected.
+
+# Returns
+
+* `apprnt_mag`: Apparent magnitude of Triton
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn triton_apprnt_mag(JD: f64) -> f64 {
+
+    let (r, delta, _) = planet::dists(&planet::Planet::Neptune, JD);
+
+    -1.24 + 5.0*(r*delta).log10()
+
+}
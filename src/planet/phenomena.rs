@@ -0,0 +1,490 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Conjunctions, oppositions, greatest elongations and stations of the planets,
+//! and conjunctions of any two bodies
+
+/*
+
+The time of an event is first approximated with the mean values of
+*Meeus* (Astronomical Algorithms, table 36.A), and is then found from
+the geocentric apparent positions of the planet and the Sun computed
+with VSOP87, by searching near the mean time for the event and
+refining it by bisection. The result is accurate to within a minute or
+so, which is better than with the periodic terms of *Meeus*.
+
+Conjunctions and oppositions are in ecliptic longitude, and
+elongations are the angular distances of the planets from the Sun.
+
+Conjunctions of any two bodies, or appulses, are instead the least
+angular distances between the bodies, found by sampling the distance
+and refining each minimum by repeated three-point interpolation.
+
+*/
+
+use aberr;
+use angle;
+use body::Body;
+use coords;
+use error;
+use interpol;
+use numerics;
+use planet;
+use std;
+use sun;
+use transit;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Represents a type of planetary phenomenon
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EventType {
+    /// Inferior conjunction of Mercury or Venus, when the planet passes
+    /// between the Earth and the Sun
+    InferiorConjunction,
+    /// Superior conjunction of Mercury or Venus, when the planet passes
+    /// beyond the Sun
+    SuperiorConjunction,
+    /// Conjunction of an outer planet with the Sun
+    Conjunction,
+    /// Opposition of an outer planet to the Sun
+    Opposition,
+    /// Greatest eastern elongation of Mercury or Venus, when it's an
+    /// evening star
+    GreatestEastElongation,
+    /// Greatest western elongation of Mercury or Venus, when it's a
+    /// morning star
+    GreatestWestElongation,
+    /// Station at which the planet's motion in longitude turns
+    /// retrograde
+    RetrogradeStation,
+    /// Station at which the planet's motion in longitude turns direct
+    DirectStation
+}
+
+/// Holds a planetary phenomenon
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Event {
+    /// The type of the event
+    pub event_type: EventType,
+    /// Julian (Ephemeris) day of the event
+    pub JD: f64,
+    /// Elongation of the planet from the Sun at the event *| in
+    /// radians*
+    pub elong: f64
+}
+
+/**
+Computes the time of a phenomenon of a planet
+
+# Returns
+
+* `Ok(event)` : The [Event](./struct.Event.html) of type `event_type`
+                that is closest to `JD`, or
+* `Err(error)`: `Error::InvalidBody` for the Earth, or for a planet
+                that doesn't have events of type `event_type`, such
+                as Venus for oppositions
+
+Stations are those of the retrograde motion about the inferior
+conjunction or opposition that is closest to `JD`.
+
+# Arguments
+
+* `planet`    : Any variant of [Planet](../enum.Planet.html) other
+                than `Planet::Earth`
+* `event_type`: The [EventType](./enum.EventType.html)
+* `JD`        : Julian (Ephemeris) day near the event
+**/
+pub fn event (
+
+    planet     : &planet::Planet,
+    event_type : &EventType,
+    JD         : f64
+
+) -> Result<Event, error::Error> {
+
+    let inner = match *planet {
+        planet::Planet::Mercury | planet::Planet::Venus => true,
+        planet::Planet::Earth => return Err(error::Error::InvalidBody),
+        _ => false
+    };
+
+    let conj_or_opp = match (*event_type, inner) {
+        (EventType::InferiorConjunction, true)
+      | (EventType::GreatestEastElongation, true)
+      | (EventType::GreatestWestElongation, true)
+      | (EventType::RetrogradeStation, true)
+      | (EventType::DirectStation, true)
+      | (EventType::Opposition, false)
+      | (EventType::RetrogradeStation, false)
+      | (EventType::DirectStation, false)      => EventType::InferiorConjunction,
+        (EventType::SuperiorConjunction, true)
+      | (EventType::Conjunction, false)        => EventType::SuperiorConjunction,
+        _ => return Err(error::Error::InvalidBody)
+    };
+
+    let (A, B) = mean_event(planet, &conj_or_opp);
+
+    // the greatest elongations are about a quarter of the orbit of
+    // the planet from the inferior conjunction, as seen from the Sun
+    let elong_offset = if inner {
+        let a = match *planet {
+            planet::Planet::Mercury => 0.387098_f64,
+            _                       => 0.72333
+        };
+        B * a.acos() / angle::TWO_PI
+    } else {
+        0.0
+    };
+    let offset = match *event_type {
+        EventType::GreatestEastElongation => elong_offset,
+        EventType::GreatestWestElongation => -elong_offset,
+        _                                 => 0.0
+    };
+
+    let k = ((JD + offset - A) / B).round();
+    let JD_mean = A + B*k;
+
+    // the conjunction or opposition, in longitude
+    let target = if !inner && conj_or_opp == EventType::InferiorConjunction {
+        std::f64::consts::PI
+    } else {
+        0.0
+    };
+    let JD_conj_opp = zero_crossing(|JD| wrap(long_diff(planet, JD) - target), JD_mean, B / 4.0);
+
+    let JD_event = match *event_type {
+        EventType::GreatestEastElongation => max_in_range (
+            |JD| elong(planet, JD), JD_conj_opp - 2.0*elong_offset, JD_conj_opp
+        ),
+        EventType::GreatestWestElongation => max_in_range (
+            |JD| elong(planet, JD), JD_conj_opp, JD_conj_opp + 2.0*elong_offset
+        ),
+        EventType::RetrogradeStation => numerics::brent (
+            |JD| long_rate(planet, JD), JD_conj_opp - B/4.0, JD_conj_opp, 1e-6
+        ).unwrap_or(JD_conj_opp),
+        EventType::DirectStation => numerics::brent (
+            |JD| long_rate(planet, JD), JD_conj_opp, JD_conj_opp + B/4.0, 1e-6
+        ).unwrap_or(JD_conj_opp),
+        _ => JD_conj_opp
+    };
+
+    Ok(Event {
+        event_type: *event_type,
+        JD: JD_event,
+        elong: elong(planet, JD_event)
+    })
+
+}
+
+/// Holds a conjunction of two bodies
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Conjunction {
+    /// Julian (Ephemeris) day of the least separation
+    pub JD: f64,
+    /// Least separation of the bodies *| in radians*
+    pub sepr: f64,
+    /// Position angle of the second body with respect to the
+    /// first, measured from the north towards the east
+    /// *| in radians*
+    pub pos_angl: f64
+}
+
+/**
+Finds the conjunctions of two bodies
+
+A conjunction here is an instant of least angular separation of the
+bodies, as seen from the Earth's center, in their apparent places.
+
+# Returns
+
+* `Ok(conjunctions)`: The [Conjunction](./struct.Conjunction.html)s
+                      with a separation of at most `max_sepr`, in the
+                      order of time, or
+* `Err(error)`      : `Error::InvalidBody` for the Earth, or
+                      `Error::DateOutOfRange` for Pluto outside the
+                      years 1885 to 2099 of its theory
+
+# Arguments
+
+* `body_a`  : The first [Body](../../body/enum.Body.html)
+* `body_b`  : The second [Body](../../body/enum.Body.html)
+* `JD_start`: Julian (Ephemeris) day to start the search from
+* `JD_end`  : Julian (Ephemeris) day to end the search at
+* `max_sepr`: Greatest separation of the conjunctions to find
+              *| in radians*
+**/
+pub fn conjunctions (
+
+    body_a   : &Body,
+    body_b   : &Body,
+    JD_start : f64,
+    JD_end   : f64,
+    max_sepr : f64
+
+) -> Result<Vec<Conjunction>, error::Error> {
+
+    // half the squared chord between the bodies, which varies
+    // smoothly through the least separation
+    let half_chord_sqr = |JD: f64| -> Result<f64, error::Error> {
+        let (a, b) = (body_eq_point(body_a, JD)?, body_eq_point(body_b, JD)?);
+        Ok(1.0 - a.anglr_sepr(&b).cos())
+    };
+
+    // the Moon moves by a few degrees in a quarter of a day
+    let step = match (body_a, body_b) {
+        (&Body::Moon, _) | (_, &Body::Moon) => 0.25,
+        _                                   => 1.0
+    };
+    let n = ((JD_end - JD_start) / step).ceil() as usize;
+    let max_half_chord_sqr = 1.0 - max_sepr.cos();
+
+    let mut conjunctions = Vec::new();
+    let mut y = [
+        half_chord_sqr(JD_start)?,
+        half_chord_sqr(JD_start + step)?,
+        0.0
+    ];
+    for i in 2..(n + 1) {
+        let JD = JD_start + (i as f64)*step;
+        y[2] = half_chord_sqr(JD)?;
+
+        let is_least = y[1] <= y[0] && y[1] < y[2];
+        if is_least && least_value(y[0], y[1], y[2]) <= 2.0*max_half_chord_sqr {
+            let JD_conj = least_value_time(&half_chord_sqr, JD - step, step)?;
+            if JD_conj >= JD_start && JD_conj <= JD_end {
+                let (a, b) = (body_eq_point(body_a, JD_conj)?, body_eq_point(body_b, JD_conj)?);
+                let sepr = a.anglr_sepr(&b);
+                if sepr <= max_sepr {
+                    conjunctions.push(Conjunction {
+                        JD: JD_conj,
+                        sepr,
+                        pos_angl: angle::pos_angl_rad(
+                            angle::Rad(a.asc), angle::Rad(a.dec), angle::Rad(b.asc), angle::Rad(b.dec)
+                        ).0
+                    });
+                }
+            }
+        }
+
+        y[0] = y[1];
+        y[1] = y[2];
+    }
+
+    Ok(conjunctions)
+
+}
+
+// The geocentric apparent equatorial point of a body, referred to the
+// true equator and equinox of the date
+fn body_eq_point(body: &Body, JD: f64) -> Result<coords::EqPoint, error::Error> {
+
+    transit::apprnt_eq_point(body, JD).map(|(eq_point, _)| eq_point)
+
+}
+
+// The least value of a function through three equally spaced values
+fn least_value(y1: f64, y2: f64, y3: f64) -> f64 {
+
+    let c = y1 + y3 - 2.0*y2;
+    let n = if c > 0.0 { ((y1 - y3) / (2.0 * c)).clamp(-1.0, 1.0) } else { 0.0 };
+
+    interpol::three_values(y1, y2, y3, n)
+
+}
+
+// The instant of the least value of a function near JD, found by
+// fitting a parabola through three values, moving to its vertex, and
+// repeating with a shorter interval
+fn least_value_time<F>(f: &F, mut JD: f64, mut step: f64) -> Result<f64, error::Error>
+    where F: Fn(f64) -> Result<f64, error::Error>
+{
+    let mut iters = 0;
+    while step > 1e-6 {
+        let (y1, y2, y3) = (f(JD - step)?, f(JD)?, f(JD + step)?);
+        let c = y1 + y3 - 2.0*y2;
+        let n = if c > 0.0 {
+            ((y1 - y3) / (2.0 * c)).clamp(-1.0, 1.0)
+        } else if y1 < y3 {
+            -1.0
+        } else {
+            1.0
+        };
+
+        // a vertex inside the interval needs a shorter one next,
+        // while one at its edge moves the search on
+        JD += n * step;
+        if n.abs() < 1.0 {
+            step *= 0.25;
+        }
+        iters += 1;
+    }
+    trace_event!("planet::phenomena::least_value_time", Converged, iters, step);
+
+    Ok(JD)
+}
+
+// The mean values (A, B) of Meeus, from which the Julian (Ephemeris)
+// day of the mean event number k is A + kB, for inferior conjunctions
+// and oppositions (InferiorConjunction), or superior conjunctions and
+// conjunctions (SuperiorConjunction)
+fn mean_event(planet: &planet::Planet, event_type: &EventType) -> (f64, f64) {
+
+    let inferior = *event_type == EventType::InferiorConjunction;
+
+    match *planet {
+        planet::Planet::Mercury => (if inferior { 2451612.023 } else { 2451554.084 }, 115.8774771),
+        planet::Planet::Venus   => (if inferior { 2451996.706 } else { 2451704.746 }, 583.921361),
+        planet::Planet::Mars    => (if inferior { 2452097.382 } else { 2451707.414 }, 779.936104),
+        planet::Planet::Jupiter => (if inferior { 2451870.628 } else { 2451671.186 }, 398.884046),
+        planet::Planet::Saturn  => (if inferior { 2451870.170 } else { 2451681.124 }, 378.091904),
+        planet::Planet::Uranus  => (if inferior { 2451764.317 } else { 2451579.489 }, 369.656035),
+        planet::Planet::Neptune => (if inferior { 2451753.122 } else { 2451569.379 }, 367.486703),
+        planet::Planet::Earth   => (0.0, 1.0)
+    }
+
+}
+
+// The apparent ecliptic points of the planet and the Sun, referred to
+// the mean equinox of the date, which is enough for their differences
+fn points(planet: &planet::Planet, JD: f64) -> (coords::EclPoint, coords::EclPoint) {
+
+    let (sun_point, R) = sun::geocent_ecl_pos(JD);
+    let coords::EclPoint { long: sun_long, lat: sun_lat } = sun::ecl_point_to_FK5(JD, &sun_point);
+
+    let (planet_point, _) = planet::geocent_apprnt_ecl_coords(planet, JD);
+    let coords::EclPoint { long, lat } = planet::ecl_point_to_FK5(JD, &planet_point);
+
+    // the annual aberration of the planet, neglecting the eccentricity
+    // of the Earth's orbit
+    let k = angle::deg_frm_dms(0, 0, 20.49552).to_radians();
+    let long = long - k*(sun_long - long).cos()/lat.cos();
+    let lat = lat - k*lat.sin()*(sun_long - long).sin();
+
+    (
+        coords::EclPoint { long, lat },
+        coords::EclPoint { long: sun_long + aberr::sol_aberr(R), lat: sun_lat }
+    )
+
+}
+
+#[inline]
+fn wrap(angl: f64) -> f64 {
+
+    angl - angle::TWO_PI*(angl / angle::TWO_PI).round()
+
+}
+
+#[inline]
+fn long_diff(planet: &planet::Planet, JD: f64) -> f64 {
+
+    let (planet_point, sun_point) = points(planet, JD);
+
+    wrap(planet_point.long - sun_point.long)
+
+}
+
+#[inline]
+fn elong(planet: &planet::Planet, JD: f64) -> f64 {
+
+    let (planet_point, sun_point) = points(planet, JD);
+
+    planet_point.anglr_sepr(&sun_point)
+
+}
+
+// The rate of change of the planet's geocentric longitude, in radians
+// per day
+fn long_rate(planet: &planet::Planet, JD: f64) -> f64 {
+
+    let h = 0.01;
+    let (before, _) = planet::geocent_apprnt_ecl_coords(planet, JD - h);
+    let (after, _) = planet::geocent_apprnt_ecl_coords(planet, JD + h);
+
+    wrap(after.long - before.long) / (2.0 * h)
+
+}
+
+// Finds the zero of a function, whose values wrap around at ±π, that
+// is closest to JD_mean and within half_range of it
+fn zero_crossing<F>(f: F, JD_mean: f64, half_range: f64) -> f64 where F: Fn(f64) -> f64 {
+
+    let n = 40;
+    let step = 2.0 * half_range / (n as f64);
+
+    let mut closest: Option<(f64, f64)> = None;
+    let mut JD1 = JD_mean - half_range;
+    let mut f1 = f(JD1);
+    for _ in 0..n {
+        let JD2 = JD1 + step;
+        let f2 = f(JD2);
+        if f1.signum() != f2.signum() && f1.abs() < 1.0 && f2.abs() < 1.0 {
+            let is_closer = closest.is_none_or (
+                |(a, b)| (JD1 + JD2 - 2.0*JD_mean).abs() < (a + b - 2.0*JD_mean).abs()
+            );
+            if is_closer {
+                closest = Some((JD1, JD2));
+            }
+        }
+        JD1 = JD2;
+        f1 = f2;
+    }
+
+    match closest {
+        Some((a, b)) => numerics::bisection(f, a, b, 1e-6).unwrap_or(JD_mean),
+        None         => JD_mean
+    }
+
+}
+
+// Finds the maximum of a function that has a single maximum between
+// JD1 and JD2, by golden section search
+fn max_in_range<F>(f: F, mut JD1: f64, mut JD2: f64) -> f64 where F: Fn(f64) -> f64 {
+
+    let r = (5.0_f64.sqrt() - 1.0) / 2.0;
+    let mut a = JD2 - r*(JD2 - JD1);
+    let mut b = JD1 + r*(JD2 - JD1);
+    let mut fa = f(a);
+    let mut fb = f(b);
+
+    while JD2 - JD1 > 1e-5 {
+        if fa < fb {
+            JD1 = a;
+            a = b;
+            fa = fb;
+            b = JD1 + r*(JD2 - JD1);
+            fb = f(b);
+        } else {
+            JD2 = b;
+            b = a;
+            fb = fa;
+            a = JD2 - r*(JD2 - JD1);
+            fa = f(a);
+        }
+    }
+
+    (JD1 + JD2) / 2.0
+
+}
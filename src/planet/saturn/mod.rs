@@ -23,6 +23,10 @@ THE SOFTWARE.
 //! Saturn
 
 use angle;
+use ecliptic;
+use nutation;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 pub mod moon;
 pub mod ring;
@@ -47,7 +51,7 @@ pub fn apprnt_mag_muller(delta: f64, r: f64, delU: f64, B: f64) -> f64 {
 
     - 8.68
     + 5.0*(r*delta).log10()
-    + 0.044*delU.abs()
+    + 0.044*delU.abs().to_degrees()
     - 2.6*B.abs().sin()
     + 1.25*B.sin().powi(2)
 
@@ -74,7 +78,7 @@ pub fn apprnt_mag_84(delta: f64, r: f64, delU: f64, B: f64) -> f64 {
 
     - 8.88
     + 5.0*(r*delta).log10()
-    + 0.044*delU.abs()
+    + 0.044*delU.abs().to_degrees()
     - 2.6*B.abs().sin()
     + 1.25*B.sin().powi(2)
 
@@ -133,3 +137,30 @@ pub fn eq_semidiameter(saturn_earth_dist: f64) -> f64 {
     equatorial_unit_semidiameter() / saturn_earth_dist
 
 }
+
+/**
+Computes the ephemeris of the ring system of Saturn
+
+This is `ring::elements()`, with the nutation in longitude and the
+true obliquity of the ecliptic computed for the day.
+
+# Returns
+
+* `elements`: The [Elements](./ring/struct.Elements.html) of the ring
+              system, with the inclinations `B` and `B1` of the Earth
+              and the Sun to it, the position angle `P` of its axis,
+              and the major and minor axes `a` and `b` of the outer
+              edge of the outer ring. *All angles are in radians*
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn ring_ephemeris(JD: f64) -> ring::Elements {
+
+    let (nut_in_long, nut_in_oblq) = nutation::nutation(JD);
+    let true_oblq = ecliptic::mn_oblq_IAU(JD) + nut_in_oblq;
+
+    ring::elements(JD, nut_in_long, true_oblq)
+
+}
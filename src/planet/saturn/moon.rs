@@ -22,11 +22,17 @@ THE SOFTWARE.
 
 //! Eight moons of Saturn
 
+use consts;
+use error;
 use planet;
+use planet::moon_phenomena;
 use precess;
 use time;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 /// Represents a moon of Saturn
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Moon {
     /// Mimas
     Mimas,
@@ -98,18 +104,110 @@ pub fn apprnt_rect_coords(JD: f64, moon: &Moon) -> (f64, f64, f64) {
     info.beta0 = beta0;
     info.delta = saturn_earth_dist;
 
-    let (lambda_j, gamma_j, Omega_j, r_j) = match *moon {
-        Moon::Mimas     => Mimas(&info),
-        Moon::Enceladus => Enceladus(&info),
-        Moon::Tethys    => Tethys(&info),
-        Moon::Dione     => Dione(&info),
-        Moon::Rhea      => Rhea(&info),
-        Moon::Titan     => Titan(&info),
-        Moon::Hyperion  => Hyperion(&info),
-        Moon::Iapetus   => Iapetus(&info),
-    };
+    let (lambda_j, gamma_j, Omega_j, r_j) = orbit(moon, &info);
+
+    XYZ(lambda_j, gamma_j, Omega_j, r_j, &info, moon)
+
+}
+
+/**
+Computes the position of a moon of Saturn with respect to Saturn
+
+The position follows from the same theory as
+[`apprnt_rect_coords()`](./fn.apprnt_rect_coords.html), whose orbits
+are referred to the ecliptic and equinox of B1950.0, and can be given
+to the routines of [moon_phenomena](../../moon_phenomena/index.html).
+
+# Returns
+
+`(x, y, z)`
+
+* `x`, `y`, `z`: Rectangular coordinates of the moon with respect to
+                 the center of Saturn, referred to the ecliptic and
+                 mean equinox of J2000.0 *| in AU*
+
+# Arguments
+
+* `JD`  : Julian (Ephemeris) day
+* `moon`: The [Moon](./enum.Moon.html)
+**/
+pub fn saturnicent_rect_coords(JD: f64, moon: &Moon) -> (f64, f64, f64) {
 
-    XYZ(lambda_j, gamma_j, Omega_j, r_j, &info, &moon)
+    let info = create_info_struct(JD);
+
+    let (lambda_j, gamma_j, Omega_j, r_j) = orbit(moon, &info);
+
+    let u = lambda_j - Omega_j;
+    let w = Omega_j - 168.8112_f64.to_radians();
+    let X_j = r_j*(u.cos()*w.cos() - u.sin()*gamma_j.cos()*w.sin());
+    let Y_j = r_j*(u.sin()*w.cos()*gamma_j.cos() + u.cos()*w.sin());
+    let Z_j = r_j*u.sin()*gamma_j.sin();
+
+    // from Saturn's equator to the ecliptic of B1950.0
+    let A1 = X_j;
+    let B1 = info.c1*Y_j - info.s1*Z_j;
+    let C1 = info.s1*Y_j + info.c1*Z_j;
+    let A2 = info.c2*A1 - info.s2*B1;
+    let B2 = info.s2*A1 + info.c2*B1;
+
+    let (long, lat) = precess::precess_ecl_coords(
+        B2.atan2(A2), C1.atan2(A2.hypot(B2)), 2433282.423, 2451545.0
+    );
+    let r = r_j * THEORY_RADIUS / (consts::ASTRONOMICAL_UNIT / 1000.0);
+
+    (r*lat.cos()*long.cos(), r*lat.cos()*long.sin(), r*lat.sin())
+
+}
+
+/**
+Computes the eclipses, occultations, transits and shadow transits of
+a moon of Saturn in an interval of time
+
+The planet's rings are ignored, so a moon hidden by the rings alone,
+or darkened by their shadow alone, isn't reported.
+
+# Returns
+
+* `Ok(phenomena)`: [Phenomena](../../moon_phenomena/struct.Phenomenon.html)
+                   of the moon, ordered by their start, or
+* `Err(error)`   : `Error::NotPositive` if `step` isn't positive, or
+                   `Error::NotFinite`
+
+# Arguments
+
+* `moon`    : The [Moon](./enum.Moon.html)
+* `JD_start`: Julian (Ephemeris) day at the start of the search
+* `JD_end`  : Julian (Ephemeris) day at the end of the search
+* `step`    : Sampling interval, positive *| in days*, such as 0.01
+**/
+pub fn phenomena(moon: &Moon, JD_start: f64, JD_end: f64, step: f64) -> Result<Vec<moon_phenomena::Phenomenon>, error::Error> {
+
+    moon_phenomena::phenomena (
+        &moon_phenomena::primary(planet::Planet::Saturn),
+        |JD| saturnicent_rect_coords(JD, moon),
+        JD_start,
+        JD_end,
+        step
+    )
+
+}
+
+// Equatorial radius of Saturn, the unit of the radii of the orbits of
+// the theory *| in kilometers*
+const THEORY_RADIUS: f64 = 60330.0;
+
+fn orbit(moon: &Moon, info: &Info) -> (f64, f64, f64, f64) {
+
+    match *moon {
+        Moon::Mimas     => Mimas(info),
+        Moon::Enceladus => Enceladus(info),
+        Moon::Tethys    => Tethys(info),
+        Moon::Dione     => Dione(info),
+        Moon::Rhea      => Rhea(info),
+        Moon::Titan     => Titan(info),
+        Moon::Hyperion  => Hyperion(info),
+        Moon::Iapetus   => Iapetus(info),
+    }
 
 }
 
@@ -308,7 +406,7 @@ fn Rhea(info: &Info) -> (f64, f64, f64, f64) {
     let Omega = (168.8034 + 0.736936*N.sin() + 0.041*info.W3.sin()).to_radians();
     let a = 8.725924;
 
-    funroutine(e, a, Omega, i, lambda1, p, &info)
+    funroutine(e, a, Omega, i, lambda1, p, info)
 
 }
 
@@ -349,7 +447,7 @@ fn Titan(info: &Info) -> (f64, f64, f64, f64) {
     let Omega = Omega1 + 0.031843_f64.to_radians()*s*u.sin()/i1.sin();
     let a = 20.216193;
 
-    funroutine(e, a, Omega, i, lambda1, p, &info)
+    funroutine(e, a, Omega, i, lambda1, p, info)
 
 }
 
@@ -417,7 +515,7 @@ fn Hyperion(info: &Info) -> (f64, f64, f64, f64) {
         + 0.0366*theta1.sin()
     ).to_radians();
 
-    funroutine(e, a, Omega, i, lambda1, p, &info)
+    funroutine(e, a, Omega, i, lambda1, p, info)
 
 }
 
@@ -489,7 +587,7 @@ fn Iapetus(info: &Info) -> (f64, f64, f64, f64) {
     ).to_radians();
     let Omega = Omega1 + w1/i1.sin();
 
-    funroutine(e, a, Omega, i, lambda1, p, &info)
+    funroutine(e, a, Omega, i, lambda1, p, info)
 
 }
 
@@ -534,8 +632,8 @@ fn XYZ(lambda_j: f64, gamma_j: f64, Omega_j: f64, r_j: f64, info: &Info, moon: &
     let Z_9 = 1.0;
 
     // some fancy stuff
-    let (X9, Y9, Z9, D9) = D(X_9, Y_9, Z_9, 0.0, &info);
-    let (mut X, mut Y, Z, D) = D(X_j, Y_j, Z_j, D9, &info);
+    let (X9, Y9, Z9, D9) = D(X_9, Y_9, Z_9, 0.0, info);
+    let (mut X, mut Y, Z, D) = D(X_j, Y_j, Z_j, D9, info);
 
     // correct for differential light-time
     let K = match *moon {
@@ -589,3 +687,50 @@ fn D(X_j: f64, Y_j: f64, Z_j: f64, D_j: f64, info: &Info) -> (f64, f64, f64, f64
     (X, Y, Z, D)
 
 }
+
+/**
+Computes the apparent magnitude of a moon of Saturn
+
+The magnitudes at unit distances from the Sun and the Earth are
+those of the *Explanatory Supplement to the Astronomical Almanac*
+(1992). The variation with phase angle, which never exceeds `6`
+degrees for Saturn, is neglected.
+
+Iapetus has a bright trailing and a dark leading hemisphere, and is
+about `1.7` magnitudes brighter at western elongation than at eastern
+elongation. The magnitude of Iapetus is varied with the sine of
+it's orbital phase, from the apparent offset `X` given by
+`apprnt_rect_coords()`.
+
+# Returns
+
+* `apprnt_mag`: Apparent magnitude of the moon
+
+# Arguments
+
+* `JD`  : Julian (Ephemeris) day
+* `moon`: The [Moon](./enum.Moon.html)
+**/
+pub fn apprnt_mag(JD: f64, moon: &Moon) -> f64 {
+
+    let (r, delta, _) = planet::dists(&planet::Planet::Saturn, JD);
+
+    let V0 = match *moon {
+        Moon::Mimas     => 3.3,
+        Moon::Enceladus => 2.1,
+        Moon::Tethys    => 0.6,
+        Moon::Dione     => 0.8,
+        Moon::Rhea      => 0.1,
+        Moon::Titan     => -1.28,
+        Moon::Hyperion  => 4.63,
+        Moon::Iapetus   => {
+            // in units of Saturn's equatorial radius
+            let a = 59.09;
+            let (X, _, _) = apprnt_rect_coords(JD, moon);
+            1.6 - 0.85*(X / a).clamp(-1.0, 1.0)
+        },
+    };
+
+    V0 + 5.0*(r*delta).log10()
+
+}
@@ -26,6 +26,8 @@ use angle;
 use coords;
 use planet;
 use time;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 #[inline]
 pub fn inc(JC: f64) -> f64 {
@@ -55,6 +57,7 @@ pub fn ascend_node(JC: f64) -> f64 {
 
 /// Holds the elements for the ring system of Saturn
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Elements {
     /// Saturnicentric latitude of the Earth, referred to the plane
     /// of the ring
@@ -91,28 +94,18 @@ pub fn elements(JD: f64, nut_in_long: f64, true_oblq: f64) -> Elements {
 
     let (l0, b0, R) = planet::heliocent_coords(&planet::Planet::Earth, JD);
 
-    let (mut l, mut b, mut r) = (0.0, 0.0, 0.0);
-    let (mut x, mut y, mut z) = (0.0, 0.0, 0.0);
-    let mut saturn_earth_dist = 0.0;
-    let mut light_time = 0.0;
-
-    let mut i: u8 = 1;
-    while i <= 2 {
-
-        let (new_l, new_b, new_r) =
-            planet::heliocent_coords(&planet::Planet::Saturn, JD - light_time);
-        l = new_l; b = new_b; r = new_r;
-
-        let (new_x, new_y, new_z) =
-            planet::geocent_ecl_rect_coords(l0, b0, R, l, b, r);
-        x = new_x; y = new_y; z = new_z;
-
-        saturn_earth_dist = planet::dist_frm_ecl_rect_coords(x, y, z);
-        light_time = planet::light_time(saturn_earth_dist);
-
-        i += 1;
+    let dist = |tau: f64| {
+        let (l, b, r) = planet::heliocent_coords(&planet::Planet::Saturn, JD - tau);
+        let (x, y, z) = planet::geocent_ecl_rect_coords(l0, b0, R, l, b, r);
+        planet::dist_frm_ecl_rect_coords(x, y, z)
+    };
+    let light_time = planet::light_time_iter(
+        dist, planet::LIGHT_TIME_TOL, planet::LIGHT_TIME_MAX_ITERS
+    );
 
-    }
+    let (l, b, r) = planet::heliocent_coords(&planet::Planet::Saturn, JD - light_time);
+    let (x, y, z) = planet::geocent_ecl_rect_coords(l0, b0, R, l, b, r);
+    let saturn_earth_dist = planet::dist_frm_ecl_rect_coords(x, y, z);
 
     let JC = time::julian_cent(JD);
     let inc = inc(JC);
@@ -167,10 +160,10 @@ pub fn elements(JD: f64, nut_in_long: f64, true_oblq: f64) -> Elements {
             .atan2(dec0.sin()*dec.cos() - dec0.cos()*dec.sin()*(asc0 - asc).cos());
 
     Elements {
-        B      : B,
-        B1     : B1,
-        P      : P,
-        deltaU : deltaU,
+        B,
+        B1,
+        P,
+        deltaU,
         a      : semi_maj,
         b      : semi_min
     }
@@ -204,3 +197,78 @@ pub fn inn_edge_dusk_ring(a: f64, b: f64) -> (f64, f64) {
     (a * 0.5486, b * 0.5486)
 
 }
+
+/// Represents the body whose crossing of the ring plane is sought
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Crossing {
+    /// The Earth crosses the ring plane, and the ring is seen edge-on
+    Earth,
+    /// The Sun crosses the ring plane, at Saturn's equinox, and the
+    /// ring is lit edge-on
+    Sun,
+}
+
+/**
+Computes the times at which the Earth or the Sun crosses the plane
+of the ring system of Saturn
+
+Around a crossing by the Sun, the Earth usually crosses the ring plane
+once or thrice, as its orbit swings it from one side of the plane to
+the other.
+
+# Returns
+
+* `crossings`: Julian (Ephemeris) days of the crossings, in order
+
+# Arguments
+
+* `crossing`: The body whose crossing is sought
+* `JD_start`: Julian (Ephemeris) day at which to start the search
+* `JD_end`  : Julian (Ephemeris) day at which to end the search
+**/
+pub fn plane_crossings(crossing: Crossing, JD_start: f64, JD_end: f64) -> Vec<f64> {
+
+    // the latitudes of the Earth and the Sun don't depend on the
+    // nutation or the obliquity of the ecliptic
+    let lat = |JD: f64| {
+        let elements = elements(JD, 0.0, 0.0);
+        match crossing {
+            Crossing::Earth => elements.B,
+            Crossing::Sun   => elements.B1,
+        }
+    };
+
+    let step = 4.0;
+    let mut crossings = Vec::new();
+    let mut t0 = JD_start;
+    let mut b0 = lat(t0);
+
+    while t0 < JD_end {
+        let t1 = (t0 + step).min(JD_end);
+        let b1 = lat(t1);
+
+        if b0 == 0.0 {
+            crossings.push(t0);
+        } else if b0 * b1 < 0.0 {
+            let (mut lo, mut hi, mut b_lo) = (t0, t1, b0);
+            while hi - lo > 1e-6 {
+                let mid = 0.5 * (lo + hi);
+                let b_mid = lat(mid);
+                if b_lo * b_mid <= 0.0 {
+                    hi = mid;
+                } else {
+                    lo = mid;
+                    b_lo = b_mid;
+                }
+            }
+            crossings.push(0.5 * (lo + hi));
+        }
+
+        t0 = t1;
+        b0 = b1;
+    }
+
+    crossings
+
+}
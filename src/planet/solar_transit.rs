@@ -0,0 +1,287 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Transits of Mercury and Venus across the Sun
+
+/*
+
+The contacts are found from the apparent separation of the centers of
+the planet and the Sun, as seen from the Earth's center or from an
+observer on the Earth's surface. The planet's position is corrected
+for light-time, and both bodies are affected alike by aberration,
+which is ignored.
+
+*/
+
+use angle;
+use coords;
+use ecliptic;
+use error;
+use nutation;
+use numerics;
+use planet;
+use sun;
+use time;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Holds a contact of a transit
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Contact {
+    /// Julian (Ephemeris) day of the contact
+    pub JD: f64,
+    /// Position angle of the planet's center from the Sun's center,
+    /// measured eastwards from the north *| in radians*
+    pub pos_angl: f64
+}
+
+/// Holds the circumstances of a transit
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Circumstances {
+    /// First contact, the exterior ingress
+    pub first: Contact,
+    /// Second contact, the interior ingress, if the planet is
+    /// entirely within the Sun's disk during the transit
+    pub second: Option<Contact>,
+    /// Greatest transit, when the planet is nearest the Sun's center
+    pub greatest: Contact,
+    /// Third contact, the interior egress, if the planet is
+    /// entirely within the Sun's disk during the transit
+    pub third: Option<Contact>,
+    /// Fourth contact, the exterior egress
+    pub fourth: Contact,
+    /// Least separation of the centers of the planet and the Sun
+    /// *| in radians*
+    pub min_sepr: f64
+}
+
+/**
+Computes the geocentric circumstances of a transit of Mercury or Venus
+
+# Returns
+
+* `Ok(circumstances)`: The [Circumstances](./struct.Circumstances.html)
+                       of the transit, as seen from the Earth's
+                       center, or
+* `Err(error)`       : `Error::InvalidBody` for a planet other than
+                       Mercury or Venus, or `Error::NoSolution` if
+                       there's no transit near `JD`
+
+# Arguments
+
+* `planet`: `Planet::Mercury` or `Planet::Venus`
+* `JD`    : Julian (Ephemeris) day within a day or so of the transit
+**/
+pub fn geocent_circumstances (
+
+    planet : &planet::Planet,
+    JD     : f64
+
+) -> Result<Circumstances, error::Error> {
+
+    circumstances(planet, JD, &|_| (0.0, 0.0, 0.0))
+
+}
+
+/**
+Computes the topocentric circumstances of a transit of Mercury or
+Venus, for an observer on the Earth's surface
+
+The contacts differ from the geocentric ones by up to several minutes
+because of the planet's parallax.
+
+# Returns
+
+* `Ok(circumstances)`: The [Circumstances](./struct.Circumstances.html)
+                       of the transit, as seen by the observer, or
+* `Err(error)`       : `Error::InvalidBody` for a planet other than
+                       Mercury or Venus, or `Error::NoSolution` if
+                       there's no transit near `JD`
+
+# Arguments
+
+* `planet`        : `Planet::Mercury` or `Planet::Venus`
+* `JD`            : Julian (Ephemeris) day within a day or so
+                    of the transit
+* `geograph_point`: Geographic point of the observer *| in radians*
+* `height`        : Observer's height above sea level *| in meters*
+* `delta_t`       : ΔT = TT - UT *| in seconds*
+**/
+pub fn topocent_circumstances (
+
+    planet         : &planet::Planet,
+    JD             : f64,
+    geograph_point : &coords::GeographPoint,
+    height         : f64,
+    delta_t        : f64
+
+) -> Result<Circumstances, error::Error> {
+
+    let (rho_sin_phi, rho_cos_phi) = planet::earth::rho_sin_cos_phi(geograph_point.lat, height);
+    let earth_rad = planet::earth::eq_rad() / 149597870.7;
+
+    let observer = |JD: f64| {
+        let (nut_long, nut_oblq) = nutation::nutation(JD);
+        let oblq = ecliptic::mn_oblq_IAU(JD) + nut_oblq;
+        let sidr = time::apprnt_sidr(time::mn_sidr(JD - delta_t/86400.0), nut_long, oblq);
+        let loc_sidr = sidr - geograph_point.long;
+
+        (
+            earth_rad * rho_cos_phi * loc_sidr.cos(),
+            earth_rad * rho_cos_phi * loc_sidr.sin(),
+            earth_rad * rho_sin_phi
+        )
+    };
+
+    circumstances(planet, JD, &observer)
+
+}
+
+// The circumstances of a transit, for an observer whose geocentric
+// equatorial rectangular coordinates (true equator and equinox of
+// the date, in AU) are given by a function of JD
+fn circumstances<F> (
+
+    planet   : &planet::Planet,
+    JD       : f64,
+    observer : &F
+
+) -> Result<Circumstances, error::Error> where F: Fn(f64) -> (f64, f64, f64) {
+
+    match *planet {
+        planet::Planet::Mercury | planet::Planet::Venus => {},
+        _ => return Err(error::Error::InvalidBody)
+    }
+
+    // the least separation seen from the Earth's center, and then from
+    // the observer
+    let geocent_sepr = |JD: f64| apprnt_sepr(planet, JD, &|_| (0.0, 0.0, 0.0)).0;
+    let JD_geocent = minimum(&geocent_sepr, JD - 2.0, JD + 2.0, 1.0/24.0);
+
+    let sepr = |JD: f64| apprnt_sepr(planet, JD, observer);
+    let JD_greatest = minimum(&|JD| sepr(JD).0, JD_geocent - 0.05, JD_geocent + 0.05, 1.0/1440.0);
+    let (min_sepr, sun_rad, planet_rad, _) = sepr(JD_greatest);
+
+    if min_sepr >= sun_rad + planet_rad {
+        return Err(error::Error::NoSolution);
+    }
+
+    let contact = |JD: f64| Contact { JD, pos_angl: sepr(JD).3 };
+    let exterior = |JD: f64| { let (s, R, r, _) = sepr(JD); s - (R + r) };
+    let interior = |JD: f64| { let (s, R, r, _) = sepr(JD); s - (R - r) };
+
+    let (before, after) = (JD_greatest - 0.5, JD_greatest + 0.5);
+    let root = |f: &dyn Fn(f64) -> f64, a: f64, b: f64| {
+        numerics::brent(f, a, b, 1e-7).map(contact).ok_or(error::Error::NoSolution)
+    };
+    let first = root(&exterior, before, JD_greatest)?;
+    let fourth = root(&exterior, JD_greatest, after)?;
+
+    let (second, third) = if min_sepr < sun_rad - planet_rad {
+        (
+            Some(root(&interior, before, JD_greatest)?),
+            Some(root(&interior, JD_greatest, after)?)
+        )
+    } else {
+        (None, None)
+    };
+
+    Ok(Circumstances {
+        first,
+        second,
+        greatest : contact(JD_greatest),
+        third,
+        fourth,
+        min_sepr
+    })
+
+}
+
+// The apparent separation of the centers of the planet and the Sun,
+// the apparent semidiameters of the Sun and the planet, and the
+// position angle of the planet from the Sun
+fn apprnt_sepr<F>(planet: &planet::Planet, JD: f64, observer: &F) -> (f64, f64, f64, f64)
+    where F: Fn(f64) -> (f64, f64, f64) {
+
+    let (nut_long, nut_oblq) = nutation::nutation(JD);
+    let oblq = ecliptic::mn_oblq_IAU(JD) + nut_oblq;
+    let (sin_e, cos_e) = oblq.sin_cos();
+
+    let rect = |planet: &planet::Planet, JD: f64| {
+        let (L, B, R) = planet::heliocent_coords(planet, JD);
+        (R*B.cos()*L.cos(), R*B.cos()*L.sin(), R*B.sin())
+    };
+
+    let earth = rect(&planet::Planet::Earth, JD);
+    let tau = planet::light_time_iter(|tau| {
+        let pos = rect(planet, JD - tau);
+        let (x, y, z) = (pos.0 - earth.0, pos.1 - earth.1, pos.2 - earth.2);
+        (x*x + y*y + z*z).sqrt()
+    }, planet::LIGHT_TIME_TOL, planet::LIGHT_TIME_MAX_ITERS);
+    let pos = rect(planet, JD - tau);
+
+    let (ox, oy, oz) = observer(JD);
+    let topocent = |(x, y, z): (f64, f64, f64)| {
+        let (long, lat) = (y.atan2(x) + nut_long, z.atan2((x*x + y*y).sqrt()));
+        let r = (x*x + y*y + z*z).sqrt();
+        let (x, y, z) = (r*lat.cos()*long.cos(), r*lat.cos()*long.sin(), r*lat.sin());
+        let (x, y, z) = (x - ox, y*cos_e - z*sin_e - oy, y*sin_e + z*cos_e - oz);
+
+        (y.atan2(x), z.atan2((x*x + y*y).sqrt()), (x*x + y*y + z*z).sqrt())
+    };
+
+    let (sun_asc, sun_dec, sun_dist) = topocent((-earth.0, -earth.1, -earth.2));
+    let (asc, dec, dist) = topocent((pos.0 - earth.0, pos.1 - earth.1, pos.2 - earth.2));
+
+    let d_asc = asc - sun_asc;
+    let sepr = (
+        sun_dec.sin()*dec.sin() + sun_dec.cos()*dec.cos()*d_asc.cos()
+    ).clamp(-1.0, 1.0).acos();
+    let pos_angl = angle::limit_to_two_PI((dec.cos()*d_asc.sin()).atan2(
+        sun_dec.cos()*dec.sin() - sun_dec.sin()*dec.cos()*d_asc.cos()
+    ));
+
+    let sun_rad = sun::semidiameter(sun_dist).to_radians();
+    let planet_rad = planet::semidiameter(planet, dist).unwrap_or(0.0);
+
+    (sepr, sun_rad, planet_rad, pos_angl)
+
+}
+
+// The time of the least value of a function within an interval, by
+// stepping through it and refining with golden section search
+fn minimum<F>(f: &F, start: f64, end: f64, step: f64) -> f64 where F: Fn(f64) -> f64 {
+
+    let n = ((end - start)/step).ceil() as usize;
+    let mut best = start;
+    for i in 0..(n + 1) {
+        let t = start + (i as f64)*step;
+        if f(t) < f(best) {
+            best = t;
+        }
+    }
+
+    numerics::golden_section(f, best - step, best + step, 1e-7)
+
+}
@@ -0,0 +1,90 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Uranus
+
+pub mod moons;
+
+use angle;
+use consts;
+use planet;
+use planet::moon_phenomena;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Holds Uranus's ephemeris values for physical observations
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ephemeris {
+    /// Uranus-centric declination of the Earth, or the latitude of
+    /// the sub-Earth point
+    pub De: f64,
+    /// Uranus-centric declination of the Sun, or the latitude of the
+    /// sub-solar point
+    pub Ds: f64,
+    /// Geocentric position angle of Uranus's northern rotation pole,
+    /// or also called the position angle of the axis
+    pub P : f64,
+    /// Longitude of the central meridian, as seen from the Earth
+    pub w : f64,
+    /// Apparent equatorial diameter of Uranus
+    pub d : f64,
+}
+
+/**
+Computes quantites used in the ephemeris for physical observations of
+Uranus
+
+The north pole and the rotation of Uranus are those adopted by the IAU
+Working Group on Cartographic Coordinates and Rotational Elements
+(2009). As Uranus rotates in the retrograde sense, the longitude of
+the central meridian is measured towards the east, so that it
+increases with time. The position angle of the axis is referred to
+the mean equator of J2000.0, like the positions of the moons in
+[moons](./moons/index.html).
+
+# Returns
+
+* `ephemeris`: Uranus's ephemeris. *All angles are in radians*
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn ephemeris(JD: f64) -> Ephemeris {
+
+    let primary = moon_phenomena::primary(planet::Planet::Uranus);
+
+    let (D_e, D_s, P, w, dist) = planet::physical_ephemeris (
+        &planet::Planet::Uranus, JD, primary.pole_asc, primary.pole_dec,
+        |t| (203.81 - 501.1600928*(t - 2451545.0)).to_radians()
+    );
+
+    Ephemeris {
+        De: D_e,
+        Ds: D_s,
+        P,
+        w : angle::limit_to_two_PI(-w),
+        d : 2.0 * (primary.eq_radius * 1000.0 / (dist * consts::ASTRONOMICAL_UNIT)).asin()
+    }
+
+}
@@ -0,0 +1,470 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! The five major moons of Uranus
+
+/*
+
+The positions of the moons are computed from the analytical theory
+GUST86 by J. Laskar and R. A. Jacobson (1987), "An analytical
+ephemeris of the Uranian satellites", Astronomy and Astrophysics 188,
+212-224.
+
+*/
+
+use angle;
+use error;
+use planet;
+use planet::moon_phenomena;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Represents a major moon of Uranus
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Moon {
+    /// Miranda
+    Miranda,
+    /// Ariel
+    Ariel,
+    /// Umbriel
+    Umbriel,
+    /// Titania
+    Titania,
+    /// Oberon
+    Oberon
+}
+
+/**
+Computes the position of a moon of Uranus with respect to Uranus
+
+# Returns
+
+`(x, y, z)`
+
+* `x`, `y`, `z`: Rectangular coordinates of the moon with respect to
+                 the center of Uranus, referred to the ecliptic and
+                 mean equinox of J2000.0 *| in AU*
+
+# Arguments
+
+* `JD`  : Julian (Ephemeris) day
+* `moon`: The [Moon](./enum.Moon.html)
+**/
+pub fn uranicent_rect_coords(JD: f64, moon: &Moon) -> (f64, f64, f64) {
+
+    let (n, lambda, k, h, q, p) = elements(JD - 2444239.5, moon);
+
+    let mu = match *moon {
+        Moon::Miranda => 1.291892353675174e-8,
+        Moon::Ariel   => 1.291910570526396e-8,
+        Moon::Umbriel => 1.291910102284198e-8,
+        Moon::Titania => 1.291942656265575e-8,
+        Moon::Oberon  => 1.29193596709132e-8,
+    };
+    let a = (mu / (n*n)).cbrt();
+
+    // solve Kepler's equation for the eccentric longitude
+    let mut F = lambda;
+    for _ in 0..20 {
+        let delta =
+            (F - k*F.sin() + h*F.cos() - lambda)
+          / (1.0 - k*F.cos() - h*F.sin());
+        F -= delta;
+        if delta.abs() < 1e-14 { break; }
+    }
+
+    let phi = (1.0 - k*k - h*h).sqrt();
+    let psi = 1.0 / (1.0 + phi);
+
+    let X1 = a * ((1.0 - psi*h*h)*F.cos() + psi*h*k*F.sin() - k);
+    let Y1 = a * ((1.0 - psi*k*k)*F.sin() + psi*h*k*F.cos() - h);
+
+    let chi = (1.0 - q*q - p*p).sqrt();
+    let x = (1.0 - 2.0*p*p)*X1 + 2.0*p*q*Y1;
+    let y = 2.0*p*q*X1 + (1.0 - 2.0*q*q)*Y1;
+    let z = 2.0*chi*(q*Y1 - p*X1);
+
+    // from the equatorial plane of Uranus to the ecliptic of J2000.0
+    (
+        0.9753206632086812*x + 0.0619442566800147*y + 0.2119257251551257*z,
+       -0.2006444610981784*x - 0.1519328516640849*y + 0.9678110398294911*z,
+        0.0921488152327519*x - 0.9864478281437795*y - 0.1357544776485407*z
+    )
+
+}
+
+/**
+Computes the apparent position of a moon of Uranus with respect to
+Uranus, as seen from the Earth
+
+# Returns
+
+`(X, Y)`
+
+The offsets of the moon from the center of Uranus, projected on the
+plane of the sky and referred to the mean equator of J2000.0.
+
+* `X`: Offset towards the east *| in radians*
+* `Y`: Offset towards the north *| in radians*
+
+# Arguments
+
+* `JD`  : Julian (Ephemeris) day
+* `moon`: The [Moon](./enum.Moon.html)
+**/
+pub fn apprnt_offsets(JD: f64, moon: &Moon) -> (f64, f64) {
+
+    planet::satellite_apprnt_offsets (
+        &planet::Planet::Uranus, JD, |t| uranicent_rect_coords(t, moon)
+    )
+
+}
+
+/**
+Computes the transits, occultations, eclipses and shadow transits of
+a moon of Uranus in an interval of time
+
+These happen only in the seasons around the equinoxes of Uranus, such
+as that of 2007.
+
+# Returns
+
+* `Ok(phenomena)`: [Phenomena](../../moon_phenomena/struct.Phenomenon.html)
+                   of the moon, ordered by their start, or
+* `Err(error)`   : `Error::NotPositive` if `step` isn't positive, or
+                   `Error::NotFinite`
+
+# Arguments
+
+* `moon`    : The [Moon](./enum.Moon.html)
+* `JD_start`: Julian (Ephemeris) day at the start of the search
+* `JD_end`  : Julian (Ephemeris) day at the end of the search
+* `step`    : Sampling interval, positive *| in days*, such as 0.005
+**/
+pub fn phenomena(moon: &Moon, JD_start: f64, JD_end: f64, step: f64) -> Result<Vec<moon_phenomena::Phenomenon>, error::Error> {
+
+    moon_phenomena::phenomena (
+        &moon_phenomena::primary(planet::Planet::Uranus),
+        |JD| uranicent_rect_coords(JD, moon),
+        JD_start,
+        JD_end,
+        step
+    )
+
+}
+
+// Sums a series of complex terms A*exp(i*phase)
+fn sum_exp(terms: &[(f64, f64)]) -> (f64, f64) {
+
+    terms.iter().fold((0.0, 0.0), |(re, im), &(A, phase)|
+        (re + A*phase.cos(), im + A*phase.sin())
+    )
+
+}
+
+// Sums a series of terms A*cos(phase) or A*sin(phase)
+fn sum_cos(terms: &[(f64, f64)]) -> f64 {
+
+    terms.iter().fold(0.0, |sum, &(A, phase)| sum + A*phase.cos())
+
+}
+
+fn sum_sin(terms: &[(f64, f64)]) -> f64 {
+
+    terms.iter().fold(0.0, |sum, &(A, phase)| sum + A*phase.sin())
+
+}
+
+// Computes the elements (n, lambda, k, h, q, p) of a moon, t days
+// after 1980 January 1.0
+fn elements(t: f64, moon: &Moon) -> (f64, f64, f64, f64, f64, f64) {
+
+    let deg_per_year = 1.0_f64.to_radians() / 365.25;
+
+    let fqn = [4.445190550, 2.492952519, 1.516148111, 0.721718509, 0.466692120];
+    let fqe = [20.082, 6.217, 2.865, 2.078, 0.386];
+    let fqi = [-20.309, -6.288, -2.836, -1.843, -0.259];
+    let phn = [-0.238051, 3.098046, 2.285402, 0.856359, -0.915592];
+    let phe = [0.611392, 2.408974, 2.067774, 0.735131, 0.426767];
+    let phi = [5.702313, 0.395757, 0.589326, 1.746237, 4.206896];
+
+    let mut an = [0.0; 5];
+    let mut ae = [0.0; 5];
+    let mut ai = [0.0; 5];
+    for j in 0..5 {
+        an[j] = (phn[j] + fqn[j]*t) % angle::TWO_PI;
+        ae[j] = phe[j] + fqe[j]*deg_per_year*t;
+        ai[j] = phi[j] + fqi[j]*deg_per_year*t;
+    }
+
+    let (n, lambda, ecc, incl) = match *moon {
+        Moon::Miranda => (
+            4443522.67e-6 + sum_cos(&[
+                (  -34.92e-6, an[0] - 3.0*an[1] + 2.0*an[2]),
+                (    8.47e-6, 2.0*an[0] - 6.0*an[1] + 4.0*an[2]),
+                (    1.31e-6, 3.0*an[0] - 9.0*an[1] + 6.0*an[2]),
+                (  -52.28e-6, an[0] - an[1]),
+                ( -136.65e-6, 2.0*an[0] - 2.0*an[1]),
+            ]),
+            -238051.58e-6 + 4445190.55e-6*t + sum_sin(&[
+                (25472.17e-6, an[0] - 3.0*an[1] + 2.0*an[2]),
+                (-3088.31e-6, 2.0*an[0] - 6.0*an[1] + 4.0*an[2]),
+                ( -318.10e-6, 3.0*an[0] - 9.0*an[1] + 6.0*an[2]),
+                (  -37.49e-6, 4.0*an[0] - 12.0*an[1] + 8.0*an[2]),
+                (  -57.85e-6, an[0] - an[1]),
+                (  -62.32e-6, 2.0*an[0] - 2.0*an[1]),
+                (  -27.95e-6, 3.0*an[0] - 3.0*an[1]),
+            ]),
+            sum_exp(&[
+                ( 1312.38e-6, ae[0]),
+                (   71.81e-6, ae[1]),
+                (   69.77e-6, ae[2]),
+                (    6.75e-6, ae[3]),
+                (    6.27e-6, ae[4]),
+                ( -123.31e-6, -an[0] + 2.0*an[1]),
+                (   39.52e-6, -2.0*an[0] + 3.0*an[1]),
+                (  194.10e-6, an[0]),
+            ]),
+            sum_exp(&[
+                (37871.71e-6, ai[0]),
+                (   27.01e-6, ai[1]),
+                (   30.76e-6, ai[2]),
+                (   12.18e-6, ai[3]),
+                (    5.37e-6, ai[4]),
+            ])
+        ),
+        Moon::Ariel => (
+            2492542.57e-6 + sum_cos(&[
+                (    2.55e-6, an[0] - 3.0*an[1] + 2.0*an[2]),
+                (  -42.16e-6, an[1] - an[2]),
+                ( -102.56e-6, 2.0*an[1] - 2.0*an[2]),
+            ]),
+            3098046.41e-6 + 2492952.52e-6*t + sum_sin(&[
+                (-1860.50e-6, an[0] - 3.0*an[1] + 2.0*an[2]),
+                (  219.99e-6, 2.0*an[0] - 6.0*an[1] + 4.0*an[2]),
+                (   23.10e-6, 3.0*an[0] - 9.0*an[1] + 6.0*an[2]),
+                (    4.30e-6, 4.0*an[0] - 12.0*an[1] + 8.0*an[2]),
+                (  -90.11e-6, an[1] - an[2]),
+                (  -91.07e-6, 2.0*an[1] - 2.0*an[2]),
+                (  -42.75e-6, 3.0*an[1] - 3.0*an[2]),
+                (  -16.49e-6, 2.0*an[1] - 2.0*an[3]),
+            ]),
+            sum_exp(&[
+                (   -3.35e-6, ae[0]),
+                ( 1187.63e-6, ae[1]),
+                (  861.59e-6, ae[2]),
+                (   71.50e-6, ae[3]),
+                (   55.59e-6, ae[4]),
+                (  -84.60e-6, -an[1] + 2.0*an[2]),
+                (   91.81e-6, -2.0*an[1] + 3.0*an[2]),
+                (   20.03e-6, -an[1] + 2.0*an[3]),
+                (   89.77e-6, an[1]),
+            ]),
+            sum_exp(&[
+                ( -121.75e-6, ai[0]),
+                (  358.25e-6, ai[1]),
+                (  290.08e-6, ai[2]),
+                (   97.78e-6, ai[3]),
+                (   33.97e-6, ai[4]),
+            ])
+        ),
+        Moon::Umbriel => (
+            1515954.90e-6 + sum_cos(&[
+                (    9.74e-6, an[2] - 2.0*an[3] + ae[2]),
+                ( -106.00e-6, an[1] - an[2]),
+                (   54.16e-6, 2.0*an[1] - 2.0*an[2]),
+                (  -23.59e-6, an[2] - an[3]),
+                (  -70.70e-6, 2.0*an[2] - 2.0*an[3]),
+                (  -36.28e-6, 3.0*an[2] - 3.0*an[3]),
+            ]),
+            2285401.69e-6 + 1516148.11e-6*t + sum_sin(&[
+                (  660.57e-6, an[0] - 3.0*an[1] + 2.0*an[2]),
+                (  -76.51e-6, 2.0*an[0] - 6.0*an[1] + 4.0*an[2]),
+                (   -8.96e-6, 3.0*an[0] - 9.0*an[1] + 6.0*an[2]),
+                (   -2.53e-6, 4.0*an[0] - 12.0*an[1] + 8.0*an[2]),
+                (  -52.91e-6, an[2] - 4.0*an[3] + 3.0*an[4]),
+                (   -7.34e-6, an[2] - 2.0*an[3] + ae[4]),
+                (   -1.83e-6, an[2] - 2.0*an[3] + ae[3]),
+                (  147.91e-6, an[2] - 2.0*an[3] + ae[2]),
+                (   -7.77e-6, an[2] - 2.0*an[3] + ae[1]),
+                (   97.76e-6, an[1] - an[2]),
+                (   73.13e-6, 2.0*an[1] - 2.0*an[2]),
+                (   34.71e-6, 3.0*an[1] - 3.0*an[2]),
+                (   18.89e-6, 4.0*an[1] - 4.0*an[2]),
+                (  -67.89e-6, an[2] - an[3]),
+                (  -82.86e-6, 2.0*an[2] - 2.0*an[3]),
+                (  -33.81e-6, 3.0*an[2] - 3.0*an[3]),
+                (  -15.79e-6, 4.0*an[2] - 4.0*an[3]),
+                (  -10.21e-6, an[2] - an[4]),
+                (  -17.08e-6, 2.0*an[2] - 2.0*an[4]),
+            ]),
+            sum_exp(&[
+                (   -0.21e-6, ae[0]),
+                ( -227.95e-6, ae[1]),
+                ( 3904.69e-6, ae[2]),
+                (  309.17e-6, ae[3]),
+                (  221.92e-6, ae[4]),
+                (   29.34e-6, an[1]),
+                (   26.20e-6, an[2]),
+                (   51.19e-6, -an[1] + 2.0*an[2]),
+                ( -103.86e-6, -2.0*an[1] + 3.0*an[2]),
+                (  -27.16e-6, -3.0*an[1] + 4.0*an[2]),
+                (  -16.22e-6, an[3]),
+                (  549.23e-6, -an[2] + 2.0*an[3]),
+                (   34.70e-6, -2.0*an[2] + 3.0*an[3]),
+                (   12.81e-6, -3.0*an[2] + 4.0*an[3]),
+                (   21.81e-6, -an[2] + 2.0*an[4]),
+                (   46.25e-6, an[2]),
+            ]),
+            sum_exp(&[
+                (  -10.86e-6, ai[0]),
+                (  -81.51e-6, ai[1]),
+                ( 1113.36e-6, ai[2]),
+                (  350.14e-6, ai[3]),
+                (  106.50e-6, ai[4]),
+            ])
+        ),
+        Moon::Titania => (
+            721663.16e-6 + sum_cos(&[
+                (   -2.64e-6, an[2] - 2.0*an[3] + ae[2]),
+                (   -2.16e-6, 2.0*an[3] - 3.0*an[4] + ae[4]),
+                (    6.45e-6, 2.0*an[3] - 3.0*an[4] + ae[3]),
+                (   -1.11e-6, 2.0*an[3] - 3.0*an[4] + ae[2]),
+                (  -62.23e-6, an[1] - an[3]),
+                (  -56.13e-6, an[2] - an[3]),
+                (  -39.94e-6, an[3] - an[4]),
+                (  -91.85e-6, 2.0*an[3] - 2.0*an[4]),
+                (  -58.31e-6, 3.0*an[3] - 3.0*an[4]),
+                (  -38.60e-6, 4.0*an[3] - 4.0*an[4]),
+                (  -26.18e-6, 5.0*an[3] - 5.0*an[4]),
+                (  -18.06e-6, 6.0*an[3] - 6.0*an[4]),
+            ]),
+            856358.79e-6 + 721718.51e-6*t + sum_sin(&[
+                (   20.61e-6, an[2] - 4.0*an[3] + 3.0*an[4]),
+                (   -2.07e-6, an[2] - 2.0*an[3] + ae[4]),
+                (   -2.88e-6, an[2] - 2.0*an[3] + ae[3]),
+                (  -40.79e-6, an[2] - 2.0*an[3] + ae[2]),
+                (    2.11e-6, an[2] - 2.0*an[3] + ae[1]),
+                (  -51.83e-6, 2.0*an[3] - 3.0*an[4] + ae[4]),
+                (  159.87e-6, 2.0*an[3] - 3.0*an[4] + ae[3]),
+                (  -35.05e-6, 2.0*an[3] - 3.0*an[4] + ae[2]),
+                (   -1.56e-6, 3.0*an[3] - 4.0*an[4] + ae[4]),
+                (   40.54e-6, an[1] - an[3]),
+                (   46.17e-6, an[2] - an[3]),
+                ( -317.76e-6, an[3] - an[4]),
+                ( -305.59e-6, 2.0*an[3] - 2.0*an[4]),
+                ( -148.36e-6, 3.0*an[3] - 3.0*an[4]),
+                (  -82.92e-6, 4.0*an[3] - 4.0*an[4]),
+                (  -49.98e-6, 5.0*an[3] - 5.0*an[4]),
+                (  -31.56e-6, 6.0*an[3] - 6.0*an[4]),
+                (  -20.56e-6, 7.0*an[3] - 7.0*an[4]),
+                (  -13.69e-6, 8.0*an[3] - 8.0*an[4]),
+            ]),
+            sum_exp(&[
+                (   -0.02e-6, ae[0]),
+                (   -1.29e-6, ae[1]),
+                ( -324.51e-6, ae[2]),
+                (  932.81e-6, ae[3]),
+                ( 1120.89e-6, ae[4]),
+                (   33.86e-6, an[1]),
+                (   17.46e-6, an[3]),
+                (   16.58e-6, -an[1] + 2.0*an[3]),
+                (   28.89e-6, an[2]),
+                (  -35.86e-6, -an[2] + 2.0*an[3]),
+                (  -17.86e-6, an[3]),
+                (  -32.10e-6, an[4]),
+                ( -177.83e-6, -an[3] + 2.0*an[4]),
+                (  793.43e-6, -2.0*an[3] + 3.0*an[4]),
+                (   99.48e-6, -3.0*an[3] + 4.0*an[4]),
+                (   44.83e-6, -4.0*an[3] + 5.0*an[4]),
+                (   25.13e-6, -5.0*an[3] + 6.0*an[4]),
+                (   15.43e-6, -6.0*an[3] + 7.0*an[4]),
+            ]),
+            sum_exp(&[
+                (   -1.43e-6, ai[0]),
+                (   -1.06e-6, ai[1]),
+                ( -140.13e-6, ai[2]),
+                (  685.72e-6, ai[3]),
+                (  378.32e-6, ai[4]),
+            ])
+        ),
+        Moon::Oberon => (
+            466580.54e-6 + sum_cos(&[
+                (    2.08e-6, 2.0*an[3] - 3.0*an[4] + ae[4]),
+                (   -6.22e-6, 2.0*an[3] - 3.0*an[4] + ae[3]),
+                (    1.07e-6, 2.0*an[3] - 3.0*an[4] + ae[2]),
+                (  -43.10e-6, an[1] - an[4]),
+                (  -38.94e-6, an[2] - an[4]),
+                (  -80.11e-6, an[3] - an[4]),
+                (   59.06e-6, 2.0*an[3] - 2.0*an[4]),
+                (   37.49e-6, 3.0*an[3] - 3.0*an[4]),
+                (   24.82e-6, 4.0*an[3] - 4.0*an[4]),
+                (   16.84e-6, 5.0*an[3] - 5.0*an[4]),
+            ]),
+            -915591.80e-6 + 466692.12e-6*t + sum_sin(&[
+                (   -7.82e-6, an[2] - 4.0*an[3] + 3.0*an[4]),
+                (   51.29e-6, 2.0*an[3] - 3.0*an[4] + ae[4]),
+                ( -158.24e-6, 2.0*an[3] - 3.0*an[4] + ae[3]),
+                (   34.51e-6, 2.0*an[3] - 3.0*an[4] + ae[2]),
+                (   47.51e-6, an[1] - an[4]),
+                (   38.96e-6, an[2] - an[4]),
+                (  359.73e-6, an[3] - an[4]),
+                (  282.78e-6, 2.0*an[3] - 2.0*an[4]),
+                (  138.60e-6, 3.0*an[3] - 3.0*an[4]),
+                (   78.03e-6, 4.0*an[3] - 4.0*an[4]),
+                (   47.29e-6, 5.0*an[3] - 5.0*an[4]),
+                (   30.00e-6, 6.0*an[3] - 6.0*an[4]),
+                (   19.62e-6, 7.0*an[3] - 7.0*an[4]),
+                (   13.11e-6, 8.0*an[3] - 8.0*an[4]),
+            ]),
+            sum_exp(&[
+                (   -0.35e-6, ae[1]),
+                (   74.53e-6, ae[2]),
+                ( -758.68e-6, ae[3]),
+                ( 1397.34e-6, ae[4]),
+                (   39.00e-6, an[1]),
+                (   17.66e-6, -an[1] + 2.0*an[4]),
+                (   32.42e-6, an[2]),
+                (   79.75e-6, an[3]),
+                (   75.66e-6, an[4]),
+                (  134.04e-6, -an[3] + 2.0*an[4]),
+                ( -987.26e-6, -2.0*an[3] + 3.0*an[4]),
+                ( -126.09e-6, -3.0*an[3] + 4.0*an[4]),
+                (  -57.42e-6, -4.0*an[3] + 5.0*an[4]),
+                (  -32.41e-6, -5.0*an[3] + 6.0*an[4]),
+                (  -19.99e-6, -6.0*an[3] + 7.0*an[4]),
+                (  -12.94e-6, -7.0*an[3] + 8.0*an[4]),
+            ]),
+            sum_exp(&[
+                (   -0.44e-6, ai[0]),
+                (   -0.31e-6, ai[1]),
+                (   36.89e-6, ai[2]),
+                ( -596.33e-6, ai[3]),
+                (  451.69e-6, ai[4]),
+            ])
+        ),
+    };
+
+    (n, lambda, ecc.0, ecc.1, incl.0, incl.1)
+
+}
@@ -0,0 +1,169 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Positions seen from anywhere in the solar system
+
+/*
+
+The positions are astrometric, ie: corrected for light-time, but not
+for aberration due to the motion of the observer, and are referred to
+the ecliptic and mean equinox of J2000.0. The observer is placed at
+the center of a planet, which is within a few seconds of arc of any
+point on it's surface for the positions of the Sun and the planets.
+
+*/
+
+use angle;
+use coords;
+use ecliptic;
+use planet;
+use super::moon_phenomena;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Represents the location of an observer in the solar system
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Viewpoint {
+    /// The center of a planet
+    Planet(planet::Planet),
+    /// A point given by its heliocentric rectangular coordinates,
+    /// referred to the ecliptic and mean equinox of J2000.0 *| in AU*
+    Heliocent(f64, f64, f64)
+}
+
+/// Represents a body whose position is computed
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Target {
+    /// The Sun
+    Sun,
+    /// A planet, including the Earth
+    Planet(planet::Planet)
+}
+
+/**
+Computes the ecliptic position of a body seen from a viewpoint in the
+solar system
+
+# Returns
+
+`(ecl_point, dist)`
+
+* `ecl_point`: Ecliptic point of the body, referred to the ecliptic
+               and mean equinox of J2000.0 *| in radians*
+* `dist`     : Distance of the body from the viewpoint *| in AU*
+
+# Arguments
+
+* `viewpoint`: The [Viewpoint](./enum.Viewpoint.html)
+* `target`   : The [Target](./enum.Target.html) body
+* `JD`       : Julian (Ephemeris) day
+**/
+pub fn ecl_coords (
+
+    viewpoint : &Viewpoint,
+    target    : &Target,
+    JD        : f64
+
+) -> (coords::EclPoint, f64) {
+
+    let observer = match *viewpoint {
+        Viewpoint::Planet(ref planet) => planet::heliocent_ecl_rect_coords_J2000(planet, JD),
+        Viewpoint::Heliocent(x, y, z) => (x, y, z)
+    };
+
+    let target_pos = |t: f64| match *target {
+        Target::Sun                => (0.0, 0.0, 0.0),
+        Target::Planet(ref planet) => planet::heliocent_ecl_rect_coords_J2000(planet, t)
+    };
+
+    let rel_pos = |tau: f64| {
+        let pos = target_pos(JD - tau);
+        (pos.0 - observer.0, pos.1 - observer.1, pos.2 - observer.2)
+    };
+    let tau = planet::light_time_iter(
+        |tau| { let (x, y, z) = rel_pos(tau); (x*x + y*y + z*z).sqrt() },
+        planet::LIGHT_TIME_TOL, planet::LIGHT_TIME_MAX_ITERS
+    );
+
+    let (x, y, z) = rel_pos(tau);
+    let dist = (x*x + y*y + z*z).sqrt();
+
+    (
+        coords::EclPoint {
+            long : angle::limit_to_two_PI(y.atan2(x)),
+            lat  : z.atan2((x*x + y*y).sqrt())
+        },
+        dist
+    )
+
+}
+
+/**
+Computes equatorial coordinates referred to the equator of a planet,
+from ecliptic coordinates
+
+The planet's equator is that of it's north pole of rotation at
+J2000.0, adopted by the IAU, and the right ascensions are measured
+from the ascending node of the planet's equator on the Earth's mean
+equator of J2000.0.
+
+# Returns
+
+* `eq_point`: Equatorial point referred to the planet's equator
+              *| in radians*
+
+# Arguments
+
+* `planet`   : The [Planet](../enum.Planet.html) whose equator to use
+* `ecl_point`: Ecliptic point referred to the ecliptic and mean
+               equinox of J2000.0 *| in radians*
+**/
+pub fn planetocent_eq_coords(planet: planet::Planet, ecl_point: &coords::EclPoint) -> coords::EqPoint {
+
+    let primary = moon_phenomena::primary(planet);
+    let oblq = ecliptic::mn_oblq_IAU(2451545.0);
+
+    let (sin_b, cos_b) = ecl_point.lat.sin_cos();
+    let (sin_l, cos_l) = ecl_point.long.sin_cos();
+    let (x, y, z) = (cos_b*cos_l, cos_b*sin_l, sin_b);
+    let v = (x, y*oblq.cos() - z*oblq.sin(), y*oblq.sin() + z*oblq.cos());
+
+    let (sin_a, cos_a) = primary.pole_asc.sin_cos();
+    let (sin_d, cos_d) = primary.pole_dec.sin_cos();
+    let pole = (cos_d*cos_a, cos_d*sin_a, sin_d);
+    let node = (-sin_a, cos_a, 0.0);
+    let third = (
+        pole.1*node.2 - pole.2*node.1,
+        pole.2*node.0 - pole.0*node.2,
+        pole.0*node.1 - pole.1*node.0
+    );
+
+    let dot = |a: (f64, f64, f64), b: (f64, f64, f64)| a.0*b.0 + a.1*b.1 + a.2*b.2;
+
+    coords::EqPoint {
+        asc : angle::limit_to_two_PI(dot(v, third).atan2(dot(v, node))),
+        dec : dot(v, pole).asin()
+    }
+
+}
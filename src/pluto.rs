@@ -22,8 +22,26 @@ THE SOFTWARE.
 
 //! The dwarf planet Pluto
 
+use aberr;
 use angle;
+use comet;
+use coords;
+use ecliptic;
+use error::Error;
+use nutation;
+use planet;
+use precess;
 use time;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Julian (Ephemeris) day of the start of 1885 AD, from which
+/// `heliocent_pos()` is valid
+pub const JD_START: f64 = 2409542.5;
+
+/// Julian (Ephemeris) day of the end of 2099 AD, until which
+/// `heliocent_pos()` is valid
+pub const JD_END: f64 = 2488069.5;
 
 /**
 Computes the geocentric equatorial semidiameter of Pluto
@@ -182,3 +200,189 @@ pub fn mn_orb_elements_2000AD() -> (f64, f64, f64, f64, f64)
         113.768_f64.to_radians()  // w
     )
 }
+
+/**
+Computes the heliocentric coordinates of Pluto, referred to the
+standard equinox of J2000.0, checking the range of validity
+
+# Returns
+
+* `Ok((long, lat, rad_vec))`: As returned by `heliocent_pos()`, or
+* `Err(error)`              : `Error::DateOutOfRange` if `JD` is
+                              before 1885 AD or after 2099 AD, or
+                              `Error::NotFinite` if `JD` isn't finite
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn try_heliocent_pos(JD: f64) -> Result<(f64, f64, f64), Error>
+{
+    if !JD.is_finite() {
+        Err(Error::NotFinite)
+    } else if !(JD_START..=JD_END).contains(&JD) {
+        Err(Error::DateOutOfRange(JD))
+    } else {
+        Ok(heliocent_pos(JD))
+    }
+}
+
+/**
+Computes the heliocentric rectangular coordinates of Pluto, referred
+to the ecliptic and mean equinox of J2000.0, optionally beyond the
+range of validity of `heliocent_pos()`
+
+Outside the years 1885 AD to 2099 AD, Pluto is moved along the
+Keplerian orbit osculating the positions of `heliocent_pos()` at the
+nearest end of the range. As the perturbations by the planets are
+then neglected, the error grows steadily away from the range.
+
+# Returns
+
+* `Ok((x, y, z))`: Heliocentric rectangular coordinates *| in AU*, or
+* `Err(error)`   : `Error::DateOutOfRange` if `JD` is outside the
+                   range and `extrapolate` is `false`, or
+                   `Error::NotFinite` if `JD` isn't finite
+
+# Arguments
+
+* `JD`         : Julian (Ephemeris) day
+* `extrapolate`: Whether to extrapolate beyond the range of validity
+**/
+pub fn heliocent_rect_coords_J2000(JD: f64, extrapolate: bool) -> Result<(f64, f64, f64), Error>
+{
+    match try_heliocent_pos(JD) {
+        Ok((long, lat, r)) => Ok(rect_frm_sphr(long, lat, r)),
+        Err(Error::DateOutOfRange(_)) if extrapolate => {
+            let JD0 = if JD < JD_START { JD_START } else { JD_END };
+
+            // the osculating orbit at the end of the range, with the
+            // velocity from the series
+            let h = 1.0;
+            let pos = |JD: f64| { let (l, b, r) = heliocent_pos(JD); rect_frm_sphr(l, b, r) };
+            let (before, after) = (pos(JD0 - h), pos(JD0 + h));
+            let vel = (
+                (after.0 - before.0) / (2.0*h),
+                (after.1 - before.1) / (2.0*h),
+                (after.2 - before.2) / (2.0*h)
+            );
+            let elements = comet::elements_frm_heliocent_rect_state(pos(JD0), vel, JD0);
+
+            Ok(comet::heliocent_rect_state(&elements, JD).0)
+        },
+        Err(error) => Err(error)
+    }
+}
+
+/**
+Computes the geocentric astrometric equatorial coordinates of Pluto,
+referred to the mean equator and equinox of J2000.0
+
+The position is corrected for light-time, but not for aberration
+or nutation, so that it is comparable with star catalogs.
+
+# Returns
+
+* `Ok((eq_point, dist))`: `eq_point` is the astrometric equatorial
+                          point of Pluto *| in radians*, and `dist` its
+                          geometric geocentric distance *| in AU*, or
+* `Err(error)`          : As returned by
+                          `heliocent_rect_coords_J2000()`
+
+# Arguments
+
+* `JD`         : Julian (Ephemeris) day
+* `extrapolate`: Whether to extrapolate beyond the range of validity
+                 of `heliocent_pos()`
+**/
+pub fn geocent_astrometric_coords(JD: f64, extrapolate: bool) -> Result<(coords::EqPoint, f64), Error>
+{
+    let (x, y, z, dist) = geocent_ecl_J2000(JD, extrapolate)?;
+    let oblq = ecliptic::mn_oblq_IAU(2451545.0);
+    let (long, lat) = (y.atan2(x), (z / dist).asin());
+
+    Ok((
+        coords::EqPoint {
+            asc: angle::limit_to_two_PI(coords::asc_frm_ecl(long, lat, oblq)),
+            dec: coords::dec_frm_ecl(long, lat, oblq)
+        },
+        dist
+    ))
+}
+
+/**
+Computes the geocentric apparent equatorial coordinates of Pluto,
+referred to the true equator and equinox of the date
+
+The position is corrected for light-time, precession, nutation and
+annual aberration.
+
+# Returns
+
+* `Ok((eq_point, dist))`: `eq_point` is the apparent equatorial point
+                          of Pluto *| in radians*, and `dist` its
+                          geometric geocentric distance *| in AU*, or
+* `Err(error)`          : As returned by
+                          `heliocent_rect_coords_J2000()`
+
+# Arguments
+
+* `JD`         : Julian (Ephemeris) day
+* `extrapolate`: Whether to extrapolate beyond the range of validity
+                 of `heliocent_pos()`
+**/
+pub fn geocent_apprnt_eq_coords(JD: f64, extrapolate: bool) -> Result<(coords::EqPoint, f64), Error>
+{
+    let (x, y, z, dist) = geocent_ecl_J2000(JD, extrapolate)?;
+
+    let (long, lat) = precess::precess_ecl_coords(y.atan2(x), (z / dist).asin(), 2451545.0, JD);
+    let (nut_in_long, nut_in_oblq) = nutation::nutation(JD);
+    let true_oblq = ecliptic::mn_oblq_IAU(JD) + nut_in_oblq;
+    let long = long + nut_in_long;
+
+    let eq_point = coords::EqPoint {
+        asc: coords::asc_frm_ecl(long, lat, true_oblq),
+        dec: coords::dec_frm_ecl(long, lat, true_oblq)
+    };
+    let (d_asc, d_dec) = aberr::stell_aberr_in_eq_coords(&eq_point, JD);
+
+    Ok((
+        coords::EqPoint {
+            asc: angle::limit_to_two_PI(eq_point.asc + d_asc),
+            dec: eq_point.dec + d_dec
+        },
+        dist
+    ))
+}
+
+// Geocentric rectangular coordinates of Pluto, referred to the
+// ecliptic and mean equinox of J2000.0 and corrected for light-time,
+// and its geometric distance
+fn geocent_ecl_J2000(JD: f64, extrapolate: bool) -> Result<(f64, f64, f64, f64), Error>
+{
+    if !extrapolate {
+        try_heliocent_pos(JD)?;
+    }
+
+    // the light-time may carry the position just outside the range
+    let earth = planet::heliocent_ecl_rect_coords_J2000(&planet::Planet::Earth, JD);
+    let geocent = |tau: f64| -> Result<(f64, f64, f64), Error> {
+        let (x, y, z) = heliocent_rect_coords_J2000(JD - tau, true)?;
+        Ok((x - earth.0, y - earth.1, z - earth.2))
+    };
+    let norm = |p: (f64, f64, f64)| (p.0*p.0 + p.1*p.1 + p.2*p.2).sqrt();
+
+    let geomet = geocent(0.0)?;
+    let mut p = geomet;
+    for _ in 0..3 {
+        p = geocent(0.0057755183 * norm(p))?;
+    }
+
+    Ok((p.0, p.1, p.2, norm(geomet)))
+}
+
+#[inline]
+fn rect_frm_sphr(long: f64, lat: f64, r: f64) -> (f64, f64, f64)
+{
+    (r*lat.cos()*long.cos(), r*lat.cos()*long.sin(), r*lat.sin())
+}
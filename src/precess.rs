@@ -23,8 +23,12 @@ THE SOFTWARE.
 //! Corrections for precession
 
 use angle;
+use nutation;
+use star;
 use std;
 use time;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 /**
 Computes annual precession in equatorial coordinates towards a new
@@ -105,13 +109,13 @@ pub fn precess_eq_coords(old_asc: f64,
                        T*angle::deg_frm_dms(0, 0, 0.000344)) +
                       t*angle::deg_frm_dms(0, 0, 0.017998))).to_radians();
 
-    let zeta = (x + t*t*((angle::deg_frm_dms(0, 0, 1.09468) -
+    let zeta = (x + t*t*((angle::deg_frm_dms(0, 0, 1.09468) +
                        T*angle::deg_frm_dms(0, 0, 0.000066)) +
                       t*angle::deg_frm_dms(0, 0, 0.018203))).to_radians();
 
     let y = T * angle::deg_frm_dms(0, 0, 0.000217);
-    let theta = (t * (angle::deg_frm_dms(0, 0, 2004.3109) +
-                   T * (angle::deg_frm_dms(0, 0, 0.8533) - y) -
+    let theta = (t * (angle::deg_frm_dms(0, 0, 2004.3109) -
+                   T * (angle::deg_frm_dms(0, 0, 0.8533) + y) -
                   t * ((angle::deg_frm_dms(0, 0, 0.42665) + y) +
                        t*angle::deg_frm_dms(0, 0, 0.041833)))).to_radians();
 
@@ -339,3 +343,423 @@ pub fn precess_orb_elements(old_inc: f64,
 
     (new_inc, old_arg_perih + delta_w, new_long_ascend_node)
 }
+
+/**
+Computes the Fukushima-Williams precession angles of the IAU 2006
+model
+
+The angles include the frame bias, so they rotate the GCRS to the
+mean equator and equinox of date.
+
+# Returns
+
+`(gamma, phi, psi, eps)`
+
+* `gamma`: Ecliptic longitude of the mean equinox of date,
+           measured from the GCRS origin *| in radians*
+* `phi`  : Obliquity of the ecliptic of date on the GCRS
+           equator *| in radians*
+* `psi`  : Precession angle along the ecliptic of date, from the
+           GCRS equator to the mean equator of date *| in radians*
+* `eps`  : Mean obliquity of the ecliptic of date *| in radians*
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn fw_angles(JD: f64) -> (f64, f64, f64, f64)
+{
+    let t = time::julian_cent(JD);
+    let arcsec = |a: f64| angle::deg_frm_dms(0, 0, a).to_radians();
+
+    (
+        arcsec(Horner_eval!(
+            t, -0.052928, 10.556378, 0.4932044, -0.00031238, -0.000002788, 0.0000000260
+        )),
+        arcsec(Horner_eval!(
+            t, 84381.412819, -46.811016, 0.0511268, 0.00053289, -0.000000440, -0.0000000176
+        )),
+        arcsec(Horner_eval!(
+            t, -0.041775, 5038.481484, 1.5584175, -0.00018522, -0.000026452, -0.0000000148
+        )),
+        arcsec(Horner_eval!(
+            t, 84381.406, -46.836769, -0.0001831, 0.00200340, -0.000000576, -0.0000000434
+        ))
+    )
+}
+
+/**
+Computes the frame bias matrix
+
+The frame bias is the small rotation from the ICRS to the mean
+equator and equinox of J2000.0, of the IAU 2006 model.
+
+# Returns
+
+* `bias_mat`: Rotation matrix from the ICRS to the mean equator
+              and equinox of J2000.0
+**/
+pub fn bias_mat() -> [[f64; 3]; 3]
+{
+    let mas = |a: f64| angle::deg_frm_dms(0, 0, a / 1000.0).to_radians();
+
+    let xi_0 = mas(-16.6170);
+    let eta_0 = mas(-6.8192);
+    let d_asc_0 = mas(-14.6);
+
+    let mut m = rot_z(d_asc_0);
+    m = mat_mul(&rot_y(xi_0), &m);
+
+    mat_mul(&rot_x(-eta_0), &m)
+}
+
+/**
+Computes the matrix of the frame bias and precession, of the IAU
+2006 model
+
+# Returns
+
+* `bias_precess_mat`: Rotation matrix from the ICRS to the mean
+                      equator and equinox of date
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn bias_precess_mat(JD: f64) -> [[f64; 3]; 3]
+{
+    let (gamma, phi, psi, eps) = fw_angles(JD);
+
+    fw_mat(gamma, phi, psi, eps)
+}
+
+/**
+Computes the precession matrix between two epochs, of the IAU
+2006 model
+
+The matrix rotates a vector referred to the mean equator and
+equinox of the old epoch to the mean equator and equinox of
+the new epoch. Unlike `precess_eq_coords()`, the matrix stays
+accurate over many thousands of years.
+
+# Returns
+
+* `matrix`: Rotation matrix from the old epoch to the new epoch
+
+# Arguments
+
+* `JD1`: Julian (Ephemeris) day corresponding to the old epoch
+* `JD2`: Julian (Ephemeris) day corresponding to the new epoch
+**/
+pub fn matrix(JD1: f64, JD2: f64) -> [[f64; 3]; 3]
+{
+    mat_mul(&bias_precess_mat(JD2), &transpose(&bias_precess_mat(JD1)))
+}
+
+/**
+Computes equatorial coordinates referred to the mean equator and
+equinox of date, from coordinates in the ICRS
+
+# Returns
+
+`(asc, dec)`
+
+* `asc`: Right ascension referred to the mean equator and equinox
+         of date *| in radians*
+* `dec`: Declination referred to the mean equator and equinox
+         of date *| in radians*
+
+# Arguments
+
+* `icrs_asc`: Right ascension in the ICRS *| in radians*
+* `icrs_dec`: Declination in the ICRS *| in radians*
+* `JD`      : Julian (Ephemeris) day
+**/
+pub fn mn_eq_frm_icrs(icrs_asc: f64, icrs_dec: f64, JD: f64) -> (f64, f64)
+{
+    sph_frm_vec(&mat_vec_mul(&bias_precess_mat(JD), &vec_frm_sph(icrs_asc, icrs_dec)))
+}
+
+/**
+Computes equatorial coordinates in the ICRS, from coordinates
+referred to the mean equator and equinox of date
+
+This is the inverse of `mn_eq_frm_icrs()`.
+
+# Returns
+
+`(icrs_asc, icrs_dec)`
+
+* `icrs_asc`: Right ascension in the ICRS *| in radians*
+* `icrs_dec`: Declination in the ICRS *| in radians*
+
+# Arguments
+
+* `asc`: Right ascension referred to the mean equator and equinox
+         of date *| in radians*
+* `dec`: Declination referred to the mean equator and equinox
+         of date *| in radians*
+* `JD` : Julian (Ephemeris) day
+**/
+pub fn icrs_frm_mn_eq(asc: f64, dec: f64, JD: f64) -> (f64, f64)
+{
+    sph_frm_vec(&mat_vec_mul(&transpose(&bias_precess_mat(JD)), &vec_frm_sph(asc, dec)))
+}
+
+/**
+Computes equatorial coordinates referred to the true equator and
+equinox of date, from coordinates in the ICRS
+
+The nutation is that of `nutation::nutation()`.
+
+# Returns
+
+`(asc, dec)`
+
+* `asc`: Right ascension referred to the true equator and equinox
+         of date *| in radians*
+* `dec`: Declination referred to the true equator and equinox
+         of date *| in radians*
+
+# Arguments
+
+* `icrs_asc`: Right ascension in the ICRS *| in radians*
+* `icrs_dec`: Declination in the ICRS *| in radians*
+* `JD`      : Julian (Ephemeris) day
+**/
+pub fn true_eq_frm_icrs(icrs_asc: f64, icrs_dec: f64, JD: f64) -> (f64, f64)
+{
+    sph_frm_vec(&mat_vec_mul(&bias_precess_nut_mat(JD), &vec_frm_sph(icrs_asc, icrs_dec)))
+}
+
+/**
+Computes equatorial coordinates in the ICRS, from coordinates
+referred to the true equator and equinox of date
+
+This is the inverse of `true_eq_frm_icrs()`.
+
+# Returns
+
+`(icrs_asc, icrs_dec)`
+
+* `icrs_asc`: Right ascension in the ICRS *| in radians*
+* `icrs_dec`: Declination in the ICRS *| in radians*
+
+# Arguments
+
+* `asc`: Right ascension referred to the true equator and equinox
+         of date *| in radians*
+* `dec`: Declination referred to the true equator and equinox
+         of date *| in radians*
+* `JD` : Julian (Ephemeris) day
+**/
+pub fn icrs_frm_true_eq(asc: f64, dec: f64, JD: f64) -> (f64, f64)
+{
+    sph_frm_vec(&mat_vec_mul(&transpose(&bias_precess_nut_mat(JD)), &vec_frm_sph(asc, dec)))
+}
+
+/**
+Computes equatorial coordinates referred to the FK5 system at J2000,
+from coordinates referred to the FK4 system at B1950 that were
+observed at a given epoch
+
+The E-terms of aberration included in FK4 positions are removed, and
+the equinox correction and the rotation of the proper motion system
+of FK4 are applied, by the method of Standish (1982). The star is
+taken to have no proper motion in the FK5 system, as is usual for
+positions from old catalogs and plates whose proper motions are
+unknown; otherwise [star::fk5_frm_fk4()](../star/fn.fk5_frm_fk4.html)
+should be used.
+
+# Returns
+
+`(asc, dec)`
+
+* `asc`: Right ascension referred to the FK5 system at J2000
+         *| in radians*
+* `dec`: Declination referred to the FK5 system at J2000
+         *| in radians*
+
+# Arguments
+
+* `asc`  : Right ascension referred to the FK4 system at B1950
+           *| in radians*
+* `dec`  : Declination referred to the FK4 system at B1950
+           *| in radians*
+* `epoch`: Julian (Ephemeris) day of the observation
+**/
+pub fn fk5_frm_fk4(asc: f64, dec: f64, epoch: f64) -> (f64, f64)
+{
+    let p = vec_frm_sph(asc, dec);
+
+    // E-terms at the epoch, so that the FK5 proper motion is zero
+    let w = (besselian_epoch(epoch) - 1950.0) / star::pmotion_factor();
+    let mut e = star::E_TERMS;
+    for (e, rate) in e.iter_mut().zip(star::E_TERMS_RATE.iter()) {
+        *e += w*rate;
+    }
+
+    let w = p[0]*e[0] + p[1]*e[1] + p[2]*e[2];
+    let mut p1 = [0.0; 3];
+    for i in 0..3 {
+        p1[i] = p[i] - e[i] + w*p[i];
+    }
+
+    let mut pv = [0.0; 6];
+    for (i, row) in star::FK4_TO_FK5.iter().enumerate() {
+        pv[i] = row[0]*p1[0] + row[1]*p1[1] + row[2]*p1[2];
+    }
+
+    // remove the fictitious proper motion of the FK4 system
+    let w = ((epoch - 2451545.0)/365.25) / star::pmotion_factor();
+    let p2 = [pv[0] + w*pv[3], pv[1] + w*pv[4], pv[2] + w*pv[5]];
+
+    let (asc, dec) = sph_frm_vec(&p2);
+
+    (angle::limit_to_two_PI(asc), dec)
+}
+
+/**
+Computes equatorial coordinates referred to the FK4 system at B1950,
+at a given epoch, from coordinates referred to the FK5 system at J2000
+
+This is the inverse of `fk5_frm_fk4()`, for a star with no proper
+motion in the FK5 system. Such a star has a fictitious proper motion
+in the FK4 system, due to its rotation with respect to FK5, which is
+returned along with the position at the epoch.
+
+# Returns
+
+`(asc, dec, pmotion_asc, pmotion_dec)`
+
+* `asc`        : Right ascension referred to the FK4 system at B1950,
+                 at `epoch` *| in radians*
+* `dec`        : Declination referred to the FK4 system at B1950, at
+                 `epoch` *| in radians*
+* `pmotion_asc`: Fictitious proper motion in right ascension *| in
+                 radians per tropical year*
+* `pmotion_dec`: Fictitious proper motion in declination *| in
+                 radians per tropical year*
+
+# Arguments
+
+* `asc`  : Right ascension referred to the FK5 system at J2000
+           *| in radians*
+* `dec`  : Declination referred to the FK5 system at J2000
+           *| in radians*
+* `epoch`: Julian (Ephemeris) day of the observation
+**/
+pub fn fk4_frm_fk5(asc: f64, dec: f64, epoch: f64) -> (f64, f64, f64, f64)
+{
+    let fk4 = star::fk4_frm_fk5(&star::MeanPlace {
+        asc,
+        dec,
+        pmotion_asc: 0.0,
+        pmotion_dec: 0.0,
+        parallax:    0.0,
+        rad_vel:     0.0
+    });
+
+    let t = besselian_epoch(epoch) - 1950.0;
+
+    (
+        angle::limit_to_two_PI(fk4.asc + fk4.pmotion_asc*t),
+        fk4.dec + fk4.pmotion_dec*t,
+        fk4.pmotion_asc,
+        fk4.pmotion_dec
+    )
+}
+
+// Besselian epoch of a Julian day, in tropical years
+#[inline]
+fn besselian_epoch(JD: f64) -> f64
+{
+    1900.0 + (JD - 2415020.31352)/365.242198781
+}
+
+// Matrix of the frame bias, precession and nutation, from the GCRS
+// to the true equator and equinox of date
+pub(crate) fn bias_precess_nut_mat(JD: f64) -> Mat
+{
+    let (gamma, phi, psi, eps) = fw_angles(JD);
+    let (nut_in_long, nut_in_oblq) = nutation::nutation(JD);
+
+    fw_mat(gamma, phi, psi + nut_in_long, eps + nut_in_oblq)
+}
+
+pub(crate) type Mat = [[f64; 3]; 3];
+
+// Matrix of the rotation through the Fukushima-Williams angles
+fn fw_mat(gamma: f64, phi: f64, psi: f64, eps: f64) -> Mat
+{
+    let mut m = rot_z(gamma);
+    m = mat_mul(&rot_x(phi), &m);
+    m = mat_mul(&rot_z(-psi), &m);
+
+    mat_mul(&rot_x(-eps), &m)
+}
+
+pub(crate) fn rot_x(a: f64) -> Mat
+{
+    let (s, c) = a.sin_cos();
+
+    [[1.0, 0.0, 0.0], [0.0, c, s], [0.0, -s, c]]
+}
+
+pub(crate) fn rot_y(a: f64) -> Mat
+{
+    let (s, c) = a.sin_cos();
+
+    [[c, 0.0, -s], [0.0, 1.0, 0.0], [s, 0.0, c]]
+}
+
+pub(crate) fn rot_z(a: f64) -> Mat
+{
+    let (s, c) = a.sin_cos();
+
+    [[c, s, 0.0], [-s, c, 0.0], [0.0, 0.0, 1.0]]
+}
+
+pub(crate) fn mat_mul(a: &Mat, b: &Mat) -> Mat
+{
+    let mut m = [[0.0; 3]; 3];
+    for i in 0..3 {
+        for j in 0..3 {
+            m[i][j] = a[i][0]*b[0][j] + a[i][1]*b[1][j] + a[i][2]*b[2][j];
+        }
+    }
+
+    m
+}
+
+pub(crate) fn transpose(a: &Mat) -> Mat
+{
+    [
+        [a[0][0], a[1][0], a[2][0]],
+        [a[0][1], a[1][1], a[2][1]],
+        [a[0][2], a[1][2], a[2][2]]
+    ]
+}
+
+pub(crate) fn mat_vec_mul(a: &Mat, v: &[f64; 3]) -> [f64; 3]
+{
+    [
+        a[0][0]*v[0] + a[0][1]*v[1] + a[0][2]*v[2],
+        a[1][0]*v[0] + a[1][1]*v[1] + a[1][2]*v[2],
+        a[2][0]*v[0] + a[2][1]*v[1] + a[2][2]*v[2]
+    ]
+}
+
+#[inline]
+pub(crate) fn vec_frm_sph(a: f64, b: f64) -> [f64; 3]
+{
+    [b.cos() * a.cos(), b.cos() * a.sin(), b.sin()]
+}
+
+pub(crate) fn sph_frm_vec(v: &[f64; 3]) -> (f64, f64)
+{
+    (
+        angle::limit_to_two_PI(v[1].atan2(v[0])),
+        v[2].atan2((v[0]*v[0] + v[1]*v[1]).sqrt())
+    )
+}
@@ -0,0 +1,224 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Doppler and gravitational redshifts
+
+use aberr;
+use consts;
+use observer;
+use planet;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/**
+Computes the redshift of light from a source moving along the line
+of sight
+
+The exact relativistic formula is used, and any transverse motion
+of the source is ignored.
+
+# Returns
+
+* `z`: Redshift, positive for a receding source
+
+# Arguments
+
+* `rad_vel`: Radial velocity of the source, positive when
+             receding *| in kilometers per second*
+**/
+pub fn z_frm_rad_vel(rad_vel: f64) -> f64
+{
+    let beta = rad_vel * 1000.0 / consts::SPEED_OF_LIGHT;
+
+    ((1.0 + beta) / (1.0 - beta)).sqrt() - 1.0
+}
+
+/**
+Computes the radial velocity of a source from the redshift of it's
+light
+
+This is the inverse of `z_frm_rad_vel()`.
+
+# Returns
+
+* `rad_vel`: Radial velocity of the source, positive when
+             receding *| in kilometers per second*
+
+# Arguments
+
+* `z`: Redshift, positive for a receding source
+**/
+pub fn rad_vel_frm_z(z: f64) -> f64
+{
+    let s = (1.0 + z) * (1.0 + z);
+
+    (s - 1.0) / (s + 1.0) * consts::SPEED_OF_LIGHT / 1000.0
+}
+
+/**
+Computes the combined redshift of two redshifts applied in
+succession
+
+Redshifts combine by multiplying `1 + z`, and not by addition.
+A redshift is removed by combining it with `-z / (1 + z)`.
+
+# Arguments
+
+* `z1`: First redshift
+* `z2`: Second redshift
+**/
+#[inline]
+pub fn combined_z(z1: f64, z2: f64) -> f64
+{
+    (1.0 + z1) * (1.0 + z2) - 1.0
+}
+
+/**
+Computes the gravitational redshift of light climbing out of the
+gravitational field of a spherical body
+
+# Returns
+
+* `z`: Redshift of the light
+
+# Arguments
+
+* `grav_param`: Gravitational constant times the mass of the
+                body *| in meters^3 per second^2*
+* `r_emit`    : Distance of the source from the center of the
+                body *| in meters*
+* `r_obs`     : Distance of the observer from the center of the
+                body *| in meters*
+**/
+pub fn grav_z(grav_param: f64, r_emit: f64, r_obs: f64) -> f64
+{
+    let c2 = consts::SPEED_OF_LIGHT * consts::SPEED_OF_LIGHT;
+
+    ((1.0 - 2.0*grav_param/(r_obs*c2)) / (1.0 - 2.0*grav_param/(r_emit*c2))).sqrt() - 1.0
+}
+
+/**
+Computes the gravitational redshift of light from the photosphere of
+the Sun, as seen by an observer in the Solar System
+
+Solar spectra, and radial velocities measured against them, must be
+corrected by this redshift, which is about 0.633 km/s at 1 AU.
+
+# Returns
+
+* `z`: Redshift of the light
+
+# Arguments
+
+* `dist`: Distance of the observer from the Sun *| in AU*
+**/
+#[inline]
+pub fn solar_grav_z(dist: f64) -> f64
+{
+    grav_z(consts::SUN_GRAV_PARAM, consts::SUN_RADIUS, dist * consts::ASTRONOMICAL_UNIT)
+}
+
+/**
+Computes the barycentric correction of a redshift measured by an
+observer on the Earth
+
+The observed redshift of a star is shifted by the observer's motion
+about the barycenter of the solar system, and by the rate of the
+observer's clock, which is slowed by that motion and by the
+gravitational field of the Sun. The correction removes both, as
+
+`1 + z_bary = (1 + z_meas) * (1 + z_corr)`
+
+which is done with [combined_z()](./fn.combined_z.html). The Earth's
+barycentric velocity is that of the series of Ron and Vondrák in
+[aberr::earth_vel()](../aberr/fn.earth_vel.html), and the observer's
+velocity about the center of the Earth is added to it, so that the
+result is good to about a meter per second.
+
+# Returns
+
+* `z_corr`: Barycentric correction of the redshift
+
+# Arguments
+
+* `JD`      : Julian (Ephemeris) day of observation
+* `asc`     : Right ascension of the star, referred to the mean
+              equator and equinox of J2000.0 *| in radians*
+* `dec`     : Declination of the star, referred to the mean equator
+              and equinox of J2000.0 *| in radians*
+* `observer`: The [Observer](../observer/struct.Observer.html)
+**/
+pub fn bary_z_corr(JD: f64, asc: f64, dec: f64, observer: &observer::Observer) -> f64
+{
+    let earth_vel = aberr::earth_vel(JD);
+    let (_, obs_vel) = observer.geocent_rect_state(JD);
+
+    // to meters per second
+    let k = consts::ASTRONOMICAL_UNIT / 86400.0;
+    let v = (
+        (earth_vel.0 + obs_vel.0) * k,
+        (earth_vel.1 + obs_vel.1) * k,
+        (earth_vel.2 + obs_vel.2) * k
+    );
+
+    let c = consts::SPEED_OF_LIGHT;
+    let n = (dec.cos()*asc.cos(), dec.cos()*asc.sin(), dec.sin());
+    let beta = (v.0*n.0 + v.1*n.1 + v.2*n.2) / c;
+
+    let sun_dist = planet::heliocent_coords(&planet::Planet::Earth, JD).2 * consts::ASTRONOMICAL_UNIT;
+    let clock_rate =
+        1.0
+      - consts::SUN_GRAV_PARAM / (sun_dist * c * c)
+      - (v.0*v.0 + v.1*v.1 + v.2*v.2) / (2.0 * c * c);
+
+    (1.0 + beta) / clock_rate - 1.0
+}
+
+/**
+Computes the barycentric correction of a radial velocity measured by
+an observer on the Earth
+
+This is the correction of [bary_z_corr()](./fn.bary_z_corr.html),
+expressed as a velocity, as it is usually tabulated. Adding it to a
+measured radial velocity is good to about a meter per second for
+velocities of upto a few kilometers per second; otherwise, combine
+the redshifts.
+
+# Returns
+
+* `rad_vel_corr`: Barycentric correction of the radial velocity,
+                  positive when the observer moves towards the star
+                  *| in kilometers per second*
+
+# Arguments
+
+* `JD`      : Julian (Ephemeris) day of observation
+* `asc`     : Right ascension of the star, referred to the mean
+              equator and equinox of J2000.0 *| in radians*
+* `dec`     : Declination of the star, referred to the mean equator
+              and equinox of J2000.0 *| in radians*
+* `observer`: The [Observer](../observer/struct.Observer.html)
+**/
+pub fn bary_rad_vel_corr(JD: f64, asc: f64, dec: f64, observer: &observer::Observer) -> f64
+{
+    bary_z_corr(JD, asc, dec, observer) * consts::SPEED_OF_LIGHT / 1000.0
+}
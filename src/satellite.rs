@@ -0,0 +1,397 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Artificial Earth satellites
+
+use angle;
+use consts;
+use coords;
+use ecliptic;
+use error;
+use numerics;
+use observer;
+use planet;
+use planet::earth;
+use time;
+use std::f64::consts::PI;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Represents a model of the Earth's shadow
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ShadowModel {
+    /// Cylindrical shadow having the Earth's radius, with no penumbra
+    Cylindrical,
+    /// Conical shadow cast by the finite solar disk, with umbra and
+    /// penumbra
+    Conical
+}
+
+/// Represents the illumination of a satellite by the Sun
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Shadow {
+    /// The whole solar disk is visible
+    Sunlit,
+    /// Only a part of the solar disk is visible
+    Penumbra,
+    /// No part of the solar disk is visible
+    Umbra
+}
+
+/// Holds a change in the illumination of a satellite
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ShadowTransition {
+    /// Julian day of the change
+    pub JD: f64,
+    /// Illumination before the change
+    pub from: Shadow,
+    /// Illumination after the change
+    pub to: Shadow
+}
+
+/**
+Computes the Sun's geocentric equatorial rectangular coordinates in
+kilometers, referred to the mean equator and equinox of the date
+
+# Returns
+
+`(x, y, z)`
+
+* `x`, `y`, `z`: The Sun's geocentric position *| in kilometers*
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn sun_geocent_eq_rect_coords(JD: f64) -> (f64, f64, f64) {
+
+    let (L, B, R) = planet::heliocent_coords(&planet::Planet::Earth, JD);
+    let R = R * consts::ASTRONOMICAL_UNIT / 1000.0;
+    let oblq = ecliptic::mn_oblq_IAU(JD);
+
+    let (x, y, z) = (-R*B.cos()*L.cos(), -R*B.cos()*L.sin(), -R*B.sin());
+
+    (x, y*oblq.cos() - z*oblq.sin(), y*oblq.sin() + z*oblq.cos())
+
+}
+
+/**
+Computes the illumination of a satellite by the Sun
+
+# Returns
+
+* `shadow`: [Shadow](./enum.Shadow.html) the satellite is in
+
+# Arguments
+
+* `sat_pos`: Geocentric rectangular position of the satellite
+             *| in kilometers*
+* `sun_pos`: Geocentric rectangular position of the Sun, in the same
+             frame *| in kilometers*
+* `model`  : The [ShadowModel](./enum.ShadowModel.html) to use
+**/
+pub fn shadow (
+
+    sat_pos : (f64, f64, f64),
+    sun_pos : (f64, f64, f64),
+    model   : &ShadowModel
+
+) -> Shadow {
+
+    match *model {
+        ShadowModel::Cylindrical => {
+            let sun_dist = norm(sun_pos);
+            let proj = dot(sat_pos, sun_pos) / sun_dist;
+            let perp_sqr = dot(sat_pos, sat_pos) - proj*proj;
+
+            if proj < 0.0 && perp_sqr < EARTH_RADIUS*EARTH_RADIUS {
+                Shadow::Umbra
+            } else {
+                Shadow::Sunlit
+            }
+        },
+        ShadowModel::Conical => {
+            let (a, b, c) = disks(sat_pos, sun_pos);
+
+            if c >= a + b {
+                Shadow::Sunlit
+            } else if c <= b - a {
+                Shadow::Umbra
+            } else {
+                Shadow::Penumbra
+            }
+        }
+    }
+
+}
+
+/**
+Computes the fraction of the solar disk visible from a satellite,
+with the Earth as an occulting disk
+
+# Returns
+
+* `sun_visible_frac`: Fraction of the solar disk's area visible from
+                      the satellite. *range: 0.0 to 1.0*
+
+# Arguments
+
+* `sat_pos`: Geocentric rectangular position of the satellite
+             *| in kilometers*
+* `sun_pos`: Geocentric rectangular position of the Sun, in the same
+             frame *| in kilometers*
+**/
+pub fn sun_visible_frac(sat_pos: (f64, f64, f64), sun_pos: (f64, f64, f64)) -> f64 {
+
+    let (a, b, c) = disks(sat_pos, sun_pos);
+
+    if c >= a + b {
+        return 1.0;
+    }
+    if c <= b - a {
+        return 0.0;
+    }
+    if c <= a - b {
+        return 1.0 - (b*b)/(a*a);
+    }
+
+    // area of overlap of two circles of radii a and b, c apart
+    let x = (c*c + a*a - b*b) / (2.0*c);
+    let y = (a*a - x*x).max(0.0).sqrt();
+    let overlap =
+        a*a*(x/a).clamp(-1.0, 1.0).acos()
+      + b*b*((c - x)/b).clamp(-1.0, 1.0).acos()
+      - c*y;
+
+    1.0 - overlap/(PI*a*a)
+
+}
+
+/**
+Computes the times at which a satellite enters and leaves the Earth's
+shadow
+
+The satellite's illumination is sampled at every `step`, and each
+change found is refined by bisection.
+
+# Returns
+
+* `Ok(transitions)`: [ShadowTransitions](./struct.ShadowTransition.html)
+                     in chronological order, or
+* `Err(error)`     : `Error::NotPositive` if `step` isn't positive,
+                     or `Error::NotFinite`
+
+# Arguments
+
+* `sat_pos` : Function giving the satellite's geocentric equatorial
+              rectangular position *| in kilometers*, for a Julian day,
+              eg: from an SGP4 propagator
+* `JD_start`: Julian day at the start of the search
+* `JD_end`  : Julian day at the end of the search
+* `step`    : Sampling interval, positive *| in days*. Should be well
+              below the duration of the shortest shadow passage.
+* `model`   : The [ShadowModel](./enum.ShadowModel.html) to use
+**/
+pub fn shadow_transitions<F> (
+
+    sat_pos  : F,
+    JD_start : f64,
+    JD_end   : f64,
+    step     : f64,
+    model    : &ShadowModel
+
+) -> Result<Vec<ShadowTransition>, error::Error> where F: Fn(f64) -> (f64, f64, f64) {
+
+    error::check_positive(step)?;
+    if !JD_start.is_finite() || !JD_end.is_finite() {
+        return Err(error::Error::NotFinite);
+    }
+
+    let shadow_at = |JD: f64| shadow(sat_pos(JD), sun_geocent_eq_rect_coords(JD), model);
+
+    let mut transitions = Vec::new();
+    let mut JD_prev = JD_start;
+    let mut prev = shadow_at(JD_prev);
+
+    while JD_prev < JD_end {
+        let JD_next = (JD_prev + step).min(JD_end);
+        let next = shadow_at(JD_next);
+
+        if next != prev {
+            refine(&shadow_at, (JD_prev, prev), (JD_next, next), &mut transitions);
+        }
+
+        JD_prev = JD_next;
+        prev = next;
+    }
+
+    Ok(transitions)
+
+}
+
+/// Holds the view of a satellite from an observer on the Earth
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TopocentView {
+    /// Horizontal point of the satellite, as seen through the local
+    /// atmosphere *| in radians*
+    pub hz_point: coords::HzPoint,
+    /// Distance of the satellite from the observer *| in kilometers*
+    pub range: f64,
+    /// Rate of change of the range, positive when the satellite
+    /// recedes *| in kilometers per second*
+    pub range_rate: f64,
+    /// Illumination of the satellite by the Sun
+    pub shadow: Shadow
+}
+
+/**
+Computes the view of a satellite from an observer on the Earth, from
+the satellite's geocentric state vectors
+
+The state vectors may come from any propagator, such as SGP4, and are
+rotated to the observer's horizon by the mean sidereal time, as the
+TEME frame of SGP4 is.
+
+# Returns
+
+* `view`: The [TopocentView](./struct.TopocentView.html) of the
+          satellite
+
+# Arguments
+
+* `sat_pos` : Geocentric rectangular position of the satellite,
+              referred to the equator and equinox of the date
+              *| in kilometers*
+* `sat_vel` : Geocentric velocity of the satellite, in the same frame
+              *| in kilometers per second*
+* `JD`      : Julian (Universal) day
+* `observer`: The [Observer](../observer/struct.Observer.html)
+* `model`   : The [ShadowModel](./enum.ShadowModel.html) to use
+**/
+pub fn topocent_view (
+
+    sat_pos  : (f64, f64, f64),
+    sat_vel  : (f64, f64, f64),
+    JD       : f64,
+    observer : &observer::Observer,
+    model    : &ShadowModel
+
+) -> TopocentView {
+
+    let green_sidr = time::mn_sidr(JD);
+    let loc_sidr = green_sidr - observer.long;
+
+    let (rho_sin_phi, rho_cos_phi) = earth::rho_sin_cos_phi(observer.lat, observer.height);
+    let r = earth::eq_rad();
+    let obs_pos = (r*rho_cos_phi*loc_sidr.cos(), r*rho_cos_phi*loc_sidr.sin(), r*rho_sin_phi);
+
+    // rate of rotation of the Earth *| in radians per second*
+    let w = angle::TWO_PI * 1.002737811911354 / 86400.0;
+    let obs_vel = (-w*obs_pos.1, w*obs_pos.0, 0.0);
+
+    let rel_pos = (sat_pos.0 - obs_pos.0, sat_pos.1 - obs_pos.1, sat_pos.2 - obs_pos.2);
+    let rel_vel = (sat_vel.0 - obs_vel.0, sat_vel.1 - obs_vel.1, sat_vel.2 - obs_vel.2);
+    let range = norm(rel_pos);
+
+    let eq_point = coords::EqPoint {
+        asc: rel_pos.1.atan2(rel_pos.0),
+        dec: (rel_pos.2 / range).asin()
+    };
+
+    TopocentView {
+        hz_point: observer.apprnt_hz(&eq_point, green_sidr),
+        range,
+        range_rate: dot(rel_pos, rel_vel) / range,
+        shadow: shadow(sat_pos, sun_geocent_eq_rect_coords(time::tt_frm_ut(JD)), model)
+    }
+
+}
+
+// Bisects for the instant(s) of change of illumination between two
+// times, continuing after a change to an intermediate state
+fn refine<G> (
+
+    shadow_at   : &G,
+    lo          : (f64, Shadow),
+    hi          : (f64, Shadow),
+    transitions : &mut Vec<ShadowTransition>
+
+) where G: Fn(f64) -> Shadow {
+
+    let mut lo = lo;
+
+    while lo.1 != hi.1 {
+        // -1 before the change from the state at lo, and 1 after it
+        let changed = |JD: f64| if shadow_at(JD) == lo.1 { -1.0 } else { 1.0 };
+        let JD = match numerics::bisection(changed, lo.0, hi.0, 1e-7) {
+            Some(JD) => JD,
+            None     => return
+        };
+
+        let JD_after = (JD + 1e-7).min(hi.0);
+        let to = if JD_after < hi.0 { shadow_at(JD_after) } else { hi.1 };
+        transitions.push(ShadowTransition { JD, from: lo.1, to });
+
+        lo = (JD_after, to);
+    }
+
+}
+
+const EARTH_RADIUS: f64 = consts::wgs84::EQUATORIAL_RADIUS / 1000.0;
+
+const SUN_RADIUS: f64 = 696000.0;
+
+#[inline]
+fn dot(a: (f64, f64, f64), b: (f64, f64, f64)) -> f64 {
+
+    a.0*b.0 + a.1*b.1 + a.2*b.2
+
+}
+
+#[inline]
+fn norm(a: (f64, f64, f64)) -> f64 {
+
+    dot(a, a).sqrt()
+
+}
+
+// Apparent radii of the Sun and the Earth as seen from the satellite,
+// and the angular separation between their centers
+fn disks(sat_pos: (f64, f64, f64), sun_pos: (f64, f64, f64)) -> (f64, f64, f64) {
+
+    let to_sun = (sun_pos.0 - sat_pos.0, sun_pos.1 - sat_pos.1, sun_pos.2 - sat_pos.2);
+    let to_earth = (-sat_pos.0, -sat_pos.1, -sat_pos.2);
+
+    let sun_dist = norm(to_sun);
+    let earth_dist = norm(to_earth);
+
+    let a = (SUN_RADIUS / sun_dist).asin();
+    let b = (EARTH_RADIUS / earth_dist).min(1.0).asin();
+    let c = (dot(to_sun, to_earth) / (sun_dist * earth_dist)).clamp(-1.0, 1.0).acos();
+
+    (a, b, c)
+
+}
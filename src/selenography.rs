@@ -0,0 +1,476 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Named features of the lunar surface
+
+use aberr;
+use angle;
+use consts;
+use coords;
+use ecliptic;
+use lunar;
+use nutation;
+use sun;
+use time;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+const DEG: f64 = std::f64::consts::PI / 180.0;
+
+/// Mean radius of the Moon *| in kilometers*
+const MOON_RADIUS: f64 = 1737.4;
+
+/// Represents a kind of lunar feature
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FeatureKind {
+    /// Impact crater
+    Crater,
+    /// Mare
+    Mare,
+    /// Oceanus Procellarum
+    Oceanus,
+    /// Bay on the edge of a mare
+    Sinus
+}
+
+/// Represents a named lunar feature
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Feature {
+    /// Name of the feature
+    pub name: &'static str,
+    /// Kind of the feature
+    pub kind: FeatureKind,
+    /// Selenographic longitude, positive towards Mare Crisium
+    /// *| in radians*
+    pub long: f64,
+    /// Selenographic latitude *| in radians*
+    pub lat: f64,
+    /// Diameter *| in kilometers*
+    pub diameter: f64
+}
+
+macro_rules! feature {
+    ($name: expr, $kind: ident, $lat: expr, $long: expr, $diameter: expr) => {
+        Feature {
+            name: $name,
+            kind: FeatureKind::$kind,
+            long: $long * DEG,
+            lat: $lat * DEG,
+            diameter: $diameter
+        }
+    };
+}
+
+/// Prominent lunar features, with the selenographic coordinates of
+/// their centers from the IAU Gazetteer of Planetary Nomenclature
+pub const FEATURES: [Feature; 30] = [
+    feature!("Archimedes",           Crater,   29.72,  -3.99,   81.0),
+    feature!("Aristarchus",          Crater,   23.73, -47.49,   40.0),
+    feature!("Clavius",              Crater,  -58.62, -14.73,  231.0),
+    feature!("Copernicus",           Crater,    9.62, -20.08,   96.0),
+    feature!("Eratosthenes",         Crater,   14.47, -11.32,   58.0),
+    feature!("Gassendi",             Crater,  -17.55, -39.96,  110.0),
+    feature!("Grimaldi",             Crater,   -5.52, -68.33,  172.0),
+    feature!("Kepler",               Crater,    8.12, -38.01,   31.0),
+    feature!("Langrenus",            Crater,   -8.86,  61.04,  132.0),
+    feature!("Manilius",             Crater,   14.45,   9.06,   38.0),
+    feature!("Petavius",             Crater,  -25.30,  60.40,  188.0),
+    feature!("Plato",                Crater,   51.62,  -9.38,  101.0),
+    feature!("Posidonius",           Crater,   31.88,  29.99,   95.0),
+    feature!("Proclus",              Crater,   16.10,  46.84,   27.0),
+    feature!("Ptolemaeus",           Crater,   -9.16,  -1.84,  153.0),
+    feature!("Schickard",            Crater,  -44.40, -54.60,  206.0),
+    feature!("Theophilus",           Crater,  -11.45,  26.28,   99.0),
+    feature!("Tycho",                Crater,  -43.31, -11.36,   85.0),
+    feature!("Mare Crisium",         Mare,     17.00,  59.10,  556.0),
+    feature!("Mare Fecunditatis",    Mare,     -7.80,  51.30,  909.0),
+    feature!("Mare Frigoris",        Mare,     56.00,   1.40, 1596.0),
+    feature!("Mare Humorum",         Mare,    -24.40, -38.60,  389.0),
+    feature!("Mare Imbrium",         Mare,     32.80, -15.60, 1146.0),
+    feature!("Mare Nectaris",        Mare,    -15.20,  35.50,  333.0),
+    feature!("Mare Nubium",          Mare,    -21.30, -16.60,  715.0),
+    feature!("Mare Serenitatis",     Mare,     28.00,  17.50,  707.0),
+    feature!("Mare Tranquillitatis", Mare,      8.50,  31.40,  873.0),
+    feature!("Mare Vaporum",         Mare,     13.30,   3.60,  245.0),
+    feature!("Oceanus Procellarum",  Oceanus,  18.40, -57.40, 2568.0),
+    feature!("Sinus Iridum",         Sinus,    44.10, -31.50,  236.0),
+];
+
+/**
+Computes the geocentric orientation of the Moon's disk
+
+# Returns
+
+`(libr_long, libr_lat, pos_angl_axis)`
+
+* `libr_long`    : Total libration in longitude, the selenographic
+  longitude of the center of the disk *| in radians*
+* `libr_lat`     : Total libration in latitude, the selenographic
+  latitude of the center of the disk *| in radians*
+* `pos_angl_axis`: Position angle of the Moon's axis of rotation
+  *| in radians*
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn geocent_orientation(JD: f64) -> (f64, f64, f64) {
+
+    let (moon_point, _) = lunar::geocent_ecl_pos(JD);
+    let (nut_long, nut_oblq) = nutation::nutation(JD);
+    let true_oblq = ecliptic::mn_oblq_laskar(JD) + nut_oblq;
+    let apprnt_asc = coords::asc_frm_ecl(moon_point.long + nut_long, moon_point.lat, true_oblq);
+
+    let (libr_long, libr_lat) = lunar::total_libr(JD, moon_point.long, moon_point.lat);
+    let libr_long = angle::limit_to_pm_PI(libr_long);
+
+    let pos_angl_axis = lunar::pos_angl_of_axis_of_rot(
+        JD,
+        lunar::mn_ascend_node(time::julian_cent(JD)),
+        libr_lat,
+        nut_long,
+        true_oblq,
+        apprnt_asc
+    );
+
+    (libr_long, libr_lat, pos_angl_axis)
+
+}
+
+/**
+Computes the position on the Moon's apparent disk of a point on the
+lunar surface
+
+# Returns
+
+* `Some((east, north))`: Offsets of the point from the center of the
+  disk towards the celestial east and north, in units of the Moon's
+  apparent radius, or
+* `None`               : if the point is on the far side of the Moon
+
+# Arguments
+
+* `long`         : Selenographic longitude of the point *| in radians*
+* `lat`          : Selenographic latitude of the point *| in radians*
+* `libr_long`    : Total libration in longitude *| in radians*
+* `libr_lat`     : Total libration in latitude *| in radians*
+* `pos_angl_axis`: Position angle of the Moon's axis of rotation
+  *| in radians*
+**/
+pub fn disk_pos_frm_selenographic (
+
+    long          : f64,
+    lat           : f64,
+    libr_long     : f64,
+    libr_lat      : f64,
+    pos_angl_axis : f64
+
+) -> Option<(f64, f64)> {
+
+    let d_long = long - libr_long;
+    let toward_earth =
+        lat.cos() * libr_lat.cos() * d_long.cos() + lat.sin() * libr_lat.sin();
+    if toward_earth < 0.0 {
+        return None;
+    }
+
+    let xi = lat.cos() * d_long.sin();
+    let eta = lat.sin()*libr_lat.cos() - lat.cos()*libr_lat.sin()*d_long.cos();
+
+    Some(rotate_to_sky(xi, eta, pos_angl_axis))
+
+}
+
+/**
+Computes the selenographic coordinates of a point on the Moon's
+apparent disk
+
+# Returns
+
+* `Some((long, lat))`: Selenographic longitude and latitude of the
+  point *| in radians*, or
+* `None`             : if the point is off the disk
+
+# Arguments
+
+* `east`         : Offset of the point from the center of the disk
+  towards the celestial east, in units of the Moon's apparent radius
+* `north`        : Offset of the point from the center of the disk
+  towards the celestial north, in units of the Moon's apparent radius
+* `libr_long`    : Total libration in longitude *| in radians*
+* `libr_lat`     : Total libration in latitude *| in radians*
+* `pos_angl_axis`: Position angle of the Moon's axis of rotation
+  *| in radians*
+**/
+pub fn selenographic_frm_disk_pos (
+
+    east          : f64,
+    north         : f64,
+    libr_long     : f64,
+    libr_lat      : f64,
+    pos_angl_axis : f64
+
+) -> Option<(f64, f64)> {
+
+    // the rotation to the sky is its own inverse
+    let (xi, eta) = rotate_to_sky(east, north, pos_angl_axis);
+    let zeta_sqr = 1.0 - xi*xi - eta*eta;
+    if zeta_sqr < 0.0 {
+        return None;
+    }
+    let zeta = zeta_sqr.sqrt();
+
+    let (sin_l, cos_l) = libr_long.sin_cos();
+    let (sin_b, cos_b) = libr_lat.sin_cos();
+    let x = zeta*cos_b*cos_l - xi*sin_l - eta*sin_b*cos_l;
+    let y = zeta*cos_b*sin_l + xi*cos_l - eta*sin_b*sin_l;
+    let z = zeta*sin_b + eta*cos_b;
+
+    Some((y.atan2(x), z.clamp(-1.0, 1.0).asin()))
+
+}
+
+/**
+Computes the geocentric position of a lunar feature on the Moon's
+apparent disk
+
+# Returns
+
+* `Some((east, north))`: Offsets of the feature's center from the
+  center of the disk towards the celestial east and north, in units of
+  the Moon's apparent radius, or
+* `None`               : if the feature's center is on the far side
+
+# Arguments
+
+* `feature`: The lunar feature
+* `JD`     : Julian (Ephemeris) day
+**/
+pub fn feature_disk_pos(feature: &Feature, JD: f64) -> Option<(f64, f64)> {
+
+    let (libr_long, libr_lat, pos_angl_axis) = geocent_orientation(JD);
+
+    disk_pos_frm_selenographic(feature.long, feature.lat, libr_long, libr_lat, pos_angl_axis)
+
+}
+
+/**
+Finds the lunar features near a point on the lunar surface
+
+# Returns
+
+* `features`: The features whose edges are within `max_sepr` of the
+  point, each with the angular distance of its edge from the point
+  *| in radians*, nearest first. The distance is zero for a feature
+  containing the point.
+
+# Arguments
+
+* `long`    : Selenographic longitude of the point *| in radians*
+* `lat`     : Selenographic latitude of the point *| in radians*
+* `max_sepr`: Greatest angular distance of a feature's edge from the
+  point, measured from the center of the Moon *| in radians*
+**/
+pub fn features_near(long: f64, lat: f64, max_sepr: f64) -> Vec<(&'static Feature, f64)> {
+
+    let mut near: Vec<(&'static Feature, f64)> = FEATURES.iter()
+        .map(|f| {
+            let radius = f.diameter / (2.0 * MOON_RADIUS);
+            (f, (angle::anglr_sepr(long, lat, f.long, f.lat) - radius).max(0.0))
+        })
+        .filter(|&(_, sepr)| sepr <= max_sepr)
+        .collect();
+
+    near.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+
+    near
+
+}
+
+// Rotates offsets along the lunar east and north into offsets along
+// the celestial east and north. Lunar east, towards Mare Crisium,
+// points to the celestial west when the axis points north.
+#[inline]
+fn rotate_to_sky(xi: f64, eta: f64, pos_angl_axis: f64) -> (f64, f64) {
+
+    let (sin_P, cos_P) = pos_angl_axis.sin_cos();
+
+    (-xi*cos_P + eta*sin_P, xi*sin_P + eta*cos_P)
+
+}
+
+/**
+Computes the selenographic position of the Sun
+
+The Sun's selenographic coordinates are found as the librations of
+the Moon seen from the Sun rather than from the Earth, by the method
+of *Meeus* (Astronomical Algorithms, chapter 53). The morning
+terminator lies at the selenographic longitude `-colong`, so the
+colongitude is about 270 degrees at New Moon, 0 at First Quarter, 90
+at Full Moon and 180 at Last Quarter.
+
+# Returns
+
+`(long, lat, colong)`
+
+* `long`  : Selenographic longitude of the Sun *| in radians*, between
+  `-PI` and `PI`
+* `lat`   : Selenographic latitude of the Sun *| in radians*
+* `colong`: Colongitude of the Sun *| in radians*, between `0` and
+  `2PI`
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn sun_selenographic_pos(JD: f64) -> (f64, f64, f64) {
+
+    let (moon_point, moon_dist) = lunar::geocent_ecl_pos(JD);
+    let (sun_point, sun_dist) = sun::geocent_ecl_pos(JD);
+
+    // the Sun's longitude is taken without the nutation, like the
+    // Moon's in the librations
+    let sun_long = sun_point.long + aberr::sol_aberr(sun_dist);
+
+    // the heliocentric longitude and latitude of the Moon
+    let dist_ratio = moon_dist / (sun_dist * consts::ASTRONOMICAL_UNIT / 1000.0);
+    let helio_long = sun_long + std::f64::consts::PI
+                   + dist_ratio * moon_point.lat.cos() * (sun_long - moon_point.long).sin();
+    let helio_lat = dist_ratio * moon_point.lat;
+
+    let (long, lat) = lunar::total_libr(JD, helio_long, helio_lat);
+
+    (
+        angle::limit_to_pm_PI(long),
+        lat,
+        angle::limit_to_two_PI(std::f64::consts::FRAC_PI_2 - long)
+    )
+
+}
+
+/**
+Computes the altitude of the Sun over a point on the lunar surface
+
+# Returns
+
+* `alt`: Altitude of the center of the Sun over the point's horizon,
+  negative if the Sun is below it *| in radians*
+
+# Arguments
+
+* `long`    : Selenographic longitude of the point *| in radians*
+* `lat`     : Selenographic latitude of the point *| in radians*
+* `sun_long`: Selenographic longitude of the Sun *| in radians*
+* `sun_lat` : Selenographic latitude of the Sun *| in radians*
+**/
+pub fn sun_alt(long: f64, lat: f64, sun_long: f64, sun_lat: f64) -> f64 {
+
+    (
+        sun_lat.sin() * lat.sin()
+      + sun_lat.cos() * lat.cos() * (long - sun_long).cos()
+    ).clamp(-1.0, 1.0).asin()
+
+}
+
+/**
+Computes the altitude of the Sun over a lunar feature
+
+# Returns
+
+* `alt`: Altitude of the center of the Sun over the horizon of the
+  feature's center *| in radians*
+
+# Arguments
+
+* `feature`: The lunar feature
+* `JD`     : Julian (Ephemeris) day
+**/
+pub fn sun_alt_over_feature(feature: &Feature, JD: f64) -> f64 {
+
+    let (sun_long, sun_lat, _) = sun_selenographic_pos(JD);
+
+    sun_alt(feature.long, feature.lat, sun_long, sun_lat)
+
+}
+
+/**
+Computes the times of the next sunrise and sunset over a point on
+the lunar surface
+
+# Returns
+
+`(rise, set)`
+
+* `rise`: Julian (Ephemeris) day of the first sunrise after `JD`, or
+  `None` if the Sun doesn't rise over the point within a lunation
+* `set` : Julian (Ephemeris) day of the first sunset after `JD`, or
+  `None` if the Sun doesn't set over the point within a lunation
+
+The center of the Sun rises and sets on the point's horizon, which is
+taken to be flat. Near the poles, where the Sun's selenographic
+latitude can keep the Sun above or below the horizon, there may be no
+sunrise or sunset.
+
+# Arguments
+
+* `long`: Selenographic longitude of the point *| in radians*
+* `lat` : Selenographic latitude of the point *| in radians*
+* `JD`  : Julian (Ephemeris) day to search from
+**/
+pub fn sunrise_sunset(long: f64, lat: f64, JD: f64) -> (Option<f64>, Option<f64>) {
+
+    // the colongitude increases by a revolution each synodic month
+    let colong_rate = angle::TWO_PI / 29.530589;
+
+    // the Julian day at which the colongitude reaches the sunrise
+    // (sign = 1) or sunset (sign = -1) over the point, where
+    // sin(colong + long) = -tan(sun_lat) * tan(lat)
+    let crossing = |sign: f64| {
+        let mut JD_i = JD;
+        for i in 0..20 {
+            let (_, sun_lat, colong) = sun_selenographic_pos(JD_i);
+            let sin_x = -sun_lat.tan() * lat.tan();
+            if sin_x.abs() > 1.0 {
+                return None;
+            }
+            let x = if sign > 0.0 { sin_x.asin() } else { std::f64::consts::PI - sin_x.asin() };
+
+            // the first step is always forwards, to the next crossing
+            let dc = if i == 0 {
+                angle::limit_to_two_PI(x - long - colong)
+            } else {
+                angle::limit_to_pm_PI(x - long - colong)
+            };
+            JD_i += dc / colong_rate;
+
+            if dc.abs() < 1e-7 {
+                return if JD_i - JD < 1.1 * 29.530589 { Some(JD_i) } else { None };
+            }
+        }
+        None
+    };
+
+    (crossing(1.0), crossing(-1.0))
+
+}
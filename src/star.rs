@@ -22,6 +22,16 @@ THE SOFTWARE.
 
 //! Stars
 
+use aberr;
+use angle;
+use coords;
+use planet;
+use precess;
+use std;
+use sun;
+#[cfg(not(feature = "std"))]
+use math::*;
+
 /**
 Computes the combined magnitude of two stars
 
@@ -33,7 +43,7 @@ Computes the combined magnitude of two stars
 #[inline]
 pub fn combined_mag(m1: f64, m2: f64) -> f64
 {
-    m2 - 2.5 * (brightness_ratio(m1, m2) + 1.0)
+    m2 - 2.5 * (brightness_ratio(m1, m2) + 1.0).log10()
 }
 
 /**
@@ -103,13 +113,77 @@ Computes the absolute magnitude of a star from its distance from earth
 * `d`: The star's to earth *(parsecs)*
 * `am`: Apparent magnitude of the star
 **/
-
 #[inline]
 pub fn abs_mag_frm_dist(d: f64, am: f64) -> f64
 {
     am + 5.0 - 5.0*d.log10()
 }
 
+/**
+Computes the distance modulus of a star from its distance from earth
+
+# Arguments
+
+* `d`: The star's distance from earth *(parsecs)*
+**/
+#[inline]
+pub fn dist_modulus(d: f64) -> f64
+{
+    5.0*d.log10() - 5.0
+}
+
+/**
+Computes the distance of a star from earth from its distance modulus
+*(parsecs)*
+
+# Arguments
+
+* `dm`: Distance modulus, the apparent less the absolute magnitude
+**/
+#[inline]
+pub fn dist_frm_dist_modulus(dm: f64) -> f64
+{
+    10_f64.powf((dm + 5.0) / 5.0)
+}
+
+/**
+Computes the apparent magnitude of a star from its absolute magnitude
+and distance from earth
+
+# Arguments
+
+* `abs_mag`: Absolute magnitude of the star
+* `d`      : The star's distance from earth *(parsecs)*
+**/
+#[inline]
+pub fn apprnt_mag_frm_abs_mag(abs_mag: f64, d: f64) -> f64
+{
+    abs_mag + dist_modulus(d)
+}
+
+/**
+Computes the mean surface brightness of an extended object, such as a
+galaxy or a nebula, taken to be an elliptical disk
+
+# Returns
+
+* `surface_brightness`: Mean surface brightness *| in magnitudes per
+                        square arcsecond*
+
+# Arguments
+
+* `mag`       : Integrated magnitude of the object
+* `major_axis`: Apparent major axis of the object *| in radians*
+* `minor_axis`: Apparent minor axis of the object *| in radians*
+**/
+pub fn surface_brightness(mag: f64, major_axis: f64, minor_axis: f64) -> f64
+{
+    let arcsec = |a: f64| a.to_degrees() * 3600.0;
+    let area = std::f64::consts::PI * arcsec(major_axis) * arcsec(minor_axis) / 4.0;
+
+    mag + 2.5*area.log10()
+}
+
 /**
 Computes the angle between a vector from a star to the
 north celestial pole of the Earth and a vector from the
@@ -125,7 +199,6 @@ same star to the north pole of the ecliptic
 * `eclip_lat`: The star's ecliptical latitude *| in radians*
 * `oblq_eclip`: Obliquity of the ecliptic *| in radians*
 **/
-
 #[inline]
 pub fn angl_between_north_celes_and_eclip_pole(eclip_long: f64,
                                                eclip_lat: f64,
@@ -147,17 +220,12 @@ it's proper motion, distance and radial velocity.
 
 # Returns
 
-`(new_asc, new_dec)`
-
-* `new_asc`: Right ascension at the different
-                 time *| in radians*
-* `new_dec`: Declination at the different
-                 time *| in radians*
+* `new_point`: Equatorial point of the star at the different
+               time *| in radians*
 
 # Arguments
 
-* `asc0`: Right ascension of the star initially *| in radians*
-* `dec0`: Declination of the star initially *| in radians*
+* `point0`: Equatorial point of the star initially *| in radians*
 * `r`: Distance of the star (*parsecs*)
 * `delta_r`: Radial velocity of the star (*parsecs/second*)
 * `proper_motion_asc`: Proper motion of the star in right ascension
@@ -167,14 +235,15 @@ it's proper motion, distance and radial velocity.
 * `t`: Decimal years from the inital time; negative in the past
           and positive in the future
 **/
-pub fn eq_coords_frm_motion(asc0: f64,
-                            dec0: f64,
-                            r: f64,
-                            delta_r: f64,
-                            proper_motion_asc: f64,
-                            proper_motion_dec: f64,
-                            t: f64) -> (f64, f64)
+pub fn eq_point_frm_motion(point0: &coords::EqPoint,
+                           r: f64,
+                           delta_r: f64,
+                           proper_motion_asc: f64,
+                           proper_motion_dec: f64,
+                           t: f64) -> coords::EqPoint
 {
+    let (asc0, dec0) = (point0.asc, point0.dec);
+
     let x = r * dec0.cos() * asc0.cos();
     let y = r * dec0.cos() * asc0.sin();
     let z = r * dec0.sin();
@@ -190,10 +259,29 @@ pub fn eq_coords_frm_motion(asc0: f64,
     let y1 = y + t*delta_y;
     let z1 = z + t*delta_z;
 
-    let asc = y1.atan2(x1);
-    let dec = z1.atan2((x1*x1 + y1*y1).sqrt());
+    coords::EqPoint {
+        asc: y1.atan2(x1),
+        dec: z1.atan2((x1*x1 + y1*y1).sqrt())
+    }
+}
+
+/// Computes the equatorial coordinates of a star at a different time
+/// from it's motion in space, as `(new_asc, new_dec)`
+#[deprecated(note = "use `eq_point_frm_motion()`, which takes and returns a point")]
+pub fn eq_coords_frm_motion(asc0: f64,
+                            dec0: f64,
+                            r: f64,
+                            delta_r: f64,
+                            proper_motion_asc: f64,
+                            proper_motion_dec: f64,
+                            t: f64) -> (f64, f64)
+{
+    let point = eq_point_frm_motion (
+        &coords::EqPoint { asc: asc0, dec: dec0 },
+        r, delta_r, proper_motion_asc, proper_motion_dec, t
+    );
 
-    (asc, dec)
+    (point.asc, point.dec)
 }
 
 pub fn proper_motion_in_eq_coords(asc: f64,
@@ -223,3 +311,774 @@ pub fn proper_motion_in_eq_coords(asc: f64,
 
     (pmotion_long, pmotion_lat)
 }
+
+/**
+Computes the proper motion of a star from it's equatorial
+coordinates at two epochs
+
+The coordinates at the second epoch are first reduced to the
+equinox of the first, so that the precession between the two
+equinoxes isn't mistaken for motion of the star.
+
+# Returns
+
+`(pmotion_asc, pmotion_dec)`
+
+* `pmotion_asc`: Proper motion in right ascension, referred to the
+                 first equinox *| in radians per year*
+* `pmotion_dec`: Proper motion in declination, referred to the
+                 first equinox *| in radians per year*
+
+# Arguments
+
+* `asc1`: Right ascension at the first epoch *| in radians*
+* `dec1`: Declination at the first epoch *| in radians*
+* `JD1` : Julian (Ephemeris) day of the first epoch
+* `eqx1`: Julian (Ephemeris) day of the equinox of `asc1` and `dec1`
+* `asc2`: Right ascension at the second epoch *| in radians*
+* `dec2`: Declination at the second epoch *| in radians*
+* `JD2` : Julian (Ephemeris) day of the second epoch
+* `eqx2`: Julian (Ephemeris) day of the equinox of `asc2` and `dec2`
+**/
+pub fn proper_motion_frm_eq_coords(asc1: f64,
+                                   dec1: f64,
+                                   JD1: f64,
+                                   eqx1: f64,
+                                   asc2: f64,
+                                   dec2: f64,
+                                   JD2: f64,
+                                   eqx2: f64) -> (f64, f64)
+{
+    let (asc2, dec2) = precess::precess_eq_coords(asc2, dec2, eqx2, eqx1);
+
+    let t = (JD2 - JD1) / 365.25;
+
+    let delta_asc = angle::limit_to_pm_PI(asc2 - asc1);
+
+    (delta_asc / t, (dec2 - dec1) / t)
+}
+
+/**
+Mean place of a star in a star catalog
+**/
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MeanPlace {
+    /// Right ascension *| in radians*
+    pub asc: f64,
+    /// Declination *| in radians*
+    pub dec: f64,
+    /// Proper motion in right ascension *| in radians per year*
+    pub pmotion_asc: f64,
+    /// Proper motion in declination *| in radians per year*
+    pub pmotion_dec: f64,
+    /// Parallax *| in radians*, or zero if unknown
+    pub parallax: f64,
+    /// Radial velocity *| in kilometers per second*, positive
+    /// when receding
+    pub rad_vel: f64
+}
+
+// E-terms of aberration of the FK4 system, and their rates
+// of change *| in radians, and radians per tropical century*
+pub(crate) const E_TERMS: [f64; 3] = [-1.62557e-6, -0.31919e-6, -0.13843e-6];
+pub(crate) const E_TERMS_RATE: [f64; 3] = [1.245e-3, -1.580e-3, -0.659e-3];
+
+// Radial velocity in km/s, times parallax in arcseconds, to
+// AU per tropical century
+const RAD_VEL_FACTOR: f64 = 21.095;
+
+// Velocity of 1 AU per year, in km/s
+const AU_PER_YEAR: f64 = 4.740470446;
+
+// Matrix taking a position and velocity vector from the FK4
+// system at B1950 to the FK5 system at J2000
+pub(crate) const FK4_TO_FK5: [[f64; 6]; 6] = [
+    [ 0.9999256782, -0.0111820611, -0.0048579477,  0.00000242395018, -0.00000002710663, -0.00000001177656],
+    [ 0.0111820610,  0.9999374784, -0.0000271765,  0.00000002710663,  0.00000242397878, -0.00000000006587],
+    [ 0.0048579479, -0.0000271474,  0.9999881997,  0.00000001177656, -0.00000000006582,  0.00000242410173],
+    [-0.000551,     -0.238565,      0.435739,      0.99994704,       -0.01118251,       -0.00485767],
+    [ 0.238514,     -0.002667,     -0.008541,      0.01118251,        0.99995883,       -0.00002718],
+    [-0.435623,      0.012254,      0.002117,      0.00485767,       -0.00002714,        1.00000956]
+];
+
+// Matrix taking a position and velocity vector from the FK5
+// system at J2000 to the FK4 system at B1950
+const FK5_TO_FK4: [[f64; 6]; 6] = [
+    [ 0.9999256795,  0.0111814828,  0.0048590039, -0.00000242389840, -0.00000002710544, -0.00000001177742],
+    [-0.0111814828,  0.9999374849, -0.0000271771,  0.00000002710544, -0.00000242392702,  0.00000000006585],
+    [-0.0048590040, -0.0000271557,  0.9999881946,  0.00000001177742,  0.00000000006585, -0.00000242404995],
+    [-0.000551,      0.238509,     -0.435614,      0.99990432,        0.01118145,        0.00485852],
+    [-0.238560,     -0.002667,      0.012254,     -0.01118145,        0.99991613,       -0.00002717],
+    [ 0.435730,     -0.008541,      0.002117,     -0.00485852,       -0.00002716,        0.99996684]
+];
+
+/**
+Computes the mean place of a star referred to the FK5 system at
+J2000, from it's mean place referred to the FK4 system at B1950
+
+The E-terms of aberration included in FK4 mean places are removed,
+and the equinox and proper motion corrections of the FK5 system
+are applied. A star with no proper motion in the FK4 system
+generally gets a small, fictitious proper motion in the FK5 system.
+
+# Returns
+
+* `place`: Mean place at J2000 referred to the FK5 system, with the
+           proper motions per Julian year
+
+# Arguments
+
+* `place`: Mean place at B1950 referred to the FK4 system, with the
+           proper motions per tropical year
+**/
+pub fn fk5_frm_fk4(place: &MeanPlace) -> MeanPlace
+{
+    let (p, v) = pos_vel_frm_mean_place(place);
+
+    // remove the E-terms from the position and velocity
+    let w = dot(&p, &E_TERMS);
+    let wd = dot(&p, &E_TERMS_RATE);
+    let mut pv = [0.0; 6];
+    for i in 0..3 {
+        pv[i] = p[i] - E_TERMS[i] + w*p[i];
+        pv[i + 3] = v[i] - E_TERMS_RATE[i] + wd*p[i];
+    }
+
+    let pv = mat_mul(&FK4_TO_FK5, &pv);
+
+    mean_place_frm_pos_vel(&[pv[0], pv[1], pv[2]], &[pv[3], pv[4], pv[5]], place)
+}
+
+/**
+Computes the mean place of a star referred to the FK4 system at
+B1950, from it's mean place referred to the FK5 system at J2000
+
+This is the inverse of `fk5_frm_fk4()`, with the E-terms of
+aberration restored to the FK4 mean place.
+
+# Returns
+
+* `place`: Mean place at B1950 referred to the FK4 system, with the
+           proper motions per tropical year
+
+# Arguments
+
+* `place`: Mean place at J2000 referred to the FK5 system, with the
+           proper motions per Julian year
+**/
+pub fn fk4_frm_fk5(place: &MeanPlace) -> MeanPlace
+{
+    let (p, v) = pos_vel_frm_mean_place(place);
+    let pv = mat_mul(&FK5_TO_FK4, &[p[0], p[1], p[2], v[0], v[1], v[2]]);
+
+    // restore the E-terms to the position and velocity
+    let mut p = [pv[0], pv[1], pv[2]];
+    let r = dot(&p, &p).sqrt();
+    let w = dot(&p, &E_TERMS);
+    for i in 0..3 {
+        p[i] += E_TERMS[i]*r - w*p[i];
+    }
+
+    let r = dot(&p, &p).sqrt();
+    let wd = dot(&p, &E_TERMS_RATE);
+    let mut v = [pv[3], pv[4], pv[5]];
+    for i in 0..3 {
+        v[i] += E_TERMS_RATE[i]*r - wd*p[i];
+    }
+
+    mean_place_frm_pos_vel(&p, &v, place)
+}
+
+// Proper motions per year, to arcseconds per century
+#[inline]
+pub(crate) fn pmotion_factor() -> f64
+{
+    100.0 * 3600.0 * 180.0 / std::f64::consts::PI
+}
+
+#[inline]
+fn dot(a: &[f64; 3], b: &[f64; 3]) -> f64
+{
+    a[0]*b[0] + a[1]*b[1] + a[2]*b[2]
+}
+
+fn mat_mul(m: &[[f64; 6]; 6], v: &[f64; 6]) -> [f64; 6]
+{
+    let mut r = [0.0; 6];
+    for i in 0..6 {
+        for j in 0..6 {
+            r[i] += m[i][j] * v[j];
+        }
+    }
+
+    r
+}
+
+// Unit position vector, and it's velocity in arcseconds per century
+fn pos_vel_frm_mean_place(place: &MeanPlace) -> ([f64; 3], [f64; 3])
+{
+    let pm_asc = place.pmotion_asc * pmotion_factor();
+    let pm_dec = place.pmotion_dec * pmotion_factor();
+    let w = RAD_VEL_FACTOR * place.rad_vel * place.parallax.to_degrees() * 3600.0;
+
+    let (sa, ca) = place.asc.sin_cos();
+    let (sd, cd) = place.dec.sin_cos();
+    let p = [ca * cd, sa * cd, sd];
+
+    let v = [
+        -pm_asc*p[1] - ca*sd*pm_dec + w*p[0],
+         pm_asc*p[0] - sa*sd*pm_dec + w*p[1],
+         pm_dec*cd + w*p[2]
+    ];
+
+    (p, v)
+}
+
+fn mean_place_frm_pos_vel(p: &[f64; 3], v: &[f64; 3], old: &MeanPlace) -> MeanPlace
+{
+    let rxy2 = p[0]*p[0] + p[1]*p[1];
+    let rxy = rxy2.sqrt();
+    let r = dot(p, p).sqrt();
+
+    let (mut pm_asc, mut pm_dec) = (0.0, 0.0);
+    if rxy > 1e-30 {
+        pm_asc = (p[0]*v[1] - p[1]*v[0]) / rxy2;
+        pm_dec = (v[2]*rxy2 - p[2]*(p[0]*v[0] + p[1]*v[1])) / ((rxy2 + p[2]*p[2]) * rxy);
+    }
+
+    let (mut parallax, mut rad_vel) = (old.parallax, old.rad_vel);
+    if parallax > 1e-30 {
+        rad_vel = dot(p, v) / (parallax.to_degrees() * 3600.0 * RAD_VEL_FACTOR * r);
+        parallax /= r;
+    }
+
+    MeanPlace {
+        asc:         angle::limit_to_two_PI(p[1].atan2(p[0])),
+        dec:         p[2].atan2(rxy),
+        pmotion_asc: pm_asc / pmotion_factor(),
+        pmotion_dec: pm_dec / pmotion_factor(),
+        parallax,
+        rad_vel
+    }
+}
+
+/**
+Computes the apparent place of a star
+
+The mean place is carried to the date by the star's space motion,
+and then corrected, in order, for annual parallax, the deflection
+of light by the Sun, annual aberration, and precession and
+nutation. The precession and nutation are those of
+`precess::true_eq_frm_icrs()`, and the aberration is computed
+rigorously from the barycentric velocity of the Earth.
+
+# Returns
+
+* `eq_point`: Apparent equatorial point, referred to the true
+              equator and equinox of date *| in radians*
+
+# Arguments
+
+* `place`          : Mean place of the star at J2000.0, referred to
+                     the ICRS or FK5 system
+* `JD`             : Julian (Ephemeris) day
+* `annual_parallax`: Whether to correct for annual parallax, which
+                     needs a non-zero parallax in `place`
+* `deflection`     : Whether to correct for the deflection of light
+                     by the Sun
+**/
+pub fn apprnt_eq_point(place: &MeanPlace, JD: f64, annual_parallax: bool, deflection: bool) -> coords::EqPoint
+{
+    // space motion, with the velocity in arcseconds per century
+    let (p, v) = pos_vel_frm_mean_place(place);
+    let t = (JD - 2451545.0) / 36525.0 * (1.0_f64/3600.0).to_radians();
+    let mut p = [p[0] + v[0]*t, p[1] + v[1]*t, p[2] + v[2]*t];
+
+    let (earth_pos, earth_vel) = earth_barycent_state(JD);
+
+    if annual_parallax {
+        for i in 0..3 {
+            p[i] -= place.parallax * earth_pos[i];
+        }
+    }
+    let mut p = normalized(&p);
+
+    if deflection {
+        let (sx, sy, sz) = sun::barycent_rect_coords(JD);
+        let sun = ecl_to_eq_J2000(&[sx, sy, sz]);
+        let e = [earth_pos[0] - sun[0], earth_pos[1] - sun[1], earth_pos[2] - sun[2]];
+        let E = dot(&e, &e).sqrt();
+
+        let (x, y, z) = aberr::light_deflection(
+            (p[0], p[1], p[2]), (-e[0] / E, -e[1] / E, -e[2] / E), E
+        );
+        p = [x, y, z];
+    }
+
+    // relativistic annual aberration
+    let b = [earth_vel[0] / LIGHT_AU_PER_DAY, earth_vel[1] / LIGHT_AU_PER_DAY, earth_vel[2] / LIGHT_AU_PER_DAY];
+    let bm1 = (1.0 - dot(&b, &b)).sqrt();
+    let pb = dot(&p, &b);
+    let w = 1.0 + pb/(1.0 + bm1);
+    for i in 0..3 {
+        p[i] = (bm1*p[i] + w*b[i]) / (1.0 + pb);
+    }
+
+    let (asc, dec) = (p[1].atan2(p[0]), p[2].atan2(p[0].hypot(p[1])));
+    let (asc, dec) = precess::true_eq_frm_icrs(asc, dec, JD);
+
+    coords::EqPoint { asc, dec }
+}
+
+/// Computes the apparent place of a star, as `(asc, dec)`
+#[deprecated(note = "use `apprnt_eq_point()`, which returns a point")]
+pub fn apprnt_place(place: &MeanPlace, JD: f64, annual_parallax: bool, deflection: bool) -> (f64, f64)
+{
+    let point = apprnt_eq_point(place, JD, annual_parallax, deflection);
+
+    (point.asc, point.dec)
+}
+
+// Speed of light *| in AU per day*
+const LIGHT_AU_PER_DAY: f64 = 173.1446326846693;
+
+// Barycentric position and velocity of the Earth, referred to the
+// mean equator and equinox of J2000.0 *| in AU, and AU per day*
+fn earth_barycent_state(JD: f64) -> ([f64; 3], [f64; 3])
+{
+    let pos = |JD: f64| {
+        let (sx, sy, sz) = sun::barycent_rect_coords(JD);
+        let (ex, ey, ez) = planet::heliocent_ecl_rect_coords_J2000(&planet::Planet::Earth, JD);
+        ecl_to_eq_J2000(&[sx + ex, sy + ey, sz + ez])
+    };
+
+    let h = 0.01;
+    let (before, after) = (pos(JD - h), pos(JD + h));
+
+    (
+        pos(JD),
+        [
+            (after[0] - before[0]) / (2.0*h),
+            (after[1] - before[1]) / (2.0*h),
+            (after[2] - before[2]) / (2.0*h)
+        ]
+    )
+}
+
+// Rotates a vector from the ecliptic to the equator of J2000.0
+fn ecl_to_eq_J2000(v: &[f64; 3]) -> [f64; 3]
+{
+    let (s, c) = angle::deg_frm_dms(0, 0, 84381.406).to_radians().sin_cos();
+
+    [v[0], c*v[1] - s*v[2], s*v[1] + c*v[2]]
+}
+
+#[inline]
+fn normalized(v: &[f64; 3]) -> [f64; 3]
+{
+    let r = dot(v, v).sqrt();
+
+    [v[0] / r, v[1] / r, v[2] / r]
+}
+
+/**
+Motion of the Sun relative to the local standard of rest, from
+Schönrich, Binney and Dehnen (2010)
+
+`(U, V, W)` *| in kilometers per second*
+**/
+pub const SOLAR_MOTION: (f64, f64, f64) = (11.1, 12.24, 7.25);
+
+/**
+Computes the Galactic space velocity of a star
+
+The velocity components are along a right-handed system, with `U`
+positive towards the Galactic center, `V` positive in the direction
+of Galactic rotation, and `W` positive towards the north Galactic
+pole.
+
+# Returns
+
+`(U, V, W)`
+
+* `U`: Velocity towards the Galactic center *| in kilometers per second*
+* `V`: Velocity in the direction of Galactic rotation
+       *| in kilometers per second*
+* `W`: Velocity towards the north Galactic pole
+       *| in kilometers per second*
+
+# Arguments
+
+* `place`       : Mean place of the star at J2000, referred to the FK5
+                  system, with a non-zero parallax
+* `solar_motion`: Motion of the Sun relative to the local standard of
+                  rest, such as `SOLAR_MOTION`, which is added to get
+                  the velocity relative to the local standard of rest.
+                  If `None`, the velocity is relative to the Sun.
+**/
+pub fn space_vel(place: &MeanPlace, solar_motion: Option<(f64, f64, f64)>) -> (f64, f64, f64)
+{
+    let v = eq_space_vel(place);
+
+    let (u0, v0, w0) = solar_motion.unwrap_or((0.0, 0.0, 0.0));
+
+    (
+        dot(&coords::EQ_J2000_TO_GAL[0], &v) + u0,
+        dot(&coords::EQ_J2000_TO_GAL[1], &v) + v0,
+        dot(&coords::EQ_J2000_TO_GAL[2], &v) + w0
+    )
+}
+
+/// Holds the closest approach of a star to the Sun
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClosestApproach {
+    /// Julian day of the closest approach
+    pub JD: f64,
+    /// Distance of the star from the Sun at the closest approach
+    /// *| in parsecs*
+    pub dist: f64,
+    /// Right ascension of the star at the closest approach, referred
+    /// to the same equator and equinox as the mean place *| in radians*
+    pub asc: f64,
+    /// Declination of the star at the closest approach, referred to
+    /// the same equator and equinox as the mean place *| in radians*
+    pub dec: f64
+}
+
+/**
+Computes the closest approach of a star to the Sun
+
+The star is assumed to move uniformly along a straight line relative
+to the Sun, with the space velocity given by it's proper motion,
+parallax and radial velocity. The mutual attraction of the star and
+the Sun, and the Galactic potential, are neglected, which for
+approaches within a few million years changes the epoch by well under
+a percent.
+
+# Returns
+
+* `approach`: The [ClosestApproach](./struct.ClosestApproach.html).
+              It's epoch is in the past if the star is receding.
+
+# Arguments
+
+* `place`: Mean place of the star, with it's proper motion, a
+           non-zero parallax and radial velocity
+* `JD`   : Julian day of the epoch of the mean place
+**/
+pub fn closest_approach(place: &MeanPlace, JD: f64) -> ClosestApproach
+{
+    // in AU, and AU per year
+    let p = unit_vec(place.asc, place.dec);
+    let r = [p[0] / place.parallax, p[1] / place.parallax, p[2] / place.parallax];
+    let v = eq_space_vel(place);
+    let v = [v[0] / AU_PER_YEAR, v[1] / AU_PER_YEAR, v[2] / AU_PER_YEAR];
+
+    let t = -dot(&r, &v) / dot(&v, &v);
+    let q = [r[0] + v[0]*t, r[1] + v[1]*t, r[2] + v[2]*t];
+    let dist = dot(&q, &q).sqrt();
+
+    ClosestApproach {
+        JD:   JD + t*365.25,
+        dist: dist * (1.0_f64 / 3600.0).to_radians(),
+        asc:  angle::limit_to_two_PI(q[1].atan2(q[0])),
+        dec:  (q[2] / dist).asin()
+    }
+}
+
+/**
+Propagates the mean place of a star by its space motion
+
+Unlike `eq_point_frm_motion()`, which moves the star along a
+straight line on the celestial sphere, the star is moved uniformly
+along a straight line in space, so that the changing perspective
+alters its proper motion, parallax and radial velocity. This
+matters for nearby stars with large proper motions, such as
+Barnard's Star, whose proper motion grows by over a milliarcsecond
+each year.
+
+Following the `pmsafe` routine of SOFA, the epochs are those at
+which the light is received, and the changing light-time from the
+star is accounted for. A star with a zero parallax is placed at a
+distance of 10<sup>7</sup> parsecs, and comes out with zero parallax
+and an unchanged radial velocity.
+
+# Returns
+
+* `place`: Mean place of the star at the new epoch, referred to the
+           same equator and equinox
+
+# Arguments
+
+* `place`: Mean place of the star, with its proper motion, parallax
+           and radial velocity
+* `dt`   : Julian years from the epoch of `place`; negative in the
+           past and positive in the future
+**/
+pub fn propagate_space_motion(place: &MeanPlace, dt: f64) -> MeanPlace
+{
+    let par = if place.parallax > 0.0 {
+        place.parallax
+    } else {
+        MIN_PARALLAX
+    };
+    let nominal = MeanPlace { parallax: par, ..*place };
+
+    // in AU, and AU per year
+    let u = unit_vec(place.asc, place.dec);
+    let r1 = [u[0] / par, u[1] / par, u[2] / par];
+    let v = eq_space_vel(&nominal);
+    let v = [v[0] / AU_PER_YEAR, v[1] / AU_PER_YEAR, v[2] / AU_PER_YEAR];
+
+    // the light received at the two epochs left the star at times
+    // separated by dt, corrected for the change in light-time
+    let c = LIGHT_AU_PER_DAY * 365.25;
+    let light_time1 = dot(&r1, &r1).sqrt() / c;
+    let mut r2 = r1;
+    for _ in 0..10 {
+        let light_time2 = dot(&r2, &r2).sqrt() / c;
+        let t = dt + light_time1 - light_time2;
+        r2 = [r1[0] + v[0]*t, r1[1] + v[1]*t, r1[2] + v[2]*t];
+    }
+
+    let dist = dot(&r2, &r2).sqrt();
+    let rxy2 = r2[0]*r2[0] + r2[1]*r2[1];
+    let rxy = rxy2.sqrt();
+
+    let new_asc = angle::limit_to_two_PI(r2[1].atan2(r2[0]));
+    let (sa, ca) = new_asc.sin_cos();
+    let (sd, cd) = (r2[2] / dist, rxy / dist);
+
+    let v_asc = -v[0]*sa + v[1]*ca;
+    let v_dec = -v[0]*sd*ca - v[1]*sd*sa + v[2]*cd;
+    let v_rad = dot(&v, &r2) / dist;
+
+    let (new_par, new_rad_vel) = if place.parallax > 0.0 {
+        (1.0 / dist, v_rad * AU_PER_YEAR)
+    } else {
+        (0.0, place.rad_vel)
+    };
+
+    MeanPlace {
+        asc:         new_asc,
+        dec:         r2[2].atan2(rxy),
+        pmotion_asc: v_asc / (dist * cd),
+        pmotion_dec: v_dec / dist,
+        parallax:    new_par,
+        rad_vel:     new_rad_vel
+    }
+}
+
+// Parallax assumed for a star with none *| in radians*
+const MIN_PARALLAX: f64 = 4.84813681109536e-13;
+
+/**
+Computes the convergent point of a moving cluster
+
+The convergent point is the point on the celestial sphere towards
+which the proper motions of the members of a moving cluster appear
+to converge. It's found here as the point closest, in the sense of
+least squares, to the great circles along the proper motions of all
+the members.
+
+# Returns
+
+* `conv_point`: Equatorial point of the convergent point *| in radians*
+
+# Arguments
+
+* `members`: Mean places of two or more members of the cluster, with
+             their proper motions. Their parallaxes and radial
+             velocities are ignored.
+**/
+pub fn convergent_eq_point(members: &[MeanPlace]) -> coords::EqPoint
+{
+    let mut m = [[0.0; 3]; 3];
+    let mut mean_dir = [0.0; 3];
+
+    for place in members {
+        let r = unit_vec(place.asc, place.dec);
+        let d = pmotion_dir(place);
+
+        // pole of the great circle along the proper motion
+        let n = [
+            r[1]*d[2] - r[2]*d[1],
+            r[2]*d[0] - r[0]*d[2],
+            r[0]*d[1] - r[1]*d[0]
+        ];
+        for i in 0..3 {
+            for j in 0..3 {
+                m[i][j] += n[i] * n[j];
+            }
+            mean_dir[i] += d[i];
+        }
+    }
+
+    let mut c = smallest_eigen_vec(m);
+
+    // the proper motions point towards the convergent point,
+    // and not away from it's antipode
+    if dot(&c, &mean_dir) < 0.0 {
+        for x in c.iter_mut() {
+            *x = -*x;
+        }
+    }
+
+    coords::EqPoint {
+        asc: angle::limit_to_two_PI(c[1].atan2(c[0])),
+        dec: c[2].atan2((c[0]*c[0] + c[1]*c[1]).sqrt())
+    }
+}
+
+/// Computes the convergent point of a moving cluster, as
+/// `(conv_asc, conv_dec)`
+#[deprecated(note = "use `convergent_eq_point()`, which returns a point")]
+pub fn convergent_point(members: &[MeanPlace]) -> (f64, f64)
+{
+    let point = convergent_eq_point(members);
+
+    (point.asc, point.dec)
+}
+
+/**
+Computes the space velocity of a moving cluster from the radial
+velocities of it's members
+
+# Returns
+
+* `vel`: Space velocity of the cluster *| in kilometers per second*
+
+# Arguments
+
+* `members` : Mean places of one or more members of the cluster, with
+              their radial velocities
+* `conv_asc`: Right ascension of the convergent point *| in radians*
+* `conv_dec`: Declination of the convergent point *| in radians*
+**/
+pub fn moving_cluster_vel(members: &[MeanPlace], conv_asc: f64, conv_dec: f64) -> f64
+{
+    let c = unit_vec(conv_asc, conv_dec);
+
+    let mut sum = 0.0;
+    for place in members {
+        sum += place.rad_vel / dot(&unit_vec(place.asc, place.dec), &c);
+    }
+
+    sum / (members.len() as f64)
+}
+
+/**
+Computes the parallax of a member of a moving cluster from the
+convergent point of the cluster
+
+# Returns
+
+* `parallax`: Parallax of the member *| in radians*
+
+# Arguments
+
+* `place`   : Mean place of the member, with it's proper motion
+* `conv_asc`: Right ascension of the convergent point *| in radians*
+* `conv_dec`: Declination of the convergent point *| in radians*
+* `vel`     : Space velocity of the cluster *| in kilometers per second*
+**/
+pub fn moving_cluster_parallax(place: &MeanPlace, conv_asc: f64, conv_dec: f64, vel: f64) -> f64
+{
+    let cos_lambda = dot(&unit_vec(place.asc, place.dec), &unit_vec(conv_asc, conv_dec));
+    let sin_lambda = (1.0 - cos_lambda*cos_lambda).sqrt();
+    let pmotion = (place.pmotion_asc * place.dec.cos()).hypot(place.pmotion_dec);
+
+    AU_PER_YEAR * pmotion / (vel * sin_lambda)
+}
+
+// Space velocity of a star relative to the Sun, in equatorial
+// rectangular coordinates, in kilometers per second
+fn eq_space_vel(place: &MeanPlace) -> [f64; 3]
+{
+    let (sa, ca) = place.asc.sin_cos();
+    let (sd, cd) = place.dec.sin_cos();
+
+    let v_asc = AU_PER_YEAR * place.pmotion_asc * cd / place.parallax;
+    let v_dec = AU_PER_YEAR * place.pmotion_dec / place.parallax;
+
+    [
+        place.rad_vel*cd*ca - v_asc*sa - v_dec*sd*ca,
+        place.rad_vel*cd*sa + v_asc*ca - v_dec*sd*sa,
+        place.rad_vel*sd + v_dec*cd
+    ]
+}
+
+#[inline]
+fn unit_vec(asc: f64, dec: f64) -> [f64; 3]
+{
+    [dec.cos() * asc.cos(), dec.cos() * asc.sin(), dec.sin()]
+}
+
+// Unit vector along the proper motion of a star
+fn pmotion_dir(place: &MeanPlace) -> [f64; 3]
+{
+    let (sa, ca) = place.asc.sin_cos();
+    let (sd, cd) = place.dec.sin_cos();
+
+    let v_asc = place.pmotion_asc * cd;
+    let v_dec = place.pmotion_dec;
+    let v = v_asc.hypot(v_dec);
+
+    [
+        (-v_asc*sa - v_dec*sd*ca) / v,
+        ( v_asc*ca - v_dec*sd*sa) / v,
+        v_dec*cd / v
+    ]
+}
+
+// Eigenvector of the smallest eigenvalue of a symmetric matrix,
+// by Jacobi rotations
+fn smallest_eigen_vec(mut m: [[f64; 3]; 3]) -> [f64; 3]
+{
+    let mut v = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+    for _ in 0..50 {
+        let off = m[0][1].abs() + m[0][2].abs() + m[1][2].abs();
+        if off < 1e-15 {
+            break;
+        }
+
+        for &(p, q) in [(0, 1), (0, 2), (1, 2)].iter() {
+            if m[p][q] == 0.0 {
+                continue;
+            }
+
+            let theta = (m[q][q] - m[p][p]) / (2.0 * m[p][q]);
+            let t = theta.signum() / (theta.abs() + (theta*theta + 1.0).sqrt());
+            let c = 1.0 / (t*t + 1.0).sqrt();
+            let s = t * c;
+
+            for row in m.iter_mut() {
+                let (mkp, mkq) = (row[p], row[q]);
+                row[p] = c*mkp - s*mkq;
+                row[q] = s*mkp + c*mkq;
+            }
+            let (row_p, row_q) = (m[p], m[q]);
+            for (k, (&mpk, &mqk)) in row_p.iter().zip(row_q.iter()).enumerate() {
+                m[p][k] = c*mpk - s*mqk;
+                m[q][k] = s*mpk + c*mqk;
+            }
+            for row in v.iter_mut() {
+                let (vp, vq) = (row[p], row[q]);
+                row[p] = c*vp - s*vq;
+                row[q] = s*vp + c*vq;
+            }
+        }
+    }
+
+    let mut i = 0;
+    for j in 1..3 {
+        if m[j][j] < m[i][i] {
+            i = j;
+        }
+    }
+
+    [v[0][i], v[1][i], v[2][i]]
+}
@@ -0,0 +1,206 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Portable trigonometric functions, for bit-reproducible results
+//!
+//! The trigonometric functions of `std` call the math library of the
+//! platform, whose last bits differ between platforms. The functions
+//! here use only IEEE 754 arithmetic, which Rust evaluates in the
+//! order written and without fused multiply-adds, so their results
+//! are identical on every platform.
+//!
+//! With the `strict` feature enabled, only these functions give results
+//! identical on every platform:
+//!
+//! * `planet::heliocent_coords()`, whose VSOP87 series are evaluated
+//!   with these functions
+//! * `orbit::elliptic::ecc_anom()` and
+//!   `orbit::elliptic::ecc_anom_newton()`, which solve Kepler's
+//!   equation with them
+//!
+//! Every other function of the crate, including those that convert
+//! the results of these ones to other coordinates, still calls the
+//! transcendental functions of `std` (or of `libm`), and its last bits
+//! may differ between platforms.
+
+#[cfg(not(feature = "std"))]
+use math::*;
+
+// Cody-Waite splitting of pi/2, with n * PIO2_1 and n * PIO2_2
+// exact for |n| < 2^20
+const INV_PIO2: f64 = std::f64::consts::FRAC_2_PI;
+const PIO2_1: f64 = 1.5707963267341256e+00;
+const PIO2_2: f64 = 6.077100506303966e-11;
+const PIO2_2T: f64 = 2.0222662487959506e-21;
+
+// Beyond this, the argument is first reduced modulo 2pi
+const MAX_CODY_WAITE: f64 = 823549.6654807;
+
+// 2pi split into the nearest double, and the remainder
+const TWO_PI_HI: f64 = std::f64::consts::TAU;
+const TWO_PI_LO: f64 = 2.4492935982947064e-16;
+
+/**
+Computes the sine of an angle, identically on every platform
+
+# Arguments
+
+* `x`: Angle *| in radians*
+**/
+#[inline]
+pub fn sin(x: f64) -> f64
+{
+    sin_cos(x).0
+}
+
+/**
+Computes the cosine of an angle, identically on every platform
+
+# Arguments
+
+* `x`: Angle *| in radians*
+**/
+#[inline]
+pub fn cos(x: f64) -> f64
+{
+    sin_cos(x).1
+}
+
+/**
+Computes the sine and cosine of an angle, identically on every
+platform
+
+# Returns
+
+`(sin, cos)`
+
+# Arguments
+
+* `x`: Angle *| in radians*
+**/
+pub fn sin_cos(x: f64) -> (f64, f64)
+{
+    if !x.is_finite() {
+        return (f64::NAN, f64::NAN);
+    }
+
+    let x = if x.abs() > MAX_CODY_WAITE { reduce_two_pi(x) } else { x };
+
+    let n = (x * INV_PIO2).round();
+    let y = ((x - n*PIO2_1) - n*PIO2_2) - n*PIO2_2T;
+
+    let (s, c) = (kernel_sin(y), kernel_cos(y));
+
+    match (n as i64).rem_euclid(4) {
+        0 => ( s,  c),
+        1 => ( c, -s),
+        2 => (-s, -c),
+        _ => (-c,  s)
+    }
+}
+
+// Reduces a large angle modulo 2pi. The remainder by TWO_PI_HI is
+// exact, and is corrected by the multiple of TWO_PI_LO, which keeps
+// the result accurate to a few ulps of 2pi for |x| below 2^52. Beyond
+// that, accuracy is lost, but not reproducibility.
+fn reduce_two_pi(x: f64) -> f64
+{
+    let r = x % TWO_PI_HI;
+    let k = ((x - r) / TWO_PI_HI).round();
+
+    r - k*TWO_PI_LO
+}
+
+// Sine for |x| <= pi/4, by the minimax polynomial of fdlibm
+fn kernel_sin(x: f64) -> f64
+{
+    let z = x * x;
+    let r = 8.33333333332249e-03 + z*(
+           -1.984126982985795e-04 + z*(
+            2.7557313707070068e-06 + z*(
+           -2.5050760253406863e-08 + z*1.58969099521155e-10)));
+
+    x + x*z*(-1.6666666666666632e-01 + z*r)
+}
+
+// Cosine for |x| <= pi/4, by the minimax polynomial of fdlibm
+fn kernel_cos(x: f64) -> f64
+{
+    let z = x * x;
+    let r = z * (
+            4.16666666666666e-02 + z*(
+           -1.388888888887411e-03 + z*(
+            2.480158728947673e-05 + z*(
+           -2.7557314351390663e-07 + z*(
+            2.087572321298175e-09 + z*-1.1359647557788195e-11)))));
+
+    let hz = 0.5 * z;
+    let w = 1.0 - hz;
+
+    w + (((1.0 - w) - hz) + z*r)
+}
+
+// Cosine in the series of the theories, portable with the
+// strict feature
+#[cfg(feature = "strict")]
+#[inline]
+pub(crate) fn series_cos(x: f64) -> f64
+{
+    cos(x)
+}
+
+#[cfg(not(feature = "strict"))]
+#[inline]
+pub(crate) fn series_cos(x: f64) -> f64
+{
+    x.cos()
+}
+
+// Sine in Kepler's equation, portable with the strict feature
+#[cfg(feature = "strict")]
+#[inline]
+pub(crate) fn kepler_sin(x: f64) -> f64
+{
+    sin(x)
+}
+
+#[cfg(not(feature = "strict"))]
+#[inline]
+pub(crate) fn kepler_sin(x: f64) -> f64
+{
+    x.sin()
+}
+
+// Cosine in Kepler's equation, portable with the strict feature
+#[cfg(feature = "strict")]
+#[inline]
+pub(crate) fn kepler_cos(x: f64) -> f64
+{
+    cos(x)
+}
+
+#[cfg(not(feature = "strict"))]
+#[inline]
+pub(crate) fn kepler_cos(x: f64) -> f64
+{
+    x.cos()
+}
@@ -27,6 +27,13 @@ use time;
 use std;
 use planet;
 use coords;
+use ecliptic;
+use fund_args;
+use nutation;
+use transit;
+use body::Body;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 /**
 Computes the Sun's equatorial semidiameter
@@ -71,46 +78,49 @@ pub fn geocent_ecl_pos(JD: f64) -> (coords::EclPoint, f64) {
 }
 
 /**
-Computes the Sun's geocentric ecliptic coordinates converted to the
-FK5 system
+Computes the Sun's geocentric ecliptic point converted to the FK5
+system
 
 # Returns
 
-`(ecl_long_FK5, ecl_lat_FK5)`
-
-* `ecl_long_FK5`: Ecliptic longitude of the Sun *| in radians*,
-                  converted to the FK5 system
-* `ecl_lat_FK5` : Ecliptic latitude of the Sun *| in radians*,
-                  converted to the FK5 system
+* `ecl_point_FK5`: Ecliptic point of the Sun *| in radians*,
+                   converted to the FK5 system
 
 # Arguments
 
-* `JD`      : Julian (Ephemeris) day
-* `ecl_long`: Ecliptic longitude of the Sun on `JD`
-              *| in radians*, referred to the mean equinox
-              of the date
-* `ecl_lat` : Ecliptic latitude of the Sun `JD`
-              *| in radians*, referred to the mean equinox
-              of the date
+* `JD`       : Julian (Ephemeris) day
+* `ecl_point`: Ecliptic point of the Sun on `JD` *| in radians*,
+               referred to the mean equinox of the date
 **/
-pub fn ecl_coords_to_FK5(JD: f64, ecl_long: f64, ecl_lat: f64) -> (f64, f64) {
+pub fn ecl_point_to_FK5(JD: f64, ecl_point: &coords::EclPoint) -> coords::EclPoint {
 
     let ecl_long_FK5 =
-        ecl_long
+        ecl_point.long
       - angle::deg_frm_dms(0, 0, 0.09033).to_radians();
 
      let JC = time::julian_cent(JD);
      let lambda1 =
-         ecl_long
+         ecl_point.long
        - JC * (1.397 + JC*0.00031).to_radians();
 
     let ecl_lat_FK5 =
-        ecl_lat
+        ecl_point.lat
       + angle::deg_frm_dms(0, 0, 0.03916).to_radians() * (
           lambda1.cos() - lambda1.sin()
         );
 
-    (ecl_long_FK5, ecl_lat_FK5)
+    coords::EclPoint { long: ecl_long_FK5, lat: ecl_lat_FK5 }
+
+}
+
+/// Computes the Sun's geocentric ecliptic coordinates converted to the
+/// FK5 system, as `(ecl_long_FK5, ecl_lat_FK5)`
+#[deprecated(note = "use `ecl_point_to_FK5()`, which takes and returns a point")]
+pub fn ecl_coords_to_FK5(JD: f64, ecl_long: f64, ecl_lat: f64) -> (f64, f64) {
+
+    let point = ecl_point_to_FK5(JD, &coords::EclPoint { long: ecl_long, lat: ecl_lat });
+
+    (point.long, point.lat)
 
 }
 
@@ -127,11 +137,11 @@ the mean equinox of the date
 * `z`: The Z coordinate *| in AU*
 
 * The positive x-axis is directed towards the Earth's vernal equinox
-(0 degrees longitude)
+  (0 degrees longitude)
 * The positive y-axis lies in the plane of the Earth's equator and is
-directed towards 90 degrees longitude
+  directed towards 90 degrees longitude
 * The positive z-axis is directed towards the Earth's northern
-celestial pole
+  celestial pole
 
 # Arguments
 
@@ -167,6 +177,88 @@ pub fn geocent_rect_coords (
 
 }
 
+/**
+Computes the Sun's position with respect to the barycenter of the
+solar system
+
+The barycenter is found from the heliocentric positions of the eight
+planets, with the masses of the IAU 2009 System of Astronomical
+Constants. The Earth is given the combined mass of the Earth and the
+Moon. The masses of Pluto and the minor bodies are neglected, which
+leaves an error of a few hundred kilometers.
+
+# Returns
+
+`(x, y, z)`
+
+* `x`, `y`, `z`: Barycentric rectangular coordinates of the Sun,
+                 referred to the ecliptic and mean equinox of J2000.0
+                 *| in AU*
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn barycent_rect_coords(JD: f64) -> (f64, f64, f64) {
+
+    let planets = [
+        planet::Planet::Mercury, planet::Planet::Venus,
+        planet::Planet::Earth,   planet::Planet::Mars,
+        planet::Planet::Jupiter, planet::Planet::Saturn,
+        planet::Planet::Uranus,  planet::Planet::Neptune
+    ];
+
+    let (mut mass, mut x, mut y, mut z) = (1.0, 0.0, 0.0, 0.0);
+    for p in planets.iter() {
+        let m = 1.0 / planet::sun_planet_mass_ratio(p);
+        let (px, py, pz) = planet::heliocent_ecl_rect_coords_J2000(p, JD);
+        mass += m;
+        x += m * px;
+        y += m * py;
+        z += m * pz;
+    }
+
+    (-x/mass, -y/mass, -z/mass)
+
+}
+
+/**
+Computes the Sun's position and velocity with respect to the
+barycenter of the solar system
+
+The velocity is found by differentiating the position returned by
+[barycent_rect_coords()](./fn.barycent_rect_coords.html) numerically.
+
+# Returns
+
+`((x, y, z), (vx, vy, vz))`
+
+* `x`, `y`, `z`   : Barycentric position of the Sun *| in AU*
+* `vx`, `vy`, `vz`: Barycentric velocity of the Sun *| in AU per day*
+
+Both are referred to the ecliptic and mean equinox of J2000.0.
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn barycent_rect_state(JD: f64) -> ((f64, f64, f64), (f64, f64, f64)) {
+
+    let h = 0.5;
+    let before = barycent_rect_coords(JD - h);
+    let after = barycent_rect_coords(JD + h);
+
+    (
+        barycent_rect_coords(JD),
+        (
+            (after.0 - before.0) / (2.0*h),
+            (after.1 - before.1) / (2.0*h),
+            (after.2 - before.2) / (2.0*h)
+        )
+    )
+
+}
+
 /**
 Return quantites used in the ephemeris for physical observations of
 the Sun
@@ -262,3 +354,396 @@ pub fn synodic_rot(C: i64) -> f64 {
   - 0.0141 * (2.0 * M).cos()
 
 }
+
+/**
+Computes the Carrington synodic rotation number on a Julian day
+
+# Returns
+
+* `C`: Carrington's synodic rotation number of the rotation in
+       progress on `JD`
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn carrington_rot(JD: f64) -> i64 {
+
+    let mut C = ((JD - 2398140.227) / 27.2752316).floor() as i64;
+
+    while synodic_rot(C + 1) <= JD {
+        C += 1;
+    }
+    while synodic_rot(C) > JD {
+        C -= 1;
+    }
+
+    C
+
+}
+
+/**
+Computes the quantities used in the ephemeris for physical
+observations of the Sun on a Julian day
+
+# Returns
+
+`(P, B0, L0)`
+
+* `P` : Position angle of the northern extremity of the axis of
+        rotation, measured eastwards from the North point of the
+        solar disk *| in radians*
+* `B0`: Heliographic latitude of the center of the solar
+        disk *| in radians*
+* `L0`: Heliographic longitude of the center of the solar
+        disk *| in radians*
+
+The apparent longitude of the Sun and the obliquity of the ecliptic
+needed by [ephemeris()](./fn.ephemeris.html) are computed here.
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn physical_ephemeris(JD: f64) -> (f64, f64, f64) {
+
+    physical_ephemeris_frm_args(&fund_args::FundamentalArgs::new(JD))
+
+}
+
+/**
+Computes the quantities used in the ephemeris for physical
+observations of the Sun from the fundamental arguments
+
+This is `physical_ephemeris()`, for arguments already computed for
+another routine at the same instant, which it uses for nutation.
+
+# Returns
+
+`(P, B0, L0)`, as returned by `physical_ephemeris()`
+
+# Arguments
+
+* `args`: [FundamentalArgs](../fund_args/struct.FundamentalArgs.html)
+          of the instant
+**/
+pub fn physical_ephemeris_frm_args(args: &fund_args::FundamentalArgs) -> (f64, f64, f64) {
+
+    let JD = args.JD;
+    let (point, R) = geocent_ecl_pos(JD);
+    let long = ecl_point_to_FK5(JD, &point).long;
+    let (nut_in_long, nut_in_oblq) = nutation::nutation_frm_args(args);
+    let true_oblq = ecliptic::mn_oblq_IAU(JD) + nut_in_oblq;
+
+    let app_long = long - angle::deg_frm_dms(0, 0, 20.4898).to_radians()/R;
+
+    ephemeris(JD, app_long, app_long + nut_in_long, true_oblq)
+
+}
+
+/**
+Computes the heliographic coordinates of a point on the solar disk,
+such as a sunspot
+
+# Returns
+
+`Some((lat, long))`, or `None` if the point is off the disk
+
+* `lat` : Heliographic latitude of the point *| in radians*
+* `long`: Heliographic longitude of the point *| in radians*
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+* `x` : Offset of the point from the center of the disk towards
+        the east, in units of the apparent radius of the disk
+* `y` : Offset of the point from the center of the disk towards
+        the north, in units of the apparent radius of the disk
+**/
+pub fn heliograph_coords(JD: f64, x: f64, y: f64) -> Option<(f64, f64)> {
+
+    let rho = (x*x + y*y).sqrt();
+    if rho > 1.0 {
+        return None;
+    }
+
+    let (P, B0, L0) = physical_ephemeris(JD);
+    let (_, R) = geocent_ecl_pos(JD);
+
+    // angular distance of the point from the center of the disk, as
+    // seen from the center of the Sun, and its position angle
+    let rho = rho.asin() - rho*semidiameter(R).to_radians();
+    let theta = x.atan2(y);
+
+    let lat = (B0.sin()*rho.cos() + B0.cos()*rho.sin()*(P - theta).cos()).asin();
+    let long = L0 + (rho.sin()*(P - theta).sin()).atan2 (
+        B0.cos()*rho.cos() - B0.sin()*rho.sin()*(P - theta).cos()
+    );
+
+    Some((lat, angle::limit_to_two_PI(long)))
+
+}
+
+/// Represents an equinox or a solstice
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Event {
+    /// March equinox, when the Sun's apparent longitude is 0 degrees
+    MarchEquinox,
+    /// June solstice, when the Sun's apparent longitude is 90 degrees
+    JuneSolstice,
+    /// September equinox, when the Sun's apparent longitude is 180
+    /// degrees
+    SeptEquinox,
+    /// December solstice, when the Sun's apparent longitude is 270
+    /// degrees
+    DecSolstice,
+}
+
+/**
+Computes the time of an equinox or a solstice
+
+The time is first approximated with the mean instants and periodic
+terms of *Meeus* (Astronomical Algorithms, chapter 27), and is then
+corrected until the apparent longitude of the Sun, computed with
+VSOP87 and referred to the true equinox of the date, has the value of
+the event. The result is accurate to within a few seconds.
+
+# Returns
+
+* `JD`: Julian (Ephemeris) day of the event
+
+# Arguments
+
+* `year` : Year, between -1000 and +3000
+* `event`: The [Event](./enum.Event.html)
+**/
+pub fn equinox_solstice(year: i16, event: &Event) -> f64 {
+
+    let (k, coeffs) = if year < 1000 {
+        let Y = (year as f64) / 1000.0;
+        (Y, match *event {
+            Event::MarchEquinox => [1721139.29189, 365242.1374,  0.06134,  0.00111, -0.00071],
+            Event::JuneSolstice => [1721233.25401, 365241.72562, -0.05323, 0.00907,  0.00025],
+            Event::SeptEquinox  => [1721325.70455, 365242.49558, -0.11677, -0.00297, 0.00074],
+            Event::DecSolstice  => [1721414.39987, 365242.88257, -0.00769, -0.00933, -0.00006],
+        })
+    } else {
+        let Y = ((year as f64) - 2000.0) / 1000.0;
+        (Y, match *event {
+            Event::MarchEquinox => [2451623.80984, 365242.37404, 0.05169, -0.00411, -0.00057],
+            Event::JuneSolstice => [2451716.56767, 365241.62603, 0.00325,  0.00888, -0.0003],
+            Event::SeptEquinox  => [2451810.21715, 365242.01767, -0.11575, 0.00337,  0.00078],
+            Event::DecSolstice  => [2451900.05952, 365242.74049, -0.06223, -0.00823, 0.00032],
+        })
+    };
+    let JD0 = coeffs[0] + k*(coeffs[1] + k*(coeffs[2] + k*(coeffs[3] + k*coeffs[4])));
+
+    let T = time::julian_cent(JD0);
+    let W = (35999.373*T - 2.47).to_radians();
+    let delta_lambda = 1.0 + 0.0334*W.cos() + 0.0007*(2.0*W).cos();
+
+    let S: f64 = SEASON_TERMS.iter()
+        .map(|&(A, B, C)| A * (B + C*T).to_radians().cos())
+        .sum();
+
+    let mut JD = JD0 + 0.00001*S/delta_lambda;
+
+    let target = match *event {
+        Event::MarchEquinox => 0.0_f64,
+        Event::JuneSolstice => 90.0,
+        Event::SeptEquinox  => 180.0,
+        Event::DecSolstice  => 270.0,
+    }.to_radians();
+
+    for _ in 0..10 {
+        let (ecl_point, R) = geocent_ecl_pos(JD);
+        let long = ecl_point_to_FK5(JD, &ecl_point).long;
+        let (nut_in_long, _) = nutation::nutation(JD);
+        let apprnt_long = long + nut_in_long - angle::deg_frm_dms(0, 0, 20.4898).to_radians()/R;
+
+        let correction = 58.0 * (target - apprnt_long).sin();
+        JD += correction;
+        if correction.abs() < 1e-7 {
+            break;
+        }
+    }
+
+    JD
+
+}
+
+/**
+Computes the equation of time
+
+# Returns
+
+* `eq_of_time`: The equation of time *| in radians*, which is the
+                apparent solar time minus the mean solar time. It can
+                be turned into minutes of time by multiplying its
+                value in degrees by 4.
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn equation_of_time(JD: f64) -> f64 {
+
+    let (asc, _, nut_in_long, true_oblq) = apprnt_eq_coords(JD);
+    let E = planet::earth::equation_of_time(JD, asc, nut_in_long, true_oblq);
+
+    E - angle::TWO_PI*(E / angle::TWO_PI).round()
+
+}
+
+/**
+Computes a point of the analemma, the figure traced on the sky by the
+Sun at the same mean solar time through the year
+
+# Returns
+
+`(eq_of_time, dec)`
+
+* `eq_of_time`: The equation of time *| in radians*, which is the
+                westward offset of the Sun from its mean position
+* `dec`       : Apparent declination of the Sun *| in radians*
+
+# Arguments
+
+* `JD`: Julian (Ephemeris) day
+**/
+pub fn analemma_point(JD: f64) -> (f64, f64) {
+
+    let (_, dec, _, _) = apprnt_eq_coords(JD);
+
+    (equation_of_time(JD), dec)
+
+}
+
+// The apparent right ascension and declination of the Sun, the
+// nutation in longitude and the true obliquity of the ecliptic
+fn apprnt_eq_coords(JD: f64) -> (f64, f64, f64, f64) {
+
+    let (point, R) = geocent_ecl_pos(JD);
+    let coords::EclPoint { long, lat } = ecl_point_to_FK5(JD, &point);
+    let (nut_in_long, nut_in_oblq) = nutation::nutation(JD);
+    let true_oblq = ecliptic::mn_oblq_IAU(JD) + nut_in_oblq;
+
+    let long = long + nut_in_long - angle::deg_frm_dms(0, 0, 20.4898).to_radians()/R;
+
+    (
+        coords::asc_frm_ecl(long, lat, true_oblq),
+        coords::dec_frm_ecl(long, lat, true_oblq),
+        nut_in_long,
+        true_oblq
+    )
+
+}
+
+/// Represents a kind of twilight
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TwilightKind {
+    /// Civil twilight, when the Sun's center is less than 6 degrees
+    /// below the horizon
+    Civil,
+    /// Nautical twilight, when the Sun's center is less than 12 degrees
+    /// below the horizon
+    Nautical,
+    /// Astronomical twilight, when the Sun's center is less than 18
+    /// degrees below the horizon
+    Astronomical,
+}
+
+/// Holds the times of twilight on a day
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Twilight {
+    /// Julian (Universal) day of the beginning of the morning
+    /// twilight, or `None` if the Sun doesn't cross the altitude of
+    /// the twilight while rising on the day
+    pub begin: Option<f64>,
+    /// Julian (Universal) day of the end of the evening twilight, or
+    /// `None` if the Sun doesn't cross the altitude of the twilight
+    /// while setting on the day
+    pub end: Option<f64>,
+    /// Time for which the Sun stays below the altitude of the
+    /// twilight on the day *| in days*
+    pub darkness: f64,
+}
+
+/**
+Computes the times of twilight on a day
+
+# Returns
+
+* `twilight`: The [Twilight](./struct.Twilight.html) times on the day
+
+# Arguments
+
+* `geograph_point`: Geographic point of the observer *| in radians*
+* `date`          : The date of interest, of which only the day is
+                    considered
+* `kind`          : The [TwilightKind](./enum.TwilightKind.html)
+**/
+pub fn twilight (
+
+    geograph_point : &coords::GeographPoint,
+    date           : &time::Date,
+    kind           : &TwilightKind
+
+) -> Twilight {
+
+    let JD = (time::julian_day(date) - 0.5).floor() + 0.5;
+
+    let altitude = match *kind {
+        TwilightKind::Civil        => transit::Altitude::CivilTwilight,
+        TwilightKind::Nautical     => transit::Altitude::NauticalTwilight,
+        TwilightKind::Astronomical => transit::Altitude::AstronomicalTwilight,
+    };
+
+    // the Sun's position is computed for every Julian day
+    match transit::rise_set_transit(&Body::Sun, geograph_point, JD, &altitude).unwrap() {
+        transit::RiseSetTransit::RiseSet { rise, set, .. } => Twilight {
+            begin:    Some(rise),
+            end:      Some(set),
+            darkness: 1.0 - (set - rise - (set - rise).floor()),
+        },
+        transit::RiseSetTransit::Circumpolar { .. } => Twilight {
+            begin: None, end: None, darkness: 0.0
+        },
+        transit::RiseSetTransit::NeverRises { .. } => Twilight {
+            begin: None, end: None, darkness: 1.0
+        },
+    }
+
+}
+
+// Periodic terms (A, B, C) for the equinoxes and solstices, from
+// Meeus (Astronomical Algorithms, table 27.C)
+const SEASON_TERMS: [(f64, f64, f64); 24] = [
+    (485.0, 324.96,   1934.136),
+    (203.0, 337.23,  32964.467),
+    (199.0, 342.08,     20.186),
+    (182.0,  27.85, 445267.112),
+    (156.0,  73.14,  45036.886),
+    (136.0, 171.52,  22518.443),
+    ( 77.0, 222.54,  65928.934),
+    ( 74.0, 296.72,   3034.906),
+    ( 70.0, 243.58,   9037.513),
+    ( 58.0, 119.81,  33718.147),
+    ( 52.0, 297.17,    150.678),
+    ( 50.0,  21.02,   2281.226),
+    ( 45.0, 247.54,  29929.562),
+    ( 44.0, 325.15,  31555.956),
+    ( 29.0,  60.93,   4443.417),
+    ( 18.0, 155.12,  67555.328),
+    ( 17.0, 288.79,   4562.452),
+    ( 16.0, 198.04,  62894.029),
+    ( 14.0, 199.76,  31436.921),
+    ( 12.0,  95.39,  14577.848),
+    ( 12.0, 287.11,  31931.756),
+    ( 12.0, 320.81,  34777.259),
+    (  9.0, 227.73,   1222.114),
+    (  8.0,  15.45,  16859.074),
+];
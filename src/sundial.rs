@@ -0,0 +1,213 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Planar sundials
+
+/*
+
+The layout of a sundial on a plane of any orientation follows *Meeus*
+(Astronomical Algorithms, chapter 58). Points are given on the plane
+in rectangular coordinates with the origin at the foot of the straight
+stylus, the x axis horizontal and positive towards the right for an
+observer facing the plane, and the y axis along the line of greatest
+slope, positive upwards. On a horizontal dial, for which there's no
+line of greatest slope, the y axis points to the north and the x axis
+to the east.
+
+*/
+
+use std::f64::consts::PI;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Holds the orientation of a planar sundial and the length of its
+/// stylus
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Sundial {
+    /// Geographic latitude of the sundial *| in radians*
+    pub lat: f64,
+    /// Gnomonic declination, the azimuth of the perpendicular to the
+    /// plane, measured westwards from the South *| in radians*
+    pub decl: f64,
+    /// Zenith distance of the perpendicular to the plane, zero for
+    /// a horizontal dial and `PI/2` for a vertical one *| in radians*
+    pub zenith_dist: f64,
+    /// Length of the straight stylus, perpendicular to the plane, in
+    /// any unit, in which the points of the dial are then given
+    pub stylus_len: f64,
+}
+
+/// The declinations of the Sun at the solstices, between which the
+/// hour lines are drawn *| in radians*
+pub const SOLSTICE_DECS: (f64, f64) = (-23.44 * PI / 180.0, 23.44 * PI / 180.0);
+
+/// An hour line, as its points `(x, y)` at the winter and the summer
+/// solstices
+pub type HourLine = ((f64, f64), (f64, f64));
+
+impl Sundial {
+    // Sine of the angle between the polar stylus and the plane, with
+    // the sign of the side of the plane on which it stands
+    fn P(&self) -> f64 {
+        let (sin_phi, cos_phi) = self.lat.sin_cos();
+        let (sin_z, cos_z) = self.zenith_dist.sin_cos();
+
+        sin_phi*cos_z - cos_phi*sin_z*self.decl.cos()
+    }
+
+    /**
+    Computes the center of the sundial, the point where the polar
+    stylus meets the plane and all the hour lines converge
+
+    # Returns
+
+    * `Some((x, y))`: Coordinates of the center, or
+    * `None`        : if the plane is parallel to the Earth's axis,
+                      and the hour lines are parallel
+    **/
+    pub fn center(&self) -> Option<(f64, f64)> {
+        let P = self.P();
+        if P.abs() < 1e-10 {
+            return None;
+        }
+
+        let (sin_phi, cos_phi) = self.lat.sin_cos();
+        let (sin_z, cos_z) = self.zenith_dist.sin_cos();
+        let a = self.stylus_len;
+
+        Some((
+            a / P * cos_phi * self.decl.sin(),
+           -a / P * (sin_phi*sin_z + cos_phi*cos_z*self.decl.cos())
+        ))
+    }
+
+    /**
+    Computes the polar stylus, parallel to the Earth's axis, from the
+    center of the sundial to the tip of the straight stylus
+
+    # Returns
+
+    `(len, angl)`
+
+    * `len` : Length of the polar stylus, infinite if the plane is
+              parallel to the Earth's axis
+    * `angl`: Angle between the polar stylus and the plane
+              *| in radians*
+    **/
+    pub fn polar_stylus(&self) -> (f64, f64) {
+        let P = self.P().abs();
+
+        (self.stylus_len / P, P.min(1.0).asin())
+    }
+
+    /**
+    Computes the point of the sundial on which the shadow of the tip
+    of the straight stylus falls
+
+    # Returns
+
+    * `Some((x, y))`: Coordinates of the shadow, or
+    * `None`        : if the Sun is below the horizon or behind the
+                      plane
+
+    # Arguments
+
+    * `hour_angle`: Local hour angle of the Sun *| in radians*
+    * `dec`       : Declination of the Sun *| in radians*
+    **/
+    pub fn shadow(&self, hour_angle: f64, dec: f64) -> Option<(f64, f64)> {
+        let (sin_phi, cos_phi) = self.lat.sin_cos();
+        let (sin_z, cos_z) = self.zenith_dist.sin_cos();
+        let (sin_D, cos_D) = self.decl.sin_cos();
+        let (sin_H, cos_H) = hour_angle.sin_cos();
+        let tan_dec = dec.tan();
+
+        let sin_alt = sin_phi*dec.sin() + cos_phi*dec.cos()*cos_H;
+        if sin_alt <= 0.0 {
+            return None;
+        }
+
+        let Q = sin_D*sin_z*sin_H
+              + (cos_phi*cos_z + sin_phi*sin_z*cos_D)*cos_H
+              + self.P()*tan_dec;
+        if Q <= 0.0 {
+            return None;
+        }
+
+        let Nx = cos_D*sin_H - sin_D*(sin_phi*cos_H - cos_phi*tan_dec);
+        let Ny = cos_z*sin_D*sin_H
+               - (cos_phi*sin_z - sin_phi*cos_z*cos_D)*cos_H
+               - (sin_phi*sin_z + cos_phi*cos_z*cos_D)*tan_dec;
+
+        let a = self.stylus_len;
+
+        Some((a*Nx/Q, a*Ny/Q))
+    }
+
+    /**
+    Computes an hour line of the sundial, the path of the shadow of
+    the tip of the straight stylus between the solstices, at an hour
+    of local apparent solar time
+
+    # Returns
+
+    * `Some((winter, summer))`: Points `(x, y)` of the hour line at
+                                the declinations of the Sun of
+                                [SOLSTICE_DECS](./constant.SOLSTICE_DECS.html),
+                                or
+    * `None`                  : if the shadow misses the plane at
+                                either solstice
+
+    # Arguments
+
+    * `hour`: Local apparent solar time, 12 at noon *| in hours*
+    **/
+    pub fn hour_line(&self, hour: f64) -> Option<HourLine> {
+        let hour_angle = (hour - 12.0) * PI / 12.0;
+
+        // the winter solstice is in June in the southern hemisphere
+        let (south, north) = SOLSTICE_DECS;
+        let (winter, summer) = if self.lat < 0.0 { (north, south) } else { (south, north) };
+
+        match (self.shadow(hour_angle, winter), self.shadow(hour_angle, summer)) {
+            (Some(w), Some(s)) => Some((w, s)),
+            _ => None
+        }
+    }
+
+    /**
+    Computes the hour lines of the sundial at every whole hour of local
+    apparent solar time
+
+    # Returns
+
+    * `hour_lines`: The hours, from 0 to 23, each with its hour line
+                    as from [hour_line()](#method.hour_line), for the
+                    hours whose lines fall on the plane
+    **/
+    pub fn hour_lines(&self) -> Vec<(u8, HourLine)> {
+        (0..24)
+            .filter_map(|hour| self.hour_line(hour as f64).map(|line| (hour, line)))
+            .collect()
+    }
+}
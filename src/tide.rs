@@ -0,0 +1,195 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Tide-generating potential of the Moon and the Sun
+
+/*
+
+The equilibrium tide is computed from the second degree term of the
+expansion of the tide-generating potential, which carries about 98%
+of it. Laplace's decomposition splits it into three species: the
+long-period (zonal), diurnal (tesseral) and semidiurnal (sectoral)
+tides.
+
+*/
+
+use body::Body;
+use consts;
+use coords;
+use ecliptic;
+use error;
+use lunar;
+use planet;
+use sun;
+use time;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/**
+Computes the tide-generating potential of a body at a point on the
+Earth's surface
+
+# Returns
+
+* `Ok((long_period, diurnal, semidiurnal))`: The long-period (zonal),
+                                             diurnal (tesseral) and
+                                             semidiurnal (sectoral)
+                                             parts of the potential, or
+* `Err(error)`                             : `Error::InvalidBody` for
+                                             a body other than the
+                                             Moon and the Sun
+
+The parts are *| in square meters per square second*. Their sum is the
+potential; dividing it by the acceleration due to gravity gives
+the height of the equilibrium tide.
+
+# Arguments
+
+* `JD`            : Julian (Ephemeris) day
+* `geograph_point`: Geographical position of the point (longitude
+                    measured positively westwards)
+* `height`        : Height of the point above sea level *| in meters*
+* `body`          : The [Body](../body/enum.Body.html)
+**/
+pub fn potential (
+
+    JD             : f64,
+    geograph_point : &coords::GeographPoint,
+    height         : f64,
+    body           : &Body
+
+) -> Result<(f64, f64, f64), error::Error> {
+
+    let g = geometry(JD, geograph_point, height, body)?;
+    let k = 0.75 * g.GM * g.r*g.r / (g.d*g.d*g.d);
+
+    let (sin_phi, cos_phi) = g.phi.sin_cos();
+    let (sin_dec, cos_dec) = g.dec.sin_cos();
+
+    Ok((
+        k * 3.0 * (sin_phi*sin_phi - 1.0/3.0) * (sin_dec*sin_dec - 1.0/3.0),
+        k * (2.0*g.phi).sin() * (2.0*g.dec).sin() * g.H.cos(),
+        k * cos_phi*cos_phi * cos_dec*cos_dec * (2.0*g.H).cos()
+    ))
+
+}
+
+/**
+Computes the tide-raising acceleration of a body at a point on the
+Earth's surface
+
+# Returns
+
+* `Ok((up, north, east))`: The upward, northward and eastward
+                           components of the acceleration, or
+* `Err(error)`           : `Error::InvalidBody` for a body other than
+                           the Moon and the Sun
+
+The components are *| in meters per square second*, and are referred
+to the geocentric vertical of the point.
+
+# Arguments
+
+* `JD`            : Julian (Ephemeris) day
+* `geograph_point`: Geographical position of the point (longitude
+                    measured positively westwards)
+* `height`        : Height of the point above sea level *| in meters*
+* `body`          : The [Body](../body/enum.Body.html)
+**/
+pub fn acceleration (
+
+    JD             : f64,
+    geograph_point : &coords::GeographPoint,
+    height         : f64,
+    body           : &Body
+
+) -> Result<(f64, f64, f64), error::Error> {
+
+    let g = geometry(JD, geograph_point, height, body)?;
+    let k = g.GM * g.r / (g.d*g.d*g.d);
+
+    let (sin_phi, cos_phi) = g.phi.sin_cos();
+    let (sin_dec, cos_dec) = g.dec.sin_cos();
+    let cos_psi = sin_phi*sin_dec + cos_phi*cos_dec*g.H.cos();
+
+    Ok((
+        k * (3.0*cos_psi*cos_psi - 1.0),
+        3.0 * k * cos_psi * (sin_dec*cos_phi - cos_dec*sin_phi*g.H.cos()),
+        3.0 * k * cos_psi * (-cos_dec * g.H.sin())
+    ))
+
+}
+
+struct Geometry {
+    // gravitational parameter of the body, in m^3/s^2
+    GM: f64,
+    // geocentric distance of the point, in meters
+    r: f64,
+    // geocentric latitude of the point
+    phi: f64,
+    // geocentric distance of the body, in meters
+    d: f64,
+    // declination and local hour angle of the body
+    dec: f64,
+    H: f64
+}
+
+fn geometry (
+
+    JD             : f64,
+    geograph_point : &coords::GeographPoint,
+    height         : f64,
+    body           : &Body
+
+) -> Result<Geometry, error::Error> {
+
+    let GM_earth = consts::wgs84::GRAV_CONST;
+
+    let (ecl_point, d, GM) = match *body {
+        Body::Moon => {
+            let (ecl_point, d) = lunar::geocent_ecl_pos(JD);
+            (ecl_point, d * 1000.0, GM_earth / consts::EARTH_MOON_MASS_RATIO)
+        },
+        Body::Sun => {
+            let (ecl_point, d) = sun::geocent_ecl_pos(JD);
+            (ecl_point, d * consts::ASTRONOMICAL_UNIT, GM_earth * consts::SUN_EARTH_MASS_RATIO)
+        },
+        _ => return Err(error::Error::InvalidBody)
+    };
+
+    let oblq = ecliptic::mn_oblq_IAU(JD);
+    let asc = coords::asc_frm_ecl(ecl_point.long, ecl_point.lat, oblq);
+    let dec = coords::dec_frm_ecl(ecl_point.long, ecl_point.lat, oblq);
+    let H = coords::hr_angl_frm_observer_long(time::mn_sidr(JD), geograph_point.long, asc);
+
+    let (rho_sin_phi, rho_cos_phi) = planet::earth::rho_sin_cos_phi(geograph_point.lat, height);
+
+    Ok(Geometry {
+        GM,
+        r   : (rho_sin_phi*rho_sin_phi + rho_cos_phi*rho_cos_phi).sqrt() * planet::earth::eq_rad() * 1000.0,
+        phi : rho_sin_phi.atan2(rho_cos_phi),
+        d,
+        dec,
+        H
+    })
+
+}
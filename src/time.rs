@@ -23,9 +23,20 @@ THE SOFTWARE.
 //! Time for astronomy
 
 use angle;
+use cio;
+use error::Error;
+#[cfg(feature = "chrono")]
+use chrono;
+#[cfg(feature = "time")]
+use time_crate;
+#[cfg(feature = "std")]
+use std::sync::RwLock;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 /// Represents a calendar type
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum CalType {
     /// Gregorian calendar
     Gregorian,
@@ -35,6 +46,7 @@ pub enum CalType {
 
 /// Represents a month in the Gregorian and Julian calendars
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Month {
     /// January
     Jan = 1,
@@ -64,6 +76,7 @@ pub enum Month {
 
 /// Represents a date with year, month, decimal day and calendar type
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Date {
     /// Year
     pub year: i16,
@@ -79,6 +92,7 @@ pub struct Date {
 
 /// Represents a day of a month with hours, minutes and seconds
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DayOfMonth {
     /// Day of month
     ///
@@ -104,6 +118,7 @@ pub struct DayOfMonth {
 
 /// Represents a day of the week
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Weekday {
     /// Sunday
     Sunday,
@@ -126,11 +141,14 @@ pub fn weekday_frm_date(date: &Date) -> Weekday {
     let date_0UT = Date {
         year: date.year,
         month: date.month,
-        decimal_day: date.decimal_day.floor() as f64,
-        cal_type: CalType::Gregorian
+        decimal_day: date.decimal_day.floor(),
+        cal_type: match date.cal_type {
+            CalType::Gregorian => CalType::Gregorian,
+            CalType::Julian    => CalType::Julian
+        }
     };
     let JD = julian_day(&date_0UT);
-    let wd = (JD + 1.5) as i64 % 7;
+    let wd = ((JD + 1.5).floor() as i64).rem_euclid(7);
 
     match wd {
         0 => Weekday::Sunday,
@@ -144,6 +162,409 @@ pub fn weekday_frm_date(date: &Date) -> Weekday {
     }
 }
 
+/**
+Computes the day of the year for a `Date`
+
+# Returns
+
+* `day_of_year`: Day of the year, 1 on January 1
+
+# Arguments
+
+* `date`: A `Date` struct
+**/
+pub fn day_of_year(date: &Date) -> u16 {
+
+    let M = date.month as i64;
+    let K = if is_leap_year(date.year, &date.cal_type) { 1 } else { 2 };
+
+    (275*M/9 - K*((M + 9)/12) + (date.decimal_day.floor() as i64) - 30) as u16
+
+}
+
+/**
+Computes the date of Easter Sunday in a year
+
+# Returns
+
+`(month, day)`
+
+* `month`: Month of Easter Sunday, 3 for March or 4 for April
+* `day`  : Day of the month
+
+# Arguments
+
+* `year`    : Year, after 1582 for the Gregorian calendar
+* `cal_type`: The calendar of the year, `CalType::Julian` giving the
+              Easter of the Julian calendar, which is that of the
+              Orthodox churches
+**/
+pub fn easter(year: i16, cal_type: &CalType) -> (u8, u8) {
+
+    let x = year as i64;
+
+    let f = match *cal_type {
+        CalType::Gregorian => {
+            let a = x % 19;
+            let b = x / 100;
+            let c = x % 100;
+            let d = b / 4;
+            let e = b % 4;
+            let f = (b + 8) / 25;
+            let g = (b - f + 1) / 3;
+            let h = (19*a + b - d - g + 15) % 30;
+            let i = c / 4;
+            let k = c % 4;
+            let l = (32 + 2*e + 2*i - h - k) % 7;
+            let m = (a + 11*h + 22*l) / 451;
+
+            h + l - 7*m + 114
+        },
+        CalType::Julian => {
+            let a = x % 4;
+            let b = x % 7;
+            let c = x % 19;
+            let d = (19*c + 15) % 30;
+            let e = (2*a + 4*b - d + 34) % 7;
+
+            d + e + 114
+        }
+    };
+
+    ((f / 31) as u8, (f % 31 + 1) as u8)
+
+}
+
+/// Represents a month of the Jewish calendar, in the order of the
+/// civil year
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum JewishMonth {
+    /// Tishri, the first month of the civil year
+    Tishri = 1,
+    /// Heshvan, of 29 or 30 days
+    Heshvan = 2,
+    /// Kislev, of 29 or 30 days
+    Kislev = 3,
+    /// Tevet
+    Tevet = 4,
+    /// Shevat
+    Shevat = 5,
+    /// Adar I, the month added in embolismic years
+    AdarI = 6,
+    /// Adar, called Adar II in embolismic years
+    Adar = 7,
+    /// Nisan
+    Nisan = 8,
+    /// Iyar
+    Iyar = 9,
+    /// Sivan
+    Sivan = 10,
+    /// Tammuz
+    Tammuz = 11,
+    /// Av
+    Av = 12,
+    /// Elul
+    Elul = 13,
+}
+
+/// Represents a date in the Jewish calendar
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct JewishDate {
+    /// Year of the Jewish era (Anno Mundi)
+    pub year: i16,
+    /// Month
+    pub month: JewishMonth,
+    /// Day of the month
+    ///
+    /// range: *1 - 30*
+    pub day: u8,
+}
+
+/// Represents a date in the tabular Islamic calendar
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IslamicDate {
+    /// Year of the Hijra
+    pub year: i16,
+    /// Month, 1 for Muharram to 12 for Dhu al-Hijja
+    pub month: u8,
+    /// Day of the month
+    ///
+    /// range: *1 - 30*
+    pub day: u8,
+}
+
+/**
+Computes the date of Pesach (Passover, 15 Nisan) in a year
+
+# Returns
+
+`(month, day)`
+
+* `month`: Month of Pesach, 3 for March or 4 for April
+* `day`  : Day of the month
+
+The date is in the Gregorian calendar from 1583, and in the Julian
+calendar before.
+
+# Arguments
+
+* `year`: Christian year, which is the Jewish year less 3760
+**/
+pub fn pesach(year: i16) -> (u8, u8) {
+
+    let X = year as i64;
+    let C = X / 100;
+    let S = if X >= 1583 { (3*C - 5) / 4 } else { 0 };
+
+    let a = (12*X + 12) % 19;
+    let b = X % 4;
+    let Q = -1.904412361576 + 1.554241796621*(a as f64) + 0.25*(b as f64)
+          - 0.003177794022*(X as f64) + (S as f64);
+    let int_Q = Q.floor() as i64;
+    let j = (int_Q + 3*X + 5*b + 2 - S).rem_euclid(7);
+    let r = Q - (int_Q as f64);
+
+    let D = if j == 2 || j == 4 || j == 6 {
+        int_Q + 23
+    } else if j == 1 && a > 6 && r >= 0.632870370 {
+        int_Q + 24
+    } else if j == 0 && a > 11 && r >= 0.897723765 {
+        int_Q + 23
+    } else {
+        int_Q + 22
+    };
+
+    if D > 31 { (4, (D - 31) as u8) } else { (3, D as u8) }
+
+}
+
+/**
+Checks if a Jewish year is embolismic, having 13 months, rather than
+common, having 12
+
+# Arguments
+
+* `year`: Jewish year
+**/
+#[inline]
+pub fn is_jewish_leap_year(year: i16) -> bool {
+
+    (7 * (year as i64) + 1).rem_euclid(19) < 7
+
+}
+
+/**
+Computes the number of days in a Jewish year
+
+# Returns
+
+* `days`: 353, 354 or 355 for a common year, or 383, 384 or 385
+          for an embolismic year, the three lengths being those of a
+          deficient, a regular and a complete year
+
+# Arguments
+
+* `year`: Jewish year
+**/
+pub fn jewish_year_days(year: i16) -> u16 {
+
+    (jewish_new_year(year + 1) - jewish_new_year(year)) as u16
+
+}
+
+/**
+Computes the Julian day of a date in the Jewish calendar
+
+# Returns
+
+* `JD`: Julian day of the civil day, at 0h, or an
+        `Error::InvalidDate` if the day is beyond the end of its
+        month, or if the month is Adar I in a common year
+
+# Arguments
+
+* `date`: A `JewishDate`
+**/
+pub fn julian_day_frm_jewish(date: &JewishDate) -> Result<f64, Error> {
+
+    if date.day < 1 || date.day > jewish_month_days(date.year, date.month) {
+        return Err(Error::InvalidDate);
+    }
+
+    let days_before: u16 = JEWISH_MONTHS.iter()
+        .take_while(|&&m| m != date.month)
+        .map(|&m| jewish_month_days(date.year, m) as u16)
+        .sum();
+
+    Ok(jewish_new_year(date.year) + (days_before as f64) + (date.day as f64) - 1.0)
+
+}
+
+/**
+Computes the date in the Jewish calendar of a Julian day
+
+# Returns
+
+* `date`: The `JewishDate` of the civil day containing the Julian day
+
+# Arguments
+
+* `JD`: Julian day
+**/
+pub fn jewish_frm_julian_day(JD: f64) -> JewishDate {
+
+    let JD_0h = (JD + 0.5).floor() - 0.5;
+    let christian_year = date_frm_julian_day(JD_0h).map(|(y, _, _)| y).unwrap_or(0);
+
+    let mut year = christian_year + 3761;
+    if JD_0h < jewish_new_year(year) {
+        year -= 1;
+    }
+
+    let mut days = (JD_0h - jewish_new_year(year)) as u16;
+    for &month in JEWISH_MONTHS.iter() {
+        let n = jewish_month_days(year, month) as u16;
+        if days < n {
+            return JewishDate { year, month, day: (days + 1) as u8 };
+        }
+        days -= n;
+    }
+
+    JewishDate { year, month: JewishMonth::Elul, day: 29 }
+
+}
+
+const JEWISH_MONTHS: [JewishMonth; 13] = [
+    JewishMonth::Tishri, JewishMonth::Heshvan, JewishMonth::Kislev,
+    JewishMonth::Tevet, JewishMonth::Shevat, JewishMonth::AdarI,
+    JewishMonth::Adar, JewishMonth::Nisan, JewishMonth::Iyar,
+    JewishMonth::Sivan, JewishMonth::Tammuz, JewishMonth::Av,
+    JewishMonth::Elul
+];
+
+// Julian day of 1 Tishri of a Jewish year, 163 days after the
+// Pesach of the year before
+fn jewish_new_year(year: i16) -> f64 {
+
+    let christian_year = year - 3761;
+    let (month, day) = pesach(christian_year);
+
+    julian_day(&Date {
+        year: christian_year,
+        month: if month == 3 { Month::Mar } else { Month::Apr },
+        decimal_day: day as f64,
+        cal_type: if christian_year >= 1583 { CalType::Gregorian } else { CalType::Julian }
+    }) + 163.0
+
+}
+
+fn jewish_month_days(year: i16, month: JewishMonth) -> u8 {
+
+    // complete years lengthen Heshvan, and deficient years shorten
+    // Kislev
+    let days = jewish_year_days(year) % 10;
+
+    match month {
+        JewishMonth::Heshvan if days == 5 => 30,
+        JewishMonth::Kislev  if days == 3 => 29,
+        JewishMonth::AdarI   if !is_jewish_leap_year(year) => 0,
+        JewishMonth::Heshvan | JewishMonth::Tevet | JewishMonth::Adar |
+        JewishMonth::Iyar | JewishMonth::Tammuz | JewishMonth::Elul => 29,
+        _ => 30
+    }
+
+}
+
+/**
+Checks if a year of the tabular Islamic calendar is a leap year, of
+355 days rather than 354
+
+The leap years are the 2nd, 5th, 7th, 10th, 13th, 16th, 18th, 21st,
+24th, 26th and 29th of each cycle of 30 years.
+
+# Arguments
+
+* `year`: Year of the Hijra
+**/
+#[inline]
+pub fn is_islamic_leap_year(year: i16) -> bool {
+
+    (14 + 11 * (year as i64)).rem_euclid(30) < 11
+
+}
+
+/**
+Computes the Julian day of a date in the tabular Islamic calendar
+
+# Returns
+
+* `JD`: Julian day of the civil day, at 0h, or an
+        `Error::InvalidDate` if the month or the day doesn't exist
+
+# Arguments
+
+* `date`: An `IslamicDate`
+**/
+pub fn julian_day_frm_islamic(date: &IslamicDate) -> Result<f64, Error> {
+
+    if date.month < 1 || date.month > 12 || date.day < 1 || date.day > islamic_month_days(date.year, date.month) {
+        return Err(Error::InvalidDate);
+    }
+
+    Ok(islamic_day_number(date.year as i64, date.month as i64, date.day as i64) as f64 - 0.5)
+
+}
+
+/**
+Computes the date in the tabular Islamic calendar of a Julian day
+
+# Returns
+
+* `date`: The `IslamicDate` of the civil day containing the
+          Julian day
+
+# Arguments
+
+* `JD`: Julian day
+**/
+pub fn islamic_frm_julian_day(JD: f64) -> IslamicDate {
+
+    let N = (JD + 0.5).floor() as i64;
+
+    let year = (30 * (N - ISLAMIC_EPOCH) + 10646).div_euclid(10631);
+    let mut month = 1;
+    while month < 12 && N >= islamic_day_number(year, month + 1, 1) {
+        month += 1;
+    }
+    let day = N - islamic_day_number(year, month, 1) + 1;
+
+    IslamicDate { year: year as i16, month: month as u8, day: day as u8 }
+
+}
+
+// Chronological Julian day number of 1 Muharram AH 1, which is 622
+// July 16 of the Julian calendar
+const ISLAMIC_EPOCH: i64 = 1948440;
+
+fn islamic_day_number(year: i64, month: i64, day: i64) -> i64 {
+
+    ISLAMIC_EPOCH - 1
+  + day
+  + (59*(month - 1) + 1) / 2
+  + 354*(year - 1)
+  + (3 + 11*year).div_euclid(30)
+
+}
+
+fn islamic_month_days(year: i16, month: u8) -> u8 {
+
+    if month % 2 == 1 || (month == 12 && is_islamic_leap_year(year)) { 30 } else { 29 }
+
+}
+
 /**
 Computes decimal day for a `DayOfMonth`
 
@@ -200,11 +621,11 @@ Checks if a year is a leap year
 * `year`    : Year
 * `cal_type`: `CalType` enum
 **/
-pub fn is_leap_year(year: i16, cal_type: &CalType) -> (bool) {
+pub fn is_leap_year(year: i16, cal_type: &CalType) -> bool {
 
-    match cal_type {
-        &CalType::Julian    => year % 4 == 0,
-        &CalType::Gregorian => {
+    match *cal_type {
+        CalType::Julian    => year % 4 == 0,
+        CalType::Gregorian => {
             if year % 100 == 0 { year % 400 == 0 }
             else               { year % 4   == 0 }
         },
@@ -271,6 +692,45 @@ pub fn julian_day(date: &Date) -> f64 {
 
 }
 
+/**
+Computes Julian day from a `Date`, checking that the date exists
+
+# Returns
+
+* `JD`: Julian day of the date, or an `Error::InvalidDate` if the day
+        is beyond the end of its month, or if a Gregorian date is
+        before the reform of 1582 October 15
+
+# Arguments
+
+`date`: A `Date`
+**/
+pub fn try_julian_day(date: &Date) -> Result<f64, Error> {
+
+    if !date.decimal_day.is_finite() {
+        return Err(Error::NotFinite);
+    }
+
+    let days_in_month = match date.month {
+        Month::Feb => if is_leap_year(date.year, &date.cal_type) { 29 } else { 28 },
+        Month::Apr | Month::June | Month::Sept | Month::Nov => 30,
+        _ => 31
+    };
+    if date.decimal_day < 1.0 || date.decimal_day >= (days_in_month as f64) + 1.0 {
+        return Err(Error::InvalidDate);
+    }
+
+    let JD = julian_day(date);
+    if let CalType::Gregorian = date.cal_type {
+        if JD < 2299160.5 {
+            return Err(Error::InvalidDate);
+        }
+    }
+
+    Ok(JD)
+
+}
+
 /**
 Computes the Julian Ephemeris day
 
@@ -286,6 +746,81 @@ pub fn julian_ephemeris_day(JD: f64, delta_t: f64) -> f64 {
 
 }
 
+// 1977 January 1.0 TAI, as a Julian day (TT), at which TT, TCG and
+// TCB agree
+const REL_TIME_EPOCH: f64 = 2443144.5003725;
+
+// Rate of TCG relative to TT, and of TCB relative to TDB
+const L_G: f64 = 6.969290134e-10;
+const L_B: f64 = 1.550519768e-8;
+
+// TDB - TCB at the epoch *| in seconds*
+const TDB_0: f64 = -6.55e-5;
+
+/**
+Computes the Julian day in TCG from a Julian day in TT
+
+TCG (Geocentric Coordinate Time) runs faster than TT by the defined
+rate L<sub>G</sub> = 6.969290134 x 10<sup>-10</sup>.
+
+# Arguments
+
+* `JD`: Julian day (TT)
+**/
+#[inline]
+pub fn tcg_frm_tt(JD: f64) -> f64 {
+
+    JD + L_G/(1.0 - L_G) * (JD - REL_TIME_EPOCH)
+
+}
+
+/**
+Computes the Julian day in TT from a Julian day in TCG
+
+# Arguments
+
+* `JD`: Julian day (TCG)
+**/
+#[inline]
+pub fn tt_frm_tcg(JD: f64) -> f64 {
+
+    JD - L_G * (JD - REL_TIME_EPOCH)
+
+}
+
+/**
+Computes the Julian day in TCB from a Julian day in TDB
+
+TCB (Barycentric Coordinate Time) runs faster than TDB by the
+defined rate L<sub>B</sub> = 1.550519768 x 10<sup>-8</sup>, with
+the offset TDB<sub>0</sub> = -6.55 x 10<sup>-5</sup> seconds of the
+IAU 2006 definition of TDB.
+
+# Arguments
+
+* `JD`: Julian day (TDB)
+**/
+#[inline]
+pub fn tcb_frm_tdb(JD: f64) -> f64 {
+
+    REL_TIME_EPOCH + (JD - REL_TIME_EPOCH - TDB_0/86400.0) / (1.0 - L_B)
+
+}
+
+/**
+Computes the Julian day in TDB from a Julian day in TCB
+
+# Arguments
+
+* `JD`: Julian day (TCB)
+**/
+#[inline]
+pub fn tdb_frm_tcb(JD: f64) -> f64 {
+
+    JD - L_B * (JD - REL_TIME_EPOCH) + TDB_0/86400.0
+
+}
+
 /**
 Computes a year, month and decimal day equivalent to a given Julian day
 
@@ -299,14 +834,22 @@ Computes a year, month and decimal day equivalent to a given Julian day
 
 # Arguments
 
-`JD`: Julian Day. **Can't be a negative value.**
+`JD`: Julian Day. Dates before 1582 October 15 are in the Julian
+      calendar, and those after it in the Gregorian calendar. Years
+      before 1 AD are counted astronomically, so that the year 0 is
+      1 BC.
 **/
 pub fn date_frm_julian_day<'a> (mut JD: f64) -> Result<(i16, u8, f64), &'a str> {
 
-    if JD < 0.0 {
-        return Err("A negative value for JD was passed to time::date_frm_julian_day()");
+    if !JD.is_finite() {
+        return Err("A non-finite value for JD was passed to time::date_frm_julian_day()");
     }
 
+    // negative Julian days are moved forward by whole cycles of four
+    // Julian years, which are taken off the year in the end
+    let cycles = if JD < 0.0 { (-JD / 1461.0).floor() + 1.0 } else { 0.0 };
+    JD += cycles * 1461.0;
+
     JD += 0.5;
     let Z = JD as i64;
     let F = JD - (Z as f64);
@@ -337,28 +880,337 @@ pub fn date_frm_julian_day<'a> (mut JD: f64) -> Result<(i16, u8, f64), &'a str>
                    return Err("Internal error in time::date_frm_julian_day()");
                };
 
-    Ok( (year as i16, month as u8, day) )
+    Ok( ((year - 4*(cycles as i64)) as i16, month as u8, day) )
 
 }
 
 /**
-Computes apparent sidereal time from the mean sidereal time
+Computes the Julian day of a date and time in ISO 8601 format
+
+The accepted forms are the extended formats `YYYY-MM-DD`,
+`YYYY-MM-DDThh:mm`, `YYYY-MM-DDThh:mm:ss` and `YYYY-MM-DDThh:mm:ss.sss`,
+with a space allowed in place of the `T`, and an optional time zone
+of `Z`, `±hh`, `±hh:mm` or `±hhmm`. Years may carry a sign and more
+than four digits. A time without a zone is taken as UTC.
 
 # Returns
 
-* `apprnt_sidr`: Apparent sidereal time *| in radians*
+* `JD`: Julian day in the time scale of the text, or an
+        `Error::InvalidFormat` if the text isn't of one of the
+        forms, or an `Error::InvalidDate` if the date or the time
+        doesn't exist
 
 # Arguments
 
-* `mn_sidr`    : Mean sidereal time *| in radians*
-* `nut_in_long`: Nutatation in longitude *| in radians*
-* `true_oblq`  : True obliquity of the ecliptic *| in radians*
+* `text`: Date and time in the proleptic Gregorian calendar, as
+          ISO 8601 requires even before the reform of 1582
 **/
-pub fn apprnt_sidr(mn_sidr: f64, nut_in_long: f64, true_oblq: f64) -> f64 {
-
-    mn_sidr + nut_in_long*true_oblq.cos()
+pub fn julian_day_frm_iso8601(text: &str) -> Result<f64, Error> {
 
-}
+    let text = text.trim();
+    let mut p = Parser { bytes: text.as_bytes(), pos: 0 };
+
+    let sign = p.sign();
+    let year_digits = p.count_digits();
+    if year_digits < 4 || (year_digits > 4 && sign.is_none()) {
+        return Err(Error::InvalidFormat);
+    }
+    let year = sign.unwrap_or(1) * p.number(year_digits)?;
+    p.expect(b'-')?;
+    let month = p.number(2)?;
+    p.expect(b'-')?;
+    let day = p.number(2)?;
+
+    let (mut hr, mut min, mut sec) = (0, 0, 0.0);
+    let mut zone_mins = 0;
+    if p.eat(b'T') || p.eat(b' ') {
+        hr = p.number(2)?;
+        p.expect(b':')?;
+        min = p.number(2)?;
+        if p.eat(b':') {
+            sec = p.number(2)? as f64;
+            if p.eat(b'.') || p.eat(b',') {
+                let digits = p.count_digits();
+                if digits == 0 {
+                    return Err(Error::InvalidFormat);
+                }
+                sec += (p.number(digits)? as f64) / 10_f64.powi(digits as i32);
+            }
+        }
+
+        if !p.eat(b'Z') {
+            if let Some(zone_sign) = p.sign() {
+                let zone_hr = p.number(2)?;
+                p.eat(b':');
+                let zone_min = if p.count_digits() > 0 { p.number(2)? } else { 0 };
+                if zone_hr > 23 || zone_min > 59 {
+                    return Err(Error::InvalidDate);
+                }
+                zone_mins = zone_sign * (60*zone_hr + zone_min);
+            }
+        }
+    }
+    if p.pos != p.bytes.len() {
+        return Err(Error::InvalidFormat);
+    }
+
+    let leap = year.rem_euclid(4) == 0 && (year.rem_euclid(100) != 0 || year.rem_euclid(400) == 0);
+    let days_in_month = match month {
+        2 => if leap { 29 } else { 28 },
+        4 | 6 | 9 | 11 => 30,
+        _ => 31
+    };
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month
+    || hr > 24 || min > 59 || sec >= 61.0 || (hr == 24 && (min > 0 || sec > 0.0)) {
+        return Err(Error::InvalidDate);
+    }
+
+    let day_fraction = ((60*hr + min - zone_mins) as f64 * 60.0 + sec) / 86400.0;
+
+    Ok(gregorian_day_number(year, month, day) as f64 - 0.5 + day_fraction)
+
+}
+
+/**
+Formats a Julian day as a date and time in ISO 8601 format
+
+The text has the form `YYYY-MM-DDThh:mm:ss.sssZ` in the proleptic
+Gregorian calendar, rounded to the millisecond. Years before 0 or
+after 9999 carry a sign.
+
+# Arguments
+
+* `JD`: Julian day, in UTC for the `Z` of the text to hold
+**/
+pub fn iso8601_frm_julian_day(JD: f64) -> String {
+
+    let mut N = (JD + 0.5).floor() as i64;
+    let mut ms = ((JD + 0.5 - (N as f64)) * 86400000.0).round() as i64;
+    if ms >= 86400000 {
+        N += 1;
+        ms -= 86400000;
+    }
+
+    let (year, month, day) = gregorian_frm_day_number(N);
+    let year = if (0..10000).contains(&year) {
+        format!("{:04}", year)
+    } else {
+        format!("{}{:04}", if year < 0 { '-' } else { '+' }, year.abs())
+    };
+
+    format!(
+        "{}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year, month, day,
+        ms / 3600000, ms / 60000 % 60, ms / 1000 % 60, ms % 1000
+    )
+
+}
+
+// Chronological Julian day number of a date in the proleptic
+// Gregorian calendar
+fn gregorian_day_number(year: i64, month: i64, day: i64) -> i64 {
+
+    let a = (14 - month) / 12;
+    let y = year + 4800 - a;
+    let m = month + 12*a - 3;
+
+    day + (153*m + 2)/5 + 365*y + y.div_euclid(4) - y.div_euclid(100) + y.div_euclid(400) - 32045
+
+}
+
+fn gregorian_frm_day_number(N: i64) -> (i64, i64, i64) {
+
+    let a = N + 32044;
+    let b = (4*a + 3).div_euclid(146097);
+    let c = a - (146097*b).div_euclid(4);
+    let d = (4*c + 3) / 1461;
+    let e = c - 1461*d/4;
+    let m = (5*e + 2) / 153;
+
+    (100*b + d - 4800 + m/10, m + 3 - 12*(m/10), e - (153*m + 2)/5 + 1)
+
+}
+
+// A cursor over the bytes of an ISO 8601 text
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn eat(&mut self, b: u8) -> bool {
+        if self.bytes.get(self.pos) == Some(&b) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, b: u8) -> Result<(), Error> {
+        if self.eat(b) { Ok(()) } else { Err(Error::InvalidFormat) }
+    }
+
+    fn sign(&mut self) -> Option<i64> {
+        if self.eat(b'+') {
+            Some(1)
+        } else if self.eat(b'-') {
+            Some(-1)
+        } else {
+            None
+        }
+    }
+
+    fn count_digits(&self) -> usize {
+        self.bytes[self.pos..].iter().take_while(|b| b.is_ascii_digit()).count()
+    }
+
+    fn number(&mut self, digits: usize) -> Result<i64, Error> {
+        if digits > 18 || self.count_digits() < digits {
+            return Err(Error::InvalidFormat);
+        }
+        let n = self.bytes[self.pos..self.pos + digits].iter()
+            .fold(0, |n, &b| 10*n + ((b - b'0') as i64));
+        self.pos += digits;
+
+        Ok(n)
+    }
+}
+
+/**
+Computes the Julian day of a `chrono` date and time
+
+Leap seconds aren't counted, as in the Unix time of `chrono`. A
+Julian day near the present holds its time to about 40 microseconds.
+
+# Returns
+
+* `JD`: Julian day in UTC
+
+# Arguments
+
+* `date_time`: A `chrono::DateTime<Utc>`
+**/
+#[cfg(feature = "chrono")]
+pub fn julian_day_frm_chrono(date_time: &chrono::DateTime<chrono::Utc>) -> f64 {
+
+    julian_day_frm_unix(date_time.timestamp(), date_time.timestamp_subsec_nanos())
+
+}
+
+/**
+Computes the `chrono` date and time of a Julian day
+
+# Returns
+
+* `date_time`: A `chrono::DateTime<Utc>`, or `None` if the Julian
+               day is out of the range of `chrono`
+
+# Arguments
+
+* `JD`: Julian day in UTC
+**/
+#[cfg(feature = "chrono")]
+pub fn chrono_frm_julian_day(JD: f64) -> Option<chrono::DateTime<chrono::Utc>> {
+
+    let (secs, nanos) = unix_frm_julian_day(JD)?;
+
+    chrono::DateTime::from_timestamp(secs, nanos)
+
+}
+
+/**
+Computes the Julian day of a date and time of the `time` crate
+
+Leap seconds aren't counted, as in the Unix time of `time`. A Julian
+day near the present holds its time to about 40 microseconds.
+
+# Returns
+
+* `JD`: Julian day in UTC
+
+# Arguments
+
+* `date_time`: A `time::OffsetDateTime`, of any offset
+**/
+#[cfg(feature = "time")]
+pub fn julian_day_frm_offset_date_time(date_time: &time_crate::OffsetDateTime) -> f64 {
+
+    julian_day_frm_unix(date_time.unix_timestamp(), date_time.nanosecond())
+
+}
+
+/**
+Computes the date and time of the `time` crate of a Julian day
+
+# Returns
+
+* `date_time`: A `time::OffsetDateTime` in UTC, or `None` if the
+               Julian day is out of the range of `time`
+
+# Arguments
+
+* `JD`: Julian day in UTC
+**/
+#[cfg(feature = "time")]
+pub fn offset_date_time_frm_julian_day(JD: f64) -> Option<time_crate::OffsetDateTime> {
+
+    let (secs, nanos) = unix_frm_julian_day(JD)?;
+
+    time_crate::OffsetDateTime::from_unix_timestamp(secs).ok()
+        .map(|date_time| date_time + time_crate::Duration::nanoseconds(nanos as i64))
+
+}
+
+// Julian day of the Unix epoch, 1970 January 1 at 0h UTC
+#[cfg(any(feature = "chrono", feature = "time"))]
+const UNIX_EPOCH_JD: f64 = 2440587.5;
+
+// The whole days and the seconds are kept apart until the end, to
+// keep the precision of the fraction of the day
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn julian_day_frm_unix(secs: i64, nanos: u32) -> f64 {
+
+    let days = secs.div_euclid(86400);
+    let secs_of_day = secs.rem_euclid(86400) as f64 + (nanos as f64) * 1e-9;
+
+    UNIX_EPOCH_JD + (days as f64) + secs_of_day/86400.0
+
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn unix_frm_julian_day(JD: f64) -> Option<(i64, u32)> {
+
+    if !JD.is_finite() || (JD - UNIX_EPOCH_JD).abs() > 1e11 {
+        return None;
+    }
+
+    let days = (JD - UNIX_EPOCH_JD).floor();
+    let micros = ((JD - UNIX_EPOCH_JD - days) * 86400e6).round() as i64;
+    let secs = (days as i64) * 86400 + micros.div_euclid(1000000);
+
+    Some((secs, (micros.rem_euclid(1000000) * 1000) as u32))
+
+}
+
+/**
+Computes apparent sidereal time from the mean sidereal time
+
+# Returns
+
+* `apprnt_sidr`: Apparent sidereal time *| in radians*
+
+# Arguments
+
+* `mn_sidr`    : Mean sidereal time *| in radians*
+* `nut_in_long`: Nutatation in longitude *| in radians*
+* `true_oblq`  : True obliquity of the ecliptic *| in radians*
+**/
+pub fn apprnt_sidr(mn_sidr: f64, nut_in_long: f64, true_oblq: f64) -> f64 {
+
+    mn_sidr + nut_in_long*true_oblq.cos()
+
+}
 
 /**
 Computes apparent sidereal time for a Julian day
@@ -408,13 +1260,104 @@ pub fn mn_sidr(JD: f64) -> f64 {
 
 }
 
+/**
+Computes mean sidereal time for a Julian day, using the IAU 2006
+expression
+
+The expression is that of the IERS Conventions (2010), consistent
+with the IAU 2006 precession. It's the Earth rotation angle plus a
+polynomial in TT, and differs from `mn_sidr()` by a few milliseconds
+of time around the present.
+
+# Returns
+
+* `mn_sidr`: Mean sidereal time *| in radians*
+
+# Arguments
+
+* `JD`: Julian day in UT
+**/
+pub fn mn_sidr_IAU2006(JD: f64) -> f64 {
+
+    let t = julian_cent(tt_frm_ut(JD));
+
+    let poly = 0.014506 + t*(4612.156534 + t*(1.3915817 + t*(-0.00000044
+             + t*(-0.000029956 - t*0.0000000368))));
+
+    angle::limit_to_two_PI(cio::earth_rot_angl(JD) + (poly/3600.0).to_radians())
+
+}
+
+/**
+Computes local sidereal time from Greenwich sidereal time
+
+The local sidereal time is mean or apparent, as the Greenwich
+sidereal time passed is.
+
+# Returns
+
+* `local_sidr`: Local sidereal time *| in radians*
+
+# Arguments
+
+* `green_sidr`   : Greenwich sidereal time *| in radians*
+* `observer_long`: Observer's geographical longitude *| in radians*
+**/
+#[inline]
+pub fn local_sidr(green_sidr: f64, observer_long: f64) -> f64 {
+
+    angle::limit_to_two_PI(green_sidr - observer_long)
+
+}
+
+// User-supplied ΔT values, as (decimal year, ΔT in seconds) in
+// increasing order of year
+#[cfg(feature = "std")]
+static DELTA_T_TABLE: RwLock<Vec<(f64, f64)>> = RwLock::new(Vec::new());
+
+/**
+Sets a table of ΔT values that overrides the polynomial expressions
+
+Within the range of years covered by the table, `delta_t()` and the
+functions that depend on it interpolate linearly between the
+tabulated values instead of evaluating the polynomial expressions.
+This allows observed values, such as those published by the IERS,
+to be used for modern dates. The pairs may be given in any order;
+passing `None` restores the polynomial expressions everywhere.
+
+The table is shared by every thread of the program, so it's best set
+once, before any thread computes ΔT.
+
+# Arguments
+
+* `table`: Pairs of (decimal year, ΔT *| in seconds*), or `None`
+**/
+#[cfg(feature = "std")]
+pub fn set_delta_t_table(table: Option<Vec<(f64, f64)>>) {
+
+    let mut table = table.unwrap_or_default();
+    table.retain(|&(y, dt)| y.is_finite() && dt.is_finite());
+    table.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+    if let Ok(mut t) = DELTA_T_TABLE.write() {
+        *t = table;
+    }
+
+}
+
 /**
 Computes an approximate value of ΔT for a given year and month
 
 This function approximates ΔT from polynomial expressions using a
 method different from that given in the *Meeus* book. The method
 used is given [here](http://eclipse.gsfc.nasa.gov/SEcat5/deltatpoly.html);
-it covers a far wider time range, and is more accurate.
+it covers a far wider time range, and is more accurate. A table set
+with `set_delta_t_table()` takes precedence over the expressions
+for the years it covers.
+
+# Returns
+
+* `delta_t`: ΔT = TT - UT *| in seconds*
 
 # Arguments
 
@@ -423,131 +1366,525 @@ it covers a far wider time range, and is more accurate.
 **/
 pub fn delta_t(year: i32, month: u8) -> f64 {
 
-    let y = (year as f64) + ((month as f64) - 0.5)/12.0;
+    delta_t_frm_decimal_year((year as f64) + ((month as f64) - 0.5)/12.0)
+
+}
+
+/**
+Computes an approximate value of ΔT for a Julian day
+
+# Returns
+
+* `delta_t`: ΔT = TT - UT *| in seconds*
+
+# Arguments
+
+* `JD`: Julian day
+**/
+pub fn delta_t_frm_julian_day(JD: f64) -> f64 {
+
+    delta_t_frm_decimal_year(2000.0 + (JD - 2451545.0)/365.25)
+
+}
+
+/**
+Computes the Julian day in TT from a Julian day in UT
+
+# Arguments
+
+* `JD`: Julian day in UT
+**/
+pub fn tt_frm_ut(JD: f64) -> f64 {
+
+    JD + delta_t_frm_julian_day(JD)/86400.0
+
+}
+
+/**
+Computes the Julian day in UT from a Julian day in TT
+
+# Arguments
+
+* `JD`: Julian day in TT
+**/
+pub fn ut_frm_tt(JD: f64) -> f64 {
+
+    // ΔT changes slowly enough that evaluating it at the TT instant,
+    // and then once more at the resulting UT instant, is exact to
+    // far below a millisecond
+    let JD_ut = JD - delta_t_frm_julian_day(JD)/86400.0;
+
+    JD - delta_t_frm_julian_day(JD_ut)/86400.0
+
+}
+
+/// Represents a time scale
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TimeScale {
+    /// Coordinated Universal Time, of civil clocks
+    UTC,
+    /// Universal Time, of the rotation of the Earth
+    UT1,
+    /// International Atomic Time
+    TAI,
+    /// Terrestrial Time, the successor of Ephemeris Time
+    TT,
+    /// Barycentric Dynamical Time
+    TDB,
+}
+
+/// Represents an instant, as a Julian day in a time scale
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Instant {
+    /// Julian day
+    pub JD: f64,
+    /// Time scale of the Julian day
+    pub scale: TimeScale,
+}
+
+impl Instant {
+    /// An instant at a Julian day in a time scale
+    pub fn new(JD: f64, scale: TimeScale) -> Instant {
+        Instant { JD, scale }
+    }
+
+    /**
+    Converts the instant to another time scale
+
+    The conversions pass through TT. UTC and TAI differ by the leap
+    seconds of `tai_minus_utc()`, and UT1 is found from UTC and the
+    hook of `set_dut1()` if one is set, or else from TT and ΔT.
+
+    # Arguments
+
+    * `scale`: The `TimeScale` to convert to
+    **/
+    pub fn to(&self, scale: TimeScale) -> Instant {
+        let tt = match self.scale {
+            TimeScale::TT  => self.JD,
+            TimeScale::TAI => tt_frm_tai(self.JD),
+            TimeScale::UTC => tt_frm_tai(tai_frm_utc(self.JD)),
+            TimeScale::UT1 => match dut1_hook() {
+                Some(dut1) => tt_frm_tai(tai_frm_utc(self.JD - dut1(self.JD)/86400.0)),
+                None       => tt_frm_ut(self.JD),
+            },
+            TimeScale::TDB => tt_frm_tdb(self.JD),
+        };
+
+        let JD = match scale {
+            TimeScale::TT  => tt,
+            TimeScale::TAI => tai_frm_tt(tt),
+            TimeScale::UTC => utc_frm_tai(tai_frm_tt(tt)),
+            TimeScale::UT1 => match dut1_hook() {
+                Some(dut1) => {
+                    let utc = utc_frm_tai(tai_frm_tt(tt));
+                    utc + dut1(utc)/86400.0
+                },
+                None => ut_frm_tt(tt),
+            },
+            TimeScale::TDB => tdb_frm_tt(tt),
+        };
+
+        Instant { JD, scale }
+    }
+}
+
+/**
+Computes the Julian day in TT from a Julian day in TAI
+
+# Arguments
+
+* `JD`: Julian day (TAI)
+**/
+#[inline]
+pub fn tt_frm_tai(JD: f64) -> f64 {
+
+    JD + TT_MINUS_TAI/86400.0
+
+}
+
+/**
+Computes the Julian day in TAI from a Julian day in TT
+
+# Arguments
+
+* `JD`: Julian day (TT)
+**/
+#[inline]
+pub fn tai_frm_tt(JD: f64) -> f64 {
+
+    JD - TT_MINUS_TAI/86400.0
+
+}
+
+/**
+Computes the Julian day in TAI from a Julian day in UTC
+
+# Arguments
+
+* `JD`: Julian day (UTC)
+**/
+#[inline]
+pub fn tai_frm_utc(JD: f64) -> f64 {
+
+    JD + tai_minus_utc(JD)/86400.0
+
+}
+
+/**
+Computes the Julian day in UTC from a Julian day in TAI
+
+During a leap second, which has no Julian day of its own in UTC,
+the result is the instant that follows it.
+
+# Arguments
+
+* `JD`: Julian day (TAI)
+**/
+pub fn utc_frm_tai(JD: f64) -> f64 {
+
+    let JD_utc = JD - tai_minus_utc(JD)/86400.0;
+
+    JD - tai_minus_utc(JD_utc)/86400.0
+
+}
+
+/**
+Computes the Julian day in TDB from a Julian day in TT
+
+TDB differs from TT by less than 2 milliseconds, mostly with the
+period of the anomalistic year. The two leading periodic terms are
+kept, which is accurate to some tens of microseconds.
+
+# Arguments
+
+* `JD`: Julian day (TT)
+**/
+#[inline]
+pub fn tdb_frm_tt(JD: f64) -> f64 {
+
+    JD + tdb_minus_tt(JD)/86400.0
+
+}
+
+/**
+Computes the Julian day in TT from a Julian day in TDB
+
+# Arguments
+
+* `JD`: Julian day (TDB)
+**/
+#[inline]
+pub fn tt_frm_tdb(JD: f64) -> f64 {
+
+    JD - tdb_minus_tt(JD)/86400.0
+
+}
+
+/**
+Computes the difference TAI - UTC, the accumulated leap seconds
+
+Before 1972, when UTC had no leap seconds, UTC is taken to be UT1,
+so that the difference is ΔT - 32.184 seconds. Leap seconds added
+with `set_leap_seconds()` extend the built-in table, which ends at
+2017 January 1.
+
+# Returns
+
+* `tai_minus_utc`: TAI - UTC *| in seconds*
+
+# Arguments
+
+* `JD`: Julian day (UTC)
+**/
+pub fn tai_minus_utc(JD: f64) -> f64 {
+
+    let start = |&(year, month, _): &(i64, i64, f64)| gregorian_day_number(year, month, 1) as f64 - 0.5;
+    if JD < start(&LEAP_SECONDS[0]) {
+        return delta_t_frm_julian_day(JD) - TT_MINUS_TAI;
+    }
+
+    #[cfg(feature = "std")]
+    {
+        if let Ok(table) = LEAP_SECONDS_ADDED.read() {
+            if let Some(&(_, seconds)) = table.iter().rev().find(|&&(JD_start, _)| JD >= JD_start) {
+                return seconds;
+            }
+        }
+    }
+
+    LEAP_SECONDS.iter()
+        .rev()
+        .find(|leap| JD >= start(leap))
+        .map_or(LEAP_SECONDS[0].2, |leap| leap.2)
+
+}
+
+/**
+Adds leap seconds after those of the built-in table
+
+Each leap second is given by the Julian day (UTC) from which the new
+value of TAI - UTC holds, normally 0h UTC of January 1 or July 1.
+Passing `None` removes the added leap seconds.
+
+# Arguments
+
+* `table`: Pairs of (Julian day (UTC), TAI - UTC *| in seconds*), or
+           `None`
+**/
+#[cfg(feature = "std")]
+pub fn set_leap_seconds(table: Option<Vec<(f64, f64)>>) {
+
+    let mut table = table.unwrap_or_default();
+    table.retain(|&(JD, s)| JD.is_finite() && s.is_finite());
+    table.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+    if let Ok(mut t) = LEAP_SECONDS_ADDED.write() {
+        *t = table;
+    }
+
+}
+
+/**
+Sets a hook that gives DUT1 = UT1 - UTC
+
+The values of DUT1 are published by the IERS from observations of the
+Earth's rotation. With a hook set, conversions between UT1 and the
+other time scales go through UTC from 1972 onwards, instead of
+through ΔT. Passing `None` removes the hook.
+
+# Arguments
+
+* `hook`: A function of the Julian day (UTC) giving DUT1
+          *| in seconds*, or `None`
+**/
+#[cfg(feature = "std")]
+pub fn set_dut1(hook: Option<fn(f64) -> f64>) {
+
+    if let Ok(mut h) = DUT1_HOOK.write() {
+        *h = hook;
+    }
+
+}
+
+// TT - TAI, in seconds
+const TT_MINUS_TAI: f64 = 32.184;
+
+// The leap seconds, as (year, month, TAI - UTC in seconds) from the
+// first day of the month
+const LEAP_SECONDS: [(i64, i64, f64); 28] = [
+    (1972, 1, 10.0), (1972, 7, 11.0), (1973, 1, 12.0), (1974, 1, 13.0),
+    (1975, 1, 14.0), (1976, 1, 15.0), (1977, 1, 16.0), (1978, 1, 17.0),
+    (1979, 1, 18.0), (1980, 1, 19.0), (1981, 7, 20.0), (1982, 7, 21.0),
+    (1983, 7, 22.0), (1985, 7, 23.0), (1988, 1, 24.0), (1990, 1, 25.0),
+    (1991, 1, 26.0), (1992, 7, 27.0), (1993, 7, 28.0), (1994, 7, 29.0),
+    (1996, 1, 30.0), (1997, 7, 31.0), (1999, 1, 32.0), (2006, 1, 33.0),
+    (2009, 1, 34.0), (2012, 7, 35.0), (2015, 7, 36.0), (2017, 1, 37.0)
+];
+
+// Leap seconds added by the user, as (Julian day (UTC), TAI - UTC)
+#[cfg(feature = "std")]
+static LEAP_SECONDS_ADDED: RwLock<Vec<(f64, f64)>> = RwLock::new(Vec::new());
+
+// A function of the Julian day (UTC) giving DUT1 in seconds
+type Dut1Hook = fn(f64) -> f64;
+
+#[cfg(feature = "std")]
+static DUT1_HOOK: RwLock<Option<Dut1Hook>> = RwLock::new(None);
+
+// The hook giving DUT1, which is used only from 1972, when UTC took
+// its present form
+#[cfg(feature = "std")]
+fn dut1_hook() -> Option<Dut1Hook> {
+
+    DUT1_HOOK.read().ok().and_then(|h| *h)
+
+}
+
+#[cfg(not(feature = "std"))]
+fn dut1_hook() -> Option<Dut1Hook> {
+
+    None
+
+}
+
+// TDB - TT, in seconds
+pub(crate) fn tdb_minus_tt(JD: f64) -> f64 {
+
+    let g = (357.53 + 0.98560028*(JD - 2451545.0)).to_radians();
+
+    0.001657*g.sin() + 0.000014*(2.0*g).sin()
+
+}
+
+#[cfg(feature = "std")]
+fn delta_t_frm_decimal_year(y: f64) -> f64 {
+
+    if let Ok(table) = DELTA_T_TABLE.read() {
+        if let Some(dt) = delta_t_frm_table(&table, y) {
+            return dt;
+        }
+    }
+
+    delta_t_poly(y)
+
+}
+
+// Without the standard library, there's no table to override the
+// polynomial expressions
+#[cfg(not(feature = "std"))]
+fn delta_t_frm_decimal_year(y: f64) -> f64 {
+
+    delta_t_poly(y)
+
+}
+
+#[cfg(feature = "std")]
+fn delta_t_frm_table(table: &[(f64, f64)], y: f64) -> Option<f64> {
+
+    let first = table.first()?;
+    let last = table.last()?;
+    if y < first.0 || y > last.0 {
+        return None;
+    }
+
+    let i = table.iter().position(|&(y_i, _)| y_i >= y)?;
+    if i == 0 || table[i].0 == y {
+        return Some(table[i].1);
+    }
+
+    let (y1, dt1) = table[i - 1];
+    let (y2, dt2) = table[i];
+
+    Some(dt1 + (dt2 - dt1)*(y - y1)/(y2 - y1))
+
+}
+
+fn delta_t_poly(y: f64) -> f64 {
 
     if y < -500.0 {
         let u = (y - 1820.0) / 100.0;
-        return 32.0*u*u - 20.0;
+        32.0*u*u - 20.0
     }
     else if y < 500.0 {
         let u = y / 100.0;
-        return 10583.6 -
-               u * (1014.41 +
-               u * (33.78311 +
-               u * (5.952053 -
-		       u * (0.1798452 -
-               u * (0.022174192 -
-                    u * 0.0090316521)
-                ))));
+        10583.6 -
+        u * (1014.41 -
+        u * (33.78311 -
+        u * (5.952053 +
+        u * (0.1798452 -
+        u * (0.022174192 +
+             u * 0.0090316521
+            )))))
     }
     else if y < 1600.0 {
         let u = (y - 1000.0) / 100.0;
-        return 1574.2 -
-               u * (556.01 -
-               u * (71.23472 +
-               u * (0.319781 -
-		       u * (0.8503463 +
-               u * (0.005050998 -
-                    u * 0.0083572073)
-                   ))));
+        1574.2 -
+        u * (556.01 -
+        u * (71.23472 +
+        u * (0.319781 -
+        u * (0.8503463 +
+        u * (0.005050998 -
+             u * 0.0083572073
+            )))))
     }
     else if y < 1700.0 {
         let u = y - 1600.0;
-        return 120.0 -
-               u * (0.9808 +
-               u * (0.01532 -
-                    u / 7129.0
-                   ));
+        120.0 -
+        u * (0.9808 +
+        u * (0.01532 -
+             u / 7129.0
+            ))
     }
     else if y < 1800.0 {
         let u = y - 1700.0;
-        return 8.83 +
-               u * (0.1603 -
-               u * (0.0059285 -
-               u * (0.00013336 +
-                    u / 1174000.0
-                   )));
+        8.83 +
+        u * (0.1603 -
+        u * (0.0059285 -
+        u * (0.00013336 -
+             u / 1174000.0
+            )))
     }
     else if y < 1860.0 {
         let u = y - 1800.0;
-        return 13.72 -
-               u * (0.332447 -
-               u * (0.0068612 +
-               u * (0.0041116 -
-               u * (0.00037436 -
-               u * (0.0000121272 +
-               u * (0.0000001699 -
-                    u * 0.000000000875
-                   ))))));
+        13.72 -
+        u * (0.332447 -
+        u * (0.0068612 +
+        u * (0.0041116 -
+        u * (0.00037436 -
+        u * (0.0000121272 -
+        u * (0.0000001699 -
+             u * 0.000000000875
+            ))))))
     }
     else if y < 1900.0 {
         let u = y - 1860.0;
-        return 7.62 +
-               u * (0.5737 -
-               u * (0.251754 -
-               u * (0.01680668 -
-               u * (0.0004473624 +
-                    u / 233174.0
-                   ))));
+        7.62 +
+        u * (0.5737 -
+        u * (0.251754 -
+        u * (0.01680668 -
+        u * (0.0004473624 -
+             u / 233174.0
+            ))))
     }
     else if y < 1920.0 {
         let u = y - 1900.0;
-        return -2.79 +
-               u * (1.494119 -
-               u * (0.0598939 -
-               u * (0.0061966 +
-                    u * 0.000197
-                   )));
+        -2.79 +
+        u * (1.494119 -
+        u * (0.0598939 -
+        u * (0.0061966 -
+             u * 0.000197
+            )))
     }
     else if y < 1941.0 {
         let u = y - 1920.0;
-        return 21.20 +
-               u * (0.84493 -
-               u * (0.076100 -
-                    u * 0.0020936
-                   ));
+        21.20 +
+        u * (0.84493 -
+        u * (0.076100 -
+             u * 0.0020936
+            ))
     }
     else if y < 1961.0 {
         let u = y - 1950.0;
-        return 29.07 +
-               u * (0.407 -
-               u * ((1.0 / 233.0) -
-                    u / 2547.0
-                   ));
+        29.07 +
+        u * (0.407 -
+        u * ((1.0 / 233.0) -
+             u / 2547.0
+            ))
     }
     else if y < 1986.0 {
         let u = y - 1975.0;
-        return 45.45 +
-               u * (1.067 -
-               u * ((1.0 / 260.0) +
-                    u / 718.0)
-                   );
+        45.45 +
+        u * (1.067 -
+        u * ((1.0 / 260.0) +
+             u / 718.0
+            ))
     }
     else if y < 2005.0 {
         let u = y - 2000.0;
-        return 63.86 +
-               u * (0.3345 -
-               u * (0.060374 -
-               u * (0.0017275 +
-               u * (0.000651814 +
-                    u * 0.00002373599)
-                   )));
+        63.86 +
+        u * (0.3345 -
+        u * (0.060374 -
+        u * (0.0017275 +
+        u * (0.000651814 +
+             u * 0.00002373599
+            ))))
     }
     else if y < 2050.0 {
         let u = y - 2000.0;
-        return 62.92 +
-               u * (0.32217 +
-                    u * 0.005589);
+        62.92 +
+        u * (0.32217 +
+             u * 0.005589)
     }
-    else if y <= 2150.0 {
+    else if y < 2150.0 {
         let u = (y - 1820.0) / 100.0;
-        return 32.0*u*u - 20.0 - 0.5628*(2150.0 - y);
+        32.0*u*u - 20.0 - 0.5628*(2150.0 - y)
     }
-    else if y > 2150.0 {
+    else {
         let u = (y - 1820.0) / 100.0;
-        return 32.0*u*u - 20.0;
+        32.0*u*u - 20.0
     }
 
-    0.0
-
 }
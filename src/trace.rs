@@ -0,0 +1,99 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+//! Diagnostic tracing of iterative routines
+//!
+//! With the `trace` feature enabled, the Kepler solvers, light-time
+//! loops and event searches report how their iterations ended to a
+//! hook set with `set_hook()`. Without the feature, the reporting
+//! compiles to nothing.
+
+use std::sync::RwLock;
+#[cfg(not(feature = "std"))]
+use math::*;
+
+/// Represents how an iterative routine ended
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Outcome {
+    /// The iteration met it's tolerance
+    Converged,
+    /// The iteration was stopped before meeting a tolerance,
+    /// after a fixed or maximum number of iterations
+    Truncated,
+    /// The iteration failed to converge
+    Failed
+}
+
+/// An event reported by an iterative routine
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Event {
+    /// Path of the routine, such as `"orbit::elliptic::ecc_anom"`
+    pub routine: &'static str,
+    /// How the iteration ended
+    pub outcome: Outcome,
+    /// Number of iterations done
+    pub iters: u32,
+    /// Change in the iterated quantity in the last iteration, in the
+    /// units of that quantity
+    pub residual: f64
+}
+
+static HOOK: RwLock<Option<fn(&Event)>> = RwLock::new(None);
+
+/**
+Sets the hook that receives the events of the iterative routines
+
+# Arguments
+
+* `hook`: Function called with each event, or `None` to stop
+          reporting
+**/
+pub fn set_hook(hook: Option<fn(&Event)>)
+{
+    if let Ok(mut h) = HOOK.write() {
+        *h = hook;
+    }
+}
+
+#[doc(hidden)]
+pub fn emit(event: &Event)
+{
+    if let Ok(h) = HOOK.read() {
+        if let Some(hook) = *h {
+            hook(event);
+        }
+    }
+}
+
+// Reports an event of an iterative routine to the hook
+macro_rules! trace_event {
+    ($routine: expr, $outcome: ident, $iters: expr, $residual: expr) => {
+        ::trace::emit(&::trace::Event {
+            routine  : $routine,
+            outcome  : ::trace::Outcome::$outcome,
+            iters    : $iters as u32,
+            residual : $residual
+        })
+    };
+}
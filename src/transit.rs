@@ -23,10 +23,24 @@ THE SOFTWARE.
 //! Time of rise, transit and set for a celestial body
 
 use angle;
+use body::Body;
 use coords;
+use ecliptic;
+use error;
 use interpol;
+use lunar;
+use nutation;
+use planet;
+use pluto;
+use star;
+use std;
+use sun;
+use time;
+#[cfg(not(feature = "std"))]
+use math::*;
 
 /// Represents a celestial body in transit
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TransitBody {
     /// A star or a planet
     StarOrPlanet,
@@ -36,7 +50,59 @@ pub enum TransitBody {
     Moon
 }
 
+/// Represents a definition of the altitude of a celestial body at
+/// rising and setting
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Altitude {
+    /// The standard altitude of the `TransitBody`, ie: -0°34' for a
+    /// star or a planet, -0°50' for the Sun, and the Moon's upper limb
+    /// on the horizon with a refraction of 0°34'
+    Standard,
+    /// The center of the body on the horizon, seen through a
+    /// refraction *| in radians*, which is zero for the geometric
+    /// horizon
+    Center {
+        /// Refraction at the horizon *| in radians*
+        refraction: f64
+    },
+    /// The upper limb of the body on the horizon, seen through a
+    /// refraction
+    UpperLimb {
+        /// Semidiameter of the body *| in radians*
+        semidiameter: f64,
+        /// Refraction at the horizon *| in radians*
+        refraction: f64
+    },
+    /// The Sun's center 6° below the horizon
+    CivilTwilight,
+    /// The Sun's center 12° below the horizon
+    NauticalTwilight,
+    /// The Sun's center 18° below the horizon
+    AstronomicalTwilight,
+    /// The center of the body at a geometric altitude *| in radians*
+    Custom(f64)
+}
+
+/// Holds the time of a rise, transit or set
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Event {
+    /// Hour of the event on the day of interest, in UTC
+    pub hour: i64,
+    /// Minute of the event
+    pub min: i64,
+    /// Second of the event
+    pub sec: f64,
+    /// The [Altitude](./enum.Altitude.html) definition used
+    pub altitude: Altitude,
+    /// Geocentric geometric altitude of the body's center at the
+    /// event, that corresponds to the definition *| in radians*
+    pub h0: f64
+}
+
 /// Represents a transit type
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TransitType {
     /// Rise
     Rise,
@@ -85,12 +151,62 @@ pub fn time (
 
 ) -> (i64, i64, f64) {
 
-    let h0 = match transit_body {
-        &TransitBody::StarOrPlanet => -0.5667_f64.to_radians(),
-        &TransitBody::Sun          => -0.8333_f64.to_radians(),
-        &TransitBody::Moon         =>  0.7275 * moon_eq_hz_parallax
-                                     - 0.5667_f64.to_radians(),
-    };
+    let event = time_at_altitude (
+        transit_type, transit_body, &Altitude::Standard, geograph_point,
+        eq_point1, eq_point2, eq_point3, apprnt_greenwhich_sidr, delta_t,
+        moon_eq_hz_parallax
+    );
+
+    (event.hour, event.min, event.sec)
+
+}
+
+/**
+Computes the time of rise, transit or set for a celestial body, for a
+given definition of it's altitude at rising and setting
+
+For the Moon, the altitude of the definition is topocentric, and
+is turned into a geocentric one with the Moon's parallax.
+
+# Returns
+
+* `event`: The [Event](./struct.Event.html), with the time on the day
+           of interest in UTC, and the definition of the altitude used
+
+# Arguments
+
+* `transit_type`  : A `TransitType`
+* `transit_body`  : The `TransitBody`
+* `altitude`      : The [Altitude](./enum.Altitude.html) definition
+* `geograph_point`: Geographic point of the observer *| in radians*
+
+Let `JD` be the Julian (Ephemeris) day of interest,
+
+* `eq_point1`: Equatorial point of the transit body on `JD - 1` *| in radians*
+* `eq_point2`: Equatorial point of the transit body on `JD` *| in radians*
+* `eq_point3`: Equatorial point of the transit body on `JD + 1` *| in radians*
+* `apprnt_greenwhich_sidr`: Apparent sidereal time at Greenwhich on `JD` *| in radians*
+* `delta_t`: ΔT for `JD` (Julian day)
+* `moon_eq_hz_parallax`: Equatorial horizontal parallax of the Moon on `JD`
+                             *| in radians*. *Pass a meaningfull value here only when*
+                             `TransitBody::Moon` *is passed for* `transit_body`.
+**/
+pub fn time_at_altitude (
+
+    transit_type           : &TransitType,
+    transit_body           : &TransitBody,
+    altitude               : &Altitude,
+    geograph_point         : &coords::GeographPoint,
+    eq_point1              : &coords::EqPoint,
+    eq_point2              : &coords::EqPoint,
+    eq_point3              : &coords::EqPoint,
+    apprnt_greenwhich_sidr : f64,
+    delta_t                : f64,
+    moon_eq_hz_parallax    : f64
+
+) -> Event {
+
+    let h0 = h0(transit_body, altitude, moon_eq_hz_parallax);
 
     let mut H0 = (
         (h0.sin() - geograph_point.lat.sin() * eq_point2.dec.sin()) /
@@ -99,7 +215,7 @@ pub fn time (
     H0 = angle::limit_to_two_PI(H0);
 
     let mut m = m(
-        &transit_type, H0, eq_point2.asc, geograph_point.long,
+        transit_type, H0, eq_point2.asc, geograph_point.long,
         apprnt_greenwhich_sidr);
     let theta0 = apprnt_greenwhich_sidr + m*360.985647_f64.to_radians();
 
@@ -107,43 +223,348 @@ pub fn time (
 
     let asc = interpol::three_values(eq_point1.asc, eq_point2.asc, eq_point3.asc, d);
 
-    let dec = match transit_type {
-        &TransitType::Transit => 0.0,
+    let dec = match *transit_type {
+        TransitType::Transit => 0.0,
 
-        &TransitType::Rise    => interpol::three_values(
+        TransitType::Rise    => interpol::three_values(
                                     eq_point1.dec, eq_point2.dec,
                                     eq_point3.dec, d),
 
-        &TransitType::Set     => interpol::three_values(
+        TransitType::Set     => interpol::three_values(
                                     eq_point1.dec, eq_point2.dec,
                                     eq_point3.dec, d)
     };
 
-    let mut H = coords::hr_angl_frm_observer_long
-        (theta0, geograph_point.long, asc).to_degrees();
-    H = angle::limit_to_360(H);
-    if H > 180.0 { H -= 360.0; }
-    H = H.to_radians();
+    let H = angle::limit_to_pm_PI(coords::hr_angl_frm_observer_long(theta0, geograph_point.long, asc));
 
-    let h = match transit_type {
-        &TransitType::Transit => 0.0,
-        &TransitType::Rise    => coords::alt_frm_eq(H, dec, geograph_point.lat),
-        &TransitType::Set     => coords::alt_frm_eq(H, dec, geograph_point.lat)
+    let h = match *transit_type {
+        TransitType::Transit => 0.0,
+        TransitType::Rise    => coords::alt_frm_eq(H, dec, geograph_point.lat),
+        TransitType::Set     => coords::alt_frm_eq(H, dec, geograph_point.lat)
     };
 
-    m += match transit_type {
-        &TransitType::Transit => -H / angle::TWO_PI,
-        &TransitType::Rise    => (h - h0) / (angle::TWO_PI * dec.cos() * geograph_point.lat.cos() * H.sin()),
-        &TransitType::Set     => (h - h0) / (angle::TWO_PI * dec.cos() * geograph_point.lat.cos() * H.sin())
+    m += match *transit_type {
+        TransitType::Transit => -H / angle::TWO_PI,
+        TransitType::Rise    => (h - h0) / (angle::TWO_PI * dec.cos() * geograph_point.lat.cos() * H.sin()),
+        TransitType::Set     => (h - h0) / (angle::TWO_PI * dec.cos() * geograph_point.lat.cos() * H.sin())
     };
 
     let h = 24.0 * m;
     let hour = h as i64;
     let m = (h - (hour as f64)) * 60.0;
-    let minute = m as i64;
-    let second = (m - (minute as f64)) * 60.0;
+    let min = m as i64;
+    let sec = (m - (min as f64)) * 60.0;
+
+    Event { hour, min, sec, altitude: *altitude, h0 }
+
+}
+
+/**
+Computes the semi-diurnal arc of a celestial body
+
+# Returns
+
+* `arc`: Hour angle of the body at setting, which is also the
+         half of the time the body is above the altitude `h0`
+         *| in radians*. `arc` is `0` if the body stays below `h0`
+         the whole day, and `π` if it stays above.
+
+# Arguments
+
+* `dec`         : Declination of the body *| in radians*
+* `observer_lat`: Observer's geographical latitude *| in radians*
+* `h0`          : Geocentric geometric altitude of the body's center
+                  at rising and setting *| in radians*
+**/
+pub fn semidiurnal_arc(dec: f64, observer_lat: f64, h0: f64) -> f64 {
+
+    let cos_H0 =
+        (h0.sin() - observer_lat.sin()*dec.sin()) /
+        (observer_lat.cos()*dec.cos());
+
+    cos_H0.clamp(-1.0, 1.0).acos()
+
+}
+
+/**
+Computes the length of the day, and it's rate of change
+
+# Returns
+
+`(day_length, rate)`
+
+* `day_length`: Time for which the Sun stays above the altitude of the
+                definition *| in days*, so that the length of the
+                night is `1 - day_length`
+* `rate`      : Rate of change of the length of the day *| in days
+                per day*
+
+# Arguments
+
+* `JD`          : Julian (Ephemeris) day, preferably near the local
+                  noon
+* `observer_lat`: Observer's geographical latitude *| in radians*
+* `altitude`    : The [Altitude](./enum.Altitude.html) definition of
+                  rising and setting
+**/
+pub fn day_length(JD: f64, observer_lat: f64, altitude: &Altitude) -> (f64, f64) {
+
+    let h0 = h0(&TransitBody::Sun, altitude, 0.0);
+
+    let length = |JD: f64| {
+        let (sun, _) = sun::geocent_ecl_pos(JD);
+        let oblq = ecliptic::mn_oblq_IAU(JD);
+        let dec = coords::dec_frm_ecl(sun.long, sun.lat, oblq);
+
+        semidiurnal_arc(dec, observer_lat, h0) / std::f64::consts::PI
+    };
+
+    (length(JD), length(JD + 0.5) - length(JD - 0.5))
+
+}
+
+/// Holds the times of rise, transit and set of a body on a day
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RiseSetTransit {
+    /// The body rises and sets on the day
+    RiseSet {
+        /// Julian (Universal) day of the rise
+        rise: f64,
+        /// Julian (Universal) day of the transit
+        transit: f64,
+        /// Julian (Universal) day of the set
+        set: f64
+    },
+    /// The body stays above the altitude of the definition the whole
+    /// day
+    Circumpolar {
+        /// Julian (Universal) day of the transit
+        transit: f64
+    },
+    /// The body stays below the altitude of the definition the whole
+    /// day
+    NeverRises {
+        /// Julian (Universal) day of the transit
+        transit: f64
+    }
+}
+
+/**
+Computes the times of rise, transit and set of a body on a day
+
+The apparent positions of the body on the day before, the day, and
+the day after are computed, and the times are found by interpolating
+them and iterating until they converge. The parallax of the Moon is
+taken into account in the altitude of the definition.
+
+# Returns
+
+* `Ok(rise_set_transit)`: The [RiseSetTransit](./enum.RiseSetTransit.html)
+                          times on the day, which may be in any
+                          order, or
+* `Err(error)`          : `Error::InvalidBody` for the Earth, or
+                          `Error::DateOutOfRange` for Pluto outside
+                          the years 1885 to 2099 of its theory
+
+# Arguments
+
+* `body`          : The [Body](../body/enum.Body.html)
+* `geograph_point`: Geographic point of the observer *| in radians*
+* `JD`            : Julian (Universal) day of 0h of the day of
+                    interest
+* `altitude`      : The [Altitude](./enum.Altitude.html) definition
+                    of rising and setting
+**/
+pub fn rise_set_transit (
+
+    body           : &Body,
+    geograph_point : &coords::GeographPoint,
+    JD             : f64,
+    altitude       : &Altitude
+
+) -> Result<RiseSetTransit, error::Error> {
+
+    let delta_t = time::delta_t_frm_julian_day(JD);
+    let JDE = JD + delta_t/86400.0;
+
+    let (nut_in_long, nut_in_oblq) = nutation::nutation(JDE);
+    let true_oblq = ecliptic::mn_oblq_IAU(JDE) + nut_in_oblq;
+    let Theta0 = time::apprnt_sidr(time::mn_sidr(JD), nut_in_long, true_oblq);
+
+    let (eq_point1, _) = apprnt_eq_point(body, JDE - 1.0)?;
+    let (eq_point2, moon_eq_hz_parallax) = apprnt_eq_point(body, JDE)?;
+    let (eq_point3, _) = apprnt_eq_point(body, JDE + 1.0)?;
+
+    // the right ascensions are kept continuous across 0h
+    let unwrap = |asc: f64| {
+        let d = asc - eq_point2.asc;
+        asc - angle::TWO_PI * (d / angle::TWO_PI).round()
+    };
+    let asc = [unwrap(eq_point1.asc), eq_point2.asc, unwrap(eq_point3.asc)];
+    let dec = [eq_point1.dec, eq_point2.dec, eq_point3.dec];
+
+    let transit_body = match *body {
+        Body::Sun  => TransitBody::Sun,
+        Body::Moon => TransitBody::Moon,
+        _          => TransitBody::StarOrPlanet
+    };
+    let h0 = h0(&transit_body, altitude, moon_eq_hz_parallax);
+    let L = geograph_point.long;
+    let lat = geograph_point.lat;
+
+    // local hour angle and declination of the body at a fraction of
+    // the day
+    let hr_angl_dec = |m: f64| {
+        let theta = Theta0 + m*360.985647_f64.to_radians();
+        let n = m + delta_t/86400.0;
+        let asc = interpol::three_values(asc[0], asc[1], asc[2], n);
+        let dec = interpol::three_values(dec[0], dec[1], dec[2], n);
+        let H = coords::hr_angl_frm_observer_long(theta, L, asc);
+
+        (H - angle::TWO_PI * (H / angle::TWO_PI).round(), dec)
+    };
+
+    let iterate = |transit_type: &TransitType, H0: f64| {
+        let mut m = m(transit_type, H0, eq_point2.asc, L, Theta0);
+        for _ in 0..10 {
+            let (H, dec) = hr_angl_dec(m);
+            let dm = match *transit_type {
+                TransitType::Transit => -H / angle::TWO_PI,
+                _ => (coords::alt_frm_eq(H, dec, lat) - h0) /
+                     (angle::TWO_PI * dec.cos() * lat.cos() * H.sin())
+            };
+            m += dm;
+            if dm.abs() < 1e-7 {
+                break;
+            }
+        }
+
+        JD + m
+    };
+
+    let transit = iterate(&TransitType::Transit, 0.0);
+
+    let cos_H0 = (h0.sin() - lat.sin()*eq_point2.dec.sin()) / (lat.cos()*eq_point2.dec.cos());
+    if cos_H0 > 1.0 {
+        return Ok(RiseSetTransit::NeverRises { transit });
+    }
+    if cos_H0 < -1.0 {
+        return Ok(RiseSetTransit::Circumpolar { transit });
+    }
+    let H0 = cos_H0.acos();
+
+    Ok(RiseSetTransit::RiseSet {
+        rise: iterate(&TransitType::Rise, H0),
+        transit,
+        set: iterate(&TransitType::Set, H0)
+    })
+
+}
+
+/**
+Computes the times of rise, transit and set of a body on a day,
+checking the observer's latitude
+
+# Returns
+
+* `rise_set_transit`: The [RiseSetTransit](./enum.RiseSetTransit.html)
+                      times on the day, or an
+                      `Error::InvalidLatitude` if the observer's
+                      latitude is beyond ±90 degrees, or an error as
+                      from `rise_set_transit()`
+
+# Arguments
+
+* `body`          : The [Body](../body/enum.Body.html)
+* `geograph_point`: Geographic point of the observer *| in radians*
+* `JD`            : Julian (Universal) day of 0h of the day of
+                    interest
+* `altitude`      : The [Altitude](./enum.Altitude.html) definition
+                    of rising and setting
+**/
+pub fn try_rise_set_transit (
+
+    body           : &Body,
+    geograph_point : &coords::GeographPoint,
+    JD             : f64,
+    altitude       : &Altitude
+
+) -> Result<RiseSetTransit, error::Error> {
+
+    if !JD.is_finite() || !geograph_point.long.is_finite() {
+        return Err(error::Error::NotFinite);
+    }
+    error::check_lat(geograph_point.lat)?;
+
+    rise_set_transit(body, geograph_point, JD, altitude)
+
+}
+
+// The geocentric apparent equatorial point of a body, referred to the
+// true equator and equinox of the date, and its equatorial horizontal
+// parallax
+pub(crate) fn apprnt_eq_point(body: &Body, JD: f64) -> Result<(coords::EqPoint, f64), error::Error> {
+
+    let (nut_in_long, nut_in_oblq) = nutation::nutation(JD);
+    let true_oblq = ecliptic::mn_oblq_IAU(JD) + nut_in_oblq;
+
+    let (long, lat, parallax) = match *body {
+        Body::Sun => {
+            let (point, R) = sun::geocent_ecl_pos(JD);
+            let coords::EclPoint { long, lat } = sun::ecl_point_to_FK5(JD, &point);
+            (long - angle::deg_frm_dms(0, 0, 20.4898).to_radians()/R, lat, 0.0)
+        },
+        Body::Moon => {
+            let (point, dist) = lunar::geocent_ecl_pos(JD);
+            (point.long, point.lat, lunar::eq_hz_parllx(dist))
+        },
+        Body::Planet(planet::Planet::Earth) => return Err(error::Error::InvalidBody),
+        Body::Planet(ref planet) => {
+            let (point, _) = planet::geocent_apprnt_ecl_coords(planet, JD);
+            let coords::EclPoint { long, lat } = planet::ecl_point_to_FK5(JD, &point);
+            (long, lat, 0.0)
+        },
+        Body::Pluto => return Ok((pluto::geocent_apprnt_eq_coords(JD, false)?.0, 0.0)),
+        Body::Star(ref place) => {
+            return Ok((star::apprnt_eq_point(place, JD, false, true), 0.0));
+        }
+    };
+    let long = long + nut_in_long;
+
+    Ok((
+        coords::EqPoint {
+            asc: coords::asc_frm_ecl(long, lat, true_oblq),
+            dec: coords::dec_frm_ecl(long, lat, true_oblq)
+        },
+        parallax
+    ))
+
+}
+
+// The geocentric geometric altitude of a body's center at rising and
+// setting, for a definition
+fn h0(transit_body: &TransitBody, altitude: &Altitude, moon_eq_hz_parallax: f64) -> f64 {
+
+    let parallax = match *transit_body {
+        TransitBody::Moon => moon_eq_hz_parallax,
+        _                 => 0.0
+    };
 
-    (hour, minute, second)
+    match *altitude {
+        Altitude::Standard => match *transit_body {
+            TransitBody::StarOrPlanet => -0.5667_f64.to_radians(),
+            TransitBody::Sun          => -0.8333_f64.to_radians(),
+            TransitBody::Moon         =>  0.7275 * moon_eq_hz_parallax
+                                        - 0.5667_f64.to_radians(),
+        },
+        Altitude::Center { refraction } => parallax - refraction,
+        Altitude::UpperLimb { semidiameter, refraction } => parallax - refraction - semidiameter,
+        Altitude::CivilTwilight        => -6.0_f64.to_radians(),
+        Altitude::NauticalTwilight     => -12.0_f64.to_radians(),
+        Altitude::AstronomicalTwilight => -18.0_f64.to_radians(),
+        Altitude::Custom(h)            => parallax + h
+    }
 
 }
 
@@ -161,10 +582,10 @@ fn m (
     let mut m = (asc + L - Theta0)/angle::TWO_PI;
     let p = H0/angle::TWO_PI;
 
-    m += match transit_type {
-        &TransitType::Transit => 0.0,
-        &TransitType::Rise    => -p,
-        &TransitType::Set     => p
+    m += match *transit_type {
+        TransitType::Transit => 0.0,
+        TransitType::Rise    => -p,
+        TransitType::Set     => p
     };
 
     if      m < 0.0 { m += 1.0 }
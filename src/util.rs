@@ -22,6 +22,9 @@ THE SOFTWARE.
 
 //! Some programming utilities
 
+#[cfg(not(feature = "std"))]
+use math::*;
+
 /// Returns a float rounded upto a certain number of decimal digits
 #[inline]
 pub fn round_upto_digits(float: f64, decimal_digits: u32) -> f64
@@ -61,3 +64,29 @@ macro_rules! Horner_eval
         }
     }
 }
+
+// Number of instants that a batch function evaluates together, and
+// that are given to each thread with the rayon feature
+pub(crate) const BATCH_CHUNK: usize = 256;
+
+// Applies a function to chunks of the inputs and the corresponding
+// chunks of the outputs, in parallel with the rayon feature
+#[cfg(feature = "rayon")]
+pub(crate) fn for_each_chunk<A, B, F>(input: &[A], output: &mut [B], f: F)
+    where A: Sync, B: Send, F: Fn(&[A], &mut [B]) + Sync
+{
+    use rayon::prelude::*;
+
+    input.par_chunks(BATCH_CHUNK)
+         .zip(output.par_chunks_mut(BATCH_CHUNK))
+         .for_each(|(i, o)| f(i, o));
+}
+
+#[cfg(not(feature = "rayon"))]
+pub(crate) fn for_each_chunk<A, B, F>(input: &[A], output: &mut [B], f: F)
+    where F: Fn(&[A], &mut [B])
+{
+    for (i, o) in input.chunks(BATCH_CHUNK).zip(output.chunks_mut(BATCH_CHUNK)) {
+        f(i, o);
+    }
+}
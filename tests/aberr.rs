@@ -47,3 +47,84 @@ fn stell_aberr_in_eq_coords() {
     assert_eq!(util::round_upto_digits(b.to_degrees(), 7), 0.0018749);
 
 }
+
+#[test]
+fn earth_vel() {
+
+    let (x, y, z) = aberr::earth_vel(2462088.69);
+    let speed = (x*x + y*y + z*z).sqrt();
+
+    // mean orbital speed of the Earth is about 0.0172 AU per day
+    assert!((speed - 0.0172).abs() < 0.0004);
+
+    // the velocity lies close to the ecliptic
+    let oblq = 23.4392911_f64.to_radians();
+    let ecl_z = -y*oblq.sin() + z*oblq.cos();
+    assert!(ecl_z.abs() < 1e-6);
+
+}
+
+#[test]
+fn planet_aberr_in_eq_coords() {
+
+    let JD = 2462088.69;
+    let eq_point = coords::EqPoint {
+        asc: 41.0540613_f64.to_radians(),
+        dec: 49.2277489_f64.to_radians()
+    };
+
+    // a body at rest suffers only stellar aberration
+    let (a, b) = aberr::planet_aberr_in_eq_coords(&eq_point, (0.0, 0.0, 0.0), JD);
+    let (c, d) = aberr::stell_aberr_in_eq_coords(&eq_point, JD);
+    assert!((a - c).abs() < 1e-12);
+    assert!((b - d).abs() < 1e-12);
+
+    // a body moving with the Earth suffers none
+    let (a, b) = aberr::planet_aberr_in_eq_coords(&eq_point, aberr::earth_vel(JD), JD);
+    assert!(a.abs() < 1e-15);
+    assert!(b.abs() < 1e-15);
+
+}
+
+#[test]
+fn diurnal_aberr_in_eq_coords() {
+
+    let eq_point = coords::EqPoint { asc: 0.0, dec: 0.0 };
+
+    let (a, b) = aberr::diurnal_aberr_in_eq_coords(&eq_point, 1.0, 0.0);
+    assert_eq!(util::round_upto_digits(a.to_degrees() * 3600.0, 3), 0.32);
+    assert_eq!(b, 0.0);
+
+    let eq_point = coords::EqPoint { asc: 0.0, dec: 60_f64.to_radians() };
+    let (a, b) = aberr::diurnal_aberr_in_eq_coords (
+        &eq_point, 0.5, 90_f64.to_radians()
+    );
+    assert!(a.abs() < 1e-15);
+    assert_eq!(util::round_upto_digits(b.to_degrees() * 3600.0, 3), 0.139);
+
+}
+
+#[test]
+fn light_deflection() {
+
+    // at the solar limb, 16 arcminutes from the center
+    let limb = 16_f64.to_radians() / 60.0;
+    let deflection = aberr::light_deflection_angl(limb, 1.0);
+    assert_eq!(util::round_upto_digits(deflection.to_degrees() * 3600.0, 2), 1.75);
+
+    // a star 90 degrees from the Sun is pushed away from it
+    let sun = (1.0, 0.0, 0.0);
+    let (x, y, z) = aberr::light_deflection((0.0, 1.0, 0.0), sun, 1.0);
+    assert!(x < 0.0 && z == 0.0);
+    assert_eq!(util::round_upto_digits((-x).asin().to_degrees() * 3600000.0, 2), 4.07);
+    assert_eq!(util::round_upto_digits(x*x + y*y + z*z, 12), 1.0);
+    assert_eq!(
+        util::round_upto_digits(-x, 15),
+        util::round_upto_digits(aberr::light_deflection_angl(90_f64.to_radians(), 1.0), 15)
+    );
+
+    // behind the Sun, the deflection stays bounded
+    let (x, _, _) = aberr::light_deflection((1.0, 0.0, 0.0), sun, 1.0);
+    assert!(x.is_finite());
+
+}
@@ -0,0 +1,166 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+
+#[test]
+fn assess_planet() {
+
+    // positions of the theory itself, displaced by known amounts
+    let offsets = [1.0, 2.0, 2.0, 4.0];
+    let refs: Vec<accuracy::RefPos> = [2415020.0, 2433282.5, 2451545.0, 2469807.5]
+        .iter()
+        .zip(offsets.iter())
+        .map(|(&JD, &d)| {
+            let (long, lat, dist) = planet::heliocent_coords(&planet::Planet::Mars, JD);
+            accuracy::RefPos {
+                JD,
+                long: long + (d / 3600.0_f64).to_radians(),
+                lat,
+                dist: dist + d * 1e-8
+            }
+        })
+        .collect();
+
+    let devs = accuracy::assess_planet(&planet::Planet::Mars, &refs);
+    assert_eq!(devs.len(), 2);
+
+    assert_eq!((devs[0].century, devs[0].n), (-1, 2));
+    assert_eq!(util::round_upto_digits(devs[0].max_dist * 1e8, 6), 2.0);
+    assert_eq!(util::round_upto_digits(devs[0].rms_dist * 1e8, 6), 1.581139);
+
+    assert_eq!((devs[1].century, devs[1].n), (0, 2));
+    assert_eq!(util::round_upto_digits(devs[1].rms_dist * 1e8, 6), 3.162278);
+    assert!(devs[1].max_angl.to_degrees() * 3600.0 < 4.0);
+
+}
+
+#[test]
+fn spec() {
+
+    let spec = accuracy::spec(&accuracy::planet_algorithm(&planet::Planet::Jupiter));
+    assert_eq!(spec.func, "planet::heliocent_coords");
+    assert!(spec.is_valid(2451545.0));
+    assert!(!spec.is_valid(2451545.0 + 1500.0 * 365.25));
+
+    // Pluto from 1885 to 2099
+    let spec = accuracy::spec(&accuracy::Algorithm::Pluto);
+    assert!(spec.is_valid(2415020.0) && !spec.is_valid(2488070.0));
+
+    for algorithm in accuracy::ALGORITHMS.iter() {
+        let spec = accuracy::spec(algorithm);
+        assert!(spec.JD_start < spec.JD_end && spec.max_err > 0.0);
+    }
+
+}
+
+#[test]
+fn refs_frm_horizons_vectors() {
+
+    // a table in the format of Horizons, of positions of the theory
+    // itself referred to the ecliptic and equinox of J2000.0
+    let mut text = String::from("*****\n$$SOE\n");
+    for &JD in [2415020.0, 2451545.0, 2488070.0].iter() {
+        let (long, lat, dist) = planet::heliocent_coords(&planet::Planet::Mars, JD);
+        let (long, lat) = precess::precess_ecl_coords(long, lat, JD, 2451545.0);
+        text.push_str(&format!(
+            "{:.9}, A.D. 2000-Jan-01 12:00:00.0000, {:.16E}, {:.16E}, {:.16E},\n",
+            JD, dist*lat.cos()*long.cos(), dist*lat.cos()*long.sin(), dist*lat.sin()
+        ));
+    }
+    text.push_str("$$EOE\n*****\n");
+
+    let refs = accuracy::refs_frm_horizons_vectors(&text).unwrap();
+    assert_eq!(refs.len(), 3);
+
+    let report = accuracy::report_planet(&planet::Planet::Mars, &refs);
+    assert_eq!(report.algorithm, accuracy::Algorithm::VSOP87Inner);
+    assert_eq!(report.deviations.len(), 3);
+    assert!(report.max_angl_in(2415020.0, 2488070.0) < 1e-9);
+    assert!(report.deviations.iter().all(|d| d.max_dist < 1e-12));
+    assert!(report.meets_spec());
+
+    assert!(accuracy::refs_frm_horizons_vectors("no table").is_err());
+    assert!(accuracy::refs_frm_horizons_vectors("$$SOE\n2451545.0, date, 1.0\n$$EOE").is_err());
+
+}
+
+// Assesses the ephemerides against the vector tables of JPL Horizons
+// in tests/data/horizons, which must hold one for each body. The
+// tables aren't shipped with the crate; once fetched, as in the README
+// there, run this with `cargo test --test accuracy -- --ignored`.
+#[test]
+#[ignore = "needs the JPL Horizons vector tables in tests/data/horizons"]
+fn horizons() {
+
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/horizons");
+
+    let bodies = [
+        "sun", "moon", "mercury", "venus", "mars", "jupiter", "saturn", "uranus", "neptune"
+    ];
+    let missing: Vec<&str> = bodies.iter()
+        .cloned()
+        .filter(|name| !dir.join(format!("{}.txt", name)).is_file())
+        .collect();
+    assert!(
+        missing.is_empty(),
+        "no vector tables in {} for {:?}; see the README there to fetch them",
+        dir.display(), missing
+    );
+
+    for entry in std::fs::read_dir(&dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension() != Some(std::ffi::OsStr::new("txt")) {
+            continue;
+        }
+
+        let name = path.file_stem().unwrap().to_str().unwrap().to_lowercase();
+        let refs = accuracy::refs_frm_horizons_vectors(&std::fs::read_to_string(&path).unwrap()).unwrap();
+
+        let report = match name.as_str() {
+            "sun"     => accuracy::report_sun(&refs),
+            "moon"    => accuracy::report_moon(&refs),
+            "mercury" => accuracy::report_planet(&planet::Planet::Mercury, &refs),
+            "venus"   => accuracy::report_planet(&planet::Planet::Venus, &refs),
+            "mars"    => accuracy::report_planet(&planet::Planet::Mars, &refs),
+            "jupiter" => accuracy::report_planet(&planet::Planet::Jupiter, &refs),
+            "saturn"  => accuracy::report_planet(&planet::Planet::Saturn, &refs),
+            "uranus"  => accuracy::report_planet(&planet::Planet::Uranus, &refs),
+            "neptune" => accuracy::report_planet(&planet::Planet::Neptune, &refs),
+            _ => panic!("no theory for {}", path.display())
+        };
+
+        for d in report.deviations.iter() {
+            println!(
+                "{:8} century {:3}: n = {:5}, max = {:8.3}\", rms = {:8.3}\"",
+                name, d.century, d.n,
+                d.max_angl.to_degrees() * 3600.0, d.rms_angl.to_degrees() * 3600.0
+            );
+        }
+        assert!(report.meets_spec(), "{} strays beyond its published accuracy", name);
+    }
+
+}
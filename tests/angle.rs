@@ -0,0 +1,291 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+use astro::*;
+
+#[test]
+fn smallest_circle() {
+
+    // Meeus's example 20.a, for Mercury, Jupiter and Saturn on
+    // 1981 Sept 11, 0h TD
+    let (diameter, is_longest_sepr) = angle::smallest_circle_rad (
+        hms(12, 41, 8.63), dms(-5, 37, 54.2),
+        hms(12, 52, 5.21), dms(-4, 22, 26.2),
+        hms(12, 39, 28.11), dms(-1, 50, 3.7)
+    );
+    let angle::Dms { deg, min, sec } = diameter.into();
+    assert_eq!((deg, min, sec.round()), (4, 15, 49.0));
+    assert!(!is_longest_sepr);
+
+    // three points nearly on a line
+    let rad = angle::Rad;
+    let (diameter, is_longest_sepr) = angle::smallest_circle_rad (
+        rad(0.0), rad(0.0), rad(0.01), rad(0.001), rad(0.02), rad(0.0)
+    );
+    assert_eq!(diameter.0, angle::anglr_sepr(0.0, 0.0, 0.02, 0.0));
+    assert!(is_longest_sepr);
+
+}
+
+fn hms(hour: i64, min: i64, sec: f64) -> angle::Rad {
+    angle::Hms { hour, min, sec }.into()
+}
+
+fn dms(deg: i64, min: i64, sec: f64) -> angle::Rad {
+    angle::Dms { deg, min, sec }.into()
+}
+
+#[test]
+fn anglr_sepr() {
+
+    // Meeus's example 17.a, Arcturus and Spica
+    let sepr = angle::anglr_sepr (
+        213.9154_f64.to_radians(), 19.1825_f64.to_radians(),
+        201.2983_f64.to_radians(), -11.1614_f64.to_radians()
+    );
+    assert_eq!(util::round_upto_digits(sepr.to_degrees(), 4), 32.7930);
+
+    // tiny separations, where the cosine formula loses all precision
+    let sepr = angle::anglr_sepr(1.0, 0.5, 1.0 + 1e-10, 0.5);
+    assert_eq!(util::round_upto_digits(sepr / (1e-10 * 0.5_f64.cos()), 6), 1.0);
+
+    // nearly antipodal points
+    let sepr = angle::anglr_sepr(0.0, 0.0, std::f64::consts::PI, 1e-10);
+    assert_eq!(util::round_upto_digits(std::f64::consts::PI - sepr, 16), 1e-10);
+
+}
+
+#[test]
+fn pos_angl() {
+
+    let rad = angle::Rad;
+    let north = angle::pos_angl_rad(rad(1.0), rad(0.2), rad(1.0), rad(0.3));
+    let east = angle::pos_angl_rad(rad(1.0), rad(0.0), rad(1.1), rad(0.0));
+    let west = angle::pos_angl_rad(rad(1.0), rad(0.0), rad(0.9), rad(0.0));
+
+    assert_eq!(north, rad(0.0));
+    assert_eq!(util::round_upto_digits(angle::Deg::from(east).0, 10), 90.0);
+    assert_eq!(util::round_upto_digits(angle::Deg::from(west).0, 10), 270.0);
+
+    // across the north celestial pole
+    let pole = angle::Rad::from(angle::Deg(89.9));
+    let pa = angle::pos_angl_rad(rad(0.0), pole, rad(std::f64::consts::PI), angle::Deg(80.0).into());
+    assert_eq!(util::round_upto_digits(angle::Deg::from(pa).0, 10), 0.0);
+    let pa = angle::pos_angl_rad(rad(0.0), pole, rad(0.0), angle::Deg(80.0).into());
+    assert_eq!(util::round_upto_digits(angle::Deg::from(pa).0, 10), 180.0);
+
+}
+
+#[test]
+fn smallest_sphr_circle() {
+
+    // Meeus's example 20.a, for Mercury, Jupiter and Saturn on
+    // 1981 Sept 11, 0h TD
+    let points = [
+        (hms(12, 41, 8.63), dms(-5, 37, 54.2)),
+        (hms(12, 52, 5.21), dms(-4, 22, 26.2)),
+        (hms(12, 39, 28.11), dms(-1, 50, 3.7))
+    ];
+    let ((a1, a2), diameter, is_longest_sepr) = angle::smallest_sphr_circle_rad(&points);
+
+    let (planar, _) = angle::smallest_circle_rad (
+        points[0].0, points[0].1, points[1].0, points[1].1, points[2].0, points[2].1
+    );
+    assert!((diameter - planar).0.abs().to_degrees() * 3600.0 < 2.0);
+    assert!(!is_longest_sepr);
+    for p in &points {
+        let r = angle::anglr_sepr_rad(a1, a2, p.0, p.1);
+        assert!((r - diameter/2.0).0.abs() < 1e-12);
+    }
+
+    // three points on a great circle, far apart
+    let rad = angle::Rad;
+    let points = [(rad(0.0), rad(0.0)), (rad(1.0), rad(0.0)), (rad(2.5), rad(0.0))];
+    let ((a1, a2), diameter, is_longest_sepr) = angle::smallest_sphr_circle_rad(&points);
+    assert_eq!(util::round_upto_digits(diameter.0, 12), 2.5);
+    assert_eq!(util::round_upto_digits(a1.0, 12), 1.25);
+    assert_eq!(util::round_upto_digits(a2.0, 12), 0.0);
+    assert!(is_longest_sepr);
+
+}
+
+#[test]
+fn sphr_polygon() {
+
+    let deg = |long: f64, lat: f64| (angle::Deg(long).into(), angle::Deg(lat).into());
+
+    let octant = [deg(0.0, 0.0), deg(90.0, 0.0), deg(0.0, 90.0)];
+    let area = angle::sphr_polygon_area_rad(&octant);
+    assert_eq!(util::round_upto_digits(area, 10), util::round_upto_digits(std::f64::consts::FRAC_PI_2, 10));
+
+    let mut reversed = octant;
+    reversed.reverse();
+    assert_eq!(util::round_upto_digits(angle::sphr_polygon_area_rad(&reversed), 10), util::round_upto_digits(area, 10));
+
+    let is_in = |long: f64, lat: f64, vertices: &[(angle::Rad, angle::Rad)]| {
+        let (a1, a2) = deg(long, lat);
+        angle::is_in_sphr_polygon_rad(a1, a2, vertices)
+    };
+    assert!(is_in(30.0, 30.0, &octant));
+    assert!(!is_in(100.0, 10.0, &octant));
+    assert!(!is_in(30.0, -30.0, &octant));
+
+    // a cap around the north celestial pole, straddling 0h
+    let cap: Vec<(angle::Rad, angle::Rad)> = (0..8)
+        .map(|i| deg(i as f64 * 45.0, 80.0))
+        .collect();
+    assert!(is_in(200.0, 89.0, &cap));
+    assert!(is_in(350.0, 82.0, &cap));
+    assert!(!is_in(10.0, 70.0, &cap));
+    assert!(!is_in(10.0, -89.0, &cap));
+
+    let area = angle::sphr_polygon_area_rad(&cap);
+    let circle_area = 2.0 * std::f64::consts::PI * (1.0 - 80_f64.to_radians().sin());
+    assert!(area < circle_area && area > 0.9 * circle_area);
+
+}
+
+#[test]
+#[allow(deprecated)]
+fn f64_shims() {
+
+    let (p1, p2, p3) = ((3.3, -0.1), (3.37, -0.08), (3.31, -0.03));
+    let rad = |p: (f64, f64)| (angle::Rad(p.0), angle::Rad(p.1));
+    let typed = [rad(p1), rad(p2), rad(p3)];
+
+    assert_eq!(
+        angle::pos_angl(p1.0, p1.1, p2.0, p2.1),
+        angle::pos_angl_rad(typed[0].0, typed[0].1, typed[1].0, typed[1].1).0
+    );
+
+    let (diameter, is_longest_sepr) = angle::smallest_circle(p1.0, p1.1, p2.0, p2.1, p3.0, p3.1);
+    let typed_circle = angle::smallest_circle_rad (
+        typed[0].0, typed[0].1, typed[1].0, typed[1].1, typed[2].0, typed[2].1
+    );
+    assert_eq!((diameter, is_longest_sepr), ((typed_circle.0).0, typed_circle.1));
+
+    let ((a1, a2), diameter, _) = angle::smallest_sphr_circle(&[p1, p2, p3]);
+    let ((b1, b2), typed_diameter, _) = angle::smallest_sphr_circle_rad(&typed);
+    assert_eq!((a1, a2, diameter), (b1.0, b2.0, typed_diameter.0));
+
+    assert_eq!(angle::sphr_polygon_area(&[p1, p2, p3]), angle::sphr_polygon_area_rad(&typed));
+    assert_eq!(
+        angle::is_in_sphr_polygon(3.32, -0.06, &[p1, p2, p3]),
+        angle::is_in_sphr_polygon_rad(angle::Rad(3.32), angle::Rad(-0.06), &typed)
+    );
+    assert!(angle::is_in_sphr_polygon(3.32, -0.06, &[p1, p2, p3]));
+
+    let dms = angle::Dms::from(angle::Deg(-23.44));
+    assert_eq!(angle::dms_frm_deg(-23.44), (dms.deg, dms.min, dms.sec));
+    let hms = angle::Hms::from(angle::Deg(188.7));
+    assert_eq!(angle::hms_frm_deg(188.7), (hms.hour, hms.min, hms.sec));
+
+}
+
+#[test]
+fn typed_angles() {
+
+    let right = angle::Rad::from(angle::Deg(90.0));
+    assert_eq!(right, angle::Rad(std::f64::consts::FRAC_PI_2));
+    assert_eq!(angle::Deg::from(right * 2.0), angle::Deg(180.0));
+    assert_eq!((angle::Deg(350.0) + angle::Deg(20.0)).limited(), angle::Deg(10.0));
+    assert_eq!(-angle::Rad(1.0) - angle::Rad(1.0), angle::Rad(-2.0));
+
+    let dms = angle::Dms::from(angle::Deg(13.5));
+    assert_eq!((dms.deg, dms.min, util::round_upto_digits(dms.sec, 6)), (13, 30, 0.0));
+    let hms = angle::Hms::from(angle::Deg(13.5));
+    assert_eq!((hms.hour, hms.min, util::round_upto_digits(hms.sec, 6)), (0, 54, 0.0));
+    assert_eq!(angle::Deg::from(angle::Hms { hour: 1, min: 0, sec: 0.0 }), angle::Deg(15.0));
+
+    let sepr = angle::anglr_sepr_rad(angle::Rad(0.0), angle::Rad(0.0), right, angle::Rad(0.0));
+    assert_eq!(util::round_upto_digits(sepr.0, 12), util::round_upto_digits(right.0, 12));
+
+}
+
+#[test]
+fn sexagesimal() {
+
+    let oblq = angle::Dms::from(angle::Deg(23.4392911));
+    assert_eq!(format!("{}", oblq), "+23°26'21.4\"");
+    assert_eq!(format!("{:.3}", oblq), "+23°26'21.448\"");
+    assert_eq!(format!("{:.0}", angle::Dms { deg: 0, min: -30, sec: 0.0 }), "-0°30'00\"");
+    assert_eq!(format!("{}", angle::Dms { deg: 10, min: 59, sec: 59.96 }), "+11°00'00.0\"");
+
+    let hms = angle::Hms::from(angle::Deg(188.73625));
+    assert_eq!(format!("{}", hms), "12h 34m 56.7s");
+    assert_eq!(format!("{:.2}", angle::Hms { hour: -1, min: 2, sec: 3.0 }), "-1h 02m 03.00s");
+
+    for text in ["+23°26'21.4\"", "23 26 21.4", "23:26:21.4", "23d26m21.4s", "23° 26′ 21.4″"].iter() {
+        let dms: angle::Dms = text.parse().unwrap();
+        assert_eq!((dms.deg, dms.min, dms.sec), (23, 26, 21.4));
+    }
+    let dms: angle::Dms = "-0 30 15".parse().unwrap();
+    assert_eq!((dms.deg, dms.min, dms.sec), (0, -30, -15.0));
+    assert_eq!(angle::Deg::from(dms), angle::Deg(-(30.25/60.0)));
+    let dms: angle::Dms = "-23°26.5'".parse().unwrap();
+    assert_eq!((dms.deg, dms.min, dms.sec), (-23, 26, 30.0));
+    let dms: angle::Dms = "23.5".parse().unwrap();
+    assert_eq!((dms.deg, dms.min, dms.sec), (23, 30, 0.0));
+
+    for text in ["12h 34m 56.7s", "12 34 56.7", "12:34:56.7", "12h34m56.7s"].iter() {
+        let hms: angle::Hms = text.parse().unwrap();
+        assert_eq!((hms.hour, hms.min, hms.sec), (12, 34, 56.7));
+    }
+
+    for text in ["", "23°x26'", "23.5 26 21", "23 60 00", "1 2 3 4", "12 34 5a"].iter() {
+        assert_eq!(text.parse::<angle::Dms>(), Err(Error::InvalidFormat));
+    }
+    assert_eq!("12d 30m".parse::<angle::Hms>(), Err(Error::InvalidFormat));
+
+    // a round trip through the text
+    let dms: angle::Dms = format!("{:.6}", oblq).parse().unwrap();
+    assert!((angle::Deg::from(dms).0 - 23.4392911).abs() < 1e-9);
+
+}
+
+#[test]
+fn limits() {
+
+    let pi = std::f64::consts::PI;
+
+    assert_eq!(angle::limit_to_pm_PI(0.5), 0.5);
+    assert_eq!(angle::limit_to_pm_PI(pi), -pi);
+    assert!((angle::limit_to_pm_PI(1.5*pi) + 0.5*pi).abs() < 1e-15);
+    assert!((angle::limit_to_pm_PI(-7.0*pi + 0.25) - (pi + 0.25 - 2.0*pi)).abs() < 1e-14);
+    assert!((angle::limit_to_pm_PI(1.5_f32 * std::f32::consts::PI) + 0.5 * std::f32::consts::PI).abs() < 1e-6);
+
+    assert_eq!(angle::limit_to_pm_180(190.0), -170.0);
+    assert_eq!(angle::limit_to_pm_180(-190.0), 170.0);
+    assert_eq!(angle::limit_to_pm_180(180.0), -180.0);
+    assert_eq!(angle::limit_to_pm_180(-45.0), -45.0);
+
+    assert_eq!(angle::limit_to_24(25.5), 1.5);
+    assert_eq!(angle::limit_to_24(-1.5), 22.5);
+    assert_eq!(angle::limit_to_24(48.0), 0.0);
+
+    assert_eq!(angle::Deg(350.0).limited_pm(), angle::Deg(-10.0));
+    assert_eq!(angle::Rad(pi/2.0).limited_pm(), angle::Rad(pi/2.0));
+
+}
@@ -0,0 +1,145 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+
+#[test]
+fn apparent_position() {
+
+    // Meeus's example 33.a, Venus on 1992 December 20 at 0h TD
+    let venus = astro::apparent_position(&Body::Planet(planet::Planet::Venus), 2448976.5, None).unwrap();
+    let sepr = venus.eq_point.anglr_sepr(&coords::EqPoint {
+        asc: angle::deg_frm_hms(21, 4, 41.454).to_radians(),
+        dec: angle::deg_frm_dms(-18, 53, 16.84).to_radians()
+    });
+    assert!(sepr.to_degrees() * 3600.0 < 3.0);
+    assert!(venus.hz_point.is_none());
+
+    // the geometric distance at the instant, while Meeus's 0.910947 AU
+    // is that of the ephemeris, when the light left Venus
+    assert_eq!(util::round_upto_digits(venus.dist, 5), 0.91085);
+    let row = ephemeris::Generator::new(&ephemeris::Analytic, Body::Planet(planet::Planet::Venus), 2448976.5, 2448976.5, 1.0)
+        .next()
+        .unwrap();
+    assert_eq!(util::round_upto_digits(row.dist, 5), 0.91095);
+
+    // the same as a row of an ephemeris
+    let JD = 2457754.5;
+    let sun = astro::apparent_position(&Body::Sun, JD, None).unwrap();
+    let row = ephemeris::Generator::new(&ephemeris::Analytic, Body::Sun, JD, JD, 1.0)
+        .next()
+        .unwrap();
+    assert_eq!(sun.eq_point, row.eq_point);
+    assert_eq!(sun.dist, row.dist);
+
+    // Pluto beyond the range of its theory
+    assert!(astro::apparent_position(&Body::Pluto, 2451545.0, None).is_ok());
+    assert!(astro::apparent_position(&Body::Pluto, 2500000.0, None).is_err());
+
+    // a star of unknown parallax is infinitely far, and the Earth
+    // isn't seen from its center
+    let regulus = star::MeanPlace {
+        asc:         152.0929622_f64.to_radians(),
+        dec:         11.9672089_f64.to_radians(),
+        pmotion_asc: 0.0,
+        pmotion_dec: 0.0,
+        parallax:    0.0,
+        rad_vel:     0.0
+    };
+    let observer = observer::Observer::try_new(0.0, 51.5_f64.to_radians(), 0.0).unwrap();
+    let star = astro::apparent_position(&Body::Star(regulus), JD, Some(&observer)).unwrap();
+    assert_eq!(star.eq_point, star::apprnt_eq_point(&regulus, JD, false, true));
+    assert_eq!(star.dist, f64::INFINITY);
+    assert_eq!(astro::apparent_position(&Body::Planet(planet::Planet::Earth), JD, None), Err(Error::InvalidBody));
+
+}
+
+#[test]
+fn topocentric() {
+
+    let JD = 2457754.5;
+    let observer = observer::Observer::try_new(0.0, 51.5_f64.to_radians(), 0.0).unwrap();
+
+    let geocent = astro::apparent_position(&Body::Moon, JD, None).unwrap();
+    let topocent = astro::apparent_position(&Body::Moon, JD, Some(&observer)).unwrap();
+
+    // the lunar parallax is up to a degree
+    let shift = topocent.eq_point.anglr_sepr(&geocent.eq_point);
+    assert!(shift > 0.05_f64.to_radians() && shift < 1.0_f64.to_radians());
+    assert_eq!(topocent.dist, geocent.dist);
+
+    // the horizontal point, seen through the standard atmosphere
+    let (nut_in_long, nut_in_oblq) = nutation::nutation(JD);
+    let true_oblq = ecliptic::mn_oblq_IAU(JD) + nut_in_oblq;
+    let JD_UT = JD - time::delta_t_frm_julian_day(JD)/86400.0;
+    let sidr = time::apprnt_sidr(time::mn_sidr(JD_UT), nut_in_long, true_oblq);
+    assert_eq!(topocent.hz_point, Some(observer.apprnt_hz(&topocent.eq_point, sidr)));
+
+}
+
+#[test]
+fn ecl_long_crossings() {
+
+    // the Sun reaches 0 degrees at the March equinox
+    let equinox = sun::equinox_solstice(2024, &sun::Event::MarchEquinox);
+    let crossings = apparent::ecl_long_crossings(&Body::Sun, 0.0, equinox - 30.0, equinox + 30.0).unwrap();
+    assert_eq!(crossings.len(), 1);
+    assert!((crossings[0] - equinox).abs() * 86400.0 < 60.0);
+
+    // the Moon reaches 0 degrees of Aries once a tropical month
+    let crossings = apparent::ecl_long_crossings(&Body::Moon, 0.0, 2460310.5, 2460310.5 + 60.0).unwrap();
+    assert_eq!(crossings.len(), 2);
+    let month = crossings[1] - crossings[0];
+    assert!(month > 27.0 && month < 27.7);
+    for &JD in crossings.iter() {
+        let long = apparent::apprnt_ecl_long(&Body::Moon, JD).unwrap();
+        assert!(angle::limit_to_pm_PI(long).abs() < 1e-7);
+    }
+
+}
+
+#[test]
+fn sign_ingresses() {
+
+    // the Sun enters each sign once a year, Capricorn at the December
+    // solstice
+    let JD = 2460310.5;
+    let ingresses = apparent::sign_ingresses(&Body::Sun, JD, JD + 365.0).unwrap();
+    assert_eq!(ingresses.len(), 12);
+    let solstice = sun::equinox_solstice(2024, &sun::Event::DecSolstice);
+    let &(JD_capricorn, _) = ingresses.iter()
+        .find(|&&(_, sign)| sign == ecliptic::ZodiacSign::Capricorn)
+        .unwrap();
+    assert!((JD_capricorn - solstice).abs() * 86400.0 < 60.0);
+
+    // Mercury retrogrades back into a sign it has left in a year
+    let ingresses = apparent::sign_ingresses(&Body::Planet(planet::Planet::Mercury), JD, JD + 365.0).unwrap();
+    assert!(ingresses.len() > 12);
+    for w in ingresses.windows(2) {
+        assert!(w[0].0 < w[1].0 && w[0].1 != w[1].1);
+    }
+
+}
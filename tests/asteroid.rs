@@ -0,0 +1,108 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+
+#[test]
+fn apprnt_mag_HG() {
+
+    // at zero phase, one AU from the Sun and the Earth
+    assert_eq!(util::round_upto_digits(asteroid::apprnt_mag_HG(3.34, 0.12, 1.0, 1.0, 0.0), 6), 3.34);
+
+    // Vesta, r = 2.5, delta = 1.6, phase = 15 degrees
+    let V = asteroid::apprnt_mag_HG(3.20, 0.32, 2.5, 1.6, 15_f64.to_radians());
+    assert_eq!(util::round_upto_digits(V, 2), 6.88);
+
+    // brighter the further the phase angle is from 90 degrees
+    let near = asteroid::apprnt_mag_HG(7.0, 0.15, 2.0, 1.0, 5_f64.to_radians());
+    let far = asteroid::apprnt_mag_HG(7.0, 0.15, 2.0, 1.0, 25_f64.to_radians());
+    assert!(near < far);
+
+}
+
+#[test]
+fn apprnt_mag_HG1G2() {
+
+    assert_eq!(util::round_upto_digits(asteroid::apprnt_mag_HG1G2(3.34, 0.3, 0.3, 1.0, 1.0, 0.0), 6), 3.34);
+
+    // the basis functions meet at 7.5 degrees, where the linear parts
+    // of phi1 and phi2 join their splines
+    let a = 7.5_f64.to_radians();
+    let below = asteroid::apprnt_mag_HG1G2(7.0, 0.6, 0.2, 1.0, 1.0, a - 1e-9);
+    let above = asteroid::apprnt_mag_HG1G2(7.0, 0.6, 0.2, 1.0, 1.0, a + 1e-9);
+    assert!((below - above).abs() < 1e-6);
+
+    // phi3 vanishes beyond 30 degrees
+    let below = asteroid::apprnt_mag_HG1G2(7.0, 0.6, 0.2, 1.0, 1.0, 30_f64.to_radians() - 1e-9);
+    let above = asteroid::apprnt_mag_HG1G2(7.0, 0.6, 0.2, 1.0, 1.0, 30_f64.to_radians() + 1e-9);
+    assert!((below - above).abs() < 1e-6);
+
+    // the magnitude grows with the phase angle
+    let mut last = 0.0;
+    for deg in 0..150 {
+        let V = asteroid::apprnt_mag_HG1G2(7.0, 0.6, 0.2, 1.0, 1.0, (deg as f64).to_radians());
+        assert!(V > last);
+        last = V;
+    }
+
+}
+
+#[test]
+fn ephemeris() {
+
+    let T = time::julian_day(
+        &time::Date {
+            year: 1990,
+            month: time::Month::Oct,
+            decimal_day: 28.54502,
+            cal_type: time::CalType::Gregorian
+        }
+    );
+    let encke = orbit::Elements::frm_perih_time(
+        2.2091404 * (1.0 - 0.8502196),
+        0.8502196,
+        11.94524_f64.to_radians(),
+        334.75006_f64.to_radians(),
+        186.23352_f64.to_radians(),
+        T
+    );
+
+    let rows: Vec<asteroid::Row> = asteroid::ephemeris(&encke, 15.0, 0.15, 2448170.5, 2448180.5, 5.0).collect();
+    assert_eq!(rows.len(), 3);
+
+    let row = rows[0];
+    assert_eq!(row.JD, 2448170.5);
+    assert_eq!(util::round_upto_digits(row.asc.to_degrees(), 3), 158.559);
+    assert_eq!(util::round_upto_digits(row.dec.to_degrees(), 3), 19.158);
+    assert_eq!(util::round_upto_digits(row.delta, 4), 0.8243);
+    assert_eq!(util::round_upto_digits(row.r, 3), 0.652);
+    assert_eq!(util::round_upto_digits(row.elong.to_degrees(), 1), 40.5);
+
+    let mag = asteroid::apprnt_mag_HG(15.0, 0.15, row.r, row.delta, row.phase_angl);
+    assert_eq!(row.mag, mag);
+
+}
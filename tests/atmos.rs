@@ -0,0 +1,131 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+use astro::atmos::RefracModel;
+
+fn arcmin(a: f64) -> f64 {
+    a.to_degrees() * 60.0
+}
+
+#[test]
+fn refrac_frm_alt_15() {
+
+    // at 45 degrees, the tangent cubed term is tiny
+    let R = atmos::refrac_frm_apprnt_alt_15(45_f64.to_radians());
+    assert_eq!(util::round_upto_digits(R.to_degrees() * 3600.0, 2), 58.23);
+
+    let R = atmos::refrac_frm_true_alt_15(45_f64.to_radians());
+    assert_eq!(util::round_upto_digits(R.to_degrees() * 3600.0, 2), 58.19);
+
+}
+
+#[test]
+fn refrac_models() {
+
+    let (p, t) = (1010.0, 283.0);
+
+    // Bennett's formula at half a degree, as in chapter 16 of Meeus
+    let true_alt = atmos::true_alt_frm_apprnt(0.5_f64.to_radians(), p, t, &RefracModel::Bennett);
+    assert_eq!(util::round_upto_digits(arcmin(0.5_f64.to_radians() - true_alt), 1), 28.8);
+
+    for model in [RefracModel::Bennett, RefracModel::Saemundsson, RefracModel::Integrated].iter() {
+        // round trips
+        let apprnt_alt = atmos::apprnt_alt_frm_true(10_f64.to_radians(), p, t, model);
+        let true_alt = atmos::true_alt_frm_apprnt(apprnt_alt, p, t, model);
+        assert!(arcmin((true_alt - 10_f64.to_radians()).abs()) < 1e-6);
+
+        // about 35 arcminutes on the horizon, and 1 arcminute at 45
+        // degrees
+        let horizon = arcmin(0.0 - atmos::true_alt_frm_apprnt(0.0, p, t, model));
+        assert!(horizon > 33.0 && horizon < 36.0);
+        let mid = arcmin(45_f64.to_radians() - atmos::true_alt_frm_apprnt(45_f64.to_radians(), p, t, model));
+        assert_eq!(util::round_upto_digits(mid, 1), 1.0);
+    }
+
+    // the integrated refraction scales with the pressure, and falls
+    // with the temperature
+    let refrac = |p: f64, t: f64| 0.5 - atmos::true_alt_frm_apprnt(0.5, p, t, &RefracModel::Integrated);
+    assert_eq!(util::round_upto_digits(refrac(500.0, t) / refrac(1000.0, t), 2), 0.5);
+    assert!(refrac(p, 303.0) < refrac(p, 263.0));
+
+}
+
+#[test]
+fn airmass() {
+
+    let zenith = 90_f64.to_radians();
+    assert_eq!(util::round_upto_digits(atmos::airmass_kasten_young(zenith), 3), 1.0);
+    assert_eq!(util::round_upto_digits(atmos::airmass_pickering(zenith), 3), 1.0);
+
+    // about the secant of the zenith distance high up, and about 38
+    // on the horizon
+    let alt = 60_f64.to_radians();
+    assert_eq!(util::round_upto_digits(atmos::airmass_kasten_young(alt), 2), util::round_upto_digits(1.0 / alt.sin(), 2));
+    assert_eq!(util::round_upto_digits(atmos::airmass_kasten_young(0.0), 1), 37.9);
+    assert_eq!(util::round_upto_digits(atmos::airmass_pickering(0.0), 1), 38.7);
+
+}
+
+#[test]
+fn extinction() {
+
+    let k_V = atmos::extinction_coeff(&atmos::Band::V, 0.0);
+    assert_eq!(util::round_upto_digits(k_V, 2), 0.26);
+
+    // bluer light is more extinguished, and less so higher up
+    assert!(atmos::extinction_coeff(&atmos::Band::B, 0.0) > k_V);
+    assert!(atmos::extinction_coeff(&atmos::Band::V, 2500.0) < k_V);
+
+    let zenith = atmos::extinction(90_f64.to_radians(), &atmos::Band::V, 0.0);
+    assert_eq!(util::round_upto_digits(zenith, 3), util::round_upto_digits(k_V, 3));
+    assert!(atmos::extinction(10_f64.to_radians(), &atmos::Band::V, 0.0) > 5.0 * zenith);
+
+}
+
+#[test]
+fn sky_brightness() {
+
+    let zenith = 90_f64.to_radians();
+    let k = 0.172;
+
+    let dark = atmos::sky_brightness(zenith, 21.7, k, None);
+    assert_eq!(util::round_upto_digits(dark, 6), 21.7);
+    assert_eq!(util::round_upto_digits(atmos::limiting_mag(dark, zenith, k), 2), 6.48);
+
+    // a full Moon brightens the sky by several magnitudes, more so
+    // near it, while a Moon below the horizon doesn't
+    let full_near = atmos::sky_brightness(zenith, 21.7, k, Some((60_f64.to_radians(), 30_f64.to_radians(), 0.0)));
+    let full_far = atmos::sky_brightness(zenith, 21.7, k, Some((10_f64.to_radians(), 80_f64.to_radians(), 0.0)));
+    let crescent = atmos::sky_brightness(zenith, 21.7, k, Some((60_f64.to_radians(), 30_f64.to_radians(), 2.5)));
+    let set = atmos::sky_brightness(zenith, 21.7, k, Some((-0.1, 30_f64.to_radians(), 0.0)));
+
+    assert!(full_near < full_far && full_far < dark - 1.0);
+    assert!(crescent > full_near && crescent < dark);
+    assert_eq!(set, dark);
+    assert!(atmos::limiting_mag(full_near, zenith, k) < 5.0);
+
+}
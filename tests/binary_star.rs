@@ -0,0 +1,187 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+use astro::binary_star::{EclipsingBinary, Minimum};
+
+fn algol() -> EclipsingBinary {
+    EclipsingBinary {
+        epoch: 2445641.5540,
+        P: 2.867315,
+        e: 0.0,
+        w: 0.0,
+        i: 81.4_f64.to_radians(),
+        r1: 0.2,
+        r2: 0.23,
+        L1: 0.9,
+        light_time: None
+    }
+}
+
+#[test]
+fn minimum() {
+
+    let binary = algol();
+
+    let prim = binary_star::minimum(&binary, 10, &Minimum::Primary);
+    assert_eq!(util::round_upto_digits(prim - binary.epoch, 6), 28.67315);
+
+    let sec = binary_star::minimum(&binary, 10, &Minimum::Secondary);
+    assert_eq!(util::round_upto_digits(sec - prim, 5), 1.43366);
+
+    let (cycle, JD) = binary_star::next_minimum(&binary, prim - 0.1, &Minimum::Primary).unwrap();
+    assert_eq!((cycle, util::round_upto_digits(JD - prim, 6)), (10, 0.0));
+
+    let (cycle, _) = binary_star::next_minimum(&binary, prim, &Minimum::Primary).unwrap();
+    assert_eq!(cycle, 11);
+
+    let mut binary = algol();
+    binary.P = 0.0;
+    assert_eq!(
+        binary_star::next_minimum(&binary, prim, &Minimum::Primary),
+        Err(error::Error::NotPositive(0.0))
+    );
+
+}
+
+#[test]
+fn light_time() {
+
+    let mut binary = algol();
+    binary.light_time = Some(binary_star::LightTimeOrbit {
+        P: 680.1,
+        T: 2446936.0,
+        e: 0.23,
+        w: 313.2_f64.to_radians(),
+        a_sin_i: 0.2
+    });
+
+    // the epoch is an observed minimum, so it must be reproduced
+    let prim = binary_star::minimum(&binary, 0, &Minimum::Primary);
+    assert_eq!(util::round_upto_digits(prim - binary.epoch, 8), 0.0);
+
+}
+
+#[test]
+fn ecc_of_apprnt_orb() {
+
+    // Meeus's example 57.b, of eta Coronae Borealis
+    let e = binary_star::ecc_of_apprnt_orb(
+        0.2763, 219.907_f64.to_radians(), 59.025_f64.to_radians()
+    );
+    assert_eq!(util::round_upto_digits(e, 3), 0.86);
+
+}
+
+#[test]
+fn rel_flux() {
+
+    let binary = algol();
+
+    let prim = binary_star::rel_flux(&binary, binary.epoch);
+    let sec = binary_star::rel_flux(&binary, binary.epoch + 0.5 * binary.P);
+    let out = binary_star::rel_flux(&binary, binary.epoch + 0.25 * binary.P);
+
+    assert_eq!(out, 1.0);
+    assert!(prim < sec && sec < 1.0);
+    assert!(binary_star::mag_drop(&binary, binary.epoch) > 0.0);
+
+}
+
+fn eta_cor_bor() -> binary_star::VisualBinary {
+    binary_star::VisualBinary {
+        P: 41.623,
+        T: 1934.008,
+        e: 0.2763,
+        a: 0.907,
+        i: 59.025_f64.to_radians(),
+        asc_node: 23.717_f64.to_radians(),
+        w: 219.907_f64.to_radians()
+    }
+}
+
+#[test]
+fn apprnt_position() {
+
+    let binary = eta_cor_bor();
+
+    let pos = binary_star::apprnt_position(&binary, 1980.0);
+    assert_eq!(util::round_upto_digits(pos.pos_angl.to_degrees(), 1), 318.4);
+    assert_eq!(util::round_upto_digits(pos.sepr, 3), 0.411);
+
+    let table: Vec<_> = binary_star::ephemeris(&binary, 1980.0, 1990.0, 0.5).collect();
+    assert_eq!(table.len(), 21);
+    assert_eq!(table[0].pos_angl, pos.pos_angl);
+    assert_eq!(table[20].year, 1990.0);
+
+}
+
+#[test]
+fn apprnt_ellipse() {
+
+    let binary = eta_cor_bor();
+    let ellipse = binary_star::apprnt_ellipse(&binary);
+
+    let e = binary_star::ecc_of_apprnt_orb(binary.e, binary.w, binary.i);
+    assert_eq!(util::round_upto_digits(ellipse.e, 3), 0.86);
+    assert!((ellipse.e - e).abs() < 1e-9);
+
+    // every position of the companion lies on the ellipse
+    let (sp, cp) = ellipse.pos_angl.sin_cos();
+    for pos in binary_star::ephemeris(&binary, 1980.0, 1980.0 + binary.P, 1.0) {
+        let x = pos.sepr * pos.pos_angl.cos() - ellipse.center_north;
+        let y = pos.sepr * pos.pos_angl.sin() - ellipse.center_east;
+        let major = x*cp + y*sp;
+        let minor = -x*sp + y*cp;
+        let f = (major / ellipse.a).powi(2) + (minor / ellipse.b).powi(2);
+        assert!((f - 1.0).abs() < 1e-9);
+    }
+
+}
+
+#[test]
+fn rad_vels() {
+
+    let binary = eta_cor_bor();
+    let (parallax, mass_ratio, sys_vel) = (0.056, 0.9, -9.0);
+
+    let (v1, v2) = binary_star::rad_vels(&binary, 1980.0, parallax, mass_ratio, sys_vel);
+    assert!((v1 + mass_ratio*v2 - (1.0 + mass_ratio)*sys_vel).abs() < 1e-12);
+
+    // the relative radial velocity is the rate of change of the
+    // distance of the companion behind the primary
+    let depth = |t: f64| {
+        let n = binary_star::mn_ann_motion_of_compan(binary.P);
+        let M = binary_star::mn_anom_of_compan(n, t, binary.T);
+        let v = orbit::elliptic::true_anom_frm_mn_anom(M, binary.e, 1e-12);
+        let r = binary.a * (1.0 - binary.e*binary.e) / (1.0 + binary.e*v.cos());
+        r * (v + binary.w).sin() * binary.i.sin() / parallax
+    };
+    let h = 1e-4;
+    let rel_vel = 4.740470446 * (depth(1980.0 + h) - depth(1980.0 - h)) / (2.0*h);
+    assert_eq!(util::round_upto_digits(v2 - v1, 4), util::round_upto_digits(rel_vel, 4));
+
+}
@@ -0,0 +1,58 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+
+#[test]
+fn series() {
+
+    let s = chebyshev::Series::fit(|x: f64| x.exp(), 0.0, 2.0, 15);
+    for &x in [0.0, 0.3, 1.1, 2.0].iter() {
+        assert!((s.eval(x) - x.exp()).abs() < 1e-13);
+    }
+
+}
+
+#[test]
+fn planet_ephemeris() {
+
+    let (start, end) = (2451545.0, 2451545.0 + 100.0);
+    let eph = chebyshev::planet_ephemeris(&planet::Planet::Mars, start, end, 8.0, 12).unwrap();
+
+    let mut JD = start;
+    while JD <= end {
+        let (x, y, z) = eph.pos(JD).unwrap();
+        let (x0, y0, z0) = planet::heliocent_ecl_rect_coords_J2000(&planet::Planet::Mars, JD);
+        assert!((x - x0).abs() < 1e-9 && (y - y0).abs() < 1e-9 && (z - z0).abs() < 1e-9);
+        JD += 1.7;
+    }
+
+    assert!(eph.pos(start - 1.0).is_none());
+    assert!(eph.pos(end + 10.0).is_none());
+
+    assert!(chebyshev::planet_ephemeris(&planet::Planet::Mars, start, end, 0.0, 12).is_err());
+
+}
@@ -0,0 +1,78 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+
+#[test]
+fn cip_xy() {
+
+    let (X, Y) = cio::cip_xy(2453736.5);
+    assert_eq!(util::round_upto_digits(X, 8), 0.00057913);
+    assert_eq!(util::round_upto_digits(Y, 7), 0.0000402);
+
+}
+
+#[test]
+fn cio_locator() {
+
+    let s = cio::cio_locator(2453736.5, 0.5791308486706011e-3, 0.4020579816732961e-4);
+    assert_eq!(util::round_upto_digits(s, 11), -0.0000000122);
+
+}
+
+#[test]
+fn eq_of_origins() {
+
+    let eo = cio::eq_of_origins(2453736.5);
+    assert_eq!(util::round_upto_digits(eo, 7), -0.0013329);
+
+    let era = cio::earth_rot_angl(2454388.5);
+    assert_eq!(util::round_upto_digits(era, 12), 0.402283724003);
+
+}
+
+#[test]
+fn terr_frm_celes() {
+
+    let (JD, JD_UT1) = (2453736.5, 2453736.5 - 65.0/86400.0);
+    let (xp, yp) = (
+        (0.0349282 / 3600.0_f64).to_radians(),
+        (0.4833163 / 3600.0_f64).to_radians()
+    );
+
+    let (long, lat) = cio::terr_frm_celes(1.1, 0.3, JD, JD_UT1, xp, yp);
+    let (asc, dec) = cio::celes_frm_terr(long, lat, JD, JD_UT1, xp, yp);
+    assert_eq!(util::round_upto_digits(asc, 12), 1.1);
+    assert_eq!(util::round_upto_digits(dec, 12), 0.3);
+
+    // the terrestrial longitude differs from the hour angle only
+    // by the equation of the origins, and the precession of the
+    // coordinates from the GCRS
+    let hr_angl = angle::limit_to_two_PI(cio::earth_rot_angl(JD_UT1) - 1.1);
+    let diff = angle::limit_to_two_PI(long + hr_angl);
+    assert!(!(0.01..=angle::TWO_PI - 0.01).contains(&diff));
+
+}
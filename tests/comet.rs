@@ -0,0 +1,167 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+use astro::*;
+
+fn hale_bopp() -> comet::Elements {
+
+    comet::Elements {
+        q     : 0.914142,
+        e     : 0.995068,
+        i     : 89.4298_f64.to_radians(),
+        sigma : 282.4707_f64.to_radians(),
+        w     : 130.5887_f64.to_radians(),
+        T     : 2450539.6227
+    }
+
+}
+
+#[test]
+fn heliocent_rect_state() {
+
+    let elements = hale_bopp();
+
+    let ((x, y, z), (vx, vy, vz)) = comet::heliocent_rect_state(&elements, elements.T);
+    let r = (x*x + y*y + z*z).sqrt();
+    let v = (vx*vx + vy*vy + vz*vz).sqrt();
+    let v_peri = consts::GAUSS_GRAV * ((1.0 + elements.e) / elements.q).sqrt();
+
+    assert_eq!(util::round_upto_digits(r, 6), elements.q);
+    assert_eq!(util::round_upto_digits(v, 8), util::round_upto_digits(v_peri, 8));
+
+    let ((x, y, z), _) = comet::heliocent_rect_state(&elements, elements.T + 100.0);
+    let r = (x*x + y*y + z*z).sqrt();
+    assert!(r > 1.5 && r < 2.0);
+
+}
+
+#[test]
+fn syndyne_and_synchrone() {
+
+    let elements = hale_bopp();
+    let JD = 2450527.5;
+
+    let syndyne = comet::syndyne(&elements, JD, 0.0, &[5.0, 10.0, 20.0]);
+    for point in syndyne.iter() {
+        assert!(point.dist < 1e-9);
+    }
+
+    let synchrone = comet::synchrone(&elements, JD, 10.0, &[0.2, 0.5, 0.8]);
+    assert!(synchrone[0].dist > 0.0);
+    assert!(synchrone[1].dist > synchrone[0].dist);
+    assert!(synchrone[2].dist > synchrone[1].dist);
+
+    // young particles lie close to the prolonged radius vector, so the
+    // syndyne's tip and the synchrone point share the same direction
+    let syndyne = comet::syndyne(&elements, JD, 0.5, &[0.5, 1.0]);
+    assert!((syndyne[0].pos_angl - syndyne[1].pos_angl).abs() < 0.05);
+    assert!(syndyne[1].dist > syndyne[0].dist);
+
+}
+
+#[test]
+fn elements_frm_heliocent_rect_state() {
+
+    let elements = hale_bopp();
+    let JD = 2450527.5;
+
+    let (pos, vel) = comet::heliocent_rect_state(&elements, JD);
+    let computed = comet::elements_frm_heliocent_rect_state(pos, vel, JD);
+
+    assert_eq!(util::round_upto_digits(computed.q, 6), elements.q);
+    assert_eq!(util::round_upto_digits(computed.e, 6), elements.e);
+    assert_eq!(util::round_upto_digits(computed.i.to_degrees(), 4), 89.4298);
+    assert_eq!(util::round_upto_digits(computed.sigma.to_degrees(), 4), 282.4707);
+    assert_eq!(util::round_upto_digits(computed.w.to_degrees(), 4), 130.5887);
+    assert_eq!(util::round_upto_digits(computed.T, 4), elements.T);
+
+}
+
+#[test]
+fn elements_of_planar_orbit() {
+
+    // an orbit in the plane of the ecliptic, with its perihelion at
+    // a longitude of 75 degrees, direct and then retrograde
+    let mut elements = comet::Elements {
+        q     : 0.8,
+        e     : 0.6,
+        i     : 0.0,
+        sigma : 0.0,
+        w     : 75_f64.to_radians(),
+        T     : 2451545.0
+    };
+    let JD = 2451600.5;
+
+    for &i in [0_f64, 180.0].iter() {
+        elements.i = i.to_radians();
+        let (pos, vel) = comet::heliocent_rect_state(&elements, JD);
+        let computed = comet::elements_frm_heliocent_rect_state(pos, vel, JD);
+
+        assert_eq!(util::round_upto_digits(computed.i.to_degrees(), 6), i);
+        assert_eq!(computed.sigma, 0.0);
+        assert_eq!(util::round_upto_digits(computed.w.to_degrees(), 6), 75.0);
+        assert_eq!(util::round_upto_digits(computed.q, 6), elements.q);
+        assert_eq!(util::round_upto_digits(computed.T, 4), elements.T);
+    }
+
+}
+
+#[test]
+fn sky_uncertainty() {
+
+    let JD = 2450450.5;
+    let mut uncertain = comet::UncertainElements {
+        elements : hale_bopp(),
+        covar    : [[0.0; 6]; 6]
+    };
+
+    // an uncertain time of perihelion displaces the comet along it's
+    // path only
+    let sigma_T = 0.01;
+    uncertain.covar[5][5] = sigma_T * sigma_T;
+    let ellipse = comet::sky_uncertainty(&uncertain, JD);
+
+    let mut later = uncertain;
+    later.elements.T += sigma_T;
+    let shifted = comet::sky_uncertainty(&later, JD);
+    let sepr = angle::anglr_sepr(ellipse.asc, ellipse.dec, shifted.asc, shifted.dec);
+    let pos_angl = (
+        (shifted.asc - ellipse.asc).sin() * shifted.dec.cos()
+    ).atan2(shifted.dec.sin() - ellipse.dec.sin());
+
+    assert!(((ellipse.semimaj_axis - sepr) / sepr).abs() < 1e-3);
+    assert!(ellipse.semimin_axis < 1e-6 * ellipse.semimaj_axis);
+    assert!((ellipse.pos_angl - angle::limit_to_two_PI(pos_angl) % std::f64::consts::PI).abs() < 1e-3);
+
+    // the axes scale with the standard deviations
+    let mut wider = uncertain;
+    wider.covar[5][5] *= 4.0;
+    wider.covar[0][0] = 1e-12;
+    wider.covar[2][2] = 1e-10;
+    let wide = comet::sky_uncertainty(&wider, JD);
+    assert!(wide.semimaj_axis > 1.99 * ellipse.semimaj_axis);
+    assert!(wide.semimin_axis > 0.0);
+
+}
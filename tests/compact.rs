@@ -0,0 +1,67 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+use astro::planet::Planet;
+
+fn arcmin(angl: f64) -> f64 {
+    let a = angle::limit_to_two_PI(angl);
+    a.min(2.0*std::f64::consts::PI - a).to_degrees() * 60.0
+}
+
+#[test]
+fn heliocent_coords() {
+
+    let planets = [
+        (Planet::Mercury, 1.0), (Planet::Venus, 1.0), (Planet::Earth, 1.0),
+        (Planet::Mars, 1.5), (Planet::Jupiter, 2.0), (Planet::Saturn, 2.0),
+        (Planet::Uranus, 2.0), (Planet::Neptune, 2.0)
+    ];
+
+    for &JD in [2448976.5, 2451545.0, 2455197.5].iter() {
+        for &(ref planet, tol) in planets.iter() {
+            let (L, B, R) = compact::heliocent_coords(planet, JD);
+            let (L0, B0, R0) = planet::heliocent_coords(planet, JD);
+            let (dL, dB) = (arcmin(L - L0) * B0.cos(), arcmin(B - B0));
+            assert!(dL < tol && dB < tol);
+            assert!(((R - R0) / R0).abs() < 5e-3);
+        }
+    }
+
+}
+
+#[test]
+fn moon_geocent_ecl_pos() {
+
+    for &JD in [2448724.5, 2451545.0, 2455197.5, 2457000.5].iter() {
+        let (point, dist) = compact::moon_geocent_ecl_pos(JD);
+        let (point0, dist0) = lunar::geocent_ecl_pos(JD);
+        assert!(arcmin(point.long - point0.long) < 3.0);
+        assert!(arcmin(point.lat - point0.lat) < 1.5);
+        assert!((dist - dist0).abs() < 500.0);
+    }
+
+}
@@ -0,0 +1,329 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+use astro::*;
+
+#[test]
+fn heliophysics_frames() {
+
+    let JD = 2448908.5;
+    let (L, B, R) = planet::heliocent_coords(&planet::Planet::Earth, JD);
+
+    let (x, y, z) = coords::hee_frm_heliocent_ecl(L, B, R, JD);
+    assert_eq!(util::round_upto_digits(x, 8), util::round_upto_digits(R, 8));
+    assert_eq!(util::round_upto_digits(y, 8), 0.0);
+    assert!(z.abs() < 1e-5);
+
+    // the Earth lies at heliographic latitude B0, on the x-z plane
+    // of HEEQ
+    let (x, y, z) = coords::heeq_frm_heliocent_ecl(L, B, R, JD);
+    assert_eq!(util::round_upto_digits(y, 8), 0.0);
+    assert_eq!(util::round_upto_digits(z.atan2(x).to_degrees(), 2), 5.99);
+
+    let (carr_long, carr_lat) = coords::carrington_frm_heliocent_ecl(L, B, JD);
+    assert_eq!(util::round_upto_digits(carr_long.to_degrees(), 1), 238.6);
+    assert_eq!(util::round_upto_digits(carr_lat.to_degrees(), 2), 5.99);
+
+}
+
+#[test]
+fn parallactic_angl() {
+
+    let (dec, lat) = (15_f64.to_radians(), 40_f64.to_radians());
+
+    // on the meridian, the zenith and the pole lie in one direction
+    assert_eq!(misc::parllc_angl(lat, 0.0, dec), 0.0);
+
+    // at setting, the parallactic angle is the angle between the
+    // diurnal path and the horizon
+    let H0 = (-lat.tan() * dec.tan()).acos();
+    let q = misc::parllc_angl_on_hz(lat, dec);
+    assert_eq!(util::round_upto_digits(misc::parllc_angl(lat, H0, dec), 9), util::round_upto_digits(q, 9));
+    assert_eq!(util::round_upto_digits(misc::parllc_angl(lat, -H0, dec), 9), util::round_upto_digits(-q, 9));
+    assert_eq!(
+        util::round_upto_digits(q, 9),
+        util::round_upto_digits(planet::earth::angl_betwn_diurnal_path_and_hz(dec, lat), 9)
+    );
+
+    // a circumpolar body never reaches the horizon
+    assert!(misc::parllc_angl_on_hz(lat, 60_f64.to_radians()).is_nan());
+
+    // on the celestial equator, objects rise more slowly away from
+    // the Earth's equator
+    let rate = planet::earth::alt_rate_on_hz(0.0, lat);
+    assert_eq!(util::round_upto_digits(rate / planet::earth::rot_angular_velocity(), 9), util::round_upto_digits(lat.cos(), 9));
+
+    // the rate of field rotation matches the change of the parallactic
+    // angle over a minute of time
+    let w = planet::earth::rot_angular_velocity();
+    for &H in [-1.0, -0.2, 0.3, 1.4].iter() {
+        let dq = misc::parllc_angl(lat, H + 30.0*w, dec)
+               - misc::parllc_angl(lat, H - 30.0*w, dec);
+        let rate = coords::parallactic_angl_rate(H, dec, lat);
+        assert!((dq/60.0 - rate).abs() < 1e-9);
+    }
+
+}
+
+#[test]
+fn projection() {
+
+    let (c1, c2) = (83.8_f64.to_radians(), -5.4_f64.to_radians());
+    let (a1, a2) = (88.8_f64.to_radians(), 7.4_f64.to_radians());
+
+    for proj in [coords::Projection::Stereographic, coords::Projection::Orthographic, coords::Projection::Gnomonic].iter() {
+        let (x, y) = coords::plane_frm_sky(*proj, a1, a2, c1, c2).unwrap();
+        assert!(x > 0.0 && y > 0.0);
+
+        let (b1, b2) = coords::sky_frm_plane(*proj, x, y, c1, c2).unwrap();
+        assert_eq!(util::round_upto_digits(b1, 10), util::round_upto_digits(a1, 10));
+        assert_eq!(util::round_upto_digits(b2, 10), util::round_upto_digits(a2, 10));
+
+        assert_eq!(coords::plane_frm_sky(*proj, c1, c2, c1, c2), Some((0.0, 0.0)));
+    }
+
+    // a point 60 degrees from the center along the y-axis
+    let (_, y) = coords::plane_frm_sky(coords::Projection::Gnomonic, 0.0, 60_f64.to_radians(), 0.0, 0.0).unwrap();
+    assert_eq!(util::round_upto_digits(y, 10), util::round_upto_digits(3_f64.sqrt(), 10));
+    let (_, y) = coords::plane_frm_sky(coords::Projection::Orthographic, 0.0, 60_f64.to_radians(), 0.0, 0.0).unwrap();
+    assert_eq!(util::round_upto_digits(y, 10), util::round_upto_digits(3_f64.sqrt() / 2.0, 10));
+    let (_, y) = coords::plane_frm_sky(coords::Projection::Stereographic, 0.0, 60_f64.to_radians(), 0.0, 0.0).unwrap();
+    assert_eq!(util::round_upto_digits(y, 10), util::round_upto_digits(2.0 / 3_f64.sqrt(), 10));
+
+    // the far hemisphere
+    let PI = std::f64::consts::PI;
+    assert!(coords::plane_frm_sky(coords::Projection::Gnomonic, PI - 0.1, 0.0, 0.0, 0.0).is_none());
+    assert!(coords::plane_frm_sky(coords::Projection::Orthographic, PI - 0.1, 0.0, 0.0, 0.0).is_none());
+    assert!(coords::plane_frm_sky(coords::Projection::Stereographic, PI - 0.1, 0.0, 0.0, 0.0).is_some());
+    assert!(coords::sky_frm_plane(coords::Projection::Orthographic, 1.0, 0.5, 0.0, 0.0).is_none());
+
+}
+
+#[test]
+fn great_circle_path() {
+
+    let (a1, a2) = (10_f64.to_radians(), 20_f64.to_radians());
+    let (b1, b2) = (80_f64.to_radians(), -30_f64.to_radians());
+    let path = coords::great_circle_path(a1, a2, b1, b2, 11);
+    assert_eq!(path.len(), 11);
+    assert_eq!(util::round_upto_digits(path[10].0, 10), util::round_upto_digits(b1, 10));
+    assert_eq!(util::round_upto_digits(path[10].1, 10), util::round_upto_digits(b2, 10));
+
+    // evenly spaced, and a straight line on a gnomonic chart
+    let d = angle::anglr_sepr(a1, a2, b1, b2);
+    let step = angle::anglr_sepr(path[3].0, path[3].1, path[4].0, path[4].1);
+    assert_eq!(util::round_upto_digits(step * 10.0, 10), util::round_upto_digits(d, 10));
+
+    let plane: Vec<(f64, f64)> = path.iter()
+        .map(|p| coords::plane_frm_sky(coords::Projection::Gnomonic, p.0, p.1, a1, 0.0).unwrap())
+        .collect();
+    let cross = (plane[5].0 - plane[0].0) * (plane[10].1 - plane[0].1)
+              - (plane[5].1 - plane[0].1) * (plane[10].0 - plane[0].0);
+    assert!(cross.abs() < 1e-10);
+
+}
+
+#[test]
+fn hz_path() {
+
+    // a star on the celestial equator transits at the altitude of the
+    // colatitude, and rises and sets six hours from transit
+    let observer = coords::GeographPoint { long: 0.0, lat: 40_f64.to_radians() };
+    let JD = 2451545.0;
+    let asc = time::mn_sidr(JD);
+    let sidr_day = 1.0 / 1.00273790935;
+
+    let path = coords::hz_path(asc, 0.0, &observer, JD - sidr_day/4.0, JD + sidr_day/4.0, 3);
+    assert_eq!(path.len(), 3);
+    assert_eq!(util::round_upto_digits(path[1].2.to_degrees(), 4), 50.0);
+    assert!(path[0].2.abs() < 1e-3 && path[2].2.abs() < 1e-3);
+    assert_eq!(util::round_upto_digits(path[0].1.to_degrees().abs(), 2), 90.0);
+
+}
+
+#[test]
+fn point_conversions() {
+
+    // Pollux, from the Meeus book
+    let pollux = coords::EqPoint { asc: 116.328942_f64.to_radians(), dec: 28.026183_f64.to_radians() };
+    let ecl = pollux.to_ecl(23.4392911_f64.to_radians());
+    assert_eq!(util::round_upto_digits(ecl.long.to_degrees(), 6), 113.21563);
+    assert_eq!(util::round_upto_digits(ecl.lat.to_degrees(), 6), 6.68417);
+
+    let eq = ecl.to_eq(23.4392911_f64.to_radians());
+    assert_eq!(util::round_upto_digits(eq.anglr_sepr(&pollux), 12), 0.0);
+
+    let observer = coords::GeographPoint { long: 77.0656_f64.to_radians(), lat: 38.9213_f64.to_radians() };
+    let hz = pollux.to_hz(2.0, &observer);
+    let eq = hz.to_eq(2.0, &observer);
+    assert_eq!(util::round_upto_digits(eq.anglr_sepr(&pollux), 12), 0.0);
+
+    let gal = pollux.to_gal();
+    assert_eq!(util::round_upto_digits(gal.to_eq().anglr_sepr(&pollux), 12), 0.0);
+
+}
+
+#[test]
+fn try_geograph_point() {
+
+    assert!(coords::GeographPoint::try_new(1.0, 0.5).is_ok());
+    assert_eq!(coords::GeographPoint::try_new(1.0, 2.0), Err(Error::InvalidLatitude(2.0)));
+    assert_eq!(coords::GeographPoint::try_new(f64::NAN, 0.5), Err(Error::NotFinite));
+
+}
+
+#[test]
+fn galactic_frames() {
+
+    let deg = |a: f64, d: u32| util::round_upto_digits(a.to_degrees(), d);
+    let r = |a: f64| a.to_radians();
+
+    // the north galactic pole, and the galactic longitude of the
+    // north celestial pole
+    let eq = |asc: f64, dec: f64| coords::EqPoint { asc, dec };
+    assert_eq!(deg(eq(r(192.85948), r(27.12825)).to_gal_J2000().lat, 5), 90.0);
+    assert_eq!(deg(eq(0.0, r(90.0)).to_gal_J2000().long, 5), 122.93192);
+
+    assert_eq!(deg(eq(r(192.25), r(27.4)).to_gal_B1950().lat, 8), 90.0);
+    assert_eq!(deg(eq(0.0, r(90.0)).to_gal_B1950().long, 8), 123.0);
+
+    // agrees with the trigonometric formulae
+    let (asc, dec) = (r(217.3), r(-62.7));
+    let coords::GalPoint { long: l, lat: b } = eq(asc, dec).to_gal_B1950();
+    assert_eq!(deg(l, 8), deg(angle::limit_to_two_PI(coords::gal_long_frm_eq(asc, dec)), 8));
+    assert_eq!(deg(b, 8), deg(coords::gal_lat_frm_eq(asc, dec), 8));
+
+    // the supergalactic pole and origin
+    let (_, sb) = coords::sgal_frm_gal(r(47.37), r(6.32));
+    assert_eq!(deg(sb, 6), 90.0);
+    let (sl, sb) = coords::sgal_frm_gal(r(137.37), 0.0);
+    assert_eq!((deg(sl, 6) % 360.0, deg(sb, 6)), (0.0, 0.0));
+
+    // round trips
+    let coords::EqPoint { asc: a, dec: d } = eq(asc, dec).to_gal_J2000().to_eq_J2000();
+    assert_eq!((deg(a, 10), deg(d, 10)), (217.3, -62.7));
+
+    let coords::GalPoint { long: l, lat: b } = eq(asc, dec).to_gal_B1950();
+    let coords::EqPoint { asc: a, dec: d } = coords::GalPoint { long: l, lat: b }.to_eq_B1950();
+    assert_eq!((deg(a, 10), deg(d, 10)), (217.3, -62.7));
+
+    // the deprecated forms on bare coordinates agree
+    #[allow(deprecated)]
+    {
+        assert_eq!(coords::gal_frm_eq_J2000(asc, dec), (eq(asc, dec).to_gal_J2000().long, eq(asc, dec).to_gal_J2000().lat));
+        assert_eq!(coords::eq_B1950_frm_gal(l, b), (a, d));
+    }
+
+    let (sl, sb) = coords::sgal_frm_gal(l, b);
+    let (l2, b2) = coords::gal_frm_sgal(sl, sb);
+    assert_eq!((deg(l2, 10), deg(b2, 10)), (deg(l, 10), deg(b, 10)));
+
+}
+
+#[test]
+fn apprnt_hz() {
+
+    // Venus from Washington, from the example in chapter 13 of Meeus
+    let venus = coords::EqPoint { asc: 347.3193375_f64.to_radians(), dec: -6.719892_f64.to_radians() };
+    let observer = coords::GeographPoint { long: 77.0656_f64.to_radians(), lat: 38.9213_f64.to_radians() };
+    let sidr = 128.7378734_f64.to_radians();
+
+    let hz = venus.to_apprnt_hz(sidr, &observer, None);
+    assert_eq!(util::round_upto_digits(hz.alt.to_degrees(), 3), 15.124);
+
+    let apprnt = venus.to_apprnt_hz(sidr, &observer, Some((1010.0, 283.0)));
+    let refrac = (apprnt.alt - hz.alt).to_degrees() * 60.0;
+    assert_eq!(util::round_upto_digits(refrac, 2), 3.65);
+    assert_eq!(apprnt.az, hz.az);
+
+    // the inverse agrees to within the consistency of the refraction
+    // formulae
+    let eq = apprnt.apprnt_to_eq(sidr, &observer, Some((1010.0, 283.0)));
+    assert!(eq.anglr_sepr(&venus).to_degrees() * 3600.0 < 4.0);
+
+    // lower pressure, and higher temperature, lessen refraction
+    let thin = venus.to_apprnt_hz(sidr, &observer, Some((800.0, 300.0)));
+    assert!(thin.alt < apprnt.alt && thin.alt > hz.alt);
+
+    let H = coords::hr_angl_frm_observer_long(sidr, observer.long, venus.asc);
+    assert_eq!(venus.parallactic_angl(sidr, &observer), misc::parllc_angl(observer.lat, H, venus.dec));
+
+}
+
+#[test]
+fn rotation_engine() {
+
+    let (asc, dec) = (41.054063_f64.to_radians(), 49.227750_f64.to_radians());
+    let JD = 2462088.69;
+
+    // the composed matrix agrees with the chained routines
+    let m = coords::Matrix3::bias_precess(JD).then(&coords::Matrix3::nutation(JD));
+    let (a, d) = m.apply_sph(asc, dec);
+    let (a_chain, d_chain) = precess::true_eq_frm_icrs(asc, dec, JD);
+    assert!((a - a_chain).abs() < 1e-12 && (d - d_chain).abs() < 1e-12);
+
+    let oblq = ecliptic::mn_oblq_IAU(JD);
+    let (long, lat) = coords::Matrix3::ecl_frm_eq(oblq).apply_sph(asc, dec);
+    assert!((long - coords::ecl_long_frm_eq(asc, dec, oblq)).abs() < 1e-12);
+    assert!((lat - coords::ecl_lat_frm_eq(asc, dec, oblq)).abs() < 1e-12);
+
+    let to_gal = coords::Matrix3::eq_frm_ecl(oblq).then(&coords::Matrix3::gal_frm_eq());
+    let (l, b) = to_gal.apply_sph(long, lat);
+    let coords::GalPoint { long: l_chain, lat: b_chain } = coords::EqPoint { asc, dec }.to_gal_J2000();
+    assert!((l - l_chain).abs() < 1e-12 && (b - b_chain).abs() < 1e-12);
+
+    let back = to_gal.then(&to_gal.transpose());
+    for i in 0..3 {
+        for j in 0..3 {
+            assert!((back.rows[i][j] - coords::Matrix3::identity().rows[i][j]).abs() < 1e-15);
+        }
+    }
+
+    // quaternions reproduce the matrices
+    let q = coords::Quaternion::frm_matrix(&m);
+    let (qa, qd) = q.apply_sph(asc, dec);
+    assert!((qa - a).abs() < 1e-12 && (qd - d).abs() < 1e-12);
+    let qm = q.to_matrix();
+    for i in 0..3 {
+        for j in 0..3 {
+            assert!((qm.rows[i][j] - m.rows[i][j]).abs() < 1e-14);
+        }
+    }
+
+    let x = coords::Quaternion::frm_axis_angl(&[1.0, 0.0, 0.0], -oblq);
+    let z = coords::Quaternion::frm_axis_angl(&[0.0, 0.0, 1.0], -0.3);
+    let qm = x.then(&z).to_matrix();
+    let mm = coords::Matrix3::rot_x(oblq).then(&coords::Matrix3::rot_z(0.3));
+    for i in 0..3 {
+        for j in 0..3 {
+            assert!((qm.rows[i][j] - mm.rows[i][j]).abs() < 1e-14);
+        }
+    }
+
+    let half = coords::Quaternion::identity().slerp(&z, 0.5);
+    let expected = coords::Quaternion::frm_axis_angl(&[0.0, 0.0, 1.0], -0.15);
+    assert!((half.w - expected.w).abs() < 1e-14 && (half.z - expected.z).abs() < 1e-14);
+
+}
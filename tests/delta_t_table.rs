@@ -0,0 +1,45 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+#![cfg(feature = "std")]
+
+extern crate astro;
+
+use astro::*;
+
+// The table of ΔT is global, so the test that sets it is alone in it's
+// own test binary, where no other test reads ΔT while it's set
+#[test]
+fn delta_t_table() {
+
+    let JD = 2453371.5;
+    let poly = time::delta_t_frm_julian_day(JD);
+
+    time::set_delta_t_table(Some(vec![(2010.0, 66.07), (2000.0, 63.83)]));
+    assert_eq!(util::round_upto_digits(time::delta_t_frm_julian_day(JD), 2), 64.95);
+    assert_eq!(time::delta_t(1600, 1).round(), 120.0);
+
+    time::set_delta_t_table(None);
+    assert_eq!(time::delta_t_frm_julian_day(JD), poly);
+
+}
@@ -82,3 +82,60 @@ fn radii() {
         util::round_upto_digits(6364.033, 2)
     );
 }
+
+#[test]
+fn vincenty() {
+
+    // Flinders Peak to Buninyong, Vincenty (1975)
+    let flinders = coords::GeographPoint {
+        long: -angle::deg_frm_dms(144, 25, 29.5244).to_radians(),
+        lat : -angle::deg_frm_dms(37, 57, 3.7203).to_radians()
+    };
+    let buninyong = coords::GeographPoint {
+        long: -angle::deg_frm_dms(143, 55, 35.3839).to_radians(),
+        lat : -angle::deg_frm_dms(37, 39, 10.1561).to_radians()
+    };
+    let grs80 = planet::earth::Ellipsoid::GRS80;
+
+    let (dist, azi1, azi2) = planet::earth::vincenty_dist(&flinders, &buninyong, &grs80).unwrap();
+    assert_eq!(util::round_upto_digits(dist, 6), 54.972271);
+    assert_eq!(util::round_upto_digits(azi1.to_degrees(), 5), util::round_upto_digits(angle::deg_frm_dms(306, 52, 5.37), 5));
+    assert_eq!(util::round_upto_digits(azi2.to_degrees(), 5), util::round_upto_digits(angle::deg_frm_dms(307, 10, 25.07), 5));
+
+    let (p, azi) = planet::earth::vincenty_dest(&flinders, azi1, dist, &grs80);
+    assert!((p.long - buninyong.long).abs() < 1e-11);
+    assert!((p.lat - buninyong.lat).abs() < 1e-11);
+    assert!((azi - azi2).abs() < 1e-11);
+
+    // agrees with Meeus's approximation to within its error
+    let paris = coords::GeographPoint {
+        long: angle::deg_frm_dms(-2, 20, 14.0).to_radians(),
+        lat : angle::deg_frm_dms(48, 50, 11.0).to_radians()
+    };
+    let washington = coords::GeographPoint {
+        long: angle::deg_frm_dms(77,  3, 56.0).to_radians(),
+        lat : angle::deg_frm_dms(38, 55, 17.0).to_radians()
+    };
+    let (dist, _, _) = planet::earth::vincenty_dist(&paris, &washington, &planet::earth::Ellipsoid::default()).unwrap();
+    assert!((dist - planet::earth::geodesic_dist(&paris, &washington)).abs() < 0.1);
+
+}
+
+#[test]
+fn ellipsoid() {
+
+    let wgs84 = planet::earth::Ellipsoid::WGS84;
+    let lat = 42_f64.to_radians();
+
+    assert_eq!(wgs84.pol_rad(), planet::earth::pol_rad());
+    assert_eq!(wgs84.ecc(), planet::earth::ecc_of_meridian());
+    assert_eq!(wgs84.rad_curv_of_meridian(lat), planet::earth::rad_curv_of_meridian(lat));
+    assert!((wgs84.rad_of_parll_lat(lat) - planet::earth::rad_of_parll_lat(lat)).abs() < 1e-9);
+
+    let M = wgs84.rad_curv_of_meridian(lat);
+    let N = wgs84.rad_curv_of_prime_vertical(lat);
+    assert_eq!(wgs84.rad_curv_in_azi(lat, 0.0), M);
+    assert!((wgs84.rad_curv_in_azi(lat, 90_f64.to_radians()) - N).abs() < 1e-9);
+    assert_eq!(util::round_upto_digits(N, 3), 6387.717);
+
+}
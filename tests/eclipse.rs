@@ -0,0 +1,285 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+use astro::*;
+
+const NASA_2017_AUG_21: &str = "
+Polynomial Besselian Elements for the Total Solar Eclipse of 2017 August 21
+
+                     t0 = 18.00 TDT
+                     ΔT = 70.3 s
+
+   n      x           y           d          l1          l2          μ
+   0  -0.1295710   0.4854160  11.8669600   0.5420930  -0.0040250  89.2454300
+   1   0.5406426  -0.1416400  -0.0136220   0.0001241   0.0001234  15.0039400
+   2  -0.0000294  -0.0000905  -0.0000020  -0.0000118  -0.0000117
+   3  -0.0000081   0.0000021
+
+Tan f1 = 0.0046222    Tan f2 = 0.0045992
+";
+
+#[test]
+fn earth_shadow_radii() {
+
+    // the total lunar eclipse of 2000 Jan 21, with the parallaxes and
+    // the Sun's semidiameter near the greatest eclipse
+    let moon_parllx = angle::deg_frm_dms(0, 0, 3641.5).to_radians();
+    let sun_parllx = angle::deg_frm_dms(0, 0, 8.9).to_radians();
+    let sun_semidiameter = angle::deg_frm_dms(0, 0, 975.1).to_radians();
+
+    let (umbra, penumbra) = eclipse::earth_shadow_radii (
+        moon_parllx, sun_parllx, sun_semidiameter, &eclipse::ShadowEnlargement::Geometric
+    );
+    assert_eq!(util::round_upto_digits(umbra.to_degrees()*3600.0, 1), 2669.3);
+    assert_eq!(util::round_upto_digits(penumbra.to_degrees()*3600.0, 1), 4619.5);
+
+    let (umbra_t, penumbra_t) = eclipse::earth_shadow_radii (
+        moon_parllx, sun_parllx, sun_semidiameter, &eclipse::ShadowEnlargement::Traditional
+    );
+    assert_eq!(util::round_upto_digits(umbra_t/umbra, 6), 1.02);
+    assert_eq!(util::round_upto_digits(penumbra_t/penumbra, 6), 1.02);
+
+    // Danjon's rule enlarges the shadow less than the traditional one
+    let (umbra_d, penumbra_d) = eclipse::earth_shadow_radii (
+        moon_parllx, sun_parllx, sun_semidiameter, &eclipse::ShadowEnlargement::Danjon
+    );
+    assert!(umbra < umbra_d && umbra_d < umbra_t);
+    assert!(penumbra < penumbra_d && penumbra_d < penumbra_t);
+
+    let (umbra, penumbra) = eclipse::earth_shadow_radii_at (
+        2451564.697, &eclipse::ShadowEnlargement::Danjon
+    );
+    assert_eq!(util::round_upto_digits(umbra.to_degrees(), 2), 0.75);
+    assert_eq!(util::round_upto_digits(penumbra.to_degrees(), 2), 1.3);
+
+}
+
+#[test]
+fn besselian_elements() {
+
+    let nasa = eclipse::besselian::parse(NASA_2017_AUG_21).unwrap();
+
+    assert_eq!(nasa.JD0, 2457987.25);
+    assert_eq!(nasa.delta_t, 70.3);
+    assert_eq!(nasa.y[3], 0.0000021);
+    assert_eq!(nasa.mu[2], 0.0);
+
+    // cross-validate against the elements of this library
+    let own = eclipse::besselian::elements(nasa.JD0, nasa.delta_t);
+    assert!((own.tan_f1 - nasa.tan_f1).abs() < 1e-6);
+    assert!((own.tan_f2 - nasa.tan_f2).abs() < 1e-6);
+
+    for &h in &[-2.0, 0.0, 1.5] {
+        let JD = nasa.JD0 + h/24.0;
+        let a = eclipse::besselian::values(&nasa, JD);
+        let b = eclipse::besselian::values(&own, JD);
+        assert!((a.x - b.x).abs() < 0.002);
+        assert!((a.y - b.y).abs() < 0.002);
+        assert!((a.d - b.d).abs().to_degrees() < 0.002);
+        assert!((a.mu - b.mu).abs().to_degrees() < 0.005);
+        assert!((a.l1 - b.l1).abs() < 0.0005);
+        assert!((a.l2 - b.l2).abs() < 0.0005);
+    }
+
+    assert_eq!(eclipse::besselian::parse("t0 = 18.00 TDT").err(), Some(Error::InvalidFormat));
+
+}
+
+#[test]
+fn limit_lines() {
+
+    let nasa = eclipse::besselian::parse(NASA_2017_AUG_21).unwrap();
+
+    // greatest eclipse at 18:26:40 TDT, on the central line at
+    // 36.97° N, 87.67° W, with a path 115 km wide
+    let JD = nasa.JD0 + (26.0 + 40.0/60.0)/1440.0;
+    let north = eclipse::path::limit_point (
+        &nasa, JD, &eclipse::path::Cone::Umbra, &eclipse::path::Limit::Northern
+    ).unwrap();
+    let south = eclipse::path::limit_point (
+        &nasa, JD, &eclipse::path::Cone::Umbra, &eclipse::path::Limit::Southern
+    ).unwrap();
+    assert_eq!(util::round_upto_digits((north.lat + south.lat).to_degrees()/2.0, 1), 37.0);
+    assert_eq!(util::round_upto_digits((north.long + south.long).to_degrees()/2.0, 1), 87.7);
+    assert_eq!((planet::earth::geodesic_dist(&north, &south)/5.0).round()*5.0, 115.0);
+
+    let line = eclipse::path::limit_line (
+        &nasa, &eclipse::path::Cone::Umbra, &eclipse::path::Limit::Northern,
+        nasa.JD0 - 1.0/24.0, nasa.JD0 + 1.0/24.0, 10.0/1440.0
+    ).unwrap();
+    assert_eq!(line.len(), 13);
+    assert!(line.windows(2).all(|w| w[1].point.long < w[0].point.long));
+
+    // an occultation of a star on the axis of the eclipse's shadow
+    let v = eclipse::besselian::values(&nasa, nasa.JD0);
+    let (nut_long, nut_oblq) = nutation::nutation(nasa.JD0);
+    let oblq = ecliptic::mn_oblq_IAU(nasa.JD0) + nut_oblq;
+    let asc = time::apprnt_sidr(time::mn_sidr(nasa.JD0), nut_long, oblq) - v.mu;
+    let occult = eclipse::besselian::occult_elements(nasa.JD0, nasa.delta_t, asc, v.d);
+    assert!((occult.x[0] - nasa.x[0]).abs() < 0.002 && (occult.y[0] - nasa.y[0]).abs() < 0.002);
+    assert_eq!(util::round_upto_digits(occult.l1[0], 7), 0.2725076);
+
+    let north = eclipse::path::limit_point (
+        &occult, nasa.JD0, &eclipse::path::Cone::Umbra, &eclipse::path::Limit::Northern
+    ).unwrap();
+    let south = eclipse::path::limit_point (
+        &occult, nasa.JD0, &eclipse::path::Cone::Umbra, &eclipse::path::Limit::Southern
+    ).unwrap();
+    assert!(planet::earth::geodesic_dist(&north, &south) > 2.0*1737.4);
+
+}
+
+#[test]
+fn central_line() {
+
+    let nasa = eclipse::besselian::parse(NASA_2017_AUG_21).unwrap();
+
+    let JD = nasa.JD0 + (26.0 + 40.0/60.0)/1440.0;
+    let (point, width) = eclipse::path::central_point(&nasa, JD).unwrap();
+    assert_eq!(util::round_upto_digits(point.lat.to_degrees(), 1), 37.0);
+    assert_eq!(util::round_upto_digits(point.long.to_degrees(), 1), 87.7);
+    assert_eq!(width.round(), 115.0);
+
+    let path = eclipse::path::path(&nasa, nasa.JD0 - 3.0/24.0, nasa.JD0 + 3.0/24.0, 5.0/1440.0).unwrap();
+    let first = &path.central_line[0];
+    let last = &path.central_line[path.central_line.len() - 1];
+    // from the Pacific at sunrise to the Atlantic at sunset
+    assert_eq!(path.central_line.len(), 38);
+    assert!(first.point.long.to_degrees() > 150.0 && last.point.long.to_degrees() < 45.0);
+    assert_eq!(util::round_upto_digits((first.JD - first.JD_UT)*86400.0, 1), 70.3);
+    assert_eq!(path.northern_limit.len(), 38);
+    assert!(path.southern_penumbral_limit.len() > path.northern_penumbral_limit.len());
+
+    assert_eq!(
+        eclipse::path::path(&nasa, nasa.JD0 - 3.0/24.0, nasa.JD0 + 3.0/24.0, 0.0).err(),
+        Some(error::Error::NotPositive(0.0))
+    );
+
+}
+
+#[test]
+fn next_eclipses() {
+
+    // Meeus's example 54.a, the partial solar eclipse of 1993 May 21
+    let eclipse = eclipse::next_solar(2449100.0, &[], None);
+    assert_eq!(eclipse.kind, eclipse::SolarEclipseType::Partial);
+    assert_eq!(util::round_upto_digits(eclipse.JD, 4), 2449129.0978);
+    assert_eq!(util::round_upto_digits(eclipse.gamma, 4), 1.1348);
+    assert_eq!(util::round_upto_digits(eclipse.u, 4), 0.0097);
+    assert_eq!(util::round_upto_digits(eclipse.magnitude, 3), 0.74);
+
+    let eclipse = eclipse::next_solar(2457800.0, &[eclipse::SolarEclipseType::Total], None);
+    assert!(eclipse.central);
+    assert_eq!(util::round_upto_digits(eclipse.JD, 2), 2457987.27);
+    assert_eq!(util::round_upto_digits(eclipse.magnitude, 3), 1.031);
+
+    // the total lunar eclipse of 2000 Jan 21
+    let eclipse = eclipse::next_lunar(2451500.0, &[], None);
+    assert_eq!(eclipse.kind, eclipse::LunarEclipseType::Total);
+    assert_eq!(util::round_upto_digits(eclipse.JD, 2), 2451564.7);
+    assert_eq!(util::round_upto_digits(eclipse.umbral_mag, 2), 1.32);
+    assert_eq!((eclipse.total_semidur * 2.0 * 1440.0).round(), 76.0);
+
+    // the eclipse of 2017 Aug 21 wasn't seen from Tokyo, but the
+    // partial eclipse of 2019 Jan 6 was
+    let nashville = observer::Observer::try_new(86.78_f64.to_radians(), 36.16_f64.to_radians(), 0.0).unwrap();
+    let tokyo = observer::Observer::try_new(-139.69_f64.to_radians(), 35.69_f64.to_radians(), 0.0).unwrap();
+    let eclipse = eclipse::next_solar(2457814.0, &[], Some(&nashville));
+    assert_eq!(util::round_upto_digits(eclipse.JD, 2), 2457987.27);
+    let eclipse = eclipse::next_solar(2457814.0, &[], Some(&tokyo));
+    assert_eq!(util::round_upto_digits(eclipse.JD, 2), 2458489.57);
+
+}
+
+#[test]
+fn eclipses_in_range() {
+
+    // the eclipses of 2017
+    let (JD_start, JD_end) = (2457754.5, 2458119.5);
+
+    let kinds: Vec<_> = eclipse::solar_in_range(JD_start, JD_end).iter()
+        .map(|eclipse| (eclipse.kind, util::round_upto_digits(eclipse.JD, 0)))
+        .collect();
+    assert_eq!(kinds, vec![
+        (eclipse::SolarEclipseType::Annular, 2457811.0),
+        (eclipse::SolarEclipseType::Total, 2457987.0)
+    ]);
+
+    let kinds: Vec<_> = eclipse::lunar_in_range(JD_start, JD_end).iter()
+        .map(|eclipse| (eclipse.kind, util::round_upto_digits(eclipse.JD, 0)))
+        .collect();
+    assert_eq!(kinds, vec![
+        (eclipse::LunarEclipseType::Penumbral, 2457796.0),
+        (eclipse::LunarEclipseType::Partial, 2457973.0)
+    ]);
+
+    // the range bounds the times of greatest eclipse
+    let eclipses = eclipse::solar_in_range(2457987.0, 2457987.5);
+    assert_eq!(eclipses.len(), 1);
+    assert!(eclipse::solar_in_range(2457987.5, 2458100.0).is_empty());
+
+}
+
+#[test]
+fn local_circumstances() {
+
+    let nasa = eclipse::besselian::parse(NASA_2017_AUG_21).unwrap();
+
+    // near the point of the greatest duration, 2m 40s
+    let hopkinsville = coords::GeographPoint {
+        long: 87.67_f64.to_radians(),
+        lat : 37.00_f64.to_radians()
+    };
+    let c = eclipse::local::local_circumstances(&nasa, &hopkinsville, 170.0).unwrap();
+    assert_eq!(c.eclipse_type, eclipse::local::LocalEclipseType::Total);
+    assert!((c.duration - 160.0).abs() < 2.0);
+    assert_eq!(c.obscuration, 1.0);
+    assert!(c.magnitude > 1.0);
+    assert_eq!(util::round_upto_digits(c.sun_alt.to_degrees(), 0), 64.0);
+    let UT_mins = |JD: f64| ((JD - nasa.delta_t/86400.0 - 2457986.5) * 1440.0).round() - 16.0*60.0;
+    assert_eq!(UT_mins(c.first.JD), 56.0);
+    assert_eq!(UT_mins(c.fourth.JD), 3.0*60.0 + 51.0);
+
+    // a partial eclipse, in New York
+    let new_york = coords::GeographPoint {
+        long: 74.01_f64.to_radians(),
+        lat : 40.71_f64.to_radians()
+    };
+    let c = eclipse::local::local_circumstances(&nasa, &new_york, 0.0).unwrap();
+    assert_eq!(c.eclipse_type, eclipse::local::LocalEclipseType::Partial);
+    assert_eq!(c.duration, 0.0);
+    assert_eq!(util::round_upto_digits(c.magnitude, 2), 0.77);
+    assert_eq!(util::round_upto_digits(c.obscuration, 3), 0.716);
+    assert_eq!(UT_mins(c.first.JD), 60.0 + 23.0);
+    assert_eq!(UT_mins(c.maximum), 2.0*60.0 + 45.0);
+
+    // outside the penumbra
+    let sydney = coords::GeographPoint { long: -151.2_f64.to_radians(), lat: -33.9_f64.to_radians() };
+    assert!(eclipse::local::local_circumstances(&nasa, &sydney, 0.0).is_none());
+
+    assert_eq!(eclipse::local::obscuration(0.0, 1.03), 0.0);
+    assert_eq!(eclipse::local::obscuration(1.03, 1.03), 1.0);
+
+}
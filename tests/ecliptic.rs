@@ -28,10 +28,57 @@ use astro::*;
 #[test]
 fn mn_oblq_Laskar() {
 
-    let (d, m, s) = angle::dms_frm_deg (
-        ecliptic::mn_oblq_laskar(2446895.5).to_degrees()
-    );
+    let angle::Dms { deg: d, min: m, sec: s } = angle::Rad (
+        ecliptic::mn_oblq_laskar(2446895.5)
+    ).into();
 
     assert_eq!((d, m, util::round_upto_digits(s, 3)), (23, 26, 27.407));
 
 }
+
+#[test]
+fn eclip_and_hz() {
+
+    // Meeus's example 14.a, for latitude 51° and local sidereal time
+    // 5h, with the ecliptic points at 169°21'29" and 349°21'29"
+    let oblq = 23.44_f64.to_radians();
+    let (setting, rising) = ecliptic::eclip_points_on_hz(oblq, 51_f64.to_radians(), 75_f64.to_radians());
+    assert_eq!(angle::Dms::from(angle::Rad(setting).limited()).min, 21);
+    assert_eq!(angle::limit_to_360(setting.to_degrees()).floor(), 349.0);
+    assert_eq!(angle::limit_to_360(rising.to_degrees()).floor(), 169.0);
+    let I = ecliptic::angl_betwn_eclip_and_hz(oblq, 51_f64.to_radians(), 75_f64.to_radians());
+    assert_eq!(I.to_degrees().round(), 62.0);
+
+    // the rising point lies on the eastern horizon
+    let JD = 2448908.5;
+    let point = coords::GeographPoint { long: -13.4_f64.to_radians(), lat: 52.5_f64.to_radians() };
+    let (rising, setting, angl) = ecliptic::eclip_and_hz_at(JD, 57.0, &point);
+    assert_eq!(util::round_upto_digits((rising - setting).abs().to_degrees(), 6), 180.0);
+
+    let (nut_long, nut_oblq) = nutation::nutation(JD);
+    let oblq = ecliptic::mn_oblq_IAU(JD) + nut_oblq;
+    let sidr = time::apprnt_sidr(time::mn_sidr(JD - 57.0/86400.0), nut_long, oblq);
+    let asc = coords::asc_frm_ecl(rising, 0.0, oblq);
+    let dec = coords::dec_frm_ecl(rising, 0.0, oblq);
+    let hour_angle = coords::hr_angl_frm_observer_long(sidr, point.long, asc);
+    assert!(coords::alt_frm_eq(hour_angle, dec, point.lat).abs() < 1e-9);
+    assert!(angle::limit_to_two_PI(hour_angle) > std::f64::consts::PI);
+    assert!(angl > 0.0 && angl < std::f64::consts::PI);
+
+}
+
+#[test]
+fn zodiac_sign() {
+
+    let (sign, long) = ecliptic::zodiac_sign(95_f64.to_radians());
+    assert_eq!(sign, ecliptic::ZodiacSign::Cancer);
+    assert_eq!(util::round_upto_digits(long.to_degrees(), 9), 5.0);
+
+    assert_eq!(ecliptic::zodiac_sign(-1_f64.to_radians()).0, ecliptic::ZodiacSign::Pisces);
+    assert_eq!(ecliptic::zodiac_sign(0.0).0, ecliptic::ZodiacSign::Aries);
+    assert_eq!(ecliptic::ZODIAC_SIGNS[ecliptic::ZodiacSign::Libra as usize], ecliptic::ZodiacSign::Libra);
+
+    assert!(ecliptic::in_zodiacal_band(-5_f64.to_radians()));
+    assert!(!ecliptic::in_zodiacal_band(17_f64.to_radians()));
+
+}
@@ -32,6 +32,32 @@ fn ecc_anom() {
 
     assert_eq!(util::round_upto_digits(ecc_anom.to_degrees(), 6), 5.554589);
 
+    let ecc_anom = orbit::elliptic::ecc_anom_newton(5_f64.to_radians(), 0.1, 1e-12);
+    assert_eq!(util::round_upto_digits(ecc_anom.to_degrees(), 6), 5.554589);
+
+    let ecc_anom = orbit::elliptic::ecc_anom_markley(5_f64.to_radians(), 0.1);
+    assert_eq!(util::round_upto_digits(ecc_anom.to_degrees(), 6), 5.554589);
+
+}
+
+#[test]
+fn ecc_anom_extreme() {
+
+    // Kepler's equation holds to near machine precision for
+    // eccentricities approaching 1, and mean anomalies near zero,
+    // near π, and beyond a revolution
+    for &e in [0.0, 0.5, 0.9, 0.99, 0.999999, 1.0 - 1e-12].iter() {
+        for &M in [1e-9, 0.001, 0.3, 1.0, 3.0, std::f64::consts::PI - 1e-5, -2.0, 7.5, -40.0].iter() {
+            let E1 = orbit::elliptic::ecc_anom_newton(M, e, 1e-14);
+            let E2 = orbit::elliptic::ecc_anom_markley(M, e);
+
+            assert!((E1 - e*E1.sin() - M).abs() < 1e-12, "newton: e = {}, M = {}", e, M);
+            assert!((E2 - e*E2.sin() - M).abs() < 1e-12, "markley: e = {}, M = {}", e, M);
+        }
+    }
+
+    assert_eq!(orbit::elliptic::ecc_anom_markley(0.0, 0.99), 0.0);
+
 }
 
 #[test]
@@ -67,13 +93,71 @@ fn passage_through_nodes() {
     let (ascen, r_a) = orbit::elliptic::passage_through_node (
         w, n, a, e, T, &orbit::Node::Ascend
     );
-    assert_eq!(util::round_upto_digits((T - ascen), 4), 92.2998);
+    assert_eq!(util::round_upto_digits(T - ascen, 4), 92.2998);
     assert_eq!(util::round_upto_digits(r_a, 4), 1.8045);
 
     let (descend, r_b) = orbit::elliptic::passage_through_node (
         w, n, a, e, T, &orbit::Node::Descend
     );
-    assert_eq!(util::round_upto_digits((T - descend), 4), -28.9105);
+    assert_eq!(util::round_upto_digits(T - descend, 4), -28.9105);
     assert_eq!(util::round_upto_digits(r_b, 4), 0.8493);
 
 }
+
+#[test]
+fn anomalies() {
+
+    let e = 0.1;
+    let M = 5_f64.to_radians();
+
+    let E = orbit::elliptic::ecc_anom(M, e, 1e-12);
+    let v = orbit::elliptic::true_anom(E, e);
+
+    assert_eq!(
+        util::round_upto_digits(orbit::elliptic::ecc_anom_frm_true_anom(v, e), 10),
+        util::round_upto_digits(E, 10)
+    );
+    assert_eq!(
+        util::round_upto_digits(orbit::elliptic::mn_anom_frm_true_anom(v, e), 10),
+        util::round_upto_digits(M, 10)
+    );
+    assert_eq!(
+        util::round_upto_digits(orbit::elliptic::true_anom_frm_mn_anom(M, e, 1e-12), 10),
+        util::round_upto_digits(v, 10)
+    );
+
+}
+
+#[test]
+fn perih_time_and_mn_anom() {
+
+    let a = 2.2091404;
+    let e = 0.8502196;
+    let epoch = 2448200.5;
+    let T = 2448192.5 + 0.54502;
+
+    let M = orbit::elliptic::mn_anom_frm_perih_time(T, epoch, a, e).unwrap();
+    assert_eq!(
+        util::round_upto_digits(M.to_degrees(), 6),
+        util::round_upto_digits((orbit::elliptic::mn_motion(a) * (epoch - T)).to_degrees(), 6)
+    );
+
+    let T_back = orbit::elliptic::perih_time_frm_mn_anom(M, epoch, a, e).unwrap();
+    assert_eq!(util::round_upto_digits(T_back, 6), util::round_upto_digits(T, 6));
+
+    assert_eq!(orbit::elliptic::perih_time_frm_mn_anom(M, epoch, a, 1.2), Err(Error::InvalidEccentricity(1.2)));
+    assert_eq!(orbit::elliptic::mn_anom_frm_perih_time(T, epoch, -1.0, e), Err(Error::NotPositive(-1.0)));
+
+}
+
+#[test]
+fn try_ecc_anom() {
+
+    let E = orbit::elliptic::try_ecc_anom(5_f64.to_radians(), 0.1, 0.000001).unwrap();
+    assert_eq!(E, orbit::elliptic::ecc_anom(5_f64.to_radians(), 0.1, 0.000001));
+
+    assert_eq!(orbit::elliptic::try_ecc_anom(0.1, 1.2, 0.000001), Err(Error::InvalidEccentricity(1.2)));
+    assert_eq!(orbit::elliptic::try_true_anom_frm_mn_anom(0.1, -0.1, 0.000001), Err(Error::InvalidEccentricity(-0.1)));
+    assert_eq!(orbit::elliptic::try_ecc_anom(0.1, 0.5, 0.0), Err(Error::NotFinite));
+
+}
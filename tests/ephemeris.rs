@@ -0,0 +1,197 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+use astro::ephemeris::Provider;
+
+const AU: f64 = 149597870.7;
+const EMRAT: f64 = 81.30056907;
+
+// A header of a JPL ephemeris with 13 items, each with 2 coefficients
+// per component and one subinterval per record of 32 days
+fn header() -> String {
+    let offsets: Vec<String> = (0..13).map(|i| (3 + 6*i).to_string()).collect();
+    format!(
+"KSIZE=   160    NCOEFF=    80
+
+GROUP   1010
+
+Test ephemeris
+
+GROUP   1030
+
+  2451536.50  2451600.50         32.
+
+GROUP   1040
+
+     3
+  DENUM   AU      EMRAT
+
+GROUP   1041
+
+     3
+  0.999000000000000000D+03  {}  {}
+
+GROUP   1050
+
+{}
+{}
+{}
+
+GROUP   1070
+",
+        fortran(AU), fortran(EMRAT),
+        offsets.join(" "), ["2"; 13].join(" "), ["1"; 13].join(" ")
+    )
+}
+
+fn fortran(x: f64) -> String {
+    format!("{:.16E}", x).replace('E', "D")
+}
+
+// A record of coefficients, with the items at rest on the equator
+// except the Moon, which moves along the x-axis
+fn record(num: usize, start: f64) -> String {
+    let e = 84381.448_f64.to_radians() / 3600.0;
+    let mut coeffs = vec![start, start + 32.0];
+    for i in 0..13 {
+        let (x, y, z, dx) = match i {
+            0 => (0.4*AU, 0.0, 0.0, 0.0),
+            1 => (0.0, 0.7*AU*e.cos(), 0.7*AU*e.sin(), 0.0),
+            2 => (AU, 0.0, 0.0, 0.0),
+            9 => (384400.0, 0.0, 0.0, 1000.0),
+            _ => (0.0, 0.0, 0.0, 0.0),
+        };
+        coeffs.extend_from_slice(&[x, dx, y, 0.0, z, 0.0]);
+    }
+
+    let mut text = format!("{:6}{:6}\n", num, coeffs.len());
+    for line in coeffs.chunks(3) {
+        let mut nums: Vec<String> = line.iter().map(|&c| fortran(c)).collect();
+        while nums.len() < 3 {
+            nums.push(fortran(0.0));
+        }
+        text.push_str(&format!("  {}\n", nums.join("  ")));
+    }
+
+    text
+}
+
+#[test]
+fn jpl_de() {
+
+    let data1 = record(1, 2451536.5) + &record(2, 2451568.5);
+    let data2 = record(1, 2451568.5);
+    let de = ephemeris::JplDe::parse_ascii(&header(), &[&data1, &data2]).unwrap();
+
+    assert_eq!((de.start, de.end, de.interval), (2451536.5, 2451600.5, 32.0));
+    assert_eq!(de.AU, AU);
+    assert_eq!(de.EMRAT, EMRAT);
+
+    let round = |p: (f64, f64, f64)| (
+        util::round_upto_digits(p.0, 9), util::round_upto_digits(p.1, 9), util::round_upto_digits(p.2, 9)
+    );
+
+    let JD = 2451545.0;
+    assert_eq!(round(de.heliocent_ecl_rect_coords_J2000(&planet::Planet::Mercury, JD).unwrap()), (0.4, 0.0, 0.0));
+    assert_eq!(round(de.heliocent_ecl_rect_coords_J2000(&planet::Planet::Venus, JD).unwrap()), (0.0, 0.7, 0.0));
+
+    // the Moon moves from 383400 to 385400 km in each record
+    let (x, _, _) = de.moon_geocent_ecl_rect_coords_J2000(2451552.5).unwrap();
+    assert_eq!(util::round_upto_digits(x, 6), 384400.0);
+    let (x, _, _) = de.moon_geocent_ecl_rect_coords_J2000(2451568.5 + 24.0).unwrap();
+    assert_eq!(util::round_upto_digits(x, 6), 384900.0);
+
+    // the Earth lies off the Earth-Moon barycenter, away from the Moon
+    let (x, _, _) = de.heliocent_ecl_rect_coords_J2000(&planet::Planet::Earth, 2451552.5).unwrap();
+    assert_eq!(util::round_upto_digits((1.0 - x) * AU, 3), util::round_upto_digits(384400.0 / (1.0 + EMRAT), 3));
+
+    let (x, y, z) = de.geocent_ecl_rect_coords_J2000(&planet::Planet::Venus, 2451552.5).unwrap();
+    assert_eq!(util::round_upto_digits(y, 9), 0.7);
+    assert!(x < -0.99 && z.abs() < 1e-12);
+
+    assert!(de.heliocent_ecl_rect_coords_J2000(&planet::Planet::Mars, 2451601.0).is_none());
+    assert_eq!(ephemeris::JplDe::parse_ascii("GROUP 1010", &[&data1]).err(), Some(Error::InvalidFormat));
+
+}
+
+#[test]
+fn analytic() {
+
+    let JD = 2448976.5;
+    let provider = ephemeris::Analytic;
+
+    assert_eq!(
+        provider.heliocent_ecl_rect_coords_J2000(&planet::Planet::Venus, JD),
+        Some(planet::heliocent_ecl_rect_coords_J2000(&planet::Planet::Venus, JD))
+    );
+
+    let (x, y, z) = provider.moon_geocent_ecl_rect_coords_J2000(JD).unwrap();
+    let (_, dist) = lunar::geocent_ecl_pos(JD);
+    assert_eq!(util::round_upto_digits((x*x + y*y + z*z).sqrt(), 6), util::round_upto_digits(dist, 6));
+
+}
+
+#[test]
+fn generator() {
+
+    let provider = ephemeris::Analytic;
+
+    // Meeus's example 33.a, Venus on 1992 December 20, 0h TD
+    let rows: Vec<ephemeris::Row> = ephemeris::Generator::new (
+        &provider, Body::Planet(planet::Planet::Venus), 2448976.5, 2448986.5, 5.0
+    ).collect();
+    assert_eq!(rows.len(), 3);
+    let row = rows[0];
+    let sepr = angle::anglr_sepr (
+        row.eq_point.asc, row.eq_point.dec,
+        angle::deg_frm_hms(21, 4, 41.454).to_radians(), angle::deg_frm_dms(-18, 53, 16.84).to_radians()
+    );
+    assert!(sepr.to_degrees() * 3600.0 < 3.0);
+    assert_eq!(util::round_upto_digits(row.dist, 5), 0.91095);
+    assert!(row.mag.unwrap() < -4.0);
+    assert_eq!(rows[2].JD, 2448986.5);
+
+    // Meeus's example 25.b, the Sun on 1992 October 13, 0h TD
+    let row = ephemeris::Generator::new(&provider, Body::Sun, 2448908.5, 2448908.5, 1.0)
+        .next().unwrap();
+    let sepr = angle::anglr_sepr (
+        row.eq_point.asc, row.eq_point.dec,
+        angle::deg_frm_hms(13, 13, 30.749).to_radians(), angle::deg_frm_dms(-7, 47, 1.74).to_radians()
+    );
+    assert!(sepr.to_degrees() * 3600.0 < 2.0);
+    assert_eq!(row.elong, 0.0);
+
+    // Meeus's example 47.a, the Moon on 1992 April 12, 0h TD
+    let row = ephemeris::Generator::new(&provider, Body::Moon, 2448724.5, 2448724.5, 1.0)
+        .next().unwrap();
+    assert_eq!(util::round_upto_digits(row.eq_point.asc.to_degrees(), 3), 134.688);
+    assert_eq!(util::round_upto_digits(row.eq_point.dec.to_degrees(), 3), 13.768);
+    assert_eq!(util::round_upto_digits(row.dist * 149597870.7, 0), 368410.0);
+    assert!(row.mag.is_none());
+
+}
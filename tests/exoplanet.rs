@@ -0,0 +1,114 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+
+// a hypothetical planet of a star near the north celestial pole
+fn ephemeris() -> exoplanet::TransitEphemeris {
+    exoplanet::TransitEphemeris {
+        T0: 2458000.3,
+        P: 3.5,
+        duration: 0.12,
+        asc: 30.0_f64.to_radians(),
+        dec: 85.0_f64.to_radians()
+    }
+}
+
+#[test]
+fn bjd() {
+
+    let (asc, dec) = (280.0_f64.to_radians(), -5.0_f64.to_radians());
+
+    for &JD in [2451545.0, 2458000.3, 2460300.75].iter() {
+        let BJD = exoplanet::bjd_frm_jd(JD, asc, dec);
+        assert!((BJD - JD).abs() * 1440.0 < 8.4);
+        assert!((exoplanet::jd_frm_bjd(BJD, asc, dec) - JD).abs() * 86400.0 < 1e-3);
+    }
+
+    // the light reaches the Earth first, when the Earth is between
+    // the Sun and the star
+    let BJD = exoplanet::bjd_frm_jd(2451545.0, 100.0_f64.to_radians(), 23.0_f64.to_radians());
+    assert!(BJD - 2451545.0 > 8.0 / 1440.0);
+
+}
+
+#[test]
+fn hjd() {
+
+    let (asc, dec) = (280.0_f64.to_radians(), -5.0_f64.to_radians());
+    let observer = observer::Observer::try_new(0.0, 0.0, 0.0).unwrap();
+
+    for &JD in [2451545.0, 2458000.3, 2460300.75].iter() {
+        let HJD = exoplanet::hjd_frm_jd(JD, asc, dec);
+        assert!((HJD - JD).abs() * 1440.0 < 8.4);
+        assert!((exoplanet::jd_frm_hjd(HJD, asc, dec) - JD).abs() * 86400.0 < 1e-3);
+
+        // the Sun is within about 0.01 AU of the barycenter
+        assert!((exoplanet::bjd_frm_jd(JD, asc, dec) - HJD).abs() * 86400.0 < 8.0);
+
+        // the light-time across the Earth
+        let topocent = exoplanet::topocent_bjd_frm_jd(JD, asc, dec, &observer);
+        let geocent = exoplanet::bjd_frm_jd(JD, asc, dec);
+        assert!((topocent - geocent).abs() * 86400.0 < 0.0214);
+    }
+
+}
+
+#[test]
+fn transit_windows() {
+
+    let ephemeris = ephemeris();
+    let observer = observer::Observer::try_new(0.0, 60.0_f64.to_radians(), 0.0).unwrap();
+
+    let anytime = exoplanet::Constraints {
+        min_alt: 0.0,
+        max_sun_alt: 90.0_f64.to_radians(),
+        min_moon_sepr: 0.0
+    };
+    let windows = exoplanet::transit_windows(&ephemeris, &observer, &anytime, 2458100.0, 2458135.0);
+    assert_eq!(windows.len(), 10);
+    assert_eq!(windows[0].epoch, 29);
+    for w in windows.iter() {
+        assert!(w.ingress_visible && w.mid_visible && w.egress_visible);
+        assert!((w.egress - w.ingress - ephemeris.duration).abs() < 1e-9);
+        assert!((w.mid_BJD - exoplanet::bjd_frm_jd(w.mid, ephemeris.asc, ephemeris.dec)).abs() < 1e-8);
+    }
+
+    // only at night
+    let dark = exoplanet::Constraints {
+        max_sun_alt: -18.0_f64.to_radians(),
+        ..anytime
+    };
+    let windows = exoplanet::transit_windows(&ephemeris, &observer, &dark, 2458100.0, 2458135.0);
+    assert!(windows.iter().any(|w| w.mid_visible));
+    assert!(windows.iter().any(|w| !w.mid_visible));
+
+    // too far north for an observer at the south pole
+    let south = observer::Observer::try_new(0.0, -89.0_f64.to_radians(), 0.0).unwrap();
+    let windows = exoplanet::transit_windows(&ephemeris, &south, &anytime, 2458100.0, 2458135.0);
+    assert!(windows.iter().all(|w| !w.ingress_visible && !w.mid_visible && !w.egress_visible));
+
+}
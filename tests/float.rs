@@ -0,0 +1,141 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+use astro::float::Float;
+
+#[test]
+fn f32_algorithms() {
+
+    // the anomalies of Meeus's example 30.a, in single precision
+    let (M, e) = (5_f32.to_radians(), 0.1_f32);
+    let E = 5.554589_f32.to_radians();
+    assert!((orbit::elliptic::mn_anom_frm_ecc_anom(E, e) - M).abs() < 1e-6);
+
+    let v = orbit::elliptic::true_anom(E, e);
+    assert!((orbit::elliptic::ecc_anom_frm_true_anom(v, e) - E).abs() < 1e-6);
+    assert!((orbit::elliptic::true_anom(E as f64, e as f64) as f32 - v).abs() < 1e-6);
+
+    let y = interpol::three_values(0.884226_f32, 0.877366, 0.870531, 0.18125);
+    assert!((y - 0.876125).abs() < 1e-6);
+
+    assert_eq!(angle::limit_to_360(-400_f32), 320.0);
+    assert!((angle::limit_to_two_PI(-f32::PI) - f32::PI).abs() < 1e-6);
+
+    let sepr = angle::anglr_sepr(
+        213.9154_f32.to_radians(), 19.1825_f32.to_radians(),
+        201.2983_f32.to_radians(), -11.1614_f32.to_radians()
+    );
+    assert!((sepr.to_degrees() - 32.7930).abs() < 1e-3);
+
+    // Pollux in Meeus's example 13.a, from equatorial to ecliptic
+    // coordinates and back, to the precision of f32
+    let (asc, dec, oblq) = (116.32894_f32.to_radians(), 28.026183_f32.to_radians(), 23.439291_f32.to_radians());
+    let long = coords::ecl_long_frm_eq(asc, dec, oblq);
+    let lat = coords::ecl_lat_frm_eq(asc, dec, oblq);
+    assert!((long.to_degrees() - 113.21563).abs() < 1e-4);
+    assert!((lat.to_degrees() - 6.68417).abs() < 1e-4);
+    assert!((coords::asc_frm_ecl(long, lat, oblq) - asc).abs() < 1e-6);
+    assert!((coords::dec_frm_ecl(long, lat, oblq) - dec).abs() < 1e-6);
+
+}
+
+// A float type that forwards to f64, standing in for the extended
+// precision types implemented outside the crate
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+struct Wrapped(f64);
+
+macro_rules! forward_op {
+    ($tr: ident, $f: ident, $tr_assign: ident, $f_assign: ident, $op: tt) => {
+        impl std::ops::$tr for Wrapped {
+            type Output = Wrapped;
+            fn $f(self, other: Wrapped) -> Wrapped { Wrapped(self.0 $op other.0) }
+        }
+        impl std::ops::$tr_assign for Wrapped {
+            fn $f_assign(&mut self, other: Wrapped) { self.0 = self.0 $op other.0; }
+        }
+    };
+}
+
+forward_op!(Add, add, AddAssign, add_assign, +);
+forward_op!(Sub, sub, SubAssign, sub_assign, -);
+forward_op!(Mul, mul, MulAssign, mul_assign, *);
+forward_op!(Div, div, DivAssign, div_assign, /);
+
+impl std::ops::Rem for Wrapped {
+    type Output = Wrapped;
+    fn rem(self, other: Wrapped) -> Wrapped { Wrapped(self.0 % other.0) }
+}
+
+impl std::ops::Neg for Wrapped {
+    type Output = Wrapped;
+    fn neg(self) -> Wrapped { Wrapped(-self.0) }
+}
+
+macro_rules! forward_fns {
+    ($($f: ident),*) => { $(fn $f(self) -> Wrapped { Wrapped(self.0.$f()) })* };
+}
+
+impl Float for Wrapped {
+    const PI: Wrapped = Wrapped(std::f64::consts::PI);
+
+    fn from_f64(x: f64) -> Wrapped { Wrapped(x) }
+    fn to_f64(self) -> f64 { self.0 }
+
+    forward_fns!(
+        abs, signum, floor, ceil, round, trunc, fract, sqrt, cbrt, exp, ln,
+        log10, to_radians, to_degrees, sin, cos, tan, asin, acos, atan,
+        sinh, cosh, tanh, atanh
+    );
+
+    fn copysign(self, sign: Wrapped) -> Wrapped { Wrapped(self.0.copysign(sign.0)) }
+    fn min(self, other: Wrapped) -> Wrapped { Wrapped(self.0.min(other.0)) }
+    fn max(self, other: Wrapped) -> Wrapped { Wrapped(self.0.max(other.0)) }
+    fn is_finite(self) -> bool { self.0.is_finite() }
+    fn rem_euclid(self, rhs: Wrapped) -> Wrapped { Wrapped(self.0.rem_euclid(rhs.0)) }
+    fn hypot(self, other: Wrapped) -> Wrapped { Wrapped(self.0.hypot(other.0)) }
+    fn powi(self, n: i32) -> Wrapped { Wrapped(self.0.powi(n)) }
+    fn powf(self, n: Wrapped) -> Wrapped { Wrapped(self.0.powf(n.0)) }
+    fn atan2(self, other: Wrapped) -> Wrapped { Wrapped(self.0.atan2(other.0)) }
+    fn sin_cos(self) -> (Wrapped, Wrapped) { (self.sin(), self.cos()) }
+}
+
+#[test]
+fn user_type() {
+
+    let x = [Wrapped(29.43), Wrapped(30.97), Wrapped(27.69), Wrapped(28.11), Wrapped(31.58), Wrapped(33.05)];
+    let y = [
+        Wrapped(0.4913598), Wrapped(0.5145891), Wrapped(0.4646875),
+        Wrapped(0.4711658), Wrapped(0.5236885), Wrapped(0.5453707)
+    ];
+    let val = interpol::lagrange(&x, &y, Wrapped(30.0));
+    assert!((val.0 - 0.5).abs() < 1e-6);
+
+    let v = orbit::elliptic::true_anom(Wrapped(0.5), Wrapped(0.3));
+    assert!((v.0 - orbit::elliptic::true_anom(0.5, 0.3)).abs() < 1e-15);
+
+}
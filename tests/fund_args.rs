@@ -0,0 +1,52 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+
+#[test]
+fn fundamental_args() {
+
+    let JD = 2448724.5;
+    let args = fund_args::FundamentalArgs::new(JD);
+
+    // Meeus's example 47.a
+    assert_eq!(util::round_upto_digits(args.D.to_degrees(), 6), 113.842304);
+    assert_eq!(util::round_upto_digits(args.M.to_degrees(), 6), 97.643514);
+    assert_eq!(util::round_upto_digits(args.M1.to_degrees(), 6), 5.150833);
+    assert_eq!(util::round_upto_digits(args.F.to_degrees(), 6), 219.889721);
+    assert_eq!(util::round_upto_digits(args.L1.to_degrees(), 6), 134.290182);
+    assert_eq!(util::round_upto_digits(args.E, 6), 1.000194);
+
+    // the routines give the same results from the arguments
+    let (moon, dist) = lunar::geocent_ecl_pos(JD);
+    let (moon_args, dist_args) = lunar::geocent_ecl_pos_frm_args(&args);
+    assert_eq!((moon.long, moon.lat, dist), (moon_args.long, moon_args.lat, dist_args));
+
+    assert_eq!(nutation::nutation(JD), nutation::nutation_frm_args(&args));
+    assert_eq!(sun::physical_ephemeris(JD), sun::physical_ephemeris_frm_args(&args));
+
+}
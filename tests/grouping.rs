@@ -0,0 +1,86 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+use astro::*;
+
+#[test]
+fn groupings() {
+
+    // Meeus's example 20.a, with Mercury, Jupiter and Saturn within a
+    // circle of diameter 4°15'49" on 1981 Sept 11, 0h TD, from the
+    // positions of this library
+    let bodies = [
+        Body::Planet(planet::Planet::Mercury),
+        Body::Planet(planet::Planet::Jupiter),
+        Body::Planet(planet::Planet::Saturn)
+    ];
+    let (diameter, members) = planet::grouping::smallest_circle(&bodies, 3, 2444858.5).unwrap();
+    assert_eq!(util::round_upto_digits(diameter.to_degrees(), 2), util::round_upto_digits(angle::deg_frm_dms(4, 15, 49.0), 2));
+    assert_eq!(members, vec![0, 1, 2]);
+
+    let groupings = planet::grouping::groupings (
+        &bodies, 3, 5.0_f64.to_radians(), 2444840.5, 2444880.5, 0.5
+    ).unwrap();
+    assert_eq!(groupings.len(), 1);
+    assert_eq!(util::round_upto_digits(groupings[0].start, 1), 2444856.3);
+    assert_eq!(util::round_upto_digits(groupings[0].end, 1), 2444859.9);
+    assert!(groupings[0].diameter < diameter);
+
+    // the smallest circle over the episode, within the diameter and
+    // least at the tightest instant
+    let circles = &groupings[0].circles;
+    assert_eq!((circles[0].JD, circles[circles.len() - 1].JD), (groupings[0].start, groupings[0].end));
+    for pair in circles.windows(2) {
+        assert!(pair[0].JD < pair[1].JD);
+    }
+    for c in circles.iter() {
+        assert!(c.diameter <= 5.0_f64.to_radians() + 1e-9);
+        assert!(c.diameter >= groupings[0].diameter);
+        assert_eq!(c.members, vec![0, 1, 2]);
+    }
+    assert!(circles.iter().any(|c| c.JD == groupings[0].JD_tightest));
+
+    // the crescent Moon joining Mercury, Jupiter and Saturn on 2021
+    // Jan 14
+    let bodies = [
+        Body::Planet(planet::Planet::Venus),
+        Body::Planet(planet::Planet::Mercury),
+        Body::Planet(planet::Planet::Mars),
+        Body::Planet(planet::Planet::Jupiter),
+        Body::Planet(planet::Planet::Saturn),
+        Body::Moon
+    ];
+    let groupings = planet::grouping::groupings (
+        &bodies, 4, 10.0_f64.to_radians(), 2459200.5, 2459250.5, 0.25
+    ).unwrap();
+    assert_eq!(groupings.len(), 1);
+    assert_eq!(groupings[0].members, vec![1, 3, 4, 5]);
+    assert_eq!(groupings[0].JD_tightest.round(), 2459228.0);
+
+    // the Sun isn't one of the bodies of a grouping
+    let bodies = [Body::Sun, Body::Moon];
+    assert_eq!(planet::grouping::smallest_circle(&bodies, 2, 2459228.5), Err(Error::InvalidBody));
+
+}
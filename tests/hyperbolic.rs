@@ -0,0 +1,65 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+use astro::*;
+
+#[test]
+fn hyp_anom() {
+
+    // Kepler's equation holds for eccentricities just above 1, and
+    // for large mean anomalies
+    for &e in [1.0 + 1e-9, 1.0001, 1.1, 2.0, 10.0].iter() {
+        for &M in [0.0, 1e-6, 0.5, 5.0, 100.0, -3.0, 1e4].iter() {
+            let H = orbit::hyperbolic::hyp_anom(M, e, 1e-15);
+            let residual = (e*H.sinh() - H - M).abs() / M.abs().max(1.0);
+            assert!(residual < 1e-12, "e = {}, M = {}", e, M);
+        }
+    }
+
+}
+
+#[test]
+fn true_anom_and_rad_vec() {
+
+    // 1I/'Oumuamua, with q = 0.2552 AU and e = 1.2011, at perihelion
+    // and two months after
+    let (q, e, T) = (0.25523, 1.20113, 2458006.0);
+
+    let (v, r) = orbit::hyperbolic::true_anom_and_rad_vec(T, T, q, e, 1e-12);
+    assert_eq!((util::round_upto_digits(v, 12), util::round_upto_digits(r, 5)), (0.0, q));
+
+    let (v, r) = orbit::hyperbolic::true_anom_and_rad_vec(T + 60.0, T, q, e, 1e-12);
+    // the radius vector follows from the true anomaly by the equation
+    // of the conic
+    assert_eq!(
+        util::round_upto_digits(r, 9),
+        util::round_upto_digits(q*(1.0 + e)/(1.0 + e*v.cos()), 9)
+    );
+    assert!(v > 0.0 && v < (-1.0/e).acos());
+
+    let (v_before, r_before) = orbit::hyperbolic::true_anom_and_rad_vec(T - 60.0, T, q, e, 1e-12);
+    assert_eq!((v_before, r_before), (-v, r));
+
+}
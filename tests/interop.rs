@@ -0,0 +1,72 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+#![cfg(any(feature = "chrono", feature = "time"))]
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+#[cfg(feature = "chrono")]
+extern crate chrono;
+#[cfg(feature = "time")]
+extern crate time as time_crate;
+
+use astro::*;
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono() {
+
+    let date_time = chrono::DateTime::from_timestamp(946728000, 0).unwrap();
+    assert_eq!(time::julian_day_frm_chrono(&date_time), 2451545.0);
+    assert_eq!(time::chrono_frm_julian_day(2451545.0), Some(date_time));
+
+    let date_time = chrono::DateTime::parse_from_rfc3339("1957-10-04T19:26:24.123456Z").unwrap().to_utc();
+    let JD = time::julian_day_frm_chrono(&date_time);
+    let round_trip = time::chrono_frm_julian_day(JD).unwrap();
+    assert!((round_trip - date_time).num_microseconds().unwrap().abs() <= 40);
+    assert_eq!(time::iso8601_frm_julian_day(JD), "1957-10-04T19:26:24.123Z");
+
+    assert_eq!(time::chrono_frm_julian_day(f64::NAN), None);
+    assert_eq!(time::chrono_frm_julian_day(1e12), None);
+
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time() {
+
+    let date_time = time_crate::OffsetDateTime::from_unix_timestamp(946728000).unwrap();
+    assert_eq!(time::julian_day_frm_offset_date_time(&date_time), 2451545.0);
+    assert_eq!(time::offset_date_time_frm_julian_day(2451545.0), Some(date_time));
+
+    // the offset doesn't change the instant
+    let shifted = date_time.to_offset(time_crate::UtcOffset::from_hms(5, 30, 0).unwrap());
+    assert_eq!(time::julian_day_frm_offset_date_time(&shifted), 2451545.0);
+
+    let date_time = date_time - time_crate::Duration::microseconds(1234567890);
+    let JD = time::julian_day_frm_offset_date_time(&date_time);
+    let round_trip = time::offset_date_time_frm_julian_day(JD).unwrap();
+    assert!((round_trip - date_time).whole_microseconds().abs() <= 40);
+
+    assert_eq!(time::offset_date_time_frm_julian_day(1e12), None);
+
+}
@@ -53,3 +53,110 @@ fn five_values() {
     assert_eq!(util::round_upto_digits(y, 3), 13.369);
 
 }
+
+#[test]
+fn extremum_three_values() {
+
+    let (y, n) = interpol::extremum_three_values(1.3814294, 1.3812213, 1.3812453);
+
+    assert_eq!(util::round_upto_digits(y, 7), 1.3812030);
+    assert_eq!(util::round_upto_digits(n, 4), 0.3966);
+
+}
+
+#[test]
+fn zero_three_values() {
+
+    let n = interpol::zero_three_values(-1693.4, 406.3, 2303.2).unwrap();
+
+    assert_eq!(util::round_upto_digits(n, 5), -0.20127);
+
+    // a parabola above the axis, and values that aren't numbers
+    assert_eq!(interpol::zero_three_values(1.0, 0.5, 1.0), None);
+    assert_eq!(interpol::zero_three_values(f64::NAN, 0.5, 1.0), None);
+
+}
+
+#[test]
+fn zero_five_values() {
+
+    let secs = |d: f64, m: f64, s: f64| d*3600.0 + d.signum()*(m*60.0 + s);
+    let n = interpol::zero_five_values(
+        -secs(1.0, 11.0, 21.23),
+        -(28.0*60.0 + 12.31),
+        16.0*60.0 + 7.02,
+        secs(1.0, 1.0, 0.13),
+        secs(1.0, 45.0, 46.33)
+    ).unwrap();
+
+    assert_eq!(util::round_upto_digits(n, 6), -0.361413);
+
+    assert_eq!(interpol::zero_five_values(2.0, 1.0, 0.5, 1.0, 2.0), None);
+
+}
+
+#[test]
+fn lagrange() {
+
+    let x = [29.43, 30.97, 27.69, 28.11, 31.58, 33.05];
+    let y = [0.4913598, 0.5145891, 0.4646875, 0.4711658, 0.5236885, 0.5453707];
+
+    assert_eq!(util::round_upto_digits(interpol::lagrange(&x, &y, 30.0), 6), 0.5);
+
+    // exact at the given arguments
+    assert_eq!(util::round_upto_digits(interpol::lagrange(&x, &y, 27.69), 7), 0.4646875);
+
+}
+
+#[test]
+fn hermite() {
+
+    // a cubic is found exactly from two values and derivatives
+    let f = |x: f64| 2.0*x*x*x - x*x + 3.0*x - 5.0;
+    let d = |x: f64| 6.0*x*x - 2.0*x + 3.0;
+    let x = [1.0, 3.0];
+    let y = [f(1.0), f(3.0)];
+    let deriv = [d(1.0), d(3.0)];
+
+    for &arg in &[0.0, 1.5, 2.2, 4.0] {
+        assert!((interpol::hermite(&x, &y, &deriv, arg) - f(arg)).abs() < 1e-9);
+    }
+
+    // and sin from a few of its values and derivatives
+    let x = [0.0, 0.5, 1.0];
+    let y: Vec<f64> = x.iter().map(|v: &f64| v.sin()).collect();
+    let deriv: Vec<f64> = x.iter().map(|v: &f64| v.cos()).collect();
+    assert!((interpol::hermite(&x, &y, &deriv, 0.7) - 0.7_f64.sin()).abs() < 1e-5);
+
+}
+
+#[test]
+fn cubic_spline() {
+
+    // a natural spline through a straight line is the line itself
+    let spline = interpol::CubicSpline::natural(&[0.0, 1.0, 3.0, 4.0], &[1.0, 3.0, 7.0, 9.0]);
+    assert!((spline.value(2.5) - 6.0).abs() < 1e-12);
+    assert!((spline.deriv(0.5) - 2.0).abs() < 1e-12);
+
+    // a clamped spline through sin, with its extremum and zeros
+    let x: Vec<f64> = (0..=20).map(|i| i as f64 * 0.5).collect();
+    let y: Vec<f64> = x.iter().map(|v| v.sin()).collect();
+    let spline = interpol::CubicSpline::clamped(&x, &y, 1.0, 10_f64.cos());
+
+    assert!((spline.value(1.3) - 1.3_f64.sin()).abs() < 1e-3);
+    assert!((spline.deriv(1.3) - 1.3_f64.cos()).abs() < 1e-2);
+
+    let extrema = spline.extrema();
+    assert_eq!(extrema.len(), 3);
+    assert!((extrema[0].0 - std::f64::consts::FRAC_PI_2).abs() < 1e-3);
+    assert!((extrema[0].1 - 1.0).abs() < 1e-3);
+    assert!((extrema[1].1 + 1.0).abs() < 1e-3);
+
+    let zeros = spline.zeros();
+    assert_eq!(zeros.len(), 4);
+    assert!(zeros[0].abs() < 1e-12);
+    for (i, zero) in zeros.iter().enumerate() {
+        assert!((zero - i as f64 * std::f64::consts::PI).abs() < 1e-3);
+    }
+
+}
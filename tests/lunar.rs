@@ -61,6 +61,13 @@ fn time_of_passage_through_nodes() {
         util::round_upto_digits(ascend_JD, 5), 2446938.76803
     );
 
+    let JD = lunar::node_passage(&date, &lunar::Node::Ascend);
+    assert_eq!(util::round_upto_digits(JD, 5), 2446938.76803);
+
+    // the closest descending node precedes it by half a draconic month
+    let JD = lunar::node_passage(&date, &lunar::Node::Descend);
+    assert_eq!(util::round_upto_digits(ascend_JD - JD, 0), 13.0);
+
 }
 
 #[test]
@@ -124,4 +131,109 @@ fn phases() {
         cal_type    : time::CalType::Gregorian};
     let JD_new_moon = lunar::time_of_phase(&date_new_moon, &lunar::Phase::New);
     assert_eq!(util::round_upto_digits(JD_new_moon, 5), 2443192.65118);
+
+    // the phases of 2024 January
+    let phases = lunar::phases_in_range(2460310.5, 2460341.5);
+    let kinds: Vec<lunar::Phase> = phases.iter().map(|&(_, phase)| phase).collect();
+    assert_eq!(kinds, vec![lunar::Phase::Last, lunar::Phase::New, lunar::Phase::First, lunar::Phase::Full]);
+    let days: Vec<f64> = phases.iter().map(|&(JD, _)| util::round_upto_digits(JD - 2460310.5, 2)).collect();
+    assert_eq!(days, vec![3.15, 10.5, 17.16, 24.75]);
+}
+
+#[test]
+fn earth_frm_moon() {
+
+    let JD = 2448724.5;
+
+    let i = lunar::earth_phase_angl_frm_moon(JD);
+    assert_eq!(util::round_upto_digits(i.to_degrees(), 1), 110.8);
+
+    // the Earth's phase is complementary to the Moon's
+    let k = lunar::earth_illum_frac_frm_moon(JD);
+    assert_eq!(util::round_upto_digits(k, 2), 0.32);
+
+    let full = lunar::earth_apprnt_mag_frm_moon(2451550.26);
+    assert!(full < -16.5 && full > -17.5);
+    assert!(lunar::earth_apprnt_mag_frm_moon(JD) > full);
+
+    let E = lunar::earthshine_illuminance(2451550.26);
+    assert!(E > 10.0 && E < 25.0);
+
+}
+
+#[test]
+fn apsis() {
+
+    let date = time::Date {
+        year        : 1988,
+        month       : time::Month::Oct,
+        decimal_day : 1.0,
+        cal_type    : time::CalType::Gregorian
+    };
+
+    let (JD, dist) = lunar::apsis(&date, &lunar::Apsis::Apogee);
+    assert_eq!(util::round_upto_digits(JD, 4), 2447442.3543);
+    assert_eq!(util::round_upto_digits(lunar::eq_hz_parllx(dist).to_degrees() * 3600.0, 3), 3240.679);
+
+    // the preceding perigee is about half an anomalistic month earlier
+    let (JD_perigee, dist_perigee) = lunar::apsis(&date, &lunar::Apsis::Perigee);
+    assert_eq!(util::round_upto_digits(JD - JD_perigee, 0), 13.0);
+    assert!(dist_perigee < 370000.0);
+
+}
+
+#[test]
+fn rise_set() {
+
+    let boston = coords::GeographPoint { long: 71.0833_f64.to_radians(), lat: 42.3333_f64.to_radians() };
+    let date = |day| time::Date {
+        year        : 1988,
+        month       : time::Month::Mar,
+        decimal_day : day,
+        cal_type    : time::CalType::Gregorian
+    };
+
+    let (rise, set) = lunar::rise_set(&boston, &date(20.0));
+    assert_eq!(util::round_upto_digits(rise.unwrap() - 2447240.5, 3), 0.494);
+    assert_eq!(util::round_upto_digits(set.unwrap() - 2447240.5, 3), 0.052);
+
+    // there's a day without a moonrise and one without a moonset in
+    // the month
+    let days: Vec<(Option<f64>, Option<f64>)> = (1..32).map(|day| lunar::rise_set(&boston, &date(day as f64))).collect();
+    assert_eq!(days.iter().filter(|&&(rise, _)| rise.is_none()).count(), 1);
+    assert_eq!(days.iter().filter(|&&(_, set)| set.is_none()).count(), 1);
+    assert!(days[4].0.is_none() && days[18].1.is_none());
+
+}
+
+#[test]
+fn disk_state() {
+
+    // 1992 April 12, at 0h TD
+    let state = lunar::disk_state(2448724.5);
+
+    assert_eq!(util::round_upto_digits(state.illum_frac, 4), 0.6786);
+    assert_eq!(util::round_upto_digits(state.phase_angl.to_degrees(), 2), 69.08);
+    assert_eq!(util::round_upto_digits(state.bright_limb.to_degrees(), 1), 285.0);
+
+    assert_eq!(util::round_upto_digits(state.optical_libr_long.to_degrees(), 3), -1.206);
+    assert_eq!(util::round_upto_digits(state.optical_libr_lat.to_degrees(), 3), 4.194);
+    assert_eq!(util::round_upto_digits(state.physical_libr_long.to_degrees(), 3), -0.025);
+    assert_eq!(util::round_upto_digits(state.physical_libr_lat.to_degrees(), 3), 0.006);
+    assert_eq!(util::round_upto_digits(state.total_libr_long.to_degrees(), 2), -1.23);
+    assert_eq!(util::round_upto_digits(state.total_libr_lat.to_degrees(), 2), 4.2);
+    assert_eq!(util::round_upto_digits(state.pos_angl_of_axis.to_degrees(), 2), 15.08);
+
+    let (moon_point, moon_dist) = lunar::geocent_ecl_pos(2448724.5);
+    let (sun_point, sun_dist) = sun::geocent_ecl_pos(2448724.5);
+    let oblq = ecliptic::mn_oblq_IAU(2448724.5);
+    let eq_point = |point: coords::EclPoint| coords::EqPoint {
+        asc: coords::asc_frm_ecl(point.long, point.lat, oblq),
+        dec: coords::dec_frm_ecl(point.long, point.lat, oblq)
+    };
+    let k = lunar::illum_frac_frm_eq_coords (
+        &eq_point(sun_point), &eq_point(moon_point), moon_dist, sun_dist * 149597870.7
+    );
+    assert_eq!(util::round_upto_digits(k, 2), 0.68);
+
 }
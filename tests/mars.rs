@@ -49,11 +49,123 @@ fn ephemeris() {
     assert_eq!(ephemeris.P, 347.64);
     assert_eq!(ephemeris.w, 111.5);
 
-    let (h1, m1, s1) = angle::dms_frm_deg(ephemeris.d.to_degrees());
+    let angle::Dms { deg: h1, min: m1, sec: s1 } = angle::Rad(ephemeris.d).into();
     assert_eq!((h1, m1), (0, 0));
     assert_eq!(util::round_upto_digits(s1, 2), 10.75);
 
-    let (h2, m2, s2) = angle::dms_frm_deg(ephemeris.q.to_degrees());
+    let angle::Dms { deg: h2, min: m2, sec: s2 } = angle::Rad(ephemeris.q).into();
     assert_eq!((h2, m2), (0, 0));
     assert_eq!(util::round_upto_digits(s2, 2), 1.06);
+
+    // the greatest defect lies opposite the bright limb
+    let oblq = 23.44022_f64.to_radians();
+    let (sun, _) = sun::geocent_ecl_pos(JD);
+    let (mars, _) = planet::geocent_apprnt_ecl_coords(&planet::Planet::Mars, JD);
+    let bright_limb = planet::pos_angle_of_bright_limb(
+        coords::EqPoint { asc: coords::asc_frm_ecl(sun.long, sun.lat, oblq), dec: coords::dec_frm_ecl(sun.long, sun.lat, oblq) },
+        coords::EqPoint { asc: coords::asc_frm_ecl(mars.long, mars.lat, oblq), dec: coords::dec_frm_ecl(mars.long, mars.lat, oblq) }
+    );
+    assert_eq!(
+        util::round_upto_digits(ephemeris.Q.to_degrees(), 1),
+        util::round_upto_digits(angle::limit_to_360(bright_limb.to_degrees() + 180.0), 1)
+    );
+}
+
+#[test]
+fn timekeeping() {
+
+    // 2000 January 6, 0h UTC, from Allison and McEwen (2000)
+    let JD = 2451549.5 + 64.184/86400.0;
+
+    assert_eq!(util::round_upto_digits(planet::mars::areocent_solar_long(JD).to_degrees(), 4), 277.1876);
+    assert_eq!(util::round_upto_digits(planet::mars::eq_of_time(JD).to_degrees(), 3), -5.188);
+    assert_eq!(util::round_upto_digits(planet::mars::sol_date(JD), 4), 44795.9998);
+    assert_eq!(util::round_upto_digits(planet::mars::coord_time(JD), 3), 23.994);
+
+    let long = 137.4_f64.to_radians();
+    let LMST = planet::mars::loc_mn_solar_time(JD, long);
+    assert_eq!(util::round_upto_digits(LMST, 3), util::round_upto_digits(23.994 + 137.4/15.0 - 24.0, 3));
+    let LTST = planet::mars::loc_true_solar_time(JD, long);
+    assert_eq!(util::round_upto_digits(LTST - LMST, 3), -0.346);
+
+    // a sol after landing is sol 1 of a mission counting from sol 0
+    let landing = 2456145.72;
+    assert_eq!(planet::mars::mission_sol(landing, landing, long, 0), 0);
+    assert_eq!(planet::mars::mission_sol(landing + planet::mars::SOL, landing, long, 0), 1);
+    assert_eq!(planet::mars::mission_sol(landing + 100.0*planet::mars::SOL, landing, long, 1), 101);
+
+}
+
+#[test]
+fn darian_calendar() {
+
+    let start = |year| planet::mars::sol_date_frm_darian(
+        &planet::mars::DarianDate { year, month: 1, decimal_sol: 1.0 }
+    );
+    assert_eq!(start(1) - start(0), 669.0);
+    assert_eq!(start(3) - start(2), 668.0);
+    assert_eq!(start(11) - start(10), 669.0);
+    assert_eq!(start(101) - start(100), 668.0);
+    assert_eq!(start(0), -94129.0);
+
+    for &MSD in [-94129.0, 44795.9999, 52000.25, -100000.5].iter() {
+        let date = planet::mars::darian_frm_sol_date(MSD);
+        assert!(date.month >= 1 && date.month <= 24);
+        assert!(date.decimal_sol >= 1.0 && date.decimal_sol < 29.0);
+        assert!((planet::mars::sol_date_frm_darian(&date) - MSD).abs() < 1e-6);
+    }
+
+    let date = planet::mars::darian_frm_sol_date(start(214) + 8.0*28.0 + 27.0 + 1.5);
+    assert_eq!((date.year, date.month, date.decimal_sol), (214, 10, 2.5));
+    assert_eq!(planet::mars::DARIAN_MONTHS[date.month as usize - 1], "Mesha");
+
+}
+
+#[test]
+fn moons() {
+
+    use planet::mars::Moon;
+
+    // mean distances from Mars in km, eccentricities, and periods in
+    // days
+    let data = [
+        (Moon::Phobos, 9379.4, 0.0150, 0.31891023),
+        (Moon::Deimos, 23459.0, 0.0004, 1.2624407),
+    ];
+
+    let (l, b) = (352.9_f64.to_radians(), 63.3_f64.to_radians());
+    let dist = |(x, y, z): (f64, f64, f64)| (x*x + y*y + z*z).sqrt() * 149597870.7;
+
+    for &(ref moon, a, e, period) in data.iter() {
+        for i in 0..10 {
+            let JD = 2451545.0 + (i as f64)*0.13;
+
+            let p0 = planet::mars::moon_areocent_rect_coords(JD, moon);
+            assert!((dist(p0) - a).abs() <= a*e + 1.0);
+
+            // back near its place after a period
+            let p1 = planet::mars::moon_areocent_rect_coords(JD + period, moon);
+            assert!(dist((p1.0 - p0.0, p1.1 - p0.1, p1.2 - p0.2)) < 0.01 * a);
+
+            // prograde, ie: with the rotation of Mars
+            let p2 = planet::mars::moon_areocent_rect_coords(JD + 0.01, moon);
+            let h = (p0.1*p2.2 - p0.2*p2.1, p0.2*p2.0 - p0.0*p2.2, p0.0*p2.1 - p0.1*p2.0);
+            assert!(h.0*b.cos()*l.cos() + h.1*b.cos()*l.sin() + h.2*b.sin() > 0.0);
+        }
+    }
+
+    // greatest elongations at the opposition of 2003 August 28, when
+    // Mars came nearest the Earth
+    let mut max_sep = [0.0_f64; 2];
+    for i in 0..100 {
+        let moons = planet::mars::moons(2452879.5 + (i as f64)*0.0253);
+        for (max_sep, &(X, Y, pos_angl, sep)) in max_sep.iter_mut().zip(moons.iter()) {
+            assert!((sep - (X*X + Y*Y).sqrt()).abs() < 1e-10);
+            assert!((X - sep*pos_angl.sin()).abs() < 1e-9 && (Y - sep*pos_angl.cos()).abs() < 1e-9);
+            *max_sep = max_sep.max(sep.to_degrees() * 3600.0);
+        }
+    }
+    assert!(max_sep[0] > 33.0 && max_sep[0] < 35.0);
+    assert!(max_sep[1] > 84.0 && max_sep[1] < 87.0);
+
 }
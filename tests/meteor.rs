@@ -0,0 +1,54 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+use astro::*;
+
+#[test]
+fn heliocent_orbit() {
+
+    // a Perseid meteor observed near the shower's maximum in 2016
+    let radiant = coords::EqPoint {
+        asc : 48.2_f64.to_radians(),
+        dec : 58.1_f64.to_radians()
+    };
+    let geograph_point = coords::GeographPoint {
+        long : -10.0_f64.to_radians(),
+        lat  : 50.0_f64.to_radians()
+    };
+    let JD = 2457613.0;
+
+    let (geocent_radiant, geocent_vel) = meteor::zenith_attraction(
+        &radiant, 60.0, JD, &geograph_point
+    );
+    assert_eq!(util::round_upto_digits(geocent_vel, 1), 59.0);
+    assert!(geocent_radiant.dec < radiant.dec);
+
+    let elements = meteor::heliocent_orbit(&radiant, 60.0, JD, &geograph_point);
+
+    assert_eq!(util::round_upto_digits(elements.sigma.to_degrees(), 0), 140.0);
+    assert_eq!(util::round_upto_digits(elements.i.to_degrees(), 0), 113.0);
+    assert_eq!(util::round_upto_digits(elements.q, 2), 0.95);
+
+}
@@ -0,0 +1,125 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+use astro::*;
+use planet::moon_phenomena::PhenomenonType;
+
+#[test]
+fn circular_orbit() {
+
+    // an Io-like satellite on a circular orbit in Jupiter's equator
+    let primary = planet::moon_phenomena::primary(planet::Planet::Jupiter);
+
+    let oblq = 23.4392911_f64.to_radians();
+    let (x, y, z) = (
+        primary.pole_dec.cos() * primary.pole_asc.cos(),
+        primary.pole_dec.cos() * primary.pole_asc.sin(),
+        primary.pole_dec.sin()
+    );
+    let k = (x, y*oblq.cos() + z*oblq.sin(), -y*oblq.sin() + z*oblq.cos());
+    let n = (k.0*k.0 + k.1*k.1).sqrt();
+    let e1 = (k.1/n, -k.0/n, 0.0);
+    let e2 = (k.1*e1.2 - k.2*e1.1, k.2*e1.0 - k.0*e1.2, k.0*e1.1 - k.1*e1.0);
+
+    let r = 421700.0 / 149597870.7;
+    let period = 1.769138;
+    let sat = |JD: f64| {
+        let theta = 2.0*std::f64::consts::PI * (JD - 2457000.0) / period;
+        (
+            r*(theta.cos()*e1.0 + theta.sin()*e2.0),
+            r*(theta.cos()*e1.1 + theta.sin()*e2.1),
+            r*(theta.cos()*e1.2 + theta.sin()*e2.2)
+        )
+    };
+
+    let JD_start = 2457000.0;
+    let JD_end = JD_start + 3.0*period;
+    let phenomena = planet::moon_phenomena::phenomena (
+        &primary, sat, JD_start, JD_end, 0.01
+    ).unwrap();
+
+    let kinds = [
+        PhenomenonType::Transit,
+        PhenomenonType::Occultation,
+        PhenomenonType::Eclipse,
+        PhenomenonType::ShadowTransit
+    ];
+    for kind in kinds.iter() {
+        let complete: Vec<&planet::moon_phenomena::Phenomenon> = phenomena.iter()
+            .filter(|p| p.kind == *kind && p.start > JD_start && p.end < JD_end)
+            .collect();
+        assert!(complete.len() >= 2);
+
+        for p in complete.iter() {
+            // a central passage across the disk lasts about 2*R/v = 2.29 h
+            let hours = (p.end - p.start) * 24.0;
+            assert!(hours > 2.2 && hours < 2.4);
+            assert!(planet::moon_phenomena::is_in_phenomenon (
+                &primary, kind, (p.start + p.end)/2.0, &sat
+            ));
+        }
+    }
+
+}
+
+#[test]
+fn miranda_at_uranian_equinox() {
+
+    let primary = planet::moon_phenomena::primary(planet::Planet::Uranus);
+    let miranda = |JD: f64| planet::uranus::moons::uranicent_rect_coords (
+        JD, &planet::uranus::moons::Moon::Miranda
+    );
+
+    let phenomena = planet::moon_phenomena::phenomena (
+        &primary, miranda, 2454440.5, 2454443.5, 0.005
+    ).unwrap();
+
+    assert!(phenomena.iter().any(|p| p.kind == PhenomenonType::Transit));
+    assert!(phenomena.iter().any(|p| p.kind == PhenomenonType::Occultation));
+
+}
+
+#[test]
+fn uranus_moons_phenomena() {
+
+    let moon = planet::uranus::moons::Moon::Miranda;
+    let phenomena = planet::uranus::moons::phenomena(&moon, 2454440.5, 2454443.5, 0.005).unwrap();
+
+    let primary = planet::moon_phenomena::primary(planet::Planet::Uranus);
+    let expected = planet::moon_phenomena::phenomena (
+        &primary, |JD| planet::uranus::moons::uranicent_rect_coords(JD, &moon), 2454440.5, 2454443.5, 0.005
+    ).unwrap();
+
+    assert_eq!(phenomena.len(), expected.len());
+    for (p, q) in phenomena.iter().zip(expected.iter()) {
+        assert_eq!((p.kind, p.start, p.end), (q.kind, q.start, q.end));
+    }
+
+    assert_eq!(
+        planet::uranus::moons::phenomena(&moon, 2454440.5, 2454443.5, 0.0).err(),
+        Some(Error::NotPositive(0.0))
+    );
+
+}
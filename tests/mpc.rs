@@ -0,0 +1,110 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+
+#![cfg(feature = "mpc")]
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+use astro::io::mpc;
+
+const MPCORB: &str = "\
+MINOR PLANET CENTER ORBIT DATABASE (MPCORB)
+
+Des'n     H     G   Epoch     M        Peri.      Node       Incl.       e            n           a        Reference #Obs #Opp    Arc    rms  Perts   Computer
+----------------------------------------------------------------------------------------------------------------------------------------------------------------
+00001    3.34  0.12 K205V 162.68631   73.73161   80.28698   10.58862  0.0775571  0.21406009   2.7676569  0 MPO492748  6751 115 1801-2019 0.60 M-v 30h Williams   0000      (1) Ceres              20200204
+
+";
+
+#[test]
+fn minor_planets() {
+
+    let minor_planets = mpc::minor_planets(MPCORB).unwrap();
+    assert_eq!(minor_planets.len(), 1);
+
+    let ceres = &minor_planets[0];
+    assert_eq!(ceres.designation, "1");
+    assert_eq!((ceres.H, ceres.G), (Some(3.34), Some(0.12)));
+    assert_eq!(ceres.elements.epoch, 2459000.5);
+    assert_eq!(ceres.elements.a, 2.7676569);
+    assert_eq!(ceres.elements.e, 0.0775571);
+    assert_eq!(util::round_upto_digits(ceres.elements.M.to_degrees(), 5), 162.68631);
+
+    // the mean motion follows from the semimajor axis
+    assert_eq!(util::round_upto_digits(ceres.elements.mn_motion().to_degrees(), 6), 0.21406);
+
+    assert_eq!(mpc::minor_planet_frm_line("00001    3.34"), Err(Error::InvalidFormat));
+
+}
+
+#[test]
+fn observations() {
+
+    let observations = mpc::observations(&[
+        "     K07Tf8A* C2007 10 09.25763 02 41 24.64 +12 11 42.4          19.2 R      704",
+        "00433         C2023 01 15.12345 05 41 23.50 -00 02 03.1          11.5 V      568",
+        "00433         s2023 01 15.12345 1 - 3466.0651 + 5337.0216 + 1429.8461        250",
+    ].join("\n")).unwrap();
+    assert_eq!(observations.len(), 2);
+
+    let obs = &observations[0];
+    assert_eq!(obs.designation, "2007 TA418");
+    assert!(obs.discovery);
+    assert_eq!((obs.kind, obs.band, obs.mag), ('C', 'R', Some(19.2)));
+    assert_eq!(obs.observatory, "704");
+    assert_eq!(util::round_upto_digits(obs.JD, 5), 2454382.75763);
+    assert_eq!(util::round_upto_digits(obs.asc.to_degrees(), 6), 40.352667);
+    assert_eq!(util::round_upto_digits(obs.dec.to_degrees(), 6), 12.195111);
+
+    let obs = &observations[1];
+    assert_eq!(obs.designation, "433");
+    assert!(!obs.discovery);
+    assert_eq!(util::round_upto_digits(obs.dec.to_degrees(), 6), -0.034194);
+
+    assert_eq!(
+        mpc::observation_frm_line("00433         s2023 01 15.12345 1 - 3466.0651 + 5337.0216 + 1429.8461        250"),
+        Err(Error::InvalidFormat)
+    );
+    assert_eq!(
+        mpc::observation_frm_line("00433         C2023 13 15.12345 05 41 23.50 -00 02 03.1          11.5 V      568"),
+        Err(Error::InvalidFormat)
+    );
+
+}
+
+#[test]
+fn designations() {
+
+    assert_eq!(mpc::unpack_designation("00433"), Some("433".to_string()));
+    assert_eq!(mpc::unpack_designation("A0001"), Some("100001".to_string()));
+    assert_eq!(mpc::unpack_designation("~0000"), Some("620000".to_string()));
+    assert_eq!(mpc::unpack_designation("J95X00A"), Some("1995 XA".to_string()));
+    assert_eq!(mpc::unpack_designation("K07Tf8A"), Some("2007 TA418".to_string()));
+    assert_eq!(mpc::unpack_designation("K07T"), None);
+
+    assert_eq!(mpc::unpack_epoch("K243V"), Some(2460400.5));
+    assert_eq!(mpc::unpack_epoch("K24D1"), None);
+
+}
@@ -0,0 +1,90 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![cfg(feature = "nbody")]
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+
+#[test]
+fn propagate_two_body() {
+
+    let (a, e, i, sigma, w) = (
+        2.7675, 0.0758, 10.59_f64.to_radians(),
+        80.31_f64.to_radians(), 73.12_f64.to_radians()
+    );
+    let M0 = 95.99_f64.to_radians();
+    let n = orbit::elliptic::mn_motion(a);
+
+    let state0 = orbit::nbody::state_frm_orb_elements(a, e, i, sigma, w, M0);
+    let state1 = orbit::nbody::propagate(&state0, 2451545.0, 2451845.0, 1.0, &[]).unwrap();
+    let expected = orbit::nbody::state_frm_orb_elements(a, e, i, sigma, w, M0 + 300.0*n);
+
+    assert_eq!(util::round_upto_digits(state1.x, 8), util::round_upto_digits(expected.x, 8));
+    assert_eq!(util::round_upto_digits(state1.y, 8), util::round_upto_digits(expected.y, 8));
+    assert_eq!(util::round_upto_digits(state1.z, 8), util::round_upto_digits(expected.z, 8));
+
+    let state2 = orbit::nbody::propagate(&state1, 2451845.0, 2451545.0, 1.0, &[]).unwrap();
+    assert_eq!(util::round_upto_digits(state2.x, 8), util::round_upto_digits(state0.x, 8));
+    assert_eq!(util::round_upto_digits(state2.vy, 10), util::round_upto_digits(state0.vy, 10));
+
+}
+
+#[test]
+fn propagate_perturbed() {
+
+    let state0 = orbit::nbody::state_frm_orb_elements(
+        5.2, 0.05, 1.0_f64.to_radians(), 100.0_f64.to_radians(),
+        270.0_f64.to_radians(), 40.0_f64.to_radians()
+    );
+
+    let kepler = orbit::nbody::propagate(&state0, 2451545.0, 2451910.0, 2.0, &[]).unwrap();
+    let perturbed = orbit::nbody::propagate(
+        &state0, 2451545.0, 2451910.0, 2.0, &[planet::Planet::Jupiter, planet::Planet::Saturn]
+    ).unwrap();
+
+    let dx = perturbed.x - kepler.x;
+    let dy = perturbed.y - kepler.y;
+    let dz = perturbed.z - kepler.z;
+    let d = (dx*dx + dy*dy + dz*dz).sqrt();
+
+    assert!(d > 1e-5 && d < 0.5);
+
+}
+
+#[test]
+fn propagate_invalid_step() {
+
+    let state0 = orbit::nbody::state_frm_orb_elements(2.7675, 0.0758, 0.2, 1.4, 1.3, 1.7);
+
+    assert_eq!(
+        orbit::nbody::propagate(&state0, 2451545.0, 2451845.0, 0.0, &[]).err(),
+        Some(error::Error::NotPositive(0.0))
+    );
+    assert_eq!(
+        orbit::nbody::propagate(&state0, 2451545.0, 2451845.0, -1.0, &[]).err(),
+        Some(error::Error::NotPositive(-1.0))
+    );
+
+}
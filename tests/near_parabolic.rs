@@ -33,5 +33,31 @@ fn true_anom_and_rad_vec() {
 
     assert_eq!(util::round_upto_digits(tru_anom.to_degrees(), 5), 102.74426);
     assert_eq!(util::round_upto_digits(rad_vec, 6), 2.364192);
-    
+
+}
+
+#[test]
+fn table_35_a() {
+
+    // (q, e, t - T, v, r) from the worked examples in Meeus, Table 35.A
+    let cases = [
+        (0.1,       0.987,     254.9,   164.50029, 4.063777),
+        (0.123456,  0.99997,   -30.47,  221.91190, 0.965053),
+        (3.363943,  1.05731,   1237.1,  109.40598, 10.668551),
+        (0.5871018, 0.9672746, 20.0,    52.85331,  0.729116),
+        (0.5871018, 0.9672746, 0.0,     0.0,       0.5871018),
+    ];
+
+    for &(q, e, t, v, r) in cases.iter() {
+        let (tru_anom, rad_vec) = orbit::near_parabolic::true_anom_and_rad_vec (
+            t, 0.0, e, q, 0.000000001
+        ).unwrap();
+
+        assert_eq!(util::round_upto_digits(tru_anom.to_degrees(), 5), v);
+        assert_eq!(util::round_upto_digits(rad_vec, 6), util::round_upto_digits(r, 6));
+    }
+
+    // Too far from a parabola for the series to converge
+    assert!(orbit::near_parabolic::true_anom_and_rad_vec(10000.0, 0.0, 0.9, 0.1, 0.000000001).is_err());
+
 }
@@ -0,0 +1,84 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+use astro::*;
+
+#[test]
+fn triton() {
+
+    let (x0, y0, z0) = planet::neptune::triton_neptunicent_rect_coords(2451545.0);
+    let r = (x0*x0 + y0*y0 + z0*z0).sqrt() * 149597870.7;
+    assert_eq!((r/100.0).round(), 3543.0);
+
+    // Triton returns close to it's place after a period of 5.877 days,
+    // the regression of the node shifting it by about a hundred km
+    let (x1, y1, z1) = planet::neptune::triton_neptunicent_rect_coords(2451545.0 + 5.876854);
+    assert!(((x1-x0).powi(2) + (y1-y0).powi(2) + (z1-z0).powi(2)).sqrt() * 149597870.7 < 250.0);
+
+    // retrograde, ie: against the rotation of Neptune, whose north pole
+    // of the IAU at right ascension 299.36 and declination 43.46
+    // degrees lies at ecliptic longitude 319.2 and latitude 62.0
+    // degrees
+    let (asc, dec) = (299.36_f64.to_radians(), 43.46_f64.to_radians());
+    let oblq = ecliptic::mn_oblq_IAU(2451545.0);
+    let (l, b) = (coords::ecl_long_frm_eq(asc, dec, oblq), coords::ecl_lat_frm_eq(asc, dec, oblq));
+    assert_eq!(util::round_upto_digits(angle::limit_to_360(l.to_degrees()), 1), 319.2);
+    assert_eq!(util::round_upto_digits(b.to_degrees(), 1), 62.0);
+
+    let (x2, y2, z2) = planet::neptune::triton_neptunicent_rect_coords(2451545.0 + 0.1);
+    let h = (y0*z2 - z0*y2, z0*x2 - x0*z2, x0*y2 - y0*x2);
+    let cos_incl = (h.0*b.cos()*l.cos() + h.1*b.cos()*l.sin() + h.2*b.sin())
+                 / (h.0*h.0 + h.1*h.1 + h.2*h.2).sqrt();
+    // inclined by about 157 degrees to Neptune's equator
+    let incl = cos_incl.acos().to_degrees();
+    assert!(incl > 155.0 && incl < 160.0);
+
+    let (X, Y) = planet::neptune::triton_apprnt_offsets(2451545.0);
+    assert!((X*X + Y*Y).sqrt().to_degrees()*3600.0 < 17.0);
+
+}
+
+#[test]
+fn triton_mag() {
+
+    // opposition of 2010 August 20
+    let mag = planet::neptune::triton_apprnt_mag(2455428.5);
+    assert_eq!(util::round_upto_digits(mag, 1), 13.5);
+
+}
+
+#[test]
+fn ephemeris() {
+
+    let eph = planet::neptune::ephemeris(2460310.5);
+    assert_eq!(util::round_upto_digits(eph.d.to_degrees()*3600.0, 1), 2.3);
+    assert_eq!(eph.De.to_degrees().round(), -22.0);
+    assert_eq!(eph.Ds.to_degrees().round(), -21.0);
+
+    // one rotation in 16.11 hours
+    let w = planet::neptune::ephemeris(2460310.6).w - eph.w;
+    assert_eq!(w.to_degrees().round(), 54.0);
+
+}
@@ -0,0 +1,73 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+
+#[test]
+fn bisection() {
+
+    let zero = numerics::bisection(|x: f64| x*x - 2.0, 0.0, 2.0, 1e-10).unwrap();
+    assert!((zero - 2_f64.sqrt()).abs() < 1e-10);
+
+    assert_eq!(numerics::bisection(|x: f64| x*x + 1.0, -1.0, 1.0, 1e-10), None);
+
+}
+
+#[test]
+fn brent() {
+
+    let zero = numerics::brent(|x: f64| x.cos() - x, 0.0, 1.0, 1e-12).unwrap();
+    assert!((zero - 0.7390851332151607).abs() < 1e-12);
+
+    // the instant of the March equinox of 1962, as the zero of the
+    // apparent longitude of the Sun
+    let equinox = sun::equinox_solstice(1962, &sun::Event::MarchEquinox);
+    let f = |JD: f64| {
+        let (point, R) = sun::geocent_ecl_pos(JD);
+        let long = sun::ecl_point_to_FK5(JD, &point).long;
+        let (nut_in_long, _) = nutation::nutation(JD);
+        let apprnt_long = long + nut_in_long - angle::deg_frm_dms(0, 0, 20.4898).to_radians()/R;
+        apprnt_long.sin()
+    };
+    let JD = numerics::brent(f, equinox - 1.0, equinox + 1.0, 1e-7).unwrap();
+    assert!((JD - equinox).abs() < 1e-4);
+
+    assert_eq!(numerics::brent(|x: f64| x*x + 1.0, -1.0, 1.0, 1e-10), None);
+
+}
+
+#[test]
+fn golden_section() {
+
+    let minimum = numerics::golden_section(|x: f64| (x - 1.5)*(x - 1.5) + 2.0, 0.0, 4.0, 1e-6);
+    assert!((minimum - 1.5).abs() < 1e-6);
+
+    // the minimum of cos(x) in (2, 5) is at π
+    let minimum = numerics::golden_section(|x: f64| x.cos(), 5.0, 2.0, 1e-6);
+    assert!((minimum - std::f64::consts::PI).abs() < 1e-6);
+
+}
@@ -30,14 +30,34 @@ fn nutation() {
 
     let (nut_in_long, nut_in_oblq) = nutation::nutation(2446895.5);
 
-    let (d1, m1, s1) = angle::dms_frm_deg(nut_in_long.to_degrees());
+    let angle::Dms { deg: d1, min: m1, sec: s1 } = angle::Rad(nut_in_long).into();
     assert_eq!((d1, m1, util::round_upto_digits(s1, 3)), (0, 0, -3.788));
 
-    let (d2, m2, s2) = angle::dms_frm_deg(nut_in_oblq.to_degrees());
+    let angle::Dms { deg: d2, min: m2, sec: s2 } = angle::Rad(nut_in_oblq).into();
     assert_eq!((d2, m2, util::round_upto_digits(s2, 3)), (0, 0, 9.443));
 
 }
 
+#[test]
+fn nutation_far_from_J2000() {
+
+    // the lunar fundamental arguments, which in 1000 BC differ from
+    // those of the IAU 1980 theory by about 0.015 degree, and change
+    // its values of 0.409" and -9.747" by a few thousandths of an
+    // arcsecond
+    let (nut_in_long, nut_in_oblq) = nutation::nutation(1356000.5);
+
+    let angle::Dms { deg: _, min: _, sec: s1 } = angle::Rad(nut_in_long).into();
+    assert!((s1 - 0.409).abs() < 0.005);
+
+    let angle::Dms { deg: _, min: _, sec: s2 } = angle::Rad(nut_in_oblq).into();
+    assert!((s2 + 9.747).abs() < 0.005);
+
+    let args = fund_args::FundamentalArgs::new(1356000.5);
+    assert_eq!(nutation::nutation_frm_args(&args), (nut_in_long, nut_in_oblq));
+
+}
+
 #[test]
 fn nutation_in_eq_coords() {
 
@@ -56,3 +76,16 @@ fn nutation_in_eq_coords() {
     assert_eq!(util::round_upto_digits(b.to_degrees(), 7), 0.001727);
     
 }
+
+#[test]
+fn nutation_many() {
+
+    let JD: Vec<f64> = (0..600).map(|i| 2446895.5 + (i as f64)*11.3).collect();
+    let many = nutation::nutation_many(&JD);
+
+    assert_eq!(many.len(), JD.len());
+    for (&JD, &nut) in JD.iter().zip(many.iter()) {
+        assert_eq!(nut, nutation::nutation(JD));
+    }
+
+}
@@ -0,0 +1,100 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+
+#[test]
+fn try_new() {
+
+    let boston = observer::Observer::try_new(71.0833_f64.to_radians(), 42.3333_f64.to_radians(), 10.0).unwrap();
+    assert_eq!((boston.pressure, boston.temp, boston.utc_offset), (1010.0, 283.0, 0.0));
+
+    let east = observer::Observer::try_frm_east_long(-71.0833_f64.to_radians(), 42.3333_f64.to_radians(), 10.0).unwrap();
+    assert_eq!(east, boston);
+
+    assert_eq!(
+        observer::Observer::try_new(0.0, 100_f64.to_radians(), 0.0),
+        Err(Error::InvalidLatitude(100_f64.to_radians()))
+    );
+    assert_eq!(observer::Observer::try_new(0.0, 0.0, f64::NAN), Err(Error::NotFinite));
+
+}
+
+#[test]
+fn rise_set_transit() {
+
+    // Venus at Boston on 1988 March 20, from the Meeus book, on the
+    // local day of Eastern Standard Time
+    let mut boston = observer::Observer::try_new(71.0833_f64.to_radians(), 42.3333_f64.to_radians(), 0.0).unwrap();
+    boston.utc_offset = -5.0;
+
+    let date = time::Date { year: 1988, month: time::Month::Mar, decimal_day: 20.5, cal_type: time::CalType::Gregorian };
+    let venus = Body::Planet(planet::Planet::Venus);
+    match boston.rise_set_transit(&venus, &date, &transit::Altitude::Standard).unwrap() {
+        transit::RiseSetTransit::RiseSet { rise, transit, set } => {
+            assert_eq!(util::round_upto_digits(rise - 2447240.5, 3), 0.518);
+            assert_eq!(util::round_upto_digits(transit - 2447240.5, 3), 0.820);
+            assert_eq!(util::round_upto_digits(set - 2447240.5, 2), 1.12);
+
+            let local_rise = boston.local_frm_utc(rise);
+            assert_eq!(util::round_upto_digits(local_rise - 2447240.5, 2), 0.31);
+            assert_eq!(boston.utc_frm_local(local_rise), rise);
+        },
+        _ => panic!("Venus must rise and set at Boston")
+    }
+
+}
+
+#[test]
+fn weather() {
+
+    let mut obs = observer::Observer::try_new(0.0, 45_f64.to_radians(), 2000.0).unwrap();
+    let model = atmos::RefracModel::Bennett;
+
+    let apprnt = 1_f64.to_radians();
+    let refrac = apprnt - obs.true_alt_frm_apprnt(apprnt, &model);
+
+    // thinner and warmer air refracts less
+    obs.pressure = 800.0;
+    obs.temp = 293.0;
+    let thin = apprnt - obs.true_alt_frm_apprnt(apprnt, &model);
+    assert!(thin < refrac);
+    assert!((obs.apprnt_alt_frm_true(obs.true_alt_frm_apprnt(apprnt, &model), &model) - apprnt).abs() < 1e-6);
+
+    // the horizontal point seen through the same air is consistent
+    let eq_point = coords::EqPoint { asc: 1.0, dec: 0.3 };
+    let hz = obs.apprnt_hz(&eq_point, 2.0);
+    let back = obs.eq_frm_apprnt_hz(&hz, 2.0);
+    assert!((back.asc - eq_point.asc).abs() < 1e-5 && (back.dec - eq_point.dec).abs() < 1e-5);
+
+    assert!(obs.extinction(30_f64.to_radians(), &atmos::Band::V) > 0.0);
+
+    let topo = obs.topocentric(&eq_point, 0.0025, 2.0);
+    let direct = parallax::topocentric(&eq_point, 0.0025, &obs.geodetic_point(parallax::Ellipsoid::WGS84), 2.0);
+    assert_eq!(topo, direct);
+
+}
@@ -0,0 +1,79 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+
+#[test]
+fn star() {
+
+    // a star behind the center of the Sun at the greatest total solar
+    // eclipse of 2017 August 21, at 18:26:40 TDT, which was seen at
+    // 36.97° N, 87.67° W
+    let JD = 2457987.25 + (26.0 + 40.0/60.0)/1440.0;
+    let (sun, sun_dist) = sun::geocent_ecl_pos(JD);
+    let (nut_long, nut_oblq) = nutation::nutation(JD);
+    let oblq = ecliptic::mn_oblq_IAU(JD) + nut_oblq;
+    let long = sun.long + nut_long + aberr::sol_aberr(sun_dist);
+    let (asc, dec) = (coords::asc_frm_ecl(long, sun.lat, oblq), coords::dec_frm_ecl(long, sun.lat, oblq));
+
+    let observer = coords::GeographPoint {
+        long: 87.67_f64.to_radians(),
+        lat: 36.97_f64.to_radians()
+    };
+    let occult = occult::star(asc, dec, JD - 0.2, &observer, 0.0).unwrap();
+
+    // on the central line the Moon passes centrally over the star,
+    // its eastern limb first
+    let mid = (occult.disappearance + occult.reappearance) / 2.0;
+    assert!((mid - JD).abs() * 1440.0 < 1.0);
+    assert_eq!(((occult.reappearance - occult.disappearance) * 1440.0).round(), 81.0);
+    assert!(occult.disappearance_pa.to_degrees() > 45.0 && occult.disappearance_pa.to_degrees() < 135.0);
+    let pa_diff = (occult.reappearance_pa - occult.disappearance_pa).to_degrees();
+    assert!((pa_diff - 180.0).abs() < 2.0);
+
+    // 30° farther north, the Moon passes south of the star
+    let north = coords::GeographPoint { long: observer.long, lat: 66.97_f64.to_radians() };
+    assert!(occult::star(asc, dec, JD, &north, 0.0).is_none());
+
+}
+
+#[test]
+fn planet() {
+
+    // the occultation of Mars of 2022 December 8, seen from London from
+    // 05:01 to 05:59 UT
+    let london = coords::GeographPoint {
+        long: 0.13_f64.to_radians(),
+        lat: 51.51_f64.to_radians()
+    };
+    let occult = occult::planet(&planet::Planet::Mars, 2459921.6, &london, 0.0).unwrap();
+
+    let minutes_ut = |JD: f64| (time::ut_frm_tt(JD) - 2459921.5) * 1440.0;
+    assert!((minutes_ut(occult.disappearance) - 301.0).abs() < 2.0);
+    assert!((minutes_ut(occult.reappearance) - 359.0).abs() < 2.0);
+
+}
@@ -0,0 +1,107 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+
+#[test]
+fn position() {
+
+    let T = time::julian_day(
+        &time::Date {
+            year: 1990,
+            month: time::Month::Oct,
+            decimal_day: 28.54502,
+            cal_type: time::CalType::Gregorian
+        }
+    );
+    let encke = orbit::Elements::frm_perih_time(
+        2.2091404 * (1.0 - 0.8502196),
+        0.8502196,
+        11.94524_f64.to_radians(),
+        334.75006_f64.to_radians(),
+        186.23352_f64.to_radians(),
+        T
+    );
+    assert_eq!(util::round_upto_digits(encke.perih_time(), 5), util::round_upto_digits(T, 5));
+
+    let JD = 2448170.5;
+    let pos = orbit::position(&encke, JD);
+
+    assert_eq!(util::round_upto_digits(pos.asc.to_degrees(), 3), 158.559);
+    assert_eq!(util::round_upto_digits(pos.dec.to_degrees(), 3), 19.158);
+    assert_eq!(util::round_upto_digits(pos.dist, 4), 0.8243);
+
+    // elements given at another epoch describe the same orbit
+    let mut shifted = encke;
+    shifted.epoch = JD;
+    shifted.M = encke.mn_motion() * (JD - T);
+    let pos2 = orbit::position(&shifted, JD);
+    assert_eq!(util::round_upto_digits(pos2.asc, 8), util::round_upto_digits(pos.asc, 8));
+
+}
+
+#[test]
+fn determine_gauss() {
+
+    let ceres = orbit::Elements {
+        a: 2.7676569,
+        e: 0.0775571,
+        i: 10.58862_f64.to_radians(),
+        sigma: 80.28698_f64.to_radians(),
+        w: 73.73161_f64.to_radians(),
+        M: 162.68631_f64.to_radians(),
+        epoch: 2459000.5
+    };
+
+    let observe = |JD: f64| {
+        let pos = orbit::position(&ceres, JD);
+        orbit::Observation { JD, asc: pos.asc, dec: pos.dec }
+    };
+    let (obs1, obs2, obs3) = (observe(2459000.5 - 15.0), observe(2459000.5), observe(2459000.5 + 12.0));
+
+    let elements = orbit::determine_gauss(&obs1, &obs2, &obs3, None).unwrap();
+    assert_eq!(elements.epoch, obs2.JD);
+    assert_eq!(util::round_upto_digits(elements.a, 5), 2.76766);
+    assert_eq!(util::round_upto_digits(elements.e, 5), 0.07756);
+    assert_eq!(util::round_upto_digits(elements.i.to_degrees(), 4), 10.5886);
+    assert_eq!(util::round_upto_digits(elements.sigma.to_degrees(), 4), 80.287);
+    assert_eq!(util::round_upto_digits(elements.M.to_degrees(), 3), 162.686);
+
+    // reducing geocentric observations to an observer on the
+    // surface shifts the orbit only a little
+    let observer = coords::GeographPoint { long: 0.0, lat: 45_f64.to_radians() };
+    let topocent = orbit::determine_gauss(&obs1, &obs2, &obs3, Some((&observer, 0.0))).unwrap();
+    assert!((topocent.a - elements.a).abs() < 0.001);
+
+    assert_eq!(orbit::determine_gauss(&obs2, &obs1, &obs3, None).err(), Some(Error::NotPositive(obs1.JD - obs2.JD)));
+
+    // a body seen in the same direction three times
+    let fixed = |JD: f64| orbit::Observation { JD, asc: obs2.asc, dec: obs2.dec };
+    let (fix1, fix2, fix3) = (fixed(obs1.JD), fixed(obs2.JD), fixed(obs3.JD));
+    assert_eq!(orbit::determine_gauss(&fix1, &fix2, &fix3, None).err(), Some(Error::NoSolution));
+
+}
@@ -43,10 +43,80 @@ pub fn topocent_eq_coords() {
         angle::deg_frm_hms(1, 40, 45.0).to_radians()
     );
 
-    let (h, m1, s1) = angle::hms_frm_deg(topo_eq_point.asc.to_degrees());
+    let angle::Hms { hour: h, min: m1, sec: s1 } = angle::Rad(topo_eq_point.asc).into();
     assert_eq!((h, m1, util::round_upto_digits(s1, 2)), (22, 38, 8.54));
 
-    let (d, m2, s2) = angle::dms_frm_deg(topo_eq_point.dec.to_degrees());
+    let angle::Dms { deg: d, min: m2, sec: s2 } = angle::Rad(topo_eq_point.dec).into();
     assert_eq!((d, m2, util::round_upto_digits(s2, 1)), (-15, -46, -30.0));
 
 }
+
+#[test]
+pub fn topocentric() {
+
+    // Mars from Palomar, as in topocent_eq_coords(), from the distance
+    // for the parallax of 23.592"
+    let observer = parallax::GeodeticPoint {
+        long: angle::deg_frm_hms(7, 47, 27.0).to_radians(),
+        lat: 33.356111_f64.to_radians(),
+        height: 1706.0,
+        ellipsoid: parallax::Ellipsoid::IAU1976
+    };
+    let eq_point = coords::EqPoint {
+        asc: 339.530208_f64.to_radians(),
+        dec: -15.771083_f64.to_radians()
+    };
+    let green_sidr = angle::deg_frm_hms(1, 40, 45.0).to_radians();
+    let topo = parallax::topocentric(&eq_point, 0.37276, &observer, green_sidr);
+
+    let angle::Hms { hour: h, min: m1, sec: s1 } = angle::Rad(topo.asc).into();
+    assert_eq!((h, m1, util::round_upto_digits(s1, 2)), (22, 38, 8.54));
+    let angle::Dms { deg: d, min: m2, sec: s2 } = angle::Rad(topo.dec).into();
+    assert_eq!((d, m2, util::round_upto_digits(s2, 1)), (-15, -46, -30.0));
+
+    // the same in ecliptic coordinates
+    let oblq = 23.44_f64.to_radians();
+    let ecl_point = coords::EclPoint {
+        long: coords::ecl_long_frm_eq(eq_point.asc, eq_point.dec, oblq),
+        lat: coords::ecl_lat_frm_eq(eq_point.asc, eq_point.dec, oblq)
+    };
+    let topo_ecl = parallax::topocentric_ecl(&ecl_point, 0.37276, &observer, green_sidr, oblq);
+    assert!((angle::limit_to_two_PI(coords::asc_frm_ecl(topo_ecl.long, topo_ecl.lat, oblq)) - topo.asc).abs() < 1e-10);
+    assert!((coords::dec_frm_ecl(topo_ecl.long, topo_ecl.lat, oblq) - topo.dec).abs() < 1e-10);
+
+}
+
+#[test]
+pub fn geodetic() {
+
+    // Palomar, as in Meeus's example 11.a
+    let palomar = parallax::GeodeticPoint {
+        long: 0.0,
+        lat: 33.356111_f64.to_radians(),
+        height: 1706.0,
+        ellipsoid: parallax::Ellipsoid::IAU1976
+    };
+    let (rho_sin, rho_cos) = parallax::rho_sin_cos_phi(&palomar);
+    assert_eq!(util::round_upto_digits(rho_sin, 6), 0.546861);
+    assert_eq!(util::round_upto_digits(rho_cos, 6), 0.836339);
+
+    let ellipsoids = [parallax::Ellipsoid::IAU1976, parallax::Ellipsoid::WGS84, planet::earth::Ellipsoid::CLARKE1866];
+    for ellipsoid in ellipsoids.iter() {
+        for &(lat, height) in [(0.0, 0.0), (33.356111, 1706.0), (-89.9, 2835.0), (60.0, -400.0)].iter() {
+            let observer = parallax::GeodeticPoint {
+                long: 1.0,
+                lat: f64::to_radians(lat),
+                height,
+                ellipsoid: *ellipsoid
+            };
+            let (rho_sin, rho_cos) = parallax::rho_sin_cos_phi(&observer);
+            let back = parallax::geodetic_frm_rho_sin_cos_phi(rho_sin, rho_cos, 1.0, *ellipsoid);
+            assert!((back.lat - observer.lat).abs() < 1e-12);
+            assert!((back.height - height).abs() < 1e-4);
+        }
+    }
+
+    let geocent = parallax::geocent_lat(&parallax::GeodeticPoint { height: 0.0, ..palomar });
+    assert_eq!(util::round_upto_digits((palomar.lat - geocent).to_degrees() * 3600.0, 0), 635.0);
+
+}
@@ -0,0 +1,124 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+use astro::planet::phenomena::EventType;
+
+fn event(planet: planet::Planet, event_type: EventType, JD: f64) -> planet::phenomena::Event {
+    planet::phenomena::event(&planet, &event_type, JD).unwrap()
+}
+
+#[test]
+fn conjunctions() {
+
+    // the transit of Mercury of 1993 November 6
+    let e = event(planet::Planet::Mercury, EventType::InferiorConjunction, 2449290.5);
+    assert_eq!(util::round_upto_digits(e.JD, 2), 2449297.65);
+    assert!(e.elong.to_degrees() < 0.3);
+
+    // 2020 June 3, 17h43m UT
+    let e = event(planet::Planet::Venus, EventType::InferiorConjunction, 2459000.5);
+    assert_eq!(util::round_upto_digits(e.JD, 3), 2459004.239);
+
+    // 2003 August 28, 17h56m UT
+    let e = event(planet::Planet::Mars, EventType::Opposition, 2452880.5);
+    assert_eq!(util::round_upto_digits(e.JD, 2), 2452880.25);
+
+    // 2000 July 1
+    let e = event(planet::Planet::Mars, EventType::Conjunction, 2451700.5);
+    assert_eq!(util::round_upto_digits(e.JD, 0), 2451727.0);
+
+}
+
+#[test]
+fn elongations_and_stations() {
+
+    // 2020 March 24, 46.1 degrees
+    let e = event(planet::Planet::Venus, EventType::GreatestEastElongation, 2458930.5);
+    assert_eq!(util::round_upto_digits(e.JD, 1), 2458933.4);
+    assert_eq!(util::round_upto_digits(e.elong.to_degrees(), 1), 46.1);
+
+    // 1993 November 22, 19.8 degrees
+    let e = event(planet::Planet::Mercury, EventType::GreatestWestElongation, 2449290.5);
+    assert_eq!(util::round_upto_digits(e.JD, 0), 2449314.0);
+    assert_eq!(util::round_upto_digits(e.elong.to_degrees(), 1), 19.8);
+
+    // the retrograde loop of Mars in 2020, from September 9 to
+    // November 14
+    let e = event(planet::Planet::Mars, EventType::RetrogradeStation, 2459130.5);
+    assert_eq!(util::round_upto_digits(e.JD, 1), 2459102.4);
+    let e = event(planet::Planet::Mars, EventType::DirectStation, 2459130.5);
+    assert_eq!(util::round_upto_digits(e.JD, 1), 2459167.5);
+
+    assert_eq!(planet::phenomena::event(&planet::Planet::Venus, &EventType::Opposition, 2459130.5).err(), Some(Error::InvalidBody));
+    assert_eq!(planet::phenomena::event(&planet::Planet::Earth, &EventType::Conjunction, 2459130.5).err(), Some(Error::InvalidBody));
+
+}
+
+#[test]
+fn conjunctions_of_two_bodies() {
+
+    // the great conjunction of Jupiter and Saturn of 2020 December 21,
+    // at about 18h UT, with Saturn 6.1' to the north
+    let found = planet::phenomena::conjunctions (
+        &Body::Planet(planet::Planet::Jupiter), &Body::Planet(planet::Planet::Saturn),
+        2459180.5, 2459230.5, 1_f64.to_radians()
+    ).unwrap();
+    assert_eq!(found.len(), 1);
+    let c = found[0];
+    assert_eq!(util::round_upto_digits(c.JD, 1), 2459205.3);
+    assert_eq!(util::round_upto_digits(c.sepr.to_degrees() * 60.0, 1), 6.1);
+    assert!(c.pos_angl.to_degrees() > 340.0);
+
+    // the Moon passes Regulus once a sidereal month
+    let regulus = star::MeanPlace {
+        asc:         152.0929622_f64.to_radians(),
+        dec:         11.9672089_f64.to_radians(),
+        pmotion_asc: 0.0,
+        pmotion_dec: 0.0,
+        parallax:    0.0,
+        rad_vel:     0.0
+    };
+    let found = planet::phenomena::conjunctions (
+        &Body::Moon, &Body::Star(regulus), 2459215.5, 2459400.5, 10_f64.to_radians()
+    ).unwrap();
+    assert_eq!(found.len(), 7);
+    for pair in found.windows(2) {
+        assert_eq!(util::round_upto_digits(pair[1].JD - pair[0].JD, 0), 27.0);
+    }
+    for c in &found {
+        assert!(c.sepr.to_degrees() > 4.0 && c.sepr.to_degrees() < 5.0);
+    }
+
+    // the Earth isn't seen from the Earth's center
+    assert_eq!(
+        planet::phenomena::conjunctions(
+            &Body::Moon, &Body::Planet(planet::Planet::Earth), 2459215.5, 2459216.5, 1.0
+        ),
+        Err(Error::InvalidBody)
+    );
+
+}
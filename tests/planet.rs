@@ -22,7 +22,6 @@ THE SOFTWARE.
 
 #![allow(non_snake_case)]
 
-#[macro_use]
 extern crate astro;
 use astro::*;
 
@@ -43,10 +42,9 @@ fn heliocent_coords() {
 }
 
 #[test]
-#[allow(unused_variables)]
-fn geocent_geomet_ecl_coords() {
+fn geocent_geomet_ecl_point() {
 
-    let (L, B, mut R, mut t) = planet::geocent_geomet_ecl_coords(
+    let (_, mut R, mut t) = planet::geocent_geomet_ecl_point(
         88.35704_f64.to_radians(),
         0.00014_f64.to_radians(),
         0.983824,
@@ -62,13 +60,13 @@ fn geocent_geomet_ecl_coords() {
 }
 
 #[test]
-fn ecl_coords_to_FK5() {
+fn ecl_point_to_FK5() {
 
-    let (FK5_long, FK5_lat) = planet::ecl_coords_to_FK5(
-        2448976.5,
-        313.07689_f64.to_radians(),
-        -2.08489_f64.to_radians()
-    );
+    let point = coords::EclPoint {
+        long: 313.07689_f64.to_radians(),
+        lat: -2.08489_f64.to_radians()
+    };
+    let coords::EclPoint { long: FK5_long, lat: FK5_lat } = planet::ecl_point_to_FK5(2448976.5, &point);
 
     assert_eq!(
         util::round_upto_digits(FK5_long.to_degrees(), 5),
@@ -95,3 +93,97 @@ fn geocent_apprnt_ecl_coords() {
     assert_eq!((L, B, R), (313.08, -2.08, 0.9109));
     
 }
+
+#[test]
+fn apprnt_mag() {
+
+    // Meeus's example 41.a, of Venus on 1992 December 20
+    let (i, delta, r) = (72.96_f64.to_radians(), 0.910947, 0.724604);
+
+    let mag = planet::apprnt_mag_muller(&planet::Planet::Venus, i, delta, r).unwrap();
+    assert_eq!(util::round_upto_digits(mag, 1), -3.8);
+
+    let mag = planet::apprnt_mag_84(&planet::Planet::Venus, i, delta, r).unwrap();
+    assert_eq!(util::round_upto_digits(mag, 1), -4.2);
+
+}
+
+#[test]
+fn light_time_iter() {
+
+    // a body receding at 0.01 AU per day, 5 AU away
+    let dist = |tau: f64| 5.0 + 0.01*tau;
+    let exact = 0.0057755183 * 5.0 / (1.0 - 0.0057755183 * 0.01);
+
+    let tau = planet::light_time_iter(
+        dist, planet::LIGHT_TIME_TOL, planet::LIGHT_TIME_MAX_ITERS
+    );
+    assert_eq!(util::round_upto_digits(tau, 9), util::round_upto_digits(exact, 9));
+
+    // stopped after the first iteration
+    let tau = planet::light_time_iter(dist, 0.0, 1);
+    assert_eq!(tau, 0.0057755183 * 5.0);
+
+}
+
+#[test]
+fn phase_and_mag() {
+
+    // Venus on 1992 December 20, from the Meeus book
+    let JD = 2448976.5;
+    let i = planet::geocent_phase_angl(&planet::Planet::Venus, JD);
+    assert_eq!(util::round_upto_digits(i.to_degrees(), 2), 72.96);
+    assert_eq!(util::round_upto_digits(planet::geocent_illum_frac(&planet::Planet::Venus, JD), 3), 0.647);
+
+    let mag = |planet, formula| util::round_upto_digits (
+        planet::apprnt_mag(&planet, JD, &formula).unwrap(), 1
+    );
+    assert_eq!(mag(planet::Planet::Venus, planet::MagFormula::Muller), -3.8);
+    assert_eq!(mag(planet::Planet::Venus, planet::MagFormula::Almanac84), -4.2);
+    assert_eq!(mag(planet::Planet::Saturn, planet::MagFormula::Muller), 0.9);
+    assert_eq!(mag(planet::Planet::Jupiter, planet::MagFormula::Almanac84), -1.9);
+    assert_eq!(planet::apprnt_mag(&planet::Planet::Earth, JD, &planet::MagFormula::Muller), Err(Error::InvalidBody));
+
+}
+
+#[test]
+fn heliocent_coords_many() {
+
+    let JD: Vec<f64> = (0..600).map(|i| 2448976.5 + (i as f64)*3.7).collect();
+    let many = planet::heliocent_coords_many(&planet::Planet::Venus, &JD);
+
+    assert_eq!(many.len(), JD.len());
+    for (&JD, &coords) in JD.iter().zip(many.iter()) {
+        assert_eq!(coords, planet::heliocent_coords(&planet::Planet::Venus, JD));
+    }
+
+    assert!(planet::heliocent_coords_many(&planet::Planet::Mars, &[]).is_empty());
+
+}
+
+#[test]
+fn apsis() {
+
+    let (JD, _) = planet::apsis(&planet::Planet::Venus, 1978.79, &planet::Apsis::Perihelion);
+    assert_eq!(util::round_upto_digits(JD, 3), 2443873.704);
+
+    let (JD, _) = planet::apsis(&planet::Planet::Mars, 2032.5, &planet::Apsis::Aphelion);
+    assert_eq!(util::round_upto_digits(JD, 3), 2463530.456);
+
+    let date = |JD| {
+        let (y, m, d) = time::date_frm_julian_day(JD).unwrap();
+        (y, m, d.floor() as u8)
+    };
+
+    let (JD, rad_vec) = planet::apsis(&planet::Planet::Jupiter, 2011.2, &planet::Apsis::Perihelion);
+    assert_eq!(date(JD), (2011, 3, 17));
+    assert_eq!(util::round_upto_digits(rad_vec, 3), 4.948);
+
+    let (JD, _) = planet::apsis(&planet::Planet::Saturn, 2003.5, &planet::Apsis::Perihelion);
+    assert_eq!(date(JD), (2003, 7, 26));
+
+    let (JD, rad_vec) = planet::apsis(&planet::Planet::Saturn, 2018.0, &planet::Apsis::Aphelion);
+    assert_eq!(date(JD), (2018, 4, 17));
+    assert_eq!(util::round_upto_digits(rad_vec, 3), 10.066);
+
+}
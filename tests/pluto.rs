@@ -0,0 +1,88 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+
+#[test]
+fn heliocent_pos() {
+
+    // Meeus's example 37.a, on 1992 October 13, 0h TD
+    let (l, b, r) = pluto::try_heliocent_pos(2448908.5).unwrap();
+    assert_eq!(util::round_upto_digits(l.to_degrees(), 5), 232.74071);
+    assert_eq!(util::round_upto_digits(b.to_degrees(), 5), 14.58782);
+    assert_eq!(util::round_upto_digits(r, 6), 29.711111);
+
+    assert_eq!(pluto::try_heliocent_pos(2400000.5), Err(Error::DateOutOfRange(2400000.5)));
+    assert_eq!(pluto::try_heliocent_pos(f64::NAN), Err(Error::NotFinite));
+
+}
+
+#[test]
+fn geocent_coords() {
+
+    // Meeus's example 37.a
+    let (eq_point, dist) = pluto::geocent_astrometric_coords(2448908.5, false).unwrap();
+    let angle::Hms { hour: h, min: m, sec: s } = angle::Rad(eq_point.asc).into();
+    assert_eq!((h, m, util::round_upto_digits(s, 1)), (15, 31, 43.8));
+    let angle::Dms { deg: d, min: m, sec: s } = angle::Rad(eq_point.dec).into();
+    assert_eq!((d, m, util::round_upto_digits(s, 0)), (-4, -27, -29.0));
+    assert_eq!(util::round_upto_digits(dist, 5), 30.52875);
+
+    // the apparent place differs by the precession since J2000.0,
+    // the nutation and the aberration
+    let (apprnt, _) = pluto::geocent_apprnt_eq_coords(2448908.5, false).unwrap();
+    let sepr = angle::anglr_sepr(eq_point.asc, eq_point.dec, apprnt.asc, apprnt.dec);
+    assert!(sepr.to_degrees() > 0.08 && sepr.to_degrees() < 0.12);
+
+    assert!(pluto::geocent_apprnt_eq_coords(2300000.5, false).is_err());
+
+}
+
+#[test]
+fn extrapolation() {
+
+    // continuous at the ends of the range
+    for &JD in [pluto::JD_START, pluto::JD_END].iter() {
+        let inside = pluto::heliocent_rect_coords_J2000(JD, false).unwrap();
+        let outside = pluto::heliocent_rect_coords_J2000(JD + 1e-6, true).unwrap();
+        let d = (inside.0 - outside.0).hypot(inside.1 - outside.1).hypot(inside.2 - outside.2);
+        assert!(d < 1e-5);
+    }
+
+    // a decade beyond the range, Pluto stays close to the mean orbit
+    let (x, y, z) = pluto::heliocent_rect_coords_J2000(pluto::JD_END + 3652.5, true).unwrap();
+    let r = (x*x + y*y + z*z).sqrt();
+    let (a, e, _, _, _) = pluto::mn_orb_elements_2000AD();
+    assert!(r > a*(1.0 - e) && r < a*(1.0 + e));
+
+    assert!(pluto::geocent_astrometric_coords(pluto::JD_END + 3652.5, true).is_ok());
+    assert_eq!(
+        pluto::heliocent_rect_coords_J2000(pluto::JD_END + 1.0, false),
+        Err(Error::DateOutOfRange(pluto::JD_END + 1.0))
+    );
+
+}
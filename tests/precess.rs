@@ -40,11 +40,11 @@ fn annual_precess() {
         time::julian_day(&d)
     );
 
-    let (a, b, c) = angle::hms_frm_deg(new_asc.to_degrees());
+    let angle::Hms { hour: a, min: b, sec: c } = angle::Rad(new_asc).into();
     assert_eq!((a, b), (0, 0));
     assert_eq!(util::round_upto_digits(c, 2), util::round_upto_digits(3.208, 2));
 
-    let (d, e, f) = angle::dms_frm_deg(new_dec.to_degrees());
+    let angle::Dms { deg: d, min: e, sec: f } = angle::Rad(new_dec).into();
     assert_eq!((d, e), (0, 0));
     assert_eq!(util::round_upto_digits(f, 2), -17.71);
 
@@ -65,6 +65,14 @@ fn precess_eq_coords() {
         (41.547214, 49.348483)
     );
 
+    // and back, from an epoch other than J2000.0, where the terms in T
+    // of the angles don't vanish
+    let (old_asc, old_dec) = precess::precess_eq_coords(
+        new_asc, new_dec, 2462088.69, 2451545.0
+    );
+    assert!((old_asc.to_degrees() - 41.054063).abs() < 1e-7);
+    assert!((old_dec.to_degrees() - 49.22775).abs() < 1e-7);
+
 }
 
 #[test]
@@ -98,3 +106,93 @@ fn precess_ecl_coords() {
     assert_eq!((util::round_upto_digits(new_asc.to_degrees(), 3),
                 util::round_upto_digits(new_dec.to_degrees(), 3)), (118.704, 1.615));
 }
+
+#[test]
+fn matrix() {
+
+    // agrees with the IAU 1976 precession of the example above, to
+    // within the difference between the two models
+    let m = precess::matrix(2451545.0, 2462088.69);
+    let (asc, dec) = (41.054063_f64.to_radians(), 49.22775_f64.to_radians());
+    let v = [dec.cos()*asc.cos(), dec.cos()*asc.sin(), dec.sin()];
+    let w: Vec<f64> = m.iter().map(|r| r[0]*v[0] + r[1]*v[1] + r[2]*v[2]).collect();
+
+    let new_asc = w[1].atan2(w[0]).to_degrees();
+    let new_dec = w[2].asin().to_degrees();
+    assert_eq!(
+        (util::round_upto_digits(new_asc, 4), util::round_upto_digits(new_dec, 4)),
+        (41.5472, 49.3485)
+    );
+
+    // the identity between equal epochs
+    let m = precess::matrix(2462088.69, 2462088.69);
+    for (i, row) in m.iter().enumerate() {
+        for (j, &m_ij) in row.iter().enumerate() {
+            let e = if i == j { 1.0 } else { 0.0 };
+            assert!((m_ij - e).abs() < 1e-15);
+        }
+    }
+
+}
+
+#[test]
+fn bias() {
+
+    // the mean equinox of J2000.0 lies at -14.6 mas in the ICRS
+    let (asc, dec) = precess::mn_eq_frm_icrs(0.0, 0.0, 2451545.0);
+    assert_eq!(util::round_upto_digits(asc.to_degrees() * 3600000.0, 1), 14.6);
+    assert_eq!(util::round_upto_digits(dec.to_degrees() * 3600000.0, 1), -16.6);
+
+    let b = precess::bias_mat();
+    let pb = precess::bias_precess_mat(2451545.0);
+    for i in 0..3 {
+        for j in 0..3 {
+            assert!((b[i][j] - pb[i][j]).abs() < 1e-9);
+        }
+    }
+
+}
+
+#[test]
+fn icrs_round_trip() {
+
+    let JD = 2460000.5;
+    let (icrs_asc, icrs_dec) = (1.2, -0.4);
+
+    let (asc, dec) = precess::mn_eq_frm_icrs(icrs_asc, icrs_dec, JD);
+    let (a, d) = precess::icrs_frm_mn_eq(asc, dec, JD);
+    assert_eq!(
+        (util::round_upto_digits(a, 12), util::round_upto_digits(d, 12)),
+        (icrs_asc, icrs_dec)
+    );
+
+    let (asc, dec) = precess::true_eq_frm_icrs(icrs_asc, icrs_dec, JD);
+    let (a, d) = precess::icrs_frm_true_eq(asc, dec, JD);
+    assert_eq!(
+        (util::round_upto_digits(a, 12), util::round_upto_digits(d, 12)),
+        (icrs_asc, icrs_dec)
+    );
+
+}
+
+#[test]
+fn fk4_fk5() {
+
+    // Besselian epoch 1960.0
+    let epoch = 2415020.31352 + 60.0*365.242198781;
+
+    let (asc, dec) = precess::fk5_frm_fk4(1.2, -0.3, epoch);
+    assert_eq!(util::round_upto_digits(asc, 11), 1.20978122290);
+    assert_eq!(util::round_upto_digits(dec, 11), -0.29826111711);
+
+    let (asc, dec, pmotion_asc, pmotion_dec) = precess::fk4_frm_fk5(1.2, -0.3, epoch);
+    assert_eq!(util::round_upto_digits(asc, 11), 1.19022218058);
+    assert_eq!(util::round_upto_digits(dec, 11), -0.30178317646);
+    assert_eq!(util::round_upto_digits(pmotion_asc * 1e8, 6), -1.783087);
+    assert_eq!(util::round_upto_digits(pmotion_dec * 1e8, 6), 0.719606);
+
+    let (asc, dec) = precess::fk5_frm_fk4(1.2, -0.3, epoch);
+    let (a, d, _, _) = precess::fk4_frm_fk5(asc, dec, epoch);
+    assert!((a - 1.2).abs() < 1e-9 && (d + 0.3).abs() < 1e-9);
+
+}
@@ -0,0 +1,85 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+
+#[test]
+fn z_frm_rad_vel() {
+
+    assert_eq!(redshift::z_frm_rad_vel(0.0), 0.0);
+    assert_eq!(util::round_upto_digits(redshift::z_frm_rad_vel(-100.0), 10), -0.0003335085);
+
+    // relativistic at large velocities
+    let z = redshift::z_frm_rad_vel(0.6 * 299792.458);
+    assert_eq!(util::round_upto_digits(z, 10), 1.0);
+    assert_eq!(util::round_upto_digits(redshift::rad_vel_frm_z(z), 6), 0.6 * 299792.458);
+
+    let z = redshift::combined_z(0.01, -0.01 / 1.01);
+    assert_eq!(util::round_upto_digits(z, 12), 0.0);
+
+}
+
+#[test]
+fn grav_z() {
+
+    let z = redshift::solar_grav_z(1.0);
+    assert_eq!(util::round_upto_digits(redshift::rad_vel_frm_z(z), 3), 0.633);
+
+    assert_eq!(redshift::grav_z(consts::SUN_GRAV_PARAM, 1e9, 1e9), 0.0);
+
+}
+
+#[test]
+fn bary_corr() {
+
+    // at the pole, the observer's motion is that of the center of the
+    // Earth, whose rate of approach to the star follows from the
+    // change of the light-time across the solar system
+    let pole = observer::Observer::try_new(0.0, 90.0_f64.to_radians(), 0.0).unwrap();
+    let equator = observer::Observer::try_new(0.0, 0.0, 0.0).unwrap();
+
+    for &(asc, dec) in [(26.0_f64, -15.9_f64), (101.3, -16.7), (280.0, 38.8)].iter() {
+        let (asc, dec) = (asc.to_radians(), dec.to_radians());
+
+        for &JD in [2458000.3, 2458091.6, 2458182.9].iter() {
+            let light_time = |t: f64| exoplanet::topocent_bjd_frm_jd(t, asc, dec, &pole) - t;
+            let rate = |h: f64| (light_time(JD + h) - light_time(JD - h)) / (2.0 * h);
+            let rad_vel = (4.0*rate(0.5) - rate(1.0)) / 3.0 * 299792.458;
+
+            // less the slowing of the clock, of about 4.4 m/s
+            let corr = redshift::bary_rad_vel_corr(JD, asc, dec, &pole);
+            assert!((corr - rad_vel - 0.0044).abs() < 0.0005);
+
+            // the rotation of the Earth adds upto 0.465 km/s
+            let diurnal = redshift::bary_rad_vel_corr(JD, asc, dec, &equator) - corr;
+            assert!(diurnal.abs() < 0.465);
+        }
+    }
+
+    let z = redshift::bary_z_corr(2458000.3, 0.0, 0.0, &pole);
+    assert!((redshift::bary_rad_vel_corr(2458000.3, 0.0, 0.0, &pole) - z*299792.458).abs() < 1e-12);
+
+}
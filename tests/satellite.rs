@@ -0,0 +1,146 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+use astro::*;
+
+// Geocentric position of a satellite, for a Julian day
+type Position = Box<dyn Fn(f64) -> (f64, f64, f64)>;
+
+fn circular_orbit(JD0: f64) -> (f64, f64, Position) {
+
+    let r = 6778.0;
+    let period = 2.0*std::f64::consts::PI * (r*r*r / 398600.4418_f64).sqrt() / 86400.0;
+
+    // orbit in the plane containing the Sun, starting below the Sun
+    let (x, y, z) = satellite::sun_geocent_eq_rect_coords(JD0);
+    let d = (x*x + y*y + z*z).sqrt();
+    let u = (x/d, y/d, z/d);
+    let w = (u.1, -u.0, 0.0);
+    let w_n = (w.0*w.0 + w.1*w.1).sqrt();
+    let w = (w.0/w_n, w.1/w_n, 0.0);
+
+    let pos = move |JD: f64| {
+        let theta = 2.0*std::f64::consts::PI * (JD - JD0) / period;
+        (
+            r*(theta.cos()*u.0 + theta.sin()*w.0),
+            r*(theta.cos()*u.1 + theta.sin()*w.1),
+            r*(theta.cos()*u.2 + theta.sin()*w.2)
+        )
+    };
+
+    (r, period, Box::new(pos))
+
+}
+
+#[test]
+fn shadow_transitions() {
+
+    let JD0 = 2457754.5;
+    let (r, period, pos) = circular_orbit(JD0);
+
+    let transitions = satellite::shadow_transitions(
+        &pos, JD0, JD0 + period, 1.0/1440.0, &satellite::ShadowModel::Cylindrical
+    ).unwrap();
+    assert_eq!(transitions.len(), 2);
+    assert_eq!(transitions[0].to, satellite::Shadow::Umbra);
+    assert_eq!(transitions[1].to, satellite::Shadow::Sunlit);
+
+    let duration = (transitions[1].JD - transitions[0].JD) * 1440.0;
+    let expected = 2.0*(6378.137/r).asin() / (2.0*std::f64::consts::PI) * period * 1440.0;
+    assert_eq!(util::round_upto_digits(duration, 1), util::round_upto_digits(expected, 1));
+
+    let transitions = satellite::shadow_transitions(
+        &pos, JD0, JD0 + period, 1.0/1440.0, &satellite::ShadowModel::Conical
+    ).unwrap();
+    let states: Vec<satellite::Shadow> = transitions.iter().map(|t| t.to).collect();
+    assert_eq!(states, vec![
+        satellite::Shadow::Penumbra,
+        satellite::Shadow::Umbra,
+        satellite::Shadow::Penumbra,
+        satellite::Shadow::Sunlit
+    ]);
+
+    assert_eq!(
+        satellite::shadow_transitions(&pos, JD0, JD0 + period, 0.0, &satellite::ShadowModel::Conical).err(),
+        Some(error::Error::NotPositive(0.0))
+    );
+
+}
+
+#[test]
+fn sun_visible_frac() {
+
+    let JD0 = 2457754.5;
+    let (r, _, _) = circular_orbit(JD0);
+    let sun = satellite::sun_geocent_eq_rect_coords(JD0);
+    let d = (sun.0*sun.0 + sun.1*sun.1 + sun.2*sun.2).sqrt();
+
+    let day = (r*sun.0/d, r*sun.1/d, r*sun.2/d);
+    let night = (-day.0, -day.1, -day.2);
+
+    assert_eq!(satellite::sun_visible_frac(day, sun), 1.0);
+    assert_eq!(satellite::sun_visible_frac(night, sun), 0.0);
+    assert_eq!(
+        satellite::shadow(night, sun, &satellite::ShadowModel::Conical),
+        satellite::Shadow::Umbra
+    );
+
+}
+
+#[test]
+fn topocent_view() {
+
+    let JD = 2457754.5;
+    let observer = observer::Observer::try_new(0.0, 0.0, 0.0).unwrap();
+
+    // a satellite 1000 kilometers above the observer's zenith, moving
+    // north, and the same one beyond the horizon
+    let green_sidr = time::mn_sidr(JD);
+    let r = 6378.137 + 1000.0;
+    let zenith = (r*green_sidr.cos(), r*green_sidr.sin(), 0.0);
+    let vel = (0.0, 0.0, 7.35);
+
+    let view = satellite::topocent_view(
+        zenith, vel, JD, &observer, &satellite::ShadowModel::Conical
+    );
+    assert_eq!(util::round_upto_digits(view.hz_point.alt.to_degrees(), 6), 90.0);
+    assert_eq!(util::round_upto_digits(view.range, 3), 1000.0);
+    assert!(view.range_rate.abs() < 1e-9);
+
+    let behind = (-zenith.0, -zenith.1, 0.0);
+    let view = satellite::topocent_view(
+        behind, vel, JD, &observer, &satellite::ShadowModel::Conical
+    );
+    assert!(view.hz_point.alt < 0.0);
+    assert_eq!(util::round_upto_digits(view.range, 3), r + 6378.137);
+
+    // climbing at 2 kilometers per second over the observer
+    let climbing = (2.0*green_sidr.cos(), 2.0*green_sidr.sin(), 7.35);
+    let view = satellite::topocent_view(
+        zenith, climbing, JD, &observer, &satellite::ShadowModel::Conical
+    );
+    assert_eq!(util::round_upto_digits(view.range_rate, 9), 2.0);
+
+}
@@ -44,18 +44,51 @@ fn ring_elements() {
     assert_eq!(elements.P, 6.741);
     assert_eq!(elements.deltaU, 4.198);
 
-    let (d_a, m_a, s_a) = angle::dms_frm_deg(elements.a.to_degrees());
+    let angle::Dms { deg: d_a, min: m_a, sec: s_a } = angle::Rad(elements.a).into();
     assert_eq!((d_a, m_a), (0, 0));
     assert_eq!(util::round_upto_digits(s_a, 2), 35.87);
 
-    let (d_b, m_b, s_b) = angle::dms_frm_deg(elements.b.to_degrees());
+    let angle::Dms { deg: d_b, min: m_b, sec: s_b } = angle::Rad(elements.b).into();
     assert_eq!((d_b, m_b), (0, 0));
     assert_eq!(util::round_upto_digits(s_b, 2), 10.15);
 
 }
 
+#[test]
+fn ring_ephemeris() {
+
+    // Meeus's example 45.a, with the nutation and obliquity computed
+    let eph = planet::saturn::ring_ephemeris(2448972.50068);
+
+    assert_eq!(util::round_upto_digits(eph.B.to_degrees(), 3), 16.442);
+    assert_eq!(util::round_upto_digits(eph.B1.to_degrees(), 3), 14.679);
+    assert_eq!(util::round_upto_digits(angle::limit_to_360(eph.P.to_degrees()), 3), 6.741);
+    assert_eq!(util::round_upto_digits(eph.a.to_degrees()*3600.0, 2), 35.87);
+    assert_eq!(util::round_upto_digits(eph.b.to_degrees()*3600.0, 2), 10.15);
+
+}
+
+#[test]
+fn apprnt_mag() {
+
+    // the magnitude grows by 0.044 per degree of deltaU
+    let (delta, r, B) = (9.0, 9.9, 16.4_f64.to_radians());
+    let delU = 4.0_f64.to_radians();
+
+    let mag = planet::saturn::apprnt_mag_muller(delta, r, delU, B);
+    let mag_0 = planet::saturn::apprnt_mag_muller(delta, r, 0.0, B);
+    assert!((mag - mag_0 - 0.176).abs() < 1e-9);
+
+    let mag = planet::saturn::apprnt_mag_84(delta, r, delU, B);
+    let mag_0 = planet::saturn::apprnt_mag_84(delta, r, 0.0, B);
+    assert!((mag - mag_0 - 0.176).abs() < 1e-9);
+
+}
+
 #[test]
 #[allow(unused_variables)]
+// the published offset of Enceladus, 0.318, happens to be near 1/PI
+#[allow(clippy::approx_constant)]
 fn moons() {
 
     let data = [
@@ -79,3 +112,92 @@ fn moons() {
     }
 
 }
+
+#[test]
+fn moon_mags() {
+
+    // opposition of 2009 March 8
+    let JD = 2454898.5;
+
+    let titan = planet::saturn::moon::apprnt_mag(JD, &planet::saturn::moon::Moon::Titan);
+    assert_eq!(util::round_upto_digits(titan, 1), 8.2);
+
+    // Iapetus is brightest near western elongation
+    let mut mags: Vec<(f64, f64)> = (0..80).map(|d| {
+        let t = JD + (d as f64);
+        (
+            planet::saturn::moon::apprnt_rect_coords(t, &planet::saturn::moon::Moon::Iapetus).0,
+            planet::saturn::moon::apprnt_mag(t, &planet::saturn::moon::Moon::Iapetus)
+        )
+    }).collect();
+    mags.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+    let (brightest, faintest) = (mags[0], mags[mags.len() - 1]);
+    assert!(brightest.0 > 50.0 && faintest.0 < -50.0);
+    assert!(faintest.1 - brightest.1 > 1.6);
+
+}
+
+#[test]
+fn ring_plane_crossings() {
+
+    let dates = |crossing, JD_start, JD_end| {
+        planet::saturn::ring::plane_crossings(crossing, JD_start, JD_end)
+            .iter()
+            .map(|&JD| {
+                let (y, m, d) = time::date_frm_julian_day(JD).unwrap();
+                (y, m, d.floor() as u8)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // the triple crossing of 1995-96, around Saturn's equinox of
+    // 1995 November
+    assert_eq!(
+        dates(planet::saturn::ring::Crossing::Earth, 2449700.5, 2450300.5),
+        vec![(1995, 5, 21), (1995, 8, 11), (1996, 2, 11)]
+    );
+    assert_eq!(
+        dates(planet::saturn::ring::Crossing::Sun, 2449700.5, 2450300.5),
+        vec![(1995, 11, 18)]
+    );
+
+    // the single crossing of 2009
+    assert_eq!(
+        dates(planet::saturn::ring::Crossing::Earth, 2454800.5, 2455200.5),
+        vec![(2009, 9, 4)]
+    );
+    assert_eq!(
+        dates(planet::saturn::ring::Crossing::Sun, 2454800.5, 2455200.5),
+        vec![(2009, 8, 10)]
+    );
+
+}
+
+#[test]
+fn moon_phenomena() {
+
+    use planet::saturn::moon::{self, Moon};
+    use planet::moon_phenomena::PhenomenonType;
+
+    let titan = moon::saturnicent_rect_coords(2454880.5, &Moon::Titan);
+    let dist = (titan.0*titan.0 + titan.1*titan.1 + titan.2*titan.2).sqrt() * 149597870.7;
+    assert!((dist - 1221870.0).abs() < 40000.0);
+
+    // Titan and it's shadow crossed Saturn's disk on 2009 February 24,
+    // near the equinox of Saturn, as imaged by the Hubble Space Telescope
+    let phenomena = moon::phenomena(&Moon::Titan, 2454880.5, 2454890.5, 0.01).unwrap();
+    for kind in [PhenomenonType::Transit, PhenomenonType::ShadowTransit].iter() {
+        let p = phenomena.iter().find(|p| p.kind == *kind).unwrap();
+        assert!(p.start > 2454886.5 && p.end < 2454887.5);
+
+        // the apparent position of the same theory is on the disk
+        if *kind == PhenomenonType::Transit {
+            let (X, Y, Z) = moon::apprnt_rect_coords((p.start + p.end) / 2.0, &Moon::Titan);
+            assert!(X*X + Y*Y < 1.0);
+            assert!(Z < 0.0);
+        }
+    }
+
+    assert!(moon::phenomena(&Moon::Titan, 2454880.5, 2454890.5, -0.01).is_err());
+
+}
@@ -0,0 +1,115 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+use astro::selenography::FEATURES;
+
+#[test]
+fn geocent_orientation() {
+
+    let (l, b, P) = selenography::geocent_orientation(2448724.5);
+
+    assert_eq!(util::round_upto_digits(l.to_degrees(), 2), -1.23);
+    assert_eq!(util::round_upto_digits(b.to_degrees(), 2), 4.2);
+    assert_eq!(util::round_upto_digits(P.to_degrees(), 1), 15.1);
+
+}
+
+#[test]
+fn disk_pos() {
+
+    let (l, b, P) = (0.1, -0.08, 0.3);
+
+    for feature in FEATURES.iter() {
+        let (east, north) = selenography::disk_pos_frm_selenographic(
+            feature.long, feature.lat, l, b, P
+        ).unwrap();
+        let (long, lat) = selenography::selenographic_frm_disk_pos(
+            east, north, l, b, P
+        ).unwrap();
+
+        assert!((long - feature.long).abs() < 1e-12);
+        assert!((lat - feature.lat).abs() < 1e-12);
+    }
+
+    // Mare Crisium lies towards the celestial west
+    let crisium = FEATURES.iter().find(|f| f.name == "Mare Crisium").unwrap();
+    let (east, _) = selenography::feature_disk_pos(crisium, 2448724.5).unwrap();
+    assert!(east < -0.7);
+
+    // the far side, and points off the disk
+    assert!(selenography::disk_pos_frm_selenographic(3.0, 0.0, 0.0, 0.0, 0.0).is_none());
+    assert!(selenography::selenographic_frm_disk_pos(0.8, 0.7, 0.0, 0.0, 0.0).is_none());
+
+}
+
+#[test]
+fn features_near() {
+
+    let tycho = FEATURES.iter().find(|f| f.name == "Tycho").unwrap();
+
+    let near = selenography::features_near(tycho.long, tycho.lat, 0.05);
+    assert_eq!(near[0].0.name, "Tycho");
+    assert_eq!(near[0].1, 0.0);
+    assert!(near.iter().all(|&(f, _)| f.name != "Mare Crisium"));
+
+}
+
+#[test]
+fn sun_selenographic_pos() {
+
+    let (l0, b0, c0) = selenography::sun_selenographic_pos(2448724.5);
+
+    assert_eq!(util::round_upto_digits(l0.to_degrees(), 2), 67.89);
+    assert_eq!(util::round_upto_digits(b0.to_degrees(), 2), 1.46);
+    assert_eq!(util::round_upto_digits(c0.to_degrees(), 2), 22.11);
+
+}
+
+#[test]
+fn sunrise_sunset() {
+
+    let copernicus = FEATURES.iter().find(|f| f.name == "Copernicus").unwrap();
+    let JD = 2448724.5;
+
+    // the morning terminator, at a longitude of -22.11 degrees, has
+    // just passed Copernicus
+    let alt = selenography::sun_alt_over_feature(copernicus, JD);
+    assert!(alt > 0.0 && alt < 0.05);
+
+    let (rise, set) = selenography::sunrise_sunset(copernicus.long, copernicus.lat, JD);
+    let (rise, set) = (rise.unwrap(), set.unwrap());
+    assert!(set > JD && set < JD + 15.0);
+    assert!(rise > set && rise < JD + 29.6);
+
+    for &t in [rise, set].iter() {
+        let alt = selenography::sun_alt_over_feature(copernicus, t);
+        assert!(alt.abs() < 1e-6);
+    }
+    assert!(selenography::sun_alt_over_feature(copernicus, rise + 0.5) > 0.0);
+    assert!(selenography::sun_alt_over_feature(copernicus, set + 0.5) < 0.0);
+
+}
@@ -0,0 +1,52 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![cfg(feature = "serde")]
+#![allow(non_snake_case)]
+
+extern crate astro;
+extern crate serde_json;
+
+use astro::*;
+
+#[test]
+fn round_trip() {
+
+    let date = time::Date {
+        year: 1987,
+        month: time::Month::Apr,
+        decimal_day: 10.5,
+        cal_type: time::CalType::Gregorian
+    };
+    let json = serde_json::to_string(&date).unwrap();
+    let back: time::Date = serde_json::from_str(&json).unwrap();
+    assert_eq!(time::julian_day(&back), time::julian_day(&date));
+
+    let point = coords::EqPoint { asc: 1.5, dec: -0.25 };
+    let back: coords::EqPoint = serde_json::from_str(&serde_json::to_string(&point).unwrap()).unwrap();
+    assert_eq!((back.asc, back.dec), (point.asc, point.dec));
+
+    let elements = orbit::Elements::frm_perih_time(0.33, 0.85, 0.2, 5.8, 3.2, 2448193.0);
+    let back: orbit::Elements = serde_json::from_str(&serde_json::to_string(&elements).unwrap()).unwrap();
+    assert_eq!(back, elements);
+
+}
@@ -0,0 +1,85 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+use astro::*;
+
+// Seconds of a Julian (Ephemeris) day, from 0h UT
+fn secs_UT(JD: f64, delta_t: f64) -> f64 {
+
+    ((JD - delta_t/86400.0 + 0.5).fract() * 86400.0).round()
+
+}
+
+#[test]
+fn geocent_circumstances() {
+
+    // the transit of Venus of 2012 June 5-6, with contacts I to IV at
+    // 22:09:38, 22:27:34, 04:31:39 and 04:49:35 UT
+    let c = planet::solar_transit::geocent_circumstances(&planet::Planet::Venus, 2456084.5).unwrap();
+    let delta_t = 66.9;
+    assert!((secs_UT(c.first.JD, delta_t) - 79778.0).abs() <= 2.0);
+    assert!((secs_UT(c.second.unwrap().JD, delta_t) - 80854.0).abs() <= 2.0);
+    assert!((secs_UT(c.third.unwrap().JD, delta_t) - 16299.0).abs() <= 2.0);
+    assert!((secs_UT(c.fourth.JD, delta_t) - 17375.0).abs() <= 2.0);
+    assert_eq!((c.min_sepr.to_degrees()*3600.0).round(), 554.0);
+    assert_eq!(c.first.pos_angl.to_degrees().round(), 41.0);
+
+    // the transit of Mercury of 2019 Nov 11, with it's greatest transit
+    // at 15:19:48 UT
+    let c = planet::solar_transit::geocent_circumstances(&planet::Planet::Mercury, 2458799.0).unwrap();
+    assert_eq!(secs_UT(c.greatest.JD, 69.2), 55188.0);
+    assert_eq!((c.min_sepr.to_degrees()*3600.0).round(), 76.0);
+
+    assert_eq!(
+        planet::solar_transit::geocent_circumstances(&planet::Planet::Mars, 2458799.0).err(),
+        Some(Error::InvalidBody)
+    );
+    assert_eq!(
+        planet::solar_transit::geocent_circumstances(&planet::Planet::Venus, 2458799.0).err(),
+        Some(Error::NoSolution)
+    );
+
+}
+
+#[test]
+fn topocent_circumstances() {
+
+    // the transit of Venus of 2012, seen from Honolulu, where the
+    // parallax shifts the contacts by several minutes
+    let honolulu = coords::GeographPoint {
+        long: 157.86_f64.to_radians(),
+        lat: 21.31_f64.to_radians()
+    };
+    let geocent = planet::solar_transit::geocent_circumstances(&planet::Planet::Venus, 2456084.5).unwrap();
+    let topocent = planet::solar_transit::topocent_circumstances (
+        &planet::Planet::Venus, 2456084.5, &honolulu, 0.0, 66.9
+    ).unwrap();
+
+    for &(g, t) in &[(geocent.first.JD, topocent.first.JD), (geocent.fourth.JD, topocent.fourth.JD)] {
+        let shift = (t - g).abs() * 1440.0;
+        assert!(shift > 0.5 && shift < 8.0);
+    }
+
+}
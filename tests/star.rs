@@ -20,27 +20,301 @@ OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
 THE SOFTWARE.
 */
 
+#![allow(non_snake_case)]
+
 extern crate astro;
 
 use astro::*;
 
 #[test]
-fn eq_coords_frm_motion() {
+fn eq_point_frm_motion() {
 
-    let (asc, dec) = star::eq_coords_frm_motion(
-        101.286962_f64.to_radians(),
-       -16.716108_f64.to_radians(),
+    let point0 = coords::EqPoint {
+        asc: 101.286962_f64.to_radians(),
+        dec: -16.716108_f64.to_radians()
+    };
+    let coords::EqPoint { asc, dec } = star::eq_point_frm_motion(
+        &point0,
         2.64,
        -0.000007773,
-        (-0.03847 / 3600.0 as f64).to_radians(),
-        (-1.20530 / 3600.0 as f64).to_radians(),
+        (-0.03847 / 3600.0_f64).to_radians(),
+        (-1.20530 / 3600.0_f64).to_radians(),
        -1000.0
     );
 
-    let (h1, m1, s1) = angle::hms_frm_deg(asc.to_degrees());
+    let angle::Hms { hour: h1, min: m1, sec: s1 } = angle::Rad(asc).into();
     assert_eq!((h1, m1, util::round_upto_digits(s1, 2)), (6, 45, 47.16));
 
-    let (d2, m2, s2) = angle::dms_frm_deg(dec.to_degrees());
+    let angle::Dms { deg: d2, min: m2, sec: s2 } = angle::Rad(dec).into();
     assert_eq!((d2, m2, util::round_upto_digits(s2, 1)), (-16, -22, -56.0));
 
 }
+
+#[test]
+fn proper_motion_frm_eq_coords() {
+
+    let (asc1, dec1) = (41.054063_f64.to_radians(), 49.227750_f64.to_radians());
+    let (pm_asc, pm_dec) = (
+        (0.0001 * 15.0_f64).to_radians() / 3600.0 * 100.0,
+        (-0.02_f64).to_radians() / 3600.0 * 100.0
+    );
+
+    let t = 50.0;
+    let JD1 = 2451545.0;
+    let JD2 = JD1 + t * 365.25;
+
+    // moved along, and referred to the equinox of the second epoch
+    let (asc2, dec2) = precess::precess_eq_coords(
+        asc1 + pm_asc * t, dec1 + pm_dec * t, JD1, JD2
+    );
+
+    let (a, d) = star::proper_motion_frm_eq_coords(
+        asc1, dec1, JD1, JD1, asc2, dec2, JD2, JD2
+    );
+    assert_eq!(util::round_upto_digits(a / pm_asc, 6), 1.0);
+    assert_eq!(util::round_upto_digits(d / pm_dec, 6), 1.0);
+
+}
+
+#[test]
+fn fk5_frm_fk4() {
+
+    let fk4 = star::MeanPlace {
+        asc:         1.234,
+        dec:        -0.123,
+        pmotion_asc: -1e-5,
+        pmotion_dec: 8e-6,
+        parallax:    (0.29 / 3600.0_f64).to_radians(),
+        rad_vel:     -35.0
+    };
+
+    let fk5 = star::fk5_frm_fk4(&fk4);
+    assert_eq!(util::round_upto_digits(fk5.asc, 6), 1.244118);
+    assert_eq!(util::round_upto_digits(fk5.dec, 6), -0.121016);
+
+    let back = star::fk4_frm_fk5(&fk5);
+    assert_eq!(util::round_upto_digits(back.asc, 9), fk4.asc);
+    assert_eq!(util::round_upto_digits(back.dec, 9), fk4.dec);
+    assert_eq!(util::round_upto_digits(back.pmotion_asc / fk4.pmotion_asc, 6), 1.0);
+    assert_eq!(util::round_upto_digits(back.pmotion_dec / fk4.pmotion_dec, 6), 1.0);
+    assert_eq!(util::round_upto_digits(back.rad_vel, 4), fk4.rad_vel);
+
+}
+
+#[test]
+fn space_vel() {
+
+    let mut place = star::MeanPlace {
+        asc:         266.405_f64.to_radians(),
+        dec:        -28.936_f64.to_radians(),
+        pmotion_asc: 0.0,
+        pmotion_dec: 0.0,
+        parallax:    (0.1 / 3600.0_f64).to_radians(),
+        rad_vel:     10.0
+    };
+
+    // receding towards the Galactic center
+    let (U, V, W) = star::space_vel(&place, None);
+    assert_eq!(
+        (util::round_upto_digits(U, 3), util::round_upto_digits(V, 3), util::round_upto_digits(W, 3)),
+        (10.0, 0.0, 0.0)
+    );
+
+    // moving 1 AU per year in declination, at the north Galactic pole
+    place.asc = 192.85948_f64.to_radians();
+    place.dec = 27.12825_f64.to_radians();
+    place.pmotion_dec = place.parallax;
+    place.rad_vel = 0.0;
+    let (U, V, W) = star::space_vel(&place, Some(star::SOLAR_MOTION));
+    assert_eq!(util::round_upto_digits((U - 11.1).hypot(V - 12.24), 3), 4.74);
+    assert_eq!(util::round_upto_digits(W, 3), 7.25);
+
+}
+
+#[test]
+fn closest_approach() {
+
+    // Gliese 710, from Gaia DR2 at the epoch J2015.5
+    let mas = |x: f64| (x / 3600000.0).to_radians();
+    let dec = -1.9385_f64.to_radians();
+    let place = star::MeanPlace {
+        asc:         274.9581_f64.to_radians(),
+        dec,
+        pmotion_asc: mas(-0.414) / dec.cos(),
+        pmotion_dec: mas(-0.108),
+        parallax:    mas(52.35),
+        rad_vel:    -14.53
+    };
+
+    let approach = star::closest_approach(&place, 2457206.375);
+    let years = (approach.JD - 2457206.375) / 365.25;
+
+    assert_eq!(util::round_upto_digits(years / 1e6, 2), 1.29);
+    assert_eq!(util::round_upto_digits(approach.dist, 3), 0.051);
+
+    // nearly perpendicular to the line of sight of today
+    let sepr = angle::anglr_sepr(place.asc, place.dec, approach.asc, approach.dec);
+    assert_eq!(util::round_upto_digits(sepr.to_degrees(), 0), 90.0);
+
+}
+
+#[test]
+fn moving_cluster() {
+
+    let k = 4.740470446;
+    let (conv_asc, conv_dec) = (97.0_f64.to_radians(), 7.0_f64.to_radians());
+    let vel = 46.0;
+    let parallax = (0.0213 / 3600.0_f64).to_radians();
+
+    let c = [
+        conv_dec.cos() * conv_asc.cos(), conv_dec.cos() * conv_asc.sin(), conv_dec.sin()
+    ];
+
+    // members of a Hyades-like cluster, all at the same distance
+    let members: Vec<star::MeanPlace> = [(62.0, 12.0), (66.5, 15.9), (70.0, 19.0), (67.0, 22.0), (64.0, 17.0)]
+        .iter()
+        .map(|&(asc, dec): &(f64, f64)| {
+            let (asc, dec) = (asc.to_radians(), dec.to_radians());
+            let (sa, ca) = asc.sin_cos();
+            let (sd, cd) = dec.sin_cos();
+
+            let v_asc = vel * (-sa*c[0] + ca*c[1]);
+            let v_dec = vel * (-sd*ca*c[0] - sd*sa*c[1] + cd*c[2]);
+
+            star::MeanPlace {
+                asc,
+                dec,
+                pmotion_asc: v_asc * parallax / (k * cd),
+                pmotion_dec: v_dec * parallax / k,
+                parallax:    0.0,
+                rad_vel:     vel * (cd*ca*c[0] + cd*sa*c[1] + sd*c[2])
+            }
+        })
+        .collect();
+
+    let coords::EqPoint { asc: a, dec: d } = star::convergent_eq_point(&members);
+    assert_eq!(util::round_upto_digits(a.to_degrees(), 6), 97.0);
+    assert_eq!(util::round_upto_digits(d.to_degrees(), 6), 7.0);
+
+    let v = star::moving_cluster_vel(&members, a, d);
+    assert_eq!(util::round_upto_digits(v, 6), vel);
+
+    for place in members.iter() {
+        let p = star::moving_cluster_parallax(place, a, d, v);
+        assert_eq!(util::round_upto_digits(p / parallax, 6), 1.0);
+    }
+
+}
+
+#[test]
+fn apprnt_eq_point() {
+
+    // Theta Persei, from the example in chapter 23 of Meeus
+    let place = star::MeanPlace {
+        asc:         angle::deg_frm_hms(2, 44, 11.986).to_radians(),
+        dec:         angle::deg_frm_dms(49, 13, 42.48).to_radians(),
+        pmotion_asc: angle::deg_frm_hms(0, 0, 0.03425).to_radians(),
+        pmotion_dec: angle::deg_frm_dms(0, 0, -0.0895).to_radians(),
+        parallax:    0.0,
+        rad_vel:     0.0
+    };
+
+    let coords::EqPoint { asc, dec } = star::apprnt_eq_point(&place, 2462088.69, false, false);
+
+    let angle::Hms { hour: h, min: m, sec: s } = angle::Rad(asc).into();
+    assert_eq!((h, m, util::round_upto_digits(s, 2)), (2, 46, 14.39));
+
+    let angle::Dms { deg: d, min: m, sec: s } = angle::Rad(dec).into();
+    assert_eq!((d, m, util::round_upto_digits(s, 1)), (49, 21, 7.4));
+
+    // the annual parallax and the deflection of light move the star
+    // by no more than its parallax, and a few milliarcseconds
+    let mut near = place;
+    near.parallax = angle::deg_frm_dms(0, 0, 0.75).to_radians();
+    let coords::EqPoint { asc: asc_p, dec: dec_p } = star::apprnt_eq_point(&near, 2462088.69, true, false);
+    let coords::EqPoint { asc: asc_d, dec: dec_d } = star::apprnt_eq_point(&place, 2462088.69, false, true);
+
+    let arcsec = |a1: f64, d1: f64| ((a1 - asc)*dec.cos()).hypot(d1 - dec).to_degrees() * 3600.0;
+    assert!(arcsec(asc_p, dec_p) > 0.1 && arcsec(asc_p, dec_p) < 0.75);
+    assert!(arcsec(asc_d, dec_d) > 0.0005 && arcsec(asc_d, dec_d) < 0.01);
+
+}
+
+#[test]
+fn propagate_space_motion() {
+
+    // Barnard's Star, from Hipparcos at the epoch J1991.25
+    let mas = |x: f64| (x / 3600000.0).to_radians();
+    let dec = 4.66828815_f64.to_radians();
+    let place = star::MeanPlace {
+        asc:         269.45402305_f64.to_radians(),
+        dec,
+        pmotion_asc: mas(-798.58) / dec.cos(),
+        pmotion_dec: mas(10328.12),
+        parallax:    mas(548.31),
+        rad_vel:    -110.6
+    };
+
+    let total_pmotion = |p: &star::MeanPlace| {
+        (p.pmotion_asc * p.dec.cos()).hypot(p.pmotion_dec).to_degrees() * 3600.0
+    };
+
+    // the proper motion grows by 2*mu*v_r*parallax a year, about
+    // 1.29 milliarcseconds over the next century
+    let later = star::propagate_space_motion(&place, 100.0);
+    let growth = (total_pmotion(&later) - total_pmotion(&place)) / 100.0;
+    assert_eq!(util::round_upto_digits(growth * 1000.0, 2), 1.29);
+    assert!(later.parallax > place.parallax);
+
+    // the perspective acceleration over a century, compared with the
+    // linear motion in right ascension and declination, is a few
+    // arcseconds
+    let (asc, dec) = (
+        place.asc + place.pmotion_asc*100.0,
+        place.dec + place.pmotion_dec*100.0
+    );
+    let sepr = angle::anglr_sepr(asc, dec, later.asc, later.dec).to_degrees() * 3600.0;
+    assert!(sepr > 5.0 && sepr < 8.0);
+
+    // the motion reverses exactly
+    let back = star::propagate_space_motion(&later, -100.0);
+    assert!((back.asc - place.asc).abs() < 1e-12);
+    assert!((back.dec - place.dec).abs() < 1e-12);
+    assert!((back.pmotion_dec - place.pmotion_dec).abs() < 1e-15);
+    assert!((back.rad_vel - place.rad_vel).abs() < 1e-9);
+
+    // the closest approach to the Sun, about 11,800 years from now
+    let approach = star::closest_approach(&place, 2448349.0625);
+    let years = (approach.JD - 2448349.0625) / 365.25;
+    let then = star::propagate_space_motion(&place, years);
+    let dist = (1.0_f64 / 3600.0).to_radians() / then.parallax;
+    assert_eq!(util::round_upto_digits(dist, 4), util::round_upto_digits(approach.dist, 4));
+    assert!(then.rad_vel.abs() < 0.1);
+
+}
+
+#[test]
+fn magnitudes() {
+
+    // Meeus's examples 56.a, 56.b and 56.c
+    assert_eq!(util::round_upto_digits(star::combined_mag(1.96, 2.89), 2), 1.58);
+    assert_eq!(util::round_upto_digits(star::combined_mag_of_many(&[4.73, 5.22, 5.60]), 2), 3.93);
+    assert_eq!(util::round_upto_digits(star::brightness_ratio(0.14, 2.12), 2), 6.19);
+    assert_eq!(util::round_upto_digits(star::mag_diff(500.0), 2), 6.75);
+
+    let d = 42.0;
+    let dm = star::dist_modulus(d);
+    assert_eq!(util::round_upto_digits(star::dist_frm_dist_modulus(dm), 9), d);
+    assert_eq!(star::dist_modulus(10.0), 0.0);
+    let abs_mag = star::abs_mag_frm_dist(d, 6.5);
+    assert_eq!(util::round_upto_digits(star::apprnt_mag_frm_abs_mag(abs_mag, d), 9), 6.5);
+    assert_eq!(
+        util::round_upto_digits(star::abs_mag_frm_parallax((1.0/d/3600.0).to_radians(), 6.5), 9),
+        util::round_upto_digits(abs_mag, 9)
+    );
+
+    // a disk of a square arcsecond spreads its light over that area
+    let axis = (2.0 / std::f64::consts::PI.sqrt() / 3600.0).to_radians();
+    assert_eq!(util::round_upto_digits(star::surface_brightness(12.0, axis, axis), 9), 12.0);
+
+}
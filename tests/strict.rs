@@ -0,0 +1,71 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+
+#[test]
+fn sin_cos() {
+
+    let mut x = -1000.0;
+    while x < 1000.0 {
+        let (s, c) = strict::sin_cos(x);
+        assert!((s - x.sin()).abs() < 1e-15);
+        assert!((c - x.cos()).abs() < 1e-15);
+        x += 0.0137;
+    }
+
+    assert_eq!(strict::sin(0.0), 0.0);
+    assert_eq!(strict::cos(0.0), 1.0);
+    assert!((strict::sin(1e7) - 1e7_f64.sin()).abs() < 1e-8);
+
+    // beyond the Cody-Waite reduction, in every quadrant
+    for i in 0..8 {
+        let x = 1e6 + (i as f64)*std::f64::consts::FRAC_PI_2;
+        for &x in [x, -x, x*1e3].iter() {
+            let (s, c) = strict::sin_cos(x);
+            assert!((s - x.sin()).abs() < 1e-12, "sin({})", x);
+            assert!((c - x.cos()).abs() < 1e-12, "cos({})", x);
+        }
+    }
+    assert!(strict::cos(f64::INFINITY).is_nan());
+
+}
+
+#[cfg(feature = "strict")]
+#[test]
+fn reproducible_results() {
+
+    // the bits of the results on every platform
+    let (L, B, R) = planet::heliocent_coords(&planet::Planet::Mars, 2448976.5);
+    assert_eq!(
+        (L.to_bits(), B.to_bits(), R.to_bits()),
+        (4610437359640539248, 4582703180975105430, 4609843475081518153)
+    );
+
+    let E = orbit::elliptic::ecc_anom_newton(5_f64.to_radians(), 0.9, 1e-15);
+    assert_eq!(E.to_bits(), 4603417142778560935);
+
+}
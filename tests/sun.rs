@@ -44,20 +44,20 @@ fn ephemeris() {
 }
 
 #[test]
-fn ecl_coords_to_FK5() {
+fn ecl_point_to_FK5() {
 
-    let (FK5_long, FK5_lat) = sun::ecl_coords_to_FK5(
-        2448908.5,
-        199.907372_f64.to_radians(),
-        angle::deg_frm_dms(0, 0, 0.644).to_radians()
-    );
+    let point = coords::EclPoint {
+        long: 199.907372_f64.to_radians(),
+        lat: angle::deg_frm_dms(0, 0, 0.644).to_radians()
+    };
+    let coords::EclPoint { long: FK5_long, lat: FK5_lat } = sun::ecl_point_to_FK5(2448908.5, &point);
 
     assert_eq!(
         util::round_upto_digits(FK5_long.to_degrees(), 6),
         199.907347
     );
 
-    let (d, m, s) = angle::dms_frm_deg(FK5_lat.to_degrees());
+    let angle::Dms { deg: d, min: m, sec: s } = angle::Rad(FK5_lat).into();
     assert_eq!(
         (d, m, util::round_upto_digits(s, 2)),
         (0, 0, 0.62)
@@ -94,3 +94,117 @@ fn approx_synd_rot() {
     );
 
 }
+
+#[test]
+fn barycent_rect_state() {
+
+    // DE405 gives about (-0.007137, -0.002796, 0.000207) AU, referred
+    // to the ecliptic of J2000.0
+    let ((x, y, z), (vx, vy, vz)) = sun::barycent_rect_state(2451545.0);
+
+    assert_eq!(util::round_upto_digits(x, 5), -0.00714);
+    assert_eq!(util::round_upto_digits(y, 5), -0.0028);
+    assert_eq!(util::round_upto_digits(z, 5), 0.00021);
+
+    assert_eq!(util::round_upto_digits(vx * 1e6, 1), 5.4);
+    assert_eq!(util::round_upto_digits(vy * 1e6, 1), -7.4);
+    assert_eq!(util::round_upto_digits(vz * 1e6, 1), -0.1);
+
+}
+
+#[test]
+fn equinox_solstice() {
+
+    // Meeus's example 27.a, the June solstice of 1962, at
+    // 21h 24m 42s TD
+    let JD = sun::equinox_solstice(1962, &sun::Event::JuneSolstice);
+    assert_eq!(util::round_upto_digits(JD, 5), 2437837.39215);
+
+    // the March equinox of 2024, at 3h 6m UTC
+    let JD = sun::equinox_solstice(2024, &sun::Event::MarchEquinox);
+    let (y, m, d) = time::date_frm_julian_day(JD - 69.2/86400.0).unwrap();
+    assert_eq!((y, m, d.floor()), (2024, 3, 20.0));
+    assert_eq!((d.fract() * 1440.0).floor(), 186.0);
+
+    // the events follow each other in order, before 1000 too
+    let events = [
+        sun::Event::MarchEquinox, sun::Event::JuneSolstice,
+        sun::Event::SeptEquinox, sun::Event::DecSolstice
+    ];
+    let times: Vec<f64> = events.iter().map(|event| sun::equinox_solstice(500, event)).collect();
+    for i in 1..4 {
+        let gap = times[i] - times[i - 1];
+        assert!(gap > 88.0 && gap < 95.0);
+    }
+
+}
+
+#[test]
+fn twilight() {
+
+    let boston = coords::GeographPoint { long: 71.0833_f64.to_radians(), lat: 42.3333_f64.to_radians() };
+    let date = time::Date { year: 1988, month: time::Month::Mar, decimal_day: 20.5, cal_type: time::CalType::Gregorian };
+
+    let civil = sun::twilight(&boston, &date, &sun::TwilightKind::Civil);
+    let nautical = sun::twilight(&boston, &date, &sun::TwilightKind::Nautical);
+    let astro = sun::twilight(&boston, &date, &sun::TwilightKind::Astronomical);
+
+    assert_eq!(util::round_upto_digits(civil.begin.unwrap() - 2447240.5, 2), 0.43);
+    assert_eq!(util::round_upto_digits(civil.end.unwrap() - 2447240.5, 2), 0.98);
+    assert!(astro.begin < nautical.begin && nautical.begin < civil.begin);
+    assert!(astro.darkness < nautical.darkness && nautical.darkness < civil.darkness);
+
+    let tromso = coords::GeographPoint { long: -18.96_f64.to_radians(), lat: 69.65_f64.to_radians() };
+    let date = time::Date { year: 2024, month: time::Month::June, decimal_day: 21.0, cal_type: time::CalType::Gregorian };
+    let summer = sun::twilight(&tromso, &date, &sun::TwilightKind::Civil);
+    assert_eq!((summer.begin, summer.end, summer.darkness), (None, None, 0.0));
+
+}
+
+#[test]
+fn equation_of_time() {
+
+    // from the Meeus book, 13m42.7s on 1992 October 13
+    let E = sun::equation_of_time(2448908.5);
+    assert_eq!(util::round_upto_digits(E.to_degrees(), 3), 3.427);
+    assert_eq!(util::round_upto_digits(E.to_degrees() * 4.0, 2), 13.71);
+
+    let (E_analemma, dec) = sun::analemma_point(2448908.5);
+    assert_eq!(E_analemma, E);
+    assert_eq!(util::round_upto_digits(dec.to_degrees(), 2), -7.78);
+
+    // the Sun is behind its mean position in February
+    assert!(sun::equation_of_time(2460354.5).to_degrees() * 4.0 < -14.0);
+
+}
+
+#[test]
+fn heliograph_coords() {
+
+    // the example of the Meeus book, 1992 October 13
+    let JD = 2448908.50068;
+    let (P, B0, L0) = sun::physical_ephemeris(JD);
+    assert_eq!(
+        (
+            util::round_upto_digits(P.to_degrees(), 2),
+            util::round_upto_digits(B0.to_degrees(), 2),
+            util::round_upto_digits(L0.to_degrees(), 2)
+        ),
+        (26.27, 5.99, 238.63)
+    );
+
+    // rotation 1861 began on 1992 October 3
+    assert_eq!(sun::carrington_rot(JD), 1861);
+    assert_eq!(sun::carrington_rot(sun::synodic_rot(1861) - 0.01), 1860);
+
+    // the center of the disk, and a point towards the north pole
+    let (lat, long) = sun::heliograph_coords(JD, 0.0, 0.0).unwrap();
+    assert_eq!(util::round_upto_digits(lat.to_degrees(), 6), util::round_upto_digits(B0.to_degrees(), 6));
+    assert_eq!(util::round_upto_digits(long.to_degrees(), 6), util::round_upto_digits(L0.to_degrees(), 6));
+
+    let (lat, _) = sun::heliograph_coords(JD, 0.5 * P.sin(), 0.5 * P.cos()).unwrap();
+    assert_eq!(util::round_upto_digits(lat.to_degrees() - B0.to_degrees(), 3), 29.866);
+
+    assert!(sun::heliograph_coords(JD, 0.8, 0.8).is_none());
+
+}
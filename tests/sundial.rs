@@ -0,0 +1,115 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+use astro::sundial::Sundial;
+
+// Angle of an hour line from the noon line, seen from the center
+fn hour_line_angl(dial: &Sundial, hour: f64) -> f64 {
+
+    let (x0, y0) = dial.center().unwrap();
+    let (_, (x, y)) = dial.hour_line(hour).unwrap();
+
+    (x - x0).atan2((y - y0).abs())
+
+}
+
+#[test]
+fn horizontal() {
+
+    let lat = 40_f64.to_radians();
+    let dial = Sundial { lat, decl: 0.0, zenith_dist: 0.0, stylus_len: 1.0 };
+
+    // the polar stylus is inclined at the latitude, towards the north
+    let (x0, y0) = dial.center().unwrap();
+    assert_eq!(x0, 0.0);
+    assert_eq!(util::round_upto_digits(y0, 12), util::round_upto_digits(-1.0 / lat.tan(), 12));
+    let (_, angl) = dial.polar_stylus();
+    assert_eq!(util::round_upto_digits(angl, 12), util::round_upto_digits(lat, 12));
+
+    // tan(angle) = sin(lat) * tan(hour angle), afternoon to the east
+    for &hour in [9.0, 11.0, 13.0, 15.0].iter() {
+        let H: f64 = (hour - 12.0) * 15_f64.to_radians();
+        let expected = (lat.sin() * H.tan()).atan();
+        assert_eq!(util::round_upto_digits(hour_line_angl(&dial, hour), 12), util::round_upto_digits(expected, 12));
+    }
+
+    // there's no shadow at night
+    assert!(dial.hour_line(0.0).is_none());
+    assert!(dial.hour_lines().iter().all(|&(hour, _)| (5..=19).contains(&hour)));
+
+}
+
+#[test]
+fn vertical() {
+
+    let lat = 52_f64.to_radians();
+    let dial = Sundial { lat, decl: 0.0, zenith_dist: std::f64::consts::FRAC_PI_2, stylus_len: 1.0 };
+
+    // tan(angle) = cos(lat) * tan(hour angle), afternoon to the east,
+    // on the right of an observer facing the wall
+    for &hour in [9.0, 10.0, 14.0, 15.0].iter() {
+        let H: f64 = (hour - 12.0) * 15_f64.to_radians();
+        let expected = (lat.cos() * H.tan()).atan();
+        assert_eq!(util::round_upto_digits(hour_line_angl(&dial, hour), 12), util::round_upto_digits(expected, 12));
+    }
+
+    // a wall facing south is in its own shadow before 6 and after 18
+    assert!(dial.hour_line(5.0).is_none());
+    assert!(dial.hour_line(19.0).is_none());
+
+    // a wall facing east, parallel to the Earth's axis, has parallel
+    // hour lines and no center
+    let east = Sundial { decl: -std::f64::consts::FRAC_PI_2, ..dial };
+    assert!(east.center().is_none());
+    assert!(east.polar_stylus().0.is_infinite() || east.polar_stylus().0 > 1e9);
+
+}
+
+#[test]
+fn declining_inclined() {
+
+    let dial = Sundial {
+        lat: -35_f64.to_radians(),
+        decl: 70_f64.to_radians(),
+        zenith_dist: 50_f64.to_radians(),
+        stylus_len: 2.0
+    };
+    let (x0, y0) = dial.center().unwrap();
+
+    // the shadows of a day lie along the hour lines through the center
+    for (_, (w, s)) in dial.hour_lines() {
+        let cross = (w.0 - x0)*(s.1 - y0) - (w.1 - y0)*(s.0 - x0);
+        assert!(cross.abs() < 1e-9);
+    }
+
+    // the polar stylus ends at the tip of the straight stylus
+    let (len, angl) = dial.polar_stylus();
+    let foot = (x0*x0 + y0*y0).sqrt();
+    assert_eq!(util::round_upto_digits(len*len, 9), util::round_upto_digits(foot*foot + 4.0, 9));
+    assert_eq!(util::round_upto_digits(angl.sin() * len, 9), 2.0);
+
+}
@@ -0,0 +1,58 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+use astro::*;
+
+#[test]
+fn potential_and_acceleration() {
+
+    let geograph_point = coords::GeographPoint {
+        long : 71.0833_f64.to_radians(),
+        lat  : 42.3333_f64.to_radians()
+    };
+    let r = 6368.4e+3;
+
+    for i in 0..48 {
+        let JD = 2448724.5 + (i as f64)/48.0;
+
+        for body in [Body::Moon, Body::Sun].iter() {
+            let (l, d, s) = tide::potential(JD, &geograph_point, 0.0, body).unwrap();
+            let (up, north, east) = tide::acceleration(JD, &geograph_point, 0.0, body).unwrap();
+
+            // the vertical acceleration is the radial derivative of
+            // the potential, which varies as the square of the radius
+            assert!((up - 2.0*(l + d + s)/r).abs() < 1e-3 * up.abs().max(1e-7));
+
+            let max_up = if *body == Body::Moon { 1.2e-6 } else { 0.6e-6 };
+            assert!(up.abs() < max_up);
+            assert!((north*north + east*east).sqrt() < 0.8 * max_up);
+        }
+    }
+
+    // only the Moon and the Sun raise tides here
+    let jupiter = Body::Planet(planet::Planet::Jupiter);
+    assert_eq!(tide::potential(2448724.5, &geograph_point, 0.0, &jupiter), Err(Error::InvalidBody));
+
+}
@@ -22,7 +22,6 @@ THE SOFTWARE.
 
 #![allow(non_snake_case)]
 
-#[macro_use]
 extern crate astro;
 
 use astro::*;
@@ -30,12 +29,21 @@ use astro::*;
 #[test]
 fn sidreal_time() {
 
-    let (h1, m1, s1) = angle::hms_frm_deg(time::mn_sidr(2446895.5).to_degrees());
+    let angle::Hms { hour: h1, min: m1, sec: s1 } = angle::Rad(time::mn_sidr(2446895.5)).into();
     assert_eq!((h1, m1, util::round_upto_digits(s1, 4)), (13, 10, 46.3668));
 
-    let (h2, m2, s2) = angle::hms_frm_deg(apprnt_sidr!(2446895.5).to_degrees());
+    let angle::Hms { hour: h2, min: m2, sec: s2 } = angle::Rad(apprnt_sidr!(2446895.5)).into();
     assert_eq!((h2, m2, util::round_upto_digits(s2, 4)), (13, 10, 46.1351));
 
+    let angle::Hms { hour: h3, min: m3, sec: s3 } = angle::Rad(time::mn_sidr_IAU2006(2446895.5)).into();
+    assert_eq!((h3, m3, util::round_upto_digits(s3, 2)), (13, 10, 46.37));
+
+    // at Greenwich, and 4 hours west of it
+    let sidr = time::mn_sidr(2446895.5);
+    assert_eq!(time::local_sidr(sidr, 0.0), sidr);
+    let angle::Hms { hour: h4, min: m4, sec: _ } = angle::Rad(time::local_sidr(sidr, 60_f64.to_radians())).into();
+    assert_eq!((h4, m4), (9, 10));
+
 }
 
 #[test]
@@ -122,3 +130,270 @@ fn date_frm_julian_day() {
     }
 
 }
+
+#[test]
+fn tcg_frm_tt() {
+
+    // TCG - TT, and TCB - TDB, at J2000
+    let JD = 2451545.0;
+
+    let d = (time::tcg_frm_tt(JD) - JD) * 86400.0;
+    assert_eq!(util::round_upto_digits(d, 3), 0.506);
+    assert_eq!(util::round_upto_digits(time::tt_frm_tcg(time::tcg_frm_tt(JD)) - JD, 10), 0.0);
+
+    let d = (time::tcb_frm_tdb(JD) - JD) * 86400.0;
+    assert_eq!(util::round_upto_digits(d, 2), 11.25);
+    assert_eq!(util::round_upto_digits(time::tdb_frm_tcb(time::tcb_frm_tdb(JD)) - JD, 9), 0.0);
+
+}
+
+#[test]
+fn try_julian_day() {
+
+    let date = |year, month, decimal_day, cal_type| time::Date { year, month, decimal_day, cal_type };
+
+    let sputnik = date(1957, time::Month::Oct, 4.81, time::CalType::Gregorian);
+    assert_eq!(time::try_julian_day(&sputnik), Ok(time::julian_day(&sputnik)));
+
+    assert_eq!(time::try_julian_day(&date(1900, time::Month::Feb, 29.0, time::CalType::Gregorian)), Err(Error::InvalidDate));
+    assert!(time::try_julian_day(&date(1900, time::Month::Feb, 29.0, time::CalType::Julian)).is_ok());
+    assert_eq!(time::try_julian_day(&date(2024, time::Month::Apr, 0.5, time::CalType::Gregorian)), Err(Error::InvalidDate));
+    assert_eq!(time::try_julian_day(&date(1582, time::Month::Oct, 10.0, time::CalType::Gregorian)), Err(Error::InvalidDate));
+    assert!(time::try_julian_day(&date(1582, time::Month::Oct, 15.0, time::CalType::Gregorian)).is_ok());
+
+}
+
+#[test]
+fn calendar_round_trip() {
+
+    let months = [
+        time::Month::Jan, time::Month::Feb, time::Month::Mar, time::Month::Apr,
+        time::Month::May, time::Month::June, time::Month::July, time::Month::Aug,
+        time::Month::Sept, time::Month::Oct, time::Month::Nov, time::Month::Dec
+    ];
+
+    // every 997.3 days from before -4712 to after 3000, across the
+    // reform of the calendar
+    let mut JD = -20000.25;
+    while JD < 2900000.0 {
+        let (year, month, day) = time::date_frm_julian_day(JD).unwrap();
+        let date = time::Date {
+            year,
+            month: months[(month - 1) as usize],
+            decimal_day: day,
+            cal_type: if JD < 2299160.5 { time::CalType::Julian } else { time::CalType::Gregorian }
+        };
+        assert_eq!(util::round_upto_digits(time::julian_day(&date) - JD, 6), 0.0);
+        JD += 997.3;
+    }
+
+    // the day before the reform, and the first day of -4712
+    assert_eq!(time::date_frm_julian_day(2299159.5).unwrap(), (1582, 10, 4.0));
+    assert_eq!(time::date_frm_julian_day(-0.5).unwrap(), (-4712, 1, 1.0));
+    assert_eq!(time::date_frm_julian_day(-1.5).unwrap(), (-4713, 12, 31.0));
+
+}
+
+#[test]
+fn day_of_year() {
+
+    let date = |year, month, decimal_day| time::Date { year, month, decimal_day, cal_type: time::CalType::Gregorian };
+
+    assert_eq!(time::day_of_year(&date(1978, time::Month::Nov, 14.0)), 318);
+    assert_eq!(time::day_of_year(&date(1988, time::Month::Apr, 22.5)), 113);
+    assert_eq!(time::day_of_year(&date(2024, time::Month::Dec, 31.0)), 366);
+
+    // 1582 October 4 was a Thursday in the Julian calendar
+    match time::weekday_frm_date(&time::Date { year: 1582, month: time::Month::Oct, decimal_day: 4.0, cal_type: time::CalType::Julian }) {
+        time::Weekday::Thursday => {},
+        _ => panic!("time::weekday_frm_date failed")
+    }
+
+}
+
+#[test]
+fn easter() {
+
+    // from the Meeus book
+    for &(year, date) in [(1991, (3, 31)), (1992, (4, 19)), (1993, (4, 11)), (1954, (4, 18)),
+                          (2000, (4, 23)), (1818, (3, 22))].iter() {
+        assert_eq!(time::easter(year, &time::CalType::Gregorian), date);
+    }
+    for &(year, date) in [(179, (4, 12)), (711, (4, 12)), (1243, (4, 12))].iter() {
+        assert_eq!(time::easter(year, &time::CalType::Julian), date);
+    }
+
+}
+
+#[test]
+fn delta_t() {
+
+    assert_eq!(time::delta_t(200, 7).round(), 8636.0);
+    assert_eq!(time::delta_t(1650, 1).round(), 50.0);
+    assert_eq!(time::delta_t(1750, 1).round(), 13.0);
+    assert_eq!(time::delta_t(1850, 1).round(), 7.0);
+    assert_eq!(time::delta_t(1900, 1).round(), -3.0);
+    assert_eq!(time::delta_t(2000, 1).round(), 64.0);
+
+    let JD = 2451545.0;
+    let JDE = time::tt_frm_ut(JD);
+    assert_eq!(util::round_upto_digits((JDE - JD)*86400.0, 3), util::round_upto_digits(time::delta_t_frm_julian_day(JD), 3));
+    assert_eq!(util::round_upto_digits((time::ut_frm_tt(JDE) - JD)*86400.0, 6), 0.0);
+
+    assert_eq!(util::round_upto_digits(time::delta_t_frm_julian_day(2451544.5), 2), 63.86);
+
+}
+
+#[test]
+fn jewish_calendar() {
+
+    // from the Meeus book
+    assert_eq!(time::pesach(1990), (4, 10));
+    assert!(!time::is_jewish_leap_year(5751));
+    assert_eq!(time::jewish_year_days(5751), 354);
+
+    for &(year, date) in [(2024, (4, 23)), (2023, (4, 6)), (1583, (4, 7))].iter() {
+        assert_eq!(time::pesach(year), date);
+    }
+
+    // Rosh Hashanah 5785, and the year 5784, embolismic and deficient
+    let rosh_hashanah = time::JewishDate { year: 5785, month: time::JewishMonth::Tishri, day: 1 };
+    let JD = time::julian_day_frm_jewish(&rosh_hashanah).unwrap();
+    assert_eq!(time::date_frm_julian_day(JD).unwrap(), (2024, 10, 3.0));
+    assert!(time::is_jewish_leap_year(5784));
+    assert_eq!(time::jewish_year_days(5784), 383);
+
+    let adar_ii = time::JewishDate { year: 5784, month: time::JewishMonth::Adar, day: 14 };
+    let JD = time::julian_day_frm_jewish(&adar_ii).unwrap();
+    assert_eq!(time::date_frm_julian_day(JD).unwrap(), (2024, 3, 24.0));
+    assert_eq!(time::jewish_frm_julian_day(JD + 0.3), adar_ii);
+
+    let adar_i = time::JewishDate { year: 5785, month: time::JewishMonth::AdarI, day: 1 };
+    assert_eq!(time::julian_day_frm_jewish(&adar_i), Err(Error::InvalidDate));
+
+    for JD in (2440000..2470000).step_by(97) {
+        let JD = JD as f64 - 0.5;
+        assert_eq!(time::julian_day_frm_jewish(&time::jewish_frm_julian_day(JD)), Ok(JD));
+    }
+
+}
+
+#[test]
+fn islamic_calendar() {
+
+    // 1 Muharram 1421 and 1 Ramadan 1445
+    let new_year = time::IslamicDate { year: 1421, month: 1, day: 1 };
+    let JD = time::julian_day_frm_islamic(&new_year).unwrap();
+    assert_eq!(time::date_frm_julian_day(JD).unwrap(), (2000, 4, 6.0));
+    assert_eq!(time::islamic_frm_julian_day(JD), new_year);
+
+    let ramadan = time::IslamicDate { year: 1445, month: 9, day: 1 };
+    let JD = time::julian_day_frm_islamic(&ramadan).unwrap();
+    assert_eq!(time::date_frm_julian_day(JD).unwrap(), (2024, 3, 11.0));
+
+    // the epoch, 622 July 16 (Julian)
+    let epoch = time::IslamicDate { year: 1, month: 1, day: 1 };
+    assert_eq!(time::julian_day_frm_islamic(&epoch), Ok(1948439.5));
+
+    assert!(time::is_islamic_leap_year(1445));
+    assert!(!time::is_islamic_leap_year(1444));
+    let dhu_al_hijja_30 = time::IslamicDate { year: 1445, month: 12, day: 30 };
+    assert!(time::julian_day_frm_islamic(&dhu_al_hijja_30).is_ok());
+    let dhu_al_hijja_30 = time::IslamicDate { year: 1444, month: 12, day: 30 };
+    assert_eq!(time::julian_day_frm_islamic(&dhu_al_hijja_30), Err(Error::InvalidDate));
+
+    for JD in (1948440..2470000).step_by(997) {
+        let JD = JD as f64 - 0.5;
+        assert_eq!(time::julian_day_frm_islamic(&time::islamic_frm_julian_day(JD)), Ok(JD));
+    }
+
+}
+
+#[test]
+fn iso8601() {
+
+    assert_eq!(time::julian_day_frm_iso8601("2000-01-01T12:00:00Z"), Ok(2451545.0));
+    assert_eq!(time::julian_day_frm_iso8601("2000-01-01"), Ok(2451544.5));
+    assert_eq!(time::julian_day_frm_iso8601("2000-01-01 18:00"), Ok(2451545.25));
+    assert_eq!(time::julian_day_frm_iso8601("2000-01-01T13:30:00+01:30"), Ok(2451545.0));
+    assert_eq!(time::julian_day_frm_iso8601("2000-01-01T10:00-0200"), Ok(2451545.0));
+    assert_eq!(time::julian_day_frm_iso8601("2000-01-01T24:00"), Ok(2451545.5));
+    let JD = time::julian_day_frm_iso8601("1957-10-04T19:26:24.5").unwrap();
+    assert_eq!(util::round_upto_digits(JD, 8), util::round_upto_digits(2436116.31 + 0.5/86400.0, 8));
+
+    // the proleptic Gregorian calendar, before the reform
+    assert_eq!(time::julian_day_frm_iso8601("1582-10-04"), Ok(2299149.5));
+    assert_eq!(time::julian_day_frm_iso8601("-4713-11-24T12:00Z"), Ok(0.0));
+    assert_eq!(time::julian_day_frm_iso8601("+12000-01-01"), Ok(6103969.5));
+
+    for text in ["2000-1-01", "2000-01-01T12", "2000-01-01T12:00:00.", "2000/01/01", "12000-01-01", "2000-01-01Z"].iter() {
+        assert_eq!(time::julian_day_frm_iso8601(text), Err(Error::InvalidFormat));
+    }
+    for text in ["2023-02-29", "2000-13-01", "2000-01-01T24:01", "2000-01-01T12:60"].iter() {
+        assert_eq!(time::julian_day_frm_iso8601(text), Err(Error::InvalidDate));
+    }
+
+    assert_eq!(time::iso8601_frm_julian_day(2451545.0), "2000-01-01T12:00:00.000Z");
+    assert_eq!(time::iso8601_frm_julian_day(2436116.31), "1957-10-04T19:26:24.000Z");
+    assert_eq!(time::iso8601_frm_julian_day(2451545.5 - 1e-10), "2000-01-02T00:00:00.000Z");
+    assert_eq!(time::iso8601_frm_julian_day(0.0), "-4713-11-24T12:00:00.000Z");
+    assert_eq!(time::iso8601_frm_julian_day(6103969.5), "+12000-01-01T00:00:00.000Z");
+
+    let JD = 2460000.123456;
+    let round_trip = time::julian_day_frm_iso8601(&time::iso8601_frm_julian_day(JD)).unwrap();
+    assert!((round_trip - JD).abs() * 86400.0 < 0.0005);
+
+}
+
+#[test]
+fn time_scales() {
+
+    let new_year_2017 = time::julian_day_frm_iso8601("2017-01-01").unwrap();
+    assert_eq!(time::tai_minus_utc(new_year_2017), 37.0);
+    assert_eq!(time::tai_minus_utc(new_year_2017 - 1.0/86400.0), 36.0);
+    assert_eq!(time::tai_minus_utc(time::julian_day_frm_iso8601("1972-06-30T12:00").unwrap()), 10.0);
+    let dt_1960 = time::tai_minus_utc(time::julian_day_frm_iso8601("1960-01-01").unwrap()) + 32.184;
+    assert_eq!(util::round_upto_digits(dt_1960, 0), 33.0);
+
+    let JD = time::julian_day_frm_iso8601("2020-03-20T03:50").unwrap();
+    let utc = time::Instant::new(JD, time::TimeScale::UTC);
+    let tt = utc.to(time::TimeScale::TT);
+    assert_eq!(tt.scale, time::TimeScale::TT);
+    assert_eq!(util::round_upto_digits((tt.JD - JD)*86400.0, 3), 69.184);
+    let tai = utc.to(time::TimeScale::TAI);
+    assert_eq!(util::round_upto_digits((tai.JD - JD)*86400.0, 3), 37.0);
+
+    let tdb = utc.to(time::TimeScale::TDB);
+    assert!((tdb.JD - tt.JD).abs()*86400.0 < 0.002);
+    assert!((time::tt_frm_tdb(tdb.JD) - tt.JD).abs()*86400.0 < 1e-6);
+
+    for scale in [time::TimeScale::UT1, time::TimeScale::TAI, time::TimeScale::TT, time::TimeScale::TDB].iter() {
+        let back = utc.to(*scale).to(time::TimeScale::UTC);
+        assert!((back.JD - JD).abs()*86400.0 < 1e-4);
+    }
+
+    // UT1 from ΔT, and then from a DUT1 hook
+    let ut1 = utc.to(time::TimeScale::UT1);
+    assert!((ut1.JD - time::ut_frm_tt(tt.JD)).abs()*86400.0 < 1e-4);
+
+    #[cfg(feature = "std")]
+    {
+        fn dut1(_: f64) -> f64 { -0.2 }
+        time::set_dut1(Some(dut1));
+        let ut1 = utc.to(time::TimeScale::UT1);
+        assert_eq!(util::round_upto_digits((ut1.JD - JD)*86400.0, 3), -0.2);
+        let back = ut1.to(time::TimeScale::UTC);
+        assert!((back.JD - JD).abs()*86400.0 < 1e-4);
+        time::set_dut1(None);
+
+        // a leap second beyond the built-in table
+        let JD_2040 = time::julian_day_frm_iso8601("2040-01-01").unwrap();
+        assert_eq!(time::tai_minus_utc(JD_2040), 37.0);
+        time::set_leap_seconds(Some(vec![(JD_2040 - 365.0, 38.0)]));
+        assert_eq!(time::tai_minus_utc(JD_2040), 38.0);
+        assert_eq!(time::tai_minus_utc(JD), 37.0);
+        time::set_leap_seconds(None);
+        assert_eq!(time::tai_minus_utc(JD_2040), 37.0);
+    }
+
+}
@@ -0,0 +1,53 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![cfg(feature = "trace")]
+#![allow(non_snake_case)]
+
+extern crate astro;
+
+use astro::*;
+use std::sync::Mutex;
+
+static EVENTS: Mutex<Vec<trace::Event>> = Mutex::new(Vec::new());
+
+fn record(event: &trace::Event) {
+    EVENTS.lock().unwrap().push(*event);
+}
+
+#[test]
+fn ecc_anom() {
+
+    trace::set_hook(Some(record));
+    let E = orbit::elliptic::ecc_anom(5_f64.to_radians(), 0.1, 1e-12);
+    trace::set_hook(None);
+
+    let events = EVENTS.lock().unwrap();
+    let event = events.iter()
+        .find(|e| e.routine == "orbit::elliptic::ecc_anom")
+        .unwrap();
+
+    assert_eq!(event.outcome, trace::Outcome::Converged);
+    assert!(event.iters > 1 && event.residual <= 1e-12);
+    assert_eq!(util::round_upto_digits(E.to_degrees(), 6), 5.554589);
+
+}
@@ -94,3 +94,111 @@ fn time() {
     assert_eq!((h_set, m_set), (2, 54));
 
 }
+
+#[test]
+fn time_at_altitude() {
+
+    // Meeus's example 15.a, Venus at Boston on 1988 March 20
+    let eq_point1 = coords::EqPoint { asc: 40.68021_f64.to_radians(), dec: 18.04761_f64.to_radians() };
+    let eq_point2 = coords::EqPoint { asc: 41.73129_f64.to_radians(), dec: 18.44092_f64.to_radians() };
+    let eq_point3 = coords::EqPoint { asc: 42.78204_f64.to_radians(), dec: 18.82742_f64.to_radians() };
+    let geograph_point = coords::GeographPoint { long: 71.0833_f64.to_radians(), lat: 42.3333_f64.to_radians() };
+    let Theta0 = 177.74208_f64.to_radians();
+    let deltaT = time::delta_t(1988, 3);
+
+    let rise = |altitude: &transit::Altitude| {
+        let event = transit::time_at_altitude (
+            &transit::TransitType::Rise, &transit::TransitBody::StarOrPlanet, altitude,
+            &geograph_point, &eq_point1, &eq_point2, &eq_point3, Theta0, deltaT, 0.0
+        );
+        assert_eq!(event.altitude, *altitude);
+        (event.hour as f64)*3600.0 + (event.min as f64)*60.0 + event.sec
+    };
+
+    let standard = rise(&transit::Altitude::Standard);
+    assert_eq!(standard, rise(&transit::Altitude::Custom(-0.5667_f64.to_radians())));
+
+    // the geometric rising of the center comes a few minutes later, and
+    // the upper limb rises before the center
+    let geometric = rise(&transit::Altitude::Center { refraction: 0.0 });
+    assert!(geometric - standard > 120.0 && geometric - standard < 300.0);
+    let limb = rise(&transit::Altitude::UpperLimb { semidiameter: 0.001, refraction: 0.0 });
+    assert!(limb < geometric);
+
+    let civil = rise(&transit::Altitude::CivilTwilight);
+    let nautical = rise(&transit::Altitude::NauticalTwilight);
+    assert!(nautical < civil && civil < standard);
+
+}
+
+#[test]
+fn day_length() {
+
+    // near the March equinox of 2000, the days are a few minutes longer
+    // than 12 hours because of refraction and the Sun's semidiameter
+    let JD = 2451624.0;
+    let (day, _) = transit::day_length(JD, 0.0, &transit::Altitude::Standard);
+    assert_eq!((day*1440.0).round(), 727.0);
+
+    // at 60° N, they lengthen by 5.5 minutes a day
+    let lat = 60_f64.to_radians();
+    let (day, rate) = transit::day_length(JD, lat, &transit::Altitude::Standard);
+    assert_eq!((day*1440.0).round(), 734.0);
+    assert_eq!(util::round_upto_digits(rate*1440.0, 1), 5.5);
+
+    // and reach 18h52m at the June solstice, beyond the Arctic circle
+    // the Sun doesn't set
+    let (day, rate) = transit::day_length(JD + 92.0, lat, &transit::Altitude::Standard);
+    assert_eq!((day*1440.0).round(), 1132.0);
+    assert!(rate.abs()*1440.0 < 0.2);
+    let (day, rate) = transit::day_length(JD + 92.0, 70_f64.to_radians(), &transit::Altitude::Standard);
+    assert_eq!((day, rate), (1.0, 0.0));
+
+    assert_eq!(transit::semidiurnal_arc(0.0, lat, 0.0), std::f64::consts::PI/2.0);
+
+}
+
+#[test]
+fn rise_set_transit() {
+
+    // Venus at Boston on 1988 March 20, from the Meeus book
+    let boston = coords::GeographPoint { long: 71.0833_f64.to_radians(), lat: 42.3333_f64.to_radians() };
+    let venus = Body::Planet(planet::Planet::Venus);
+    match transit::rise_set_transit(&venus, &boston, 2447240.5, &transit::Altitude::Standard).unwrap() {
+        transit::RiseSetTransit::RiseSet { rise, transit, set } => {
+            assert_eq!(util::round_upto_digits(rise - 2447240.5, 3), 0.518);
+            assert_eq!(util::round_upto_digits(transit - 2447240.5, 3), 0.820);
+            assert_eq!(util::round_upto_digits(set - 2447240.5, 3), 0.121);
+        },
+        _ => panic!("Venus must rise and set at Boston")
+    }
+
+    // the Sun at Tromsø on the solstices of 2024
+    let tromso = coords::GeographPoint { long: -18.96_f64.to_radians(), lat: 69.65_f64.to_radians() };
+    match transit::rise_set_transit(&Body::Sun, &tromso, 2460482.5, &transit::Altitude::Standard).unwrap() {
+        transit::RiseSetTransit::Circumpolar { .. } => (),
+        r => panic!("{:?}", r)
+    }
+    match transit::rise_set_transit(&Body::Sun, &tromso, 2460665.5, &transit::Altitude::Standard).unwrap() {
+        transit::RiseSetTransit::NeverRises { .. } => (),
+        r => panic!("{:?}", r)
+    }
+
+}
+
+#[test]
+fn try_rise_set_transit() {
+
+    let pole = coords::GeographPoint { long: 0.0, lat: 100_f64.to_radians() };
+    assert_eq!(
+        transit::try_rise_set_transit(&Body::Sun, &pole, 2460482.5, &transit::Altitude::Standard),
+        Err(Error::InvalidLatitude(100_f64.to_radians()))
+    );
+
+    let boston = coords::GeographPoint { long: 71.0833_f64.to_radians(), lat: 42.3333_f64.to_radians() };
+    assert_eq!(
+        transit::try_rise_set_transit(&Body::Planet(planet::Planet::Earth), &boston, 2447240.5, &transit::Altitude::Standard),
+        Err(Error::InvalidBody)
+    );
+
+}
@@ -0,0 +1,76 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+use astro::*;
+
+#[test]
+fn moons() {
+
+    use planet::uranus::moons::Moon;
+
+    // mean distances from Uranus in km, and the largest elongations
+    // possible when Uranus is nearest the Earth, in arcseconds
+    let data = [
+        (Moon::Miranda, 129900.0, 10.0),
+        (Moon::Ariel,   190900.0, 14.0),
+        (Moon::Umbriel, 266000.0, 19.5),
+        (Moon::Titania, 436300.0, 32.0),
+        (Moon::Oberon,  583500.0, 43.0),
+    ];
+
+    for &(ref moon, a, max_elong) in data.iter() {
+        for i in 0..20 {
+            let JD = 2451545.0 + (i as f64)*0.37;
+
+            let (x, y, z) = planet::uranus::moons::uranicent_rect_coords(JD, moon);
+            let r = (x*x + y*y + z*z).sqrt() * 149597870.7;
+            assert!((r - a).abs() / a < 0.01);
+
+            let (X, Y) = planet::uranus::moons::apprnt_offsets(JD, moon);
+            assert!((X*X + Y*Y).sqrt().to_degrees()*3600.0 < max_elong);
+        }
+    }
+
+}
+
+#[test]
+fn ephemeris() {
+
+    // Voyager 2 found the south pole facing the Sun in 1986, and the
+    // Sun crossed the equator of Uranus on 2007 December 7
+    let eph = planet::uranus::ephemeris(2446450.5);
+    assert_eq!(eph.Ds.to_degrees().round(), -82.0);
+    let eph = planet::uranus::ephemeris(2454442.5);
+    assert!(eph.Ds.to_degrees().abs() < 0.1);
+
+    let eph = planet::uranus::ephemeris(2460310.5);
+    assert_eq!(util::round_upto_digits(eph.d.to_degrees()*3600.0, 1), 3.7);
+    assert!(eph.De.to_degrees() > 60.0 && eph.De.to_degrees() < 61.0);
+
+    // the central meridian moves east with the retrograde rotation
+    let w = planet::uranus::ephemeris(2460310.6).w - eph.w;
+    assert_eq!(w.to_degrees().round(), 50.0);
+
+}
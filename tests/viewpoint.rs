@@ -0,0 +1,75 @@
+/*
+Copyright (c) 2015, 2016 Saurav Sachidanand
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in
+all copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+THE SOFTWARE.
+*/
+
+#![allow(non_snake_case)]
+
+extern crate astro;
+use astro::*;
+use astro::planet::viewpoint::{Viewpoint, Target};
+
+#[test]
+fn ecl_coords() {
+
+    // seen from the Earth, as the geocentric positions of this library
+    // precessed to J2000.0
+    let JD = 2448976.5;
+    let (point, dist) = planet::viewpoint::ecl_coords (
+        &Viewpoint::Planet(planet::Planet::Earth), &Target::Planet(planet::Planet::Venus), JD
+    );
+    let (geocent, geocent_dist) = planet::geocent_apprnt_ecl_coords(&planet::Planet::Venus, JD);
+    let (long, lat) = precess::precess_ecl_coords(geocent.long, geocent.lat, JD, 2451545.0);
+    assert!((point.long - long).abs().to_degrees()*3600.0 < 0.01);
+    assert!((point.lat - lat).abs().to_degrees()*3600.0 < 0.01);
+    assert_eq!(util::round_upto_digits(dist, 6), util::round_upto_digits(geocent_dist, 6));
+
+    // seen from the Sun, the Earth is at it's heliocentric position
+    let (point, dist) = planet::viewpoint::ecl_coords (
+        &Viewpoint::Heliocent(0.0, 0.0, 0.0), &Target::Planet(planet::Planet::Earth), JD
+    );
+    let (x, y, _) = planet::heliocent_ecl_rect_coords_J2000(&planet::Planet::Earth, JD - 0.0057755183*dist);
+    assert_eq!(util::round_upto_digits(point.long, 8), util::round_upto_digits(angle::limit_to_two_PI(y.atan2(x)), 8));
+
+    // at the opposition of Mars of 2003 Aug 28, the Earth is in inferior
+    // conjunction in the sky of Mars, 4.9° from the Sun
+    let mars = Viewpoint::Planet(planet::Planet::Mars);
+    let (sun, _) = planet::viewpoint::ecl_coords(&mars, &Target::Sun, 2452879.9);
+    let (earth, _) = planet::viewpoint::ecl_coords(&mars, &Target::Planet(planet::Planet::Earth), 2452879.9);
+    assert_eq!(util::round_upto_digits(sun.anglr_sepr(&earth).to_degrees(), 1), 4.9);
+
+}
+
+#[test]
+fn planetocent_eq_coords() {
+
+    // the Sun's declination seen from Mars reaches the obliquity of
+    // Mars, 25.19°, in a Martian year
+    let mut max_dec: f64 = 0.0;
+    for i in 0..138 {
+        let (sun, _) = planet::viewpoint::ecl_coords (
+            &Viewpoint::Planet(planet::Planet::Mars), &Target::Sun, 2451545.0 + 5.0*(i as f64)
+        );
+        let eq_point = planet::viewpoint::planetocent_eq_coords(planet::Planet::Mars, &sun);
+        max_dec = max_dec.max(eq_point.dec);
+    }
+    assert_eq!(util::round_upto_digits(max_dec.to_degrees(), 1), 25.2);
+
+}